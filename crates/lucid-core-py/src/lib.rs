@@ -0,0 +1,1066 @@
+//! Python bindings for lucid-core memory retrieval engine.
+//!
+//! Exposes spreading, temporal spreading, `PageRank` and recall scoring to
+//! Python so cognitive-modeling work can fit parameters against behavioral
+//! data with the same algorithms the engine runs. Float inputs accept
+//! `NumPy` arrays or anything `numpy.asarray` converts; outputs are `NumPy`
+//! arrays. Settings are small config classes whose attributes can be
+//! changed between fits.
+//!
+//! Built with maturin as the `lucid_core` Python module.
+
+// Memory indices will never exceed u32::MAX in practice
+#![allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+// PyO3 hands arguments over by value
+#![allow(clippy::needless_pass_by_value)]
+
+use numpy::{AllowTypeChange, PyArray1, PyArray2, PyArrayLike1, PyArrayLike2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use lucid_core::{
+	activation::{compute_base_level, nonlinear_activation_batch, retrieval_probability_batch},
+	graph::AssociationGraph as CoreGraph,
+	retrieval::{
+		retrieve as core_retrieve, score as core_score, RetrievalConfig as CoreRetrievalConfig,
+		RetrievalInput, ScoreConfig as CoreScoreConfig, ScoreInput, ScoreNormalization,
+		ScoreWeights,
+	},
+	spreading::{
+		compute_pagerank_graph, compute_weighted_pagerank_graph,
+		create_episode_links as core_create_episode_links, spread_activation_graph,
+		spread_temporal_activation as core_spread_temporal, Association, AssociationType,
+		SpreadingConfig as CoreSpreadingConfig, TemporalLink,
+		TemporalSpreadingConfig as CoreTemporalConfig,
+	},
+	DuplicatePolicy, GraphBuilder,
+};
+
+type Floats<'py> = PyArrayLike1<'py, f64, AllowTypeChange>;
+
+fn floats(array: &Floats<'_>) -> Vec<f64> {
+	array.as_array().to_vec()
+}
+
+fn parse_normalization(s: &str) -> Option<ScoreNormalization> {
+	match s.to_lowercase().as_str() {
+		"none" => Some(ScoreNormalization::None),
+		"minmax" | "min_max" => Some(ScoreNormalization::MinMax),
+		"zscore" | "z_score" => Some(ScoreNormalization::ZScore),
+		_ => None,
+	}
+}
+
+const fn normalization_name(normalization: ScoreNormalization) -> &'static str {
+	match normalization {
+		ScoreNormalization::None => "none",
+		ScoreNormalization::MinMax => "minmax",
+		ScoreNormalization::ZScore => "zscore",
+	}
+}
+
+/// Python spelling of a bool for `__repr__`.
+const fn py_bool(value: bool) -> &'static str {
+	if value {
+		"True"
+	} else {
+		"False"
+	}
+}
+
+/// Python spelling of an optional float for `__repr__`.
+fn py_float(value: Option<f64>) -> String {
+	value.map_or_else(|| "None".to_string(), |v| format!("{v:?}"))
+}
+
+fn to_i64(values: &[usize]) -> Vec<i64> {
+	values.iter().map(|&v| v as i64).collect()
+}
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Spreading settings; omitted arguments keep the core defaults.
+#[pyclass(module = "lucid_core", get_all, set_all)]
+#[derive(Clone)]
+pub struct SpreadingConfig {
+	/// Decay per hop (default: 0.7)
+	pub decay_per_hop: f64,
+	/// Minimum activation to propagate (default: 0.01)
+	pub minimum_activation: f64,
+	/// Maximum nodes to visit (default: 1000)
+	pub max_nodes: usize,
+	/// Whether to spread bidirectionally (default: true)
+	pub bidirectional: bool,
+	/// Spread until a level adds less than this; overrides depth
+	pub convergence_epsilon: Option<f64>,
+	/// Level cap when spreading to convergence (default: 100)
+	pub max_iterations: usize,
+	/// Logistic noise scale; 0 disables noise (default: 0)
+	pub activation_noise: f64,
+	/// Seed for activation noise (default: 0)
+	pub noise_seed: u64,
+}
+
+#[pymethods]
+impl SpreadingConfig {
+	#[new]
+	#[pyo3(signature = (
+		*,
+		decay_per_hop = None,
+		minimum_activation = None,
+		max_nodes = None,
+		bidirectional = None,
+		convergence_epsilon = None,
+		max_iterations = None,
+		activation_noise = None,
+		noise_seed = None,
+	))]
+	fn new(
+		decay_per_hop: Option<f64>,
+		minimum_activation: Option<f64>,
+		max_nodes: Option<usize>,
+		bidirectional: Option<bool>,
+		convergence_epsilon: Option<f64>,
+		max_iterations: Option<usize>,
+		activation_noise: Option<f64>,
+		noise_seed: Option<u64>,
+	) -> Self {
+		let default = CoreSpreadingConfig::default();
+		Self {
+			decay_per_hop: decay_per_hop.unwrap_or(default.decay_per_hop),
+			minimum_activation: minimum_activation.unwrap_or(default.minimum_activation),
+			max_nodes: max_nodes.unwrap_or(default.max_nodes),
+			bidirectional: bidirectional.unwrap_or(default.bidirectional),
+			convergence_epsilon: convergence_epsilon.or(default.convergence_epsilon),
+			max_iterations: max_iterations.unwrap_or(default.max_iterations),
+			activation_noise: activation_noise.unwrap_or(default.activation_noise),
+			noise_seed: noise_seed.unwrap_or(default.noise_seed),
+		}
+	}
+
+	fn __repr__(&self) -> String {
+		format!(
+			"SpreadingConfig(decay_per_hop={:?}, minimum_activation={:?}, max_nodes={}, \
+			 bidirectional={}, convergence_epsilon={}, max_iterations={}, \
+			 activation_noise={:?}, noise_seed={})",
+			self.decay_per_hop,
+			self.minimum_activation,
+			self.max_nodes,
+			py_bool(self.bidirectional),
+			py_float(self.convergence_epsilon),
+			self.max_iterations,
+			self.activation_noise,
+			self.noise_seed,
+		)
+	}
+}
+
+impl SpreadingConfig {
+	fn to_core(&self) -> CoreSpreadingConfig {
+		CoreSpreadingConfig {
+			decay_per_hop: self.decay_per_hop,
+			minimum_activation: self.minimum_activation,
+			max_nodes: self.max_nodes,
+			bidirectional: self.bidirectional,
+			convergence_epsilon: self.convergence_epsilon,
+			max_iterations: self.max_iterations,
+			activation_noise: self.activation_noise,
+			noise_seed: self.noise_seed,
+			..CoreSpreadingConfig::default()
+		}
+	}
+}
+
+/// Temporal spreading settings; omitted arguments keep the core defaults.
+#[pyclass(module = "lucid_core", get_all, set_all)]
+#[derive(Clone)]
+pub struct TemporalSpreadingConfig {
+	/// Forward link strength multiplier (default: 1.0)
+	pub forward_strength: f64,
+	/// Backward link strength multiplier (default: 0.7)
+	pub backward_strength: f64,
+	/// Distance decay rate (default: 0.3)
+	pub distance_decay_rate: f64,
+	/// Activation boost for episode-linked memories (default: 1.2)
+	pub episode_boost: f64,
+	/// TCM context persistence (default: 0.7)
+	pub context_persistence: f64,
+	/// Maximum positions between linked events (default: 10)
+	pub max_temporal_distance: usize,
+}
+
+#[pymethods]
+impl TemporalSpreadingConfig {
+	#[new]
+	#[pyo3(signature = (
+		*,
+		forward_strength = None,
+		backward_strength = None,
+		distance_decay_rate = None,
+		episode_boost = None,
+		context_persistence = None,
+		max_temporal_distance = None,
+	))]
+	fn new(
+		forward_strength: Option<f64>,
+		backward_strength: Option<f64>,
+		distance_decay_rate: Option<f64>,
+		episode_boost: Option<f64>,
+		context_persistence: Option<f64>,
+		max_temporal_distance: Option<usize>,
+	) -> Self {
+		let default = CoreTemporalConfig::default();
+		Self {
+			forward_strength: forward_strength.unwrap_or(default.forward_strength),
+			backward_strength: backward_strength.unwrap_or(default.backward_strength),
+			distance_decay_rate: distance_decay_rate.unwrap_or(default.distance_decay_rate),
+			episode_boost: episode_boost.unwrap_or(default.episode_boost),
+			context_persistence: context_persistence.unwrap_or(default.context_persistence),
+			max_temporal_distance: max_temporal_distance.unwrap_or(default.max_temporal_distance),
+		}
+	}
+
+	fn __repr__(&self) -> String {
+		format!(
+			"TemporalSpreadingConfig(forward_strength={:?}, backward_strength={:?}, \
+			 distance_decay_rate={:?}, episode_boost={:?}, context_persistence={:?}, \
+			 max_temporal_distance={})",
+			self.forward_strength,
+			self.backward_strength,
+			self.distance_decay_rate,
+			self.episode_boost,
+			self.context_persistence,
+			self.max_temporal_distance,
+		)
+	}
+}
+
+impl TemporalSpreadingConfig {
+	const fn to_core(&self) -> CoreTemporalConfig {
+		CoreTemporalConfig {
+			forward_strength: self.forward_strength,
+			backward_strength: self.backward_strength,
+			distance_decay_rate: self.distance_decay_rate,
+			episode_boost: self.episode_boost,
+			context_persistence: self.context_persistence,
+			max_temporal_distance: self.max_temporal_distance,
+		}
+	}
+}
+
+/// Retrieval settings; omitted arguments keep the core defaults.
+#[pyclass(module = "lucid_core", get_all, set_all)]
+#[derive(Clone)]
+pub struct RetrievalConfig {
+	/// Base-level decay rate (default: 0.5)
+	pub decay_rate: f64,
+	/// Retrieval threshold τ (default: 0.3)
+	pub activation_threshold: f64,
+	/// Noise parameter s (default: 0.1)
+	pub noise_parameter: f64,
+	/// Spreading depth (default: 3)
+	pub spreading_depth: usize,
+	/// Spreading decay per hop (default: 0.7)
+	pub spreading_decay: f64,
+	/// Minimum probability to include (default: 0.1)
+	pub min_probability: f64,
+	/// Maximum results to return (default: 10)
+	pub max_results: usize,
+	/// Whether to spread bidirectionally (default: true)
+	pub bidirectional: bool,
+}
+
+#[pymethods]
+impl RetrievalConfig {
+	#[new]
+	#[pyo3(signature = (
+		*,
+		decay_rate = None,
+		activation_threshold = None,
+		noise_parameter = None,
+		spreading_depth = None,
+		spreading_decay = None,
+		min_probability = None,
+		max_results = None,
+		bidirectional = None,
+	))]
+	fn new(
+		decay_rate: Option<f64>,
+		activation_threshold: Option<f64>,
+		noise_parameter: Option<f64>,
+		spreading_depth: Option<usize>,
+		spreading_decay: Option<f64>,
+		min_probability: Option<f64>,
+		max_results: Option<usize>,
+		bidirectional: Option<bool>,
+	) -> Self {
+		let default = CoreRetrievalConfig::default();
+		Self {
+			decay_rate: decay_rate.unwrap_or(default.decay_rate),
+			activation_threshold: activation_threshold.unwrap_or(default.activation_threshold),
+			noise_parameter: noise_parameter.unwrap_or(default.noise_parameter),
+			spreading_depth: spreading_depth.unwrap_or(default.spreading_depth),
+			spreading_decay: spreading_decay.unwrap_or(default.spreading_decay),
+			min_probability: min_probability.unwrap_or(default.min_probability),
+			max_results: max_results.unwrap_or(default.max_results),
+			bidirectional: bidirectional.unwrap_or(default.bidirectional),
+		}
+	}
+
+	fn __repr__(&self) -> String {
+		format!(
+			"RetrievalConfig(decay_rate={:?}, activation_threshold={:?}, noise_parameter={:?}, \
+			 spreading_depth={}, spreading_decay={:?}, min_probability={:?}, max_results={}, \
+			 bidirectional={})",
+			self.decay_rate,
+			self.activation_threshold,
+			self.noise_parameter,
+			self.spreading_depth,
+			self.spreading_decay,
+			self.min_probability,
+			self.max_results,
+			py_bool(self.bidirectional),
+		)
+	}
+}
+
+impl RetrievalConfig {
+	const fn to_core(&self) -> CoreRetrievalConfig {
+		CoreRetrievalConfig {
+			decay_rate: self.decay_rate,
+			activation_threshold: self.activation_threshold,
+			noise_parameter: self.noise_parameter,
+			spreading_depth: self.spreading_depth,
+			spreading_decay: self.spreading_decay,
+			min_probability: self.min_probability,
+			max_results: self.max_results,
+			bidirectional: self.bidirectional,
+		}
+	}
+}
+
+/// Unified scoring settings; omitted arguments keep the core defaults.
+#[pyclass(module = "lucid_core", get_all, set_all)]
+#[derive(Clone)]
+pub struct ScoreConfig {
+	/// Weight of base-level activation (default: 1.0)
+	pub base_level_weight: f64,
+	/// Weight of spreading activation (default: 1.0)
+	pub spreading_weight: f64,
+	/// Weight of probe similarity (default: 1.0)
+	pub similarity_weight: f64,
+	/// Weight of recency (default: 0.5)
+	pub recency_weight: f64,
+	/// "none", "minmax" or "zscore" (default: "minmax")
+	pub normalization: String,
+	/// Recency half-life in ms (default: one day)
+	pub recency_half_life_ms: f64,
+	/// Minimum score to include (default: keep all)
+	pub min_score: Option<f64>,
+	/// Maximum results to return (default: 10)
+	pub max_results: usize,
+}
+
+#[pymethods]
+impl ScoreConfig {
+	#[new]
+	#[pyo3(signature = (
+		*,
+		base_level_weight = None,
+		spreading_weight = None,
+		similarity_weight = None,
+		recency_weight = None,
+		normalization = None,
+		recency_half_life_ms = None,
+		min_score = None,
+		max_results = None,
+	))]
+	fn new(
+		base_level_weight: Option<f64>,
+		spreading_weight: Option<f64>,
+		similarity_weight: Option<f64>,
+		recency_weight: Option<f64>,
+		normalization: Option<String>,
+		recency_half_life_ms: Option<f64>,
+		min_score: Option<f64>,
+		max_results: Option<usize>,
+	) -> Self {
+		let default = CoreScoreConfig::default();
+		Self {
+			base_level_weight: base_level_weight.unwrap_or(default.weights.base_level),
+			spreading_weight: spreading_weight.unwrap_or(default.weights.spreading),
+			similarity_weight: similarity_weight.unwrap_or(default.weights.similarity),
+			recency_weight: recency_weight.unwrap_or(default.weights.recency),
+			normalization: normalization
+				.unwrap_or_else(|| normalization_name(default.normalization).to_string()),
+			recency_half_life_ms: recency_half_life_ms.unwrap_or(default.recency_half_life_ms),
+			min_score: min_score.or(default.min_score),
+			max_results: max_results.unwrap_or(default.max_results),
+		}
+	}
+
+	fn __repr__(&self) -> String {
+		format!(
+			"ScoreConfig(base_level_weight={:?}, spreading_weight={:?}, similarity_weight={:?}, \
+			 recency_weight={:?}, normalization='{}', recency_half_life_ms={:?}, min_score={}, \
+			 max_results={})",
+			self.base_level_weight,
+			self.spreading_weight,
+			self.similarity_weight,
+			self.recency_weight,
+			self.normalization,
+			self.recency_half_life_ms,
+			py_float(self.min_score),
+			self.max_results,
+		)
+	}
+}
+
+impl ScoreConfig {
+	fn to_core(&self) -> PyResult<CoreScoreConfig> {
+		let normalization = parse_normalization(&self.normalization).ok_or_else(|| {
+			PyValueError::new_err(format!("unknown normalization \"{}\"", self.normalization))
+		})?;
+		Ok(CoreScoreConfig {
+			weights: ScoreWeights {
+				base_level: self.base_level_weight,
+				spreading: self.spreading_weight,
+				similarity: self.similarity_weight,
+				recency: self.recency_weight,
+			},
+			normalization,
+			recency_half_life_ms: self.recency_half_life_ms,
+			min_score: self.min_score,
+			max_results: self.max_results,
+		})
+	}
+}
+
+// ============================================================================
+// Association Graph
+// ============================================================================
+
+/// Validate column-wise edge arrays and zip them into associations.
+///
+/// Errors are plain messages so the checks run without an interpreter.
+fn build_associations(
+	num_nodes: usize,
+	sources: &[usize],
+	targets: &[usize],
+	forward_strengths: &[f64],
+	backward_strengths: &[f64],
+	association_types: Option<&[String]>,
+) -> Result<Vec<Association>, String> {
+	let len = sources.len();
+	if targets.len() != len || forward_strengths.len() != len || backward_strengths.len() != len {
+		return Err(
+			"sources, targets, forward_strengths and backward_strengths must have equal length"
+				.to_string(),
+		);
+	}
+	if association_types.is_some_and(|t| t.len() != len) {
+		return Err("association_types must match sources in length".to_string());
+	}
+
+	// Endpoints and strengths are checked by the builder
+	let mut builder = GraphBuilder::new().with_duplicate_policy(DuplicatePolicy::Last);
+	let _ = builder.add_nodes(num_nodes);
+	for i in 0..len {
+		let association_type = match association_types {
+			Some(types) => types[i]
+				.parse()
+				.map_err(|_| format!("unknown association type \"{}\"", types[i]))?,
+			None => AssociationType::default(),
+		};
+		let _ = builder.add_association(Association {
+			source: sources[i],
+			target: targets[i],
+			forward_strength: forward_strengths[i],
+			backward_strength: backward_strengths[i],
+			association_type,
+		});
+	}
+	builder.into_associations().map_err(|e| e.to_string())
+}
+
+/// Reject seeds outside `0..num_nodes`.
+fn check_seeds(seeds: &[usize], num_nodes: usize) -> Result<(), String> {
+	seeds
+		.iter()
+		.find(|&&seed| seed >= num_nodes)
+		.map_or(Ok(()), |seed| {
+			Err(format!(
+				"seed {seed} is out of range: the graph has {num_nodes} nodes"
+			))
+		})
+}
+
+/// Association graph indexed once and queried many times.
+///
+/// Edges are given column-wise: the `i`-th entry of each array describes
+/// one association.
+#[pyclass(module = "lucid_core", frozen)]
+pub struct AssociationGraph {
+	associations: Vec<Association>,
+	graph: CoreGraph,
+}
+
+#[pymethods]
+impl AssociationGraph {
+	/// Build the graph index.
+	///
+	/// `association_types` entries are "semantic" (default), "temporal",
+	/// "causal", "entity", or "`visual_similarity`".
+	///
+	/// Strengths must be finite and within `0..=1`; for a repeated
+	/// `(source, target)` pair the last one wins.
+	///
+	/// Raises `ValueError` if the arrays differ in length, an endpoint or
+	/// strength is out of range, or a type is unknown.
+	#[new]
+	#[pyo3(signature = (
+		num_nodes,
+		sources,
+		targets,
+		forward_strengths,
+		backward_strengths,
+		association_types = None,
+	))]
+	fn new(
+		num_nodes: usize,
+		sources: Vec<usize>,
+		targets: Vec<usize>,
+		forward_strengths: Floats<'_>,
+		backward_strengths: Floats<'_>,
+		association_types: Option<Vec<String>>,
+	) -> PyResult<Self> {
+		let forward_strengths = floats(&forward_strengths);
+		let backward_strengths = floats(&backward_strengths);
+		let associations = build_associations(
+			num_nodes,
+			&sources,
+			&targets,
+			&forward_strengths,
+			&backward_strengths,
+			association_types.as_deref(),
+		)
+		.map_err(PyValueError::new_err)?;
+
+		let graph = CoreGraph::new(num_nodes, &associations);
+		Ok(Self {
+			associations,
+			graph,
+		})
+	}
+
+	/// Number of nodes in the graph.
+	#[getter]
+	const fn num_nodes(&self) -> usize {
+		self.graph.num_nodes()
+	}
+
+	/// Number of associations in the graph.
+	#[getter]
+	fn num_edges(&self) -> usize {
+		self.graph.num_edges()
+	}
+
+	/// Spread activation from seed nodes.
+	///
+	/// Returns one activation per node. Raises `ValueError` if a seed is
+	/// out of range.
+	#[pyo3(signature = (seed_indices, seed_activations, depth = 3, config = None))]
+	fn spread<'py>(
+		&self,
+		py: Python<'py>,
+		seed_indices: Vec<usize>,
+		seed_activations: Floats<'py>,
+		depth: usize,
+		config: Option<SpreadingConfig>,
+	) -> PyResult<Bound<'py, PyArray1<f64>>> {
+		check_seeds(&seed_indices, self.graph.num_nodes()).map_err(PyValueError::new_err)?;
+		let config = config.map_or_else(CoreSpreadingConfig::default, |c| c.to_core());
+		let result = spread_activation_graph(
+			&self.graph,
+			&seed_indices,
+			&floats(&seed_activations),
+			&config,
+			depth,
+		);
+		Ok(PyArray1::from_vec(py, result.activations))
+	}
+
+	/// `PageRank` with rank split evenly over out-links.
+	#[pyo3(signature = (damping = 0.85, iterations = 20))]
+	fn pagerank<'py>(
+		&self,
+		py: Python<'py>,
+		damping: f64,
+		iterations: usize,
+	) -> Bound<'py, PyArray1<f64>> {
+		PyArray1::from_vec(py, compute_pagerank_graph(&self.graph, damping, iterations))
+	}
+
+	/// `PageRank` with rank split in proportion to forward strengths.
+	#[pyo3(signature = (damping = 0.85, iterations = 20))]
+	fn weighted_pagerank<'py>(
+		&self,
+		py: Python<'py>,
+		damping: f64,
+		iterations: usize,
+	) -> Bound<'py, PyArray1<f64>> {
+		PyArray1::from_vec(
+			py,
+			compute_weighted_pagerank_graph(&self.graph, damping, iterations),
+		)
+	}
+}
+
+// ============================================================================
+// Temporal Spreading
+// ============================================================================
+
+/// Temporal links between the events of one episode.
+#[pyclass(module = "lucid_core", frozen)]
+pub struct TemporalLinks {
+	links: Vec<TemporalLink>,
+}
+
+#[pymethods]
+impl TemporalLinks {
+	fn __len__(&self) -> usize {
+		self.links.len()
+	}
+
+	/// Source memory of each link.
+	#[getter]
+	fn source_memories<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<i64>> {
+		let memories: Vec<usize> = self.links.iter().map(|l| l.source_memory).collect();
+		PyArray1::from_vec(py, to_i64(&memories))
+	}
+
+	/// Target memory of each link.
+	#[getter]
+	fn target_memories<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<i64>> {
+		let memories: Vec<usize> = self.links.iter().map(|l| l.target_memory).collect();
+		PyArray1::from_vec(py, to_i64(&memories))
+	}
+
+	/// Forward strength of each link.
+	#[getter]
+	fn forward_strengths<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+		PyArray1::from_iter(py, self.links.iter().map(|l| l.forward_strength))
+	}
+
+	/// Backward strength of each link.
+	#[getter]
+	fn backward_strengths<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+		PyArray1::from_iter(py, self.links.iter().map(|l| l.backward_strength))
+	}
+}
+
+/// Create temporal links between the events of an episode, in order.
+#[pyfunction]
+#[pyo3(signature = (event_memory_indices, config = None))]
+fn create_episode_links(
+	event_memory_indices: Vec<usize>,
+	config: Option<TemporalSpreadingConfig>,
+) -> TemporalLinks {
+	let config = config.map_or_else(CoreTemporalConfig::default, |c| c.to_core());
+	TemporalLinks {
+		links: core_create_episode_links(&event_memory_indices, &config),
+	}
+}
+
+/// Spread activation along an episode's temporal links from one seed
+/// memory.
+///
+/// Returns a dict of `activations` (one per memory), `forward_activated`
+/// and `backward_activated` (memory indices).
+#[pyfunction]
+#[pyo3(signature = (num_memories, links, seed_memory, seed_activation = 1.0, config = None))]
+fn spread_temporal_activation<'py>(
+	py: Python<'py>,
+	num_memories: usize,
+	links: &TemporalLinks,
+	seed_memory: usize,
+	seed_activation: f64,
+	config: Option<TemporalSpreadingConfig>,
+) -> PyResult<Bound<'py, PyDict>> {
+	let config = config.map_or_else(CoreTemporalConfig::default, |c| c.to_core());
+	let result = core_spread_temporal(
+		num_memories,
+		&links.links,
+		seed_memory,
+		seed_activation,
+		&config,
+	);
+
+	let dict = PyDict::new(py);
+	dict.set_item("activations", PyArray1::from_vec(py, result.activations))?;
+	dict.set_item(
+		"forward_activated",
+		PyArray1::from_vec(py, to_i64(&result.forward_activated)),
+	)?;
+	dict.set_item(
+		"backward_activated",
+		PyArray1::from_vec(py, to_i64(&result.backward_activated)),
+	)?;
+	Ok(dict)
+}
+
+// ============================================================================
+// Recall Scoring
+// ============================================================================
+
+/// Full retrieval pipeline: probe similarity, base-level activation,
+/// spreading, and retrieval probability.
+///
+/// `memory_embeddings` is a 2-D array with one row per memory;
+/// `access_histories_ms` holds one array of access times per memory.
+/// Returns a dict of arrays, one entry per returned candidate in rank
+/// order: `index`, `base_level`, `probe_activation`, `spreading`,
+/// `emotional_weight`, `total_activation`, `probability`.
+#[pyfunction]
+#[pyo3(signature = (
+	probe_embedding,
+	memory_embeddings,
+	access_histories_ms,
+	emotional_weights,
+	decay_rates,
+	working_memory_boosts,
+	current_time_ms,
+	graph = None,
+	config = None,
+))]
+fn retrieve<'py>(
+	py: Python<'py>,
+	probe_embedding: Floats<'py>,
+	memory_embeddings: PyArrayLike2<'py, f64, AllowTypeChange>,
+	access_histories_ms: Vec<Floats<'py>>,
+	emotional_weights: Floats<'py>,
+	decay_rates: Floats<'py>,
+	working_memory_boosts: Floats<'py>,
+	current_time_ms: f64,
+	graph: Option<&AssociationGraph>,
+	config: Option<RetrievalConfig>,
+) -> PyResult<Bound<'py, PyDict>> {
+	let memory_embeddings: Vec<Vec<f64>> = memory_embeddings
+		.as_array()
+		.rows()
+		.into_iter()
+		.map(|row| row.to_vec())
+		.collect();
+	if access_histories_ms.len() != memory_embeddings.len() {
+		return Err(PyValueError::new_err(
+			"access_histories_ms must have one entry per memory",
+		));
+	}
+	let access_histories_ms: Vec<Vec<f64>> = access_histories_ms.iter().map(floats).collect();
+	let config = config.map_or_else(CoreRetrievalConfig::default, |c| c.to_core());
+
+	let input = RetrievalInput {
+		probe_embedding: &floats(&probe_embedding),
+		memory_embeddings: &memory_embeddings,
+		access_histories_ms: &access_histories_ms,
+		emotional_weights: &floats(&emotional_weights),
+		decay_rates: &floats(&decay_rates),
+		working_memory_boosts: &floats(&working_memory_boosts),
+		retrieval_thresholds: &[],
+		associations: graph.map_or(&[][..], |g| &g.associations),
+		current_time_ms,
+	};
+	let candidates = core_retrieve(&input, &config);
+
+	let column = |f: fn(&lucid_core::RetrievalCandidate) -> f64| {
+		PyArray1::from_iter(py, candidates.iter().map(f))
+	};
+	let dict = PyDict::new(py);
+	dict.set_item(
+		"index",
+		PyArray1::from_iter(py, candidates.iter().map(|c| c.index as i64)),
+	)?;
+	dict.set_item("base_level", column(|c| c.base_level))?;
+	dict.set_item("probe_activation", column(|c| c.probe_activation))?;
+	dict.set_item("spreading", column(|c| c.spreading))?;
+	dict.set_item("emotional_weight", column(|c| c.emotional_weight))?;
+	dict.set_item("total_activation", column(|c| c.total_activation))?;
+	dict.set_item("probability", column(|c| c.probability))?;
+	Ok(dict)
+}
+
+/// Unified retrieval scoring over precomputed signals.
+///
+/// Returns a dict with `index` and `score` in rank order, plus
+/// `components` (raw signals) and `contributions` (weighted, normalized
+/// signals), each an `(n, 4)` array with columns base level, spreading,
+/// similarity, recency.
+///
+/// Raises `ValueError` if the configured normalization is unknown.
+#[pyfunction]
+#[pyo3(signature = (
+	base_levels,
+	spreading,
+	similarities,
+	last_access_ms,
+	current_time_ms,
+	config = None,
+))]
+fn score<'py>(
+	py: Python<'py>,
+	base_levels: Floats<'py>,
+	spreading: Floats<'py>,
+	similarities: Floats<'py>,
+	last_access_ms: Floats<'py>,
+	current_time_ms: f64,
+	config: Option<ScoreConfig>,
+) -> PyResult<Bound<'py, PyDict>> {
+	let config = config.map_or_else(|| Ok(CoreScoreConfig::default()), |c| c.to_core())?;
+	let input = ScoreInput {
+		base_levels: &floats(&base_levels),
+		spreading: &floats(&spreading),
+		similarities: &floats(&similarities),
+		last_access_ms: &floats(&last_access_ms),
+		current_time_ms,
+	};
+	let scored = core_score(&input, &config);
+
+	let rows = |f: fn(&lucid_core::ScoredMemory) -> &lucid_core::ScoreComponents| {
+		scored
+			.iter()
+			.map(|m| {
+				let c = f(m);
+				vec![c.base_level, c.spreading, c.similarity, c.recency]
+			})
+			.collect::<Vec<_>>()
+	};
+	let dict = PyDict::new(py);
+	dict.set_item(
+		"index",
+		PyArray1::from_iter(py, scored.iter().map(|m| m.index as i64)),
+	)?;
+	dict.set_item(
+		"score",
+		PyArray1::from_iter(py, scored.iter().map(|m| m.score)),
+	)?;
+	for (key, values) in [
+		("components", rows(|m| &m.components)),
+		("contributions", rows(|m| &m.contributions)),
+	] {
+		let array = if values.is_empty() {
+			PyArray2::zeros(py, [0, 4], false)
+		} else {
+			PyArray2::from_vec2(py, &values).map_err(|e| PyValueError::new_err(e.to_string()))?
+		};
+		dict.set_item(key, array)?;
+	}
+	Ok(dict)
+}
+
+/// ACT-R base-level activation from access times (ms):
+/// `ln(Σ t^(-decay))`, or `-inf` with no accesses.
+#[pyfunction]
+#[pyo3(signature = (access_times_ms, current_time_ms, decay = 0.5))]
+fn base_level(access_times_ms: Floats<'_>, current_time_ms: f64, decay: f64) -> f64 {
+	compute_base_level(&floats(&access_times_ms), current_time_ms, decay)
+}
+
+/// MINERVA 2 cubic activation of each similarity.
+#[pyfunction]
+fn nonlinear_activation<'py>(
+	py: Python<'py>,
+	similarities: Floats<'py>,
+) -> Bound<'py, PyArray1<f64>> {
+	PyArray1::from_vec(py, nonlinear_activation_batch(&floats(&similarities)))
+}
+
+/// Logistic retrieval probability of each activation:
+/// `1 / (1 + e^((τ - A) / s))`.
+#[pyfunction]
+#[pyo3(signature = (activations, threshold = 0.3, noise = 0.1))]
+fn retrieval_probability<'py>(
+	py: Python<'py>,
+	activations: Floats<'py>,
+	threshold: f64,
+	noise: f64,
+) -> Bound<'py, PyArray1<f64>> {
+	PyArray1::from_vec(
+		py,
+		retrieval_probability_batch(&floats(&activations), threshold, noise),
+	)
+}
+
+// ============================================================================
+// Module
+// ============================================================================
+
+/// Library version
+#[pyfunction]
+#[allow(clippy::missing_const_for_fn)] // pyfunction exports can't be const
+fn version() -> &'static str {
+	lucid_core::VERSION
+}
+
+#[pymodule]
+#[pyo3(name = "lucid_core")]
+fn lucid_core_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+	m.add_class::<SpreadingConfig>()?;
+	m.add_class::<TemporalSpreadingConfig>()?;
+	m.add_class::<RetrievalConfig>()?;
+	m.add_class::<ScoreConfig>()?;
+	m.add_class::<AssociationGraph>()?;
+	m.add_class::<TemporalLinks>()?;
+	m.add_function(wrap_pyfunction!(create_episode_links, m)?)?;
+	m.add_function(wrap_pyfunction!(spread_temporal_activation, m)?)?;
+	m.add_function(wrap_pyfunction!(retrieve, m)?)?;
+	m.add_function(wrap_pyfunction!(score, m)?)?;
+	m.add_function(wrap_pyfunction!(base_level, m)?)?;
+	m.add_function(wrap_pyfunction!(nonlinear_activation, m)?)?;
+	m.add_function(wrap_pyfunction!(retrieval_probability, m)?)?;
+	m.add_function(wrap_pyfunction!(version, m)?)?;
+	m.add("__version__", lucid_core::VERSION)?;
+	Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_spreading_config_to_core() {
+		let default = CoreSpreadingConfig::default();
+		let core = SpreadingConfig::new(None, None, None, None, None, None, None, None).to_core();
+		assert_eq!(core.decay_per_hop, default.decay_per_hop);
+		assert_eq!(core.minimum_activation, default.minimum_activation);
+		assert_eq!(core.max_nodes, default.max_nodes);
+		assert_eq!(core.bidirectional, default.bidirectional);
+		assert_eq!(core.convergence_epsilon, default.convergence_epsilon);
+		assert_eq!(core.max_iterations, default.max_iterations);
+
+		let mut config = SpreadingConfig::new(
+			Some(0.5),
+			None,
+			Some(10),
+			Some(false),
+			Some(1e-3),
+			None,
+			Some(0.2),
+			Some(7),
+		);
+		config.minimum_activation = 0.05;
+		let core = config.to_core();
+		assert_eq!(core.decay_per_hop, 0.5);
+		assert_eq!(core.minimum_activation, 0.05);
+		assert_eq!(core.max_nodes, 10);
+		assert!(!core.bidirectional);
+		assert_eq!(core.convergence_epsilon, Some(1e-3));
+		assert_eq!(core.activation_noise, 0.2);
+		assert_eq!(core.noise_seed, 7);
+	}
+
+	#[test]
+	fn test_temporal_and_retrieval_config_to_core() {
+		let default = CoreTemporalConfig::default();
+		let core = TemporalSpreadingConfig::new(None, None, None, None, None, Some(3)).to_core();
+		assert_eq!(core.forward_strength, default.forward_strength);
+		assert_eq!(core.context_persistence, default.context_persistence);
+		assert_eq!(core.max_temporal_distance, 3);
+
+		let default = CoreRetrievalConfig::default();
+		let core =
+			RetrievalConfig::new(None, Some(0.0), None, Some(1), None, None, None, None).to_core();
+		assert_eq!(core.decay_rate, default.decay_rate);
+		assert_eq!(core.activation_threshold, 0.0);
+		assert_eq!(core.spreading_depth, 1);
+		assert_eq!(core.max_results, default.max_results);
+	}
+
+	// `ScoreConfig::to_core` builds a Python exception on failure, which
+	// cannot link without an interpreter; its parts are tested instead.
+	#[test]
+	fn test_score_config_defaults_and_normalization() {
+		let default = CoreScoreConfig::default();
+		let config = ScoreConfig::new(None, Some(2.0), None, None, None, None, None, None);
+		assert_eq!(config.spreading_weight, 2.0);
+		assert_eq!(config.recency_weight, default.weights.recency);
+		assert_eq!(config.max_results, default.max_results);
+		assert_eq!(
+			parse_normalization(&config.normalization),
+			Some(default.normalization)
+		);
+
+		for normalization in [
+			ScoreNormalization::None,
+			ScoreNormalization::MinMax,
+			ScoreNormalization::ZScore,
+		] {
+			assert_eq!(
+				parse_normalization(normalization_name(normalization)),
+				Some(normalization)
+			);
+		}
+		assert_eq!(
+			parse_normalization("Z_Score"),
+			Some(ScoreNormalization::ZScore)
+		);
+		assert_eq!(parse_normalization("softmax"), None);
+	}
+
+	#[test]
+	fn test_build_associations() {
+		let types = ["entity".to_string(), "VisualSimilarity".to_string()];
+		let associations =
+			build_associations(3, &[0, 1], &[1, 2], &[0.5, 0.8], &[0.2, 0.4], Some(&types))
+				.unwrap_or_default();
+		assert_eq!(associations.len(), 2);
+		assert_eq!(associations[0].association_type, AssociationType::Entity);
+		assert_eq!(
+			associations[1].association_type,
+			AssociationType::VisualSimilarity
+		);
+
+		let untyped = build_associations(3, &[0], &[1], &[0.5], &[0.2], None);
+		assert!(untyped.is_ok_and(|a| a[0].association_type == AssociationType::Semantic));
+
+		let repeated = build_associations(2, &[0, 0], &[1, 1], &[0.5, 0.8], &[0.2, 0.4], None);
+		assert!(repeated.is_ok_and(|a| a.len() == 1 && (a[0].forward_strength - 0.8).abs() < 1e-12));
+	}
+
+	#[test]
+	fn test_build_associations_rejects_bad_input() {
+		let result = build_associations(3, &[0, 1], &[1, 2], &[0.5], &[0.2, 0.4], None);
+		assert!(result.is_err_and(|e| e.contains("equal length")));
+
+		let types = ["causal".to_string()];
+		let result =
+			build_associations(3, &[0, 1], &[1, 2], &[0.5, 0.8], &[0.2, 0.4], Some(&types));
+		assert!(result.is_err_and(|e| e.contains("association_types")));
+
+		let result = build_associations(3, &[3], &[0], &[0.5], &[0.2], None);
+		assert!(result.is_err_and(|e| e.contains("source 3") && e.contains("3 nodes")));
+
+		let result = build_associations(3, &[0], &[1], &[f64::NAN], &[0.2], None);
+		assert!(result.is_err_and(|e| e.contains("forward_strength")));
+		let result = build_associations(3, &[0], &[1], &[0.5], &[1.5], None);
+		assert!(result.is_err_and(|e| e.contains("backward_strength")));
+
+		let types = ["friendship".to_string()];
+		let result = build_associations(3, &[0], &[1], &[0.5], &[0.2], Some(&types));
+		assert!(result.is_err_and(|e| e.contains("\"friendship\"")));
+	}
+
+	#[test]
+	fn test_check_seeds() {
+		assert!(check_seeds(&[0, 2], 3).is_ok());
+		let result = check_seeds(&[0, usize::MAX], 3);
+		assert!(result.is_err_and(|e| e.contains("out of range") && e.contains("3 nodes")));
+	}
+}
@@ -3,15 +3,20 @@
 //! Tests performance of:
 //! - Base-level activation (ACT-R decay function)
 //! - Cosine similarity (batch and single)
+//! - HNSW approximate nearest-neighbor search
 //! - Nonlinear activation (MINERVA 2 cubing)
 //! - Retrieval probability computation
 
 #![allow(clippy::expect_used)] // Fine in benchmarks
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use lucid_core::activation::{
-	compute_base_level, cosine_similarity, nonlinear_activation, retrieval_probability,
-	ActivationConfig,
+use lucid_core::{
+	activation::{
+		compute_base_level, cosine_similarity, nonlinear_activation, retrieval_probability,
+		ActivationConfig,
+	},
+	embeddings::{self, Metric},
+	HnswConfig, HnswIndex,
 };
 use rand::Rng;
 
@@ -90,6 +95,65 @@ fn bench_cosine_similarity_batch(c: &mut Criterion) {
 	group.finish();
 }
 
+fn bench_embeddings_f32(c: &mut Criterion) {
+	let mut group = c.benchmark_group("embeddings_f32");
+
+	for dim in &[384, 768, 1536] {
+		let embeddings = generate_embeddings(2, *dim);
+		#[allow(clippy::cast_possible_truncation)]
+		let a: Vec<f32> = embeddings[0].iter().map(|&x| x as f32).collect();
+		#[allow(clippy::cast_possible_truncation)]
+		let b: Vec<f32> = embeddings[1].iter().map(|&x| x as f32).collect();
+
+		let _ = group.throughput(Throughput::Elements(1));
+		let _ = group.bench_with_input(BenchmarkId::new("cosine_f64", dim), dim, |bench, _| {
+			bench.iter(|| cosine_similarity(black_box(&embeddings[0]), black_box(&embeddings[1])));
+		});
+		let _ = group.bench_with_input(BenchmarkId::new("cosine_f32", dim), dim, |bench, _| {
+			bench.iter(|| embeddings::cosine(black_box(&a), black_box(&b)));
+		});
+	}
+
+	for memory_count in &[1000, 10_000] {
+		#[allow(clippy::cast_possible_truncation)]
+		let memories: Vec<Vec<f32>> = generate_embeddings(*memory_count, 768)
+			.into_iter()
+			.map(|m| m.into_iter().map(|x| x as f32).collect())
+			.collect();
+		let probe = memories[0].clone();
+
+		let _ = group.throughput(Throughput::Elements(*memory_count as u64));
+		let _ = group.bench_with_input(
+			BenchmarkId::new("top_k_cosine", memory_count),
+			memory_count,
+			|bench, _| {
+				bench.iter(|| {
+					embeddings::top_k_similar(
+						black_box(&probe),
+						black_box(&memories),
+						10,
+						Metric::Cosine,
+					)
+				});
+			},
+		);
+
+		let mut index = HnswIndex::new(768, HnswConfig::default());
+		for (id, memory) in memories.iter().enumerate() {
+			index.insert(id, memory).expect("matching dimension");
+		}
+		let _ = group.bench_with_input(
+			BenchmarkId::new("hnsw_search", memory_count),
+			memory_count,
+			|bench, _| {
+				bench.iter(|| index.search(black_box(&probe), 10));
+			},
+		);
+	}
+
+	group.finish();
+}
+
 fn bench_base_level_activation(c: &mut Criterion) {
 	let mut group = c.benchmark_group("base_level_activation");
 	let current_time = 1_000_000.0;
@@ -212,6 +276,7 @@ criterion_group!(
 	benches,
 	bench_cosine_similarity,
 	bench_cosine_similarity_batch,
+	bench_embeddings_f32,
 	bench_base_level_activation,
 	bench_nonlinear_activation,
 	bench_retrieval_probability,
@@ -9,8 +9,12 @@
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use lucid_core::{
+	graph::AssociationGraph,
 	retrieval::{retrieve, RetrievalConfig, RetrievalInput},
-	spreading::Association,
+	spreading::{
+		get_top_activated, get_top_activated_with_scores, spread_activation,
+		spread_activation_graph, Association, AssociationType, SpreadingConfig,
+	},
 };
 use rand::Rng;
 
@@ -53,6 +57,7 @@ fn generate_associations(memory_count: usize, association_count: usize) -> Vec<A
 			target: rng.gen_range(0..memory_count),
 			forward_strength: rng.gen::<f64>().mul_add(0.8, 0.1),
 			backward_strength: rng.gen::<f64>() * 0.4,
+			association_type: AssociationType::Semantic,
 		})
 		.filter(|a| a.source != a.target)
 		.collect()
@@ -90,6 +95,7 @@ fn bench_retrieval_no_spreading(c: &mut Criterion) {
 					emotional_weights: &emotional_weights,
 					decay_rates: &decay_rates,
 					working_memory_boosts: &working_memory_boosts,
+					retrieval_thresholds: &[],
 					associations: &[],
 					current_time_ms: current_time,
 				};
@@ -137,6 +143,7 @@ fn bench_retrieval_with_spreading(c: &mut Criterion) {
 					emotional_weights: &emotional_weights,
 					decay_rates: &decay_rates,
 					working_memory_boosts: &working_memory_boosts,
+					retrieval_thresholds: &[],
 					associations: &associations,
 					current_time_ms: current_time,
 				};
@@ -174,6 +181,7 @@ fn bench_retrieval_varying_dimensions(c: &mut Criterion) {
 				emotional_weights: &emotional_weights,
 				decay_rates: &decay_rates,
 				working_memory_boosts: &working_memory_boosts,
+				retrieval_thresholds: &[],
 				associations: &[],
 				current_time_ms: current_time,
 			};
@@ -220,6 +228,7 @@ fn bench_retrieval_varying_association_density(c: &mut Criterion) {
 					emotional_weights: &emotional_weights,
 					decay_rates: &decay_rates,
 					working_memory_boosts: &working_memory_boosts,
+					retrieval_thresholds: &[],
 					associations: &associations,
 					current_time_ms: current_time,
 				};
@@ -262,6 +271,7 @@ fn bench_retrieval_spreading_depth(c: &mut Criterion) {
 				emotional_weights: &emotional_weights,
 				decay_rates: &decay_rates,
 				working_memory_boosts: &working_memory_boosts,
+				retrieval_thresholds: &[],
 				associations: &associations,
 				current_time_ms: current_time,
 			};
@@ -272,6 +282,84 @@ fn bench_retrieval_spreading_depth(c: &mut Criterion) {
 	group.finish();
 }
 
+fn bench_spreading_prebuilt_graph(c: &mut Criterion) {
+	let mut group = c.benchmark_group("spreading_prebuilt_graph");
+	let node_count = 100_000;
+	let config = SpreadingConfig {
+		max_nodes: node_count,
+		..Default::default()
+	};
+	let seeds: Vec<usize> = (0..5).collect();
+	let seed_activations = vec![1.0; seeds.len()];
+
+	for edges_per_node in &[2, 10, 20] {
+		let associations = generate_associations(node_count, node_count * edges_per_node);
+		let graph = AssociationGraph::new(node_count, &associations);
+
+		let _ = group.throughput(Throughput::Elements(associations.len() as u64));
+		let _ = group.bench_with_input(
+			BenchmarkId::new("rebuild_per_query", edges_per_node),
+			edges_per_node,
+			|bench, _| {
+				bench.iter(|| {
+					spread_activation(
+						black_box(node_count),
+						black_box(&associations),
+						&seeds,
+						&seed_activations,
+						&config,
+						3,
+					)
+				});
+			},
+		);
+		let _ = group.bench_with_input(
+			BenchmarkId::new("prebuilt", edges_per_node),
+			edges_per_node,
+			|bench, _| {
+				bench.iter(|| {
+					spread_activation_graph(
+						black_box(&graph),
+						&seeds,
+						&seed_activations,
+						&config,
+						3,
+					)
+				});
+			},
+		);
+	}
+
+	group.finish();
+}
+
+fn bench_top_activated(c: &mut Criterion) {
+	let mut group = c.benchmark_group("top_activated");
+	let mut rng = rand::thread_rng();
+
+	for node_count in &[10_000_usize, 1_000_000] {
+		let activations: Vec<f64> = (0..*node_count).map(|_| rng.gen::<f64>()).collect();
+
+		let _ = group.throughput(Throughput::Elements(*node_count as u64));
+		let _ = group.bench_with_input(
+			BenchmarkId::new("sort", node_count),
+			node_count,
+			|bench, _| {
+				bench.iter(|| get_top_activated(black_box(&activations), 20));
+			},
+		);
+		let _ = group.bench_with_input(
+			BenchmarkId::new("heap", node_count),
+			node_count,
+			|bench, _| {
+				bench.iter(|| get_top_activated_with_scores(black_box(&activations), 20));
+			},
+		);
+	}
+
+	group.finish();
+}
+
 criterion_group!(
 	benches,
 	bench_retrieval_no_spreading,
@@ -279,6 +367,8 @@ criterion_group!(
 	bench_retrieval_varying_dimensions,
 	bench_retrieval_varying_association_density,
 	bench_retrieval_spreading_depth,
+	bench_spreading_prebuilt_graph,
+	bench_top_activated,
 );
 
 criterion_main!(benches);
@@ -62,6 +62,7 @@ fn main() {
 		emotional_weights: &emotional_weights,
 		decay_rates: &decay_rates,
 		working_memory_boosts: &working_memory_boosts,
+		retrieval_thresholds: &[],
 		associations: &associations,
 		current_time_ms,
 	};
@@ -8,7 +8,7 @@
 
 use lucid_core::{
 	retrieval::{retrieve, RetrievalConfig, RetrievalInput},
-	spreading::Association,
+	spreading::{Association, AssociationType},
 };
 
 fn main() {
@@ -53,6 +53,7 @@ fn main() {
 			target: 1,
 			forward_strength: 0.8,
 			backward_strength: 0.6,
+			association_type: AssociationType::Semantic,
 		},
 		// Coffee morning → Paris café (you think of coffee, remember Paris)
 		Association {
@@ -60,6 +61,7 @@ fn main() {
 			target: 2,
 			forward_strength: 0.7,
 			backward_strength: 0.5,
+			association_type: AssociationType::Semantic,
 		},
 		// Kitchen routine → Conversation (mornings remind you of talks)
 		Association {
@@ -67,6 +69,7 @@ fn main() {
 			target: 3,
 			forward_strength: 0.5,
 			backward_strength: 0.3,
+			association_type: AssociationType::Semantic,
 		},
 		// Paris café ↔ Conversation (the café is where you had that talk)
 		Association {
@@ -74,6 +77,7 @@ fn main() {
 			target: 3,
 			forward_strength: 0.9,
 			backward_strength: 0.9,
+			association_type: AssociationType::Entity,
 		},
 		// Paris café → Travel plans
 		Association {
@@ -81,6 +85,7 @@ fn main() {
 			target: 4,
 			forward_strength: 0.6,
 			backward_strength: 0.2,
+			association_type: AssociationType::Semantic,
 		},
 	];
 
@@ -102,6 +107,7 @@ fn main() {
 		emotional_weights: &emotional_weights,
 		decay_rates: &decay_rates,
 		working_memory_boosts: &working_memory_boosts,
+		retrieval_thresholds: &[],
 		associations: &[], // No associations
 		current_time_ms,
 	};
@@ -131,6 +137,7 @@ fn main() {
 		emotional_weights: &emotional_weights,
 		decay_rates: &decay_rates,
 		working_memory_boosts: &working_memory_boosts,
+		retrieval_thresholds: &[],
 		associations: &associations,
 		current_time_ms,
 	};
@@ -274,7 +274,8 @@ pub fn combine_activations(
 /// - `s` = noise parameter
 ///
 /// This is a logistic function centered on the threshold.
-/// Higher activation = higher probability.
+/// Higher activation = higher probability. With `s <= 0` (no noise) it
+/// becomes a step: 1.0 at or above the threshold, 0.0 below.
 #[inline]
 #[must_use]
 pub fn retrieval_probability(
@@ -282,6 +283,13 @@ pub fn retrieval_probability(
 	activation_threshold: f64,
 	noise_parameter: f64,
 ) -> f64 {
+	if noise_parameter <= 0.0 {
+		return if total_activation >= activation_threshold {
+			1.0
+		} else {
+			0.0
+		};
+	}
 	let exponent = (activation_threshold - total_activation) / noise_parameter;
 	1.0 / (1.0 + exponent.exp())
 }
@@ -310,6 +318,15 @@ pub fn retrieval_latency(total_activation: f64, latency_factor: f64) -> f64 {
 	latency_factor * (-total_activation).exp() * 1000.0
 }
 
+/// Batch estimate retrieval latencies in milliseconds.
+#[must_use]
+pub fn retrieval_latency_batch(activations: &[f64], latency_factor: f64) -> Vec<f64> {
+	activations
+		.iter()
+		.map(|&a| retrieval_latency(a, latency_factor))
+		.collect()
+}
+
 // ============================================================================
 // Working Memory Boost
 // ============================================================================
@@ -980,6 +997,22 @@ mod tests {
 		// Well above threshold, probability approaches 1
 		let prob_high = retrieval_probability(1.0, 0.3, 0.1);
 		assert!(prob_high > 0.99);
+
+		// Zero noise is a hard threshold rather than NaN
+		assert!((retrieval_probability(0.3, 0.3, 0.0) - 1.0).abs() < 1e-10);
+		assert!(retrieval_probability(0.29, 0.3, 0.0).abs() < 1e-10);
+	}
+
+	#[test]
+	fn test_retrieval_latency() {
+		// A = 0 → latency = F seconds
+		assert!((retrieval_latency(0.0, 0.5) - 500.0).abs() < 1e-10);
+
+		// Higher activation retrieves faster
+		let latencies = retrieval_latency_batch(&[0.0, 1.0, 2.0], 0.5);
+		assert!(latencies[0] > latencies[1]);
+		assert!(latencies[1] > latencies[2]);
+		assert!((latencies[1] - 500.0 * (-1.0_f64).exp()).abs() < 1e-10);
 	}
 
 	#[test]
@@ -0,0 +1,601 @@
+//! Approximate Nearest Neighbors
+//!
+//! Brute-force similarity ([`crate::embeddings::top_k_similar`]) is linear
+//! in the number of memories. Past a few tens of thousands of embeddings,
+//! semantic retrieval needs a sublinear index.
+//!
+//! [`HnswIndex`] is a Hierarchical Navigable Small World graph
+//! (Malkov & Yashunin, 2018). Each vector lives on layers `0..=l`, where
+//! `l` is drawn from a geometric distribution:
+//!
+//! `l = ⌊-ln(u) × m_L⌋`, `m_L = 1 / ln(M)`
+//!
+//! Upper layers are sparse long-range shortcuts; layer 0 holds every
+//! vector. A search descends greedily from the top layer and finishes
+//! with a beam search of width `ef` on layer 0, visiting `O(log n)` nodes.
+//!
+//! Deletion is a tombstone: the node keeps routing searches but is never
+//! returned. [`HnswIndex::compact`] rebuilds without tombstones.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::embeddings::{dot, norm, squared_euclidean, Metric};
+use crate::error::{Classify, ErrorKind};
+use crate::rng::SplitMix64;
+
+/// Highest layer a node can be assigned to.
+const MAX_LEVEL: usize = 16;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Configuration for an HNSW index.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HnswConfig {
+	/// Links per node on upper layers; layer 0 allows `2 × m` (default: 16)
+	pub m: usize,
+	/// Beam width while inserting (default: 200)
+	pub ef_construction: usize,
+	/// Beam width while searching; raised to `k` if smaller (default: 64)
+	pub ef_search: usize,
+	/// Similarity metric (default: cosine)
+	pub metric: Metric,
+	/// Seed for layer assignment (default: 0)
+	pub seed: u64,
+}
+
+impl Default for HnswConfig {
+	fn default() -> Self {
+		Self {
+			m: 16,
+			ef_construction: 200,
+			ef_search: 64,
+			metric: Metric::Cosine,
+			seed: 0,
+		}
+	}
+}
+
+/// Error type for index updates.
+#[derive(Debug, thiserror::Error)]
+pub enum AnnError {
+	/// Vector length does not match the index dimension.
+	#[error("Dimension mismatch: expected {expected}, got {actual}")]
+	DimensionMismatch {
+		/// Index dimension
+		expected: usize,
+		/// Length of the rejected vector
+		actual: usize,
+	},
+
+	/// The id is already present in the index.
+	#[error("Duplicate id: {0}")]
+	DuplicateId(usize),
+}
+
+impl Classify for AnnError {
+	fn kind(&self) -> ErrorKind {
+		ErrorKind::InvalidInput
+	}
+}
+
+// ============================================================================
+// Index
+// ============================================================================
+
+/// A node visited during search, ordered by distance then slot.
+#[derive(Clone, Copy, Debug)]
+struct Candidate {
+	distance: f32,
+	slot: usize,
+}
+
+impl PartialEq for Candidate {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Candidate {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.distance
+			.total_cmp(&other.distance)
+			.then(self.slot.cmp(&other.slot))
+	}
+}
+
+/// HNSW index over `f32` embeddings, keyed by caller-chosen ids
+/// (typically memory indices).
+///
+/// Serializable with serde, so an index can be persisted and reloaded
+/// instead of rebuilt.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HnswIndex {
+	config: HnswConfig,
+	dimension: usize,
+	/// Stored vectors by slot (unit-normalized for cosine)
+	vectors: Vec<Vec<f32>>,
+	/// External id by slot
+	ids: Vec<usize>,
+	/// Neighbor lists by slot, then layer
+	neighbors: Vec<Vec<Vec<usize>>>,
+	/// Tombstones by slot
+	deleted: Vec<bool>,
+	/// Slot by live external id
+	slots: HashMap<usize, usize>,
+	entry_point: Option<usize>,
+	max_level: usize,
+	rng: SplitMix64,
+}
+
+impl HnswIndex {
+	/// Create an empty index for vectors of length `dimension`.
+	#[must_use]
+	pub fn new(dimension: usize, config: HnswConfig) -> Self {
+		let rng = SplitMix64::new(config.seed);
+		Self {
+			config,
+			dimension,
+			vectors: Vec::new(),
+			ids: Vec::new(),
+			neighbors: Vec::new(),
+			deleted: Vec::new(),
+			slots: HashMap::new(),
+			entry_point: None,
+			max_level: 0,
+			rng,
+		}
+	}
+
+	/// Vector length accepted by this index.
+	#[must_use]
+	pub const fn dimension(&self) -> usize {
+		self.dimension
+	}
+
+	/// Index configuration.
+	#[must_use]
+	pub const fn config(&self) -> &HnswConfig {
+		&self.config
+	}
+
+	/// Number of live (non-deleted) vectors.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.slots.len()
+	}
+
+	/// Whether the index has no live vectors.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.slots.is_empty()
+	}
+
+	/// Number of tombstoned vectors still held for routing.
+	#[must_use]
+	pub fn num_deleted(&self) -> usize {
+		self.vectors.len() - self.slots.len()
+	}
+
+	/// Whether `id` is live in the index.
+	#[must_use]
+	pub fn contains(&self, id: usize) -> bool {
+		self.slots.contains_key(&id)
+	}
+
+	/// Insert a vector under `id`.
+	///
+	/// # Errors
+	///
+	/// Returns [`AnnError::DimensionMismatch`] if the vector length differs
+	/// from the index dimension, or [`AnnError::DuplicateId`] if `id` is
+	/// already live (remove it first to replace it).
+	pub fn insert(&mut self, id: usize, vector: &[f32]) -> Result<(), AnnError> {
+		if vector.len() != self.dimension {
+			return Err(AnnError::DimensionMismatch {
+				expected: self.dimension,
+				actual: vector.len(),
+			});
+		}
+		if self.slots.contains_key(&id) {
+			return Err(AnnError::DuplicateId(id));
+		}
+
+		let query = self.prepare(vector);
+		let level = self.random_level();
+		let slot = self.vectors.len();
+		self.vectors.push(query.clone());
+		self.ids.push(id);
+		self.neighbors.push(vec![Vec::new(); level + 1]);
+		self.deleted.push(false);
+		let _ = self.slots.insert(id, slot);
+
+		let Some(mut entry) = self.entry_point else {
+			self.entry_point = Some(slot);
+			self.max_level = level;
+			return Ok(());
+		};
+
+		for layer in (level + 1..=self.max_level).rev() {
+			entry = self.greedy_closest(&query, entry, layer);
+		}
+
+		let mut entries = vec![entry];
+		for layer in (0..=level.min(self.max_level)).rev() {
+			let candidates =
+				self.search_layer(&query, &entries, self.config.ef_construction, layer);
+			let capacity = self.layer_capacity(layer);
+			let selected = self.select_neighbors(&candidates, capacity);
+
+			for &neighbor in &selected {
+				let links = &mut self.neighbors[neighbor][layer];
+				links.push(slot);
+				if links.len() > capacity {
+					self.shrink_links(neighbor, layer, capacity);
+				}
+			}
+			self.neighbors[slot][layer] = selected;
+			entries = candidates.iter().map(|c| c.slot).collect();
+		}
+
+		if level > self.max_level {
+			self.max_level = level;
+			self.entry_point = Some(slot);
+		}
+		Ok(())
+	}
+
+	/// Remove `id` from search results.
+	///
+	/// The vector stays in the graph as a routing node until
+	/// [`compact`](Self::compact). Returns `false` if `id` was not live.
+	pub fn remove(&mut self, id: usize) -> bool {
+		let Some(slot) = self.slots.remove(&id) else {
+			return false;
+		};
+		self.deleted[slot] = true;
+		true
+	}
+
+	/// The `k` live vectors most similar to `query`, as `(id, similarity)`.
+	///
+	/// Similarity matches [`crate::embeddings::similarity_batch`] for the
+	/// configured metric. Sorted by descending similarity. Returns an empty
+	/// list if the query length differs from the index dimension.
+	#[must_use]
+	pub fn search(&self, query: &[f32], k: usize) -> Vec<(usize, f32)> {
+		self.search_with_ef(query, k, self.config.ef_search)
+	}
+
+	/// [`search`](Self::search) with an explicit beam width.
+	///
+	/// Larger `ef` trades speed for recall.
+	#[must_use]
+	pub fn search_with_ef(&self, query: &[f32], k: usize, ef: usize) -> Vec<(usize, f32)> {
+		let Some(mut entry) = self.entry_point else {
+			return Vec::new();
+		};
+		if k == 0 || query.len() != self.dimension {
+			return Vec::new();
+		}
+
+		let query = self.prepare(query);
+		for layer in (1..=self.max_level).rev() {
+			entry = self.greedy_closest(&query, entry, layer);
+		}
+
+		self.search_layer(&query, &[entry], ef.max(k), 0)
+			.into_iter()
+			.filter(|c| !self.deleted[c.slot])
+			.take(k)
+			.map(|c| (self.ids[c.slot], self.similarity(c.distance)))
+			.collect()
+	}
+
+	/// Rebuild the index from its live vectors, dropping tombstones.
+	///
+	/// Stored vectors are reused as-is; for cosine they are already
+	/// normalized.
+	#[must_use]
+	pub fn compact(&self) -> Self {
+		let mut live: Vec<(usize, usize)> =
+			self.slots.iter().map(|(&id, &slot)| (id, slot)).collect();
+		live.sort_unstable_by_key(|&(_, slot)| slot);
+
+		let mut index = Self::new(self.dimension, self.config.clone());
+		for (id, slot) in live {
+			// Ids are unique and dimensions match, so insertion cannot fail
+			let _ = index.insert(id, &self.vectors[slot]);
+		}
+		index
+	}
+
+	// ------------------------------------------------------------------------
+	// Internals
+	// ------------------------------------------------------------------------
+
+	/// Normalize for cosine so distance reduces to a dot product.
+	fn prepare(&self, vector: &[f32]) -> Vec<f32> {
+		match self.config.metric {
+			Metric::Cosine => {
+				let magnitude = norm(vector);
+				if magnitude == 0.0 {
+					vector.to_vec()
+				} else {
+					vector.iter().map(|x| x / magnitude).collect()
+				}
+			}
+			Metric::Dot | Metric::Euclidean => vector.to_vec(),
+		}
+	}
+
+	/// Internal distance; lower is closer.
+	fn distance(&self, a: &[f32], b: &[f32]) -> f32 {
+		match self.config.metric {
+			Metric::Cosine => 1.0 - dot(a, b),
+			Metric::Dot => -dot(a, b),
+			Metric::Euclidean => squared_euclidean(a, b),
+		}
+	}
+
+	/// Convert an internal distance back to the public similarity scale.
+	fn similarity(&self, distance: f32) -> f32 {
+		match self.config.metric {
+			Metric::Cosine => 1.0 - distance,
+			Metric::Dot => -distance,
+			Metric::Euclidean => -distance.sqrt(),
+		}
+	}
+
+	fn random_level(&mut self) -> usize {
+		#[allow(clippy::cast_precision_loss)]
+		let level_mult = 1.0 / (self.config.m.max(2) as f64).ln();
+		// 1 - u lies in (0, 1], so the logarithm is finite
+		let u = 1.0 - self.rng.next_f64();
+		#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+		let level = (-u.ln() * level_mult).floor() as usize;
+		level.min(MAX_LEVEL)
+	}
+
+	const fn layer_capacity(&self, layer: usize) -> usize {
+		if layer == 0 {
+			self.config.m * 2
+		} else {
+			self.config.m
+		}
+	}
+
+	fn links(&self, slot: usize, layer: usize) -> &[usize] {
+		self.neighbors[slot].get(layer).map_or(&[], Vec::as_slice)
+	}
+
+	/// Greedy walk to the closest node on one layer.
+	fn greedy_closest(&self, query: &[f32], entry: usize, layer: usize) -> usize {
+		let mut current = entry;
+		let mut current_distance = self.distance(query, &self.vectors[entry]);
+		loop {
+			let mut improved = false;
+			for &neighbor in self.links(current, layer) {
+				let d = self.distance(query, &self.vectors[neighbor]);
+				if d < current_distance {
+					current = neighbor;
+					current_distance = d;
+					improved = true;
+				}
+			}
+			if !improved {
+				return current;
+			}
+		}
+	}
+
+	/// Beam search on one layer; returns up to `ef` nodes, closest first.
+	fn search_layer(
+		&self,
+		query: &[f32],
+		entries: &[usize],
+		ef: usize,
+		layer: usize,
+	) -> Vec<Candidate> {
+		let mut visited = vec![false; self.vectors.len()];
+		let mut frontier: BinaryHeap<Reverse<Candidate>> = BinaryHeap::new();
+		let mut nearest: BinaryHeap<Candidate> = BinaryHeap::new();
+
+		for &slot in entries {
+			if !visited[slot] {
+				visited[slot] = true;
+				let candidate = Candidate {
+					distance: self.distance(query, &self.vectors[slot]),
+					slot,
+				};
+				frontier.push(Reverse(candidate));
+				nearest.push(candidate);
+			}
+		}
+		while nearest.len() > ef {
+			let _ = nearest.pop();
+		}
+
+		while let Some(Reverse(current)) = frontier.pop() {
+			let furthest = nearest.peek().map_or(f32::INFINITY, |c| c.distance);
+			if nearest.len() >= ef && current.distance > furthest {
+				break;
+			}
+
+			for &neighbor in self.links(current.slot, layer) {
+				if visited[neighbor] {
+					continue;
+				}
+				visited[neighbor] = true;
+
+				let distance = self.distance(query, &self.vectors[neighbor]);
+				let furthest = nearest.peek().map_or(f32::INFINITY, |c| c.distance);
+				if nearest.len() < ef || distance < furthest {
+					let candidate = Candidate {
+						distance,
+						slot: neighbor,
+					};
+					frontier.push(Reverse(candidate));
+					nearest.push(candidate);
+					if nearest.len() > ef {
+						let _ = nearest.pop();
+					}
+				}
+			}
+		}
+
+		nearest.into_sorted_vec()
+	}
+
+	/// Diversity heuristic: keep a candidate only if it is closer to the
+	/// base node than to any neighbor already kept.
+	fn select_neighbors(&self, candidates: &[Candidate], capacity: usize) -> Vec<usize> {
+		let mut selected: Vec<usize> = Vec::with_capacity(capacity);
+		for candidate in candidates {
+			if selected.len() >= capacity {
+				break;
+			}
+			let vector = &self.vectors[candidate.slot];
+			let diverse = selected
+				.iter()
+				.all(|&kept| self.distance(vector, &self.vectors[kept]) >= candidate.distance);
+			if diverse {
+				selected.push(candidate.slot);
+			}
+		}
+		selected
+	}
+
+	/// Re-select a node's links on one layer after it exceeds capacity.
+	fn shrink_links(&mut self, slot: usize, layer: usize, capacity: usize) {
+		let base = &self.vectors[slot];
+		let mut candidates: Vec<Candidate> = self.neighbors[slot][layer]
+			.iter()
+			.map(|&neighbor| Candidate {
+				distance: self.distance(base, &self.vectors[neighbor]),
+				slot: neighbor,
+			})
+			.collect();
+		candidates.sort_unstable();
+		self.neighbors[slot][layer] = self.select_neighbors(&candidates, capacity);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::embeddings::top_k_similar;
+
+	#[allow(clippy::cast_possible_truncation)]
+	fn make_vectors(count: usize, dimension: usize, seed: u64) -> Vec<Vec<f32>> {
+		let mut rng = SplitMix64::new(seed);
+		(0..count)
+			.map(|_| {
+				(0..dimension)
+					.map(|_| (rng.next_f64() - 0.5) as f32)
+					.collect()
+			})
+			.collect()
+	}
+
+	fn build(vectors: &[Vec<f32>], metric: Metric) -> HnswIndex {
+		let config = HnswConfig {
+			metric,
+			..HnswConfig::default()
+		};
+		let mut index = HnswIndex::new(vectors[0].len(), config);
+		for (id, vector) in vectors.iter().enumerate() {
+			assert!(index.insert(id, vector).is_ok());
+		}
+		index
+	}
+
+	#[test]
+	fn test_hnsw_recall_matches_brute_force() {
+		let vectors = make_vectors(1000, 16, 1);
+		let queries = make_vectors(20, 16, 2);
+
+		for metric in [Metric::Cosine, Metric::Euclidean] {
+			let index = build(&vectors, metric);
+			let mut hits = 0;
+			for query in &queries {
+				let exact: Vec<usize> = top_k_similar(query, &vectors, 10, metric)
+					.into_iter()
+					.map(|(i, _)| i)
+					.collect();
+				let approx = index.search(query, 10);
+				assert_eq!(approx.len(), 10);
+				hits += approx.iter().filter(|(id, _)| exact.contains(id)).count();
+			}
+			// Recall@10 over 200 neighbors
+			assert!(hits >= 190, "{metric:?} recall {hits}/200");
+		}
+	}
+
+	#[test]
+	fn test_hnsw_similarity_scale() {
+		let vectors = vec![vec![1.0, 0.0], vec![0.0, 2.0], vec![-3.0, 0.0]];
+		let index = build(&vectors, Metric::Cosine);
+
+		let results = index.search(&[2.0, 0.0], 3);
+		let ids: Vec<usize> = results.iter().map(|(id, _)| *id).collect();
+		assert_eq!(ids, vec![0, 1, 2]);
+		assert!((results[0].1 - 1.0).abs() < 1e-6);
+		assert!((results[2].1 + 1.0).abs() < 1e-6);
+	}
+
+	#[test]
+	fn test_hnsw_remove_and_compact() {
+		let vectors = make_vectors(200, 8, 3);
+		let mut index = build(&vectors, Metric::Euclidean);
+
+		let nearest = index.search(&vectors[5], 1);
+		assert_eq!(nearest[0].0, 5);
+
+		assert!(index.remove(5));
+		assert!(!index.remove(5));
+		assert!(!index.contains(5));
+		assert_eq!((index.len(), index.num_deleted()), (199, 1));
+		assert!(index.search(&vectors[5], 10).iter().all(|(id, _)| *id != 5));
+
+		let compacted = index.compact();
+		assert_eq!((compacted.len(), compacted.num_deleted()), (199, 0));
+		assert_eq!(compacted.search(&vectors[7], 1)[0].0, 7);
+
+		// Removed ids can be reused
+		assert!(index.insert(5, &vectors[5]).is_ok());
+		assert_eq!(index.search(&vectors[5], 1)[0].0, 5);
+	}
+
+	#[test]
+	fn test_hnsw_rejects_bad_input() {
+		let mut index = HnswIndex::new(3, HnswConfig::default());
+		assert!(index.search(&[1.0, 0.0, 0.0], 5).is_empty());
+
+		assert!(index.insert(0, &[1.0, 0.0, 0.0]).is_ok());
+		assert!(matches!(
+			index.insert(1, &[1.0, 0.0]),
+			Err(AnnError::DimensionMismatch {
+				expected: 3,
+				actual: 2
+			})
+		));
+		assert!(matches!(
+			index.insert(0, &[0.0, 1.0, 0.0]),
+			Err(AnnError::DuplicateId(0))
+		));
+		assert!(index.search(&[1.0, 0.0], 5).is_empty());
+	}
+}
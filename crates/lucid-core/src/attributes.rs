@@ -0,0 +1,235 @@
+//! Property Graph Attributes
+//!
+//! Typed key/value attributes on nodes and edges, and predicates over
+//! them that restrict traversal.
+//!
+//! Attributes are attached to a prebuilt [`AssociationGraph`] with
+//! [`AssociationGraph::with_node_attributes`] and
+//! [`AssociationGraph::with_edge_attributes`]. A [`TraversalFilter`] is
+//! then checked edge by edge as spreading, path finding, and `PageRank`
+//! run, so a query such as "only links created after March" costs no
+//! copy of the graph. Results match running the same query on the
+//! subgraph the filter selects.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::graph::AssociationGraph;
+
+/// A typed attribute value.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AttributeValue {
+	/// Boolean flag
+	Bool(bool),
+	/// Signed integer
+	Int(i64),
+	/// Floating-point number (timestamps in ms, scores, ...)
+	Float(f64),
+	/// Text
+	Text(String),
+}
+
+impl AttributeValue {
+	/// The value as a boolean, if it is one.
+	#[must_use]
+	pub const fn as_bool(&self) -> Option<bool> {
+		match self {
+			Self::Bool(value) => Some(*value),
+			_ => None,
+		}
+	}
+
+	/// The value as an integer, if it is one.
+	#[must_use]
+	pub const fn as_i64(&self) -> Option<i64> {
+		match self {
+			Self::Int(value) => Some(*value),
+			_ => None,
+		}
+	}
+
+	/// The value as a float; integers are widened.
+	#[must_use]
+	pub const fn as_f64(&self) -> Option<f64> {
+		match self {
+			#[allow(clippy::cast_precision_loss)]
+			Self::Int(value) => Some(*value as f64),
+			Self::Float(value) => Some(*value),
+			_ => None,
+		}
+	}
+
+	/// The value as text, if it is text.
+	#[must_use]
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			Self::Text(value) => Some(value),
+			_ => None,
+		}
+	}
+}
+
+/// Attributes of one node or edge.
+pub type Attributes = HashMap<String, AttributeValue>;
+
+/// Attributes of nodes and edges that have none.
+pub(crate) static NO_ATTRIBUTES: LazyLock<Attributes> = LazyLock::new(HashMap::new);
+
+/// Predicate over the attributes of a node or edge.
+pub type AttributePredicate = dyn Fn(&Attributes) -> bool + Sync;
+
+/// Node and edge predicates applied during traversal.
+///
+/// A node or edge without attributes is tested against an empty map.
+/// The default filter admits everything.
+#[derive(Clone, Copy, Default)]
+pub struct TraversalFilter<'a> {
+	/// Nodes that may be entered
+	pub node: Option<&'a AttributePredicate>,
+	/// Edges that may be traversed
+	pub edge: Option<&'a AttributePredicate>,
+}
+
+impl std::fmt::Debug for TraversalFilter<'_> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("TraversalFilter")
+			.field("node", &self.node.is_some())
+			.field("edge", &self.edge.is_some())
+			.finish()
+	}
+}
+
+impl TraversalFilter<'_> {
+	/// Whether the filter admits everything.
+	#[inline]
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.node.is_none() && self.edge.is_none()
+	}
+
+	/// Whether `node` may be entered.
+	#[inline]
+	#[must_use]
+	pub fn allows_node(&self, graph: &AssociationGraph, node: usize) -> bool {
+		self.node
+			.is_none_or(|predicate| predicate(graph.node_attributes(node)))
+	}
+
+	/// Whether edge `edge` may be traversed.
+	#[inline]
+	#[must_use]
+	pub fn allows_edge(&self, graph: &AssociationGraph, edge: usize) -> bool {
+		self.edge
+			.is_none_or(|predicate| predicate(graph.edge_attributes(edge)))
+	}
+
+	/// Whether edge `edge` into `target` may be traversed.
+	#[inline]
+	#[must_use]
+	pub fn allows(&self, graph: &AssociationGraph, edge: usize, target: usize) -> bool {
+		self.allows_edge(graph, edge) && self.allows_node(graph, target)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::paths::{find_k_strongest_paths_filtered, find_strongest_path_filtered};
+	use crate::spreading::{
+		compute_pagerank_filtered, find_activation_path_filtered, spread_activation_filtered,
+		spread_activation_graph, Association, AssociationType, SpreadingConfig,
+	};
+
+	fn make_assoc(source: usize, target: usize, strength: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	fn created(ms: f64) -> Attributes {
+		Attributes::from([("created_ms".to_string(), AttributeValue::Float(ms))])
+	}
+
+	/// 0 → 1 → 3 (old links) and 0 → 2 → 3 (new links)
+	fn dated_graph() -> AssociationGraph {
+		let associations = vec![
+			make_assoc(0, 1, 0.9),
+			make_assoc(1, 3, 0.9),
+			make_assoc(0, 2, 0.5),
+			make_assoc(2, 3, 0.5),
+		];
+		AssociationGraph::new(4, &associations).with_edge_attributes(vec![
+			created(100.0),
+			created(100.0),
+			created(500.0),
+			created(500.0),
+		])
+	}
+
+	fn recent(attributes: &Attributes) -> bool {
+		attributes
+			.get("created_ms")
+			.and_then(AttributeValue::as_f64)
+			.is_some_and(|t| t > 200.0)
+	}
+
+	#[test]
+	fn test_filtered_spreading_matches_subgraph() {
+		let config = SpreadingConfig {
+			bidirectional: false,
+			..SpreadingConfig::default()
+		};
+		let filter = TraversalFilter {
+			edge: Some(&recent),
+			..TraversalFilter::default()
+		};
+		let filtered =
+			spread_activation_filtered(&dated_graph(), &[0], &[1.0], &config, 2, &filter);
+
+		let subgraph = AssociationGraph::new(4, &[make_assoc(0, 2, 0.5), make_assoc(2, 3, 0.5)]);
+		let expected = spread_activation_graph(&subgraph, &[0], &[1.0], &config, 2);
+
+		assert!(filtered.activations[1].abs() < f64::EPSILON);
+		for (a, b) in filtered.activations.iter().zip(&expected.activations) {
+			assert!((a - b).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn test_filtered_path_and_pagerank() {
+		let graph = dated_graph();
+		let filter = TraversalFilter {
+			edge: Some(&recent),
+			..TraversalFilter::default()
+		};
+
+		let path = find_activation_path_filtered(&graph, 0, 3, &filter);
+		assert_eq!(path.map(|p| p.nodes), Some(vec![0, 2, 3]));
+
+		// The strong old chain is skipped, not merely relabelled
+		let strongest = find_strongest_path_filtered(&graph, 0, 3, &filter);
+		assert!(strongest
+			.is_some_and(|p| p.nodes == vec![0, 2, 3] && (p.strength - 0.25).abs() < 1e-12));
+		let paths = find_k_strongest_paths_filtered(&graph, 0, 3, 5, &filter);
+		let nodes: Vec<Vec<usize>> = paths.into_iter().map(|p| p.nodes).collect();
+		assert_eq!(nodes, vec![vec![0, 2, 3]]);
+
+		let hidden = |attributes: &Attributes| !attributes.contains_key("hidden");
+		let graph = graph.with_node_attributes(vec![
+			Attributes::new(),
+			Attributes::from([("hidden".to_string(), AttributeValue::Bool(true))]),
+		]);
+		let filter = TraversalFilter {
+			node: Some(&hidden),
+			..TraversalFilter::default()
+		};
+		let ranks = compute_pagerank_filtered(&graph, 0.85, 20, &filter);
+		assert!(ranks[1].abs() < f64::EPSILON);
+		assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+	}
+}
@@ -0,0 +1,357 @@
+//! Graph Builder
+//!
+//! Assemble a graph in code with every association checked before the
+//! index is built. [`AssociationGraph::new`] drops associations whose
+//! endpoints are out of range without a word, which hides bugs in the
+//! code that produced them; [`GraphBuilder`] reports them instead.
+//!
+//! Each association is a record, numbered in the order it was added.
+//! Endpoints must name nodes already added, strengths must be finite and
+//! within the configured range, and parallel edges (the same source and
+//! target more than once) are merged per [`DuplicatePolicy`]. Errors name
+//! the record and field at fault.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::error::{Classify, ErrorKind};
+use crate::graph::{AssociationGraph, MemoryGraph};
+use crate::import::DuplicatePolicy;
+use crate::spreading::Association;
+
+/// Error type for graph building.
+#[derive(Debug, thiserror::Error)]
+pub enum GraphBuildError {
+	/// An endpoint names a node that was never added.
+	#[error("Association {record}: {field} {node} out of range (graph has {num_nodes} nodes)")]
+	NodeOutOfRange {
+		/// Position of the association among those added
+		record: usize,
+		/// `"source"` or `"target"`
+		field: &'static str,
+		/// Offending index
+		node: usize,
+		/// Nodes added
+		num_nodes: usize,
+	},
+
+	/// A strength is not finite or outside the allowed range.
+	#[error("Association {record}: {field} out of range: {value}")]
+	StrengthOutOfRange {
+		/// Position of the association among those added
+		record: usize,
+		/// `"forward_strength"` or `"backward_strength"`
+		field: &'static str,
+		/// The offending strength
+		value: f64,
+	},
+
+	/// A parallel edge under [`DuplicatePolicy::Reject`].
+	#[error("Association {record} duplicates association {first} ({from} -> {to})")]
+	DuplicateEdge {
+		/// Position of the rejected association
+		record: usize,
+		/// Position of the association it duplicates
+		first: usize,
+		/// Source node
+		from: usize,
+		/// Target node
+		to: usize,
+	},
+}
+
+impl Classify for GraphBuildError {
+	fn kind(&self) -> ErrorKind {
+		ErrorKind::InvalidInput
+	}
+}
+
+/// Validating builder for association graphs.
+///
+/// ```
+/// use lucid_core::{Association, AssociationType, GraphBuilder};
+///
+/// let mut builder = GraphBuilder::new();
+/// let nodes = builder.add_nodes(3);
+/// let _ = builder.add_association(Association {
+///     source: nodes.start,
+///     target: nodes.start + 1,
+///     forward_strength: 0.8,
+///     backward_strength: 0.4,
+///     association_type: AssociationType::Semantic,
+/// });
+///
+/// let graph = builder.build();
+/// assert!(graph.is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct GraphBuilder {
+	num_nodes: usize,
+	records: Vec<Association>,
+	duplicate_policy: DuplicatePolicy,
+	min_strength: f64,
+	max_strength: f64,
+}
+
+impl Default for GraphBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl GraphBuilder {
+	/// Create an empty builder.
+	///
+	/// Parallel edges are rejected and strengths must lie in `0..=1`
+	/// until configured otherwise.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			num_nodes: 0,
+			records: Vec::new(),
+			duplicate_policy: DuplicatePolicy::Reject,
+			min_strength: 0.0,
+			max_strength: 1.0,
+		}
+	}
+
+	/// Set how parallel edges are merged (default: `Reject`).
+	#[must_use]
+	pub const fn with_duplicate_policy(mut self, policy: DuplicatePolicy) -> Self {
+		self.duplicate_policy = policy;
+		self
+	}
+
+	/// Set the valid strength range, inclusive (default: `0.0..=1.0`).
+	///
+	/// `Sum` merges are capped at `max`.
+	#[must_use]
+	pub const fn with_strength_range(mut self, min: f64, max: f64) -> Self {
+		self.min_strength = min;
+		self.max_strength = max;
+		self
+	}
+
+	/// Nodes added so far.
+	#[inline]
+	#[must_use]
+	pub const fn num_nodes(&self) -> usize {
+		self.num_nodes
+	}
+
+	/// Associations added so far, before validation.
+	#[inline]
+	#[must_use]
+	pub fn num_records(&self) -> usize {
+		self.records.len()
+	}
+
+	/// Add a node and return its index.
+	pub const fn add_node(&mut self) -> usize {
+		self.num_nodes += 1;
+		self.num_nodes - 1
+	}
+
+	/// Add `count` nodes and return their indices.
+	pub const fn add_nodes(&mut self, count: usize) -> Range<usize> {
+		let start = self.num_nodes;
+		self.num_nodes += count;
+		start..self.num_nodes
+	}
+
+	/// Add an association and return its record number.
+	///
+	/// Nothing is checked until [`validate`](Self::validate) or a build,
+	/// so associations may be added before the nodes they link.
+	pub fn add_association(&mut self, association: Association) -> usize {
+		self.records.push(association);
+		self.records.len() - 1
+	}
+
+	/// Every problem with the records added so far, in record order.
+	#[must_use]
+	pub fn validate(&self) -> Vec<GraphBuildError> {
+		self.resolve().1
+	}
+
+	/// Build the query index.
+	///
+	/// # Errors
+	///
+	/// Returns the first [`GraphBuildError`] in record order; use
+	/// [`validate`](Self::validate) to see them all.
+	pub fn build(self) -> Result<AssociationGraph, GraphBuildError> {
+		let num_nodes = self.num_nodes;
+		let associations = self.into_associations()?;
+		Ok(AssociationGraph::new(num_nodes, &associations))
+	}
+
+	/// Build an editable [`MemoryGraph`].
+	///
+	/// # Errors
+	///
+	/// As [`build`](Self::build).
+	pub fn build_memory_graph(self) -> Result<MemoryGraph, GraphBuildError> {
+		let num_nodes = self.num_nodes;
+		let associations = self.into_associations()?;
+		Ok(MemoryGraph::from_associations(num_nodes, associations))
+	}
+
+	/// The validated associations, parallel edges merged, without building
+	/// a graph.
+	///
+	/// # Errors
+	///
+	/// As [`build`](Self::build).
+	pub fn into_associations(self) -> Result<Vec<Association>, GraphBuildError> {
+		let (associations, errors) = self.resolve();
+		errors.into_iter().next().map_or(Ok(associations), Err)
+	}
+
+	/// Validate every record and merge parallel edges.
+	fn resolve(&self) -> (Vec<Association>, Vec<GraphBuildError>) {
+		let mut associations: Vec<Association> = Vec::with_capacity(self.records.len());
+		// (source, target) → (first record, position in `associations`)
+		let mut lookup: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+		let mut errors = Vec::new();
+
+		for (record, association) in self.records.iter().enumerate() {
+			if let Err(error) = self.check(record, association) {
+				errors.push(error);
+				continue;
+			}
+			let key = (association.source, association.target);
+			let Some(&(first, kept)) = lookup.get(&key) else {
+				let _ = lookup.insert(key, (record, associations.len()));
+				associations.push(association.clone());
+				continue;
+			};
+			if !self.duplicate_policy.merge(
+				&mut associations[kept],
+				association.clone(),
+				self.max_strength,
+			) {
+				errors.push(GraphBuildError::DuplicateEdge {
+					record,
+					first,
+					from: key.0,
+					to: key.1,
+				});
+			}
+		}
+
+		(associations, errors)
+	}
+
+	fn check(&self, record: usize, association: &Association) -> Result<(), GraphBuildError> {
+		for (field, node) in [
+			("source", association.source),
+			("target", association.target),
+		] {
+			if node >= self.num_nodes {
+				return Err(GraphBuildError::NodeOutOfRange {
+					record,
+					field,
+					node,
+					num_nodes: self.num_nodes,
+				});
+			}
+		}
+		for (field, value) in [
+			("forward_strength", association.forward_strength),
+			("backward_strength", association.backward_strength),
+		] {
+			if !(value.is_finite() && (self.min_strength..=self.max_strength).contains(&value)) {
+				return Err(GraphBuildError::StrengthOutOfRange {
+					record,
+					field,
+					value,
+				});
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::AssociationType;
+
+	fn make_assoc(source: usize, target: usize, forward: f64, backward: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: forward,
+			backward_strength: backward,
+			association_type: AssociationType::default(),
+		}
+	}
+
+	#[test]
+	fn test_builder_reports_record_and_field() {
+		let mut builder = GraphBuilder::new();
+		let _ = builder.add_nodes(3);
+		let _ = builder.add_association(make_assoc(0, 1, 0.5, 0.5));
+		let _ = builder.add_association(make_assoc(1, 3, 0.5, 0.5));
+		let _ = builder.add_association(make_assoc(1, 2, 0.5, f64::NAN));
+		let _ = builder.add_association(make_assoc(0, 1, 0.9, 0.1));
+
+		let errors = builder.validate();
+		assert_eq!(errors.len(), 3);
+		assert!(matches!(
+			errors[0],
+			GraphBuildError::NodeOutOfRange {
+				record: 1,
+				field: "target",
+				node: 3,
+				num_nodes: 3
+			}
+		));
+		assert!(matches!(
+			errors[1],
+			GraphBuildError::StrengthOutOfRange {
+				record: 2,
+				field: "backward_strength",
+				..
+			}
+		));
+		assert!(matches!(
+			errors[2],
+			GraphBuildError::DuplicateEdge {
+				record: 3,
+				first: 0,
+				from: 0,
+				to: 1
+			}
+		));
+
+		let result = builder.build();
+		assert!(matches!(
+			result,
+			Err(GraphBuildError::NodeOutOfRange { record: 1, .. })
+		));
+	}
+
+	#[test]
+	fn test_builder_merges_duplicates_by_policy() {
+		let mut builder = GraphBuilder::new()
+			.with_duplicate_policy(DuplicatePolicy::Sum)
+			.with_strength_range(0.0, 2.0);
+		let a = builder.add_node();
+		let b = builder.add_node();
+		let _ = builder.add_association(make_assoc(a, b, 1.5, 0.25));
+		let _ = builder.add_association(make_assoc(a, b, 1.0, 0.5));
+		assert!(builder.validate().is_empty());
+
+		let result = builder.build_memory_graph();
+		assert!(result.is_ok());
+		let Ok(graph) = result else { return };
+		assert_eq!(graph.num_nodes(), 2);
+		assert_eq!(graph.num_edges(), 1);
+		let strengths = graph
+			.association(a, b)
+			.map(|assoc| (assoc.forward_strength, assoc.backward_strength));
+		assert_eq!(strengths, Some((2.0, 0.75)));
+	}
+}
@@ -0,0 +1,1029 @@
+//! Graph Centrality
+//!
+//! Node-importance measures over the association graph, complementing
+//! [`crate::spreading::compute_pagerank`].
+//!
+//! - **Betweenness** counts how many shortest paths pass through a node.
+//!   High-betweenness memories are bridges between otherwise separate
+//!   clusters; pruning them disconnects the graph.
+//! - **Closeness** is the inverse mean distance from a node to everything
+//!   it can reach.
+//! - **HITS** separates *hubs*, which point at many good memories (an
+//!   index of episodes), from *authorities*, which many good hubs point
+//!   at. `PageRank` folds both roles into one score.
+//! - **Eigenvector** centrality scores a node by the scores of the nodes
+//!   linking to it. Without `PageRank`'s teleportation, score pools inside
+//!   densely interlinked clusters, picking out their core concepts.
+//! - **Personalized `PageRank`** ranks memories by relevance to a set of
+//!   query nodes, approximated by local push so work scales with the
+//!   neighborhood touched rather than the whole graph.
+//! - **Random walk with restart** samples the same relevance by simulation:
+//!   an anytime estimate for very large graphs, and a source of varied
+//!   "related memories" when resurfacing something unexpected.
+//!
+//! Paths follow forward edges. Unweighted measures count hops; weighted
+//! measures use [`edge_cost`], so a strong chain of associations counts as
+//! "closer" than one weak direct link.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::graph::AssociationGraph;
+use crate::rng::SplitMix64;
+
+/// Tolerance when comparing weighted path lengths for equality.
+const COST_EPSILON: f64 = 1e-12;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Configuration for path-based centrality.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CentralityConfig {
+	/// Measure path length by [`edge_cost`] instead of hop count (default: false)
+	pub weighted: bool,
+	/// Scale betweenness by `1 / ((n-1)(n-2))` into 0-1 (default: true)
+	pub normalized: bool,
+}
+
+impl Default for CentralityConfig {
+	fn default() -> Self {
+		Self {
+			weighted: false,
+			normalized: true,
+		}
+	}
+}
+
+/// Traversal cost of an edge with the given strength.
+///
+/// `cost = -ln(strength)`
+///
+/// Summing costs multiplies strengths, so the cheapest path is the one
+/// with the highest product of strengths. Strengths above 1.0 cost 0.0;
+/// non-positive strengths are not traversable and return `None`.
+#[inline]
+#[must_use]
+pub fn edge_cost(strength: f64) -> Option<f64> {
+	(strength > 0.0).then(|| -strength.min(1.0).ln())
+}
+
+/// Min-heap entry ordered by ascending cost, then node index.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MinCost {
+	pub(crate) cost: f64,
+	pub(crate) node: usize,
+}
+
+impl PartialEq for MinCost {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for MinCost {}
+
+impl PartialOrd for MinCost {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for MinCost {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// Reversed so BinaryHeap pops the cheapest entry first
+		other
+			.cost
+			.total_cmp(&self.cost)
+			.then(other.node.cmp(&self.node))
+	}
+}
+
+// ============================================================================
+// Single-Source Shortest Paths
+// ============================================================================
+
+/// Shortest-path DAG from one source.
+struct ShortestPaths {
+	/// Reached nodes in non-decreasing distance order
+	order: Vec<usize>,
+	/// Predecessors on shortest paths
+	predecessors: Vec<Vec<usize>>,
+	/// Number of shortest paths from the source
+	path_counts: Vec<f64>,
+	/// Distance from the source (`INFINITY` if unreachable)
+	distances: Vec<f64>,
+}
+
+fn shortest_paths(graph: &AssociationGraph, source: usize, weighted: bool) -> ShortestPaths {
+	let num_nodes = graph.num_nodes();
+	let mut paths = ShortestPaths {
+		order: Vec::new(),
+		predecessors: vec![Vec::new(); num_nodes],
+		path_counts: vec![0.0; num_nodes],
+		distances: vec![f64::INFINITY; num_nodes],
+	};
+	paths.path_counts[source] = 1.0;
+	paths.distances[source] = 0.0;
+
+	if weighted {
+		let mut settled = vec![false; num_nodes];
+		let mut heap = BinaryHeap::new();
+		heap.push(MinCost {
+			cost: 0.0,
+			node: source,
+		});
+
+		while let Some(MinCost { cost, node }) = heap.pop() {
+			if settled[node] {
+				continue;
+			}
+			settled[node] = true;
+			paths.order.push(node);
+
+			for (target, strength) in graph.forward(node).iter() {
+				let Some(step) = edge_cost(strength) else {
+					continue;
+				};
+				let candidate = cost + step;
+				let known = paths.distances[target];
+				if candidate < known - COST_EPSILON {
+					paths.distances[target] = candidate;
+					paths.path_counts[target] = paths.path_counts[node];
+					paths.predecessors[target].clear();
+					paths.predecessors[target].push(node);
+					heap.push(MinCost {
+						cost: candidate,
+						node: target,
+					});
+				} else if !settled[target] && (candidate - known).abs() <= COST_EPSILON {
+					paths.path_counts[target] += paths.path_counts[node];
+					paths.predecessors[target].push(node);
+				}
+			}
+		}
+	} else {
+		let mut queue = VecDeque::new();
+		queue.push_back(source);
+
+		while let Some(node) = queue.pop_front() {
+			paths.order.push(node);
+			let next = paths.distances[node] + 1.0;
+
+			for &target in graph.forward(node).targets {
+				if paths.distances[target].is_infinite() {
+					paths.distances[target] = next;
+					queue.push_back(target);
+				}
+				if (paths.distances[target] - next).abs() < f64::EPSILON {
+					paths.path_counts[target] += paths.path_counts[node];
+					paths.predecessors[target].push(node);
+				}
+			}
+		}
+	}
+
+	paths
+}
+
+// ============================================================================
+// Betweenness
+// ============================================================================
+
+/// Dependency of `source` on every node (Brandes, 2001).
+///
+/// `δ(v) = Σ_{w : v ∈ pred(w)} (σ_v / σ_w) × (1 + δ(w))`
+fn source_dependencies(graph: &AssociationGraph, source: usize, weighted: bool) -> Vec<f64> {
+	let paths = shortest_paths(graph, source, weighted);
+	let mut dependency = vec![0.0; graph.num_nodes()];
+
+	for &node in paths.order.iter().rev() {
+		let share = (1.0 + dependency[node]) / paths.path_counts[node];
+		for &predecessor in &paths.predecessors[node] {
+			dependency[predecessor] += paths.path_counts[predecessor] * share;
+		}
+	}
+	dependency[source] = 0.0;
+	dependency
+}
+
+fn add_scores(mut a: Vec<f64>, b: Vec<f64>) -> Vec<f64> {
+	for (x, y) in a.iter_mut().zip(b) {
+		*x += y;
+	}
+	a
+}
+
+/// Betweenness centrality of every node (Brandes' algorithm).
+///
+/// `C_B(v) = Σ_{s ≠ v ≠ t} σ_st(v) / σ_st`
+///
+/// Where `σ_st` is the number of shortest paths from `s` to `t` and
+/// `σ_st(v)` the number passing through `v`. Runs in `O(V × E)` for
+/// unweighted paths and `O(V × E log V)` for weighted; with the `parallel`
+/// feature, sources are processed across threads.
+#[must_use]
+pub fn betweenness_centrality(graph: &AssociationGraph, config: &CentralityConfig) -> Vec<f64> {
+	let num_nodes = graph.num_nodes();
+
+	#[cfg(feature = "parallel")]
+	let mut scores = {
+		use rayon::prelude::*;
+		(0..num_nodes)
+			.into_par_iter()
+			.map(|source| source_dependencies(graph, source, config.weighted))
+			.reduce(|| vec![0.0; num_nodes], add_scores)
+	};
+
+	#[cfg(not(feature = "parallel"))]
+	let mut scores = (0..num_nodes)
+		.map(|source| source_dependencies(graph, source, config.weighted))
+		.fold(vec![0.0; num_nodes], add_scores);
+
+	if config.normalized && num_nodes > 2 {
+		#[allow(clippy::cast_precision_loss)]
+		let pairs = ((num_nodes - 1) * (num_nodes - 2)) as f64;
+		for score in &mut scores {
+			*score /= pairs;
+		}
+	}
+	scores
+}
+
+// ============================================================================
+// Closeness
+// ============================================================================
+
+/// Closeness centrality of every node.
+///
+/// `C(u) = (r / (n - 1)) × (r / Σ_v d(u, v))`
+///
+/// Where `r` is the number of nodes reachable from `u`. The first factor
+/// (Wasserman & Faust, 1994) keeps nodes in small components from
+/// outranking well-connected ones. Nodes that reach nothing score 0.0.
+/// Always normalized to 0-1; `config.normalized` is ignored.
+#[must_use]
+pub fn closeness_centrality(graph: &AssociationGraph, config: &CentralityConfig) -> Vec<f64> {
+	let num_nodes = graph.num_nodes();
+	if num_nodes < 2 {
+		return vec![0.0; num_nodes];
+	}
+	#[allow(clippy::cast_precision_loss)]
+	let others = (num_nodes - 1) as f64;
+
+	(0..num_nodes)
+		.map(|source| {
+			let paths = shortest_paths(graph, source, config.weighted);
+			let total: f64 = paths.order.iter().map(|&v| paths.distances[v]).sum();
+			#[allow(clippy::cast_precision_loss)]
+			let reachable = (paths.order.len() - 1) as f64;
+			if total > 0.0 {
+				(reachable / total) * (reachable / others)
+			} else {
+				0.0
+			}
+		})
+		.collect()
+}
+
+// ============================================================================
+// HITS
+// ============================================================================
+
+/// Configuration for HITS.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HitsConfig {
+	/// Maximum power iterations (default: 100)
+	pub max_iterations: usize,
+	/// Stop when the L1 change of both vectors falls below this (default: 1e-8)
+	pub tolerance: f64,
+	/// Weight links by forward strength instead of counting them (default: true)
+	pub weighted: bool,
+}
+
+impl Default for HitsConfig {
+	fn default() -> Self {
+		Self {
+			max_iterations: 100,
+			tolerance: 1e-8,
+			weighted: true,
+		}
+	}
+}
+
+/// Hub and authority scores from [`hits`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HitsScores {
+	/// Hub score per node (sums to 1.0)
+	pub hubs: Vec<f64>,
+	/// Authority score per node (sums to 1.0)
+	pub authorities: Vec<f64>,
+	/// Power iterations performed
+	pub iterations: usize,
+}
+
+/// Scale to unit sum; all-zero vectors are left unchanged.
+fn normalize_sum(scores: &mut [f64]) {
+	let total: f64 = scores.iter().sum();
+	if total > 0.0 {
+		for score in scores {
+			*score /= total;
+		}
+	}
+}
+
+/// Hub and authority scores (Kleinberg, 1999).
+///
+/// `a(v) = Σ_{u → v} w_uv × h(u)`
+/// `h(u) = Σ_{u → v} w_uv × a(v)`
+///
+/// Iterated from uniform scores, normalizing both vectors each step.
+/// Nodes without outgoing links have hub score 0; nodes without incoming
+/// links have authority score 0.
+#[must_use]
+pub fn hits(graph: &AssociationGraph, config: &HitsConfig) -> HitsScores {
+	let num_nodes = graph.num_nodes();
+	#[allow(clippy::cast_precision_loss)]
+	let uniform = if num_nodes == 0 {
+		0.0
+	} else {
+		1.0 / num_nodes as f64
+	};
+	let weight = |strength: f64| if config.weighted { strength } else { 1.0 };
+
+	let mut hubs = vec![uniform; num_nodes];
+	let mut authorities = vec![uniform; num_nodes];
+	let mut iterations = 0;
+
+	while iterations < config.max_iterations {
+		iterations += 1;
+
+		let mut next_authorities = vec![0.0; num_nodes];
+		for (source, &hub) in hubs.iter().enumerate() {
+			for (target, strength) in graph.forward(source).iter() {
+				next_authorities[target] += weight(strength) * hub;
+			}
+		}
+		normalize_sum(&mut next_authorities);
+
+		let mut next_hubs: Vec<f64> = (0..num_nodes)
+			.map(|source| {
+				graph
+					.forward(source)
+					.iter()
+					.map(|(target, strength)| weight(strength) * next_authorities[target])
+					.sum()
+			})
+			.collect();
+		normalize_sum(&mut next_hubs);
+
+		let change: f64 = hubs
+			.iter()
+			.zip(&next_hubs)
+			.chain(authorities.iter().zip(&next_authorities))
+			.map(|(old, new)| (old - new).abs())
+			.sum();
+		hubs = next_hubs;
+		authorities = next_authorities;
+		if change < config.tolerance {
+			break;
+		}
+	}
+
+	HitsScores {
+		hubs,
+		authorities,
+		iterations,
+	}
+}
+
+// ============================================================================
+// Eigenvector Centrality
+// ============================================================================
+
+/// Configuration for eigenvector centrality.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EigenvectorConfig {
+	/// Maximum power iterations (default: 100)
+	pub max_iterations: usize,
+	/// Stop when the L1 change of the scores falls below this (default: 1e-8)
+	pub tolerance: f64,
+	/// Weight links by forward strength instead of counting them (default: true)
+	pub weighted: bool,
+}
+
+impl Default for EigenvectorConfig {
+	fn default() -> Self {
+		Self {
+			max_iterations: 100,
+			tolerance: 1e-8,
+			weighted: true,
+		}
+	}
+}
+
+/// Scores from [`eigenvector_centrality`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EigenvectorScores {
+	/// Centrality per node (sums to 1.0)
+	pub scores: Vec<f64>,
+	/// Power iterations performed
+	pub iterations: usize,
+	/// Whether the change fell below the tolerance
+	pub converged: bool,
+}
+
+/// Eigenvector centrality by power iteration.
+///
+/// `x(v) = (1 / λ) × Σ_{u → v} w_uv × x(u)`
+///
+/// Iterated from uniform scores as `x ← x + Aᵀx`, normalized each step.
+/// The added `x` shifts every eigenvalue by 1, which leaves the ranking
+/// unchanged but stops the oscillation plain iteration shows on
+/// bipartite or cyclic graphs. Negative strengths count as 0.
+#[must_use]
+pub fn eigenvector_centrality(
+	graph: &AssociationGraph,
+	config: &EigenvectorConfig,
+) -> EigenvectorScores {
+	let num_nodes = graph.num_nodes();
+	#[allow(clippy::cast_precision_loss)]
+	let uniform = if num_nodes == 0 {
+		0.0
+	} else {
+		1.0 / num_nodes as f64
+	};
+	let weight = |strength: f64| {
+		if config.weighted {
+			strength.max(0.0)
+		} else {
+			1.0
+		}
+	};
+
+	let mut scores = vec![uniform; num_nodes];
+	let mut iterations = 0;
+	let mut converged = false;
+
+	while iterations < config.max_iterations {
+		iterations += 1;
+
+		let mut next = scores.clone();
+		for (source, &score) in scores.iter().enumerate() {
+			for (target, strength) in graph.forward(source).iter() {
+				next[target] += weight(strength) * score;
+			}
+		}
+		normalize_sum(&mut next);
+
+		let change: f64 = scores
+			.iter()
+			.zip(&next)
+			.map(|(old, new)| (old - new).abs())
+			.sum();
+		scores = next;
+		if change < config.tolerance {
+			converged = true;
+			break;
+		}
+	}
+
+	EigenvectorScores {
+		scores,
+		iterations,
+		converged,
+	}
+}
+
+// ============================================================================
+// Personalized PageRank (Forward Push)
+// ============================================================================
+
+/// Configuration for approximate personalized `PageRank`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PersonalizedPageRankConfig {
+	/// Probability of following a link rather than restarting at the seeds (default: 0.85)
+	pub damping: f64,
+	/// Residual tolerance per unit of out-degree; smaller is more accurate (default: 1e-6)
+	pub epsilon: f64,
+	/// Safety cap on push operations (default: 10,000,000)
+	pub max_pushes: usize,
+}
+
+impl Default for PersonalizedPageRankConfig {
+	fn default() -> Self {
+		Self {
+			damping: 0.85,
+			epsilon: 1e-6,
+			max_pushes: 10_000_000,
+		}
+	}
+}
+
+/// Result of [`personalized_pagerank`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PersonalizedPageRank {
+	/// Approximate score per node (underestimates; sums to `1 - residual`)
+	pub scores: Vec<f64>,
+	/// Probability mass not yet pushed; bounds the total L1 error
+	pub residual: f64,
+	/// Push operations performed
+	pub pushes: usize,
+}
+
+/// Approximate personalized `PageRank` by forward push
+/// (Andersen, Chung & Lang, 2006).
+///
+/// `π = (1 - d) × s + d × π P`
+///
+/// Where `s` is the seed distribution and `P` moves along forward edges in
+/// proportion to strength. Each push settles a `(1 - d)` share of a node's
+/// residual mass into its score and spreads the rest to its neighbors.
+/// Pushing stops once every node's residual is below `ε × out_degree`,
+/// so only the region around the seeds is touched. Mass reaching a node
+/// with no outgoing links restarts at the seeds.
+///
+/// # Arguments
+///
+/// * `graph` - Association graph
+/// * `seeds` - Query nodes with weights; normalized to sum 1. Out-of-range
+///   and non-positive entries are ignored.
+/// * `config` - Damping and tolerance
+#[must_use]
+pub fn personalized_pagerank(
+	graph: &AssociationGraph,
+	seeds: &[(usize, f64)],
+	config: &PersonalizedPageRankConfig,
+) -> PersonalizedPageRank {
+	let num_nodes = graph.num_nodes();
+	let mut scores = vec![0.0; num_nodes];
+	let mut residual = vec![0.0; num_nodes];
+
+	let valid: Vec<(usize, f64)> = seeds
+		.iter()
+		.copied()
+		.filter(|&(node, weight)| node < num_nodes && weight > 0.0)
+		.collect();
+	let seed_total: f64 = valid.iter().map(|&(_, w)| w).sum();
+	if seed_total <= 0.0 {
+		return PersonalizedPageRank {
+			scores,
+			residual: 0.0,
+			pushes: 0,
+		};
+	}
+	let restart: Vec<(usize, f64)> = valid
+		.iter()
+		.map(|&(node, weight)| (node, weight / seed_total))
+		.collect();
+
+	let damping = config.damping.clamp(0.0, 1.0);
+	let out_weight: Vec<f64> = (0..num_nodes)
+		.map(|node| graph.forward(node).strengths.iter().sum())
+		.collect();
+	#[allow(clippy::cast_precision_loss)]
+	let threshold: Vec<f64> = (0..num_nodes)
+		.map(|node| config.epsilon * graph.forward(node).len().max(1) as f64)
+		.collect();
+
+	let mut queue = VecDeque::new();
+	let mut queued = vec![false; num_nodes];
+	let enqueue =
+		|node: usize, residual: &[f64], queue: &mut VecDeque<usize>, queued: &mut [bool]| {
+			if !queued[node] && residual[node] > threshold[node] {
+				queued[node] = true;
+				queue.push_back(node);
+			}
+		};
+
+	for &(node, mass) in &restart {
+		residual[node] += mass;
+	}
+	for &(node, _) in &restart {
+		enqueue(node, &residual, &mut queue, &mut queued);
+	}
+
+	let mut pushes = 0;
+	while let Some(node) = queue.pop_front() {
+		if pushes >= config.max_pushes {
+			break;
+		}
+		pushes += 1;
+		queued[node] = false;
+
+		let mass = std::mem::take(&mut residual[node]);
+		scores[node] += (1.0 - damping) * mass;
+		let spread = damping * mass;
+
+		if out_weight[node] > 0.0 {
+			for (target, strength) in graph.forward(node).iter() {
+				residual[target] += spread * strength / out_weight[node];
+				enqueue(target, &residual, &mut queue, &mut queued);
+			}
+		} else {
+			for &(seed, share) in &restart {
+				residual[seed] += spread * share;
+				enqueue(seed, &residual, &mut queue, &mut queued);
+			}
+		}
+	}
+
+	PersonalizedPageRank {
+		scores,
+		residual: residual.iter().sum(),
+		pushes,
+	}
+}
+
+// ============================================================================
+// Random Walk with Restart
+// ============================================================================
+
+/// Configuration for [`random_walk_with_restart`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RandomWalkConfig {
+	/// Probability of jumping back to a seed at each step (default: 0.15)
+	pub restart_probability: f64,
+	/// Steps to simulate; more steps give a closer estimate (default: 10,000)
+	pub num_steps: usize,
+	/// Choose the next node by forward strength instead of uniformly (default: true)
+	pub weighted: bool,
+	/// Random seed; the same seed yields the same walk (default: 0)
+	pub seed: u64,
+}
+
+impl Default for RandomWalkConfig {
+	fn default() -> Self {
+		Self {
+			restart_probability: 0.15,
+			num_steps: 10_000,
+			weighted: true,
+			seed: 0,
+		}
+	}
+}
+
+/// Visit statistics from [`random_walk_with_restart`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RandomWalkSample {
+	/// Times each node was visited
+	pub visits: Vec<usize>,
+	/// Share of steps spent at each node (sums to 1.0 after any steps)
+	pub frequencies: Vec<f64>,
+	/// Steps simulated
+	pub steps: usize,
+}
+
+/// Pick an index from `(index, weight)` pairs in proportion to weight.
+fn sample_weighted(
+	rng: &mut SplitMix64,
+	choices: impl Iterator<Item = (usize, f64)>,
+	total: f64,
+) -> Option<usize> {
+	let mut remaining = rng.next_f64() * total;
+	let mut last = None;
+	for (index, weight) in choices {
+		if weight <= 0.0 {
+			continue;
+		}
+		if remaining < weight {
+			return Some(index);
+		}
+		remaining -= weight;
+		last = Some(index);
+	}
+	// Rounding can leave a sliver past the final weight
+	last
+}
+
+/// Estimate personalized relevance by simulating a random walk.
+///
+/// Each step the walker jumps back to a seed with probability
+/// `restart_probability`, and otherwise follows a forward edge. A node
+/// without outgoing links also sends it back to a seed. Visit
+/// frequencies converge to personalized `PageRank` with damping
+/// `1 - restart_probability`, and can be read at any step count.
+///
+/// # Arguments
+///
+/// * `graph` - Association graph
+/// * `seeds` - Restart nodes with weights. Out-of-range and non-positive
+///   entries are ignored.
+/// * `config` - Restart probability, step count and seed
+///
+/// # Returns
+///
+/// Visit counts and frequencies; all zero when no seed is valid.
+#[must_use]
+pub fn random_walk_with_restart(
+	graph: &AssociationGraph,
+	seeds: &[(usize, f64)],
+	config: &RandomWalkConfig,
+) -> RandomWalkSample {
+	let num_nodes = graph.num_nodes();
+	let mut visits = vec![0; num_nodes];
+
+	let valid: Vec<(usize, f64)> = seeds
+		.iter()
+		.copied()
+		.filter(|&(node, weight)| node < num_nodes && weight > 0.0)
+		.collect();
+	let seed_total: f64 = valid.iter().map(|&(_, w)| w).sum();
+	if seed_total <= 0.0 {
+		return RandomWalkSample {
+			visits,
+			frequencies: vec![0.0; num_nodes],
+			steps: 0,
+		};
+	}
+
+	let mut rng = SplitMix64::new(config.seed);
+	let weight = |strength: f64| {
+		if config.weighted {
+			strength.max(0.0)
+		} else {
+			1.0
+		}
+	};
+	let restart = |rng: &mut SplitMix64| {
+		sample_weighted(rng, valid.iter().copied(), seed_total).unwrap_or(valid[0].0)
+	};
+
+	let mut current = restart(&mut rng);
+	for _ in 0..config.num_steps {
+		let neighbors = graph.forward(current);
+		let total: f64 = neighbors.strengths.iter().map(|&s| weight(s)).sum();
+		current = if total <= 0.0 || rng.next_f64() < config.restart_probability {
+			restart(&mut rng)
+		} else {
+			let choices = neighbors
+				.iter()
+				.map(|(target, strength)| (target, weight(strength)));
+			sample_weighted(&mut rng, choices, total).unwrap_or(current)
+		};
+		visits[current] += 1;
+	}
+
+	#[allow(clippy::cast_precision_loss)]
+	let frequencies = visits
+		.iter()
+		.map(|&count| {
+			if config.num_steps == 0 {
+				0.0
+			} else {
+				count as f64 / config.num_steps as f64
+			}
+		})
+		.collect();
+
+	RandomWalkSample {
+		visits,
+		frequencies,
+		steps: config.num_steps,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::{Association, AssociationType};
+
+	fn make_assoc(source: usize, target: usize, strength: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	/// Both directions, since paths follow forward edges.
+	fn make_link(a: usize, b: usize, strength: f64) -> [Association; 2] {
+		[make_assoc(a, b, strength), make_assoc(b, a, strength)]
+	}
+
+	#[test]
+	fn test_betweenness_chain() {
+		// 0 → 1 → 2: only node 1 lies between a pair
+		let graph = AssociationGraph::new(3, &[make_assoc(0, 1, 0.5), make_assoc(1, 2, 0.5)]);
+		let raw = betweenness_centrality(
+			&graph,
+			&CentralityConfig {
+				normalized: false,
+				..CentralityConfig::default()
+			},
+		);
+		assert_eq!(raw, vec![0.0, 1.0, 0.0]);
+
+		let normalized = betweenness_centrality(&graph, &CentralityConfig::default());
+		assert!((normalized[1] - 0.5).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_betweenness_finds_bridges() {
+		// Two triangles {0,1,2} and {3,4,5} bridged by 2 — 3
+		let mut associations = Vec::new();
+		for (a, b) in [(0, 1), (1, 2), (0, 2), (3, 4), (4, 5), (3, 5), (2, 3)] {
+			associations.extend(make_link(a, b, 0.8));
+		}
+		let graph = AssociationGraph::new(6, &associations);
+		let scores = betweenness_centrality(&graph, &CentralityConfig::default());
+
+		let max = scores.iter().copied().fold(0.0, f64::max);
+		assert!((scores[2] - max).abs() < 1e-12);
+		assert!((scores[3] - max).abs() < 1e-12);
+		assert!(scores[0].abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_weighted_prefers_strong_chain() {
+		// Weak direct link 0 → 2 versus strong chain 0 → 1 → 2
+		let graph = AssociationGraph::new(
+			3,
+			&[
+				make_assoc(0, 2, 0.1),
+				make_assoc(0, 1, 0.9),
+				make_assoc(1, 2, 0.9),
+			],
+		);
+		let config = CentralityConfig {
+			normalized: false,
+			..CentralityConfig::default()
+		};
+		assert!(betweenness_centrality(&graph, &config)[1].abs() < 1e-12);
+
+		let weighted = CentralityConfig {
+			weighted: true,
+			..config
+		};
+		assert!((betweenness_centrality(&graph, &weighted)[1] - 1.0).abs() < 1e-12);
+
+		assert!(edge_cost(0.0).is_none());
+		assert!(edge_cost(1.5).is_some_and(|c| c.abs() < f64::EPSILON));
+	}
+
+	#[test]
+	fn test_closeness() {
+		// Star: hub 0 linked both ways to 1..=3
+		let mut associations = Vec::new();
+		for leaf in 1..=3 {
+			associations.extend(make_link(0, leaf, 0.5));
+		}
+		associations.push(make_assoc(4, 0, 0.5));
+		let graph = AssociationGraph::new(6, &associations);
+		let scores = closeness_centrality(&graph, &CentralityConfig::default());
+
+		// Hub reaches 3 of 5 others at distance 1
+		assert!((scores[0] - 0.6).abs() < 1e-12);
+		assert!(scores[0] > scores[1]);
+		assert!(scores[5].abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn test_hits_separates_hubs_and_authorities() {
+		// 0 and 4 index episodes 1-3; nothing links back
+		let graph = AssociationGraph::new(
+			5,
+			&[
+				make_assoc(0, 1, 0.8),
+				make_assoc(0, 2, 0.8),
+				make_assoc(0, 3, 0.8),
+				make_assoc(4, 1, 0.8),
+				make_assoc(4, 2, 0.8),
+			],
+		);
+		let scores = hits(&graph, &HitsConfig::default());
+
+		assert!(scores.iterations < 100);
+		assert!((scores.hubs.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+		assert!(scores.hubs[0] > scores.hubs[4]);
+		assert!(scores.hubs[1].abs() < f64::EPSILON);
+
+		assert!(scores.authorities[1] > scores.authorities[3]);
+		assert!((scores.authorities[1] - scores.authorities[2]).abs() < 1e-9);
+		assert!(scores.authorities[0].abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn test_eigenvector_centrality_finds_cluster_core() {
+		// Triangle 0-1-2 with 3 hanging off 1, plus a 4-5 pair
+		let mut associations = Vec::new();
+		for (a, b) in [(0, 1), (1, 2), (2, 0), (1, 3), (4, 5)] {
+			associations.extend(make_link(a, b, 0.8));
+		}
+		let graph = AssociationGraph::new(6, &associations);
+		let result = eigenvector_centrality(&graph, &EigenvectorConfig::default());
+
+		assert!(result.converged);
+		assert!((result.scores.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+		assert!(result.scores[1] > result.scores[0]);
+		assert!(result.scores[0] > result.scores[3]);
+		assert!(result.scores[3] > result.scores[4]);
+	}
+
+	#[test]
+	fn test_personalized_pagerank_matches_power_iteration() {
+		let associations = [
+			make_assoc(0, 1, 0.9),
+			make_assoc(1, 2, 0.6),
+			make_assoc(2, 0, 0.3),
+			make_assoc(1, 3, 0.3),
+			make_assoc(3, 4, 0.8),
+		];
+		let graph = AssociationGraph::new(6, &associations);
+		let config = PersonalizedPageRankConfig {
+			epsilon: 1e-10,
+			..PersonalizedPageRankConfig::default()
+		};
+		let result = personalized_pagerank(&graph, &[(0, 1.0)], &config);
+
+		// Reference: power iteration with dangling mass restarting at the seed
+		let mut exact = vec![0.0; 6];
+		exact[0] = 1.0;
+		for _ in 0..500 {
+			let mut next = vec![0.0; 6];
+			next[0] += 0.15;
+			for (node, &score) in exact.iter().enumerate() {
+				let edges = graph.forward(node);
+				let total: f64 = edges.strengths.iter().sum();
+				if total > 0.0 {
+					for (target, strength) in edges.iter() {
+						next[target] += 0.85 * score * strength / total;
+					}
+				} else {
+					next[0] += 0.85 * score;
+				}
+			}
+			exact = next;
+		}
+
+		for (approx, reference) in result.scores.iter().zip(&exact) {
+			assert!((approx - reference).abs() < 1e-6);
+		}
+		assert!(result.residual < 1e-6);
+
+		// Node 5 is unreachable from the seed
+		assert!(result.scores[5].abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn test_personalized_pagerank_local() {
+		let graph = AssociationGraph::new(3, &[make_assoc(0, 1, 0.5)]);
+
+		let config = PersonalizedPageRankConfig::default();
+		let empty = personalized_pagerank(&graph, &[(9, 1.0), (1, 0.0)], &config);
+		assert_eq!(empty.pushes, 0);
+		assert!(empty.scores.iter().all(|&s| s == 0.0));
+
+		// Coarse tolerance stops early and reports the unpushed mass
+		let coarse = PersonalizedPageRankConfig {
+			epsilon: 0.1,
+			..PersonalizedPageRankConfig::default()
+		};
+		let result = personalized_pagerank(&graph, &[(0, 2.0)], &coarse);
+		let total: f64 = result.scores.iter().sum();
+		assert!((total + result.residual - 1.0).abs() < 1e-12);
+		assert!(result.scores[0] > result.scores[1]);
+	}
+
+	#[test]
+	fn test_random_walk_with_restart() {
+		// 0 → 1 strong, 0 → 2 weak, 3 unreachable
+		let graph = AssociationGraph::new(4, &[make_assoc(0, 1, 0.9), make_assoc(0, 2, 0.1)]);
+		let config = RandomWalkConfig {
+			num_steps: 20_000,
+			seed: 3,
+			..RandomWalkConfig::default()
+		};
+		let sample = random_walk_with_restart(&graph, &[(0, 1.0)], &config);
+
+		assert_eq!(sample.visits.iter().sum::<usize>(), 20_000);
+		assert!((sample.frequencies.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+		assert!(sample.frequencies[1] > 4.0 * sample.frequencies[2]);
+		assert_eq!(sample.visits[3], 0);
+
+		// Frequencies approach personalized PageRank
+		let exact = personalized_pagerank(
+			&graph,
+			&[(0, 1.0)],
+			&PersonalizedPageRankConfig {
+				damping: 0.85,
+				epsilon: 1e-10,
+				..PersonalizedPageRankConfig::default()
+			},
+		);
+		for (sampled, expected) in sample.frequencies.iter().zip(&exact.scores) {
+			assert!((sampled - expected).abs() < 0.02);
+		}
+
+		let again = random_walk_with_restart(&graph, &[(0, 1.0)], &config);
+		assert_eq!(again.visits, sample.visits);
+		assert_eq!(
+			random_walk_with_restart(&graph, &[(7, 1.0)], &config).steps,
+			0
+		);
+	}
+}
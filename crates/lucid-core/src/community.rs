@@ -0,0 +1,471 @@
+//! Community Detection
+//!
+//! Groups memories into topical clusters ("memory neighborhoods") from
+//! the association graph alone.
+//!
+//! Associations are treated as undirected, weighted by the mean of their
+//! forward and backward strengths. Cluster quality is measured by
+//! modularity (Newman, 2006):
+//!
+//! `Q = Σ_c [ L_c / m - γ (d_c / 2m)² ]`
+//!
+//! Where:
+//! - `L_c` = total weight of edges inside community `c`
+//! - `d_c` = total weighted degree of the nodes in `c`
+//! - `m` = total edge weight
+//! - `γ` = resolution (higher favors smaller communities)
+//!
+//! Two algorithms are provided:
+//! - **Louvain** (Blondel et al., 2008) greedily moves nodes between
+//!   communities to maximize modularity, then merges each community into a
+//!   single node and repeats. Deterministic.
+//! - **Label propagation** (Raghavan et al., 2007) repeatedly adopts the
+//!   label carrying the most edge weight among a node's neighbors. Faster
+//!   but noisier; visit order is drawn from the seed.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::graph::AssociationGraph;
+use crate::rng::SplitMix64;
+
+/// Weighted undirected adjacency lists. A self-loop entry `(i, w)` holds
+/// the summed weight of ordered pairs inside an aggregated node.
+type Adjacency = Vec<Vec<(usize, f64)>>;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Community detection algorithm.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommunityAlgorithm {
+	/// Multi-level modularity optimization (default)
+	#[default]
+	Louvain,
+	/// Asynchronous label propagation
+	LabelPropagation,
+}
+
+/// Configuration for community detection.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommunityConfig {
+	/// Algorithm to run
+	pub algorithm: CommunityAlgorithm,
+	/// Modularity resolution γ; used by Louvain and the reported score (default: 1.0)
+	pub resolution: f64,
+	/// Maximum passes (Louvain: per level; label propagation: total) (default: 100)
+	pub max_iterations: usize,
+	/// Seed for label propagation visit order (default: 0)
+	pub seed: u64,
+}
+
+impl Default for CommunityConfig {
+	fn default() -> Self {
+		Self {
+			algorithm: CommunityAlgorithm::Louvain,
+			resolution: 1.0,
+			max_iterations: 100,
+			seed: 0,
+		}
+	}
+}
+
+/// Result of community detection.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Communities {
+	/// Community id per node, numbered `0..num_communities` in order of
+	/// each community's lowest node index
+	pub assignments: Vec<usize>,
+	/// Number of distinct communities (isolated nodes form their own)
+	pub num_communities: usize,
+	/// Modularity of the partition
+	pub modularity: f64,
+}
+
+impl Communities {
+	/// Node indices in each community, ordered by community id.
+	#[must_use]
+	pub fn members(&self) -> Vec<Vec<usize>> {
+		let mut members = vec![Vec::new(); self.num_communities];
+		for (node, &community) in self.assignments.iter().enumerate() {
+			members[community].push(node);
+		}
+		members
+	}
+}
+
+// ============================================================================
+// Detection
+// ============================================================================
+
+/// Partition the graph into communities.
+#[must_use]
+pub fn detect_communities(graph: &AssociationGraph, config: &CommunityConfig) -> Communities {
+	let adjacency = undirected_adjacency(graph);
+	let raw = match config.algorithm {
+		CommunityAlgorithm::Louvain => louvain(&adjacency, config),
+		CommunityAlgorithm::LabelPropagation => label_propagation(&adjacency, config),
+	};
+
+	let (assignments, num_communities) = renumber(&raw);
+	let modularity = adjacency_modularity(&adjacency, &assignments, config.resolution);
+	Communities {
+		assignments,
+		num_communities,
+		modularity,
+	}
+}
+
+/// Modularity of a partition of `graph` at resolution `γ`.
+///
+/// Returns 0.0 for a graph with no edge weight.
+///
+/// # Panics
+///
+/// Panics if `assignments.len() != graph.num_nodes()`.
+#[must_use]
+pub fn modularity(graph: &AssociationGraph, assignments: &[usize], resolution: f64) -> f64 {
+	assert_eq!(assignments.len(), graph.num_nodes());
+	let (assignments, _) = renumber(assignments);
+	adjacency_modularity(&undirected_adjacency(graph), &assignments, resolution)
+}
+
+/// Symmetric adjacency with weight `(forward + backward) / 2` per
+/// association. Parallel edges are summed; self-loops are dropped.
+fn undirected_adjacency(graph: &AssociationGraph) -> Adjacency {
+	let num_nodes = graph.num_nodes();
+	let mut weights: HashMap<(usize, usize), f64> = HashMap::new();
+
+	for node in 0..num_nodes {
+		for (target, strength) in graph.forward(node).iter() {
+			if target != node {
+				*weights
+					.entry((node.min(target), node.max(target)))
+					.or_insert(0.0) += strength / 2.0;
+			}
+		}
+		for (source, strength) in graph.backward(node).iter() {
+			if source != node {
+				*weights
+					.entry((node.min(source), node.max(source)))
+					.or_insert(0.0) += strength / 2.0;
+			}
+		}
+	}
+
+	let mut adjacency: Adjacency = vec![Vec::new(); num_nodes];
+	let mut pairs: Vec<((usize, usize), f64)> = weights.into_iter().collect();
+	pairs.sort_unstable_by_key(|&(pair, _)| pair);
+	for ((a, b), weight) in pairs {
+		if weight > 0.0 {
+			adjacency[a].push((b, weight));
+			adjacency[b].push((a, weight));
+		}
+	}
+	adjacency
+}
+
+/// Relabel communities `0..k` by lowest member index.
+fn renumber(assignments: &[usize]) -> (Vec<usize>, usize) {
+	let mut ids: HashMap<usize, usize> = HashMap::new();
+	let renumbered = assignments
+		.iter()
+		.map(|&label| {
+			let next = ids.len();
+			*ids.entry(label).or_insert(next)
+		})
+		.collect();
+	(renumbered, ids.len())
+}
+
+fn adjacency_modularity(adjacency: &Adjacency, assignments: &[usize], resolution: f64) -> f64 {
+	let num_communities = assignments.iter().max().map_or(0, |&c| c + 1);
+	let mut internal = vec![0.0; num_communities];
+	let mut degree = vec![0.0; num_communities];
+	let mut two_m = 0.0;
+
+	for (node, edges) in adjacency.iter().enumerate() {
+		let community = assignments[node];
+		for &(neighbor, weight) in edges {
+			two_m += weight;
+			degree[community] += weight;
+			if assignments[neighbor] == community {
+				internal[community] += weight;
+			}
+		}
+	}
+	if two_m <= 0.0 {
+		return 0.0;
+	}
+
+	internal
+		.iter()
+		.zip(&degree)
+		.map(|(&inside, &total)| {
+			let share = total / two_m;
+			(resolution * share).mul_add(-share, inside / two_m)
+		})
+		.sum()
+}
+
+/// Whether candidate `c` beats the best so far: strictly better, or tied
+/// with the lowest id unless the node's current community holds the tie.
+const fn prefer(order: Ordering, c: usize, best: usize, current: usize) -> bool {
+	match order {
+		Ordering::Greater => true,
+		Ordering::Equal => c < best && best != current,
+		Ordering::Less => false,
+	}
+}
+
+// ============================================================================
+// Louvain
+// ============================================================================
+
+fn louvain(adjacency: &Adjacency, config: &CommunityConfig) -> Vec<usize> {
+	let mut assignments: Vec<usize> = (0..adjacency.len()).collect();
+	let mut level = adjacency.clone();
+
+	loop {
+		let (communities, moved) = louvain_local_moves(&level, config);
+		if !moved {
+			return assignments;
+		}
+
+		let (communities, count) = renumber(&communities);
+		for community in &mut assignments {
+			*community = communities[*community];
+		}
+		level = aggregate(&level, &communities, count);
+	}
+}
+
+/// One Louvain level: move nodes until no move improves modularity.
+///
+/// Returns the community per node and whether any node moved.
+fn louvain_local_moves(adjacency: &Adjacency, config: &CommunityConfig) -> (Vec<usize>, bool) {
+	let num_nodes = adjacency.len();
+	let degree: Vec<f64> = adjacency
+		.iter()
+		.map(|edges| edges.iter().map(|&(_, w)| w).sum())
+		.collect();
+	let two_m: f64 = degree.iter().sum();
+	let mut community: Vec<usize> = (0..num_nodes).collect();
+	if two_m <= 0.0 {
+		return (community, false);
+	}
+
+	let mut total = degree.clone();
+	let mut links_to = vec![0.0; num_nodes];
+	let mut touched: Vec<usize> = Vec::new();
+	let mut moved = false;
+
+	for _ in 0..config.max_iterations {
+		let mut improved = false;
+
+		for node in 0..num_nodes {
+			let current = community[node];
+			total[current] -= degree[node];
+
+			for &(neighbor, weight) in &adjacency[node] {
+				if neighbor != node {
+					let c = community[neighbor];
+					if links_to[c] == 0.0 {
+						touched.push(c);
+					}
+					links_to[c] += weight;
+				}
+			}
+
+			// ΔQ ∝ k_i,in(c) - γ × tot(c) × k_i / 2m
+			let gain = |c: usize, links: f64| {
+				(config.resolution * total[c] * degree[node]).mul_add(-1.0 / two_m, links)
+			};
+			let mut best = current;
+			let mut best_gain = gain(current, links_to[current]);
+			for &c in &touched {
+				let g = gain(c, links_to[c]);
+				if prefer(g.total_cmp(&best_gain), c, best, current) {
+					best = c;
+					best_gain = g;
+				}
+			}
+
+			for &c in &touched {
+				links_to[c] = 0.0;
+			}
+			touched.clear();
+
+			total[best] += degree[node];
+			if best != current {
+				community[node] = best;
+				improved = true;
+				moved = true;
+			}
+		}
+
+		if !improved {
+			break;
+		}
+	}
+
+	(community, moved)
+}
+
+/// Collapse each community into one node; internal weight becomes a self-loop.
+fn aggregate(adjacency: &Adjacency, communities: &[usize], count: usize) -> Adjacency {
+	let mut merged: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+	for (node, edges) in adjacency.iter().enumerate() {
+		let from = communities[node];
+		for &(neighbor, weight) in edges {
+			*merged[from].entry(communities[neighbor]).or_insert(0.0) += weight;
+		}
+	}
+	merged
+		.into_iter()
+		.map(|edges| {
+			let mut edges: Vec<(usize, f64)> = edges.into_iter().collect();
+			edges.sort_unstable_by_key(|&(c, _)| c);
+			edges
+		})
+		.collect()
+}
+
+// ============================================================================
+// Label Propagation
+// ============================================================================
+
+fn label_propagation(adjacency: &Adjacency, config: &CommunityConfig) -> Vec<usize> {
+	let num_nodes = adjacency.len();
+	let mut labels: Vec<usize> = (0..num_nodes).collect();
+	let mut order: Vec<usize> = (0..num_nodes).collect();
+	let mut rng = SplitMix64::new(config.seed);
+	let mut weight_of = vec![0.0; num_nodes];
+	let mut touched: Vec<usize> = Vec::new();
+
+	for _ in 0..config.max_iterations {
+		// Fisher-Yates shuffle of the visit order
+		for i in (1..num_nodes).rev() {
+			#[allow(clippy::cast_possible_truncation)]
+			let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+			order.swap(i, j);
+		}
+
+		let mut changed = false;
+		for &node in &order {
+			for &(neighbor, weight) in &adjacency[node] {
+				let label = labels[neighbor];
+				if weight_of[label] == 0.0 {
+					touched.push(label);
+				}
+				weight_of[label] += weight;
+			}
+
+			// Heaviest label; keep the current one on ties, else the lowest
+			let current = labels[node];
+			let mut best = current;
+			let mut best_weight = weight_of[current];
+			for &label in &touched {
+				let w = weight_of[label];
+				if prefer(w.total_cmp(&best_weight), label, best, current) {
+					best = label;
+					best_weight = w;
+				}
+			}
+
+			for &label in &touched {
+				weight_of[label] = 0.0;
+			}
+			touched.clear();
+
+			if best != current && best_weight > 0.0 {
+				labels[node] = best;
+				changed = true;
+			}
+		}
+
+		if !changed {
+			break;
+		}
+	}
+
+	labels
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::{Association, AssociationType};
+
+	fn make_assoc(source: usize, target: usize, strength: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	/// Two 4-cliques {0..3} and {4..7} joined by one weak bridge.
+	fn make_two_cliques() -> AssociationGraph {
+		let mut associations = Vec::new();
+		for offset in [0, 4] {
+			for a in 0..4 {
+				for b in a + 1..4 {
+					associations.push(make_assoc(offset + a, offset + b, 0.9));
+				}
+			}
+		}
+		associations.push(make_assoc(3, 4, 0.1));
+		AssociationGraph::new(8, &associations)
+	}
+
+	#[test]
+	fn test_communities_split_cliques() {
+		let graph = make_two_cliques();
+
+		for algorithm in [
+			CommunityAlgorithm::Louvain,
+			CommunityAlgorithm::LabelPropagation,
+		] {
+			let config = CommunityConfig {
+				algorithm,
+				..CommunityConfig::default()
+			};
+			let result = detect_communities(&graph, &config);
+
+			assert_eq!(result.num_communities, 2, "{algorithm:?}");
+			assert_eq!(result.assignments, vec![0, 0, 0, 0, 1, 1, 1, 1]);
+			assert!(result.modularity > 0.4);
+			assert_eq!(result.members()[1], vec![4, 5, 6, 7]);
+		}
+	}
+
+	#[test]
+	fn test_modularity() {
+		let graph = make_two_cliques();
+
+		// One community holding everything scores zero
+		assert!(modularity(&graph, &[0; 8], 1.0).abs() < 1e-12);
+
+		// Splitting the cliques beats splitting across them
+		let good = modularity(&graph, &[0, 0, 0, 0, 1, 1, 1, 1], 1.0);
+		let bad = modularity(&graph, &[0, 1, 0, 1, 0, 1, 0, 1], 1.0);
+		assert!(good > bad);
+
+		// Edgeless graph
+		let empty = AssociationGraph::new(3, &[]);
+		assert!(modularity(&empty, &[0, 1, 2], 1.0).abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn test_isolated_nodes_form_own_communities() {
+		let graph = AssociationGraph::new(4, &[make_assoc(0, 1, 0.8)]);
+		let result = detect_communities(&graph, &CommunityConfig::default());
+		assert_eq!(result.assignments, vec![0, 0, 1, 2]);
+		assert_eq!(result.num_communities, 3);
+	}
+}
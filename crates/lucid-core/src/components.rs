@@ -0,0 +1,154 @@
+//! Connectivity
+//!
+//! Which memories can reach each other at all, ignoring direction and
+//! strength, and which memories are cut off from everything.
+//!
+//! Spreading activation can never reach a memory outside the components
+//! of its cues, so a memory in a small component (or alone in its own)
+//! is effectively unreachable. Orphans, memories with no associations
+//! and no episode, are the extreme case: candidates for re-linking or
+//! archiving.
+
+use serde::{Deserialize, Serialize};
+
+use crate::episodes::Episode;
+use crate::graph::AssociationGraph;
+
+/// Weakly connected components of the association graph.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Components {
+	/// Component id per node, numbered `0..num_components` in order of
+	/// each component's lowest node index
+	pub assignments: Vec<usize>,
+	/// Number of components (isolated nodes form their own)
+	pub num_components: usize,
+}
+
+impl Components {
+	/// Node indices in each component, ordered by component id.
+	#[must_use]
+	pub fn members(&self) -> Vec<Vec<usize>> {
+		let mut members = vec![Vec::new(); self.num_components];
+		for (node, &component) in self.assignments.iter().enumerate() {
+			members[component].push(node);
+		}
+		members
+	}
+
+	/// Number of nodes in each component, ordered by component id.
+	#[must_use]
+	pub fn sizes(&self) -> Vec<usize> {
+		let mut sizes = vec![0; self.num_components];
+		for &component in &self.assignments {
+			sizes[component] += 1;
+		}
+		sizes
+	}
+}
+
+/// Find the weakly connected components of the graph.
+///
+/// Two memories share a component if a chain of associations joins them,
+/// in either direction.
+#[must_use]
+pub fn connected_components(graph: &AssociationGraph) -> Components {
+	let num_nodes = graph.num_nodes();
+	let mut parent: Vec<usize> = (0..num_nodes).collect();
+
+	for node in 0..num_nodes {
+		for &target in graph.forward(node).targets {
+			let (a, b) = (find_root(&mut parent, node), find_root(&mut parent, target));
+			// Keep the lower index as root so ids follow lowest members
+			parent[a.max(b)] = a.min(b);
+		}
+	}
+
+	let mut ids = vec![usize::MAX; num_nodes];
+	let mut num_components = 0;
+	let mut assignments = Vec::with_capacity(num_nodes);
+	for node in 0..num_nodes {
+		let root = find_root(&mut parent, node);
+		if ids[root] == usize::MAX {
+			ids[root] = num_components;
+			num_components += 1;
+		}
+		assignments.push(ids[root]);
+	}
+
+	Components {
+		assignments,
+		num_components,
+	}
+}
+
+/// List memories with no associations and no episode membership.
+///
+/// # Arguments
+///
+/// * `graph` - Association graph
+/// * `episodes` - Episodes whose events count as membership
+///
+/// # Returns
+///
+/// Orphaned node indices, ascending.
+#[must_use]
+pub fn find_orphans(graph: &AssociationGraph, episodes: &[Episode]) -> Vec<usize> {
+	let mut in_episode = vec![false; graph.num_nodes()];
+	for &event in episodes.iter().flat_map(|episode| &episode.events) {
+		if let Some(member) = in_episode.get_mut(event) {
+			*member = true;
+		}
+	}
+
+	(0..graph.num_nodes())
+		.filter(|&node| {
+			!in_episode[node] && graph.forward(node).is_empty() && graph.backward(node).is_empty()
+		})
+		.collect()
+}
+
+/// Union-find root of `node`, halving paths along the way.
+fn find_root(parent: &mut [usize], mut node: usize) -> usize {
+	while parent[node] != node {
+		parent[node] = parent[parent[node]];
+		node = parent[node];
+	}
+	node
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::{Association, AssociationType, TemporalSpreadingConfig};
+
+	fn make_assoc(source: usize, target: usize, strength: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	#[test]
+	fn test_components_and_orphans() {
+		// {0, 2, 4} joined through edges pointing both ways, {1, 3}, and
+		// isolated 5 and 6
+		let associations = vec![
+			make_assoc(4, 2, 0.5),
+			make_assoc(0, 2, 0.5),
+			make_assoc(3, 1, 0.5),
+		];
+		let graph = AssociationGraph::new(7, &associations);
+
+		let components = connected_components(&graph);
+		assert_eq!(components.assignments, vec![0, 1, 0, 1, 0, 2, 3]);
+		assert_eq!(components.sizes(), vec![3, 2, 1, 1]);
+		assert_eq!(components.members()[1], vec![1, 3]);
+
+		assert_eq!(find_orphans(&graph, &[]), vec![5, 6]);
+		let episode = Episode::new(vec![6, 0], &TemporalSpreadingConfig::default());
+		assert_eq!(find_orphans(&graph, &[episode]), vec![5]);
+	}
+}
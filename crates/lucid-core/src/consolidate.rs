@@ -0,0 +1,702 @@
+//! Consolidation
+//!
+//! Offline routines that restructure the memory graph, turning repeated
+//! episodic experience into semantic structure as complementary learning
+//! systems theory describes for sleep and rest.
+//!
+//! ## Schema Extraction
+//!
+//! Entity patterns that recur across episodes — the same people, project
+//! and bug showing up together again and again — become schema nodes.
+//! Patterns are found Apriori-style (Agrawal & Srikant, 1994) and only
+//! closed patterns are kept: a pattern is dropped when a larger one occurs
+//! in exactly the same episodes. Each schema links to every memory in its
+//! instance episodes, and links strengthen with support:
+//!
+//! `strength = base × (1 - 1 / support)`
+//!
+//! So a pattern seen twice is half-formed and one seen ten times is
+//! nearly at full strength — gradual semanticization rather than a switch.
+//!
+//! ## Replay
+//!
+//! `replay_consolidation` is a batch "sleep" pass, meant to run nightly.
+//! The most salient episodes are replayed, much as the hippocampus
+//! reactivates recent experience during sleep:
+//!
+//! 1. Links among each replayed episode's memories get a Hebbian update
+//! 2. Activation spreads from each episode; nodes that keep lighting up
+//!    together across replays without a direct link get a shortcut
+//! 3. Links no replay touched decay by a constant factor, and those that
+//!    fall below the floor are pruned
+//!
+//! ## Duplicate Merging
+//!
+//! `merge_duplicates` collapses near-identical memories — the same fact
+//! saved twice — into one node. Two memories are duplicates when their
+//! embeddings are nearly identical, or merely very similar while also
+//! sharing most of their associations. Duplicates are grouped
+//! transitively, nodes are renumbered densely, and parallel associations
+//! that end up between the same pair have their strengths summed.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::activation::cosine_similarity;
+use crate::episodes::Episode;
+use crate::graph::MemoryGraph;
+use crate::learning::hebbian_update;
+use crate::spreading::{
+	spread_activation_batch_graph, Association, AssociationType, SpreadingConfig, SpreadingQuery,
+};
+
+// ============================================================================
+// Schema Extraction
+// ============================================================================
+
+/// Configuration for schema extraction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SchemaConfig {
+	/// Episodes a pattern must occur in to become a schema (default: 3)
+	pub min_support: usize,
+	/// Fewest entities in a pattern (default: 2)
+	pub min_pattern_size: usize,
+	/// Most entities in a pattern (default: 4)
+	pub max_pattern_size: usize,
+	/// Schema → instance strength as support grows large (default: 0.8)
+	pub strength: f64,
+}
+
+impl Default for SchemaConfig {
+	fn default() -> Self {
+		Self {
+			min_support: 3,
+			min_pattern_size: 2,
+			max_pattern_size: 4,
+			strength: 0.8,
+		}
+	}
+}
+
+/// A recurring entity pattern abstracted into a node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Schema {
+	/// Node index assigned to the schema
+	pub node: usize,
+	/// Entities in the pattern, sorted
+	pub pattern: Vec<String>,
+	/// Indices of the episodes the pattern occurs in, ascending
+	pub instances: Vec<usize>,
+}
+
+/// Schemas found across episodes and their links to instance memories.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SchemaExtraction {
+	/// Schemas, largest support first
+	pub schemas: Vec<Schema>,
+	/// Schema → instance memory associations
+	pub associations: Vec<Association>,
+}
+
+/// Find recurring entity patterns across episodes and abstract them into
+/// schema nodes.
+///
+/// # Arguments
+///
+/// * `num_nodes` - Nodes already in the graph; schemas are numbered from here
+/// * `episodes` - Episodes to consolidate
+/// * `entities` - Entity/tag set of each episode, parallel to `episodes`;
+///   missing entries count as empty
+/// * `config` - Extraction parameters
+///
+/// # Returns
+///
+/// Schemas (nodes `num_nodes..num_nodes + schemas.len()`) and `Semantic`
+/// associations from each schema to the memories of its instances.
+#[must_use]
+pub fn extract_schemas<S: AsRef<str>>(
+	num_nodes: usize,
+	episodes: &[Episode],
+	entities: &[Vec<S>],
+	config: &SchemaConfig,
+) -> SchemaExtraction {
+	let sets: Vec<HashSet<&str>> = (0..episodes.len())
+		.map(|i| {
+			entities
+				.get(i)
+				.map(|tags| tags.iter().map(AsRef::as_ref).collect())
+				.unwrap_or_default()
+		})
+		.collect();
+	let min_support = config.min_support.max(1);
+	let support_of = |pattern: &[&str]| -> Vec<usize> {
+		sets.iter()
+			.enumerate()
+			.filter(|(_, set)| pattern.iter().all(|e| set.contains(e)))
+			.map(|(i, _)| i)
+			.collect()
+	};
+
+	// Level 1: frequent single entities
+	let vocabulary: BTreeSet<&str> = sets.iter().flatten().copied().collect();
+	let mut level: Vec<(Vec<&str>, Vec<usize>)> = vocabulary
+		.into_iter()
+		.map(|e| (vec![e], support_of(&[e])))
+		.filter(|(_, instances)| instances.len() >= min_support)
+		.collect();
+	let mut frequent = Vec::new();
+
+	// Level k: join (k-1)-patterns sharing all but their last entity
+	for _ in 1..config.max_pattern_size {
+		let mut next = Vec::new();
+		for (i, (a, _)) in level.iter().enumerate() {
+			for (b, _) in &level[i + 1..] {
+				if a[..a.len() - 1] != b[..b.len() - 1] {
+					continue;
+				}
+				let mut candidate = a.clone();
+				candidate.push(b[b.len() - 1]);
+				let instances = support_of(&candidate);
+				if instances.len() >= min_support {
+					next.push((candidate, instances));
+				}
+			}
+		}
+		frequent.append(&mut level);
+		if next.is_empty() {
+			break;
+		}
+		level = next;
+	}
+	frequent.append(&mut level);
+
+	// Keep closed patterns of an allowed size
+	let closed: Vec<&(Vec<&str>, Vec<usize>)> = frequent
+		.iter()
+		.filter(|(pattern, _)| {
+			pattern.len() >= config.min_pattern_size && pattern.len() <= config.max_pattern_size
+		})
+		.filter(|(pattern, instances)| {
+			!frequent.iter().any(|(other, other_instances)| {
+				other.len() > pattern.len()
+					&& other_instances == instances
+					&& pattern.iter().all(|e| other.contains(e))
+			})
+		})
+		.collect();
+
+	let mut ranked = closed;
+	ranked.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(&b.0)));
+
+	let mut schemas = Vec::with_capacity(ranked.len());
+	let mut associations = Vec::new();
+	for (k, (pattern, instances)) in ranked.into_iter().enumerate() {
+		let node = num_nodes + k;
+		#[allow(clippy::cast_precision_loss)]
+		let support = instances.len() as f64;
+		let strength = config.strength * (1.0 - 1.0 / support);
+
+		let mut members: Vec<usize> = instances
+			.iter()
+			.flat_map(|&e| episodes[e].events.iter().copied())
+			.collect();
+		members.sort_unstable();
+		members.dedup();
+		associations.extend(members.into_iter().map(|memory| Association {
+			source: node,
+			target: memory,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Semantic,
+		}));
+
+		schemas.push(Schema {
+			node,
+			pattern: pattern.iter().map(|e| (*e).to_string()).collect(),
+			instances: instances.clone(),
+		});
+	}
+
+	SchemaExtraction {
+		schemas,
+		associations,
+	}
+}
+
+// ============================================================================
+// Replay
+// ============================================================================
+
+/// Configuration for replay-based consolidation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayConfig {
+	/// Most episodes replayed per run, by salience (default: 20)
+	pub max_episodes: usize,
+	/// Hebbian rate for links inside a replayed episode (default: 0.1)
+	pub learning_rate: f64,
+	/// Spreading used to find co-activated nodes
+	pub spreading: SpreadingConfig,
+	/// Spreading depth per replay (default: 2)
+	pub depth: usize,
+	/// Activation a node needs to count as co-activated (default: 0.1)
+	pub coactivation_threshold: f64,
+	/// Most co-activated nodes considered per replay (default: 16)
+	pub max_coactivated: usize,
+	/// Replays two unlinked nodes must co-activate in to get a shortcut
+	/// (default: 2)
+	pub min_coactivations: usize,
+	/// Strength of new shortcut associations (default: 0.2)
+	pub shortcut_strength: f64,
+	/// Multiplier for links no replay touched (default: 0.95)
+	pub untouched_decay: f64,
+	/// Links weaker than this in both directions after decay are pruned
+	/// (default: 0.05)
+	pub prune_threshold: f64,
+}
+
+impl Default for ReplayConfig {
+	fn default() -> Self {
+		Self {
+			max_episodes: 20,
+			learning_rate: 0.1,
+			spreading: SpreadingConfig::default(),
+			depth: 2,
+			coactivation_threshold: 0.1,
+			max_coactivated: 16,
+			min_coactivations: 2,
+			shortcut_strength: 0.2,
+			untouched_decay: 0.95,
+			prune_threshold: 0.05,
+		}
+	}
+}
+
+/// What a replay pass changed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayReport {
+	/// Indices of the episodes replayed, most salient first
+	pub replayed: Vec<usize>,
+	/// Links strengthened inside replayed episodes
+	pub strengthened: usize,
+	/// `(source, target)` of each shortcut created
+	pub shortcuts: Vec<(usize, usize)>,
+	/// Links decayed because no replay touched them
+	pub decayed: usize,
+	/// Links removed after decay
+	pub pruned: Vec<Association>,
+}
+
+/// Replay salient episodes through the graph ("sleep" consolidation).
+///
+/// # Arguments
+///
+/// * `graph` - Graph to consolidate, updated in place
+/// * `episodes` - Candidate episodes
+/// * `salience` - Salience of each episode, parallel to `episodes`;
+///   episodes with no positive salience are never replayed
+/// * `config` - Replay parameters
+///
+/// # Returns
+///
+/// A report of what changed.
+pub fn replay_consolidation(
+	graph: &mut MemoryGraph,
+	episodes: &[Episode],
+	salience: &[f64],
+	config: &ReplayConfig,
+) -> ReplayReport {
+	let mut ranked: Vec<(usize, f64)> = salience
+		.iter()
+		.copied()
+		.enumerate()
+		.take(episodes.len())
+		.filter(|&(_, s)| s > 0.0)
+		.collect();
+	ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+	ranked.truncate(config.max_episodes);
+	let replayed: Vec<usize> = ranked.into_iter().map(|(i, _)| i).collect();
+
+	// 1. Strengthen links inside each replayed episode
+	let members: Vec<HashSet<usize>> = replayed
+		.iter()
+		.map(|&e| episodes[e].events.iter().copied().collect())
+		.collect();
+	let mut touched: HashSet<(usize, usize)> = HashSet::new();
+	for (association, _) in graph.edges_mut() {
+		for set in &members {
+			if set.contains(&association.source) && set.contains(&association.target) {
+				association.forward_strength =
+					hebbian_update(association.forward_strength, config.learning_rate);
+				association.backward_strength =
+					hebbian_update(association.backward_strength, config.learning_rate);
+				let _ = touched.insert((association.source, association.target));
+			}
+		}
+	}
+	let strengthened = touched.len();
+
+	// 2. Shortcuts between nodes repeatedly co-activated without a link
+	let index = graph.index();
+	let queries: Vec<SpreadingQuery<'_>> = replayed
+		.iter()
+		.map(|&e| SpreadingQuery {
+			seed_indices: &episodes[e].events,
+			seed_activations: &[],
+		})
+		.collect();
+	let results = spread_activation_batch_graph(&index, &queries, &config.spreading, config.depth);
+
+	let mut coactivations: HashMap<(usize, usize), usize> = HashMap::new();
+	for result in &results {
+		let mut active: Vec<(usize, f64)> = result
+			.activations
+			.iter()
+			.copied()
+			.enumerate()
+			.filter(|&(_, a)| a >= config.coactivation_threshold)
+			.collect();
+		active.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+		active.truncate(config.max_coactivated);
+
+		for (i, &(a, _)) in active.iter().enumerate() {
+			for &(b, _) in &active[i + 1..] {
+				*coactivations.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+			}
+		}
+	}
+
+	let mut shortcuts: Vec<(usize, usize)> = coactivations
+		.into_iter()
+		.filter(|&((a, b), count)| {
+			count >= config.min_coactivations.max(1)
+				&& graph.association(a, b).is_none()
+				&& graph.association(b, a).is_none()
+		})
+		.map(|(pair, _)| pair)
+		.collect();
+	shortcuts.sort_unstable();
+	for &(source, target) in &shortcuts {
+		let _ = graph.insert_association(Association {
+			source,
+			target,
+			forward_strength: config.shortcut_strength,
+			backward_strength: config.shortcut_strength,
+			association_type: AssociationType::default(),
+		});
+		let _ = touched.insert((source, target));
+	}
+
+	// 3. Decay untouched links and prune what falls below the floor
+	let mut decayed = 0;
+	for (association, _) in graph.edges_mut() {
+		if !touched.contains(&(association.source, association.target)) {
+			association.forward_strength *= config.untouched_decay;
+			association.backward_strength *= config.untouched_decay;
+			decayed += 1;
+		}
+	}
+	let pruned = graph.remove_associations_where(|association, _| {
+		association
+			.forward_strength
+			.max(association.backward_strength)
+			< config.prune_threshold
+	});
+
+	ReplayReport {
+		replayed,
+		strengthened,
+		shortcuts,
+		decayed,
+		pruned,
+	}
+}
+
+// ============================================================================
+// Duplicate Merging
+// ============================================================================
+
+/// Configuration for near-duplicate merging.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DuplicateConfig {
+	/// Embedding similarity that alone marks a duplicate (default: 0.97)
+	pub similarity_threshold: f64,
+	/// Lower similarity that marks a duplicate when neighbors overlap
+	/// (default: 0.9)
+	pub related_similarity_threshold: f64,
+	/// Jaccard overlap of neighbor sets required at the lower threshold
+	/// (default: 0.5)
+	pub min_neighbor_overlap: f64,
+}
+
+impl Default for DuplicateConfig {
+	fn default() -> Self {
+		Self {
+			similarity_threshold: 0.97,
+			related_similarity_threshold: 0.9,
+			min_neighbor_overlap: 0.5,
+		}
+	}
+}
+
+/// Result of merging near-duplicate memories.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DuplicateMerge {
+	/// New index of each original node
+	pub remap: Vec<usize>,
+	/// Number of nodes after merging
+	pub num_nodes: usize,
+	/// Original nodes merged together, one group per merged node with more
+	/// than one member, each ascending
+	pub groups: Vec<Vec<usize>>,
+	/// Associations rewired to new indices
+	pub associations: Vec<Association>,
+}
+
+/// Union-find root with path halving.
+fn find_root(parent: &mut [usize], mut node: usize) -> usize {
+	while parent[node] != node {
+		parent[node] = parent[parent[node]];
+		node = parent[node];
+	}
+	node
+}
+
+/// Find near-duplicate memories and merge each group into one node.
+///
+/// Compares every pair of embeddings, so it is meant for offline
+/// maintenance rather than the query path. Each group keeps its lowest
+/// original index as the representative; merged nodes are numbered in
+/// order of their representatives. Associations are rewired through the
+/// remap, links inside a group are dropped, and links that now share a
+/// `(source, target)` pair are combined by summing strengths (capped at
+/// 1.0) and keeping the first one's type.
+///
+/// # Arguments
+///
+/// * `embeddings` - Embedding of each memory
+/// * `associations` - Association graph over the same memories
+/// * `config` - Duplicate thresholds
+///
+/// # Returns
+///
+/// The remap table, merged groups, and rewired associations.
+#[must_use]
+pub fn merge_duplicates(
+	embeddings: &[Vec<f64>],
+	associations: &[Association],
+	config: &DuplicateConfig,
+) -> DuplicateMerge {
+	let num_nodes = associations
+		.iter()
+		.map(|a| a.source.max(a.target) + 1)
+		.max()
+		.unwrap_or(0)
+		.max(embeddings.len());
+
+	let mut neighbors: Vec<HashSet<usize>> = vec![HashSet::new(); num_nodes];
+	for a in associations {
+		let _ = neighbors[a.source].insert(a.target);
+		let _ = neighbors[a.target].insert(a.source);
+	}
+
+	let mut parent: Vec<usize> = (0..num_nodes).collect();
+	for i in 0..embeddings.len() {
+		for j in (i + 1)..embeddings.len() {
+			let similarity = cosine_similarity(&embeddings[i], &embeddings[j]);
+			if similarity < config.related_similarity_threshold {
+				continue;
+			}
+			let duplicate = similarity >= config.similarity_threshold || {
+				let mut a = neighbors[i].clone();
+				let mut b = neighbors[j].clone();
+				let _ = a.remove(&j);
+				let _ = b.remove(&i);
+				let union = a.union(&b).count();
+				#[allow(clippy::cast_precision_loss)]
+				let overlap = if union == 0 {
+					0.0
+				} else {
+					a.intersection(&b).count() as f64 / union as f64
+				};
+				overlap >= config.min_neighbor_overlap
+			};
+			if duplicate {
+				let (ri, rj) = (find_root(&mut parent, i), find_root(&mut parent, j));
+				parent[ri.max(rj)] = ri.min(rj);
+			}
+		}
+	}
+
+	// Representatives are group minima, so ascending order numbers groups
+	// by representative
+	let mut remap = vec![0; num_nodes];
+	let mut group_of_root: HashMap<usize, usize> = HashMap::new();
+	let mut members: Vec<Vec<usize>> = Vec::new();
+	for (node, new_index) in remap.iter_mut().enumerate() {
+		let root = find_root(&mut parent, node);
+		let next = members.len();
+		let group = *group_of_root.entry(root).or_insert(next);
+		if group == next {
+			members.push(Vec::new());
+		}
+		members[group].push(node);
+		*new_index = group;
+	}
+
+	let mut merged: Vec<Association> = Vec::new();
+	let mut slot: HashMap<(usize, usize), usize> = HashMap::new();
+	for a in associations {
+		let (source, target) = (remap[a.source], remap[a.target]);
+		if source == target {
+			continue;
+		}
+		if let Some(&i) = slot.get(&(source, target)) {
+			let existing = &mut merged[i];
+			existing.forward_strength = (existing.forward_strength + a.forward_strength).min(1.0);
+			existing.backward_strength =
+				(existing.backward_strength + a.backward_strength).min(1.0);
+		} else {
+			let _ = slot.insert((source, target), merged.len());
+			merged.push(Association {
+				source,
+				target,
+				..a.clone()
+			});
+		}
+	}
+
+	DuplicateMerge {
+		remap,
+		num_nodes: members.len(),
+		groups: members.into_iter().filter(|g| g.len() > 1).collect(),
+		associations: merged,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::TemporalSpreadingConfig;
+
+	#[test]
+	fn test_extract_schemas_finds_closed_patterns() {
+		let config = TemporalSpreadingConfig::default();
+		let episodes: Vec<Episode> = (0..5)
+			.map(|i| Episode::new(vec![2 * i, 2 * i + 1], &config))
+			.collect();
+		let entities = vec![
+			vec!["standup", "alice", "bob"],
+			vec!["standup", "alice", "bob"],
+			vec!["standup", "alice", "bob", "carol"],
+			vec!["lunch", "alice"],
+			vec!["lunch", "carol"],
+		];
+
+		let extraction = extract_schemas(10, &episodes, &entities, &SchemaConfig::default());
+
+		// {alice, bob, standup} recurs 3 times; its subsets occur in the
+		// same episodes so only the closed pattern survives
+		assert_eq!(extraction.schemas.len(), 1);
+		let schema = &extraction.schemas[0];
+		assert_eq!(schema.node, 10);
+		assert_eq!(schema.pattern, vec!["alice", "bob", "standup"]);
+		assert_eq!(schema.instances, vec![0, 1, 2]);
+
+		assert_eq!(extraction.associations.len(), 6);
+		let expected = 0.8 * (1.0 - 1.0 / 3.0);
+		assert!(extraction.associations.iter().all(|a| a.source == 10
+			&& a.target < 6
+			&& (a.forward_strength - expected).abs() < 1e-12));
+	}
+
+	fn make_assoc(source: usize, target: usize, strength: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	#[test]
+	fn test_replay_consolidation() {
+		// Episodes {0, 1} and {2, 3} both reach hub 4, and from it node 5;
+		// 6 — 7 is an unrelated weak link
+		let mut graph = MemoryGraph::from_associations(
+			8,
+			vec![
+				make_assoc(0, 1, 0.5),
+				make_assoc(2, 3, 0.5),
+				make_assoc(1, 4, 0.9),
+				make_assoc(3, 4, 0.9),
+				make_assoc(4, 5, 0.9),
+				make_assoc(6, 7, 0.05),
+			],
+		);
+		let temporal = TemporalSpreadingConfig::default();
+		let episodes = vec![
+			Episode::new(vec![0, 1], &temporal),
+			Episode::new(vec![2, 3], &temporal),
+			Episode::new(vec![6, 7], &temporal),
+		];
+
+		let report = replay_consolidation(
+			&mut graph,
+			&episodes,
+			&[0.9, 0.8, 0.0],
+			&ReplayConfig::default(),
+		);
+
+		assert_eq!(report.replayed, vec![0, 1]);
+		assert_eq!(report.strengthened, 2);
+		assert!(graph
+			.association(0, 1)
+			.is_some_and(|a| (a.forward_strength - 0.55).abs() < 1e-12));
+
+		// 4 and 5 light up in both replays but were already linked; the
+		// episode memories and 5 were not
+		assert!(report.shortcuts.contains(&(1, 5)));
+		assert!(!report.shortcuts.contains(&(4, 5)));
+		assert!(graph.association(1, 5).is_some());
+
+		// Untouched weak link decays below the floor and is pruned
+		assert!(report.decayed >= 3);
+		assert_eq!(report.pruned.len(), 1);
+		assert!(graph.association(6, 7).is_none());
+	}
+
+	#[test]
+	fn test_merge_duplicates() {
+		let embeddings = vec![
+			vec![1.0, 0.0, 0.0],
+			vec![0.0, 1.0, 0.0],
+			vec![0.999, 0.01, 0.0], // near-identical to 0
+			vec![0.0, 0.0, 1.0],
+			vec![0.0, 0.35, 0.94], // similar to 3, shares its neighbor
+			vec![0.35, 0.0, 0.94], // similar to 3, no shared neighbors
+		];
+		let associations = vec![
+			make_assoc(0, 1, 0.4),
+			make_assoc(2, 1, 0.8),
+			make_assoc(0, 2, 0.9),
+			make_assoc(3, 1, 0.3),
+			make_assoc(4, 1, 0.3),
+		];
+
+		let merge = merge_duplicates(&embeddings, &associations, &DuplicateConfig::default());
+
+		assert_eq!(merge.groups, vec![vec![0, 2], vec![3, 4]]);
+		assert_eq!(merge.num_nodes, 4);
+		assert_eq!(merge.remap, vec![0, 1, 0, 2, 2, 3]);
+
+		// 0 → 1 and 2 → 1 combine (capped); the 0 → 2 link disappears
+		assert_eq!(merge.associations.len(), 2);
+		let combined = &merge.associations[0];
+		assert_eq!((combined.source, combined.target), (0, 1));
+		assert!((combined.forward_strength - 1.0).abs() < 1e-12);
+		let other = &merge.associations[1];
+		assert_eq!((other.source, other.target), (2, 1));
+		assert!((other.forward_strength - 0.6).abs() < 1e-12);
+	}
+}
@@ -0,0 +1,458 @@
+//! Temporal Context Model
+//!
+//! A drifting context vector for episodic cueing (Howard & Kahana, 2002).
+//!
+//! Context is a unit vector that drifts as events are experienced. Each
+//! event retrieves an input context `t_in` (initially its own context
+//! unit) and context moves toward it:
+//!
+//! `t_i = ρ × t_(i-1) + β × t_in`
+//!
+//! Where `ρ` is chosen so `‖t_i‖ = 1`:
+//!
+//! `ρ = √(1 + β²((t_(i-1)·t_in)² - 1)) - β(t_(i-1)·t_in)`
+//!
+//! Two associative stores are learned as events are presented:
+//! - **Item → context** (`M^FT`): what context an item reinstates when
+//!   recalled. Starts as the item's own unit, then mixes in the context
+//!   it was studied in, weighted by `γ`.
+//! - **Context → item** (`M^TF`): which items the current context cues.
+//!   Support for item `i` is `max(0, M^TF_i · t)`.
+//!
+//! Because each item is bound to the context *before* it, and recalling an
+//! item reinstates context that already contains it, cueing with an item
+//! favors its successors over its predecessors — the forward asymmetry
+//! `create_episode_links` approximates with fixed positional weights.
+//!
+//! Context unit 0 is the start-of-list context, so recall can begin by
+//! reinstating it.
+//!
+//! ## Source Context (CMR)
+//!
+//! Following the Context Maintenance and Retrieval model (Polyn, Norman &
+//! Kahana, 2009), events may carry source features — application,
+//! location, project — that get their own context units. A source feature
+//! joins the event's input context and is bound directly to the item, so
+//! drifting context toward "VS Code" or "Paris trip" cues the memories
+//! experienced there, and recalling one of them reinstates its source.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Configuration for the temporal context model.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TemporalContextConfig {
+	/// Context drift rate while events are encoded (default: 0.6)
+	pub beta_encoding: f64,
+	/// Context drift rate when a recalled item reinstates its context
+	/// (default: 0.5)
+	pub beta_retrieval: f64,
+	/// Weight of learned vs. pre-experimental item → context associations
+	/// (default: 0.5)
+	pub gamma: f64,
+	/// Weight of source features relative to item features in an event's
+	/// input context (default: 0.5)
+	pub source_weight: f64,
+}
+
+impl Default for TemporalContextConfig {
+	fn default() -> Self {
+		Self {
+			beta_encoding: 0.6,
+			beta_retrieval: 0.5,
+			gamma: 0.5,
+			source_weight: 0.5,
+		}
+	}
+}
+
+// ============================================================================
+// Context Drift
+// ============================================================================
+
+/// Drift a unit context vector toward a unit input.
+///
+/// `t = ρ × t + β × t_in`, with `ρ` chosen to keep `‖t‖ = 1`.
+///
+/// `context` grows to the length of `input` if needed; missing entries
+/// are zero.
+///
+/// # Arguments
+///
+/// * `context` - Current context (unit length), updated in place
+/// * `input` - Input context (unit length)
+/// * `beta` - Drift rate in `[0, 1]`
+pub fn drift_context(context: &mut Vec<f64>, input: &[f64], beta: f64) {
+	if context.len() < input.len() {
+		context.resize(input.len(), 0.0);
+	}
+	let beta = beta.clamp(0.0, 1.0);
+	let overlap = dot(context, input);
+	let rho =
+		beta.mul_add(beta * overlap.mul_add(overlap, -1.0), 1.0)
+			.max(0.0)
+			.sqrt() - beta * overlap;
+
+	for (i, c) in context.iter_mut().enumerate() {
+		let t_in = input.get(i).copied().unwrap_or(0.0);
+		*c = rho.mul_add(*c, beta * t_in);
+	}
+}
+
+/// Dot product over the shared prefix (missing entries are zero).
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+	a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Scale to unit length; zero vectors are left unchanged.
+fn normalized(v: &[f64]) -> Vec<f64> {
+	let norm = dot(v, v).sqrt();
+	if norm > 0.0 {
+		v.iter().map(|x| x / norm).collect()
+	} else {
+		v.to_vec()
+	}
+}
+
+/// Add `scale × source` into `target`, growing `target` if needed.
+fn add_scaled(target: &mut Vec<f64>, source: &[f64], scale: f64) {
+	if target.len() < source.len() {
+		target.resize(source.len(), 0.0);
+	}
+	for (t, s) in target.iter_mut().zip(source) {
+		*t = scale.mul_add(*s, *t);
+	}
+}
+
+// ============================================================================
+// Temporal Context
+// ============================================================================
+
+/// Drifting temporal context with learned item ↔ context associations.
+///
+/// Items are memory indices. Each distinct memory gets its own context
+/// unit the first time it is presented; vectors grow as units are added.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TemporalContext {
+	config: TemporalContextConfig,
+	/// Current context state (unit length)
+	context: Vec<f64>,
+	/// Number of context units allocated (unit 0 is start-of-list)
+	num_units: usize,
+	/// Memory index of each item, in first-presentation order
+	memories: Vec<usize>,
+	/// Memory index → item
+	items: HashMap<usize, usize>,
+	/// Context unit of each item
+	item_units: Vec<usize>,
+	/// Source feature → context unit
+	source_units: HashMap<String, usize>,
+	/// Item → context associations (`M^FT`), one context vector per item
+	item_to_context: Vec<Vec<f64>>,
+	/// Context → item associations (`M^TF`), one context vector per item
+	context_to_item: Vec<Vec<f64>>,
+}
+
+impl TemporalContext {
+	/// Create an empty context positioned at the start of a list.
+	#[must_use]
+	pub fn new(config: TemporalContextConfig) -> Self {
+		Self {
+			config,
+			context: vec![1.0],
+			num_units: 1,
+			memories: Vec::new(),
+			items: HashMap::new(),
+			item_units: Vec::new(),
+			source_units: HashMap::new(),
+			item_to_context: Vec::new(),
+			context_to_item: Vec::new(),
+		}
+	}
+
+	/// Encode an episode, presenting its memories in order.
+	#[must_use]
+	pub fn from_episode(event_memory_indices: &[usize], config: TemporalContextConfig) -> Self {
+		let mut context = Self::new(config);
+		for &memory in event_memory_indices {
+			context.present(memory);
+		}
+		context
+	}
+
+	/// The configuration in use.
+	#[must_use]
+	pub const fn config(&self) -> &TemporalContextConfig {
+		&self.config
+	}
+
+	/// Current context state (unit length).
+	#[must_use]
+	pub fn context(&self) -> &[f64] {
+		&self.context
+	}
+
+	/// Distinct memories presented so far, in first-presentation order.
+	#[must_use]
+	pub fn memories(&self) -> &[usize] {
+		&self.memories
+	}
+
+	/// Number of distinct memories presented.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.memories.len()
+	}
+
+	/// Whether nothing has been presented.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.memories.is_empty()
+	}
+
+	/// Allocate a new context unit.
+	const fn allocate_unit(&mut self) -> usize {
+		let unit = self.num_units;
+		self.num_units += 1;
+		unit
+	}
+
+	/// Item for a memory, allocating one (and its context unit) if new.
+	fn item_for(&mut self, memory: usize) -> usize {
+		if let Some(&item) = self.items.get(&memory) {
+			return item;
+		}
+		let item = self.memories.len();
+		let unit = self.allocate_unit();
+		let mut pre_experimental = vec![0.0; unit + 1];
+		pre_experimental[unit] = 1.0 - self.config.gamma;
+
+		self.memories.push(memory);
+		let _ = self.items.insert(memory, item);
+		self.item_units.push(unit);
+		self.item_to_context.push(pre_experimental);
+		self.context_to_item.push(Vec::new());
+		item
+	}
+
+	/// Unit vector over the given source features, allocating units for
+	/// new ones. Empty if there are no sources.
+	fn source_vector<S: AsRef<str>>(&mut self, sources: &[S]) -> Vec<f64> {
+		let mut units = Vec::with_capacity(sources.len());
+		for source in sources {
+			let unit = if let Some(&unit) = self.source_units.get(source.as_ref()) {
+				unit
+			} else {
+				let unit = self.allocate_unit();
+				let _ = self.source_units.insert(source.as_ref().to_string(), unit);
+				unit
+			};
+			if !units.contains(&unit) {
+				units.push(unit);
+			}
+		}
+		let mut vector = vec![0.0; units.iter().max().map_or(0, |&u| u + 1)];
+		for &unit in &units {
+			vector[unit] = 1.0;
+		}
+		normalized(&vector)
+	}
+
+	/// Experience an event: drift context toward the memory's input
+	/// context, then bind the memory to the context it followed.
+	pub fn present(&mut self, memory: usize) {
+		self.present_with_sources::<&str>(memory, &[]);
+	}
+
+	/// Experience an event that happened in the given source context
+	/// (application, location, project, ...).
+	///
+	/// Source features are added to the memory's input context and bound
+	/// to it, weighted by `source_weight`. Only the temporal part of the
+	/// preceding context is bound, so a memory is not cued by the source
+	/// of the event before it.
+	pub fn present_with_sources<S: AsRef<str>>(&mut self, memory: usize, sources: &[S]) {
+		let item = self.item_for(memory);
+		let source = self.source_vector(sources);
+		add_scaled(
+			&mut self.item_to_context[item],
+			&source,
+			self.config.source_weight,
+		);
+
+		let input = normalized(&self.item_to_context[item]);
+		let mut previous = self.context.clone();
+		drift_context(&mut self.context, &input, self.config.beta_encoding);
+
+		for &unit in self.source_units.values() {
+			if let Some(value) = previous.get_mut(unit) {
+				*value = 0.0;
+			}
+		}
+		add_scaled(
+			&mut self.item_to_context[item],
+			&previous,
+			self.config.gamma,
+		);
+		add_scaled(&mut self.context_to_item[item], &previous, 1.0);
+		add_scaled(
+			&mut self.context_to_item[item],
+			&source,
+			self.config.source_weight,
+		);
+	}
+
+	/// Cue by source: drift context toward the given source features
+	/// ("when I was in VS Code").
+	///
+	/// Unknown sources are ignored. Returns `false` (and leaves context
+	/// unchanged) if none are known.
+	pub fn cue_sources<S: AsRef<str>>(&mut self, sources: &[S], beta: f64) -> bool {
+		let known: Vec<&str> = sources
+			.iter()
+			.map(AsRef::as_ref)
+			.filter(|s| self.source_units.contains_key(*s))
+			.collect();
+		if known.is_empty() {
+			return false;
+		}
+		let input = self.source_vector(&known);
+		drift_context(&mut self.context, &input, beta);
+		true
+	}
+
+	/// Recall a memory: drift context toward the context it reinstates.
+	///
+	/// Returns `false` (and leaves context unchanged) if the memory was
+	/// never presented.
+	pub fn reinstate(&mut self, memory: usize) -> bool {
+		let Some(&item) = self.items.get(&memory) else {
+			return false;
+		};
+		let input = normalized(&self.item_to_context[item]);
+		drift_context(&mut self.context, &input, self.config.beta_retrieval);
+		true
+	}
+
+	/// Drift context back toward the start-of-list state.
+	pub fn reinstate_start(&mut self, beta: f64) {
+		drift_context(&mut self.context, &[1.0], beta);
+	}
+
+	/// Support the current context gives each item, in first-presentation
+	/// order.
+	///
+	/// `support_i = max(0, M^TF_i · t)`
+	#[must_use]
+	pub fn support(&self) -> Vec<(usize, f64)> {
+		self.memories
+			.iter()
+			.zip(&self.context_to_item)
+			.map(|(&memory, bound)| (memory, dot(bound, &self.context).max(0.0)))
+			.collect()
+	}
+
+	/// Support as an activation vector indexed by memory.
+	///
+	/// Memories at or beyond `num_memories`, or never presented, get 0.
+	#[must_use]
+	pub fn activations(&self, num_memories: usize) -> Vec<f64> {
+		let mut activations = vec![0.0; num_memories];
+		for (memory, support) in self.support() {
+			if memory < num_memories {
+				activations[memory] = support;
+			}
+		}
+		activations
+	}
+
+	/// Context unit assigned to a memory, if it was presented.
+	#[must_use]
+	pub fn unit_of(&self, memory: usize) -> Option<usize> {
+		self.items.get(&memory).map(|&item| self.item_units[item])
+	}
+
+	/// Context unit assigned to a source feature, if it was seen.
+	#[must_use]
+	pub fn source_unit(&self, source: &str) -> Option<usize> {
+		self.source_units.get(source).copied()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn norm(v: &[f64]) -> f64 {
+		dot(v, v).sqrt()
+	}
+
+	#[test]
+	fn test_drift_keeps_unit_length() {
+		let mut context = vec![1.0];
+		drift_context(&mut context, &[0.0, 1.0], 0.6);
+		assert!((norm(&context) - 1.0).abs() < 1e-12);
+		assert!((context[1] - 0.6).abs() < 1e-12);
+
+		drift_context(&mut context, &[0.0, 0.0, 1.0], 0.3);
+		assert!((norm(&context) - 1.0).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_context_drifts_away_from_start() {
+		let context =
+			TemporalContext::from_episode(&[5, 6, 7, 8], TemporalContextConfig::default());
+		assert_eq!(context.len(), 4);
+		assert_eq!(context.unit_of(7), Some(3));
+		assert!(context.context()[0] < 0.5);
+		assert!((norm(context.context()) - 1.0).abs() < 1e-9);
+
+		// Most recent item is best supported by end-of-list context (recency)
+		let support = context.support();
+		let best = support
+			.iter()
+			.max_by(|a, b| a.1.total_cmp(&b.1))
+			.map(|(m, _)| *m);
+		assert_eq!(best, Some(8));
+	}
+
+	#[test]
+	fn test_reinstatement_favors_forward_neighbor() {
+		let mut context =
+			TemporalContext::from_episode(&[0, 1, 2, 3, 4], TemporalContextConfig::default());
+		assert!(context.reinstate(2));
+		assert!(!context.reinstate(99));
+
+		let activations = context.activations(5);
+		assert!(activations[3] > activations[1]);
+		assert!(activations[1] > activations[0]);
+	}
+
+	#[test]
+	fn test_source_cue_retrieves_memories_from_source() {
+		let mut context = TemporalContext::new(TemporalContextConfig::default());
+		context.present_with_sources(0, &["vscode"]);
+		context.present_with_sources(1, &["vscode"]);
+		context.present_with_sources(2, &["paris"]);
+		context.present_with_sources(3, &["paris"]);
+		context.present_with_sources(4, &["slack"]);
+		assert!(context.source_unit("paris").is_some());
+		assert_eq!(context.source_unit("tokyo"), None);
+
+		assert!(!context.cue_sources(&["tokyo"], 0.9));
+		assert!(context.cue_sources(&["vscode"], 0.9));
+		let activations = context.activations(5);
+		assert!(activations[0] > activations[2]);
+		assert!(activations[1] > activations[3]);
+
+		// Recalling a Paris memory reinstates the Paris source
+		let unit = context.source_unit("paris").unwrap_or_default();
+		let before = context.context()[unit];
+		assert!(context.reinstate(2));
+		assert!(context.context()[unit] > before);
+	}
+}
@@ -0,0 +1,317 @@
+//! Graph Diffing
+//!
+//! What changed between two versions of a [`MemoryGraph`]: nodes added or
+//! removed and associations added, removed, or modified. A diff can be
+//! replayed onto another copy of the older graph with [`apply_diff`],
+//! which is how two devices holding the same memory state stay in sync,
+//! and read directly to audit what a consolidation pass did.
+//!
+//! Nodes are dense indices, so node changes are a change in node count.
+//! Associations are keyed by `(source, target)`; an association counts as
+//! changed when its strengths, type, or decay bookkeeping differ. Floats
+//! are compared bit for bit, so a diff never hides a rounding change.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Classify, ErrorKind};
+use crate::graph::{EdgeMetadata, MemoryGraph};
+use crate::spreading::Association;
+
+/// An association together with its bookkeeping.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EdgeState {
+	/// The association
+	pub association: Association,
+	/// Its decay bookkeeping
+	pub metadata: EdgeMetadata,
+}
+
+/// An association whose state differs between graphs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EdgeChange {
+	/// State in the older graph
+	pub before: EdgeState,
+	/// State in the newer graph
+	pub after: EdgeState,
+}
+
+/// Differences from one graph to another.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GraphDiff {
+	/// Node count of the older graph
+	pub nodes_before: usize,
+	/// Node count of the newer graph
+	pub nodes_after: usize,
+	/// Associations only in the newer graph, in its insertion order
+	pub added_edges: Vec<EdgeState>,
+	/// Associations only in the older graph, in its insertion order
+	pub removed_edges: Vec<EdgeState>,
+	/// Associations in both whose state changed, in the newer graph's order
+	pub changed_edges: Vec<EdgeChange>,
+}
+
+impl GraphDiff {
+	/// Whether the graphs are identical.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.nodes_before == self.nodes_after
+			&& self.added_edges.is_empty()
+			&& self.removed_edges.is_empty()
+			&& self.changed_edges.is_empty()
+	}
+
+	/// Nodes present only in the newer graph.
+	#[must_use]
+	pub fn added_nodes(&self) -> std::ops::Range<usize> {
+		self.nodes_before..self.nodes_after.max(self.nodes_before)
+	}
+
+	/// Nodes present only in the older graph.
+	#[must_use]
+	pub fn removed_nodes(&self) -> std::ops::Range<usize> {
+		self.nodes_after..self.nodes_before.max(self.nodes_after)
+	}
+}
+
+/// Error type for applying a diff.
+#[derive(Debug, thiserror::Error)]
+pub enum DiffError {
+	/// The graph's node count is not the diff's starting count.
+	#[error("Node count mismatch: diff expects {expected}, graph has {actual}")]
+	NodeCountMismatch {
+		/// `nodes_before` of the diff
+		expected: usize,
+		/// Node count of the graph
+		actual: usize,
+	},
+
+	/// An association the diff adds already exists.
+	#[error("Association {from} -> {to} already exists")]
+	EdgeExists {
+		/// Source node
+		from: usize,
+		/// Target node
+		to: usize,
+	},
+
+	/// An association the diff removes or changes is missing or differs
+	/// from the diff's `before` state.
+	#[error("Association {from} -> {to} does not match the diff")]
+	EdgeConflict {
+		/// Source node
+		from: usize,
+		/// Target node
+		to: usize,
+	},
+}
+
+impl Classify for DiffError {
+	fn kind(&self) -> ErrorKind {
+		ErrorKind::InvalidInput
+	}
+}
+
+/// Compute the changes that turn `before` into `after`.
+#[must_use]
+pub fn diff_graphs(before: &MemoryGraph, after: &MemoryGraph) -> GraphDiff {
+	let removed_edges = edge_states(before)
+		.filter(|state| {
+			after
+				.association(state.association.source, state.association.target)
+				.is_none()
+		})
+		.collect();
+
+	let mut added_edges = Vec::new();
+	let mut changed_edges = Vec::new();
+	for state in edge_states(after) {
+		match edge_state(before, state.association.source, state.association.target) {
+			None => added_edges.push(state),
+			Some(old) if !same_state(&old, &state) => changed_edges.push(EdgeChange {
+				before: old,
+				after: state,
+			}),
+			Some(_) => {}
+		}
+	}
+
+	GraphDiff {
+		nodes_before: before.num_nodes(),
+		nodes_after: after.num_nodes(),
+		added_edges,
+		removed_edges,
+		changed_edges,
+	}
+}
+
+/// Replay a diff onto a copy of its older graph.
+///
+/// Every removal and change is checked against the diff's `before` state
+/// first, so a graph that has drifted from the diff's base is rejected
+/// unchanged rather than half-updated.
+///
+/// # Errors
+///
+/// Returns [`DiffError::NodeCountMismatch`] if the node count differs
+/// from `nodes_before`, [`DiffError::EdgeConflict`] if a removed or
+/// changed association is missing or differs, and
+/// [`DiffError::EdgeExists`] if an added association is already present.
+pub fn apply_diff(graph: &mut MemoryGraph, diff: &GraphDiff) -> Result<(), DiffError> {
+	if graph.num_nodes() != diff.nodes_before {
+		return Err(DiffError::NodeCountMismatch {
+			expected: diff.nodes_before,
+			actual: graph.num_nodes(),
+		});
+	}
+	let expected = diff
+		.removed_edges
+		.iter()
+		.chain(diff.changed_edges.iter().map(|change| &change.before));
+	for state in expected {
+		let (source, target) = (state.association.source, state.association.target);
+		if !edge_state(graph, source, target).is_some_and(|current| same_state(&current, state)) {
+			return Err(DiffError::EdgeConflict {
+				from: source,
+				to: target,
+			});
+		}
+	}
+	for state in &diff.added_edges {
+		let (source, target) = (state.association.source, state.association.target);
+		if graph.association(source, target).is_some() {
+			return Err(DiffError::EdgeExists {
+				from: source,
+				to: target,
+			});
+		}
+	}
+
+	let _ = graph.remove_associations_where(|a, _| {
+		diff.removed_edges.iter().any(|state| {
+			state.association.source == a.source && state.association.target == a.target
+		})
+	});
+	let _ = graph.truncate_nodes(diff.nodes_after);
+	while graph.num_nodes() < diff.nodes_after {
+		let _ = graph.add_node();
+	}
+	for state in diff
+		.changed_edges
+		.iter()
+		.map(|change| &change.after)
+		.chain(&diff.added_edges)
+	{
+		let (source, target) = (state.association.source, state.association.target);
+		let _ = graph.insert_association(state.association.clone());
+		if let Some(meta) = graph.metadata_mut(source, target) {
+			*meta = state.metadata;
+		}
+	}
+
+	Ok(())
+}
+
+fn edge_states(graph: &MemoryGraph) -> impl Iterator<Item = EdgeState> + '_ {
+	graph.associations().iter().map(|association| EdgeState {
+		association: association.clone(),
+		metadata: graph
+			.metadata(association.source, association.target)
+			.copied()
+			.unwrap_or_default(),
+	})
+}
+
+fn edge_state(graph: &MemoryGraph, source: usize, target: usize) -> Option<EdgeState> {
+	Some(EdgeState {
+		association: graph.association(source, target)?.clone(),
+		metadata: graph.metadata(source, target).copied().unwrap_or_default(),
+	})
+}
+
+/// Bitwise equality of two edge states.
+fn same_state(a: &EdgeState, b: &EdgeState) -> bool {
+	let (x, y) = (&a.association, &b.association);
+	x.source == y.source
+		&& x.target == y.target
+		&& x.forward_strength.to_bits() == y.forward_strength.to_bits()
+		&& x.backward_strength.to_bits() == y.backward_strength.to_bits()
+		&& x.association_type == y.association_type
+		&& a.metadata.state == b.metadata.state
+		&& a.metadata.last_reinforced_ms.map(f64::to_bits)
+			== b.metadata.last_reinforced_ms.map(f64::to_bits)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::AssociationType;
+
+	fn make_assoc(source: usize, target: usize, strength: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	fn graphs() -> (MemoryGraph, MemoryGraph) {
+		let before = MemoryGraph::from_associations(
+			3,
+			vec![
+				make_assoc(0, 1, 0.5),
+				make_assoc(1, 2, 0.5),
+				make_assoc(2, 0, 0.5),
+			],
+		);
+		let mut after = before.clone();
+		let _ = after.remove_association(1, 2);
+		let _ = after.insert_association(make_assoc(0, 1, 0.9));
+		let _ = after.add_node();
+		let _ = after.insert_association(make_assoc(2, 3, 0.3));
+		let _ = after.mark_reinforced(2, 0, 100.0);
+		(before, after)
+	}
+
+	#[test]
+	fn test_diff_graphs() {
+		let (before, after) = graphs();
+		let diff = diff_graphs(&before, &after);
+
+		assert_eq!(diff.added_nodes(), 3..4);
+		assert!(diff.removed_nodes().is_empty());
+		assert_eq!(diff.added_edges.len(), 1);
+		assert_eq!(diff.added_edges[0].association.target, 3);
+		assert_eq!(diff.removed_edges.len(), 1);
+		assert_eq!(diff.removed_edges[0].association.source, 1);
+		// Strength change on 0 → 1 and a new reinforcement stamp on 2 → 0
+		assert_eq!(diff.changed_edges.len(), 2);
+
+		assert!(diff_graphs(&after, &after).is_empty());
+	}
+
+	#[test]
+	fn test_apply_diff_round_trip() {
+		let (before, after) = graphs();
+		let diff = diff_graphs(&before, &after);
+
+		let mut replica = before.clone();
+		assert!(apply_diff(&mut replica, &diff).is_ok());
+		assert!(diff_graphs(&replica, &after).is_empty());
+
+		// Reverse direction shrinks the graph back
+		let mut reverted = after.clone();
+		assert!(apply_diff(&mut reverted, &diff_graphs(&after, &before)).is_ok());
+		assert!(diff_graphs(&reverted, &before).is_empty());
+
+		// A replica that drifted is rejected untouched
+		let mut drifted = before;
+		let _ = drifted.insert_association(make_assoc(0, 1, 0.1));
+		assert!(matches!(
+			apply_diff(&mut drifted, &diff),
+			Err(DiffError::EdgeConflict { from: 0, to: 1 })
+		));
+		assert_eq!(drifted.num_nodes(), 3);
+	}
+}
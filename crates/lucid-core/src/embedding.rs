@@ -10,6 +10,8 @@ use parking_lot::Mutex;
 use std::path::PathBuf;
 use tokenizers::Tokenizer;
 
+use crate::error::{Classify, ErrorKind};
+
 /// Default model directory: `~/.lucid/models`
 fn default_model_dir() -> PathBuf {
 	dirs::home_dir()
@@ -66,6 +68,15 @@ pub enum EmbeddingError {
 	Shape(#[from] ndarray::ShapeError),
 }
 
+impl Classify for EmbeddingError {
+	fn kind(&self) -> ErrorKind {
+		match self {
+			Self::NotFound(_) => ErrorKind::MissingDependency,
+			Self::Ort(_) | Self::Tokenizer(_) | Self::Shape(_) => ErrorKind::Internal,
+		}
+	}
+}
+
 impl EmbeddingModel {
 	/// Load the ONNX model and tokenizer from disk.
 	///
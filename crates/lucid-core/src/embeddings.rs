@@ -0,0 +1,693 @@
+//! Embedding Similarity
+//!
+//! Vector similarity over `f32` embeddings — the semantic counterpart to
+//! graph spreading. Embedding models emit `f32`, so these kernels work on
+//! `&[f32]` directly instead of widening to `f64`.
+//!
+//! Kernels split each vector into fixed-width lanes with independent
+//! accumulators. Without a loop-carried dependency between lanes, LLVM
+//! lowers them to packed SIMD (SSE/AVX on `x86_64`, NEON on `aarch64`)
+//! while the crate stays free of `unsafe` intrinsics. Build with
+//! `-C target-cpu=native` to let it use the widest registers available.
+//!
+//! Kernels use plain `x * y + acc` rather than `mul_add`: without an FMA
+//! target feature, `mul_add` becomes a libm call that blocks vectorization.
+//!
+//! For large stores, embeddings can be quantized: [`quantize_int8`] keeps
+//! one byte per dimension (4× smaller), and [`ProductQuantizer`] keeps one
+//! byte per subspace (32-64× smaller for typical settings).
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Classify, ErrorKind};
+use crate::rng::SplitMix64;
+
+/// Accumulator lanes per kernel (one 256-bit register of `f32`).
+const LANES: usize = 8;
+
+// ============================================================================
+// Kernels
+// ============================================================================
+
+/// Lane-parallel `Σ f(a_i, b_i)` over equal-length slices.
+#[inline]
+fn lane_sum(a: &[f32], b: &[f32], f: impl Fn(f32, f32, f32) -> f32) -> f32 {
+	let mut acc = [0.0f32; LANES];
+	let chunks_a = a.chunks_exact(LANES);
+	let chunks_b = b.chunks_exact(LANES);
+	let (tail_a, tail_b) = (chunks_a.remainder(), chunks_b.remainder());
+
+	for (ca, cb) in chunks_a.zip(chunks_b) {
+		for lane in 0..LANES {
+			acc[lane] = f(ca[lane], cb[lane], acc[lane]);
+		}
+	}
+
+	let mut total = acc.iter().sum::<f32>();
+	for (&x, &y) in tail_a.iter().zip(tail_b) {
+		total = f(x, y, total);
+	}
+	total
+}
+
+/// Dot product `Σ a_i × b_i`.
+///
+/// Returns 0.0 if the vectors have different lengths.
+#[inline]
+#[must_use]
+#[allow(clippy::suboptimal_flops)]
+pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+	if a.len() != b.len() {
+		return 0.0;
+	}
+	lane_sum(a, b, |x, y, acc| x * y + acc)
+}
+
+/// Euclidean norm `‖a‖`.
+#[inline]
+#[must_use]
+#[allow(clippy::suboptimal_flops)]
+pub fn norm(a: &[f32]) -> f32 {
+	lane_sum(a, a, |x, _, acc| x * x + acc).sqrt()
+}
+
+/// Squared Euclidean distance `Σ (a_i - b_i)²`.
+///
+/// Returns `f32::INFINITY` if the vectors have different lengths.
+#[inline]
+#[must_use]
+#[allow(clippy::suboptimal_flops)]
+pub fn squared_euclidean(a: &[f32], b: &[f32]) -> f32 {
+	if a.len() != b.len() {
+		return f32::INFINITY;
+	}
+	lane_sum(a, b, |x, y, acc| {
+		let d = x - y;
+		d * d + acc
+	})
+}
+
+/// Euclidean distance `‖a - b‖`.
+///
+/// Returns `f32::INFINITY` if the vectors have different lengths.
+#[inline]
+#[must_use]
+pub fn euclidean(a: &[f32], b: &[f32]) -> f32 {
+	squared_euclidean(a, b).sqrt()
+}
+
+/// Cosine similarity `a·b / (‖a‖ × ‖b‖)`.
+///
+/// Returns 0.0 if either vector is zero or the lengths differ.
+#[inline]
+#[must_use]
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+	cosine_with_norm(a, norm(a), b)
+}
+
+#[inline]
+fn cosine_with_norm(a: &[f32], norm_a: f32, b: &[f32]) -> f32 {
+	let magnitude = norm_a * norm(b);
+	if magnitude == 0.0 || a.len() != b.len() {
+		0.0
+	} else {
+		dot(a, b) / magnitude
+	}
+}
+
+// ============================================================================
+// Batch Scoring
+// ============================================================================
+
+/// Similarity metric for batch scoring.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Metric {
+	/// Cosine similarity (default)
+	#[default]
+	Cosine,
+	/// Raw dot product (equals cosine for unit-normalized embeddings)
+	Dot,
+	/// Negated Euclidean distance, so higher is still more similar
+	Euclidean,
+}
+
+/// Score one query against many vectors; higher is more similar.
+///
+/// For cosine, the query norm is computed once.
+#[must_use]
+pub fn similarity_batch<V: AsRef<[f32]>>(query: &[f32], vectors: &[V], metric: Metric) -> Vec<f32> {
+	match metric {
+		Metric::Cosine => {
+			let query_norm = norm(query);
+			vectors
+				.iter()
+				.map(|v| cosine_with_norm(query, query_norm, v.as_ref()))
+				.collect()
+		}
+		Metric::Dot => vectors.iter().map(|v| dot(query, v.as_ref())).collect(),
+		Metric::Euclidean => vectors
+			.iter()
+			.map(|v| -euclidean(query, v.as_ref()))
+			.collect(),
+	}
+}
+
+/// The `k` vectors most similar to `query`, as `(index, similarity)`.
+///
+/// Sorted by descending similarity; ties keep the lower index first.
+#[must_use]
+pub fn top_k_similar<V: AsRef<[f32]>>(
+	query: &[f32],
+	vectors: &[V],
+	k: usize,
+	metric: Metric,
+) -> Vec<(usize, f32)> {
+	let mut scored: Vec<(usize, f32)> = similarity_batch(query, vectors, metric)
+		.into_iter()
+		.enumerate()
+		.collect();
+	scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+	scored.truncate(k);
+	scored
+}
+
+// ============================================================================
+// Int8 Quantization
+// ============================================================================
+
+/// An embedding stored as signed bytes with one symmetric scale.
+///
+/// `x_i ≈ codes_i × scale`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Int8Vector {
+	/// Quantized components in `[-127, 127]`
+	pub codes: Vec<i8>,
+	/// Value of one quantization step
+	pub scale: f32,
+}
+
+/// Quantize to int8 with scale `max|x_i| / 127`.
+///
+/// A zero vector gets scale 0.0 and all-zero codes.
+#[must_use]
+pub fn quantize_int8(vector: &[f32]) -> Int8Vector {
+	let max_abs = vector.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+	if max_abs == 0.0 || !max_abs.is_finite() {
+		return Int8Vector {
+			codes: vec![0; vector.len()],
+			scale: 0.0,
+		};
+	}
+
+	let scale = max_abs / 127.0;
+	#[allow(clippy::cast_possible_truncation)]
+	let codes = vector
+		.iter()
+		.map(|x| (x / scale).round().clamp(-127.0, 127.0) as i8)
+		.collect();
+	Int8Vector { codes, scale }
+}
+
+/// Reconstruct an approximate `f32` vector.
+#[must_use]
+pub fn dequantize_int8(vector: &Int8Vector) -> Vec<f32> {
+	vector
+		.codes
+		.iter()
+		.map(|&c| f32::from(c) * vector.scale)
+		.collect()
+}
+
+/// Integer dot product of the codes.
+fn code_dot(a: &[i8], b: &[i8]) -> i32 {
+	a.iter()
+		.zip(b)
+		.map(|(&x, &y)| i32::from(x) * i32::from(y))
+		.sum()
+}
+
+/// Dot product of two int8 vectors, accumulated in integers.
+///
+/// Returns 0.0 if the vectors have different lengths.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn dot_int8(a: &Int8Vector, b: &Int8Vector) -> f32 {
+	if a.codes.len() != b.codes.len() {
+		return 0.0;
+	}
+	code_dot(&a.codes, &b.codes) as f32 * a.scale * b.scale
+}
+
+/// Cosine similarity of two int8 vectors.
+///
+/// Scales cancel, so only the codes are compared. Returns 0.0 if either
+/// vector is zero or the lengths differ.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn cosine_int8(a: &Int8Vector, b: &Int8Vector) -> f32 {
+	if a.codes.len() != b.codes.len() {
+		return 0.0;
+	}
+	let magnitude =
+		((code_dot(&a.codes, &a.codes) as f32) * (code_dot(&b.codes, &b.codes) as f32)).sqrt();
+	if magnitude == 0.0 {
+		0.0
+	} else {
+		code_dot(&a.codes, &b.codes) as f32 / magnitude
+	}
+}
+
+// ============================================================================
+// Product Quantization
+// ============================================================================
+
+/// Error type for product quantizer training.
+#[derive(Debug, thiserror::Error)]
+pub enum QuantizationError {
+	/// No training vectors were given.
+	#[error("No training vectors")]
+	Empty,
+
+	/// Training vectors have different lengths.
+	#[error("Dimension mismatch: expected {expected}, got {actual}")]
+	DimensionMismatch {
+		/// Length of the first training vector
+		expected: usize,
+		/// Length of the offending vector
+		actual: usize,
+	},
+
+	/// The dimension does not split evenly into subspaces.
+	#[error("Dimension {dimension} is not divisible into {subspaces} subspaces")]
+	InvalidSubspaces {
+		/// Vector dimension
+		dimension: usize,
+		/// Requested subspace count
+		subspaces: usize,
+	},
+}
+
+impl Classify for QuantizationError {
+	fn kind(&self) -> ErrorKind {
+		ErrorKind::InvalidInput
+	}
+}
+
+/// Configuration for product quantizer training.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProductQuantizerConfig {
+	/// Subspaces per vector; one code byte each (default: 8)
+	pub num_subspaces: usize,
+	/// Centroids per subspace, at most 256 (default: 256)
+	pub num_centroids: usize,
+	/// k-means iterations (default: 20)
+	pub iterations: usize,
+	/// Seed for centroid initialization (default: 0)
+	pub seed: u64,
+}
+
+impl Default for ProductQuantizerConfig {
+	fn default() -> Self {
+		Self {
+			num_subspaces: 8,
+			num_centroids: 256,
+			iterations: 20,
+			seed: 0,
+		}
+	}
+}
+
+/// Product quantizer (Jégou et al., 2011).
+///
+/// Each vector is split into `M` subspaces and every subvector is replaced
+/// by the index of its nearest centroid, learned per subspace with k-means.
+/// Distances to a query are computed asymmetrically: the query stays `f32`
+/// and only the database side is quantized.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProductQuantizer {
+	dimension: usize,
+	sub_dimension: usize,
+	num_centroids: usize,
+	/// Flattened centroids per subspace: `num_centroids × sub_dimension`
+	codebooks: Vec<Vec<f32>>,
+}
+
+/// Per-query table of squared distances from each query subvector to each
+/// centroid, for fast asymmetric distance over many codes.
+#[derive(Clone, Debug)]
+pub struct PqDistanceTable {
+	num_centroids: usize,
+	/// `num_subspaces × num_centroids` squared distances
+	distances: Vec<f32>,
+}
+
+impl PqDistanceTable {
+	/// Approximate squared Euclidean distance from the query to `codes`.
+	#[must_use]
+	pub fn squared_distance(&self, codes: &[u8]) -> f32 {
+		codes
+			.iter()
+			.enumerate()
+			.map(|(sub, &code)| self.distances[sub * self.num_centroids + usize::from(code)])
+			.sum()
+	}
+}
+
+impl ProductQuantizer {
+	/// Learn codebooks from training vectors.
+	///
+	/// Uses at most as many centroids as there are training vectors.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `vectors` is empty, lengths differ, or the
+	/// dimension is not divisible by `config.num_subspaces`.
+	pub fn train<V: AsRef<[f32]>>(
+		vectors: &[V],
+		config: &ProductQuantizerConfig,
+	) -> Result<Self, QuantizationError> {
+		let dimension = vectors
+			.first()
+			.ok_or(QuantizationError::Empty)?
+			.as_ref()
+			.len();
+		if let Some(bad) = vectors.iter().find(|v| v.as_ref().len() != dimension) {
+			return Err(QuantizationError::DimensionMismatch {
+				expected: dimension,
+				actual: bad.as_ref().len(),
+			});
+		}
+		let subspaces = config.num_subspaces;
+		if subspaces == 0 || dimension == 0 || dimension % subspaces != 0 {
+			return Err(QuantizationError::InvalidSubspaces {
+				dimension,
+				subspaces,
+			});
+		}
+
+		let sub_dimension = dimension / subspaces;
+		let num_centroids = config.num_centroids.clamp(1, 256).min(vectors.len());
+		let mut rng = SplitMix64::new(config.seed);
+
+		let codebooks = (0..subspaces)
+			.map(|sub| {
+				let range = sub * sub_dimension..(sub + 1) * sub_dimension;
+				let points: Vec<&[f32]> =
+					vectors.iter().map(|v| &v.as_ref()[range.clone()]).collect();
+				kmeans(&points, num_centroids, config.iterations, &mut rng)
+			})
+			.collect();
+
+		Ok(Self {
+			dimension,
+			sub_dimension,
+			num_centroids,
+			codebooks,
+		})
+	}
+
+	/// Vector length accepted by this quantizer.
+	#[must_use]
+	pub const fn dimension(&self) -> usize {
+		self.dimension
+	}
+
+	/// Code bytes per vector.
+	#[must_use]
+	pub fn code_size(&self) -> usize {
+		self.codebooks.len()
+	}
+
+	fn centroid(&self, sub: usize, code: usize) -> &[f32] {
+		&self.codebooks[sub][code * self.sub_dimension..(code + 1) * self.sub_dimension]
+	}
+
+	/// Encode a vector as one centroid index per subspace.
+	///
+	/// Returns an empty code if the length differs from the dimension.
+	#[must_use]
+	pub fn encode(&self, vector: &[f32]) -> Vec<u8> {
+		if vector.len() != self.dimension {
+			return Vec::new();
+		}
+		vector
+			.chunks_exact(self.sub_dimension)
+			.enumerate()
+			.map(|(sub, subvector)| {
+				let nearest = (0..self.num_centroids)
+					.map(|code| squared_euclidean(subvector, self.centroid(sub, code)))
+					.enumerate()
+					.min_by(|a, b| a.1.total_cmp(&b.1))
+					.map_or(0, |(code, _)| code);
+				// At most 256 centroids, so every index fits in a byte
+				u8::try_from(nearest).unwrap_or(u8::MAX)
+			})
+			.collect()
+	}
+
+	/// Reconstruct an approximate vector from its codes.
+	#[must_use]
+	pub fn decode(&self, codes: &[u8]) -> Vec<f32> {
+		codes
+			.iter()
+			.take(self.codebooks.len())
+			.enumerate()
+			.flat_map(|(sub, &code)| self.centroid(sub, usize::from(code)).iter().copied())
+			.collect()
+	}
+
+	/// Precompute query-to-centroid distances.
+	///
+	/// Build once per query, then score each stored code with
+	/// [`PqDistanceTable::squared_distance`] in `M` lookups.
+	#[must_use]
+	pub fn distance_table(&self, query: &[f32]) -> PqDistanceTable {
+		let distances = query
+			.chunks_exact(self.sub_dimension)
+			.take(self.codebooks.len())
+			.enumerate()
+			.flat_map(|(sub, subvector)| {
+				(0..self.num_centroids)
+					.map(move |code| squared_euclidean(subvector, self.centroid(sub, code)))
+			})
+			.collect();
+		PqDistanceTable {
+			num_centroids: self.num_centroids,
+			distances,
+		}
+	}
+}
+
+/// Lloyd's k-means over subvectors; returns flattened centroids.
+///
+/// Centroids start at distinct random samples. A centroid that loses all
+/// its points keeps its previous position.
+fn kmeans(points: &[&[f32]], k: usize, iterations: usize, rng: &mut SplitMix64) -> Vec<f32> {
+	let dim = points[0].len();
+
+	// Partial Fisher-Yates shuffle picks k distinct starting points
+	let mut order: Vec<usize> = (0..points.len()).collect();
+	for i in 0..k {
+		#[allow(clippy::cast_possible_truncation)]
+		let j = i + (rng.next_u64() % (points.len() - i) as u64) as usize;
+		order.swap(i, j);
+	}
+	let mut centroids: Vec<f32> = order[..k]
+		.iter()
+		.flat_map(|&i| points[i].iter().copied())
+		.collect();
+
+	let mut assignment = vec![0usize; points.len()];
+	for _ in 0..iterations {
+		let mut changed = false;
+		for (point, assigned) in points.iter().zip(assignment.iter_mut()) {
+			let nearest = centroids
+				.chunks_exact(dim)
+				.map(|c| squared_euclidean(point, c))
+				.enumerate()
+				.min_by(|a, b| a.1.total_cmp(&b.1))
+				.map_or(0, |(code, _)| code);
+			changed |= nearest != *assigned;
+			*assigned = nearest;
+		}
+
+		let mut sums = vec![0.0f32; k * dim];
+		let mut counts = vec![0usize; k];
+		for (point, &cluster) in points.iter().zip(&assignment) {
+			counts[cluster] += 1;
+			for (sum, x) in sums[cluster * dim..(cluster + 1) * dim]
+				.iter_mut()
+				.zip(*point)
+			{
+				*sum += x;
+			}
+		}
+		for (cluster, &count) in counts.iter().enumerate() {
+			if count > 0 {
+				#[allow(clippy::cast_precision_loss)]
+				let inv = 1.0 / count as f32;
+				for (c, sum) in centroids[cluster * dim..(cluster + 1) * dim]
+					.iter_mut()
+					.zip(&sums[cluster * dim..(cluster + 1) * dim])
+				{
+					*c = sum * inv;
+				}
+			}
+		}
+
+		if !changed {
+			break;
+		}
+	}
+	centroids
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Scalar reference implementations.
+	fn dot_ref(a: &[f32], b: &[f32]) -> f32 {
+		a.iter().zip(b).map(|(x, y)| x * y).sum()
+	}
+
+	fn make_vector(len: usize, seed: f32) -> Vec<f32> {
+		#[allow(clippy::cast_precision_loss)]
+		(0..len)
+			.map(|i| (i as f32).mul_add(0.37, seed).sin())
+			.collect()
+	}
+
+	#[test]
+	fn test_kernels_match_scalar() {
+		// Lengths around the lane width exercise the remainder path
+		for len in [0, 1, 7, 8, 9, 31, 768] {
+			let a = make_vector(len, 0.3);
+			let b = make_vector(len, 1.1);
+
+			assert!((dot(&a, &b) - dot_ref(&a, &b)).abs() < 1e-3);
+			assert!((norm(&a) - dot_ref(&a, &a).sqrt()).abs() < 1e-3);
+
+			let diff: Vec<f32> = a.iter().zip(&b).map(|(x, y)| x - y).collect();
+			assert!((euclidean(&a, &b) - dot_ref(&diff, &diff).sqrt()).abs() < 1e-3);
+		}
+	}
+
+	#[test]
+	fn test_cosine() {
+		assert!((cosine(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+		assert!(cosine(&[1.0, 0.0], &[0.0, 3.0]).abs() < 1e-6);
+		assert!((cosine(&[1.0, 0.0], &[-1.0, 0.0]) + 1.0).abs() < 1e-6);
+
+		// Degenerate inputs
+		assert!(cosine(&[0.0, 0.0], &[1.0, 0.0]).abs() < f32::EPSILON);
+		assert!(cosine(&[1.0], &[1.0, 0.0]).abs() < f32::EPSILON);
+		assert!(euclidean(&[1.0], &[1.0, 0.0]).is_infinite());
+	}
+
+	#[test]
+	fn test_top_k_similar() {
+		let vectors = vec![
+			vec![0.0, 1.0, 0.0],
+			vec![1.0, 0.1, 0.0],
+			vec![0.9, 0.0, 0.1],
+			vec![-1.0, 0.0, 0.0],
+		];
+		let query = [1.0, 0.0, 0.0];
+
+		let cosine_top = top_k_similar(&query, &vectors, 2, Metric::Cosine);
+		let indices: Vec<usize> = cosine_top.iter().map(|(i, _)| *i).collect();
+		assert_eq!(indices, vec![1, 2]);
+
+		let euclid_top = top_k_similar(&query, &vectors, 1, Metric::Euclidean);
+		assert_eq!(euclid_top[0].0, 1);
+		assert!(euclid_top[0].1 <= 0.0);
+
+		let scores = similarity_batch(&query, &vectors, Metric::Dot);
+		assert_eq!(scores.len(), 4);
+		assert!((scores[3] + 1.0).abs() < f32::EPSILON);
+	}
+
+	#[test]
+	fn test_int8_quantization() {
+		let a = make_vector(384, 0.3);
+		let b = make_vector(384, 1.1);
+		let qa = quantize_int8(&a);
+		let qb = quantize_int8(&b);
+
+		// Reconstruction error is at most half a step per component
+		let restored = dequantize_int8(&qa);
+		assert!(a
+			.iter()
+			.zip(&restored)
+			.all(|(x, y)| (x - y).abs() <= qa.scale.mul_add(0.5, 1e-6)));
+
+		assert!((cosine_int8(&qa, &qb) - cosine(&a, &b)).abs() < 0.01);
+		assert!((dot_int8(&qa, &qb) - dot(&a, &b)).abs() < 0.05 * dot(&a, &a).sqrt());
+
+		let zero = quantize_int8(&[0.0, 0.0]);
+		assert_eq!(zero.codes, vec![0, 0]);
+		assert!(cosine_int8(&zero, &quantize_int8(&[1.0, 0.0])).abs() < f32::EPSILON);
+	}
+
+	#[test]
+	#[allow(clippy::cast_precision_loss)]
+	fn test_product_quantizer() {
+		let vectors: Vec<Vec<f32>> = (0..300).map(|i| make_vector(32, i as f32 * 0.7)).collect();
+		let config = ProductQuantizerConfig {
+			num_subspaces: 4,
+			num_centroids: 64,
+			..ProductQuantizerConfig::default()
+		};
+		let pq = ProductQuantizer::train(&vectors, &config);
+		assert!(pq.is_ok());
+		let Ok(pq) = pq else { return };
+		assert_eq!(pq.code_size(), 4);
+
+		let codes: Vec<Vec<u8>> = vectors.iter().map(|v| pq.encode(v)).collect();
+		let query = &vectors[10];
+		let table = pq.distance_table(query);
+
+		// Asymmetric distance equals the distance to the decoded vector
+		let decoded = pq.decode(&codes[42]);
+		let direct = squared_euclidean(query, &decoded);
+		assert!((table.squared_distance(&codes[42]) - direct).abs() < 1e-3);
+
+		// Quantization error is well below the typical inter-vector distance
+		let error: f32 = vectors
+			.iter()
+			.zip(&codes)
+			.map(|(v, c)| squared_euclidean(v, &pq.decode(c)))
+			.sum::<f32>()
+			/ vectors.len() as f32;
+		let spread = squared_euclidean(&vectors[0], &vectors[150]);
+		assert!(error < spread * 0.5);
+
+		// The query's own code ranks it among the nearest
+		let mut ranked: Vec<(usize, f32)> = codes
+			.iter()
+			.map(|c| table.squared_distance(c))
+			.enumerate()
+			.collect();
+		ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+		assert!(ranked[..5].iter().any(|(i, _)| *i == 10));
+	}
+
+	#[test]
+	fn test_product_quantizer_rejects_bad_input() {
+		let config = ProductQuantizerConfig::default();
+		let empty: Vec<Vec<f32>> = Vec::new();
+		assert!(matches!(
+			ProductQuantizer::train(&empty, &config),
+			Err(QuantizationError::Empty)
+		));
+		assert!(matches!(
+			ProductQuantizer::train(&[vec![0.0; 10]], &config),
+			Err(QuantizationError::InvalidSubspaces { .. })
+		));
+		assert!(matches!(
+			ProductQuantizer::train(&[vec![0.0; 16], vec![0.0; 8]], &config),
+			Err(QuantizationError::DimensionMismatch { .. })
+		));
+	}
+}
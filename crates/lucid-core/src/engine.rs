@@ -0,0 +1,475 @@
+//! Recall Engine
+//!
+//! One call from a query to ranked memories, running every stage of
+//! retrieval against prebuilt indices:
+//!
+//! 1. Embed the query text (with the `embedding` feature)
+//! 2. Find candidates with the HNSW index
+//! 3. Boost similarity for memories in working memory, then cube it
+//!    (MINERVA 2)
+//! 4. Weight by base-level activation and emotional salience
+//! 5. Spread from the best candidates through the association graph
+//! 6. Spread from the same seeds along episode temporal links (TCM)
+//! 7. Rank by total activation
+//!
+//! Every returned memory carries a [`RecallExplanation`] with each stage's
+//! contribution, so callers can show why something was recalled.
+//!
+//! Spread scores exclude the activation a seed started with, so a memory
+//! found directly is not counted twice.
+
+use serde::{Deserialize, Serialize};
+
+use crate::activation::{
+	combine_activations, compute_base_level, nonlinear_activation, retrieval_probability,
+};
+use crate::ann::HnswIndex;
+#[cfg(feature = "embedding")]
+use crate::embedding::{EmbeddingError, EmbeddingModel};
+use crate::episodes::Episode;
+use crate::graph::AssociationGraph;
+use crate::spreading::{
+	spread_activation_graph, spread_temporal_activation, SpreadingConfig, TemporalSpreadingConfig,
+};
+use crate::working_memory::WorkingMemory;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Configuration for [`RecallEngine`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecallConfig {
+	/// Nearest neighbors fetched from the index; raised to `k` if smaller
+	/// (default: 50)
+	pub candidates: usize,
+	/// Decay rate for memories without their own (default: 0.5)
+	pub decay_rate: f64,
+	/// Retrieval threshold τ (default: 0.3)
+	pub activation_threshold: f64,
+	/// Noise parameter s (default: 0.1)
+	pub noise_parameter: f64,
+	/// Minimum retrieval probability to include (default: 0.0)
+	pub min_probability: f64,
+	/// Best candidates used as spreading seeds (default: 5)
+	pub max_seeds: usize,
+	/// Minimum probe activation for a candidate to seed (default: 0.1)
+	pub seed_threshold: f64,
+	/// Hops of association spreading; 0 disables it (default: 3)
+	pub spreading_depth: usize,
+	/// Association spreading parameters
+	pub spreading: SpreadingConfig,
+	/// Weight of activation spread along temporal links; 0.0 disables
+	/// temporal spreading (default: 1.0)
+	pub temporal_weight: f64,
+	/// Temporal spreading parameters
+	pub temporal: TemporalSpreadingConfig,
+}
+
+impl Default for RecallConfig {
+	fn default() -> Self {
+		Self {
+			candidates: 50,
+			decay_rate: 0.5,
+			activation_threshold: 0.3,
+			noise_parameter: 0.1,
+			min_probability: 0.0,
+			max_seeds: 5,
+			seed_threshold: 0.1,
+			spreading_depth: 3,
+			spreading: SpreadingConfig::default(),
+			temporal_weight: 1.0,
+			temporal: TemporalSpreadingConfig::default(),
+		}
+	}
+}
+
+/// The situation a query is asked in.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RecallContext<'a> {
+	/// Current time (ms)
+	pub current_time_ms: f64,
+	/// Memories currently in focus; each boosts its similarity by
+	/// `1 + activation` (capped at 2x)
+	pub working_memory: Option<&'a WorkingMemory>,
+}
+
+// ============================================================================
+// Output
+// ============================================================================
+
+/// Why a memory was recalled: the value of each stage for it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecallExplanation {
+	/// Index similarity to the query (0 if reached only by spreading)
+	pub similarity: f64,
+	/// Working memory multiplier on similarity (1.0 = not in focus)
+	pub working_memory_boost: f64,
+	/// Boosted similarity cubed, modulated by emotional weight
+	pub probe_activation: f64,
+	/// Base-level activation from access history
+	pub base_level: f64,
+	/// Emotional weight (0-1)
+	pub emotional_weight: f64,
+	/// Activation received over associations from the seeds
+	pub spreading: f64,
+	/// Activation received over temporal links from the seeds, before
+	/// `temporal_weight`
+	pub temporal: f64,
+	/// Whether the memory seeded spreading
+	pub seed: bool,
+}
+
+/// A memory returned by [`RecallEngine`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecalledMemory {
+	/// Memory index
+	pub index: usize,
+	/// Total activation
+	pub score: f64,
+	/// Retrieval probability (0-1)
+	pub probability: f64,
+	/// Per-stage breakdown of `score`
+	pub explanation: RecallExplanation,
+}
+
+// ============================================================================
+// Engine
+// ============================================================================
+
+/// Everything recall reads, borrowed from the caller.
+///
+/// Per-memory slices are indexed by memory; a memory past the end of a
+/// slice has no accesses, `config.decay_rate`, and emotional weight 0.5.
+/// Index ids are memory indices.
+pub struct RecallEngine<'a> {
+	/// Embedding index over memories
+	pub index: &'a HnswIndex,
+	/// Association graph over memories
+	pub graph: &'a AssociationGraph,
+	/// Episodes whose temporal links carry spreading through time
+	pub episodes: &'a [Episode],
+	/// Access timestamps (ms) per memory
+	pub access_histories_ms: &'a [Vec<f64>],
+	/// Decay rate per memory
+	pub decay_rates: &'a [f64],
+	/// Emotional weight per memory (0-1)
+	pub emotional_weights: &'a [f64],
+	/// Pipeline parameters
+	pub config: RecallConfig,
+}
+
+impl RecallEngine<'_> {
+	/// Embed `query_text` and return the `k` best memories for it.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the query cannot be embedded.
+	#[cfg(feature = "embedding")]
+	pub fn recall(
+		&self,
+		model: &EmbeddingModel,
+		query_text: &str,
+		context: &RecallContext<'_>,
+		k: usize,
+	) -> Result<Vec<RecalledMemory>, EmbeddingError> {
+		let query = model.embed(query_text)?;
+		Ok(self.recall_embedding(&query, context, k))
+	}
+
+	/// The `k` best memories for an already embedded query.
+	///
+	/// Returns an empty list if the query length differs from the index
+	/// dimension.
+	#[must_use]
+	pub fn recall_embedding(
+		&self,
+		query: &[f32],
+		context: &RecallContext<'_>,
+		k: usize,
+	) -> Vec<RecalledMemory> {
+		if k == 0 {
+			return Vec::new();
+		}
+		#[cfg(feature = "metrics")]
+		let started = std::time::Instant::now();
+		let config = &self.config;
+		let now = context.current_time_ms;
+		let hits = self.index.search(query, config.candidates.max(k));
+		if hits.is_empty() {
+			return Vec::new();
+		}
+
+		let n = hits
+			.iter()
+			.map(|&(id, _)| id + 1)
+			.chain(
+				self.episodes
+					.iter()
+					.flat_map(|e| e.events.iter().map(|&m| m + 1)),
+			)
+			.fold(self.graph.num_nodes(), usize::max);
+
+		let emotional = |i: usize| self.emotional_weights.get(i).copied().unwrap_or(0.5);
+		let base_level = |i: usize| {
+			let decay_rate = self
+				.decay_rates
+				.get(i)
+				.copied()
+				.unwrap_or(config.decay_rate);
+			self.access_histories_ms
+				.get(i)
+				.map_or(f64::NEG_INFINITY, |history| {
+					compute_base_level(history, now, decay_rate)
+				})
+		};
+
+		// 1. Direct matches: boosted, cubed similarity
+		let mut similarity = vec![0.0; n];
+		let mut boost = vec![1.0; n];
+		let mut probe = vec![0.0; n];
+		for &(id, sim) in &hits {
+			let sim = f64::from(sim);
+			let wm_boost = context
+				.working_memory
+				.map_or(1.0, |wm| 1.0 + wm.activation(id, now).clamp(0.0, 1.0));
+			similarity[id] = sim;
+			boost[id] = wm_boost;
+			probe[id] = nonlinear_activation((sim * wm_boost).min(1.0));
+		}
+
+		// 2. Seeds: strongest direct matches by initial activation
+		let mut seeds: Vec<(usize, f64)> = hits
+			.iter()
+			.filter(|&&(id, _)| probe[id] > config.seed_threshold)
+			.map(|&(id, _)| {
+				let initial = combine_activations(base_level(id), probe[id], 0.0, emotional(id));
+				(id, initial.total)
+			})
+			.collect();
+		seeds.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+		seeds.truncate(config.max_seeds);
+
+		// 3. Association and temporal spreading from the seeds
+		let mut spreading = vec![0.0; n];
+		let mut temporal = vec![0.0; n];
+		if !seeds.is_empty() {
+			// Indexed memories the graph has not grown to yet spread nothing
+			let (seed_indices, seed_activations): (Vec<usize>, Vec<f64>) = seeds
+				.iter()
+				.filter(|&&(i, _)| i < self.graph.num_nodes())
+				.copied()
+				.unzip();
+			if config.spreading_depth > 0 && !seed_indices.is_empty() {
+				let result = spread_activation_graph(
+					self.graph,
+					&seed_indices,
+					&seed_activations,
+					&config.spreading,
+					config.spreading_depth,
+				);
+				for (i, &received) in result.activations.iter().enumerate().take(n) {
+					spreading[i] = received;
+				}
+				for &(seed, activation) in &seeds {
+					spreading[seed] = (spreading[seed] - activation).max(0.0);
+				}
+			}
+			if config.temporal_weight > 0.0 {
+				// A memory in several episodes takes its strongest boost,
+				// as in `spread_temporal_activation_multi`
+				for &(seed, activation) in &seeds {
+					for episode in self.episodes.iter().filter(|e| e.events.contains(&seed)) {
+						let result = spread_temporal_activation(
+							n,
+							&episode.links,
+							seed,
+							activation,
+							&config.temporal,
+						);
+						for (i, &received) in result.activations.iter().enumerate() {
+							if i != seed && received > temporal[i] {
+								temporal[i] = received;
+							}
+						}
+					}
+				}
+			}
+		}
+
+		// 4. Combine and rank everything that received activation
+		let mut recalled: Vec<RecalledMemory> = (0..n)
+			.filter(|&i| probe[i] > 0.0 || spreading[i] > 0.0 || temporal[i] > 0.0)
+			.filter_map(|i| {
+				let breakdown =
+					combine_activations(base_level(i), probe[i], spreading[i], emotional(i));
+				let score = config.temporal_weight.mul_add(temporal[i], breakdown.total);
+				let probability = retrieval_probability(
+					score,
+					config.activation_threshold,
+					config.noise_parameter,
+				);
+				if probability < config.min_probability {
+					return None;
+				}
+				Some(RecalledMemory {
+					index: i,
+					score,
+					probability,
+					explanation: RecallExplanation {
+						similarity: similarity[i],
+						working_memory_boost: boost[i],
+						probe_activation: breakdown.probe_activation,
+						base_level: breakdown.base_level,
+						emotional_weight: breakdown.emotional_weight,
+						spreading: spreading[i],
+						temporal: temporal[i],
+						seed: seeds.iter().any(|&(s, _)| s == i),
+					},
+				})
+			})
+			.collect();
+
+		recalled.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.index.cmp(&b.index)));
+		recalled.truncate(k);
+
+		#[cfg(feature = "metrics")]
+		crate::metrics::record_recall_query(started.elapsed());
+		recalled
+	}
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ann::HnswConfig;
+	use crate::spreading::{Association, AssociationType};
+
+	fn make_index(vectors: &[[f32; 3]]) -> HnswIndex {
+		let mut index = HnswIndex::new(3, HnswConfig::default());
+		for (id, vector) in vectors.iter().enumerate() {
+			assert!(index.insert(id, vector).is_ok());
+		}
+		index
+	}
+
+	#[test]
+	fn test_recall_combines_stages() {
+		// 0 matches the query; 1 is associated with 0; 3 follows 0 in an
+		// episode; 2 is unrelated
+		let index = make_index(&[
+			[1.0, 0.0, 0.0],
+			[0.0, 1.0, 0.0],
+			[0.0, 0.0, 1.0],
+			[0.0, 1.0, 1.0],
+		]);
+		let associations = vec![Association {
+			source: 0,
+			target: 1,
+			forward_strength: 0.8,
+			backward_strength: 0.8,
+			association_type: AssociationType::Semantic,
+		}];
+		let graph = AssociationGraph::new(4, &associations);
+		let episodes = vec![Episode::new(
+			vec![0, 3],
+			&TemporalSpreadingConfig::default(),
+		)];
+		let now = 100_000.0;
+		let histories = vec![vec![now - 1_000.0]; 4];
+		let engine = RecallEngine {
+			index: &index,
+			graph: &graph,
+			episodes: &episodes,
+			access_histories_ms: &histories,
+			decay_rates: &[],
+			emotional_weights: &[],
+			config: RecallConfig::default(),
+		};
+		let context = RecallContext {
+			current_time_ms: now,
+			working_memory: None,
+		};
+
+		let recalled = engine.recall_embedding(&[1.0, 0.0, 0.0], &context, 3);
+		assert_eq!(recalled.len(), 3);
+		assert_eq!(recalled[0].index, 0);
+		assert!(recalled[0].explanation.seed);
+		assert!((recalled[0].explanation.similarity - 1.0).abs() < 1e-6);
+
+		let by_index = |i: usize| recalled.iter().find(|r| r.index == i);
+		assert!(by_index(1).is_some_and(|r| r.explanation.spreading > 0.0));
+		assert!(by_index(3).is_some_and(|r| r.explanation.temporal > 0.0));
+		assert!(by_index(2).is_none());
+		assert!(recalled.windows(2).all(|w| w[0].score >= w[1].score));
+
+		// Nothing is found for a query of the wrong dimension
+		assert!(engine.recall_embedding(&[1.0, 0.0], &context, 3).is_empty());
+	}
+
+	#[test]
+	fn test_working_memory_boosts_similarity() {
+		let index = make_index(&[[1.0, 0.2, 0.0], [0.2, 1.0, 0.0]]);
+		let graph = AssociationGraph::new(2, &[]);
+		let now = 10_000.0;
+		let mut engine = RecallEngine {
+			index: &index,
+			graph: &graph,
+			episodes: &[],
+			access_histories_ms: &[],
+			decay_rates: &[],
+			emotional_weights: &[],
+			config: RecallConfig::default(),
+		};
+		engine.config.temporal_weight = 0.0;
+
+		let query = [0.6, 0.8, 0.0];
+		let unfocused = RecallContext {
+			current_time_ms: now,
+			working_memory: None,
+		};
+		let before = engine.recall_embedding(&query, &unfocused, 2);
+		assert_eq!(before.first().map(|r| r.index), Some(1));
+
+		let mut wm = WorkingMemory::default();
+		let _ = wm.focus(0, 1.0, now);
+		let focused = RecallContext {
+			current_time_ms: now,
+			working_memory: Some(&wm),
+		};
+		let after = engine.recall_embedding(&query, &focused, 2);
+		assert_eq!(after.first().map(|r| r.index), Some(0));
+		assert!(after[0].explanation.working_memory_boost > 1.5);
+	}
+
+	#[test]
+	fn test_recall_with_ids_past_graph() {
+		let mut index = HnswIndex::new(3, HnswConfig::default());
+		assert!(index.insert(0, &[1.0, 0.0, 0.0]).is_ok());
+		assert!(index.insert(3, &[0.9, 0.1, 0.0]).is_ok());
+		let graph = AssociationGraph::new(1, &[]);
+		let engine = RecallEngine {
+			index: &index,
+			graph: &graph,
+			episodes: &[],
+			access_histories_ms: &[],
+			decay_rates: &[],
+			emotional_weights: &[],
+			config: RecallConfig::default(),
+		};
+		let context = RecallContext {
+			current_time_ms: 10_000.0,
+			working_memory: None,
+		};
+
+		let recalled = engine.recall_embedding(&[1.0, 0.0, 0.0], &context, 2);
+		let mut indices: Vec<usize> = recalled.iter().map(|r| r.index).collect();
+		indices.sort_unstable();
+		assert_eq!(indices, vec![0, 3]);
+		assert!(recalled.iter().all(|r| r.explanation.spreading == 0.0));
+	}
+}
@@ -0,0 +1,417 @@
+//! Entity Registry
+//!
+//! One node per real-world entity, however many recordings mention it.
+//! Extracted names arrive spelled slightly differently each time ("Jason
+//! Docton", "jason  docton", "Jason Doctn"); if each spelling got its own
+//! node, associations about the same person would fragment across them
+//! and never add up.
+//!
+//! [`EntityRegistry::resolve`] maps a mention to a canonical node:
+//!
+//! 1. **Exact**: the normalized text is a known canonical name or alias
+//! 2. **Fuzzy**: the closest known name of the same kind is similar enough
+//!    (`1 - levenshtein / max_len`); the mention is recorded as an alias
+//! 3. **New**: otherwise a node is created for it
+//!
+//! URLs are only ever matched exactly, after normalization: two URLs a
+//! character apart are usually different pages.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// What an entity is; entities of different kinds never match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum EntityKind {
+	/// A person or organization
+	#[default]
+	Name,
+	/// A project, product, or codebase
+	Project,
+	/// A web address
+	Url,
+}
+
+/// A canonical entity and the spellings that resolve to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entity {
+	/// Graph node holding the entity
+	pub node: usize,
+	/// What the entity is
+	pub kind: EntityKind,
+	/// The first spelling seen, as given
+	pub canonical: String,
+	/// Other spellings resolved to this entity, normalized
+	pub aliases: Vec<String>,
+	/// Mentions resolved to this entity
+	pub mentions: u32,
+}
+
+/// How a mention was resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Resolution {
+	/// Matched a canonical name or alias
+	Exact,
+	/// Matched a known name with this similarity (0-1)
+	Fuzzy(f64),
+	/// Created a new entity
+	New,
+}
+
+/// Result of resolving one mention.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedEntity {
+	/// Canonical node for the mention
+	pub node: usize,
+	/// How it was found
+	pub resolution: Resolution,
+}
+
+/// Configuration for entity resolution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityRegistryConfig {
+	/// Similarity at which a mention joins a known entity (default: 0.85)
+	pub min_similarity: f64,
+
+	/// Shortest normalized mention, in characters, matched fuzzily (default: 5)
+	pub min_fuzzy_length: usize,
+}
+
+impl Default for EntityRegistryConfig {
+	fn default() -> Self {
+		Self {
+			min_similarity: 0.85,
+			min_fuzzy_length: 5,
+		}
+	}
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// Resolves entity mentions to canonical nodes.
+///
+/// To keep nodes stable across recordings, persist
+/// [`entities`](Self::entities) and restore with
+/// [`from_entities`](Self::from_entities).
+#[derive(Debug, Clone, Default)]
+pub struct EntityRegistry {
+	config: EntityRegistryConfig,
+	entities: Vec<Entity>,
+	/// (kind, normalized spelling) → position in `entities`
+	lookup: HashMap<(EntityKind, String), usize>,
+	/// node → position in `entities`
+	by_node: HashMap<usize, usize>,
+}
+
+impl EntityRegistry {
+	/// Create an empty registry.
+	#[must_use]
+	pub fn new(config: EntityRegistryConfig) -> Self {
+		Self {
+			config,
+			entities: Vec::new(),
+			lookup: HashMap::new(),
+			by_node: HashMap::new(),
+		}
+	}
+
+	/// Restore a registry from previously registered entities.
+	///
+	/// Where two entities claim the same spelling, the earlier one keeps it.
+	#[must_use]
+	pub fn from_entities(config: EntityRegistryConfig, entities: Vec<Entity>) -> Self {
+		let mut registry = Self::new(config);
+		for entity in entities {
+			let position = registry.entities.len();
+			let _ = registry.by_node.insert(entity.node, position);
+			let spellings = std::iter::once(normalize_mention(entity.kind, &entity.canonical))
+				.chain(entity.aliases.iter().cloned());
+			for spelling in spellings {
+				let _ = registry
+					.lookup
+					.entry((entity.kind, spelling))
+					.or_insert(position);
+			}
+			registry.entities.push(entity);
+		}
+		registry
+	}
+
+	/// Number of entities.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.entities.len()
+	}
+
+	/// Whether no entities are registered.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.entities.is_empty()
+	}
+
+	/// Registered entities, in creation order.
+	#[must_use]
+	pub fn entities(&self) -> &[Entity] {
+		&self.entities
+	}
+
+	/// The entity on `node`, if any.
+	#[must_use]
+	pub fn get(&self, node: usize) -> Option<&Entity> {
+		self.by_node.get(&node).map(|&i| &self.entities[i])
+	}
+
+	/// Find the entity a mention would resolve to, without recording it.
+	#[must_use]
+	pub fn find(&self, kind: EntityKind, text: &str) -> Option<ResolvedEntity> {
+		let key = normalize_mention(kind, text);
+		if key.is_empty() {
+			return None;
+		}
+		self.matching(kind, &key)
+			.map(|(position, resolution)| ResolvedEntity {
+				node: self.entities[position].node,
+				resolution,
+			})
+	}
+
+	/// Resolve a mention to its canonical node.
+	///
+	/// Known entities gain a mention, and fuzzy matches gain an alias so
+	/// the same spelling matches exactly next time. Otherwise `new_node`
+	/// is called for the node of a new entity, e.g.
+	/// `|| builder.add_node()` with a [`GraphBuilder`](crate::GraphBuilder).
+	///
+	/// # Returns
+	///
+	/// `None` if the mention is empty after normalization.
+	pub fn resolve(
+		&mut self,
+		kind: EntityKind,
+		text: &str,
+		new_node: impl FnOnce() -> usize,
+	) -> Option<ResolvedEntity> {
+		let key = normalize_mention(kind, text);
+		if key.is_empty() {
+			return None;
+		}
+
+		if let Some((position, resolution)) = self.matching(kind, &key) {
+			let entity = &mut self.entities[position];
+			entity.mentions += 1;
+			if matches!(resolution, Resolution::Fuzzy(_)) {
+				entity.aliases.push(key.clone());
+				let _ = self.lookup.insert((kind, key), position);
+			}
+			return Some(ResolvedEntity {
+				node: entity.node,
+				resolution,
+			});
+		}
+
+		let node = new_node();
+		let position = self.entities.len();
+		self.entities.push(Entity {
+			node,
+			kind,
+			canonical: text.trim().to_string(),
+			aliases: Vec::new(),
+			mentions: 1,
+		});
+		let _ = self.lookup.insert((kind, key), position);
+		let _ = self.by_node.insert(node, position);
+		Some(ResolvedEntity {
+			node,
+			resolution: Resolution::New,
+		})
+	}
+
+	/// Add an alias for the entity on `node`, e.g. a nickname that is not
+	/// spelled like the name.
+	///
+	/// Returns `false` if there is no entity on `node`, or the alias is
+	/// empty or already claimed.
+	pub fn add_alias(&mut self, node: usize, alias: &str) -> bool {
+		let Some(&position) = self.by_node.get(&node) else {
+			return false;
+		};
+		let kind = self.entities[position].kind;
+		let key = normalize_mention(kind, alias);
+		if key.is_empty() || self.lookup.contains_key(&(kind, key.clone())) {
+			return false;
+		}
+		self.entities[position].aliases.push(key.clone());
+		let _ = self.lookup.insert((kind, key), position);
+		true
+	}
+
+	/// Exact, then best fuzzy match among entities of `kind`.
+	fn matching(&self, kind: EntityKind, key: &str) -> Option<(usize, Resolution)> {
+		if let Some(&position) = self.lookup.get(&(kind, key.to_string())) {
+			return Some((position, Resolution::Exact));
+		}
+		if kind == EntityKind::Url || key.chars().count() < self.config.min_fuzzy_length {
+			return None;
+		}
+
+		// Ties go to the entity registered first
+		let mut best: Option<(usize, f64)> = None;
+		for ((spelling_kind, spelling), &position) in &self.lookup {
+			if *spelling_kind != kind {
+				continue;
+			}
+			let similarity = string_similarity(key, spelling);
+			let better = best.is_none_or(|(best_position, best_similarity)| {
+				similarity
+					.total_cmp(&best_similarity)
+					.then(best_position.cmp(&position))
+					.is_gt()
+			});
+			if similarity >= self.config.min_similarity && better {
+				best = Some((position, similarity));
+			}
+		}
+		best.map(|(position, similarity)| (position, Resolution::Fuzzy(similarity)))
+	}
+}
+
+// ============================================================================
+// Matching
+// ============================================================================
+
+/// Normalize a mention for comparison.
+///
+/// Names and projects are lowercased with punctuation and runs of
+/// whitespace collapsed to single spaces, so "Lucid-Memory" and
+/// "lucid memory" agree. URLs are lowercased and stripped of scheme,
+/// `www.`, fragment, and trailing slash.
+#[must_use]
+pub fn normalize_mention(kind: EntityKind, text: &str) -> String {
+	let lower = text.trim().to_lowercase();
+	if kind == EntityKind::Url {
+		let rest = lower
+			.strip_prefix("https://")
+			.or_else(|| lower.strip_prefix("http://"))
+			.unwrap_or(&lower);
+		let rest = rest.strip_prefix("www.").unwrap_or(rest);
+		let rest = rest.split('#').next().unwrap_or_default();
+		return rest.trim_end_matches('/').to_string();
+	}
+
+	lower
+		.split(|c: char| !c.is_alphanumeric())
+		.filter(|word| !word.is_empty())
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+/// Edit-distance similarity of two strings, by character.
+///
+/// `similarity = 1 - levenshtein(a, b) / max(len_a, len_b)`
+#[must_use]
+pub fn string_similarity(a: &str, b: &str) -> f64 {
+	let a: Vec<char> = a.chars().collect();
+	let b: Vec<char> = b.chars().collect();
+	let longest = a.len().max(b.len());
+	if longest == 0 {
+		return 1.0;
+	}
+
+	let mut previous: Vec<usize> = (0..=b.len()).collect();
+	let mut current = vec![0; b.len() + 1];
+	for (i, ca) in a.iter().enumerate() {
+		current[0] = i + 1;
+		for (j, cb) in b.iter().enumerate() {
+			let substitution = previous[j] + usize::from(ca != cb);
+			current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+		}
+		std::mem::swap(&mut previous, &mut current);
+	}
+
+	#[allow(clippy::cast_precision_loss)]
+	let similarity = 1.0 - previous[b.len()] as f64 / longest as f64;
+	similarity
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_resolve_across_recordings() {
+		let mut registry = EntityRegistry::new(EntityRegistryConfig::default());
+		let mut next = 0;
+		let mut node = || {
+			next += 1;
+			next - 1
+		};
+
+		// First recording
+		let jason = registry.resolve(EntityKind::Name, "Jason Docton", &mut node);
+		let lucid = registry.resolve(EntityKind::Project, "lucid-memory", &mut node);
+		assert_eq!(jason.map(|r| r.resolution), Some(Resolution::New));
+		assert_eq!(lucid.map(|r| r.node), Some(1));
+
+		// Second recording: respelled, misspelled, and a new entity
+		let respelled = registry.resolve(EntityKind::Name, "  jason   DOCTON ", &mut node);
+		assert_eq!(
+			respelled.map(|r| (r.node, r.resolution)),
+			Some((0, Resolution::Exact))
+		);
+		let misspelled = registry.resolve(EntityKind::Name, "Jason Doctn", &mut node);
+		assert!(
+			misspelled.is_some_and(|r| r.node == 0 && matches!(r.resolution, Resolution::Fuzzy(_)))
+		);
+		let project = registry.resolve(EntityKind::Project, "Lucid Memory", &mut node);
+		assert_eq!(project.map(|r| r.node), Some(1));
+		// Same text, different kind
+		let name = registry.resolve(EntityKind::Name, "Lucid Memory", &mut node);
+		assert_eq!(name.map(|r| r.node), Some(2));
+		assert_eq!(registry.resolve(EntityKind::Name, " !? ", &mut node), None);
+
+		let entity = registry.get(0);
+		assert!(entity.is_some_and(|e| e.mentions == 3 && e.aliases == ["jason doctn"]));
+
+		// Restored registries resolve the alias exactly
+		let restored = EntityRegistry::from_entities(
+			EntityRegistryConfig::default(),
+			registry.entities().to_vec(),
+		);
+		assert_eq!(restored.len(), 3);
+		let found = restored.find(EntityKind::Name, "Jason Doctn");
+		assert_eq!(
+			found.map(|r| (r.node, r.resolution)),
+			Some((0, Resolution::Exact))
+		);
+	}
+
+	#[test]
+	fn test_urls_and_aliases() {
+		let mut registry = EntityRegistry::new(EntityRegistryConfig::default());
+		let first = registry.resolve(EntityKind::Url, "https://www.example.com/docs/", || 7);
+		let same = registry.resolve(EntityKind::Url, "http://example.com/docs#intro", || 8);
+		let other = registry.resolve(EntityKind::Url, "https://example.com/doc", || 9);
+		assert_eq!(first.map(|r| r.node), Some(7));
+		assert_eq!(same.map(|r| r.node), Some(7));
+		assert_eq!(other.map(|r| r.node), Some(9));
+
+		let _ = registry.resolve(EntityKind::Name, "Robert", || 10);
+		assert!(registry.add_alias(10, "Bob"));
+		assert!(!registry.add_alias(10, "bob"));
+		assert!(!registry.add_alias(11, "Rob"));
+		let bob = registry.find(EntityKind::Name, "BOB");
+		assert_eq!(bob.map(|r| r.node), Some(10));
+
+		assert!((string_similarity("kitten", "sitting") - (1.0 - 3.0 / 7.0)).abs() < 1e-12);
+		assert!((string_similarity("", "") - 1.0).abs() < 1e-12);
+	}
+}
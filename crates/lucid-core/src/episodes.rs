@@ -0,0 +1,643 @@
+//! Episodes
+//!
+//! Finding episode boundaries and restructuring episodes once found.
+//!
+//! ## Segmentation
+//!
+//! Detects episode boundaries in a time-ordered event stream, following
+//! Event Segmentation Theory (Zacks et al., 2007): people hold a running
+//! model of the current situation and perceive a new event when it
+//! predicts the next moment badly.
+//!
+//! Each event's surprise is its prediction error against an exponential
+//! moving average of the current episode:
+//!
+//! `surprise_t = 1 - cos(x_t, p_(t-1))`, `p_t = (1 - α) × p_(t-1) + α × x_t`
+//!
+//! A boundary is placed before event `t` when
+//!
+//! `surprise_t > max(min_surprise, μ + k × σ)`
+//!
+//! Where `μ` and `σ` are the mean and standard deviation of surprise so
+//! far in the current episode, so a stream that is always noisy needs a
+//! larger jump than one that is usually steady. Callers that already have
+//! change scores can skip the prediction step with `segment_by_change`.
+//!
+//! ## Merging and Splitting
+//!
+//! An [`Episode`] pairs its events with their temporal links. When a user
+//! corrects a boundary, `merge` keeps both sides' links and derives new
+//! ones across the seam; `split_at` keeps the links on each side and drops
+//! those that crossed. Memory indices never change, only positions.
+//!
+//! ## Cross-Episode Entities
+//!
+//! `link_episodes_by_entities` connects memories in different episodes
+//! that share entities or tags, so spreading reaches "all the times we
+//! discussed the billing bug" even when those episodes are weeks apart.
+//! Episode similarity is an IDF-weighted Jaccard index, so a rare entity
+//! ("billing bug") links more strongly than one that is everywhere.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use crate::activation::cosine_similarity;
+use crate::spreading::{
+	compute_temporal_link_strength, create_episode_links, Association, AssociationType,
+	TemporalLink, TemporalSpreadingConfig,
+};
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Configuration for episode boundary detection.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SegmentationConfig {
+	/// Weight of each new event in the running prediction, `α` (default: 0.5)
+	pub prediction_rate: f64,
+	/// Standard deviations above the episode's mean surprise needed for a
+	/// boundary, `k` (default: 2.0)
+	pub threshold_sd: f64,
+	/// Surprise below this never starts an episode (default: 0.2)
+	pub min_surprise: f64,
+	/// Minimum events per episode (default: 2)
+	pub min_episode_length: usize,
+}
+
+impl Default for SegmentationConfig {
+	fn default() -> Self {
+		Self {
+			prediction_rate: 0.5,
+			threshold_sd: 2.0,
+			min_surprise: 0.2,
+			min_episode_length: 2,
+		}
+	}
+}
+
+/// Episode boundaries found in an event stream.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Segmentation {
+	/// Index of the first event of each episode after the first, ascending
+	pub boundaries: Vec<usize>,
+	/// Surprise (change score) of each event; 0 for the first
+	pub surprise: Vec<f64>,
+}
+
+impl Segmentation {
+	/// Number of events segmented.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.surprise.len()
+	}
+
+	/// Whether the stream was empty.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.surprise.is_empty()
+	}
+
+	/// Event index range of each episode, in order.
+	#[must_use]
+	pub fn episodes(&self) -> Vec<Range<usize>> {
+		if self.surprise.is_empty() {
+			return Vec::new();
+		}
+		let mut starts = Vec::with_capacity(self.boundaries.len() + 1);
+		starts.push(0);
+		starts.extend_from_slice(&self.boundaries);
+
+		starts
+			.iter()
+			.enumerate()
+			.map(|(i, &start)| start..starts.get(i + 1).copied().unwrap_or(self.surprise.len()))
+			.collect()
+	}
+}
+
+// ============================================================================
+// Segmentation
+// ============================================================================
+
+/// Running mean and variance (Welford).
+#[derive(Default)]
+struct RunningStats {
+	count: usize,
+	mean: f64,
+	m2: f64,
+}
+
+impl RunningStats {
+	fn push(&mut self, x: f64) {
+		self.count += 1;
+		#[allow(clippy::cast_precision_loss)]
+		let n = self.count as f64;
+		let delta = x - self.mean;
+		self.mean += delta / n;
+		self.m2 = delta.mul_add(x - self.mean, self.m2);
+	}
+
+	fn threshold(&self, k: f64) -> f64 {
+		#[allow(clippy::cast_precision_loss)]
+		let sd = if self.count > 1 {
+			(self.m2 / (self.count - 1) as f64).sqrt()
+		} else {
+			0.0
+		};
+		k.mul_add(sd, self.mean)
+	}
+}
+
+/// Online boundary decisions over a stream of known length.
+struct BoundaryDetector<'a> {
+	config: &'a SegmentationConfig,
+	num_events: usize,
+	stats: RunningStats,
+	episode_start: usize,
+	boundaries: Vec<usize>,
+}
+
+impl<'a> BoundaryDetector<'a> {
+	fn new(config: &'a SegmentationConfig, num_events: usize) -> Self {
+		Self {
+			config,
+			num_events,
+			stats: RunningStats::default(),
+			episode_start: 0,
+			boundaries: Vec::new(),
+		}
+	}
+
+	/// Record event `t`'s score; returns whether it starts a new episode.
+	fn observe(&mut self, t: usize, score: f64) -> bool {
+		let min_length = self.config.min_episode_length.max(1);
+		let threshold = self
+			.stats
+			.threshold(self.config.threshold_sd)
+			.max(self.config.min_surprise);
+		let room = t - self.episode_start >= min_length && self.num_events - t >= min_length;
+
+		if room && score > threshold {
+			self.boundaries.push(t);
+			self.episode_start = t;
+			self.stats = RunningStats::default();
+			true
+		} else {
+			self.stats.push(score);
+			false
+		}
+	}
+}
+
+/// Segment an event stream by prediction error over feature vectors.
+///
+/// # Arguments
+///
+/// * `events` - Feature vector per event (e.g. embeddings), in time order
+/// * `config` - Boundary detection parameters
+///
+/// # Returns
+///
+/// Boundaries and per-event surprise.
+#[must_use]
+pub fn segment_events(events: &[Vec<f64>], config: &SegmentationConfig) -> Segmentation {
+	let alpha = config.prediction_rate.clamp(0.0, 1.0);
+	let mut detector = BoundaryDetector::new(config, events.len());
+	let mut surprise = Vec::with_capacity(events.len());
+	let mut prediction: Vec<f64> = Vec::new();
+
+	for (t, event) in events.iter().enumerate() {
+		if t == 0 {
+			surprise.push(0.0);
+			prediction.clone_from(event);
+			continue;
+		}
+
+		let score = 1.0 - cosine_similarity(event, &prediction);
+		surprise.push(score);
+
+		// A new episode is judged against its own running model
+		if detector.observe(t, score) || prediction.len() != event.len() {
+			prediction.clone_from(event);
+		} else {
+			for (p, &x) in prediction.iter_mut().zip(event) {
+				*p = alpha.mul_add(x - *p, *p);
+			}
+		}
+	}
+
+	Segmentation {
+		boundaries: detector.boundaries,
+		surprise,
+	}
+}
+
+/// Segment an event stream from precomputed change scores.
+///
+/// # Arguments
+///
+/// * `change_scores` - Surprise or change score per event (higher means
+///   more different from what came before), in time order
+/// * `config` - Boundary detection parameters (`prediction_rate` unused)
+///
+/// # Returns
+///
+/// Boundaries, with `surprise` holding the scores as given.
+#[must_use]
+pub fn segment_by_change(change_scores: &[f64], config: &SegmentationConfig) -> Segmentation {
+	let mut detector = BoundaryDetector::new(config, change_scores.len());
+	for (t, &score) in change_scores.iter().enumerate().skip(1) {
+		let _ = detector.observe(t, score);
+	}
+
+	Segmentation {
+		boundaries: detector.boundaries,
+		surprise: change_scores.to_vec(),
+	}
+}
+
+// ============================================================================
+// Merging and Splitting
+// ============================================================================
+
+/// An episode: memories in event order and the temporal links between them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Episode {
+	/// Memory index of each event, in order
+	pub events: Vec<usize>,
+	/// Temporal links, positions relative to `events`
+	pub links: Vec<TemporalLink>,
+}
+
+impl Episode {
+	/// Create an episode with links from `create_episode_links`.
+	#[must_use]
+	pub fn new(events: Vec<usize>, config: &TemporalSpreadingConfig) -> Self {
+		let links = create_episode_links(&events, config);
+		Self { events, links }
+	}
+
+	/// Number of events.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.events.len()
+	}
+
+	/// Whether the episode has no events.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.events.is_empty()
+	}
+
+	/// Append `other` after this episode.
+	///
+	/// Existing links on both sides are kept (custom strengths included);
+	/// links across the seam are derived from `config` like
+	/// `create_episode_links` would, within `max_temporal_distance`.
+	#[must_use]
+	pub fn merge(&self, other: &Self, config: &TemporalSpreadingConfig) -> Self {
+		let offset = self.events.len();
+		let mut events = self.events.clone();
+		events.extend_from_slice(&other.events);
+
+		let mut links = self.links.clone();
+		links.extend(other.links.iter().map(|link| TemporalLink {
+			source_position: link.source_position + offset,
+			target_position: link.target_position + offset,
+			..link.clone()
+		}));
+
+		let window = config.max_temporal_distance;
+		for i in offset.saturating_sub(window)..offset {
+			for j in offset..events.len().min(i + window + 1) {
+				let distance = j - i;
+				links.push(TemporalLink {
+					source_position: i,
+					target_position: j,
+					source_memory: events[i],
+					target_memory: events[j],
+					forward_strength: compute_temporal_link_strength(
+						config.forward_strength,
+						distance,
+						config,
+					),
+					backward_strength: compute_temporal_link_strength(
+						config.backward_strength,
+						distance,
+						config,
+					),
+				});
+			}
+		}
+
+		Self { events, links }
+	}
+
+	/// Split before `position`: events `..position` and `position..`.
+	///
+	/// Links within each side are kept (positions in the second half are
+	/// shifted to start at 0); links crossing the split are dropped.
+	/// `position` is clamped to the episode length.
+	#[must_use]
+	pub fn split_at(&self, position: usize) -> (Self, Self) {
+		let position = position.min(self.events.len());
+		let (before, after) = self.events.split_at(position);
+
+		let mut first_links = Vec::new();
+		let mut second_links = Vec::new();
+		for link in &self.links {
+			let lo = link.source_position.min(link.target_position);
+			let hi = link.source_position.max(link.target_position);
+			if hi < position {
+				first_links.push(link.clone());
+			} else if lo >= position {
+				second_links.push(TemporalLink {
+					source_position: link.source_position - position,
+					target_position: link.target_position - position,
+					..link.clone()
+				});
+			}
+		}
+
+		(
+			Self {
+				events: before.to_vec(),
+				links: first_links,
+			},
+			Self {
+				events: after.to_vec(),
+				links: second_links,
+			},
+		)
+	}
+}
+
+// ============================================================================
+// Cross-Episode Entities
+// ============================================================================
+
+/// Configuration for linking episodes through shared entities.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EntityLinkConfig {
+	/// Association strength between episodes with identical entity sets
+	/// (default: 0.5)
+	pub strength: f64,
+	/// Episode pairs less similar than this are not linked (default: 0.1)
+	pub min_similarity: f64,
+	/// Most similar partner episodes linked per episode (default: 5)
+	pub max_partners: usize,
+}
+
+impl Default for EntityLinkConfig {
+	fn default() -> Self {
+		Self {
+			strength: 0.5,
+			min_similarity: 0.1,
+			max_partners: 5,
+		}
+	}
+}
+
+/// Link memories across episodes that share entities or tags.
+///
+/// `similarity(A, B) = Σ_(e ∈ A∩B) idf(e) / Σ_(e ∈ A∪B) idf(e)`,
+/// `idf(e) = ln(1 + N / df(e))`
+///
+/// Each episode is paired with its `max_partners` most similar episodes
+/// (at least `min_similarity`), and every memory in one is linked to
+/// every memory in the other with strength `strength × similarity` in both
+/// directions. A memory pair reached through several episode pairs keeps
+/// the strongest link; a memory is never linked to itself.
+///
+/// # Arguments
+///
+/// * `episodes` - Episodes to link
+/// * `entities` - Entity/tag set of each episode, parallel to `episodes`;
+///   missing entries count as empty
+/// * `config` - Linking parameters
+///
+/// # Returns
+///
+/// `Entity` associations sorted by `(source, target)`, with `source < target`.
+#[must_use]
+pub fn link_episodes_by_entities<S: AsRef<str>>(
+	episodes: &[Episode],
+	entities: &[Vec<S>],
+	config: &EntityLinkConfig,
+) -> Vec<Association> {
+	let sets: Vec<HashSet<&str>> = (0..episodes.len())
+		.map(|i| {
+			entities
+				.get(i)
+				.map(|tags| tags.iter().map(AsRef::as_ref).collect())
+				.unwrap_or_default()
+		})
+		.collect();
+
+	let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+	for set in &sets {
+		for &entity in set {
+			*document_frequency.entry(entity).or_insert(0) += 1;
+		}
+	}
+	#[allow(clippy::cast_precision_loss)]
+	let num_episodes = episodes.len() as f64;
+	#[allow(clippy::cast_precision_loss)]
+	let idf = |entity: &str| {
+		let df = document_frequency.get(entity).copied().unwrap_or(1) as f64;
+		(num_episodes / df).ln_1p()
+	};
+
+	// Candidate partners per episode, most similar first
+	let mut partners: Vec<Vec<(usize, f64)>> = vec![Vec::new(); episodes.len()];
+	for a in 0..sets.len() {
+		for b in (a + 1)..sets.len() {
+			let shared: f64 = sets[a].intersection(&sets[b]).map(|e| idf(e)).sum();
+			if shared <= 0.0 {
+				continue;
+			}
+			let union: f64 = sets[a].union(&sets[b]).map(|e| idf(e)).sum();
+			let similarity = shared / union;
+			if similarity >= config.min_similarity {
+				partners[a].push((b, similarity));
+				partners[b].push((a, similarity));
+			}
+		}
+	}
+
+	let mut pairs: HashMap<(usize, usize), f64> = HashMap::new();
+	for (a, candidates) in partners.iter_mut().enumerate() {
+		candidates.sort_by(|x, y| y.1.total_cmp(&x.1).then_with(|| x.0.cmp(&y.0)));
+		for &(b, similarity) in candidates.iter().take(config.max_partners) {
+			let key = (a.min(b), a.max(b));
+			let _ = pairs.insert(key, similarity);
+		}
+	}
+
+	let mut links: HashMap<(usize, usize), f64> = HashMap::new();
+	for ((a, b), similarity) in pairs {
+		let strength = config.strength * similarity;
+		for &m in &episodes[a].events {
+			for &n in &episodes[b].events {
+				if m == n {
+					continue;
+				}
+				let entry = links.entry((m.min(n), m.max(n))).or_insert(0.0);
+				*entry = entry.max(strength);
+			}
+		}
+	}
+
+	let mut associations: Vec<Association> = links
+		.into_iter()
+		.map(|((source, target), strength)| Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Entity,
+		})
+		.collect();
+	associations.sort_by_key(|a| (a.source, a.target));
+	associations
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_segment_by_change_finds_spike() {
+		let scores = [0.0, 0.05, 0.04, 0.06, 0.9, 0.05, 0.03, 0.04];
+		let segmentation = segment_by_change(&scores, &SegmentationConfig::default());
+		assert_eq!(segmentation.boundaries, vec![4]);
+		assert_eq!(segmentation.episodes(), vec![0..4, 4..8]);
+	}
+
+	#[test]
+	fn test_segment_by_change_respects_min_length() {
+		let scores = [0.0, 0.9, 0.05, 0.05, 0.05, 0.9];
+		let config = SegmentationConfig {
+			min_episode_length: 2,
+			..SegmentationConfig::default()
+		};
+		// Spike at 1 would leave a 1-event episode; spike at 5 likewise
+		assert!(segment_by_change(&scores, &config).boundaries.is_empty());
+	}
+
+	#[test]
+	fn test_segment_events_by_topic_shift() {
+		let coding = |noise: f64| vec![1.0, noise, 0.0];
+		let travel = |noise: f64| vec![0.0, noise, 1.0];
+		let events = vec![
+			coding(0.1),
+			coding(0.0),
+			coding(0.2),
+			coding(0.1),
+			travel(0.1),
+			travel(0.0),
+			travel(0.2),
+		];
+
+		let segmentation = segment_events(&events, &SegmentationConfig::default());
+		assert_eq!(segmentation.boundaries, vec![4]);
+		assert_eq!(segmentation.len(), 7);
+		assert!(segmentation.surprise[4] > 0.5);
+
+		let empty = segment_events(&[], &SegmentationConfig::default());
+		assert!(empty.is_empty());
+		assert!(empty.episodes().is_empty());
+	}
+
+	#[test]
+	fn test_merge_derives_seam_links() {
+		let config = TemporalSpreadingConfig::default();
+		let first = Episode::new(vec![10, 11, 12], &config);
+		let second = Episode::new(vec![20, 21], &config);
+		let merged = first.merge(&second, &config);
+
+		assert_eq!(merged.events, vec![10, 11, 12, 20, 21]);
+		let rebuilt = Episode::new(merged.events.clone(), &config);
+		assert_eq!(merged.links.len(), rebuilt.links.len());
+
+		let seam = merged
+			.links
+			.iter()
+			.find(|l| l.source_memory == 12 && l.target_memory == 20);
+		assert!(seam.is_some_and(|l| l.source_position == 2 && l.target_position == 3));
+		let shifted = merged
+			.links
+			.iter()
+			.find(|l| l.source_memory == 20 && l.target_memory == 21);
+		assert!(shifted.is_some_and(|l| l.source_position == 3));
+	}
+
+	#[test]
+	fn test_split_then_merge_round_trips() {
+		let config = TemporalSpreadingConfig::default();
+		let episode = Episode::new(vec![5, 6, 7, 8, 9], &config);
+		let (first, second) = episode.split_at(2);
+
+		assert_eq!(first.events, vec![5, 6]);
+		assert_eq!(second.events, vec![7, 8, 9]);
+		assert_eq!(first.links.len(), 1);
+		assert_eq!(second.links.len(), 3);
+		assert!(second.links.iter().all(|l| l.target_position < 3));
+
+		let rejoined = first.merge(&second, &config);
+		assert_eq!(rejoined.events, episode.events);
+		assert_eq!(rejoined.links.len(), episode.links.len());
+
+		let (all, none) = episode.split_at(99);
+		assert_eq!(all.len(), 5);
+		assert!(none.is_empty());
+	}
+
+	#[test]
+	fn test_link_episodes_by_shared_entities() {
+		let config = TemporalSpreadingConfig::default();
+		let episodes = vec![
+			Episode::new(vec![0, 1], &config),
+			Episode::new(vec![2, 3], &config),
+			Episode::new(vec![4], &config),
+			Episode::new(vec![5], &config),
+		];
+		let entities = vec![
+			vec!["billing-bug", "alice"],
+			vec!["billing-bug", "bob"],
+			vec!["alice"],
+			vec!["lunch"],
+		];
+
+		let links = link_episodes_by_entities(&episodes, &entities, &EntityLinkConfig::default());
+		let strength = |s: usize, t: usize| {
+			links
+				.iter()
+				.find(|a| a.source == s && a.target == t)
+				.map_or(0.0, |a| a.forward_strength)
+		};
+
+		// Billing-bug episodes are fully connected
+		assert!(strength(0, 2) > 0.0);
+		assert!(strength(1, 3) > 0.0);
+		assert!(strength(0, 4) > 0.0);
+		// Nothing shares "lunch"; nothing links within an episode
+		assert!(links.iter().all(|a| a.source != 5 && a.target != 5));
+		assert!((strength(0, 1)).abs() < 1e-12);
+		assert!(links
+			.iter()
+			.all(|a| a.association_type == AssociationType::Entity && a.source < a.target));
+
+		let limited = link_episodes_by_entities(
+			&episodes,
+			&entities,
+			&EntityLinkConfig {
+				max_partners: 0,
+				..EntityLinkConfig::default()
+			},
+		);
+		assert!(limited.is_empty());
+	}
+}
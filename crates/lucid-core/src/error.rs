@@ -0,0 +1,174 @@
+//! Error Classification
+//!
+//! Every error type in this crate (and in lucid-perception) implements
+//! [`Classify`], reporting an [`ErrorKind`] so a scheduler can decide
+//! whether to retry, skip the input, or alert an operator without matching
+//! on messages.
+//!
+//! | Kind | Typical response |
+//! |------|------------------|
+//! | [`ErrorKind::Transient`] | retry |
+//! | [`ErrorKind::ResourceExhausted`] | retry with backoff |
+//! | [`ErrorKind::InvalidInput`] | skip the input |
+//! | [`ErrorKind::MissingDependency`] | alert; fix the installation |
+//! | [`ErrorKind::Internal`] | alert; report a bug |
+//!
+//! Configuration types report problems up front instead: their `validate()`
+//! methods return every [`ConfigIssue`] found, each naming the offending
+//! field.
+
+use std::io;
+
+/// Broad class of an error, independent of the operation that failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+	/// Interrupted I/O, timeouts and cancellation; the same call may succeed
+	/// if repeated.
+	Transient,
+
+	/// A required tool, model file or device is not installed or not
+	/// reachable.
+	MissingDependency,
+
+	/// The caller's data or arguments are malformed, inconsistent or
+	/// unsupported; repeating the call cannot succeed.
+	InvalidInput,
+
+	/// Memory, disk, device buffers or another limit ran out.
+	ResourceExhausted,
+
+	/// An invariant inside the library or a dependency broke.
+	Internal,
+}
+
+impl ErrorKind {
+	/// Whether repeating the failed operation may succeed.
+	///
+	/// True for [`Transient`](Self::Transient) and
+	/// [`ResourceExhausted`](Self::ResourceExhausted).
+	#[must_use]
+	pub const fn is_retryable(self) -> bool {
+		matches!(self, Self::Transient | Self::ResourceExhausted)
+	}
+
+	/// Classify a [`std::io::Error`].
+	///
+	/// Missing and unreadable files count as invalid input, full disks and
+	/// out-of-memory as resource exhaustion, and everything else as
+	/// transient.
+	#[must_use]
+	pub fn from_io(error: &io::Error) -> Self {
+		match error.kind() {
+			io::ErrorKind::NotFound
+			| io::ErrorKind::PermissionDenied
+			| io::ErrorKind::InvalidInput
+			| io::ErrorKind::InvalidData
+			| io::ErrorKind::UnexpectedEof
+			| io::ErrorKind::IsADirectory
+			| io::ErrorKind::NotADirectory
+			| io::ErrorKind::Unsupported => Self::InvalidInput,
+			io::ErrorKind::OutOfMemory
+			| io::ErrorKind::StorageFull
+			| io::ErrorKind::QuotaExceeded
+			| io::ErrorKind::FileTooLarge => Self::ResourceExhausted,
+			_ => Self::Transient,
+		}
+	}
+}
+
+/// An error that reports its [`ErrorKind`].
+pub trait Classify {
+	/// Broad class of this error; see [`ErrorKind`].
+	fn kind(&self) -> ErrorKind;
+
+	/// Whether repeating the failed operation may succeed.
+	fn is_retryable(&self) -> bool {
+		self.kind().is_retryable()
+	}
+}
+
+// ============================================================================
+// Configuration Diagnostics
+// ============================================================================
+
+/// A problem with one field of a configuration value.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{field}: {message}")]
+pub struct ConfigIssue {
+	/// Path of the offending field, dotted for nested configs
+	/// (e.g. `"type_params.semantic.weight"`)
+	pub field: String,
+	/// What is wrong and what is accepted
+	pub message: String,
+}
+
+impl ConfigIssue {
+	/// Create an issue for `field`.
+	pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+		Self {
+			field: field.into(),
+			message: message.into(),
+		}
+	}
+
+	/// Prefix the field path with the name of an enclosing config.
+	#[must_use]
+	pub fn within(mut self, parent: &str) -> Self {
+		self.field = format!("{parent}.{}", self.field);
+		self
+	}
+}
+
+impl Classify for ConfigIssue {
+	/// Always [`ErrorKind::InvalidInput`].
+	fn kind(&self) -> ErrorKind {
+		ErrorKind::InvalidInput
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_retryable_kinds() {
+		assert!(ErrorKind::Transient.is_retryable());
+		assert!(ErrorKind::ResourceExhausted.is_retryable());
+		assert!(!ErrorKind::InvalidInput.is_retryable());
+		assert!(!ErrorKind::MissingDependency.is_retryable());
+		assert!(!ErrorKind::Internal.is_retryable());
+	}
+
+	#[test]
+	fn test_from_io() {
+		let kind = |k| ErrorKind::from_io(&io::Error::from(k));
+		assert_eq!(kind(io::ErrorKind::NotFound), ErrorKind::InvalidInput);
+		assert_eq!(kind(io::ErrorKind::UnexpectedEof), ErrorKind::InvalidInput);
+		assert_eq!(
+			kind(io::ErrorKind::StorageFull),
+			ErrorKind::ResourceExhausted
+		);
+		assert_eq!(kind(io::ErrorKind::Interrupted), ErrorKind::Transient);
+		assert_eq!(kind(io::ErrorKind::TimedOut), ErrorKind::Transient);
+	}
+
+	#[test]
+	fn test_config_issue_within() {
+		let issue = ConfigIssue::new("weight", "must be finite").within("semantic");
+		assert_eq!(issue.field, "semantic.weight");
+		assert_eq!(issue.to_string(), "semantic.weight: must be finite");
+	}
+
+	#[test]
+	fn test_core_errors_classified() {
+		use crate::{AnnError, IngestError, SnapshotError};
+
+		assert_eq!(SnapshotError::Truncated.kind(), ErrorKind::InvalidInput);
+		let io = SnapshotError::Io(io::Error::from(io::ErrorKind::Interrupted));
+		assert!(io.is_retryable());
+		let ingest = IngestError::from(AnnError::DuplicateId(3));
+		assert_eq!(ingest.kind(), ErrorKind::InvalidInput);
+		assert!(!ingest.is_retryable());
+	}
+}
@@ -0,0 +1,499 @@
+//! Graph Export
+//!
+//! Serialize an [`AssociationGraph`] for inspection in external tools:
+//! `GraphML` and GEXF for Gephi, DOT for Graphviz.
+//!
+//! Every edge carries its forward and backward strengths and association
+//! type. Nodes carry their activation when one is supplied, and their
+//! tags. Node and edge attributes are exported as typed properties; a key
+//! holding integers and floats is exported as a double, and a key holding
+//! any other mix of types as a string.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::attributes::{AttributeValue, Attributes};
+use crate::graph::AssociationGraph;
+use crate::spreading::AssociationType;
+
+/// Export to `GraphML`.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to export
+/// * `activations` - Activation of each node, exported as the `activation`
+///   property; missing entries count as 0. `None` omits the property.
+#[must_use]
+pub fn export_graphml(graph: &AssociationGraph, activations: Option<&[f64]>) -> String {
+	let (node_keys, edge_keys) = attribute_keys(graph);
+	let mut out = String::new();
+	out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+	out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+
+	if activations.is_some() {
+		out.push_str(
+			"  <key id=\"activation\" for=\"node\" attr.name=\"activation\" attr.type=\"double\"/>\n",
+		);
+	}
+	out.push_str("  <key id=\"tags\" for=\"node\" attr.name=\"tags\" attr.type=\"string\"/>\n");
+	for (kind, prefix, keys) in [("node", "na", &node_keys), ("edge", "ea", &edge_keys)] {
+		for (i, (name, value_type)) in keys.iter().enumerate() {
+			let _ = writeln!(
+				out,
+				"  <key id=\"{prefix}{i}\" for=\"{kind}\" attr.name=\"{}\" attr.type=\"{}\"/>",
+				xml_escape(name),
+				value_type.name(),
+			);
+		}
+	}
+	for (id, value_type) in [
+		("forward_strength", "double"),
+		("backward_strength", "double"),
+		("association_type", "string"),
+	] {
+		let _ = writeln!(
+			out,
+			"  <key id=\"{id}\" for=\"edge\" attr.name=\"{id}\" attr.type=\"{value_type}\"/>"
+		);
+	}
+
+	out.push_str("  <graph id=\"memory\" edgedefault=\"directed\">\n");
+	for node in 0..graph.num_nodes() {
+		let _ = writeln!(out, "    <node id=\"n{node}\">");
+		if let Some(activations) = activations {
+			let _ = writeln!(
+				out,
+				"      <data key=\"activation\">{}</data>",
+				activations.get(node).copied().unwrap_or(0.0)
+			);
+		}
+		let tags = graph.node_tags(node);
+		if !tags.is_empty() {
+			let _ = writeln!(
+				out,
+				"      <data key=\"tags\">{}</data>",
+				xml_escape(&tags.join(","))
+			);
+		}
+		for (i, value) in attribute_values(graph.node_attributes(node), &node_keys) {
+			let _ = writeln!(
+				out,
+				"      <data key=\"na{i}\">{}</data>",
+				xml_escape(&value)
+			);
+		}
+		out.push_str("    </node>\n");
+	}
+	for edge in edges(graph) {
+		let _ = writeln!(
+			out,
+			"    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">",
+			edge.id, edge.source, edge.target
+		);
+		let _ = writeln!(
+			out,
+			"      <data key=\"forward_strength\">{}</data>",
+			edge.forward_strength
+		);
+		let _ = writeln!(
+			out,
+			"      <data key=\"backward_strength\">{}</data>",
+			edge.backward_strength
+		);
+		let _ = writeln!(
+			out,
+			"      <data key=\"association_type\">{}</data>",
+			edge.association_type.as_str()
+		);
+		for (i, value) in attribute_values(graph.edge_attributes(edge.id), &edge_keys) {
+			let _ = writeln!(
+				out,
+				"      <data key=\"ea{i}\">{}</data>",
+				xml_escape(&value)
+			);
+		}
+		out.push_str("    </edge>\n");
+	}
+	out.push_str("  </graph>\n</graphml>\n");
+	out
+}
+
+/// Export to GEXF 1.3.
+///
+/// Edge `weight` is the forward strength. Arguments are as for
+/// [`export_graphml`].
+#[must_use]
+pub fn export_gexf(graph: &AssociationGraph, activations: Option<&[f64]>) -> String {
+	let (node_keys, edge_keys) = attribute_keys(graph);
+	let mut out = String::new();
+	out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+	out.push_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n");
+	out.push_str("  <graph defaultedgetype=\"directed\">\n");
+
+	out.push_str("    <attributes class=\"node\">\n");
+	if activations.is_some() {
+		out.push_str("      <attribute id=\"activation\" title=\"activation\" type=\"double\"/>\n");
+	}
+	out.push_str("      <attribute id=\"tags\" title=\"tags\" type=\"string\"/>\n");
+	for (i, (name, value_type)) in node_keys.iter().enumerate() {
+		let _ = writeln!(
+			out,
+			"      <attribute id=\"na{i}\" title=\"{}\" type=\"{}\"/>",
+			xml_escape(name),
+			value_type.name(),
+		);
+	}
+	out.push_str("    </attributes>\n");
+
+	out.push_str("    <attributes class=\"edge\">\n");
+	out.push_str(
+		"      <attribute id=\"backward_strength\" title=\"backward_strength\" type=\"double\"/>\n",
+	);
+	out.push_str(
+		"      <attribute id=\"association_type\" title=\"association_type\" type=\"string\"/>\n",
+	);
+	for (i, (name, value_type)) in edge_keys.iter().enumerate() {
+		let _ = writeln!(
+			out,
+			"      <attribute id=\"ea{i}\" title=\"{}\" type=\"{}\"/>",
+			xml_escape(name),
+			value_type.name(),
+		);
+	}
+	out.push_str("    </attributes>\n");
+
+	out.push_str("    <nodes>\n");
+	for node in 0..graph.num_nodes() {
+		let _ = writeln!(out, "      <node id=\"{node}\" label=\"{node}\">");
+		out.push_str("        <attvalues>\n");
+		if let Some(activations) = activations {
+			let _ = writeln!(
+				out,
+				"          <attvalue for=\"activation\" value=\"{}\"/>",
+				activations.get(node).copied().unwrap_or(0.0)
+			);
+		}
+		let tags = graph.node_tags(node);
+		if !tags.is_empty() {
+			let _ = writeln!(
+				out,
+				"          <attvalue for=\"tags\" value=\"{}\"/>",
+				xml_escape(&tags.join(","))
+			);
+		}
+		for (i, value) in attribute_values(graph.node_attributes(node), &node_keys) {
+			let _ = writeln!(
+				out,
+				"          <attvalue for=\"na{i}\" value=\"{}\"/>",
+				xml_escape(&value)
+			);
+		}
+		out.push_str("        </attvalues>\n      </node>\n");
+	}
+	out.push_str("    </nodes>\n");
+
+	out.push_str("    <edges>\n");
+	for edge in edges(graph) {
+		let _ = writeln!(
+			out,
+			"      <edge id=\"{}\" source=\"{}\" target=\"{}\" weight=\"{}\">",
+			edge.id, edge.source, edge.target, edge.forward_strength
+		);
+		out.push_str("        <attvalues>\n");
+		let _ = writeln!(
+			out,
+			"          <attvalue for=\"backward_strength\" value=\"{}\"/>",
+			edge.backward_strength
+		);
+		let _ = writeln!(
+			out,
+			"          <attvalue for=\"association_type\" value=\"{}\"/>",
+			edge.association_type.as_str()
+		);
+		for (i, value) in attribute_values(graph.edge_attributes(edge.id), &edge_keys) {
+			let _ = writeln!(
+				out,
+				"          <attvalue for=\"ea{i}\" value=\"{}\"/>",
+				xml_escape(&value)
+			);
+		}
+		out.push_str("        </attvalues>\n      </edge>\n");
+	}
+	out.push_str("    </edges>\n  </graph>\n</gexf>\n");
+	out
+}
+
+/// Export to Graphviz DOT.
+///
+/// Properties become quoted node and edge attributes, which Graphviz
+/// carries through to its output. Edge `penwidth` scales with forward
+/// strength so strong links stand out. Arguments are as for
+/// [`export_graphml`].
+#[must_use]
+pub fn export_dot(graph: &AssociationGraph, activations: Option<&[f64]>) -> String {
+	let mut out = String::from("digraph memory {\n");
+	for node in 0..graph.num_nodes() {
+		let mut properties = vec![("label".to_string(), node.to_string())];
+		if let Some(activations) = activations {
+			let activation = activations.get(node).copied().unwrap_or(0.0);
+			properties.push(("activation".to_string(), activation.to_string()));
+		}
+		let tags = graph.node_tags(node);
+		if !tags.is_empty() {
+			properties.push(("tags".to_string(), tags.join(",")));
+		}
+		properties.extend(sorted_attributes(graph.node_attributes(node)));
+		let _ = writeln!(out, "  {node} [{}];", dot_properties(&properties));
+	}
+	for edge in edges(graph) {
+		let mut properties = vec![
+			(
+				"penwidth".to_string(),
+				4.0f64
+					.mul_add(edge.forward_strength.clamp(0.0, 1.0), 1.0)
+					.to_string(),
+			),
+			(
+				"forward_strength".to_string(),
+				edge.forward_strength.to_string(),
+			),
+			(
+				"backward_strength".to_string(),
+				edge.backward_strength.to_string(),
+			),
+			(
+				"association_type".to_string(),
+				edge.association_type.as_str().to_string(),
+			),
+		];
+		properties.extend(sorted_attributes(graph.edge_attributes(edge.id)));
+		let _ = writeln!(
+			out,
+			"  {} -> {} [{}];",
+			edge.source,
+			edge.target,
+			dot_properties(&properties)
+		);
+	}
+	out.push_str("}\n");
+	out
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// One edge with both strengths, in edge-id order.
+struct ExportEdge {
+	id: usize,
+	source: usize,
+	target: usize,
+	forward_strength: f64,
+	backward_strength: f64,
+	association_type: AssociationType,
+}
+
+fn edges(graph: &AssociationGraph) -> Vec<ExportEdge> {
+	let mut edges: Vec<ExportEdge> = (0..graph.num_edges())
+		.map(|id| ExportEdge {
+			id,
+			source: 0,
+			target: 0,
+			forward_strength: 0.0,
+			backward_strength: 0.0,
+			association_type: graph.association_type(id),
+		})
+		.collect();
+	for node in 0..graph.num_nodes() {
+		for (id, target, strength) in graph.forward(node).iter_edges() {
+			edges[id].source = node;
+			edges[id].target = target;
+			edges[id].forward_strength = strength;
+		}
+		for (id, _, strength) in graph.backward(node).iter_edges() {
+			edges[id].backward_strength = strength;
+		}
+	}
+	edges
+}
+
+/// Property type shared by `GraphML` and GEXF.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ValueType {
+	Boolean,
+	Long,
+	Double,
+	String,
+}
+
+impl ValueType {
+	const fn of(value: &AttributeValue) -> Self {
+		match value {
+			AttributeValue::Bool(_) => Self::Boolean,
+			AttributeValue::Int(_) => Self::Long,
+			AttributeValue::Float(_) => Self::Double,
+			AttributeValue::Text(_) => Self::String,
+		}
+	}
+
+	const fn merge(self, other: Self) -> Self {
+		match (self, other) {
+			(Self::Boolean, Self::Boolean) => Self::Boolean,
+			(Self::Long, Self::Long) => Self::Long,
+			(Self::Long | Self::Double, Self::Long | Self::Double) => Self::Double,
+			_ => Self::String,
+		}
+	}
+
+	const fn name(self) -> &'static str {
+		match self {
+			Self::Boolean => "boolean",
+			Self::Long => "long",
+			Self::Double => "double",
+			Self::String => "string",
+		}
+	}
+}
+
+/// Attribute names with their exported types, sorted by name.
+type AttributeKeys = Vec<(String, ValueType)>;
+
+/// Attribute keys of nodes and of edges.
+fn attribute_keys(graph: &AssociationGraph) -> (AttributeKeys, AttributeKeys) {
+	let collect = |attributes: &mut dyn Iterator<Item = &Attributes>| {
+		let mut keys = BTreeMap::<String, ValueType>::new();
+		for map in attributes {
+			for (name, value) in map {
+				let value_type = ValueType::of(value);
+				let _ = keys
+					.entry(name.clone())
+					.and_modify(|existing| *existing = existing.merge(value_type))
+					.or_insert(value_type);
+			}
+		}
+		keys.into_iter().collect::<Vec<_>>()
+	};
+	(
+		collect(&mut (0..graph.num_nodes()).map(|node| graph.node_attributes(node))),
+		collect(&mut (0..graph.num_edges()).map(|edge| graph.edge_attributes(edge))),
+	)
+}
+
+/// `(key index, formatted value)` for each key present in `attributes`.
+fn attribute_values(attributes: &Attributes, keys: &[(String, ValueType)]) -> Vec<(usize, String)> {
+	keys.iter()
+		.enumerate()
+		.filter_map(|(i, (name, _))| Some((i, format_value(attributes.get(name)?))))
+		.collect()
+}
+
+fn sorted_attributes(attributes: &Attributes) -> Vec<(String, String)> {
+	let mut properties: Vec<_> = attributes
+		.iter()
+		.map(|(name, value)| (name.clone(), format_value(value)))
+		.collect();
+	properties.sort();
+	properties
+}
+
+fn format_value(value: &AttributeValue) -> String {
+	match value {
+		AttributeValue::Bool(value) => value.to_string(),
+		AttributeValue::Int(value) => value.to_string(),
+		AttributeValue::Float(value) => value.to_string(),
+		AttributeValue::Text(value) => value.clone(),
+	}
+}
+
+fn dot_properties(properties: &[(String, String)]) -> String {
+	properties
+		.iter()
+		.map(|(name, value)| format!("\"{}\"=\"{}\"", dot_escape(name), dot_escape(value)))
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
+fn dot_escape(text: &str) -> String {
+	text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn xml_escape(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+	for c in text.chars() {
+		match c {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&apos;"),
+			_ => escaped.push(c),
+		}
+	}
+	escaped
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::Association;
+
+	fn sample_graph() -> AssociationGraph {
+		let associations = vec![
+			Association {
+				source: 0,
+				target: 1,
+				forward_strength: 0.75,
+				backward_strength: 0.25,
+				association_type: AssociationType::Causal,
+			},
+			Association {
+				source: 1,
+				target: 0,
+				forward_strength: 0.5,
+				backward_strength: 0.5,
+				association_type: AssociationType::Semantic,
+			},
+		];
+		AssociationGraph::new(2, &associations)
+			.with_node_tags(vec![vec!["work".to_string()]])
+			.with_node_attributes(vec![Attributes::from([(
+				"title".to_string(),
+				AttributeValue::Text("Q&A \"notes\"".to_string()),
+			)])])
+			.with_edge_attributes(vec![
+				Attributes::from([("created_ms".to_string(), AttributeValue::Int(5))]),
+				Attributes::from([("created_ms".to_string(), AttributeValue::Float(7.5))]),
+			])
+	}
+
+	#[test]
+	fn test_export_graphml_and_gexf() {
+		let graph = sample_graph();
+		let graphml = export_graphml(&graph, Some(&[0.9]));
+
+		assert!(graphml.contains("attr.name=\"created_ms\" attr.type=\"double\""));
+		assert!(graphml.contains("<edge id=\"e0\" source=\"n0\" target=\"n1\">"));
+		assert!(graphml.contains("<data key=\"backward_strength\">0.25</data>"));
+		assert!(graphml.contains("<data key=\"association_type\">causal</data>"));
+		assert!(graphml.contains("Q&amp;A &quot;notes&quot;"));
+		assert!(graphml.contains("<data key=\"activation\">0</data>"));
+		assert_eq!(graphml.matches("<node ").count(), 2);
+
+		let gexf = export_gexf(&graph, None);
+		assert!(gexf.contains("source=\"0\" target=\"1\" weight=\"0.75\""));
+		assert!(gexf.contains("<attvalue for=\"tags\" value=\"work\"/>"));
+		assert!(gexf.contains("<attvalue for=\"association_type\" value=\"causal\"/>"));
+		assert!(!gexf.contains("activation"));
+	}
+
+	#[test]
+	fn test_export_dot() {
+		let dot = export_dot(&sample_graph(), Some(&[0.9, 0.1]));
+
+		assert!(dot.starts_with("digraph memory {"));
+		assert!(dot.contains("\"activation\"=\"0.9\""));
+		assert!(dot.contains("\"title\"=\"Q&A \\\"notes\\\"\""));
+		assert!(dot.contains("1 -> 0 ["));
+		assert!(dot.contains("\"association_type\"=\"semantic\""));
+	}
+}
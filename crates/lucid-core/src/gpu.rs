@@ -0,0 +1,685 @@
+//! GPU Spreading
+//!
+//! Spreading activation and `PageRank` as sparse matrix-vector products
+//! on the GPU, through `wgpu` compute shaders. For graphs with tens of
+//! millions of edges, where a CPU hop no longer fits an interactive
+//! latency budget.
+//!
+//! One hop of spreading is linear in the frontier's activation, so the
+//! whole hop is a fixed sparse matrix: built once per graph and config by
+//! [`GpuSpreading::new`], then applied each level. Frontier bookkeeping
+//! (visited nodes, `max_nodes`, retrieval thresholds) stays on the CPU
+//! and follows [`spread_activation_graph`] exactly.
+//!
+//! GPUs generally lack `f64`, so products are computed in `f32`: results
+//! match the CPU path to about 7 significant digits.
+//!
+//! Requires the `gpu` feature.
+//!
+//! [`spread_activation_graph`]: crate::spreading::spread_activation_graph
+
+use std::collections::{HashMap, HashSet};
+
+use wgpu::util::DeviceExt;
+
+use crate::error::{Classify, ErrorKind};
+use crate::graph::AssociationGraph;
+use crate::rng::SplitMix64;
+use crate::spreading::{
+	admit_targets, apply_competition, propagates_from, transfer_coefficients, Competition,
+	DanglingPolicy, PageRankConfig, PageRankResult, SpreadingConfig, SpreadingResult,
+};
+
+/// Rows handled by one workgroup.
+const WORKGROUP_SIZE: u32 = 256;
+
+/// Workgroups per dispatch dimension guaranteed by every adapter.
+const MAX_WORKGROUPS_PER_DIMENSION: u32 = 65_535;
+
+/// CSR sparse matrix-vector product, one row per invocation.
+const SPMV_SHADER: &str = r"
+@group(0) @binding(0) var<storage, read> row_offsets: array<u32>;
+@group(0) @binding(1) var<storage, read> columns: array<u32>;
+@group(0) @binding(2) var<storage, read> values: array<f32>;
+@group(0) @binding(3) var<storage, read> x: array<f32>;
+@group(0) @binding(4) var<storage, read_write> y: array<f32>;
+
+@compute @workgroup_size(256)
+fn main(
+	@builtin(global_invocation_id) id: vec3<u32>,
+	@builtin(num_workgroups) groups: vec3<u32>,
+) {
+	let row = id.y * groups.x * 256u + id.x;
+	if (row >= arrayLength(&y)) {
+		return;
+	}
+	var sum = 0.0;
+	for (var k = row_offsets[row]; k < row_offsets[row + 1u]; k = k + 1u) {
+		sum = sum + values[k] * x[columns[k]];
+	}
+	y[row] = sum;
+}
+";
+
+/// Error type for GPU operations.
+#[derive(Debug, thiserror::Error)]
+pub enum GpuError {
+	/// No adapter is available.
+	#[error("No GPU adapter available")]
+	NoAdapter,
+
+	/// The adapter refused to open a device.
+	#[error("Failed to open GPU device: {0}")]
+	Device(String),
+
+	/// The matrix exceeds a buffer or index limit of the device.
+	#[error("Graph too large for GPU buffers: {0} bytes")]
+	TooLarge(u64),
+
+	/// Reading results back from the device failed.
+	#[error("Failed to read GPU results: {0}")]
+	Readback(String),
+}
+
+impl Classify for GpuError {
+	fn kind(&self) -> ErrorKind {
+		match self {
+			Self::NoAdapter | Self::Device(_) => ErrorKind::MissingDependency,
+			Self::TooLarge(_) => ErrorKind::ResourceExhausted,
+			Self::Readback(_) => ErrorKind::Transient,
+		}
+	}
+}
+
+/// An open GPU device with the matrix-vector pipeline compiled.
+///
+/// Opening a device is slow; create one context and share it.
+pub struct GpuContext {
+	device: wgpu::Device,
+	queue: wgpu::Queue,
+	pipeline: wgpu::ComputePipeline,
+}
+
+impl std::fmt::Debug for GpuContext {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("GpuContext").finish_non_exhaustive()
+	}
+}
+
+impl GpuContext {
+	/// Open the default high-performance adapter.
+	///
+	/// # Errors
+	///
+	/// Returns [`GpuError::NoAdapter`] if no adapter is found and
+	/// [`GpuError::Device`] if it cannot be opened.
+	pub fn new() -> Result<Self, GpuError> {
+		pollster::block_on(Self::open())
+	}
+
+	async fn open() -> Result<Self, GpuError> {
+		let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+		let adapter = instance
+			.request_adapter(&wgpu::RequestAdapterOptions {
+				power_preference: wgpu::PowerPreference::HighPerformance,
+				..Default::default()
+			})
+			.await
+			.ok_or(GpuError::NoAdapter)?;
+		let (device, queue) = adapter
+			.request_device(
+				&wgpu::DeviceDescriptor {
+					label: Some("lucid-core"),
+					required_limits: adapter.limits(),
+					..Default::default()
+				},
+				None,
+			)
+			.await
+			.map_err(|e| GpuError::Device(e.to_string()))?;
+
+		let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+			label: Some("spmv"),
+			source: wgpu::ShaderSource::Wgsl(SPMV_SHADER.into()),
+		});
+		let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+			label: Some("spmv"),
+			layout: None,
+			module: &module,
+			entry_point: Some("main"),
+			compilation_options: wgpu::PipelineCompilationOptions::default(),
+			cache: None,
+		});
+
+		Ok(Self {
+			device,
+			queue,
+			pipeline,
+		})
+	}
+}
+
+/// A square sparse matrix resident on the GPU, with its input, output
+/// and readback buffers.
+struct GpuMatrix {
+	num_rows: usize,
+	x: wgpu::Buffer,
+	y: wgpu::Buffer,
+	readback: wgpu::Buffer,
+	bind_group: wgpu::BindGroup,
+}
+
+impl GpuMatrix {
+	/// Upload `(row, column, value)` triples for an `n × n` matrix.
+	///
+	/// Duplicate entries are summed by the product.
+	fn new(
+		context: &GpuContext,
+		num_rows: usize,
+		triples: &[(usize, usize, f64)],
+	) -> Result<Self, GpuError> {
+		let limit = u64::from(context.device.limits().max_storage_buffer_binding_size);
+		let bytes = |len: usize| (len.max(1) * 4) as u64;
+		if bytes(triples.len()) > limit || bytes(num_rows + 1) > limit {
+			return Err(GpuError::TooLarge(bytes(triples.len().max(num_rows + 1))));
+		}
+		let index = |i: usize| u32::try_from(i).map_err(|_| GpuError::TooLarge(bytes(i)));
+
+		let mut counts = vec![0_usize; num_rows + 1];
+		for &(row, _, _) in triples {
+			counts[row + 1] += 1;
+		}
+		for row in 0..num_rows {
+			counts[row + 1] += counts[row];
+		}
+		let row_offsets = counts
+			.iter()
+			.map(|&offset| index(offset))
+			.collect::<Result<Vec<u32>, _>>()?;
+		let mut next = counts;
+		let mut columns = vec![0_u32; triples.len().max(1)];
+		let mut values = vec![0.0_f32; triples.len().max(1)];
+		for &(row, column, value) in triples {
+			let slot = next[row];
+			next[row] += 1;
+			columns[slot] = index(column)?;
+			#[allow(clippy::cast_possible_truncation)]
+			let value = value as f32;
+			values[slot] = value;
+		}
+
+		let device = &context.device;
+		let storage = |label: &str, contents: &[u8], usage: wgpu::BufferUsages| {
+			device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+				label: Some(label),
+				contents,
+				usage: wgpu::BufferUsages::STORAGE | usage,
+			})
+		};
+		let row_offsets = storage(
+			"row_offsets",
+			bytemuck::cast_slice(&row_offsets),
+			wgpu::BufferUsages::empty(),
+		);
+		let columns = storage(
+			"columns",
+			bytemuck::cast_slice(&columns),
+			wgpu::BufferUsages::empty(),
+		);
+		let values = storage(
+			"values",
+			bytemuck::cast_slice(&values),
+			wgpu::BufferUsages::empty(),
+		);
+		let vector = vec![0.0_f32; num_rows.max(1)];
+		let x = storage(
+			"x",
+			bytemuck::cast_slice(&vector),
+			wgpu::BufferUsages::COPY_DST,
+		);
+		let y = storage(
+			"y",
+			bytemuck::cast_slice(&vector),
+			wgpu::BufferUsages::COPY_SRC,
+		);
+		let readback = device.create_buffer(&wgpu::BufferDescriptor {
+			label: Some("readback"),
+			size: bytes(num_rows),
+			usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+			mapped_at_creation: false,
+		});
+
+		let layout = context.pipeline.get_bind_group_layout(0);
+		let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+			label: Some("spmv"),
+			layout: &layout,
+			entries: &[
+				wgpu::BindGroupEntry {
+					binding: 0,
+					resource: row_offsets.as_entire_binding(),
+				},
+				wgpu::BindGroupEntry {
+					binding: 1,
+					resource: columns.as_entire_binding(),
+				},
+				wgpu::BindGroupEntry {
+					binding: 2,
+					resource: values.as_entire_binding(),
+				},
+				wgpu::BindGroupEntry {
+					binding: 3,
+					resource: x.as_entire_binding(),
+				},
+				wgpu::BindGroupEntry {
+					binding: 4,
+					resource: y.as_entire_binding(),
+				},
+			],
+		});
+
+		Ok(Self {
+			num_rows,
+			x,
+			y,
+			readback,
+			bind_group,
+		})
+	}
+
+	/// Compute `y = M x` on the device.
+	fn multiply(&self, context: &GpuContext, x: &[f64]) -> Result<Vec<f64>, GpuError> {
+		if self.num_rows == 0 {
+			return Ok(Vec::new());
+		}
+		#[allow(clippy::cast_possible_truncation)]
+		let input: Vec<f32> = (0..self.num_rows)
+			.map(|i| x.get(i).copied().unwrap_or(0.0) as f32)
+			.collect();
+		context
+			.queue
+			.write_buffer(&self.x, 0, bytemuck::cast_slice(&input));
+
+		let groups = u32::try_from(self.num_rows.div_ceil(WORKGROUP_SIZE as usize))
+			.map_err(|_| GpuError::TooLarge(self.readback.size()))?;
+		let groups_x = groups.min(MAX_WORKGROUPS_PER_DIMENSION);
+		let groups_y = groups.div_ceil(groups_x);
+
+		let mut encoder = context
+			.device
+			.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+				label: Some("spmv"),
+			});
+		{
+			let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+				label: Some("spmv"),
+				timestamp_writes: None,
+			});
+			pass.set_pipeline(&context.pipeline);
+			pass.set_bind_group(0, &self.bind_group, &[]);
+			pass.dispatch_workgroups(groups_x, groups_y, 1);
+		}
+		encoder.copy_buffer_to_buffer(&self.y, 0, &self.readback, 0, self.readback.size());
+		let _ = context.queue.submit(Some(encoder.finish()));
+
+		let slice = self.readback.slice(..);
+		let (sender, receiver) = std::sync::mpsc::channel();
+		slice.map_async(wgpu::MapMode::Read, move |result| {
+			let _ = sender.send(result);
+		});
+		let _ = context.device.poll(wgpu::Maintain::Wait);
+		receiver
+			.recv()
+			.map_err(|e| GpuError::Readback(e.to_string()))?
+			.map_err(|e| GpuError::Readback(e.to_string()))?;
+
+		let output = {
+			let view = slice.get_mapped_range();
+			let values = bytemuck::cast_slice::<u8, f32>(&view);
+			values.iter().map(|&v| f64::from(v)).collect()
+		};
+		self.readback.unmap();
+		Ok(output)
+	}
+}
+
+// ============================================================================
+// Spreading
+// ============================================================================
+
+/// Spreading over one graph and config, prepared on the GPU.
+///
+/// Changing the graph or any config field that shapes a hop (type
+/// params, gains, tags, decay, direction) needs a new instance.
+pub struct GpuSpreading<'a> {
+	context: &'a GpuContext,
+	graph: &'a AssociationGraph,
+	config: SpreadingConfig,
+	hop: GpuMatrix,
+}
+
+impl std::fmt::Debug for GpuSpreading<'_> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("GpuSpreading")
+			.field("num_nodes", &self.hop.num_rows)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<'a> GpuSpreading<'a> {
+	/// Build the hop matrix for `graph` under `config` and upload it.
+	///
+	/// # Errors
+	///
+	/// Returns [`GpuError::TooLarge`] if the matrix exceeds device limits.
+	pub fn new(
+		context: &'a GpuContext,
+		graph: &'a AssociationGraph,
+		config: &SpreadingConfig,
+	) -> Result<Self, GpuError> {
+		let hop = GpuMatrix::new(
+			context,
+			graph.num_nodes(),
+			&transfer_coefficients(graph, config),
+		)?;
+		Ok(Self {
+			context,
+			graph,
+			config: config.clone(),
+			hop,
+		})
+	}
+
+	/// Spread from seeds, as [`spread_activation_graph`] would.
+	///
+	/// Provenance is not traced; depth deltas, noise and competition
+	/// follow the config. Each level of `visited_by_depth` lists its nodes
+	/// in ascending order.
+	///
+	/// # Errors
+	///
+	/// Returns [`GpuError::Readback`] if results cannot be read back.
+	///
+	/// [`spread_activation_graph`]: crate::spreading::spread_activation_graph
+	pub fn spread(
+		&self,
+		seed_indices: &[usize],
+		seed_activations: &[f64],
+		depth: usize,
+	) -> Result<SpreadingResult, GpuError> {
+		let config = &self.config;
+		let num_nodes = self.graph.num_nodes();
+		let mut activations = vec![0.0; num_nodes];
+		for (i, &idx) in seed_indices.iter().enumerate() {
+			if idx < num_nodes {
+				activations[idx] = seed_activations.get(i).copied().unwrap_or(1.0);
+			}
+		}
+
+		let seeds: Vec<usize> = seed_indices
+			.iter()
+			.copied()
+			.filter(|&idx| idx < num_nodes)
+			.collect();
+		let mut visited: HashSet<usize> = seeds.iter().copied().collect();
+		let mut visited_by_depth: Vec<Vec<usize>> = vec![seeds.clone()];
+		let mut frontier: Vec<usize> = seeds;
+		let mut total_visited = frontier.len();
+		let mut depth_deltas = config.record_depth_deltas.then(Vec::new);
+		let max_levels = if config.convergence_epsilon.is_some() {
+			config.max_iterations
+		} else {
+			depth
+		};
+
+		let mut sources = vec![0.0; num_nodes];
+		for _ in 1..=max_levels {
+			if total_visited >= config.max_nodes {
+				break;
+			}
+
+			sources.fill(0.0);
+			for &node in &frontier {
+				if node < num_nodes && propagates_from(self.graph, config, node, activations[node])
+				{
+					sources[node] = activations[node];
+				}
+			}
+			let received = self.hop.multiply(self.context, &sources)?;
+
+			let mut next_activations: HashMap<usize, f64> = HashMap::new();
+			let mut touched = Vec::new();
+			for (node, &amount) in received.iter().enumerate() {
+				if amount != 0.0 {
+					let _ = next_activations.insert(node, amount);
+					touched.push(node);
+				}
+			}
+			let next_frontier = admit_targets(
+				&mut next_activations,
+				&touched,
+				&mut visited,
+				&mut total_visited,
+				config.max_nodes,
+			);
+
+			let mut total_change = 0.0;
+			for &node in &touched {
+				if let Some(&amount) = next_activations.get(&node) {
+					activations[node] += amount;
+					total_change += amount.abs();
+				}
+			}
+			if let Some(levels) = depth_deltas.as_mut() {
+				levels.push(next_activations);
+			}
+
+			if next_frontier.is_empty() {
+				break;
+			}
+			visited_by_depth.push(next_frontier.clone());
+			frontier = next_frontier;
+
+			if config
+				.convergence_epsilon
+				.is_some_and(|epsilon| total_change < epsilon)
+			{
+				break;
+			}
+		}
+
+		if config.activation_noise > 0.0 {
+			let mut rng = SplitMix64::new(config.noise_seed);
+			for &idx in visited_by_depth.iter().flatten() {
+				if idx < num_nodes {
+					activations[idx] += rng.logistic(config.activation_noise);
+				}
+			}
+		}
+
+		if config.competition != Competition::None {
+			let candidates: Vec<usize> = visited_by_depth.iter().flatten().copied().collect();
+			apply_competition(&mut activations, &candidates, &config.competition);
+		}
+
+		Ok(SpreadingResult {
+			activations,
+			visited_by_depth,
+			provenance: None,
+			depth_deltas,
+		})
+	}
+}
+
+// ============================================================================
+// PageRank
+// ============================================================================
+
+/// Compute global or personalized `PageRank` on the GPU.
+///
+/// Same model, stopping rule and dangling handling as
+/// [`compute_pagerank_with_config`], over the whole graph. Each iteration
+/// is one matrix-vector product; teleportation and dangling mass are
+/// added on the CPU.
+///
+/// # Errors
+///
+/// Returns [`GpuError::TooLarge`] if the graph exceeds device limits and
+/// [`GpuError::Readback`] if results cannot be read back.
+///
+/// [`compute_pagerank_with_config`]: crate::spreading::compute_pagerank_with_config
+pub fn compute_pagerank_gpu(
+	context: &GpuContext,
+	graph: &AssociationGraph,
+	seeds: &[(usize, f64)],
+	config: &PageRankConfig,
+) -> Result<PageRankResult, GpuError> {
+	let num_nodes = graph.num_nodes();
+	if num_nodes == 0 {
+		return Ok(PageRankResult {
+			ranks: Vec::new(),
+			iterations: 0,
+			residual: 0.0,
+			converged: true,
+		});
+	}
+
+	// Column-stochastic transition matrix, stored by target row
+	let weight = |strength: f64| {
+		if config.weighted {
+			strength.max(0.0)
+		} else {
+			1.0
+		}
+	};
+	let mut dangling_nodes = Vec::new();
+	let mut triples = Vec::new();
+	for source in 0..num_nodes {
+		let neighbors = graph.forward(source);
+		let total: f64 = neighbors.strengths.iter().map(|&s| weight(s)).sum();
+		if total > 0.0 {
+			triples.extend(
+				neighbors
+					.iter()
+					.map(|(target, strength)| (target, source, weight(strength) / total)),
+			);
+		} else {
+			dangling_nodes.push(source);
+		}
+	}
+	let transition = GpuMatrix::new(context, num_nodes, &triples)?;
+
+	let mut teleport = vec![0.0; num_nodes];
+	for &(node, weight) in seeds {
+		if node < num_nodes && weight > 0.0 {
+			teleport[node] += weight;
+		}
+	}
+	let seed_total: f64 = teleport.iter().sum();
+	#[allow(clippy::cast_precision_loss)]
+	let uniform = 1.0 / num_nodes as f64;
+	if seed_total > 0.0 {
+		for t in &mut teleport {
+			*t /= seed_total;
+		}
+	} else {
+		teleport.fill(uniform);
+	}
+	let uniform_dangling = config.dangling == DanglingPolicy::All && seed_total > 0.0;
+
+	let damping = config.damping;
+	let mut ranks = teleport.clone();
+	let mut iterations = 0;
+	let mut residual = 0.0;
+
+	while iterations < config.max_iterations {
+		let followed = transition.multiply(context, &ranks)?;
+		let dangling: f64 = dangling_nodes.iter().map(|&node| ranks[node]).sum();
+		let dangling_mass = if config.dangling == DanglingPolicy::Drop {
+			0.0
+		} else {
+			damping * dangling
+		};
+
+		let next: Vec<f64> = followed
+			.iter()
+			.zip(&teleport)
+			.map(|(&f, &t)| {
+				let redistributed = if uniform_dangling {
+					dangling_mass * uniform
+				} else {
+					dangling_mass * t
+				};
+				(1.0 - damping).mul_add(t, damping.mul_add(f, redistributed))
+			})
+			.collect();
+
+		residual = ranks.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+		ranks = next;
+		iterations += 1;
+		if residual < config.tolerance {
+			break;
+		}
+	}
+
+	Ok(PageRankResult {
+		ranks,
+		iterations,
+		converged: iterations > 0 && residual < config.tolerance,
+		residual,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::attributes::TraversalFilter;
+	use crate::spreading::{
+		compute_pagerank_with_config, spread_activation_graph, Association, AssociationType,
+	};
+
+	fn make_assoc(source: usize, target: usize, strength: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	#[test]
+	fn test_gpu_matches_cpu() {
+		// Machines without an adapter (most CI) skip the comparison
+		let Ok(context) = GpuContext::new() else {
+			return;
+		};
+		let associations = vec![
+			make_assoc(0, 1, 0.8),
+			make_assoc(1, 2, 0.6),
+			make_assoc(0, 3, 0.4),
+			make_assoc(3, 2, 0.9),
+		];
+		let graph = AssociationGraph::new(5, &associations);
+		let config = SpreadingConfig::default();
+
+		let cpu = spread_activation_graph(&graph, &[0], &[1.0], &config, 3);
+		let spreading = GpuSpreading::new(&context, &graph, &config);
+		assert!(spreading.is_ok());
+		let Ok(spreading) = spreading else { return };
+		let gpu = spreading.spread(&[0], &[1.0], 3);
+		assert!(gpu.is_ok());
+		let Ok(gpu) = gpu else { return };
+		for (a, b) in cpu.activations.iter().zip(&gpu.activations) {
+			assert!((a - b).abs() < 1e-5);
+		}
+
+		let pagerank = PageRankConfig::default();
+		let cpu = compute_pagerank_with_config(&graph, &[], &pagerank, &TraversalFilter::default());
+		let gpu = compute_pagerank_gpu(&context, &graph, &[], &pagerank);
+		assert!(gpu.is_ok());
+		let Ok(gpu) = gpu else { return };
+		for (a, b) in cpu.ranks.iter().zip(&gpu.ranks) {
+			assert!((a - b).abs() < 1e-5);
+		}
+	}
+}
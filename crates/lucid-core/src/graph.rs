@@ -0,0 +1,806 @@
+//! Association Graph Index
+//!
+//! A compressed sparse row (CSR) view of the association graph.
+//!
+//! Rebuilding per-node adjacency lists from a flat association slice
+//! costs O(E) allocations on every query. On large graphs that build
+//! dominates latency, so the CSR index is built once and shared by
+//! every spreading, path, and ranking query that follows.
+//!
+//! Row `i` of the forward matrix holds the outgoing edges of node `i`;
+//! row `i` of the backward matrix holds the edges pointing *into* `i`,
+//! weighted by their backward strength. Each slot also records the edge
+//! id (the association's position among the indexed edges), which keys
+//! per-edge attributes such as the association type.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::activation::AssociationState;
+use crate::attributes::{Attributes, NO_ATTRIBUTES};
+use crate::normalize::StrengthNormalization;
+use crate::spreading::{Association, AssociationType};
+
+/// Compressed sparse row matrix of weighted edges.
+///
+/// Row `i` spans `targets[offsets[i]..offsets[i + 1]]`.
+#[derive(Clone, Debug, Default)]
+struct CsrMatrix {
+	offsets: Vec<usize>,
+	targets: Vec<usize>,
+	strengths: Vec<f64>,
+	edges: Vec<usize>,
+}
+
+impl CsrMatrix {
+	/// Build from `(row, target, strength)` triples, preserving input order within each row.
+	///
+	/// The position of each triple in `triples` becomes its edge id.
+	fn from_triples(
+		num_rows: usize,
+		num_entries: usize,
+		triples: impl Iterator<Item = (usize, usize, f64)> + Clone,
+	) -> Self {
+		let mut offsets = vec![0usize; num_rows + 1];
+		for (row, _, _) in triples.clone() {
+			offsets[row + 1] += 1;
+		}
+		for i in 0..num_rows {
+			offsets[i + 1] += offsets[i];
+		}
+
+		let mut cursor = offsets.clone();
+		let mut targets = vec![0usize; num_entries];
+		let mut strengths = vec![0.0; num_entries];
+		let mut edges = vec![0usize; num_entries];
+		for (edge, (row, target, strength)) in triples.enumerate() {
+			let slot = cursor[row];
+			targets[slot] = target;
+			strengths[slot] = strength;
+			edges[slot] = edge;
+			cursor[row] += 1;
+		}
+
+		Self {
+			offsets,
+			targets,
+			strengths,
+			edges,
+		}
+	}
+
+	#[inline]
+	fn row(&self, row: usize) -> Neighbors<'_> {
+		let (start, end) = (self.offsets[row], self.offsets[row + 1]);
+		Neighbors {
+			targets: &self.targets[start..end],
+			strengths: &self.strengths[start..end],
+			edges: &self.edges[start..end],
+		}
+	}
+}
+
+/// The edges leaving one node in an [`AssociationGraph`].
+#[derive(Clone, Copy, Debug)]
+pub struct Neighbors<'a> {
+	/// Neighbor node indices
+	pub targets: &'a [usize],
+	/// Edge strengths, parallel to `targets`
+	pub strengths: &'a [f64],
+	/// Edge ids, parallel to `targets`
+	pub edges: &'a [usize],
+}
+
+impl<'a> Neighbors<'a> {
+	/// Number of edges (the node's fan).
+	#[inline]
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.targets.len()
+	}
+
+	/// Whether the node has no edges in this direction.
+	#[inline]
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.targets.is_empty()
+	}
+
+	/// Iterate `(target, strength)` pairs.
+	#[inline]
+	pub fn iter(&self) -> impl Iterator<Item = (usize, f64)> + 'a {
+		self.targets
+			.iter()
+			.copied()
+			.zip(self.strengths.iter().copied())
+	}
+
+	/// Iterate `(edge, target, strength)` triples.
+	#[inline]
+	pub fn iter_edges(&self) -> impl Iterator<Item = (usize, usize, f64)> + 'a {
+		self.edges
+			.iter()
+			.copied()
+			.zip(self.iter())
+			.map(|(edge, (target, strength))| (edge, target, strength))
+	}
+}
+
+/// Prebuilt CSR index over the association graph.
+///
+/// Build once with [`AssociationGraph::new`] and reuse across queries.
+/// Associations referencing nodes outside `0..num_nodes` are skipped.
+#[derive(Clone, Debug, Default)]
+pub struct AssociationGraph {
+	num_nodes: usize,
+	forward: CsrMatrix,
+	backward: CsrMatrix,
+	association_types: Vec<AssociationType>,
+	node_gains: Option<NodeGains>,
+	node_tags: Vec<Vec<String>>,
+	node_attributes: Vec<Attributes>,
+	edge_attributes: Vec<Attributes>,
+	edge_decays: Vec<Option<f64>>,
+	node_thresholds: Vec<f64>,
+}
+
+/// Per-node multipliers on activation sent and received while spreading.
+#[derive(Clone, Debug, Default)]
+struct NodeGains {
+	emission: Vec<f64>,
+	attraction: Vec<f64>,
+}
+
+impl AssociationGraph {
+	/// Build the index from a flat association list.
+	///
+	/// Associations with an endpoint at or beyond `num_nodes` are skipped;
+	/// build through [`GraphBuilder`](crate::builder::GraphBuilder) to have
+	/// them reported instead.
+	#[must_use]
+	pub fn new(num_nodes: usize, associations: &[Association]) -> Self {
+		let valid = associations
+			.iter()
+			.filter(move |a| a.source < num_nodes && a.target < num_nodes);
+		let num_edges = valid.clone().count();
+
+		let forward = CsrMatrix::from_triples(
+			num_nodes,
+			num_edges,
+			valid
+				.clone()
+				.map(|a| (a.source, a.target, a.forward_strength)),
+		);
+		let backward = CsrMatrix::from_triples(
+			num_nodes,
+			num_edges,
+			valid
+				.clone()
+				.map(|a| (a.target, a.source, a.backward_strength)),
+		);
+		let association_types = valid.map(|a| a.association_type).collect();
+
+		Self {
+			num_nodes,
+			forward,
+			backward,
+			association_types,
+			node_gains: None,
+			node_tags: Vec::new(),
+			node_attributes: Vec::new(),
+			edge_attributes: Vec::new(),
+			edge_decays: Vec::new(),
+			node_thresholds: Vec::new(),
+		}
+	}
+
+	/// Attach tags to nodes, indexed by node.
+	///
+	/// Tags scope spreading through [`SpreadingConfig::tag_filter`] and
+	/// [`SpreadingConfig::tag_weights`]. Nodes beyond `tags` are untagged.
+	///
+	/// [`SpreadingConfig::tag_filter`]: crate::spreading::SpreadingConfig::tag_filter
+	/// [`SpreadingConfig::tag_weights`]: crate::spreading::SpreadingConfig::tag_weights
+	#[must_use]
+	pub fn with_node_tags(mut self, mut tags: Vec<Vec<String>>) -> Self {
+		tags.truncate(self.num_nodes);
+		self.node_tags = tags;
+		self
+	}
+
+	/// Attach attributes to nodes, indexed by node.
+	///
+	/// Nodes beyond `attributes` have none.
+	#[must_use]
+	pub fn with_node_attributes(mut self, mut attributes: Vec<Attributes>) -> Self {
+		attributes.truncate(self.num_nodes);
+		self.node_attributes = attributes;
+		self
+	}
+
+	/// Attach attributes to edges, indexed by edge id.
+	///
+	/// Edge ids follow the order of the indexed associations, so they match
+	/// positions in the input list when every association is in range.
+	/// Edges beyond `attributes` have none.
+	#[must_use]
+	pub fn with_edge_attributes(mut self, mut attributes: Vec<Attributes>) -> Self {
+		attributes.truncate(self.num_edges());
+		self.edge_attributes = attributes;
+		self
+	}
+
+	/// Attach per-edge decay factors, indexed by edge id.
+	///
+	/// A `Some` factor replaces `decay_per_hop` (and the association
+	/// type's decay multiplier) for activation crossing that edge, in
+	/// either direction. Edges beyond `decays`, or with `None`, use the
+	/// configured decay.
+	#[must_use]
+	pub fn with_edge_decays(mut self, mut decays: Vec<Option<f64>>) -> Self {
+		decays.truncate(self.num_edges());
+		self.edge_decays = decays;
+		self
+	}
+
+	/// Decay override of edge `edge`, if it has one.
+	#[inline]
+	#[must_use]
+	pub fn edge_decay(&self, edge: usize) -> Option<f64> {
+		self.edge_decays.get(edge).copied().flatten()
+	}
+
+	/// Attach per-node retrieval thresholds (τ), indexed by node.
+	///
+	/// A node whose activation ends below its threshold is left out of
+	/// [`get_top_retrievable`] and, by default, does not pass activation
+	/// on (see [`SpreadingConfig::below_threshold`]). It still receives
+	/// activation. Nodes beyond `thresholds` have none.
+	///
+	/// [`get_top_retrievable`]: crate::spreading::get_top_retrievable
+	/// [`SpreadingConfig::below_threshold`]: crate::spreading::SpreadingConfig::below_threshold
+	#[must_use]
+	pub fn with_node_thresholds(mut self, mut thresholds: Vec<f64>) -> Self {
+		thresholds.truncate(self.num_nodes);
+		self.node_thresholds = thresholds;
+		self
+	}
+
+	/// Retrieval threshold of `node`, if it has one.
+	#[inline]
+	#[must_use]
+	pub fn node_threshold(&self, node: usize) -> Option<f64> {
+		self.node_thresholds.get(node).copied()
+	}
+
+	/// Whether `node` at `activation` clears its retrieval threshold
+	/// (always true for nodes without one).
+	#[inline]
+	#[must_use]
+	pub fn is_retrievable(&self, node: usize, activation: f64) -> bool {
+		self.node_threshold(node)
+			.is_none_or(|tau| activation >= tau)
+	}
+
+	/// Attributes of `node` (empty if it has none).
+	#[inline]
+	#[must_use]
+	pub fn node_attributes(&self, node: usize) -> &Attributes {
+		self.node_attributes.get(node).unwrap_or(&NO_ATTRIBUTES)
+	}
+
+	/// Attributes of edge `edge` (empty if it has none).
+	#[inline]
+	#[must_use]
+	pub fn edge_attributes(&self, edge: usize) -> &Attributes {
+		self.edge_attributes.get(edge).unwrap_or(&NO_ATTRIBUTES)
+	}
+
+	/// Tags of `node` (empty if untagged).
+	#[inline]
+	#[must_use]
+	pub fn node_tags(&self, node: usize) -> &[String] {
+		self.node_tags.get(node).map_or(&[], Vec::as_slice)
+	}
+
+	/// Attach per-node spreading gains.
+	///
+	/// Activation sent from `i` to `j` is multiplied by `emission[i] ×
+	/// attraction[j]`. Missing entries default to 1.0 (no change).
+	#[must_use]
+	pub fn with_node_gains(mut self, emission: &[f64], attraction: &[f64]) -> Self {
+		let fill = |gains: &[f64]| {
+			(0..self.num_nodes)
+				.map(|i| gains.get(i).copied().unwrap_or(1.0))
+				.collect()
+		};
+		self.node_gains = Some(NodeGains {
+			emission: fill(emission),
+			attraction: fill(attraction),
+		});
+		self
+	}
+
+	/// Multiplier on activation `node` sends (1.0 without node gains).
+	#[inline]
+	#[must_use]
+	pub fn emission_gain(&self, node: usize) -> f64 {
+		self.node_gains
+			.as_ref()
+			.and_then(|gains| gains.emission.get(node))
+			.copied()
+			.unwrap_or(1.0)
+	}
+
+	/// Multiplier on activation `node` receives (1.0 without node gains).
+	#[inline]
+	#[must_use]
+	pub fn attraction_gain(&self, node: usize) -> f64 {
+		self.node_gains
+			.as_ref()
+			.and_then(|gains| gains.attraction.get(node))
+			.copied()
+			.unwrap_or(1.0)
+	}
+
+	/// Number of nodes in the graph.
+	#[inline]
+	#[must_use]
+	pub const fn num_nodes(&self) -> usize {
+		self.num_nodes
+	}
+
+	/// Number of (valid) associations indexed.
+	#[inline]
+	#[must_use]
+	pub fn num_edges(&self) -> usize {
+		self.forward.targets.len()
+	}
+
+	/// Outgoing edges of `node`, weighted by forward strength.
+	///
+	/// # Panics
+	///
+	/// Panics if `node >= num_nodes()`.
+	#[inline]
+	#[must_use]
+	pub fn forward(&self, node: usize) -> Neighbors<'_> {
+		self.forward.row(node)
+	}
+
+	/// Incoming edges of `node`, weighted by backward strength.
+	///
+	/// # Panics
+	///
+	/// Panics if `node >= num_nodes()`.
+	#[inline]
+	#[must_use]
+	pub fn backward(&self, node: usize) -> Neighbors<'_> {
+		self.backward.row(node)
+	}
+
+	/// Type of the association with edge id `edge`.
+	///
+	/// # Panics
+	///
+	/// Panics if `edge >= num_edges()`.
+	#[inline]
+	#[must_use]
+	pub fn association_type(&self, edge: usize) -> AssociationType {
+		self.association_types[edge]
+	}
+}
+
+// ============================================================================
+// Mutable Graph
+// ============================================================================
+
+/// Bookkeeping a [`MemoryGraph`] keeps for each association.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EdgeMetadata {
+	/// Time (ms) at which the stored strengths were last brought current,
+	/// by reinforcement or decay. `None` until first stamped.
+	pub last_reinforced_ms: Option<f64>,
+	/// Consolidation state, which selects the decay time constant
+	pub state: AssociationState,
+}
+
+impl Default for EdgeMetadata {
+	fn default() -> Self {
+		Self {
+			last_reinforced_ms: None,
+			state: AssociationState::Consolidated,
+		}
+	}
+}
+
+/// Editable association graph that learns from use.
+///
+/// `MemoryGraph` owns the association list and keeps an `(source, target)`
+/// lookup so edges can be updated in place. Queries run against the
+/// immutable CSR snapshot returned by [`MemoryGraph::index`].
+#[derive(Clone, Debug, Default)]
+pub struct MemoryGraph {
+	num_nodes: usize,
+	associations: Vec<Association>,
+	metadata: Vec<EdgeMetadata>,
+	edge_lookup: HashMap<(usize, usize), usize>,
+	normalization: Option<StrengthNormalization>,
+}
+
+impl MemoryGraph {
+	/// Largest node count a graph read from outside the process may
+	/// declare (2²⁸). Snapshots and imports claiming more are rejected,
+	/// so one corrupt count or stray index fails fast instead of sizing
+	/// the next [`index`](Self::index) to billions of nodes.
+	pub const MAX_NODES: usize = 1 << 28;
+
+	/// Create an empty graph with `num_nodes` nodes.
+	#[must_use]
+	pub fn new(num_nodes: usize) -> Self {
+		Self {
+			num_nodes,
+			..Self::default()
+		}
+	}
+
+	/// Create a graph from existing associations.
+	///
+	/// When the same `(source, target)` pair appears more than once, the
+	/// last association wins. Associations with an endpoint at or beyond
+	/// `num_nodes` are skipped, as [`AssociationGraph::new`] skips them.
+	#[must_use]
+	pub fn from_associations(num_nodes: usize, associations: Vec<Association>) -> Self {
+		let mut graph = Self::new(num_nodes);
+		for association in associations {
+			let _ = graph.insert_association(association);
+		}
+		graph
+	}
+
+	/// Number of nodes in the graph.
+	#[inline]
+	#[must_use]
+	pub const fn num_nodes(&self) -> usize {
+		self.num_nodes
+	}
+
+	/// Number of associations in the graph.
+	#[inline]
+	#[must_use]
+	pub fn num_edges(&self) -> usize {
+		self.associations.len()
+	}
+
+	/// Strength normalization most recently applied, if any.
+	///
+	/// Set by [`normalize_strengths`](crate::normalize::normalize_strengths).
+	/// Strengths added or learned afterwards are not rescaled, so this
+	/// records how the graph was last brought onto a common scale, not an
+	/// invariant.
+	#[inline]
+	#[must_use]
+	pub const fn normalization(&self) -> Option<StrengthNormalization> {
+		self.normalization
+	}
+
+	/// Record the strength normalization applied to the graph, for graphs
+	/// restored from storage.
+	pub const fn set_normalization(&mut self, normalization: Option<StrengthNormalization>) {
+		self.normalization = normalization;
+	}
+
+	/// Append a node and return its index.
+	pub const fn add_node(&mut self) -> usize {
+		self.num_nodes += 1;
+		self.num_nodes - 1
+	}
+
+	/// All associations, in insertion order unless
+	/// [`remove_association`](Self::remove_association) has reordered them.
+	#[inline]
+	#[must_use]
+	pub fn associations(&self) -> &[Association] {
+		&self.associations
+	}
+
+	/// The association from `source` to `target`, if any.
+	#[must_use]
+	pub fn association(&self, source: usize, target: usize) -> Option<&Association> {
+		self.edge_lookup
+			.get(&(source, target))
+			.map(|&i| &self.associations[i])
+	}
+
+	/// Mutable access to the association from `source` to `target`.
+	#[must_use]
+	pub fn association_mut(&mut self, source: usize, target: usize) -> Option<&mut Association> {
+		self.edge_lookup
+			.get(&(source, target))
+			.map(|&i| &mut self.associations[i])
+	}
+
+	/// Metadata for the association from `source` to `target`, if any.
+	#[must_use]
+	pub fn metadata(&self, source: usize, target: usize) -> Option<&EdgeMetadata> {
+		self.edge_lookup
+			.get(&(source, target))
+			.map(|&i| &self.metadata[i])
+	}
+
+	/// Mutable metadata for the association from `source` to `target`.
+	#[must_use]
+	pub fn metadata_mut(&mut self, source: usize, target: usize) -> Option<&mut EdgeMetadata> {
+		self.edge_lookup
+			.get(&(source, target))
+			.map(|&i| &mut self.metadata[i])
+	}
+
+	/// Mutable access to an association and its metadata together.
+	#[must_use]
+	pub fn edge_mut(
+		&mut self,
+		source: usize,
+		target: usize,
+	) -> Option<(&mut Association, &mut EdgeMetadata)> {
+		let &i = self.edge_lookup.get(&(source, target))?;
+		Some((&mut self.associations[i], &mut self.metadata[i]))
+	}
+
+	/// Iterate associations mutably alongside their metadata.
+	pub fn edges_mut(&mut self) -> impl Iterator<Item = (&mut Association, &mut EdgeMetadata)> {
+		self.associations.iter_mut().zip(self.metadata.iter_mut())
+	}
+
+	/// Record that the association from `source` to `target` was reinforced
+	/// at `current_time_ms`. Returns `false` if there is no such edge.
+	pub fn mark_reinforced(&mut self, source: usize, target: usize, current_time_ms: f64) -> bool {
+		self.metadata_mut(source, target)
+			.map(|meta| meta.last_reinforced_ms = Some(current_time_ms))
+			.is_some()
+	}
+
+	/// Insert an association, replacing any existing edge with the same
+	/// `(source, target)` pair.
+	///
+	/// Returns the replaced association; a replaced edge keeps its
+	/// metadata. Both endpoints must already be nodes: an association
+	/// reaching `num_nodes()` or beyond is not inserted and is returned as
+	/// is. Grow the graph with [`add_node`](Self::add_node) first.
+	pub fn insert_association(&mut self, association: Association) -> Option<Association> {
+		if association.source >= self.num_nodes || association.target >= self.num_nodes {
+			return Some(association);
+		}
+
+		let key = (association.source, association.target);
+		if let Some(&i) = self.edge_lookup.get(&key) {
+			return Some(std::mem::replace(&mut self.associations[i], association));
+		}
+
+		let _ = self.edge_lookup.insert(key, self.associations.len());
+		self.associations.push(association);
+		self.metadata.push(EdgeMetadata::default());
+		None
+	}
+
+	/// Remove the association from `source` to `target`.
+	///
+	/// Runs in constant time: the last association takes the removed one's
+	/// place, so [`associations`](Self::associations) is no longer in
+	/// insertion order afterwards.
+	pub fn remove_association(&mut self, source: usize, target: usize) -> Option<Association> {
+		let i = self.edge_lookup.remove(&(source, target))?;
+		let removed = self.associations.swap_remove(i);
+		let _ = self.metadata.swap_remove(i);
+		if let Some(moved) = self.associations.get(i) {
+			let _ = self.edge_lookup.insert((moved.source, moved.target), i);
+		}
+		Some(removed)
+	}
+
+	/// Remove every association matching `predicate`, returning them in
+	/// insertion order.
+	///
+	/// `predicate` sees every association before any is removed, so if it
+	/// panics the graph is left unchanged.
+	pub fn remove_associations_where(
+		&mut self,
+		mut predicate: impl FnMut(&Association, &EdgeMetadata) -> bool,
+	) -> Vec<Association> {
+		let matches: Vec<bool> = self
+			.associations
+			.iter()
+			.zip(&self.metadata)
+			.map(|(association, meta)| predicate(association, meta))
+			.collect();
+		if !matches.contains(&true) {
+			return Vec::new();
+		}
+
+		let mut removed = Vec::new();
+		let mut kept_associations = Vec::with_capacity(self.associations.len());
+		let mut kept_metadata = Vec::with_capacity(self.metadata.len());
+		for ((association, meta), remove) in self
+			.associations
+			.drain(..)
+			.zip(self.metadata.drain(..))
+			.zip(matches)
+		{
+			if remove {
+				removed.push(association);
+			} else {
+				kept_associations.push(association);
+				kept_metadata.push(meta);
+			}
+		}
+		self.associations = kept_associations;
+		self.metadata = kept_metadata;
+		self.reindex();
+		removed
+	}
+
+	/// Rebuild the `(source, target)` lookup from the association list.
+	pub(crate) fn reindex(&mut self) {
+		self.edge_lookup = self
+			.associations
+			.iter()
+			.enumerate()
+			.map(|(i, a)| ((a.source, a.target), i))
+			.collect();
+	}
+
+	/// Drop nodes `num_nodes..` and every association touching them.
+	///
+	/// Returns the removed associations. A graph that already has at most
+	/// `num_nodes` nodes is left unchanged.
+	pub fn truncate_nodes(&mut self, num_nodes: usize) -> Vec<Association> {
+		if num_nodes >= self.num_nodes {
+			return Vec::new();
+		}
+		self.num_nodes = num_nodes;
+		self.remove_associations_where(|a, _| a.source >= num_nodes || a.target >= num_nodes)
+	}
+
+	/// Build a CSR snapshot of the current graph for querying.
+	#[must_use]
+	pub fn index(&self) -> AssociationGraph {
+		AssociationGraph::new(self.num_nodes, &self.associations)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn make_assoc(source: usize, target: usize, strength: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength * 0.5,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	#[test]
+	fn test_csr_rows() {
+		let associations = vec![
+			make_assoc(0, 1, 1.0),
+			make_assoc(2, 1, 0.4),
+			make_assoc(0, 2, 0.8),
+		];
+		let graph = AssociationGraph::new(3, &associations);
+
+		assert_eq!(graph.num_edges(), 3);
+		assert_eq!(graph.forward(0).targets, &[1, 2]);
+		assert_eq!(graph.forward(0).strengths, &[1.0, 0.8]);
+		assert!(graph.forward(1).is_empty());
+		assert_eq!(graph.backward(1).targets, &[0, 2]);
+		assert_eq!(graph.backward(1).strengths, &[0.5, 0.2]);
+		assert_eq!(graph.forward(0).edges, &[0, 2]);
+		assert_eq!(graph.backward(1).edges, &[0, 1]);
+	}
+
+	#[test]
+	fn test_memory_graph_upsert() {
+		let mut graph = MemoryGraph::new(2);
+		assert!(graph.insert_association(make_assoc(0, 1, 0.5)).is_none());
+		let replaced = graph.insert_association(make_assoc(0, 1, 0.9));
+
+		assert!(replaced.is_some_and(|a| (a.forward_strength - 0.5).abs() < f64::EPSILON));
+		assert_eq!(graph.num_edges(), 1);
+		assert!(graph
+			.association(0, 1)
+			.is_some_and(|a| (a.forward_strength - 0.9).abs() < f64::EPSILON));
+		assert!(graph.association(1, 0).is_none());
+
+		let _ = graph.add_node();
+		let _ = graph.add_node();
+		let _ = graph.insert_association(make_assoc(1, 3, 0.4));
+		assert_eq!(graph.num_nodes(), 4);
+		assert_eq!(graph.index().forward(1).targets, &[3]);
+	}
+
+	#[test]
+	fn test_memory_graph_rejects_out_of_range_endpoints() {
+		let mut graph = MemoryGraph::new(2);
+		let rejected = graph.insert_association(make_assoc(0, usize::MAX, 0.5));
+		assert!(rejected.is_some_and(|a| a.target == usize::MAX));
+		let rejected = graph.insert_association(make_assoc(2, 0, 0.5));
+		assert!(rejected.is_some_and(|a| a.source == 2));
+		assert_eq!(graph.num_nodes(), 2);
+		assert_eq!(graph.num_edges(), 0);
+
+		let node = graph.add_node();
+		assert!(graph.insert_association(make_assoc(node, 0, 0.5)).is_none());
+		assert_eq!(graph.num_edges(), 1);
+
+		let skipped =
+			MemoryGraph::from_associations(2, vec![make_assoc(0, 1, 0.5), make_assoc(1, 9, 0.5)]);
+		assert_eq!(skipped.num_nodes(), 2);
+		assert_eq!(skipped.num_edges(), 1);
+	}
+
+	#[test]
+	fn test_memory_graph_remove() {
+		let mut graph = MemoryGraph::from_associations(
+			4,
+			vec![
+				make_assoc(0, 1, 0.1),
+				make_assoc(1, 2, 0.2),
+				make_assoc(2, 3, 0.3),
+			],
+		);
+		assert!(graph.mark_reinforced(2, 3, 5.0));
+
+		let removed = graph.remove_association(0, 1);
+		assert!(removed.is_some_and(|a| (a.forward_strength - 0.1).abs() < f64::EPSILON));
+		assert!(graph.remove_association(0, 1).is_none());
+		assert_eq!(graph.num_edges(), 2);
+
+		// The moved edge is still found, with its own metadata
+		assert!(graph
+			.association(2, 3)
+			.is_some_and(|a| (a.forward_strength - 0.3).abs() < f64::EPSILON));
+		assert!(graph
+			.metadata(2, 3)
+			.is_some_and(|m| m.last_reinforced_ms.is_some()));
+		assert!(graph.association(1, 2).is_some());
+
+		// Re-inserting after removal replaces in place
+		let _ = graph.insert_association(make_assoc(2, 3, 0.9));
+		assert_eq!(graph.num_edges(), 2);
+		assert!(graph.remove_association(2, 3).is_some());
+		assert!(graph.remove_association(1, 2).is_some());
+		assert_eq!(graph.num_edges(), 0);
+	}
+
+	#[test]
+	fn test_memory_graph_remove_where_panic_leaves_graph() {
+		let mut graph =
+			MemoryGraph::from_associations(3, vec![make_assoc(0, 1, 0.1), make_assoc(1, 2, 0.2)]);
+		let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			graph.remove_associations_where(|a, _| {
+				assert!(a.source == 0, "predicate failed");
+				true
+			})
+		}));
+		assert!(outcome.is_err());
+		assert_eq!(graph.num_edges(), 2);
+		assert!(graph.association(1, 2).is_some());
+
+		let removed = graph.remove_associations_where(|a, _| a.source == 0);
+		assert_eq!(removed.len(), 1);
+		assert!(graph.association(0, 1).is_none());
+		assert!(graph.association(1, 2).is_some());
+	}
+
+	#[test]
+	fn test_csr_skips_out_of_range() {
+		let associations = vec![make_assoc(0, 1, 1.0), make_assoc(0, 7, 1.0)];
+		let graph = AssociationGraph::new(2, &associations);
+
+		assert_eq!(graph.num_edges(), 1);
+		assert_eq!(graph.forward(0).len(), 1);
+	}
+}
@@ -0,0 +1,222 @@
+//! Activation History
+//!
+//! A rolling record of how active each memory was at successive retrieval
+//! events, for analytics ("how has this memory's activation moved over
+//! the last 30 days?") and for tuning decay against observed behavior.
+//!
+//! Each recorded event keeps only the nodes at or above a minimum
+//! activation, so a history over a large graph costs memory in proportion
+//! to what retrieval actually touched. Once the history holds `capacity`
+//! events, recording another drops the oldest.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::ops::RangeBounds;
+
+/// Configuration for an activation history.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ActivationHistoryConfig {
+	/// Maximum number of events kept (default: 1000)
+	pub capacity: usize,
+	/// Activation below which a node is not stored for an event (default: 0.01)
+	pub min_activation: f64,
+}
+
+impl Default for ActivationHistoryConfig {
+	fn default() -> Self {
+		Self {
+			capacity: 1000,
+			min_activation: 0.01,
+		}
+	}
+}
+
+/// Activations recorded at one retrieval event.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ActivationEvent {
+	/// Time of the event (ms)
+	pub timestamp_ms: f64,
+	/// `(node, activation)` for stored nodes, ascending by node
+	pub activations: Vec<(usize, f64)>,
+}
+
+impl ActivationEvent {
+	/// Activation of `node` at this event (0 if it was not stored).
+	#[must_use]
+	pub fn activation(&self, node: usize) -> f64 {
+		self.activations
+			.binary_search_by_key(&node, |&(n, _)| n)
+			.map_or(0.0, |i| self.activations[i].1)
+	}
+}
+
+/// One point on a node's activation trajectory.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActivationPoint {
+	/// Time of the event (ms)
+	pub timestamp_ms: f64,
+	/// Activation of the node at that event
+	pub activation: f64,
+}
+
+/// Per-node activation over successive retrieval events.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ActivationHistory {
+	config: ActivationHistoryConfig,
+	events: VecDeque<ActivationEvent>,
+}
+
+impl ActivationHistory {
+	/// Create an empty history.
+	#[must_use]
+	pub const fn new(config: ActivationHistoryConfig) -> Self {
+		Self {
+			config,
+			events: VecDeque::new(),
+		}
+	}
+
+	/// Number of events held.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.events.len()
+	}
+
+	/// Whether no events are held.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.events.is_empty()
+	}
+
+	/// Events held, oldest first.
+	pub fn events(&self) -> impl Iterator<Item = &ActivationEvent> {
+		self.events.iter()
+	}
+
+	/// Record the activations of one retrieval event.
+	///
+	/// `activations` is indexed by node, as in
+	/// [`SpreadingResult::activations`](crate::spreading::SpreadingResult::activations).
+	/// Events are kept in time order even if recorded out of order. With
+	/// the history full, the oldest event is dropped; an event older than
+	/// everything held is then not kept at all. A capacity of 0 keeps
+	/// nothing.
+	pub fn record(&mut self, timestamp_ms: f64, activations: &[f64]) {
+		if self.config.capacity == 0 {
+			return;
+		}
+		let event = ActivationEvent {
+			timestamp_ms,
+			activations: activations
+				.iter()
+				.enumerate()
+				.filter(|&(_, &a)| a >= self.config.min_activation)
+				.map(|(node, &a)| (node, a))
+				.collect(),
+		};
+		let position = self
+			.events
+			.partition_point(|e| e.timestamp_ms <= timestamp_ms);
+		if self.events.len() >= self.config.capacity {
+			if position == 0 {
+				return;
+			}
+			let _ = self.events.pop_front();
+			self.events.insert(position - 1, event);
+		} else {
+			self.events.insert(position, event);
+		}
+	}
+
+	/// Activation of `node` at each event in `window` (times in ms).
+	///
+	/// Every event in the window yields a point, with 0 where the node was
+	/// below the minimum activation, so gaps read as inactivity.
+	///
+	/// # Example
+	///
+	/// The last 30 days: `history.trajectory(node, now_ms - 30.0 * DAY_MS..)`.
+	#[must_use]
+	pub fn trajectory(&self, node: usize, window: impl RangeBounds<f64>) -> Vec<ActivationPoint> {
+		self.events
+			.iter()
+			.filter(|e| window.contains(&e.timestamp_ms))
+			.map(|e| ActivationPoint {
+				timestamp_ms: e.timestamp_ms,
+				activation: e.activation(node),
+			})
+			.collect()
+	}
+
+	/// Mean activation of `node` over the events in `window` (0 if none).
+	#[must_use]
+	pub fn mean_activation(&self, node: usize, window: impl RangeBounds<f64>) -> f64 {
+		let trajectory = self.trajectory(node, window);
+		if trajectory.is_empty() {
+			return 0.0;
+		}
+		#[allow(clippy::cast_precision_loss)]
+		let count = trajectory.len() as f64;
+		trajectory.iter().map(|p| p.activation).sum::<f64>() / count
+	}
+
+	/// Most recent event at which `node` was stored, if any.
+	#[must_use]
+	pub fn last_active(&self, node: usize) -> Option<&ActivationEvent> {
+		self.events.iter().rev().find(|e| {
+			e.activations
+				.binary_search_by_key(&node, |&(n, _)| n)
+				.is_ok()
+		})
+	}
+
+	/// Drop events before `timestamp_ms`, returning how many were dropped.
+	pub fn forget_before(&mut self, timestamp_ms: f64) -> usize {
+		let stale = self
+			.events
+			.partition_point(|e| e.timestamp_ms < timestamp_ms);
+		let _ = self.events.drain(..stale);
+		stale
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_history_trajectory_and_ring_buffer() {
+		let mut history = ActivationHistory::new(ActivationHistoryConfig {
+			capacity: 3,
+			min_activation: 0.1,
+		});
+		history.record(100.0, &[0.5, 0.05]);
+		history.record(300.0, &[0.9, 0.4]);
+		// Out of order: slots in before 300
+		history.record(200.0, &[0.0, 0.6]);
+		assert_eq!(history.len(), 3);
+
+		let trajectory = history.trajectory(1, ..);
+		let points: Vec<(f64, f64)> = trajectory
+			.iter()
+			.map(|p| (p.timestamp_ms, p.activation))
+			.collect();
+		assert_eq!(points, vec![(100.0, 0.0), (200.0, 0.6), (300.0, 0.4)]);
+		assert!((history.mean_activation(0, 150.0..) - 0.45).abs() < 1e-12);
+
+		// Full: the oldest event (100) is dropped
+		history.record(400.0, &[0.0, 0.0]);
+		assert_eq!(history.len(), 3);
+		assert!(history
+			.events()
+			.next()
+			.is_some_and(|e| e.timestamp_ms.total_cmp(&200.0).is_eq()));
+		assert!(history
+			.last_active(0)
+			.is_some_and(|e| e.timestamp_ms.total_cmp(&300.0).is_eq()));
+
+		assert_eq!(history.forget_before(350.0), 2);
+		assert!(history.last_active(0).is_none());
+		assert!(history.mean_activation(0, ..100.0).abs() < f64::EPSILON);
+	}
+}
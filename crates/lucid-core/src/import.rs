@@ -0,0 +1,697 @@
+//! Graph Import
+//!
+//! Build a [`MemoryGraph`] from edge lists written by other tools, as CSV
+//! or JSON.
+//!
+//! Every row is validated: node indices must be non-negative integers
+//! (and inside the graph when its size is fixed), strengths must be
+//! finite and within `0..=max_strength`, and association types must be
+//! known. A bad row is skipped and reported with its position, so one
+//! typo does not throw away the rest of the file. Parallel edges (the
+//! same source and target more than once) are merged per
+//! [`DuplicatePolicy`].
+//!
+//! CSV input needs a header row naming its columns:
+//!
+//! `source,target,forward_strength,backward_strength,association_type`
+//!
+//! Only `source` and `target` are required. `strength` or `weight` may
+//! stand in for `forward_strength`; a missing backward strength copies the
+//! forward one, and a missing strength defaults to 1.0. Blank lines and
+//! lines starting with `#` are skipped. Fields are split on commas, so
+//! values cannot contain commas.
+//!
+//! JSON input is either a bare array of edge objects with the same field
+//! names, or an object:
+//!
+//! `{"num_nodes": 4, "nodes": [{"id": 0}, 3], "edges": [{"source": 0, "target": 3}]}`
+//!
+//! Where `num_nodes` fixes the graph size and `nodes` lists node ids
+//! (bare or as `id` fields) that must exist even without edges.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Classify, ErrorKind};
+use crate::graph::MemoryGraph;
+use crate::spreading::{Association, AssociationType};
+
+/// How to merge parallel edges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DuplicatePolicy {
+	/// Add strengths, capped at the maximum strength
+	Sum,
+	/// Keep the larger of each strength (the default)
+	#[default]
+	Max,
+	/// Keep the first occurrence
+	First,
+	/// Keep the last occurrence
+	Last,
+	/// Keep the first occurrence and report the rest as errors
+	Reject,
+}
+
+impl DuplicatePolicy {
+	/// Merge a parallel edge into the one already kept.
+	///
+	/// Returns `false`, leaving `kept` untouched, under `Reject`.
+	pub(crate) fn merge(
+		self,
+		kept: &mut Association,
+		incoming: Association,
+		max_strength: f64,
+	) -> bool {
+		match self {
+			Self::Sum => {
+				kept.forward_strength =
+					(kept.forward_strength + incoming.forward_strength).min(max_strength);
+				kept.backward_strength =
+					(kept.backward_strength + incoming.backward_strength).min(max_strength);
+			}
+			Self::Max => {
+				kept.forward_strength = kept.forward_strength.max(incoming.forward_strength);
+				kept.backward_strength = kept.backward_strength.max(incoming.backward_strength);
+			}
+			Self::First => {}
+			Self::Last => *kept = incoming,
+			Self::Reject => return false,
+		}
+		true
+	}
+}
+
+/// Configuration for graph import.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImportConfig {
+	/// How parallel edges are merged (default: `Max`)
+	pub duplicate_policy: DuplicatePolicy,
+	/// Fixed node count; out-of-range indices are errors. `None` uses the
+	/// file's `num_nodes` if it has one, else grows to fit, up to
+	/// [`MemoryGraph::MAX_NODES`] (default: None)
+	pub num_nodes: Option<usize>,
+	/// Largest valid strength (default: 1.0)
+	pub max_strength: f64,
+}
+
+impl Default for ImportConfig {
+	fn default() -> Self {
+		Self {
+			duplicate_policy: DuplicatePolicy::Max,
+			num_nodes: None,
+			max_strength: 1.0,
+		}
+	}
+}
+
+/// Error type for graph import.
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+	/// The document as a whole could not be read.
+	#[error("Malformed input: {0}")]
+	Syntax(String),
+
+	/// A required field is absent.
+	#[error("Missing field: {0}")]
+	MissingField(&'static str),
+
+	/// A field has the wrong form.
+	#[error("Invalid {field}: {value}")]
+	InvalidValue {
+		/// Field name
+		field: &'static str,
+		/// The offending value
+		value: String,
+	},
+
+	/// A node index is outside a fixed-size graph, or past
+	/// [`MemoryGraph::MAX_NODES`] when the graph grows to fit.
+	#[error("Node {node} out of range (graph has {num_nodes} nodes)")]
+	NodeOutOfRange {
+		/// Offending index
+		node: usize,
+		/// Graph size
+		num_nodes: usize,
+	},
+
+	/// A strength is not finite or outside `0..=max_strength`.
+	#[error("{field} out of range: {value}")]
+	StrengthOutOfRange {
+		/// Field name
+		field: &'static str,
+		/// The offending strength
+		value: f64,
+	},
+
+	/// An association type name is not recognized.
+	#[error("Unknown association type: {0}")]
+	UnknownAssociationType(String),
+
+	/// A parallel edge under [`DuplicatePolicy::Reject`].
+	#[error("Duplicate association {from} -> {to}")]
+	DuplicateEdge {
+		/// Source node
+		from: usize,
+		/// Target node
+		to: usize,
+	},
+}
+
+impl Classify for ImportError {
+	fn kind(&self) -> ErrorKind {
+		ErrorKind::InvalidInput
+	}
+}
+
+/// Which list a bad row came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RowKind {
+	/// A node entry (JSON `nodes`)
+	Node,
+	/// An edge entry
+	Edge,
+}
+
+/// A row that was skipped or rejected.
+#[derive(Debug)]
+pub struct RowError {
+	/// List the row came from
+	pub kind: RowKind,
+	/// 1-based line number for CSV, 0-based array index for JSON
+	pub row: usize,
+	/// What was wrong with it
+	pub error: ImportError,
+}
+
+/// Result of an import.
+#[derive(Debug)]
+pub struct ImportReport {
+	/// Graph built from the valid rows
+	pub graph: MemoryGraph,
+	/// Rows that were skipped or rejected, in input order
+	pub errors: Vec<RowError>,
+	/// Parallel edges merged into an earlier row
+	pub merged_duplicates: usize,
+}
+
+impl ImportReport {
+	/// Whether every row was imported.
+	#[must_use]
+	pub fn is_clean(&self) -> bool {
+		self.errors.is_empty()
+	}
+}
+
+/// Import an edge list from CSV.
+///
+/// # Errors
+///
+/// Returns [`ImportError::Syntax`] if the header is missing or lacks a
+/// `source` or `target` column. Errors in individual rows are reported
+/// in the [`ImportReport`] instead.
+pub fn import_csv(text: &str, config: &ImportConfig) -> Result<ImportReport, ImportError> {
+	let mut lines = text
+		.lines()
+		.enumerate()
+		.map(|(i, line)| (i + 1, line.trim()))
+		.filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+	let Some((_, header)) = lines.next() else {
+		return Err(ImportError::Syntax("missing header row".to_string()));
+	};
+	let columns: Vec<String> = split_csv(header).map(str::to_lowercase).collect();
+	let column = |names: &[&str]| columns.iter().position(|c| names.contains(&c.as_str()));
+	let (Some(source), Some(target)) = (column(&["source"]), column(&["target"])) else {
+		return Err(ImportError::Syntax(
+			"header needs source and target columns".to_string(),
+		));
+	};
+	let forward = column(&["forward_strength", "strength", "weight"]);
+	let backward = column(&["backward_strength"]);
+	let association_type = column(&["association_type", "type"]);
+
+	let mut builder = Builder::new(config, None);
+	for (line_number, line) in lines {
+		let fields: Vec<&str> = split_csv(line).collect();
+		let field = |index: Option<usize>| {
+			index
+				.and_then(|i| fields.get(i))
+				.filter(|value| !value.is_empty())
+				.map_or(Field::Missing, |value| Field::Text(value))
+		};
+		builder.add_edge(
+			line_number,
+			&RawEdge {
+				source: field(Some(source)),
+				target: field(Some(target)),
+				forward: field(forward),
+				backward: field(backward),
+				association_type: field(association_type),
+			},
+		);
+	}
+	Ok(builder.finish())
+}
+
+/// Import an edge list from JSON.
+///
+/// # Errors
+///
+/// Returns [`ImportError::Syntax`] if the text is not JSON or not one of
+/// the accepted shapes. Errors in individual rows are reported in the
+/// [`ImportReport`] instead.
+pub fn import_json(text: &str, config: &ImportConfig) -> Result<ImportReport, ImportError> {
+	let document: serde_json::Value =
+		serde_json::from_str(text).map_err(|e| ImportError::Syntax(e.to_string()))?;
+
+	let (num_nodes, nodes, edges) = if let Some(edges) = document.as_array() {
+		(None, None, edges.as_slice())
+	} else if document.as_object().is_some() {
+		let num_nodes = match document.get("num_nodes") {
+			None => None,
+			Some(value) => match json_field(value).index("num_nodes") {
+				Ok(n) if n <= MemoryGraph::MAX_NODES => Some(n),
+				Ok(n) => {
+					return Err(ImportError::Syntax(format!(
+						"num_nodes {n} exceeds {}",
+						MemoryGraph::MAX_NODES
+					)))
+				}
+				Err(e) => return Err(ImportError::Syntax(e.to_string())),
+			},
+		};
+		let list = |key: &str| {
+			document.get(key).map_or(Ok(None), |value| {
+				value
+					.as_array()
+					.map(Some)
+					.ok_or_else(|| ImportError::Syntax(format!("{key} must be an array")))
+			})
+		};
+		(
+			num_nodes,
+			list("nodes")?,
+			list("edges")?.map_or(&[][..], Vec::as_slice),
+		)
+	} else {
+		return Err(ImportError::Syntax(
+			"expected an array of edges or an object".to_string(),
+		));
+	};
+
+	let mut builder = Builder::new(config, num_nodes);
+	for (row, node) in nodes.into_iter().flatten().enumerate() {
+		let id = node.get("id").unwrap_or(node);
+		builder.add_node(row, &json_field(id));
+	}
+	for (row, edge) in edges.iter().enumerate() {
+		if edge.as_object().is_none() {
+			builder.reject(
+				RowKind::Edge,
+				row,
+				ImportError::InvalidValue {
+					field: "edge",
+					value: edge.to_string(),
+				},
+			);
+			continue;
+		}
+		let field = |key: &str| edge.get(key).map_or(Field::Missing, json_field);
+		let forward = ["forward_strength", "strength", "weight"]
+			.into_iter()
+			.map(field)
+			.find(|f| !matches!(f, Field::Missing))
+			.unwrap_or(Field::Missing);
+		let association_type = match field("association_type") {
+			Field::Missing => field("type"),
+			found => found,
+		};
+		builder.add_edge(
+			row,
+			&RawEdge {
+				source: field("source"),
+				target: field("target"),
+				forward,
+				backward: field("backward_strength"),
+				association_type,
+			},
+		);
+	}
+	Ok(builder.finish())
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// An unvalidated field value.
+#[derive(Clone, Copy)]
+enum Field<'a> {
+	Missing,
+	Number(f64),
+	Text(&'a str),
+	/// A JSON value of the wrong kind, rendered for the error message
+	Other(&'a serde_json::Value),
+}
+
+impl Field<'_> {
+	fn render(&self) -> String {
+		match self {
+			Self::Missing => String::new(),
+			Self::Number(value) => value.to_string(),
+			Self::Text(value) => (*value).to_string(),
+			Self::Other(value) => value.to_string(),
+		}
+	}
+
+	fn invalid(&self, field: &'static str) -> ImportError {
+		ImportError::InvalidValue {
+			field,
+			value: self.render(),
+		}
+	}
+
+	fn number(&self, field: &'static str) -> Result<Option<f64>, ImportError> {
+		match self {
+			Self::Missing => Ok(None),
+			Self::Number(value) => Ok(Some(*value)),
+			Self::Text(text) => text.parse().map(Some).map_err(|_| self.invalid(field)),
+			Self::Other(_) => Err(self.invalid(field)),
+		}
+	}
+
+	fn index(&self, field: &'static str) -> Result<usize, ImportError> {
+		match self {
+			Self::Missing => Err(ImportError::MissingField(field)),
+			Self::Text(text) => text
+				.parse::<u32>()
+				.map(|index| index as usize)
+				.map_err(|_| self.invalid(field)),
+			Self::Number(value)
+				if value.fract() == 0.0 && (0.0..=u32::MAX.into()).contains(value) =>
+			{
+				#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+				Ok(*value as usize)
+			}
+			_ => Err(self.invalid(field)),
+		}
+	}
+}
+
+fn json_field(value: &serde_json::Value) -> Field<'_> {
+	match value {
+		serde_json::Value::Number(number) => {
+			number.as_f64().map_or(Field::Other(value), Field::Number)
+		}
+		serde_json::Value::String(text) => Field::Text(text),
+		_ => Field::Other(value),
+	}
+}
+
+fn split_csv(line: &str) -> impl Iterator<Item = &str> {
+	line.split(',')
+		.map(|field| field.trim().trim_matches('"').trim())
+}
+
+/// One edge row before validation.
+struct RawEdge<'a> {
+	source: Field<'a>,
+	target: Field<'a>,
+	forward: Field<'a>,
+	backward: Field<'a>,
+	association_type: Field<'a>,
+}
+
+/// Accumulates validated rows.
+struct Builder<'c> {
+	config: &'c ImportConfig,
+	/// Fixed node count, if any
+	limit: Option<usize>,
+	num_nodes: usize,
+	associations: Vec<Association>,
+	lookup: HashMap<(usize, usize), usize>,
+	errors: Vec<RowError>,
+	merged_duplicates: usize,
+}
+
+impl<'c> Builder<'c> {
+	fn new(config: &'c ImportConfig, file_num_nodes: Option<usize>) -> Self {
+		let limit = config.num_nodes.or(file_num_nodes);
+		Self {
+			config,
+			limit,
+			num_nodes: limit.unwrap_or(0),
+			associations: Vec::new(),
+			lookup: HashMap::new(),
+			errors: Vec::new(),
+			merged_duplicates: 0,
+		}
+	}
+
+	fn reject(&mut self, kind: RowKind, row: usize, error: ImportError) {
+		self.errors.push(RowError { kind, row, error });
+	}
+
+	fn node(&self, field: &Field<'_>, name: &'static str) -> Result<usize, ImportError> {
+		let node = field.index(name)?;
+		let num_nodes = self.limit.unwrap_or(MemoryGraph::MAX_NODES);
+		if node >= num_nodes {
+			return Err(ImportError::NodeOutOfRange { node, num_nodes });
+		}
+		Ok(node)
+	}
+
+	fn strength(&self, field: &Field<'_>, name: &'static str) -> Result<Option<f64>, ImportError> {
+		let Some(value) = field.number(name)? else {
+			return Ok(None);
+		};
+		if value.is_finite() && (0.0..=self.config.max_strength).contains(&value) {
+			Ok(Some(value))
+		} else {
+			Err(ImportError::StrengthOutOfRange { field: name, value })
+		}
+	}
+
+	fn add_node(&mut self, row: usize, id: &Field<'_>) {
+		match self.node(id, "id").and_then(|node| Self::end(node, node)) {
+			Ok(end) => self.num_nodes = self.num_nodes.max(end),
+			Err(error) => self.reject(RowKind::Node, row, error),
+		}
+	}
+
+	fn add_edge(&mut self, row: usize, raw: &RawEdge<'_>) {
+		match self.validate(raw) {
+			Ok(association) => self.insert(row, association),
+			Err(error) => self.reject(RowKind::Edge, row, error),
+		}
+	}
+
+	/// Node count needed to hold both endpoints.
+	fn end(source: usize, target: usize) -> Result<usize, ImportError> {
+		let node = source.max(target);
+		node.checked_add(1).ok_or(ImportError::NodeOutOfRange {
+			node,
+			num_nodes: MemoryGraph::MAX_NODES,
+		})
+	}
+
+	fn validate(&self, raw: &RawEdge<'_>) -> Result<Association, ImportError> {
+		let source = self.node(&raw.source, "source")?;
+		let target = self.node(&raw.target, "target")?;
+		let forward_strength = self
+			.strength(&raw.forward, "forward_strength")?
+			.unwrap_or_else(|| self.config.max_strength.min(1.0));
+		let backward_strength = self
+			.strength(&raw.backward, "backward_strength")?
+			.unwrap_or(forward_strength);
+		let association_type = match raw.association_type {
+			Field::Missing => AssociationType::default(),
+			Field::Text(name) => name
+				.parse()
+				.map_err(|_| ImportError::UnknownAssociationType(name.to_string()))?,
+			other => return Err(other.invalid("association_type")),
+		};
+		Ok(Association {
+			source,
+			target,
+			forward_strength,
+			backward_strength,
+			association_type,
+		})
+	}
+
+	fn insert(&mut self, row: usize, association: Association) {
+		let (source, target) = (association.source, association.target);
+		match Self::end(source, target) {
+			Ok(end) => self.num_nodes = self.num_nodes.max(end),
+			Err(error) => return self.reject(RowKind::Edge, row, error),
+		}
+
+		let Some(&existing) = self.lookup.get(&(source, target)) else {
+			let _ = self
+				.lookup
+				.insert((source, target), self.associations.len());
+			self.associations.push(association);
+			return;
+		};
+		let kept = &mut self.associations[existing];
+		if self
+			.config
+			.duplicate_policy
+			.merge(kept, association, self.config.max_strength)
+		{
+			self.merged_duplicates += 1;
+		} else {
+			self.reject(
+				RowKind::Edge,
+				row,
+				ImportError::DuplicateEdge {
+					from: source,
+					to: target,
+				},
+			);
+		}
+	}
+
+	fn finish(self) -> ImportReport {
+		ImportReport {
+			graph: MemoryGraph::from_associations(self.num_nodes, self.associations),
+			errors: self.errors,
+			merged_duplicates: self.merged_duplicates,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_import_csv_reports_bad_rows() {
+		let csv = "\
+source,target,strength,type
+0,1,0.5,semantic
+# comment
+1,2,1.5,causal
+2,x,0.5,
+3,0,0.25,telepathic
+0,1,0.75,semantic
+1,3,0.5,Temporal
+";
+		let result = import_csv(csv, &ImportConfig::default());
+		assert!(result.is_ok());
+		let Ok(report) = result else { return };
+
+		let rows: Vec<usize> = report.errors.iter().map(|e| e.row).collect();
+		assert_eq!(rows, vec![4, 5, 6]);
+		assert!(matches!(
+			report.errors[0].error,
+			ImportError::StrengthOutOfRange { .. }
+		));
+		assert!(matches!(
+			report.errors[2].error,
+			ImportError::UnknownAssociationType(_)
+		));
+
+		assert_eq!(report.merged_duplicates, 1);
+		assert_eq!(report.graph.num_edges(), 2);
+		assert_eq!(report.graph.num_nodes(), 4);
+		let strength = report.graph.association(0, 1).map(|a| a.forward_strength);
+		assert!(strength.is_some_and(|s| (s - 0.75).abs() < 1e-12));
+		assert!(report
+			.graph
+			.association(1, 3)
+			.is_some_and(|a| a.association_type == AssociationType::Temporal));
+
+		assert!(import_csv("# only a comment\n", &ImportConfig::default()).is_err());
+	}
+
+	#[test]
+	fn test_import_rejects_huge_node_ids() {
+		let config = ImportConfig::default();
+		for csv in [
+			"source,target\n0,18446744073709551615\n",
+			"source,target\n0,4000000000\n",
+		] {
+			let result = import_csv(csv, &config);
+			assert!(result.is_ok());
+			let Ok(report) = result else { return };
+			assert_eq!(report.errors.len(), 1);
+			assert_eq!(report.errors[0].row, 2);
+			assert_eq!(report.graph.num_nodes(), 0);
+		}
+		let result = import_csv("source,target\n0,4000000000\n", &config);
+		assert!(result.is_ok_and(|report| matches!(
+			report.errors[0].error,
+			ImportError::NodeOutOfRange {
+				node: 4_000_000_000,
+				..
+			}
+		)));
+
+		let result = import_json(r#"{"nodes": ["18446744073709551615", 2]}"#, &config);
+		assert!(result.is_ok());
+		let Ok(report) = result else { return };
+		assert_eq!(report.errors.len(), 1);
+		assert_eq!(report.errors[0].kind, RowKind::Node);
+		assert!(matches!(
+			report.errors[0].error,
+			ImportError::InvalidValue { field: "id", .. }
+		));
+		assert_eq!(report.graph.num_nodes(), 3);
+
+		assert!(import_json(r#"{"num_nodes": 4000000000}"#, &config).is_err());
+	}
+
+	#[test]
+	fn test_import_json_with_fixed_size_and_policy() {
+		let json = r#"{
+			"num_nodes": 3,
+			"nodes": [{"id": 0}, 2, {"id": -1}],
+			"edges": [
+				{"source": 0, "target": 1, "forward_strength": 0.5, "backward_strength": 0.25},
+				{"source": 0, "target": 1, "forward_strength": 0.75},
+				{"source": 1, "target": 5},
+				{"source": 1.5, "target": 2},
+				"not an edge"
+			]
+		}"#;
+		let sum = ImportConfig {
+			duplicate_policy: DuplicatePolicy::Sum,
+			..ImportConfig::default()
+		};
+		let result = import_json(json, &sum);
+		assert!(result.is_ok());
+		let Ok(report) = result else { return };
+
+		assert_eq!(report.graph.num_nodes(), 3);
+		assert_eq!(report.errors.len(), 4);
+		assert_eq!(report.errors[0].kind, RowKind::Node);
+		assert!(matches!(
+			report.errors[1].error,
+			ImportError::NodeOutOfRange {
+				node: 5,
+				num_nodes: 3
+			}
+		));
+		let edge = report.graph.association(0, 1);
+		assert!(
+			edge.is_some_and(|a| (a.forward_strength - 1.0).abs() < 1e-12
+				&& (a.backward_strength - 1.0).abs() < 1e-12)
+		);
+
+		let reject = ImportConfig {
+			duplicate_policy: DuplicatePolicy::Reject,
+			..ImportConfig::default()
+		};
+		let result = import_json(
+			r#"[{"source": 0, "target": 1}, {"source": 0, "target": 1}]"#,
+			&reject,
+		);
+		assert!(result.is_ok_and(|r| r.merged_duplicates == 0 && r.errors.len() == 1));
+
+		assert!(import_json("{\"edges\": 3}", &ImportConfig::default()).is_err());
+		assert!(import_json("[{", &ImportConfig::default()).is_err());
+	}
+}
@@ -0,0 +1,479 @@
+//! Streaming Ingestion
+//!
+//! An always-recording client produces moments a few at a time. Rebuilding
+//! the graph, episodes and embedding index after every capture session
+//! costs time proportional to the whole memory; [`Ingestor`] instead
+//! folds each moment into them as it arrives:
+//!
+//! - **Adjacency**: the moment's node is added to the [`MemoryGraph`]
+//! - **Episodes**: the moment joins the open episode, linked to the
+//!   `max_temporal_distance` events before it with strength decaying over
+//!   elapsed time, as [`create_episode_links_timed`] would link them. A gap
+//!   longer than `episode_gap_ms` closes the episode and opens a new one
+//! - **ANN index**: the moment's embedding is inserted into the
+//!   [`HnswIndex`]
+//!
+//! Structures that cannot be patched in place, the CSR snapshot and
+//! `PageRank`, are cached and rebuilt only when read after a change.
+//!
+//! Moments are taken in arrival order, which should be time order.
+//!
+//! [`create_episode_links_timed`]: crate::spreading::create_episode_links_timed
+
+use serde::{Deserialize, Serialize};
+
+use crate::ann::{AnnError, HnswConfig, HnswIndex};
+use crate::attributes::TraversalFilter;
+use crate::episodes::Episode;
+use crate::error::{Classify, ErrorKind};
+use crate::graph::{AssociationGraph, MemoryGraph};
+use crate::moment::{Modality, Moment};
+use crate::spreading::{
+	compute_elapsed_link_strength, compute_pagerank_with_config, Association, AssociationType,
+	PageRankConfig, PageRankResult, TemporalLink, TemporalSpreadingConfig,
+};
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Configuration for an [`Ingestor`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IngestConfig {
+	/// Base strengths and reach of temporal links
+	pub temporal: TemporalSpreadingConfig,
+	/// Time between moments at which temporal link strength halves, in ms
+	/// (default: 60000)
+	pub half_life_ms: f64,
+	/// Open a new episode when no moment was captured for this long, in ms;
+	/// 0 never splits (default: 30000)
+	pub episode_gap_ms: f64,
+	/// Also add temporal links to the graph as `Temporal` associations
+	/// (default: true)
+	pub temporal_associations: bool,
+	/// Which embedding of each moment goes into the index (default: text)
+	pub index_modality: Modality,
+	/// Parameters for an index created on the first embedding
+	pub hnsw: HnswConfig,
+	/// Parameters for the cached `PageRank`
+	pub pagerank: PageRankConfig,
+}
+
+impl Default for IngestConfig {
+	fn default() -> Self {
+		Self {
+			temporal: TemporalSpreadingConfig::default(),
+			half_life_ms: 60_000.0,
+			episode_gap_ms: 30_000.0,
+			temporal_associations: true,
+			index_modality: Modality::Text,
+			hnsw: HnswConfig::default(),
+			pagerank: PageRankConfig::default(),
+		}
+	}
+}
+
+/// Error type for ingestion. A rejected moment changes nothing.
+#[derive(Debug, thiserror::Error)]
+pub enum IngestError {
+	/// The moment's embedding does not fit the index.
+	#[error("Index error: {0}")]
+	Index(#[from] AnnError),
+}
+
+impl Classify for IngestError {
+	fn kind(&self) -> ErrorKind {
+		match self {
+			Self::Index(e) => e.kind(),
+		}
+	}
+}
+
+/// What ingesting one moment changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestReport {
+	/// Graph node of the moment
+	pub node: usize,
+	/// Position in [`Ingestor::episodes`] of the episode it joined
+	pub episode: usize,
+	/// Whether that episode was opened by this moment
+	pub new_episode: bool,
+	/// Temporal links created to earlier events
+	pub temporal_links: usize,
+	/// Whether an embedding was added to the index
+	pub indexed: bool,
+}
+
+// ============================================================================
+// Ingestor
+// ============================================================================
+
+/// The episode moments are currently joining.
+#[derive(Clone, Debug)]
+struct OpenEpisode {
+	/// Start time of each event (ms)
+	times: Vec<f64>,
+	/// Latest end time seen (ms)
+	last_end_ms: f64,
+}
+
+/// Incrementally maintained graph, episodes and embedding index.
+#[derive(Clone, Debug)]
+pub struct Ingestor {
+	config: IngestConfig,
+	graph: MemoryGraph,
+	index: Option<HnswIndex>,
+	episodes: Vec<Episode>,
+	open: Option<OpenEpisode>,
+	csr: Option<AssociationGraph>,
+	pagerank: Option<PageRankResult>,
+}
+
+impl Ingestor {
+	/// Create an ingestor with nothing ingested yet.
+	#[must_use]
+	pub fn new(config: IngestConfig) -> Self {
+		Self::from_parts(MemoryGraph::new(0), None, Vec::new(), config)
+	}
+
+	/// Resume from previously built structures.
+	///
+	/// `episodes` are history: the next moment opens a new episode.
+	#[must_use]
+	pub const fn from_parts(
+		graph: MemoryGraph,
+		index: Option<HnswIndex>,
+		episodes: Vec<Episode>,
+		config: IngestConfig,
+	) -> Self {
+		Self {
+			config,
+			graph,
+			index,
+			episodes,
+			open: None,
+			csr: None,
+			pagerank: None,
+		}
+	}
+
+	/// Ingestion configuration.
+	#[must_use]
+	pub const fn config(&self) -> &IngestConfig {
+		&self.config
+	}
+
+	/// The association graph.
+	#[must_use]
+	pub const fn graph(&self) -> &MemoryGraph {
+		&self.graph
+	}
+
+	/// The embedding index, once a moment with an embedding has arrived.
+	#[must_use]
+	pub const fn index(&self) -> Option<&HnswIndex> {
+		self.index.as_ref()
+	}
+
+	/// Episodes in the order they were opened; the last may still be open.
+	#[must_use]
+	pub fn episodes(&self) -> &[Episode] {
+		&self.episodes
+	}
+
+	/// Fold one moment into the graph, episodes and index.
+	///
+	/// # Errors
+	///
+	/// Returns [`IngestError::Index`] if the moment's embedding differs in
+	/// length from the index, or its id is already indexed. Nothing is
+	/// changed in that case.
+	pub fn ingest(&mut self, moment: &Moment) -> Result<IngestReport, IngestError> {
+		let vector: Option<Vec<f32>> = moment.embedding(self.config.index_modality).map(|v| {
+			#[allow(clippy::cast_possible_truncation)]
+			v.iter().map(|&x| x as f32).collect()
+		});
+		if let (Some(index), Some(vector)) = (&self.index, &vector) {
+			if vector.len() != index.dimension() {
+				return Err(AnnError::DimensionMismatch {
+					expected: index.dimension(),
+					actual: vector.len(),
+				}
+				.into());
+			}
+			if index.contains(moment.id) {
+				return Err(AnnError::DuplicateId(moment.id).into());
+			}
+		}
+
+		// Adjacency
+		while self.graph.num_nodes() <= moment.id {
+			let _ = self.graph.add_node();
+		}
+
+		// Episodes
+		let splits = self.open.as_ref().is_none_or(|open| {
+			self.config.episode_gap_ms > 0.0
+				&& moment.start_ms - open.last_end_ms > self.config.episode_gap_ms
+		});
+		if splits {
+			self.episodes.push(Episode {
+				events: Vec::new(),
+				links: Vec::new(),
+			});
+			self.open = Some(OpenEpisode {
+				times: Vec::new(),
+				last_end_ms: f64::NEG_INFINITY,
+			});
+		}
+		let links = self.link_into_open_episode(moment);
+		if self.config.temporal_associations {
+			for link in &links {
+				let _ = self.graph.insert_association(Association {
+					source: link.source_memory,
+					target: link.target_memory,
+					forward_strength: link.forward_strength,
+					backward_strength: link.backward_strength,
+					association_type: AssociationType::Temporal,
+				});
+			}
+		}
+		let temporal_links = links.len();
+		if let Some(episode) = self.episodes.last_mut() {
+			episode.links.extend(links);
+		}
+
+		// ANN index
+		let indexed = if let Some(vector) = vector {
+			let dimension = vector.len();
+			let index = self
+				.index
+				.get_or_insert_with(|| HnswIndex::new(dimension, self.config.hnsw.clone()));
+			index.insert(moment.id, &vector)?;
+			true
+		} else {
+			false
+		};
+
+		self.invalidate();
+		#[cfg(feature = "metrics")]
+		crate::metrics::record_ingested(&self.graph);
+		Ok(IngestReport {
+			node: moment.id,
+			episode: self.episodes.len() - 1,
+			new_episode: splits,
+			temporal_links,
+			indexed,
+		})
+	}
+
+	/// Ingest moments in order, stopping at the first rejected one.
+	///
+	/// # Errors
+	///
+	/// As [`ingest`](Self::ingest); moments before the rejected one stay
+	/// ingested.
+	pub fn ingest_all(&mut self, moments: &[Moment]) -> Result<Vec<IngestReport>, IngestError> {
+		moments.iter().map(|moment| self.ingest(moment)).collect()
+	}
+
+	/// Append the moment to the open episode and link it to the events
+	/// before it.
+	fn link_into_open_episode(&mut self, moment: &Moment) -> Vec<TemporalLink> {
+		let (Some(open), Some(episode)) = (self.open.as_mut(), self.episodes.last_mut()) else {
+			return Vec::new();
+		};
+		let position = episode.events.len();
+		let first = position.saturating_sub(self.config.temporal.max_temporal_distance);
+		let links = (first..position)
+			.filter(|&i| episode.events[i] != moment.id)
+			.map(|i| {
+				let elapsed = moment.start_ms - open.times[i];
+				TemporalLink {
+					source_position: i,
+					target_position: position,
+					source_memory: episode.events[i],
+					target_memory: moment.id,
+					forward_strength: compute_elapsed_link_strength(
+						self.config.temporal.forward_strength,
+						elapsed,
+						self.config.half_life_ms,
+					),
+					backward_strength: compute_elapsed_link_strength(
+						self.config.temporal.backward_strength,
+						elapsed,
+						self.config.half_life_ms,
+					),
+				}
+			})
+			.collect();
+
+		episode.events.push(moment.id);
+		open.times.push(moment.start_ms);
+		open.last_end_ms = open.last_end_ms.max(moment.end_ms.max(moment.start_ms));
+		links
+	}
+
+	/// Close the open episode, so the next moment starts a new one.
+	///
+	/// Returns `false` if no episode was open.
+	pub fn close_episode(&mut self) -> bool {
+		self.open.take().is_some()
+	}
+
+	/// Insert or replace an association, e.g. a learned semantic link.
+	///
+	/// Returns the replaced association, as
+	/// [`MemoryGraph::insert_association`].
+	pub fn add_association(&mut self, association: Association) -> Option<Association> {
+		self.invalidate();
+		self.graph.insert_association(association)
+	}
+
+	/// CSR snapshot of the current graph, rebuilt if the graph changed
+	/// since it was last read.
+	pub fn association_graph(&mut self) -> &AssociationGraph {
+		self.csr.get_or_insert_with(|| self.graph.index())
+	}
+
+	/// Global `PageRank` of the current graph, recomputed if the graph
+	/// changed since it was last read.
+	pub fn pagerank(&mut self) -> &PageRankResult {
+		let csr = self.csr.get_or_insert_with(|| self.graph.index());
+		self.pagerank.get_or_insert_with(|| {
+			compute_pagerank_with_config(
+				csr,
+				&[],
+				&self.config.pagerank,
+				&TraversalFilter::default(),
+			)
+		})
+	}
+
+	/// Give up the maintained structures.
+	#[must_use]
+	pub fn into_parts(self) -> (MemoryGraph, Option<HnswIndex>, Vec<Episode>) {
+		(self.graph, self.index, self.episodes)
+	}
+
+	fn invalidate(&mut self) {
+		self.csr = None;
+		self.pagerank = None;
+	}
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::create_episode_links_timed;
+
+	fn make_moment(id: usize, start_ms: f64, embedding: Option<Vec<f64>>) -> Moment {
+		let mut moment = Moment::new(id, Modality::Audio, start_ms, start_ms + 1_000.0);
+		if let Some(vector) = embedding {
+			moment.set_embedding(Modality::Text, vector);
+		}
+		moment
+	}
+
+	#[test]
+	fn test_ingest_matches_batch_build() {
+		let mut ingestor = Ingestor::new(IngestConfig::default());
+		let moments = vec![
+			make_moment(0, 0.0, Some(vec![1.0, 0.0])),
+			make_moment(1, 5_000.0, Some(vec![0.0, 1.0])),
+			make_moment(2, 20_000.0, None),
+			// More than `episode_gap_ms` after the previous moment ends
+			make_moment(3, 100_000.0, Some(vec![1.0, 1.0])),
+		];
+		let reports = ingestor.ingest_all(&moments);
+		assert!(reports.is_ok());
+		let Ok(reports) = reports else { return };
+		assert_eq!(
+			reports.iter().map(|r| r.episode).collect::<Vec<_>>(),
+			vec![0, 0, 0, 1]
+		);
+		assert!(reports[3].new_episode && !reports[2].indexed);
+
+		let config = ingestor.config().clone();
+		let episodes = ingestor.episodes();
+		assert_eq!(episodes.len(), 2);
+		assert_eq!(episodes[0].events, vec![0, 1, 2]);
+		let batch = create_episode_links_timed(
+			&[0, 1, 2],
+			&[0.0, 5_000.0, 20_000.0],
+			config.half_life_ms,
+			&config.temporal,
+		);
+		let key = |l: &TemporalLink| (l.source_position, l.target_position);
+		let mut incremental = episodes[0].links.clone();
+		incremental.sort_by_key(key);
+		assert_eq!(incremental.len(), batch.len());
+		for (a, b) in incremental.iter().zip(&batch) {
+			assert_eq!(key(a), key(b));
+			assert!((a.forward_strength - b.forward_strength).abs() < 1e-12);
+			assert!((a.backward_strength - b.backward_strength).abs() < 1e-12);
+		}
+
+		assert_eq!(ingestor.graph().num_nodes(), 4);
+		assert_eq!(ingestor.graph().num_edges(), 3);
+		assert!(ingestor
+			.graph()
+			.association(0, 2)
+			.is_some_and(|a| a.association_type == AssociationType::Temporal));
+		let index = ingestor.index();
+		assert!(index.is_some_and(|index| index.len() == 3));
+		let hits = index.map(|index| index.search(&[0.0, 1.0], 1));
+		assert_eq!(hits.and_then(|h| h.first().map(|&(id, _)| id)), Some(1));
+	}
+
+	#[test]
+	fn test_rejected_moment_changes_nothing() {
+		let mut ingestor = Ingestor::new(IngestConfig::default());
+		assert!(ingestor
+			.ingest(&make_moment(0, 0.0, Some(vec![1.0, 0.0])))
+			.is_ok());
+		assert!(ingestor
+			.ingest(&make_moment(1, 1_000.0, Some(vec![1.0, 0.0, 0.0])))
+			.is_err());
+		assert!(ingestor
+			.ingest(&make_moment(0, 2_000.0, Some(vec![0.0, 1.0])))
+			.is_err());
+		assert_eq!(ingestor.graph().num_nodes(), 1);
+		assert_eq!(ingestor.episodes()[0].events, vec![0]);
+	}
+
+	#[test]
+	fn test_caches_refresh_after_changes() {
+		let mut ingestor = Ingestor::new(IngestConfig {
+			temporal_associations: false,
+			..IngestConfig::default()
+		});
+		assert!(ingestor
+			.ingest_all(&[make_moment(0, 0.0, None), make_moment(1, 1_000.0, None)])
+			.is_ok());
+		assert_eq!(ingestor.association_graph().num_edges(), 0);
+		let before = ingestor.pagerank().ranks.clone();
+		assert!((before[0] - before[1]).abs() < 1e-12);
+
+		let _ = ingestor.add_association(Association {
+			source: 0,
+			target: 1,
+			forward_strength: 1.0,
+			backward_strength: 0.0,
+			association_type: AssociationType::Semantic,
+		});
+		assert_eq!(ingestor.association_graph().num_edges(), 1);
+		let after = &ingestor.pagerank().ranks;
+		assert!(after[1] > after[0]);
+
+		assert!(ingestor.close_episode());
+		assert!(!ingestor.close_episode());
+		assert!(ingestor
+			.ingest(&make_moment(2, 2_000.0, None))
+			.is_ok_and(|r| r.new_episode && r.temporal_links == 0));
+	}
+}
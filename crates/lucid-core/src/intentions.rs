@@ -0,0 +1,268 @@
+//! Prospective Memory
+//!
+//! Remembering to do something later: "remind me when I next open that
+//! project." An intention is a memory node paired with the conditions
+//! that should bring it back to mind.
+//!
+//! Cue-based intentions fire when activation on one of their cue nodes
+//! crosses a threshold, the way an encountered context spontaneously
+//! retrieves an associated plan. Time windows restrict when that may
+//! happen; an intention with no cues fires as soon as its window opens.
+//!
+//! Firing is edge-triggered. A repeating intention fires again only after
+//! its cues have dropped back below threshold, so a cue that stays active
+//! does not fire it on every check.
+
+use serde::{Deserialize, Serialize};
+
+/// A stored intention.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Intention {
+	/// Memory node holding the intention
+	pub node: usize,
+	/// Nodes whose activation triggers it; empty for purely time-based
+	/// intentions
+	pub cues: Vec<usize>,
+	/// Earliest time (ms) at which it may fire
+	pub not_before_ms: Option<f64>,
+	/// Latest time (ms) at which it may fire
+	pub not_after_ms: Option<f64>,
+	/// Keep the intention after it fires
+	pub repeating: bool,
+}
+
+impl Intention {
+	/// Whether `current_time_ms` falls inside the window.
+	#[must_use]
+	pub fn in_window(&self, current_time_ms: f64) -> bool {
+		self.not_before_ms.is_none_or(|t| current_time_ms >= t)
+			&& self.not_after_ms.is_none_or(|t| current_time_ms <= t)
+	}
+}
+
+/// Configuration for intention triggering.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IntentionConfig {
+	/// Cue activation at or above which an intention fires (default: 0.5)
+	pub cue_threshold: f64,
+}
+
+impl Default for IntentionConfig {
+	fn default() -> Self {
+		Self { cue_threshold: 0.5 }
+	}
+}
+
+/// An intention that fired.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TriggeredIntention {
+	/// Memory node holding the intention
+	pub node: usize,
+	/// Strongest cue at firing time (`None` for time-based intentions)
+	pub cue: Option<usize>,
+	/// Activation of that cue (0 for time-based intentions)
+	pub activation: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Pending {
+	intention: Intention,
+	armed: bool,
+}
+
+/// Pending intentions awaiting their triggers.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct IntentionStore {
+	config: IntentionConfig,
+	pending: Vec<Pending>,
+}
+
+impl IntentionStore {
+	/// Create an empty store.
+	#[must_use]
+	pub const fn new(config: IntentionConfig) -> Self {
+		Self {
+			config,
+			pending: Vec::new(),
+		}
+	}
+
+	/// Number of pending intentions.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.pending.len()
+	}
+
+	/// Whether no intentions are pending.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.pending.is_empty()
+	}
+
+	/// Pending intentions, in insertion order.
+	pub fn intentions(&self) -> impl Iterator<Item = &Intention> {
+		self.pending.iter().map(|p| &p.intention)
+	}
+
+	/// Add an intention. It is armed, so a cue already above threshold
+	/// fires it on the next check.
+	pub fn add(&mut self, intention: Intention) {
+		self.pending.push(Pending {
+			intention,
+			armed: true,
+		});
+	}
+
+	/// Drop every intention stored on `node`. Returns `false` if there
+	/// were none.
+	pub fn remove(&mut self, node: usize) -> bool {
+		let before = self.pending.len();
+		self.pending.retain(|p| p.intention.node != node);
+		self.pending.len() < before
+	}
+
+	/// Fire intentions whose cues crossed threshold.
+	///
+	/// An armed intention inside its window fires when its strongest cue
+	/// is at or above `cue_threshold`. One-shot intentions are then
+	/// removed; repeating ones re-arm once every cue is below threshold.
+	/// Cues outside `current_activations` count as inactive.
+	///
+	/// # Arguments
+	///
+	/// * `current_activations` - Activation per node, e.g. from spreading
+	/// * `current_time_ms` - Current time (in milliseconds)
+	///
+	/// # Returns
+	///
+	/// The intentions that fired, in insertion order.
+	pub fn check_triggers(
+		&mut self,
+		current_activations: &[f64],
+		current_time_ms: f64,
+	) -> Vec<TriggeredIntention> {
+		let threshold = self.config.cue_threshold;
+		let mut triggered = Vec::new();
+
+		self.pending.retain_mut(|pending| {
+			let intention = &pending.intention;
+			let strongest = intention
+				.cues
+				.iter()
+				.filter_map(|&cue| current_activations.get(cue).map(|&a| (cue, a)))
+				.max_by(|a, b| a.1.total_cmp(&b.1));
+			let cued = match strongest {
+				Some((_, activation)) => activation >= threshold,
+				None => intention.cues.is_empty(),
+			};
+
+			if !pending.armed || !cued || !intention.in_window(current_time_ms) {
+				// Time-based intentions never re-arm
+				if !intention.cues.is_empty() {
+					pending.armed = !cued;
+				}
+				return true;
+			}
+
+			triggered.push(TriggeredIntention {
+				node: intention.node,
+				cue: strongest.map(|(cue, _)| cue),
+				activation: strongest.map_or(0.0, |(_, a)| a),
+			});
+			pending.armed = false;
+			intention.repeating
+		});
+
+		triggered
+	}
+
+	/// Drop intentions whose window closed before `current_time_ms`.
+	///
+	/// # Returns
+	///
+	/// The expired intentions, in insertion order.
+	pub fn expire(&mut self, current_time_ms: f64) -> Vec<Intention> {
+		let mut expired = Vec::new();
+		let mut kept = Vec::with_capacity(self.pending.len());
+		for pending in std::mem::take(&mut self.pending) {
+			if pending
+				.intention
+				.not_after_ms
+				.is_some_and(|t| current_time_ms > t)
+			{
+				expired.push(pending.intention);
+			} else {
+				kept.push(pending);
+			}
+		}
+		self.pending = kept;
+		expired
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn cued(node: usize, cues: Vec<usize>, repeating: bool) -> Intention {
+		Intention {
+			node,
+			cues,
+			not_before_ms: None,
+			not_after_ms: None,
+			repeating,
+		}
+	}
+
+	#[test]
+	fn test_cue_crossing_fires_once() {
+		let mut store = IntentionStore::default();
+		store.add(cued(5, vec![1, 2], false));
+
+		assert!(store.check_triggers(&[0.0, 0.2, 0.1], 0.0).is_empty());
+		let fired = store.check_triggers(&[0.0, 0.2, 0.9], 10.0);
+		assert_eq!(fired.len(), 1);
+		assert_eq!(fired[0].node, 5);
+		assert_eq!(fired[0].cue, Some(2));
+		assert!(store.is_empty());
+	}
+
+	#[test]
+	fn test_repeating_intention_rearms_below_threshold() {
+		let mut store = IntentionStore::default();
+		store.add(cued(0, vec![1], true));
+
+		assert_eq!(store.check_triggers(&[0.0, 0.8], 0.0).len(), 1);
+		// Still active: no second firing
+		assert!(store.check_triggers(&[0.0, 0.8], 1.0).is_empty());
+		assert!(store.check_triggers(&[0.0, 0.1], 2.0).is_empty());
+		assert_eq!(store.check_triggers(&[0.0, 0.8], 3.0).len(), 1);
+		assert_eq!(store.len(), 1);
+	}
+
+	#[test]
+	fn test_time_windows() {
+		let mut store = IntentionStore::default();
+		store.add(Intention {
+			not_before_ms: Some(100.0),
+			..cued(3, vec![], false)
+		});
+		store.add(Intention {
+			not_after_ms: Some(50.0),
+			..cued(4, vec![1], false)
+		});
+
+		assert!(store.check_triggers(&[0.0, 0.0], 10.0).is_empty());
+		// Cue fires outside the window: ignored
+		assert!(store.check_triggers(&[0.0, 1.0], 60.0).is_empty());
+
+		let fired = store.check_triggers(&[0.0, 0.0], 100.0);
+		assert_eq!(fired.len(), 1);
+		assert_eq!(fired[0].cue, None);
+
+		let expired = store.expire(100.0);
+		assert_eq!(expired.len(), 1);
+		assert_eq!(expired[0].node, 4);
+		assert!(store.is_empty());
+	}
+}
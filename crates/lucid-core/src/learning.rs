@@ -0,0 +1,305 @@
+//! Association Learning
+//!
+//! The association graph should reflect how memories are actually used.
+//! Memories that are retrieved together become more strongly linked
+//! (Hebb, 1949: "cells that fire together wire together").
+//!
+//! `S' = S + η × (1 - S)`
+//!
+//! Where:
+//! - `S` = current association strength (0-1)
+//! - `η` = learning rate (0-1)
+//!
+//! The update is saturating: strong links gain less than weak ones and
+//! never exceed 1.0.
+//!
+//! Unused links fade. Strengths decay exponentially with the time since
+//! they were last reinforced, and links that fall below a floor are
+//! pruned so the graph does not grow without bound.
+
+use crate::activation::{
+	compute_association_decay, should_prune_association, AssociationDecayConfig, AssociationState,
+};
+use crate::graph::{EdgeMetadata, MemoryGraph};
+use crate::spreading::{Association, AssociationType};
+
+// ============================================================================
+// Hebbian Strengthening
+// ============================================================================
+
+/// Saturating Hebbian update for a single strength.
+///
+/// `S' = S + η × (1 - S)`
+#[inline]
+#[must_use]
+pub fn hebbian_update(strength: f64, learning_rate: f64) -> f64 {
+	let rate = learning_rate.clamp(0.0, 1.0);
+	rate.mul_add(1.0 - strength, strength).clamp(0.0, 1.0)
+}
+
+/// Strengthen associations between every pair of co-activated memories.
+///
+/// For each unordered pair `(a, b)` in `activated_nodes`, the existing
+/// edge between them (in either direction) has both its forward and
+/// backward strength updated with [`hebbian_update`]. Pairs with no edge
+/// get a new association (default type) starting at `learning_rate` in
+/// both directions.
+///
+/// Duplicate and out-of-range node indices are ignored. New edges start
+/// in [`AssociationState::Fresh`].
+///
+/// # Arguments
+///
+/// * `graph` - Graph to update
+/// * `activated_nodes` - Memories retrieved together
+/// * `learning_rate` - Hebbian rate η (0-1)
+pub fn strengthen_on_coactivation(
+	graph: &mut MemoryGraph,
+	activated_nodes: &[usize],
+	learning_rate: f64,
+) {
+	for (a, b) in coactivated_pairs(graph, activated_nodes) {
+		strengthen_pair(graph, a, b, learning_rate);
+	}
+}
+
+/// [`strengthen_on_coactivation`] with reinforcement timestamps.
+///
+/// Each existing edge is first decayed up to `current_time_ms`, then
+/// strengthened and stamped, so a later [`decay_and_prune`] measures
+/// elapsed time from this reinforcement.
+pub fn strengthen_on_coactivation_at(
+	graph: &mut MemoryGraph,
+	activated_nodes: &[usize],
+	learning_rate: f64,
+	current_time_ms: f64,
+	config: &AssociationDecayConfig,
+) {
+	for (a, b) in coactivated_pairs(graph, activated_nodes) {
+		let (source, target) = edge_key(graph, a, b);
+		if let Some((association, meta)) = graph.edge_mut(source, target) {
+			decay_edge(association, meta, current_time_ms, config);
+		}
+		strengthen_pair(graph, source, target, learning_rate);
+		let _ = graph.mark_reinforced(source, target, current_time_ms);
+	}
+}
+
+/// Unordered pairs of distinct, in-range activated nodes.
+fn coactivated_pairs(graph: &MemoryGraph, activated_nodes: &[usize]) -> Vec<(usize, usize)> {
+	let num_nodes = graph.num_nodes();
+	let mut nodes: Vec<usize> = Vec::with_capacity(activated_nodes.len());
+	for &node in activated_nodes {
+		if node < num_nodes && !nodes.contains(&node) {
+			nodes.push(node);
+		}
+	}
+
+	nodes
+		.iter()
+		.enumerate()
+		.flat_map(|(i, &a)| nodes[i + 1..].iter().map(move |&b| (a, b)))
+		.collect()
+}
+
+/// Key of the edge between `a` and `b`: the existing edge in either
+/// direction, or `(a, b)` if there is none.
+fn edge_key(graph: &MemoryGraph, a: usize, b: usize) -> (usize, usize) {
+	if graph.association(a, b).is_none() && graph.association(b, a).is_some() {
+		(b, a)
+	} else {
+		(a, b)
+	}
+}
+
+/// Strengthen the edge between `a` and `b`, creating it if needed.
+fn strengthen_pair(graph: &mut MemoryGraph, a: usize, b: usize, learning_rate: f64) {
+	let (source, target) = edge_key(graph, a, b);
+
+	if let Some(association) = graph.association_mut(source, target) {
+		association.forward_strength = hebbian_update(association.forward_strength, learning_rate);
+		association.backward_strength =
+			hebbian_update(association.backward_strength, learning_rate);
+	} else {
+		let strength = hebbian_update(0.0, learning_rate);
+		let _ = graph.insert_association(Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::default(),
+		});
+		if let Some(meta) = graph.metadata_mut(source, target) {
+			meta.state = AssociationState::Fresh;
+		}
+	}
+}
+
+// ============================================================================
+// Decay and Pruning
+// ============================================================================
+
+/// Bring an edge's strengths current and stamp it at `current_time_ms`.
+///
+/// Unstamped edges are stamped without decaying.
+fn decay_edge(
+	association: &mut Association,
+	meta: &mut EdgeMetadata,
+	current_time_ms: f64,
+	config: &AssociationDecayConfig,
+) {
+	if let Some(last_ms) = meta.last_reinforced_ms {
+		let ms_per_day = 24.0 * 60.0 * 60.0 * 1000.0;
+		let days = ((current_time_ms - last_ms) / ms_per_day).max(0.0);
+		association.forward_strength =
+			compute_association_decay(association.forward_strength, days, meta.state, config);
+		association.backward_strength =
+			compute_association_decay(association.backward_strength, days, meta.state, config);
+	}
+	meta.last_reinforced_ms = Some(current_time_ms);
+}
+
+/// Decay every association and prune those that fall below the floor.
+///
+/// `strength(t) = strength_0 × e^(-t/τ)`
+///
+/// Where `t` is the time since the edge was last reinforced (or last
+/// decayed) and τ depends on the edge's consolidation state. Because
+/// exponential decay composes, running maintenance more or less often
+/// yields the same strengths. Edges never stamped are stamped at
+/// `current_time_ms` and start decaying from there.
+///
+/// An edge is pruned when both its forward and backward strengths fall
+/// below `config.prune_threshold`.
+///
+/// # Returns
+///
+/// The removed associations, in insertion order.
+pub fn decay_and_prune(
+	graph: &mut MemoryGraph,
+	current_time_ms: f64,
+	config: &AssociationDecayConfig,
+) -> Vec<Association> {
+	for (association, meta) in graph.edges_mut() {
+		decay_edge(association, meta, current_time_ms, config);
+	}
+
+	graph.remove_associations_where(|association, _| {
+		should_prune_association(
+			association
+				.forward_strength
+				.max(association.backward_strength),
+			config,
+		)
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn make_assoc(source: usize, target: usize, strength: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	#[test]
+	fn test_hebbian_update_saturates() {
+		let mut strength = 0.0;
+		for _ in 0..100 {
+			strength = hebbian_update(strength, 0.2);
+		}
+		assert!(strength <= 1.0);
+		assert!(strength > 0.99);
+
+		assert!((hebbian_update(0.5, 0.2) - 0.6).abs() < 1e-10);
+	}
+
+	#[test]
+	fn test_strengthen_on_coactivation() {
+		let mut graph = MemoryGraph::from_associations(
+			4,
+			vec![Association {
+				backward_strength: 0.2,
+				..make_assoc(1, 0, 0.5)
+			}],
+		);
+
+		strengthen_on_coactivation(&mut graph, &[0, 1, 2, 1, 9], 0.5);
+
+		// Existing 1 → 0 edge strengthened in both directions, not duplicated
+		let existing = graph.association(1, 0);
+		assert!(
+			existing.is_some_and(|a| (a.forward_strength - 0.75).abs() < 1e-10
+				&& (a.backward_strength - 0.6).abs() < 1e-10)
+		);
+		assert!(graph.association(0, 1).is_none());
+
+		// New links for pairs that were never associated
+		let created = graph.association(0, 2);
+		assert!(created.is_some_and(|a| (a.forward_strength - 0.5).abs() < 1e-10));
+		assert!(graph.association(1, 2).is_some());
+		assert_eq!(graph.num_edges(), 3);
+	}
+
+	#[test]
+	fn test_decay_and_prune() {
+		let config = AssociationDecayConfig::default();
+		let day_ms = 24.0 * 60.0 * 60.0 * 1000.0;
+		let mut graph =
+			MemoryGraph::from_associations(3, vec![make_assoc(0, 1, 0.9), make_assoc(1, 2, 0.15)]);
+
+		// First pass only stamps
+		assert!(decay_and_prune(&mut graph, 0.0, &config).is_empty());
+
+		// 30 days at τ = 30 (consolidated): 0.9 → 0.33, 0.15 → 0.055 (pruned)
+		let removed = decay_and_prune(&mut graph, 30.0 * day_ms, &config);
+		assert_eq!(removed.len(), 1);
+		assert_eq!((removed[0].source, removed[0].target), (1, 2));
+
+		let survivor = graph.association(0, 1).map(|a| a.forward_strength);
+		let expected = 0.9 * (-1.0_f64).exp();
+		assert!(survivor.is_some_and(|s| (s - expected).abs() < 1e-10));
+		assert!(graph.association(1, 2).is_none());
+	}
+
+	#[test]
+	fn test_decay_composes_across_passes() {
+		let config = AssociationDecayConfig::default();
+		let day_ms = 24.0 * 60.0 * 60.0 * 1000.0;
+		let mut once = MemoryGraph::from_associations(2, vec![make_assoc(0, 1, 0.9)]);
+		let mut twice = once.clone();
+
+		let _ = decay_and_prune(&mut once, 0.0, &config);
+		let _ = decay_and_prune(&mut once, 10.0 * day_ms, &config);
+		let _ = decay_and_prune(&mut twice, 0.0, &config);
+		let _ = decay_and_prune(&mut twice, 4.0 * day_ms, &config);
+		let _ = decay_and_prune(&mut twice, 10.0 * day_ms, &config);
+
+		let a = once.association(0, 1).map_or(0.0, |a| a.forward_strength);
+		let b = twice.association(0, 1).map_or(0.0, |a| a.forward_strength);
+		assert!((a - b).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_reinforcement_resets_decay_clock() {
+		let config = AssociationDecayConfig::default();
+		let day_ms = 24.0 * 60.0 * 60.0 * 1000.0;
+		let mut graph = MemoryGraph::from_associations(2, vec![make_assoc(0, 1, 0.5)]);
+		let _ = decay_and_prune(&mut graph, 0.0, &config);
+
+		strengthen_on_coactivation_at(&mut graph, &[1, 0], 0.2, 30.0 * day_ms, &config);
+
+		let meta = graph.metadata(0, 1).copied();
+		assert!(meta.is_some_and(|m| m.last_reinforced_ms == Some(30.0 * day_ms)));
+		// Decayed to 0.5/e first, then strengthened
+		let expected = hebbian_update(0.5 * (-1.0_f64).exp(), 0.2);
+		let strength = graph.association(0, 1).map_or(0.0, |a| a.forward_strength);
+		assert!((strength - expected).abs() < 1e-10);
+	}
+}
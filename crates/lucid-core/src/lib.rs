@@ -47,6 +47,9 @@
 //! 4. Spread activation through the association graph
 //! 5. Combine, rank, and filter by retrieval probability
 //!
+//! [`RecallEngine`] runs the same stages end to end against an HNSW index,
+//! adding working memory and episodic temporal spreading.
+//!
 //! ## Example
 //!
 //! ```rust
@@ -72,6 +75,7 @@
 //!     emotional_weights: &[0.5, 0.5, 0.5],
 //!     decay_rates: &[0.5, 0.5, 0.5],
 //!     working_memory_boosts: &[1.0, 1.0, 1.0],  // 1.0 = no boost, up to 2.0
+//!     retrieval_thresholds: &[],  // Optional: per-memory τ
 //!     associations: &[],  // Optional: links between memories
 //!     current_time_ms: 2000.0,
 //! };
@@ -112,12 +116,52 @@
 #![allow(clippy::needless_return)]
 
 pub mod activation;
+pub mod ann;
+pub mod attributes;
+pub mod builder;
+pub mod centrality;
+pub mod community;
+pub mod components;
+pub mod consolidate;
+pub mod context;
+pub mod diff;
 #[cfg(feature = "embedding")]
 pub mod embedding;
+pub mod embeddings;
+pub mod engine;
+pub mod entities;
+pub mod episodes;
+pub mod error;
+pub mod export;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+pub mod graph;
+pub mod history;
+pub mod import;
+pub mod ingest;
+pub mod intentions;
+pub mod learning;
 pub mod location;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod moment;
+pub mod namespace;
+pub mod normalize;
+pub mod novelty;
+pub mod paths;
+pub mod recall;
 pub mod retrieval;
+pub mod review;
+mod rng;
+pub mod salience;
+pub mod shared;
+pub mod snapshot;
 pub mod spreading;
+#[cfg(feature = "store")]
+pub mod store;
+pub mod synthetic;
 pub mod visual;
+pub mod working_memory;
 
 pub use activation::{
 	combine_activations,
@@ -141,6 +185,8 @@ pub use activation::{
 	pe_zone,
 	reconsolidation_probability,
 	reinforce_association,
+	retrieval_latency,
+	retrieval_latency_batch,
 	retrieval_probability,
 	should_prune_association,
 	ActivationBreakdown,
@@ -154,21 +200,205 @@ pub use activation::{
 	THETA_HIGH,
 	THETA_LOW,
 };
-pub use retrieval::{retrieve, RetrievalCandidate, RetrievalConfig, RetrievalInput};
+pub use retrieval::{
+	exact_similarity, partial_match, retrieve, score, PartialMatch, PartialMatchConfig,
+	RetrievalCandidate, RetrievalConfig, RetrievalInput, ScoreComponents, ScoreConfig, ScoreInput,
+	ScoreNormalization, ScoreWeights, ScoredMemory,
+};
+#[allow(deprecated)] // find_temporal_neighbors stays exported for compatibility
 pub use spreading::{
+	apply_competition,
 	// Temporal Spreading (Episodic Memory)
+	compute_elapsed_link_strength,
+	compute_pagerank_filtered,
+	compute_pagerank_with_config,
 	compute_temporal_link_strength,
+	compute_weighted_pagerank,
+	compute_weighted_pagerank_filtered,
+	compute_weighted_pagerank_graph,
 	create_episode_links,
+	create_episode_links_from_timestamps,
+	create_episode_links_timed,
+	find_activation_path_filtered,
 	find_temporal_neighbors,
+	get_top_activated_with_scores,
+	get_top_retrievable,
 	spread_activation,
+	spread_activation_batch,
+	spread_activation_batch_graph,
+	spread_activation_filtered,
+	spread_activation_graph,
+	spread_activation_sampled,
+	spread_joint_cues,
 	spread_temporal_activation,
 	spread_temporal_activation_multi,
+	temporal_neighbors,
+	ActivationContribution,
 	Association,
+	AssociationType,
+	BelowThreshold,
+	Competition,
+	CueCombination,
+	DanglingPolicy,
+	FanNormalization,
+	JointCueConfig,
+	JointCueResult,
+	MonteCarloConfig,
+	MonteCarloResult,
+	PageRankConfig,
+	PageRankResult,
 	SpreadingConfig,
+	SpreadingQuery,
 	SpreadingResult,
+	TagFilter,
+	TemporalDirection,
 	TemporalLink,
+	TemporalNeighbor,
 	TemporalSpreadingConfig,
 	TemporalSpreadingResult,
+	TypeSpreadingConfig,
+	TypeSpreadingParams,
+	UnknownAssociationType,
+};
+
+// Temporal Context Model
+pub use context::{drift_context, TemporalContext, TemporalContextConfig};
+
+// Consolidation
+pub use consolidate::{
+	extract_schemas, merge_duplicates, replay_consolidation, DuplicateConfig, DuplicateMerge,
+	ReplayConfig, ReplayReport, Schema, SchemaConfig, SchemaExtraction,
+};
+
+// Episodes
+pub use episodes::{
+	link_episodes_by_entities, segment_by_change, segment_events, EntityLinkConfig, Episode,
+	Segmentation, SegmentationConfig,
+};
+
+// Entity Resolution
+pub use entities::{
+	normalize_mention, string_similarity, Entity, EntityKind, EntityRegistry, EntityRegistryConfig,
+	Resolution, ResolvedEntity,
+};
+
+// Free Recall Simulation
+pub use recall::{
+	lag_crp, serial_position_curve, serial_position_gradient, serial_position_priors,
+	simulate_free_recall, FreeRecallConfig, FreeRecallResult, SerialPositionConfig,
+};
+
+// Novelty and Surprise
+pub use novelty::{novelty_encoding_strength, score_novelty, NoveltyConfig, NoveltyScore};
+
+// Prospective Memory
+pub use intentions::{Intention, IntentionConfig, IntentionStore, TriggeredIntention};
+
+// Emotional Salience
+pub use salience::{
+	apply_salience, salience_gains, salience_intensity, salient_decay_rate, salient_decay_rates,
+	Salience, SalienceConfig, SalienceCurve,
+};
+
+// Spaced Repetition
+pub use review::{
+	next_review_time, record_retrieval, schedule_reviews, RetrievalOutcome,
+	RetrievalPracticeConfig, ReviewConfig,
+};
+
+// Activation History
+pub use history::{ActivationEvent, ActivationHistory, ActivationHistoryConfig, ActivationPoint};
+
+// Error Classification
+pub use error::{Classify, ConfigIssue, ErrorKind};
+
+// Recall Engine
+pub use engine::{RecallConfig, RecallContext, RecallEngine, RecallExplanation, RecalledMemory};
+
+// Working Memory Buffer
+pub use working_memory::{FocusOutcome, WorkingMemory, DEFAULT_WORKING_MEMORY_CAPACITY};
+
+// Embedding Similarity (f32, SIMD-friendly kernels)
+pub use embeddings::{
+	cosine_int8, dequantize_int8, dot_int8, quantize_int8, similarity_batch, top_k_similar,
+	Int8Vector, Metric, PqDistanceTable, ProductQuantizer, ProductQuantizerConfig,
+	QuantizationError,
+};
+
+// Approximate Nearest Neighbors (HNSW)
+pub use ann::{AnnError, HnswConfig, HnswIndex};
+
+// Graph Centrality
+pub use centrality::{
+	betweenness_centrality, closeness_centrality, edge_cost, eigenvector_centrality, hits,
+	personalized_pagerank, random_walk_with_restart, CentralityConfig, EigenvectorConfig,
+	EigenvectorScores, HitsConfig, HitsScores, PersonalizedPageRank, PersonalizedPageRankConfig,
+	RandomWalkConfig, RandomWalkSample,
+};
+
+// Community Detection
+pub use community::{
+	detect_communities, modularity, Communities, CommunityAlgorithm, CommunityConfig,
+};
+
+// Connectivity
+pub use components::{connected_components, find_orphans, Components};
+
+// Property Graph Attributes
+pub use attributes::{AttributePredicate, AttributeValue, Attributes, TraversalFilter};
+
+// Graph Snapshots
+pub use snapshot::{GraphSnapshot, SnapshotError, SNAPSHOT_VERSION};
+
+// Graph Diffing
+pub use diff::{apply_diff, diff_graphs, DiffError, EdgeChange, EdgeState, GraphDiff};
+
+// Graph Export
+pub use export::{export_dot, export_gexf, export_graphml};
+
+// Graph Builder
+pub use builder::{GraphBuildError, GraphBuilder};
+
+// Graph Import
+pub use import::{
+	import_csv, import_json, DuplicatePolicy, ImportConfig, ImportError, ImportReport, RowError,
+	RowKind,
+};
+
+// Streaming Ingestion
+pub use ingest::{IngestConfig, IngestError, IngestReport, Ingestor};
+
+// Synthetic Data
+pub use synthetic::{
+	generate_embeddings, generate_episodes, generate_graph, DegreeDistribution,
+	SyntheticEpisodeConfig, SyntheticEpisodes, SyntheticGraph, SyntheticGraphConfig,
+};
+
+// Concurrent Access
+pub use shared::{PublishedGraph, SharedMemoryGraph};
+
+// Multi-Tenant Namespaces
+pub use namespace::{NamespaceError, NamespaceGraph, NamespacedGraphs};
+
+// Strength Normalization
+pub use normalize::{normalize_strengths, StrengthNormalization};
+
+// Moments
+pub use moment::{moment_embeddings, moment_events, MediaRef, ModalEmbedding, Modality, Moment};
+
+// Association Graph Index
+pub use graph::{AssociationGraph, EdgeMetadata, MemoryGraph};
+
+// Association Learning
+pub use learning::{
+	decay_and_prune, hebbian_update, strengthen_on_coactivation, strengthen_on_coactivation_at,
+};
+
+// Association Paths
+pub use paths::{
+	find_k_strongest_paths, find_k_strongest_paths_filtered, find_k_strongest_paths_graph,
+	find_strongest_path, find_strongest_path_filtered, find_strongest_path_graph, ActivationPath,
+	PathEdge,
 };
 
 // Location Intuitions (spatial memory)
@@ -219,6 +449,7 @@ mod tests {
 			emotional_weights: &[0.5, 0.5, 0.5],
 			decay_rates: &[0.5, 0.5, 0.5],
 			working_memory_boosts: &[1.0, 1.0, 1.0],
+			retrieval_thresholds: &[],
 			associations: &[],
 			current_time_ms: now,
 		};
@@ -466,7 +466,7 @@ pub fn association_multiplier(
 // Location Spreading Activation
 // ============================================================================
 
-use crate::spreading::{spread_activation, Association, SpreadingConfig};
+use crate::spreading::{spread_activation, Association, AssociationType, SpreadingConfig};
 
 /// Spread activation through location association network.
 ///
@@ -493,6 +493,7 @@ pub fn spread_location_activation(
 			target: la.target as usize,
 			forward_strength: la.strength,
 			backward_strength: la.strength * location_config.backward_strength_factor,
+			association_type: AssociationType::default(),
 		})
 		.collect();
 
@@ -0,0 +1,84 @@
+//! Metrics
+//!
+//! Counters, gauges and histograms for self-hosted deployments, emitted
+//! through the [`metrics`] facade. Nothing is recorded until the
+//! application installs a recorder, e.g. `metrics-exporter-prometheus`;
+//! names follow Prometheus conventions.
+//!
+//! | Name | Kind | Recorded by |
+//! |------|------|-------------|
+//! | `lucid_spreading_query_seconds` | histogram | every graph spreading query |
+//! | `lucid_recall_query_seconds` | histogram | [`RecallEngine`] |
+//! | `lucid_graph_nodes` | gauge | [`Ingestor`], [`record_graph_size`] |
+//! | `lucid_graph_edges` | gauge | [`Ingestor`], [`record_graph_size`] |
+//! | `lucid_moments_ingested_total` | counter | [`Ingestor`] |
+//!
+//! [`RecallEngine`]: crate::engine::RecallEngine
+//! [`Ingestor`]: crate::ingest::Ingestor
+
+use std::time::Duration;
+
+use ::metrics::{
+	counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram, Unit,
+};
+
+use crate::graph::MemoryGraph;
+
+/// Latency of one spreading activation query.
+pub const SPREADING_QUERY_SECONDS: &str = "lucid_spreading_query_seconds";
+/// Latency of one end-to-end recall.
+pub const RECALL_QUERY_SECONDS: &str = "lucid_recall_query_seconds";
+/// Nodes in the most recently recorded graph.
+pub const GRAPH_NODES: &str = "lucid_graph_nodes";
+/// Associations in the most recently recorded graph.
+pub const GRAPH_EDGES: &str = "lucid_graph_edges";
+/// Moments accepted by streaming ingestion.
+pub const MOMENTS_INGESTED_TOTAL: &str = "lucid_moments_ingested_total";
+
+/// Register units and help text for every metric above.
+///
+/// Call once after installing a recorder; exporters that show help text
+/// fall back to the bare name otherwise.
+pub fn describe_metrics() {
+	describe_histogram!(
+		SPREADING_QUERY_SECONDS,
+		Unit::Seconds,
+		"Latency of one spreading activation query"
+	);
+	describe_histogram!(
+		RECALL_QUERY_SECONDS,
+		Unit::Seconds,
+		"Latency of one end-to-end recall"
+	);
+	describe_gauge!(GRAPH_NODES, Unit::Count, "Nodes in the association graph");
+	describe_gauge!(
+		GRAPH_EDGES,
+		Unit::Count,
+		"Associations in the association graph"
+	);
+	describe_counter!(
+		MOMENTS_INGESTED_TOTAL,
+		Unit::Count,
+		"Moments accepted by streaming ingestion"
+	);
+}
+
+/// Set the graph size gauges from `graph`.
+#[allow(clippy::cast_precision_loss)]
+pub fn record_graph_size(graph: &MemoryGraph) {
+	gauge!(GRAPH_NODES).set(graph.num_nodes() as f64);
+	gauge!(GRAPH_EDGES).set(graph.num_edges() as f64);
+}
+
+pub(crate) fn record_spreading_query(elapsed: Duration) {
+	histogram!(SPREADING_QUERY_SECONDS).record(elapsed.as_secs_f64());
+}
+
+pub(crate) fn record_recall_query(elapsed: Duration) {
+	histogram!(RECALL_QUERY_SECONDS).record(elapsed.as_secs_f64());
+}
+
+pub(crate) fn record_ingested(graph: &MemoryGraph) {
+	counter!(MOMENTS_INGESTED_TOTAL).increment(1);
+	record_graph_size(graph);
+}
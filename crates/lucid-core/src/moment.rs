@@ -0,0 +1,202 @@
+//! Moments
+//!
+//! The record shared by everything that produces or stores memories: a
+//! span of time with the graph node it lives on, what was said or written
+//! during it, references to the media it came from, embeddings per
+//! modality, and tags.
+//!
+//! Producers (lucid-perception's video pipeline, a chat log importer)
+//! emit moments; the graph and retrieval code consume them through
+//! [`moment_events`] and [`moment_embeddings`], so no caller has to map
+//! one crate's output fields onto another's inputs by hand.
+//!
+//! Times are milliseconds, like every other timestamp in this crate.
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A channel a memory was perceived through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Modality {
+	/// Written or transcribed text
+	#[default]
+	Text,
+	/// Images and video frames
+	Visual,
+	/// Speech and sound
+	Audio,
+}
+
+/// A pointer to the media a moment was derived from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaRef {
+	/// What kind of media it is
+	pub modality: Modality,
+	/// Where it lives: a path or URL
+	pub uri: String,
+	/// Offset into the media (ms), for clips of longer recordings
+	pub offset_ms: Option<f64>,
+	/// Position among the producer's outputs, e.g. a frame index
+	pub index: Option<usize>,
+}
+
+/// An embedding of one modality of a moment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModalEmbedding {
+	/// Which modality was embedded
+	pub modality: Modality,
+	/// The embedding vector
+	pub vector: Vec<f64>,
+}
+
+/// A memory anchored in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Moment {
+	/// Graph node holding the moment
+	pub id: usize,
+	/// The modality the moment was primarily perceived through
+	pub modality: Modality,
+	/// Start time (ms)
+	pub start_ms: f64,
+	/// End time (ms)
+	pub end_ms: f64,
+	/// Text said or written during the moment (may be empty)
+	pub text: String,
+	/// Media the moment was derived from
+	pub media: Vec<MediaRef>,
+	/// Embeddings, at most one per modality
+	pub embeddings: Vec<ModalEmbedding>,
+	/// Free-form labels
+	pub tags: Vec<String>,
+}
+
+impl Moment {
+	/// Create a moment with no text, media, embeddings, or tags.
+	#[must_use]
+	pub const fn new(id: usize, modality: Modality, start_ms: f64, end_ms: f64) -> Self {
+		Self {
+			id,
+			modality,
+			start_ms,
+			end_ms,
+			text: String::new(),
+			media: Vec::new(),
+			embeddings: Vec::new(),
+			tags: Vec::new(),
+		}
+	}
+
+	/// Length of the span (ms), never negative.
+	#[must_use]
+	pub fn duration_ms(&self) -> f64 {
+		(self.end_ms - self.start_ms).max(0.0)
+	}
+
+	/// Whether the two spans share any time.
+	#[must_use]
+	pub fn overlaps(&self, other: &Self) -> bool {
+		self.start_ms < other.end_ms && other.start_ms < self.end_ms
+	}
+
+	/// The embedding of `modality`, if there is one.
+	#[must_use]
+	pub fn embedding(&self, modality: Modality) -> Option<&[f64]> {
+		self.embeddings
+			.iter()
+			.find(|e| e.modality == modality)
+			.map(|e| e.vector.as_slice())
+	}
+
+	/// Set the embedding of `modality`, replacing any previous one.
+	pub fn set_embedding(&mut self, modality: Modality, vector: Vec<f64>) {
+		if let Some(existing) = self.embeddings.iter_mut().find(|e| e.modality == modality) {
+			existing.vector = vector;
+		} else {
+			self.embeddings.push(ModalEmbedding { modality, vector });
+		}
+	}
+
+	/// The first media reference of `modality`, if there is one.
+	#[must_use]
+	pub fn media(&self, modality: Modality) -> Option<&MediaRef> {
+		self.media.iter().find(|m| m.modality == modality)
+	}
+
+	/// Whether the moment carries `tag`.
+	#[must_use]
+	pub fn has_tag(&self, tag: &str) -> bool {
+		self.tags.iter().any(|t| t == tag)
+	}
+}
+
+// ============================================================================
+// Graph Inputs
+// ============================================================================
+
+/// `(id, start_ms)` per moment, for
+/// [`create_episode_links_from_timestamps`](crate::create_episode_links_from_timestamps).
+#[must_use]
+pub fn moment_events(moments: &[Moment]) -> Vec<(usize, f64)> {
+	moments.iter().map(|m| (m.id, m.start_ms)).collect()
+}
+
+/// One embedding of `modality` per moment, for the retrieval pipeline's
+/// `memory_embeddings`.
+///
+/// Moments without one get an empty vector, which is similar to nothing.
+#[must_use]
+pub fn moment_embeddings(moments: &[Moment], modality: Modality) -> Vec<Vec<f64>> {
+	moments
+		.iter()
+		.map(|m| m.embedding(modality).map_or_else(Vec::new, <[f64]>::to_vec))
+		.collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::activation::cosine_similarity_batch;
+
+	#[test]
+	fn test_moment_accessors_and_graph_inputs() {
+		let mut first = Moment::new(3, Modality::Visual, 0.0, 5_000.0);
+		first.set_embedding(Modality::Visual, vec![1.0, 0.0]);
+		first.set_embedding(Modality::Visual, vec![0.0, 1.0]);
+		first.media.push(MediaRef {
+			modality: Modality::Visual,
+			uri: "frame_0.jpg".to_string(),
+			offset_ms: Some(0.0),
+			index: Some(0),
+		});
+		first.tags.push("scene".to_string());
+		let second = Moment::new(4, Modality::Audio, 5_000.0, 9_000.0);
+
+		assert_eq!(first.embeddings.len(), 1);
+		assert_eq!(
+			first.embedding(Modality::Visual),
+			Some([0.0, 1.0].as_slice())
+		);
+		assert_eq!(first.embedding(Modality::Text), None);
+		assert_eq!(first.media(Modality::Visual).and_then(|m| m.index), Some(0));
+		assert!(first.has_tag("scene"));
+		assert!((second.duration_ms() - 4_000.0).abs() < 1e-12);
+		assert!(Moment::new(5, Modality::Text, 2.0, 1.0).duration_ms().abs() < 1e-12);
+		assert!(!first.overlaps(&second));
+		assert!(first.overlaps(&Moment::new(6, Modality::Text, 4_999.0, 6_000.0)));
+
+		let moments = vec![first, second];
+		assert_eq!(moment_events(&moments), vec![(3, 0.0), (4, 5_000.0)]);
+		let embeddings = moment_embeddings(&moments, Modality::Visual);
+		let similarities = cosine_similarity_batch(&[0.0, 1.0], &embeddings);
+		assert_eq!(similarities.len(), 2);
+		assert!((similarities[0] - 1.0).abs() < 1e-12);
+		assert!(similarities[1].abs() < 1e-12);
+	}
+}
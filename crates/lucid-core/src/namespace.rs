@@ -0,0 +1,392 @@
+//! Namespaced Graphs
+//!
+//! One server process often holds memory for several users or profiles.
+//! Putting them all in one [`MemoryGraph`] would let activation spread
+//! from one user's memories into another's; [`NamespacedGraphs`] instead
+//! gives each namespace its own graph with its own node index space, so
+//! node 0 in `"alice"` and node 0 in `"bob"` are unrelated and spreading
+//! can never cross between them.
+//!
+//! Entities can be resolved per namespace (the default) or through one
+//! registry shared by every namespace
+//! ([`with_shared_entities`](NamespacedGraphs::with_shared_entities)).
+//! A shared registry gives every namespace the same canonical spelling and
+//! aliases for "Jason Docton", while each namespace still holds the entity
+//! on a node of its own, created the first time it is mentioned there.
+
+use std::collections::HashMap;
+
+use crate::entities::{EntityKind, EntityRegistry, EntityRegistryConfig, ResolvedEntity};
+use crate::error::{Classify, ErrorKind};
+use crate::graph::{AssociationGraph, MemoryGraph};
+use crate::spreading::{spread_activation_graph, Association, SpreadingConfig, SpreadingResult};
+
+/// Error type for namespace operations.
+#[derive(Debug, thiserror::Error)]
+pub enum NamespaceError {
+	/// No namespace has this name.
+	#[error("Unknown namespace: {0}")]
+	UnknownNamespace(String),
+}
+
+impl Classify for NamespaceError {
+	fn kind(&self) -> ErrorKind {
+		ErrorKind::InvalidInput
+	}
+}
+
+// ============================================================================
+// Namespace
+// ============================================================================
+
+/// One namespace's graph and entities.
+#[derive(Debug, Clone, Default)]
+pub struct NamespaceGraph {
+	graph: MemoryGraph,
+	/// Entities resolved in this namespace when the registry is not shared
+	entities: EntityRegistry,
+	/// Shared entity id → node in this namespace
+	shared_nodes: HashMap<usize, usize>,
+	/// CSR snapshot of `graph`, rebuilt when read after a change
+	index: Option<AssociationGraph>,
+}
+
+impl NamespaceGraph {
+	/// Create an empty namespace.
+	#[must_use]
+	pub fn new(entity_config: EntityRegistryConfig) -> Self {
+		Self {
+			graph: MemoryGraph::default(),
+			entities: EntityRegistry::new(entity_config),
+			shared_nodes: HashMap::new(),
+			index: None,
+		}
+	}
+
+	/// The namespace's graph.
+	#[must_use]
+	pub const fn graph(&self) -> &MemoryGraph {
+		&self.graph
+	}
+
+	/// Mutable access to the graph; the query index is rebuilt on next use.
+	pub fn graph_mut(&mut self) -> &mut MemoryGraph {
+		self.index = None;
+		&mut self.graph
+	}
+
+	/// Add a node and return its index in this namespace.
+	pub fn add_node(&mut self) -> usize {
+		self.index = None;
+		self.graph.add_node()
+	}
+
+	/// Insert or replace an association between nodes of this namespace.
+	pub fn insert_association(&mut self, association: Association) -> Option<Association> {
+		self.index = None;
+		self.graph.insert_association(association)
+	}
+
+	/// Entities resolved in this namespace (empty when the registry is
+	/// shared).
+	#[must_use]
+	pub const fn entities(&self) -> &EntityRegistry {
+		&self.entities
+	}
+
+	/// This namespace's node for a shared entity, if it has been
+	/// mentioned here.
+	#[must_use]
+	pub fn shared_entity_node(&self, entity: usize) -> Option<usize> {
+		self.shared_nodes.get(&entity).copied()
+	}
+
+	/// CSR snapshot of the graph, rebuilt if the graph changed since it was
+	/// last read.
+	pub fn association_graph(&mut self) -> &AssociationGraph {
+		self.index.get_or_insert_with(|| self.graph.index())
+	}
+
+	/// Spread activation from seeds within this namespace. Seeds past the
+	/// end of this namespace's graph are ignored.
+	pub fn spread(
+		&mut self,
+		seed_indices: &[usize],
+		seed_activations: &[f64],
+		config: &SpreadingConfig,
+		depth: usize,
+	) -> SpreadingResult {
+		spread_activation_graph(
+			self.association_graph(),
+			seed_indices,
+			seed_activations,
+			config,
+			depth,
+		)
+	}
+}
+
+// ============================================================================
+// Namespaces
+// ============================================================================
+
+/// Independent memory graphs keyed by namespace name.
+#[derive(Debug, Clone, Default)]
+pub struct NamespacedGraphs {
+	namespaces: HashMap<String, NamespaceGraph>,
+	entity_config: EntityRegistryConfig,
+	/// Registry shared by all namespaces; its entity nodes are shared
+	/// entity ids, not graph nodes
+	shared_entities: Option<EntityRegistry>,
+}
+
+impl NamespacedGraphs {
+	/// Namespaces that each resolve entities on their own.
+	#[must_use]
+	pub fn new(entity_config: EntityRegistryConfig) -> Self {
+		Self {
+			namespaces: HashMap::new(),
+			entity_config,
+			shared_entities: None,
+		}
+	}
+
+	/// Namespaces that resolve entities through one shared registry.
+	#[must_use]
+	pub fn with_shared_entities(entity_config: EntityRegistryConfig) -> Self {
+		Self {
+			shared_entities: Some(EntityRegistry::new(entity_config.clone())),
+			..Self::new(entity_config)
+		}
+	}
+
+	/// Number of namespaces.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.namespaces.len()
+	}
+
+	/// Whether there are no namespaces.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.namespaces.is_empty()
+	}
+
+	/// Namespace names, sorted.
+	#[must_use]
+	pub fn names(&self) -> Vec<&str> {
+		let mut names: Vec<&str> = self.namespaces.keys().map(String::as_str).collect();
+		names.sort_unstable();
+		names
+	}
+
+	/// The namespace called `name`, created empty if it does not exist.
+	pub fn namespace(&mut self, name: &str) -> &mut NamespaceGraph {
+		self.namespaces
+			.entry(name.to_string())
+			.or_insert_with(|| NamespaceGraph::new(self.entity_config.clone()))
+	}
+
+	/// The namespace called `name`, if it exists.
+	#[must_use]
+	pub fn get(&self, name: &str) -> Option<&NamespaceGraph> {
+		self.namespaces.get(name)
+	}
+
+	/// Mutable access to the namespace called `name`, if it exists.
+	pub fn get_mut(&mut self, name: &str) -> Option<&mut NamespaceGraph> {
+		self.namespaces.get_mut(name)
+	}
+
+	/// Insert or replace a whole namespace, e.g. one restored from storage.
+	pub fn insert(&mut self, name: &str, namespace: NamespaceGraph) -> Option<NamespaceGraph> {
+		self.namespaces.insert(name.to_string(), namespace)
+	}
+
+	/// Remove a namespace and return it.
+	///
+	/// Shared entities it mentioned stay in the shared registry.
+	pub fn remove(&mut self, name: &str) -> Option<NamespaceGraph> {
+		self.namespaces.remove(name)
+	}
+
+	/// The shared entity registry, if entities are shared.
+	#[must_use]
+	pub const fn shared_entities(&self) -> Option<&EntityRegistry> {
+		self.shared_entities.as_ref()
+	}
+
+	/// Resolve an entity mention to a node of namespace `name`.
+	///
+	/// With a shared registry the mention is resolved there, and the
+	/// namespace gets a node for the entity the first time it is mentioned
+	/// in it; the [`Resolution`](crate::entities::Resolution) reported is
+	/// the shared registry's. Otherwise the namespace's own registry
+	/// resolves it.
+	///
+	/// # Errors
+	///
+	/// Returns [`NamespaceError::UnknownNamespace`] if there is no
+	/// namespace `name`.
+	pub fn resolve_entity(
+		&mut self,
+		name: &str,
+		kind: EntityKind,
+		text: &str,
+	) -> Result<Option<ResolvedEntity>, NamespaceError> {
+		let namespace = self
+			.namespaces
+			.get_mut(name)
+			.ok_or_else(|| NamespaceError::UnknownNamespace(name.to_string()))?;
+
+		let Some(shared) = self.shared_entities.as_mut() else {
+			let NamespaceGraph {
+				graph,
+				entities,
+				index,
+				..
+			} = namespace;
+			return Ok(entities.resolve(kind, text, || {
+				*index = None;
+				graph.add_node()
+			}));
+		};
+
+		let next_id = shared.len();
+		let Some(resolved) = shared.resolve(kind, text, || next_id) else {
+			return Ok(None);
+		};
+		let node = if let Some(&node) = namespace.shared_nodes.get(&resolved.node) {
+			node
+		} else {
+			let node = namespace.add_node();
+			let _ = namespace.shared_nodes.insert(resolved.node, node);
+			node
+		};
+		Ok(Some(ResolvedEntity {
+			node,
+			resolution: resolved.resolution,
+		}))
+	}
+
+	/// Spread activation from seeds within namespace `name`.
+	///
+	/// # Errors
+	///
+	/// Returns [`NamespaceError::UnknownNamespace`] if there is no
+	/// namespace `name`.
+	pub fn spread(
+		&mut self,
+		name: &str,
+		seed_indices: &[usize],
+		seed_activations: &[f64],
+		config: &SpreadingConfig,
+		depth: usize,
+	) -> Result<SpreadingResult, NamespaceError> {
+		self.namespaces
+			.get_mut(name)
+			.map(|namespace| namespace.spread(seed_indices, seed_activations, config, depth))
+			.ok_or_else(|| NamespaceError::UnknownNamespace(name.to_string()))
+	}
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::entities::Resolution;
+	use crate::spreading::AssociationType;
+
+	fn link(source: usize, target: usize) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: 0.9,
+			backward_strength: 0.9,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	#[test]
+	fn test_spreading_stays_in_namespace() {
+		let mut graphs = NamespacedGraphs::default();
+		for name in ["alice", "bob"] {
+			let namespace = graphs.namespace(name);
+			let _ = namespace.add_node();
+			let _ = namespace.add_node();
+		}
+		let _ = graphs.namespace("alice").insert_association(link(0, 1));
+		assert_eq!(graphs.names(), ["alice", "bob"]);
+
+		let config = SpreadingConfig::default();
+		let alice = graphs.spread("alice", &[0], &[1.0], &config, 2);
+		assert!(alice.is_ok_and(|r| r.activations[1] > 0.0));
+		let bob = graphs.spread("bob", &[0], &[1.0], &config, 2);
+		assert!(bob.is_ok_and(|r| r.activations[1] == 0.0));
+
+		assert!(matches!(
+			graphs.spread("carol", &[0], &[1.0], &config, 2),
+			Err(NamespaceError::UnknownNamespace(_))
+		));
+	}
+
+	#[test]
+	fn test_spread_ignores_seeds_outside_namespace() {
+		let mut graphs = NamespacedGraphs::default();
+		let _ = graphs.namespace("alice").add_node();
+		let _ = graphs.namespace("bob").add_node();
+		let _ = graphs.namespace("bob").add_node();
+
+		// Node 1 exists in bob but not in alice
+		let config = SpreadingConfig::default();
+		let alice = graphs.spread("alice", &[0, 1], &[1.0, 1.0], &config, 2);
+		assert!(alice.is_ok_and(|r| r.activations.len() == 1 && r.visited_by_depth[0] == [0]));
+	}
+
+	#[test]
+	fn test_separate_entity_registries() {
+		let mut graphs = NamespacedGraphs::default();
+		let _ = graphs.namespace("alice");
+		let _ = graphs.namespace("bob");
+
+		let a = graphs.resolve_entity("alice", EntityKind::Name, "Jason Docton");
+		assert!(a.is_ok_and(|r| r.is_some_and(|r| r.node == 0)));
+		let b = graphs.resolve_entity("bob", EntityKind::Name, "Jason Docton");
+		assert!(b.is_ok_and(|r| r.is_some_and(|r| r.resolution == Resolution::New)));
+		assert!(graphs.shared_entities().is_none());
+		assert_eq!(graphs.get("alice").map(|n| n.entities().len()), Some(1));
+	}
+
+	#[test]
+	fn test_shared_entity_registry() {
+		let mut graphs = NamespacedGraphs::with_shared_entities(EntityRegistryConfig::default());
+		let _ = graphs.namespace("alice").add_node();
+		let _ = graphs.namespace("bob");
+
+		let a = graphs.resolve_entity("alice", EntityKind::Name, "Jason Docton");
+		assert!(a.is_ok());
+		let Ok(Some(a)) = a else { return };
+		assert_eq!(a.node, 1);
+		assert_eq!(a.resolution, Resolution::New);
+
+		// Known to the shared registry, but new to bob's graph
+		let b = graphs.resolve_entity("bob", EntityKind::Name, "jason  docton");
+		assert!(b.is_ok());
+		let Ok(Some(b)) = b else { return };
+		assert_eq!(b.node, 0);
+		assert_eq!(b.resolution, Resolution::Exact);
+		assert_eq!(graphs.get("bob").map(|n| n.graph().num_nodes()), Some(1));
+
+		let again = graphs.resolve_entity("bob", EntityKind::Name, "Jason Docton");
+		assert!(again.is_ok_and(|r| r.is_some_and(|r| r.node == 0)));
+		assert_eq!(graphs.get("bob").map(|n| n.graph().num_nodes()), Some(1));
+		assert_eq!(graphs.shared_entities().map(EntityRegistry::len), Some(1));
+		assert_eq!(
+			graphs.get("bob").and_then(|n| n.shared_entity_node(0)),
+			Some(0)
+		);
+	}
+}
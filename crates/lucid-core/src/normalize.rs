@@ -0,0 +1,170 @@
+//! Strength Normalization
+//!
+//! Rescale association strengths onto a common footing. Graphs built by
+//! different ingestion versions, or merged from different sources, can
+//! hold strengths on very different scales, and spreading then favors
+//! whichever source used the larger numbers.
+//!
+//! Three schemes are provided:
+//! - **Per-node sum**: each node's outgoing strengths sum to 1, forward
+//!   strengths grouped by source and backward strengths by target. Fan is
+//!   then carried by the strengths themselves.
+//! - **Min-max**: `s' = (s - min) / (max - min)` over every strength in
+//!   the graph.
+//! - **Log**: `s' = ln(1 + s) / ln(1 + max)`, compressing a long tail of
+//!   very strong links while keeping zero at zero.
+//!
+//! The scheme applied last is recorded on the graph, see
+//! [`MemoryGraph::normalization`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::graph::MemoryGraph;
+
+/// How association strengths were rescaled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StrengthNormalization {
+	/// Each node's outgoing strengths sum to 1
+	PerNodeSum,
+	/// Global min-max rescale onto `[0, 1]`
+	MinMax,
+	/// Global log scaling onto `[0, 1]`
+	Log,
+}
+
+/// Rescale every strength in the graph and record the scheme on it.
+///
+/// Forward and backward strengths are normalized together. Negative
+/// strengths are treated as 0 by the per-node and log schemes. A node
+/// whose outgoing strengths are all 0 is left as is, and min-max maps a
+/// graph whose strengths are all equal to 1.0 (or 0.0 if they are all 0).
+pub fn normalize_strengths(graph: &mut MemoryGraph, normalization: StrengthNormalization) {
+	let num_nodes = graph.num_nodes();
+	match normalization {
+		StrengthNormalization::PerNodeSum => {
+			let mut forward_totals = vec![0.0; num_nodes];
+			let mut backward_totals = vec![0.0; num_nodes];
+			for a in graph.associations() {
+				forward_totals[a.source] += a.forward_strength.max(0.0);
+				backward_totals[a.target] += a.backward_strength.max(0.0);
+			}
+			for (a, _) in graph.edges_mut() {
+				let (forward, backward) = (forward_totals[a.source], backward_totals[a.target]);
+				if forward > 0.0 {
+					a.forward_strength = a.forward_strength.max(0.0) / forward;
+				}
+				if backward > 0.0 {
+					a.backward_strength = a.backward_strength.max(0.0) / backward;
+				}
+			}
+		}
+		StrengthNormalization::MinMax => {
+			let (min, max) = graph
+				.associations()
+				.iter()
+				.flat_map(|a| [a.forward_strength, a.backward_strength])
+				.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), s| {
+					(lo.min(s), hi.max(s))
+				});
+			let range = max - min;
+			let rescale = |s: f64| {
+				if range > 0.0 {
+					(s - min) / range
+				} else if max > 0.0 {
+					1.0
+				} else {
+					0.0
+				}
+			};
+			for (a, _) in graph.edges_mut() {
+				a.forward_strength = rescale(a.forward_strength);
+				a.backward_strength = rescale(a.backward_strength);
+			}
+		}
+		StrengthNormalization::Log => {
+			let max = graph
+				.associations()
+				.iter()
+				.flat_map(|a| [a.forward_strength, a.backward_strength])
+				.fold(0.0_f64, f64::max);
+			let scale = max.ln_1p();
+			if scale > 0.0 {
+				for (a, _) in graph.edges_mut() {
+					a.forward_strength = a.forward_strength.max(0.0).ln_1p() / scale;
+					a.backward_strength = a.backward_strength.max(0.0).ln_1p() / scale;
+				}
+			}
+		}
+	}
+	graph.set_normalization(Some(normalization));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::{Association, AssociationType};
+
+	fn make_assoc(source: usize, target: usize, forward: f64, backward: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: forward,
+			backward_strength: backward,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	fn graph() -> MemoryGraph {
+		MemoryGraph::from_associations(
+			3,
+			vec![
+				make_assoc(0, 1, 3.0, 1.0),
+				make_assoc(0, 2, 1.0, 4.0),
+				make_assoc(1, 2, 2.0, 4.0),
+			],
+		)
+	}
+
+	fn strengths(graph: &MemoryGraph) -> Vec<(f64, f64)> {
+		graph
+			.associations()
+			.iter()
+			.map(|a| (a.forward_strength, a.backward_strength))
+			.collect()
+	}
+
+	fn close(actual: &[(f64, f64)], expected: &[(f64, f64)]) -> bool {
+		actual
+			.iter()
+			.zip(expected)
+			.all(|(a, e)| (a.0 - e.0).abs() < 1e-12 && (a.1 - e.1).abs() < 1e-12)
+	}
+
+	#[test]
+	fn test_per_node_sum() {
+		let mut graph = graph();
+		assert_eq!(graph.normalization(), None);
+		normalize_strengths(&mut graph, StrengthNormalization::PerNodeSum);
+
+		// Forward grouped by source, backward grouped by target
+		let expected = [(0.75, 1.0), (0.25, 0.5), (1.0, 0.5)];
+		assert!(close(&strengths(&graph), &expected));
+		assert_eq!(
+			graph.normalization(),
+			Some(StrengthNormalization::PerNodeSum)
+		);
+	}
+
+	#[test]
+	fn test_min_max_and_log() {
+		let mut graph = graph();
+		normalize_strengths(&mut graph, StrengthNormalization::MinMax);
+		let expected = [(2.0 / 3.0, 0.0), (0.0, 1.0), (1.0 / 3.0, 1.0)];
+		assert!(close(&strengths(&graph), &expected));
+
+		let mut graph = MemoryGraph::from_associations(2, vec![make_assoc(0, 1, 0.0, 4.0)]);
+		normalize_strengths(&mut graph, StrengthNormalization::Log);
+		assert!(close(&strengths(&graph), &[(0.0, 1.0)]));
+		assert_eq!(graph.normalization(), Some(StrengthNormalization::Log));
+	}
+}
@@ -0,0 +1,187 @@
+//! Novelty and Surprise
+//!
+//! Surprising events are encoded more strongly than expected ones. A new
+//! memory can be surprising in two ways: nothing stored resembles it, or
+//! the current context did not anticipate it.
+//!
+//! Semantic novelty is the distance to the nearest existing memory:
+//!
+//! `N_s = 1 - max(0, max_i sim(e, e_i))`
+//!
+//! Contextual surprise asks how active content like this already was.
+//! Each stored memory's current activation `a_i` (for example, from
+//! spreading over the current context) is weighted by its MINERVA 2 match
+//! to the new memory, `w_i = max(0, sim_i)³`:
+//!
+//! `p = Σ(w_i × a_i) / Σ w_i`
+//!
+//! `N_c = e^(-p / scale)`
+//!
+//! The two are blended into a single novelty score the encoding layer can
+//! use to raise initial strength.
+
+use serde::{Deserialize, Serialize};
+
+use crate::activation::{cosine_similarity_batch, nonlinear_activation};
+
+/// Configuration for novelty scoring.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NoveltyConfig {
+	/// Weight of semantic novelty in the blend (default: 0.5)
+	pub similarity_weight: f64,
+	/// Weight of contextual surprise in the blend (default: 0.5)
+	pub context_weight: f64,
+	/// Predicted activation at which surprise falls to `1/e` (default: 1.0)
+	pub prediction_scale: f64,
+	/// Encoding strength added by a maximally novel memory (default: 0.3)
+	pub encoding_boost: f64,
+}
+
+impl Default for NoveltyConfig {
+	fn default() -> Self {
+		Self {
+			similarity_weight: 0.5,
+			context_weight: 0.5,
+			prediction_scale: 1.0,
+			encoding_boost: 0.3,
+		}
+	}
+}
+
+/// Novelty of a new memory against the existing graph.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NoveltyScore {
+	/// Blended novelty (0-1)
+	pub novelty: f64,
+	/// `N_s`: how unlike any stored memory it is (0-1)
+	pub semantic_novelty: f64,
+	/// `N_c`: how little the current context predicted it (0-1)
+	pub contextual_surprise: f64,
+	/// Most similar existing memory, if any
+	pub nearest: Option<usize>,
+	/// `p`: activation the context predicted for content like this
+	pub predicted_activation: f64,
+}
+
+/// Score how novel a new memory is.
+///
+/// # Arguments
+///
+/// * `embedding` - Embedding of the new memory
+/// * `memory_embeddings` - Embeddings of existing memories
+/// * `current_activations` - Current activation of each existing memory;
+///   missing entries count as 0
+/// * `config` - Blend weights and prediction scale
+///
+/// # Returns
+///
+/// The novelty score. With no existing memories the event is fully novel.
+#[must_use]
+pub fn score_novelty(
+	embedding: &[f64],
+	memory_embeddings: &[Vec<f64>],
+	current_activations: &[f64],
+	config: &NoveltyConfig,
+) -> NoveltyScore {
+	let similarities = cosine_similarity_batch(embedding, memory_embeddings);
+
+	let nearest = similarities
+		.iter()
+		.enumerate()
+		.max_by(|a, b| a.1.total_cmp(b.1).then_with(|| b.0.cmp(&a.0)))
+		.map(|(i, &s)| (i, s));
+	let semantic_novelty = 1.0 - nearest.map_or(0.0, |(_, s)| s.clamp(0.0, 1.0));
+
+	let (weighted, total_weight) =
+		similarities
+			.iter()
+			.enumerate()
+			.fold((0.0, 0.0), |(weighted, total), (i, &s)| {
+				let w = nonlinear_activation(s.max(0.0));
+				let a = current_activations.get(i).copied().unwrap_or(0.0);
+				(w.mul_add(a, weighted), total + w)
+			});
+	let predicted_activation = if total_weight > 0.0 {
+		(weighted / total_weight).max(0.0)
+	} else {
+		0.0
+	};
+	let contextual_surprise = if config.prediction_scale > 0.0 {
+		(-predicted_activation / config.prediction_scale).exp()
+	} else {
+		1.0
+	};
+
+	let weight_sum = config.similarity_weight + config.context_weight;
+	let novelty = if weight_sum > 0.0 {
+		config.similarity_weight.mul_add(
+			semantic_novelty,
+			config.context_weight * contextual_surprise,
+		) / weight_sum
+	} else {
+		0.0
+	};
+
+	NoveltyScore {
+		novelty: novelty.clamp(0.0, 1.0),
+		semantic_novelty,
+		contextual_surprise,
+		nearest: nearest.map(|(i, _)| i),
+		predicted_activation,
+	}
+}
+
+/// Raise an encoding strength by novelty.
+///
+/// `strength' = min(1, strength + boost × novelty)`
+///
+/// Feed the result to
+/// [`compute_instance_noise`](crate::activation::compute_instance_noise).
+#[inline]
+#[must_use]
+pub fn novelty_encoding_strength(strength: f64, novelty: f64, config: &NoveltyConfig) -> f64 {
+	config
+		.encoding_boost
+		.mul_add(novelty.clamp(0.0, 1.0), strength)
+		.clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_semantic_novelty() {
+		let memories = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+		let config = NoveltyConfig::default();
+
+		let familiar = score_novelty(&[1.0, 0.0], &memories, &[], &config);
+		assert!(familiar.semantic_novelty.abs() < 1e-12);
+		assert_eq!(familiar.nearest, Some(0));
+
+		let novel = score_novelty(&[-1.0, -1.0], &memories, &[], &config);
+		assert!((novel.semantic_novelty - 1.0).abs() < 1e-12);
+
+		let empty = score_novelty(&[1.0, 0.0], &[], &[], &config);
+		assert!((empty.novelty - 1.0).abs() < 1e-12);
+		assert_eq!(empty.nearest, None);
+	}
+
+	#[test]
+	fn test_context_predicted_events_are_less_surprising() {
+		let memories = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+		let config = NoveltyConfig::default();
+
+		// Same content; the context has memory 0 active in one case only
+		let expected = score_novelty(&[1.0, 0.1], &memories, &[2.0, 0.0], &config);
+		let unexpected = score_novelty(&[1.0, 0.1], &memories, &[0.0, 2.0], &config);
+
+		assert!((expected.semantic_novelty - unexpected.semantic_novelty).abs() < 1e-12);
+		assert!(expected.contextual_surprise < unexpected.contextual_surprise);
+		assert!(expected.novelty < unexpected.novelty);
+
+		let boosted = novelty_encoding_strength(0.5, unexpected.novelty, &config);
+		assert!(boosted > novelty_encoding_strength(0.5, expected.novelty, &config));
+		assert!((novelty_encoding_strength(0.9, 1.0, &config) - 1.0).abs() < 1e-12);
+	}
+}
@@ -0,0 +1,493 @@
+//! Association Paths
+//!
+//! Explains *why* two memories are related by the chain of associations
+//! connecting them.
+//!
+//! [`crate::spreading::find_activation_path`] returns the path with the
+//! fewest hops, however weak its links. The functions here weigh each
+//! edge by [`edge_cost`] (`-ln(strength)`), so the cheapest path is the
+//! one whose strengths have the highest product — the most strongly
+//! connected chain, not merely the shortest.
+//!
+//! [`find_k_strongest_paths`] returns several distinct chains, so a
+//! relationship can be explained more than one way.
+//!
+//! Every path comes back as an [`ActivationPath`] listing the associations
+//! it traverses, so explanations need no further edge lookups.
+//!
+//! Paths follow forward edges. The `_filtered` variants also skip every
+//! node and edge a [`TraversalFilter`] rejects, as if searching the
+//! subgraph it selects.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::attributes::TraversalFilter;
+use crate::centrality::{edge_cost, MinCost};
+use crate::graph::AssociationGraph;
+use crate::spreading::{Association, AssociationType};
+
+/// One association traversed by an [`ActivationPath`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PathEdge {
+	/// Edge id in the [`AssociationGraph`]
+	pub edge: usize,
+	/// Node the edge leaves
+	pub source: usize,
+	/// Node the edge enters
+	pub target: usize,
+	/// Forward strength of the association
+	pub strength: f64,
+	/// Kind of association
+	pub association_type: AssociationType,
+}
+
+/// A path between two memories, annotated with the associations it uses.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActivationPath {
+	/// Nodes from source to target, inclusive
+	pub nodes: Vec<usize>,
+	/// Traversed associations; `edges[i]` links `nodes[i]` to `nodes[i + 1]`
+	pub edges: Vec<PathEdge>,
+	/// Product of the edge strengths (1.0 for a single node)
+	pub strength: f64,
+}
+
+impl ActivationPath {
+	/// Annotate a node sequence with the strongest forward association
+	/// between each consecutive pair.
+	///
+	/// Returns `None` if the sequence is empty, contains an out-of-range
+	/// node, or two consecutive nodes are not linked.
+	#[must_use]
+	pub fn from_nodes(graph: &AssociationGraph, nodes: Vec<usize>) -> Option<Self> {
+		Self::from_nodes_filtered(graph, nodes, &TraversalFilter::default())
+	}
+
+	/// [`ActivationPath::from_nodes`], choosing only among edges `filter`
+	/// admits.
+	pub(crate) fn from_nodes_filtered(
+		graph: &AssociationGraph,
+		nodes: Vec<usize>,
+		filter: &TraversalFilter<'_>,
+	) -> Option<Self> {
+		if nodes.iter().any(|&node| node >= graph.num_nodes()) {
+			return None;
+		}
+		let edges = nodes
+			.windows(2)
+			.map(|pair| strongest_link(graph, pair[0], pair[1], filter))
+			.collect::<Option<Vec<PathEdge>>>()?;
+		let strength = edges.iter().map(|e| e.strength).product();
+		(!nodes.is_empty()).then_some(Self {
+			nodes,
+			edges,
+			strength,
+		})
+	}
+
+	/// Number of hops (edges).
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.edges.len()
+	}
+
+	/// Whether the path is a single node with no hops.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.edges.is_empty()
+	}
+}
+
+/// The strongest forward association from `source` to `target`.
+fn strongest_link(
+	graph: &AssociationGraph,
+	source: usize,
+	target: usize,
+	filter: &TraversalFilter<'_>,
+) -> Option<PathEdge> {
+	graph
+		.forward(source)
+		.iter_edges()
+		.filter(|&(edge, neighbor, _)| neighbor == target && filter.allows_edge(graph, edge))
+		.max_by(|a, b| a.2.total_cmp(&b.2).then(b.0.cmp(&a.0)))
+		.map(|(edge, _, strength)| PathEdge {
+			edge,
+			source,
+			target,
+			strength,
+			association_type: graph.association_type(edge),
+		})
+}
+
+// ============================================================================
+// Strongest Path (Dijkstra)
+// ============================================================================
+
+/// Find the most strongly connected path between two nodes.
+#[must_use]
+pub fn find_strongest_path(
+	num_nodes: usize,
+	associations: &[Association],
+	source: usize,
+	target: usize,
+) -> Option<ActivationPath> {
+	let graph = AssociationGraph::new(num_nodes, associations);
+	find_strongest_path_graph(&graph, source, target)
+}
+
+/// Find the most strongly connected path over a prebuilt [`AssociationGraph`].
+///
+/// Dijkstra's algorithm with edge cost `-ln(strength)`; stops as soon as
+/// `target` is settled.
+///
+/// # Returns
+///
+/// The path maximizing `Π strength`, or `None` if `target` is unreachable
+/// or either node is out of range.
+#[must_use]
+pub fn find_strongest_path_graph(
+	graph: &AssociationGraph,
+	source: usize,
+	target: usize,
+) -> Option<ActivationPath> {
+	find_strongest_path_filtered(graph, source, target, &TraversalFilter::default())
+}
+
+/// Find the most strongly connected path using only nodes and edges
+/// `filter` admits.
+///
+/// Returns `None` if either endpoint is rejected by the node filter.
+#[must_use]
+pub fn find_strongest_path_filtered(
+	graph: &AssociationGraph,
+	source: usize,
+	target: usize,
+	filter: &TraversalFilter<'_>,
+) -> Option<ActivationPath> {
+	if !endpoints_allowed(graph, source, target, filter) {
+		return None;
+	}
+
+	let blocked_nodes = vec![false; graph.num_nodes()];
+	cheapest_path(
+		graph,
+		source,
+		target,
+		&blocked_nodes,
+		&HashSet::new(),
+		filter,
+	)
+	.and_then(|(nodes, _)| ActivationPath::from_nodes_filtered(graph, nodes, filter))
+}
+
+/// Whether both endpoints are in range and admitted by `filter`.
+fn endpoints_allowed(
+	graph: &AssociationGraph,
+	source: usize,
+	target: usize,
+	filter: &TraversalFilter<'_>,
+) -> bool {
+	let num_nodes = graph.num_nodes();
+	source < num_nodes
+		&& target < num_nodes
+		&& filter.allows_node(graph, source)
+		&& filter.allows_node(graph, target)
+}
+
+/// Dijkstra from `source` to `target`, skipping blocked nodes and links
+/// and anything `filter` rejects.
+///
+/// Returns the node sequence and its total cost.
+fn cheapest_path(
+	graph: &AssociationGraph,
+	source: usize,
+	target: usize,
+	blocked_nodes: &[bool],
+	blocked_links: &HashSet<(usize, usize)>,
+	filter: &TraversalFilter<'_>,
+) -> Option<(Vec<usize>, f64)> {
+	let num_nodes = graph.num_nodes();
+	let mut costs = vec![f64::INFINITY; num_nodes];
+	let mut parent = vec![usize::MAX; num_nodes];
+	let mut settled = vec![false; num_nodes];
+	let mut heap = BinaryHeap::new();
+	costs[source] = 0.0;
+	heap.push(MinCost {
+		cost: 0.0,
+		node: source,
+	});
+
+	while let Some(MinCost { cost, node }) = heap.pop() {
+		if settled[node] {
+			continue;
+		}
+		settled[node] = true;
+
+		if node == target {
+			let mut nodes = Vec::new();
+			let mut current = target;
+			while current != usize::MAX {
+				nodes.push(current);
+				current = parent[current];
+			}
+			nodes.reverse();
+			return Some((nodes, cost));
+		}
+
+		for (edge, neighbor, strength) in graph.forward(node).iter_edges() {
+			if blocked_nodes[neighbor]
+				|| blocked_links.contains(&(node, neighbor))
+				|| !filter.allows(graph, edge, neighbor)
+			{
+				continue;
+			}
+			let Some(step) = edge_cost(strength) else {
+				continue;
+			};
+			let candidate = cost + step;
+			if candidate < costs[neighbor] {
+				costs[neighbor] = candidate;
+				parent[neighbor] = node;
+				heap.push(MinCost {
+					cost: candidate,
+					node: neighbor,
+				});
+			}
+		}
+	}
+
+	None
+}
+
+// ============================================================================
+// K Strongest Paths (Yen)
+// ============================================================================
+
+/// Find up to `k` distinct strongest paths between two nodes.
+#[must_use]
+pub fn find_k_strongest_paths(
+	num_nodes: usize,
+	associations: &[Association],
+	source: usize,
+	target: usize,
+	k: usize,
+) -> Vec<ActivationPath> {
+	let graph = AssociationGraph::new(num_nodes, associations);
+	find_k_strongest_paths_graph(&graph, source, target, k)
+}
+
+/// Find up to `k` distinct strongest paths over a prebuilt [`AssociationGraph`].
+///
+/// Yen's algorithm (1971): each next path branches off a previous one at
+/// some "spur" node, with the links already used from that prefix removed
+/// so the detour must differ. Paths are loopless and ordered by
+/// descending strength; ties prefer fewer hops.
+///
+/// # Returns
+///
+/// Fewer than `k` paths if the graph does not contain that many, and
+/// none if `target` is unreachable or either node is out of range.
+#[must_use]
+pub fn find_k_strongest_paths_graph(
+	graph: &AssociationGraph,
+	source: usize,
+	target: usize,
+	k: usize,
+) -> Vec<ActivationPath> {
+	find_k_strongest_paths_filtered(graph, source, target, k, &TraversalFilter::default())
+}
+
+/// Find up to `k` distinct strongest paths using only nodes and edges
+/// `filter` admits.
+///
+/// Returns no paths if either endpoint is rejected by the node filter.
+#[must_use]
+pub fn find_k_strongest_paths_filtered(
+	graph: &AssociationGraph,
+	source: usize,
+	target: usize,
+	k: usize,
+	filter: &TraversalFilter<'_>,
+) -> Vec<ActivationPath> {
+	if k == 0 || !endpoints_allowed(graph, source, target, filter) {
+		return Vec::new();
+	}
+
+	let mut blocked_nodes = vec![false; graph.num_nodes()];
+	let Some(first) = cheapest_path(
+		graph,
+		source,
+		target,
+		&blocked_nodes,
+		&HashSet::new(),
+		filter,
+	) else {
+		return Vec::new();
+	};
+
+	let mut accepted: Vec<(Vec<usize>, f64)> = vec![first];
+	let mut candidates: Vec<(Vec<usize>, f64)> = Vec::new();
+
+	while accepted.len() < k {
+		let previous = &accepted[accepted.len() - 1].0;
+		let mut root_cost = 0.0;
+
+		for i in 0..previous.len().saturating_sub(1) {
+			let spur = previous[i];
+			let root = &previous[..=i];
+
+			// Block the next link of every accepted path sharing this root
+			let blocked_links: HashSet<(usize, usize)> = accepted
+				.iter()
+				.filter(|(path, _)| path.len() > i + 1 && &path[..=i] == root)
+				.map(|(path, _)| (path[i], path[i + 1]))
+				.collect();
+			for &node in &root[..i] {
+				blocked_nodes[node] = true;
+			}
+
+			if let Some((spur_path, spur_cost)) =
+				cheapest_path(graph, spur, target, &blocked_nodes, &blocked_links, filter)
+			{
+				let mut nodes = root[..i].to_vec();
+				nodes.extend(spur_path);
+				let known = accepted.iter().chain(&candidates).any(|(p, _)| *p == nodes);
+				if !known {
+					candidates.push((nodes, root_cost + spur_cost));
+				}
+			}
+
+			for &node in &root[..i] {
+				blocked_nodes[node] = false;
+			}
+			root_cost += strongest_link(graph, spur, previous[i + 1], filter)
+				.and_then(|link| edge_cost(link.strength))
+				.unwrap_or(f64::INFINITY);
+		}
+
+		let best = candidates
+			.iter()
+			.enumerate()
+			.min_by(|(_, a), (_, b)| a.1.total_cmp(&b.1).then(a.0.len().cmp(&b.0.len())))
+			.map(|(index, _)| index);
+		let Some(best) = best else {
+			break;
+		};
+		accepted.push(candidates.swap_remove(best));
+	}
+
+	accepted
+		.into_iter()
+		.filter_map(|(nodes, _)| ActivationPath::from_nodes_filtered(graph, nodes, filter))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn make_assoc(source: usize, target: usize, strength: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	#[test]
+	fn test_strongest_path_prefers_strong_chain() {
+		let associations = vec![
+			make_assoc(0, 3, 0.1),
+			make_assoc(0, 1, 0.9),
+			make_assoc(1, 2, 0.9),
+			make_assoc(2, 3, 0.9),
+		];
+
+		let path = find_strongest_path(4, &associations, 0, 3);
+		assert!(path.is_some());
+		let Some(path) = path else { return };
+		assert_eq!(path.nodes, vec![0, 1, 2, 3]);
+		assert!((path.strength - 0.729).abs() < 1e-12);
+
+		// Fewest hops is the weak direct link
+		let hops = crate::spreading::find_activation_path(4, &associations, 0, 3);
+		assert_eq!(hops.map(|p| p.nodes), Some(vec![0, 3]));
+	}
+
+	#[test]
+	fn test_strongest_path_edge_cases() {
+		let associations = vec![make_assoc(0, 1, 0.5), make_assoc(1, 2, 0.0)];
+
+		let same = find_strongest_path(3, &associations, 1, 1);
+		assert!(same.is_some_and(|p| p.nodes == vec![1] && (p.strength - 1.0).abs() < 1e-12));
+
+		// Zero-strength links are not traversable
+		assert!(find_strongest_path(3, &associations, 0, 2).is_none());
+		assert!(find_strongest_path(3, &associations, 1, 0).is_none());
+		assert!(find_strongest_path(3, &associations, 0, 7).is_none());
+	}
+
+	#[test]
+	fn test_k_strongest_paths() {
+		// Three routes from 0 to 3
+		let associations = vec![
+			make_assoc(0, 1, 0.9),
+			make_assoc(1, 3, 0.9),
+			make_assoc(0, 2, 0.8),
+			make_assoc(2, 3, 0.8),
+			make_assoc(0, 3, 0.5),
+			make_assoc(1, 2, 0.9),
+		];
+
+		let paths = find_k_strongest_paths(4, &associations, 0, 3, 10);
+		let nodes: Vec<Vec<usize>> = paths.iter().map(|p| p.nodes.clone()).collect();
+		assert_eq!(
+			nodes,
+			vec![vec![0, 1, 3], vec![0, 1, 2, 3], vec![0, 2, 3], vec![0, 3]]
+		);
+		assert!(paths.windows(2).all(|w| w[0].strength >= w[1].strength));
+		let expected = 0.9 * 0.9 * 0.8;
+		assert!((paths[1].strength - expected).abs() < 1e-12);
+
+		let top = find_k_strongest_paths(4, &associations, 0, 3, 1);
+		assert_eq!(top.len(), 1);
+		assert_eq!(
+			Some(&top[0]),
+			find_strongest_path(4, &associations, 0, 3).as_ref()
+		);
+
+		assert!(find_k_strongest_paths(4, &associations, 3, 0, 3).is_empty());
+		assert!(find_k_strongest_paths(4, &associations, 0, 3, 0).is_empty());
+	}
+
+	#[test]
+	fn test_activation_path_edges() {
+		let associations = vec![
+			make_assoc(0, 1, 0.4),
+			Association {
+				association_type: AssociationType::Causal,
+				..make_assoc(0, 1, 0.8)
+			},
+			make_assoc(1, 2, 0.5),
+		];
+		let graph = AssociationGraph::new(3, &associations);
+
+		let path = ActivationPath::from_nodes(&graph, vec![0, 1, 2]);
+		assert!(path.is_some());
+		let Some(path) = path else { return };
+		assert_eq!(path.len(), 2);
+
+		// Parallel links resolve to the strongest one
+		assert_eq!(path.edges[0].edge, 1);
+		assert_eq!(path.edges[0].association_type, AssociationType::Causal);
+		assert_eq!((path.edges[1].source, path.edges[1].target), (1, 2));
+		assert!((path.strength - 0.4).abs() < 1e-12);
+
+		assert!(ActivationPath::from_nodes(&graph, vec![2, 0]).is_none());
+		assert!(ActivationPath::from_nodes(&graph, Vec::new()).is_none());
+		assert!(ActivationPath::from_nodes(&graph, vec![1]).is_some_and(|p| p.is_empty()));
+	}
+}
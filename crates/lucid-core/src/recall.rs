@@ -0,0 +1,416 @@
+//! Free Recall Simulation
+//!
+//! Generates recall sequences from a studied list using the temporal
+//! context model, so parameter choices can be checked against the classic
+//! free recall curves (Kahana, 2012).
+//!
+//! Recall begins by drifting context back toward the start of the list.
+//! Each step then:
+//!
+//! 1. Stops with probability `p_stop = θ_s × e^(θ_r × n)`, where `n` is
+//!    the number of items recalled so far (Polyn et al., 2009)
+//! 2. Otherwise samples a not-yet-recalled item with probability
+//!    proportional to `support^τ` (Luce choice rule)
+//! 3. Reinstates the recalled item's context, cueing its neighbors
+//!
+//! `serial_position_curve` and `lag_crp` summarize simulated (or observed)
+//! trials for comparison with published data.
+//!
+//! `serial_position_priors` gives the retrieval layer a closed-form
+//! version of the same U-shaped curve as per-event activation multipliers.
+
+use serde::{Deserialize, Serialize};
+
+use crate::context::{TemporalContext, TemporalContextConfig};
+use crate::rng::SplitMix64;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Configuration for free recall simulation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FreeRecallConfig {
+	/// Temporal context model parameters
+	pub context: TemporalContextConfig,
+	/// Drift toward start-of-list context when recall begins (default: 0.4)
+	pub start_drift: f64,
+	/// Luce choice sensitivity τ; higher makes recall more deterministic
+	/// (default: 3.0)
+	pub sensitivity: f64,
+	/// Stopping probability before the first recall, `θ_s` (default: 0.05)
+	pub stop_base: f64,
+	/// Growth of stopping probability per recall, `θ_r` (default: 0.3)
+	pub stop_growth: f64,
+	/// Seed for stopping and choice draws (default: 0)
+	pub seed: u64,
+}
+
+impl Default for FreeRecallConfig {
+	fn default() -> Self {
+		Self {
+			context: TemporalContextConfig::default(),
+			start_drift: 0.4,
+			sensitivity: 3.0,
+			stop_base: 0.05,
+			stop_growth: 0.3,
+			seed: 0,
+		}
+	}
+}
+
+/// A simulated recall sequence.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FreeRecallResult {
+	/// Recalled memory indices, in output order
+	pub recalls: Vec<usize>,
+	/// Serial position of each recall among the distinct studied memories
+	/// (0-based)
+	pub positions: Vec<usize>,
+}
+
+// ============================================================================
+// Simulation
+// ============================================================================
+
+/// Simulate free recall of a studied list.
+///
+/// A memory presented more than once is one item, recalled at most once.
+///
+/// # Arguments
+///
+/// * `studied` - Memory indices in presentation order
+/// * `config` - Model parameters and seed
+///
+/// # Returns
+///
+/// Recalls in output order; never repeats an item.
+#[must_use]
+pub fn simulate_free_recall(studied: &[usize], config: &FreeRecallConfig) -> FreeRecallResult {
+	let mut context = TemporalContext::from_episode(studied, config.context.clone());
+	let mut rng = SplitMix64::new(config.seed);
+	let num_items = context.len();
+	let mut recalled = vec![false; num_items];
+	let mut recalls = Vec::new();
+	let mut positions = Vec::new();
+
+	context.reinstate_start(config.start_drift);
+
+	while recalls.len() < num_items {
+		#[allow(clippy::cast_precision_loss)]
+		let attempts = recalls.len() as f64;
+		let p_stop = config.stop_base * (config.stop_growth * attempts).exp();
+		if rng.next_f64() < p_stop {
+			break;
+		}
+
+		let weights: Vec<f64> = context
+			.support()
+			.iter()
+			.zip(&recalled)
+			.map(|(&(_, support), &done)| {
+				if done {
+					0.0
+				} else {
+					support.powf(config.sensitivity)
+				}
+			})
+			.collect();
+		let total: f64 = weights.iter().sum();
+		if total <= 0.0 {
+			break;
+		}
+
+		let mut target = rng.next_f64() * total;
+		let mut chosen = weights.iter().rposition(|&w| w > 0.0).unwrap_or(0);
+		for (item, &w) in weights.iter().enumerate() {
+			if w > 0.0 && target < w {
+				chosen = item;
+				break;
+			}
+			target -= w;
+		}
+
+		let memory = context.memories()[chosen];
+		recalled[chosen] = true;
+		recalls.push(memory);
+		positions.push(chosen);
+		let _ = context.reinstate(memory);
+	}
+
+	FreeRecallResult { recalls, positions }
+}
+
+// ============================================================================
+// Analysis
+// ============================================================================
+
+/// Probability of recall by serial position.
+///
+/// # Arguments
+///
+/// * `trials` - Recalled serial positions per trial (0-based)
+/// * `list_length` - Number of studied positions
+///
+/// # Returns
+///
+/// Proportion of trials recalling each position. Positions outside the
+/// list are ignored.
+#[must_use]
+pub fn serial_position_curve(trials: &[Vec<usize>], list_length: usize) -> Vec<f64> {
+	let mut counts = vec![0.0; list_length];
+	if trials.is_empty() {
+		return counts;
+	}
+
+	for trial in trials {
+		let mut seen = vec![false; list_length];
+		for &position in trial {
+			if position < list_length && !seen[position] {
+				seen[position] = true;
+				counts[position] += 1.0;
+			}
+		}
+	}
+
+	#[allow(clippy::cast_precision_loss)]
+	let n = trials.len() as f64;
+	for c in &mut counts {
+		*c /= n;
+	}
+	counts
+}
+
+/// Lag-conditional response probability (Kahana, 1996).
+///
+/// For each lag, the number of transitions made at that lag divided by
+/// the number of times a transition at that lag was available (to an
+/// item not yet recalled). Repeats and out-of-list positions end the
+/// transition chain at that point.
+///
+/// # Arguments
+///
+/// * `trials` - Recalled serial positions per trial (0-based)
+/// * `list_length` - Number of studied positions
+///
+/// # Returns
+///
+/// `2 × list_length - 1` values; index `lag + list_length - 1` holds the
+/// CRP for `lag` in `-(list_length - 1)..=list_length - 1`. Lags never
+/// available (including lag 0) are 0.
+#[must_use]
+pub fn lag_crp(trials: &[Vec<usize>], list_length: usize) -> Vec<f64> {
+	if list_length == 0 {
+		return Vec::new();
+	}
+	let offset = list_length - 1;
+	let mut actual = vec![0.0; 2 * list_length - 1];
+	let mut possible = vec![0.0; 2 * list_length - 1];
+
+	for trial in trials {
+		let mut recalled = vec![false; list_length];
+		let mut previous: Option<usize> = None;
+
+		for &position in trial {
+			if position >= list_length || recalled[position] {
+				break;
+			}
+			if let Some(from) = previous {
+				for (to, &done) in recalled.iter().enumerate() {
+					if !done {
+						possible[to + offset - from] += 1.0;
+					}
+				}
+				actual[position + offset - from] += 1.0;
+			}
+			recalled[position] = true;
+			previous = Some(position);
+		}
+	}
+
+	actual
+		.iter()
+		.zip(&possible)
+		.map(|(&a, &p)| if p > 0.0 { a / p } else { 0.0 })
+		.collect()
+}
+
+// ============================================================================
+// Serial Position Priors
+// ============================================================================
+
+/// Configuration for serial position priors.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerialPositionConfig {
+	/// Extra activation for the first event of an episode (default: 0.3)
+	pub primacy_boost: f64,
+	/// Per-position decay of the primacy boost; early events are rehearsed
+	/// more (Rundus, 1971) (default: 0.7)
+	pub primacy_decay: f64,
+	/// Extra activation for the last event of an episode (default: 0.5)
+	pub recency_boost: f64,
+	/// Context drift per event, as in `TemporalContextConfig` (default: 0.6)
+	pub context_drift: f64,
+}
+
+impl Default for SerialPositionConfig {
+	fn default() -> Self {
+		Self {
+			primacy_boost: 0.3,
+			primacy_decay: 0.7,
+			recency_boost: 0.5,
+			context_drift: 0.6,
+		}
+	}
+}
+
+/// Predicted primacy/recency gradient for an episode.
+///
+/// `prior_i = 1 + primacy_i + recency_i`
+///
+/// Where:
+/// - `primacy_i = primacy_boost × e^(-primacy_decay × i)`
+/// - `recency_i = recency_boost × ρ^(n - 1 - i)`, with `ρ = √(1 - β²)`
+///   the share of context that survives each later event, so recency
+///   tracks how well end-of-episode context still matches event `i`
+///
+/// # Arguments
+///
+/// * `list_length` - Number of events in the episode
+/// * `config` - Gradient parameters
+///
+/// # Returns
+///
+/// Activation multiplier (≥ 1) per serial position.
+#[must_use]
+pub fn serial_position_gradient(list_length: usize, config: &SerialPositionConfig) -> Vec<f64> {
+	let beta = config.context_drift.clamp(0.0, 1.0);
+	let retention = beta.mul_add(-beta, 1.0).sqrt();
+
+	(0..list_length)
+		.map(|i| {
+			#[allow(clippy::cast_precision_loss)]
+			let position = i as f64;
+			let primacy = config.primacy_boost * (-config.primacy_decay * position).exp();
+			#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+			let lag = (list_length - 1 - i) as i32;
+			let recency = config.recency_boost * retention.powi(lag);
+			1.0 + primacy + recency
+		})
+		.collect()
+}
+
+/// Serial position priors indexed by memory.
+///
+/// # Arguments
+///
+/// * `event_memory_indices` - Memory index of each event, in episode order
+/// * `num_memories` - Total number of memories
+/// * `config` - Gradient parameters
+///
+/// # Returns
+///
+/// Multiplier per memory: 1.0 for memories not in the episode, the
+/// largest prior for memories that appear more than once.
+#[must_use]
+pub fn serial_position_priors(
+	event_memory_indices: &[usize],
+	num_memories: usize,
+	config: &SerialPositionConfig,
+) -> Vec<f64> {
+	let gradient = serial_position_gradient(event_memory_indices.len(), config);
+	let mut priors = vec![1.0_f64; num_memories];
+
+	for (&memory, &prior) in event_memory_indices.iter().zip(&gradient) {
+		if memory < num_memories {
+			priors[memory] = priors[memory].max(prior);
+		}
+	}
+
+	priors
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_simulate_free_recall_no_repeats() {
+		let studied: Vec<usize> = (100..112).collect();
+		let config = FreeRecallConfig {
+			stop_base: 0.0,
+			..FreeRecallConfig::default()
+		};
+		let result = simulate_free_recall(&studied, &config);
+
+		assert_eq!(result.recalls.len(), studied.len());
+		let mut sorted = result.positions.clone();
+		sorted.sort_unstable();
+		assert_eq!(sorted, (0..12).collect::<Vec<_>>());
+		for (memory, position) in result.recalls.iter().zip(&result.positions) {
+			assert_eq!(*memory, studied[*position]);
+		}
+
+		let again = simulate_free_recall(&studied, &config);
+		assert_eq!(again.recalls, result.recalls);
+	}
+
+	#[test]
+	fn test_simulated_recall_shows_contiguity_and_asymmetry() {
+		let studied: Vec<usize> = (0..10).collect();
+		let trials: Vec<Vec<usize>> = (0..300)
+			.map(|seed| {
+				let config = FreeRecallConfig {
+					seed,
+					..FreeRecallConfig::default()
+				};
+				simulate_free_recall(&studied, &config).positions
+			})
+			.collect();
+
+		let crp = lag_crp(&trials, 10);
+		let at = |lag: usize| crp[9 + lag];
+		let before = |lag: usize| crp[9 - lag];
+		assert!(at(1) > before(1));
+		assert!(before(1) > before(5));
+		assert!(at(1) > at(5));
+
+		let spc = serial_position_curve(&trials, 10);
+		assert!(spc[9] > spc[5]);
+	}
+
+	#[test]
+	fn test_lag_crp_counts_available_transitions() {
+		// List of 3: recall 0, 1, 2 then 2, 0
+		let trials = vec![vec![0, 1, 2], vec![2, 0]];
+		let crp = lag_crp(&trials, 3);
+		assert_eq!(crp.len(), 5);
+		// Lag +1: taken both times it was available (0→1, 1→2)
+		assert!((crp[3] - 1.0).abs() < 1e-12);
+		// Lag -2: taken once (2→0), available once
+		assert!((crp[0] - 1.0).abs() < 1e-12);
+		// Lag +2: available once (0→2 in the first trial), never taken
+		assert!(crp[4].abs() < 1e-12);
+
+		let spc = serial_position_curve(&trials, 3);
+		assert_eq!(spc, vec![1.0, 0.5, 1.0]);
+	}
+
+	#[test]
+	fn test_serial_position_gradient_is_u_shaped() {
+		let config = SerialPositionConfig::default();
+		let gradient = serial_position_gradient(12, &config);
+
+		let middle = gradient[6];
+		assert!(gradient[0] > middle);
+		assert!(gradient[11] > middle);
+		// Recency outweighs primacy at the default settings
+		assert!(gradient[11] > gradient[0]);
+		assert!(gradient.iter().all(|&p| p >= 1.0));
+
+		let priors = serial_position_priors(&[4, 2, 9], 5, &config);
+		assert_eq!(priors.len(), 5);
+		assert!((priors[0] - 1.0).abs() < 1e-12);
+		assert!(priors[4] > 1.0);
+	}
+}
@@ -14,7 +14,8 @@ use crate::activation::{
 	combine_activations, compute_base_level, cosine_similarity, cosine_similarity_batch,
 	nonlinear_activation_batch, retrieval_probability,
 };
-use crate::spreading::{spread_activation, Association, SpreadingConfig, SpreadingResult};
+use crate::graph::AssociationGraph;
+use crate::spreading::{spread_activation_graph, Association, SpreadingConfig, SpreadingResult};
 
 /// A memory candidate with all activation components.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -72,6 +73,11 @@ impl Default for RetrievalConfig {
 }
 
 /// Input data for retrieval.
+///
+/// Optional inputs (`working_memory_boosts`, `retrieval_thresholds`) may
+/// be left empty; build with `..RetrievalInput::default()` to leave them
+/// out.
+#[derive(Default)]
 pub struct RetrievalInput<'a> {
 	/// Probe embedding vector
 	pub probe_embedding: &'a [f64],
@@ -87,6 +93,14 @@ pub struct RetrievalInput<'a> {
 	/// Applied to similarity BEFORE nonlinear activation (MINERVA 2 cubing).
 	/// This models how prefrontal WM modulates hippocampal retrieval in real-time.
 	pub working_memory_boosts: &'a [f64],
+	/// Per-memory retrieval threshold τ, replacing `activation_threshold`.
+	/// A memory whose total activation (base level + probe + spreading)
+	/// ends below its own threshold is not returned; memories beyond the
+	/// slice use the configured threshold and are only filtered by
+	/// probability. τ does not gate spreading, whose activations are on a
+	/// different scale; see [`AssociationGraph::with_node_thresholds`] for
+	/// that.
+	pub retrieval_thresholds: &'a [f64],
 	/// Association graph edges
 	pub associations: &'a [Association],
 	/// Current time (ms)
@@ -175,7 +189,7 @@ pub fn retrieve(input: &RetrievalInput<'_>, config: &RetrievalConfig) -> Vec<Ret
 		.filter(|(i, _)| probe_activations[*i] > 0.1) // Minimum similarity threshold
 		.map(|(i, &a)| (i, a))
 		.collect();
-	seeds.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+	seeds.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
 	seeds.truncate(5); // Top 5 as seeds
 
 	// 7. Spread activation
@@ -188,11 +202,12 @@ pub fn retrieve(input: &RetrievalInput<'_>, config: &RetrievalConfig) -> Vec<Ret
 			minimum_activation: 0.01,
 			max_nodes: 1000,
 			bidirectional: config.bidirectional,
+			..SpreadingConfig::default()
 		};
 
-		spread_activation(
-			n,
-			input.associations,
+		let graph = AssociationGraph::new(n, input.associations);
+		spread_activation_graph(
+			&graph,
 			&seed_indices,
 			&seed_activations,
 			&spreading_config,
@@ -202,6 +217,8 @@ pub fn retrieve(input: &RetrievalInput<'_>, config: &RetrievalConfig) -> Vec<Ret
 		SpreadingResult {
 			activations: vec![0.0; n],
 			visited_by_depth: Vec::new(),
+			provenance: None,
+			depth_deltas: None,
 		}
 	};
 
@@ -220,9 +237,14 @@ pub fn retrieve(input: &RetrievalInput<'_>, config: &RetrievalConfig) -> Vec<Ret
 			let breakdown =
 				combine_activations(base_level, probe_activation, spreading, emotional_weight);
 
+			let threshold = input.retrieval_thresholds.get(i).copied();
+			if threshold.is_some_and(|tau| breakdown.total < tau) {
+				return None;
+			}
+
 			let probability = retrieval_probability(
 				breakdown.total,
-				config.activation_threshold,
+				threshold.unwrap_or(config.activation_threshold),
 				config.noise_parameter,
 			);
 
@@ -246,8 +268,8 @@ pub fn retrieve(input: &RetrievalInput<'_>, config: &RetrievalConfig) -> Vec<Ret
 	// 9. Sort by total activation and limit
 	candidates.sort_by(|a, b| {
 		b.total_activation
-			.partial_cmp(&a.total_activation)
-			.unwrap_or(std::cmp::Ordering::Equal)
+			.total_cmp(&a.total_activation)
+			.then(a.index.cmp(&b.index))
 	});
 	candidates.truncate(config.max_results);
 
@@ -266,11 +288,348 @@ pub fn retrieve_by_similarity(
 	let similarities = cosine_similarity_batch(probe_embedding, memory_embeddings);
 
 	let mut indexed: Vec<(usize, f64)> = similarities.into_iter().enumerate().collect();
-	indexed.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+	indexed.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
 
 	indexed.into_iter().take(top_k).map(|(i, _)| i).collect()
 }
 
+/// A memory scored against a partial cue.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PartialMatch {
+	/// Memory index
+	pub index: usize,
+	/// Activation before penalties
+	pub activation: f64,
+	/// Total mismatch penalty subtracted from activation (≥ 0)
+	pub mismatch_penalty: f64,
+	/// Match score (activation - mismatch penalty)
+	pub score: f64,
+}
+
+/// Configuration for partial matching.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PartialMatchConfig {
+	/// Mismatch penalty scale (ACT-R `P`)
+	pub mismatch_penalty: f64,
+	/// Minimum score to include (None = keep all)
+	pub min_score: Option<f64>,
+	/// Maximum results to return
+	pub max_results: usize,
+}
+
+impl Default for PartialMatchConfig {
+	fn default() -> Self {
+		Self {
+			mismatch_penalty: 1.0,
+			min_score: None,
+			max_results: 10,
+		}
+	}
+}
+
+/// Exact-match similarity: 1.0 if equal, 0.0 otherwise.
+#[inline]
+#[must_use]
+pub fn exact_similarity<V: PartialEq>(a: &V, b: &V) -> f64 {
+	if a == b {
+		1.0
+	} else {
+		0.0
+	}
+}
+
+/// Partial-matching retrieval (ACT-R).
+///
+/// `M_i = A_i - P × Σ_k (1 - sim(c_k, v_ik))`
+///
+/// Where:
+/// - `A_i` = activation of memory i
+/// - `P` = mismatch penalty
+/// - `c_k` = cue value for slot k
+/// - `v_ik` = memory i's value for slot k
+///
+/// Rather than requiring every cue slot to match, each memory pays a
+/// penalty proportional to how different it is, so the closest memory
+/// to the cue can win even when nothing matches exactly.
+///
+/// # Arguments
+///
+/// * `activations` - Activation for each memory
+/// * `memory_slots` - Attribute values for each memory, indexed by slot
+/// * `cue` - `(slot, value)` pairs to match against
+/// * `similarity` - Similarity between two values (clamped to 0-1, 1 = identical)
+/// * `config` - Partial matching configuration
+///
+/// A memory missing a cued slot pays the full penalty for it.
+///
+/// # Returns
+///
+/// Memories ranked by match score.
+#[must_use]
+pub fn partial_match<V>(
+	activations: &[f64],
+	memory_slots: &[Vec<V>],
+	cue: &[(usize, V)],
+	similarity: impl Fn(&V, &V) -> f64,
+	config: &PartialMatchConfig,
+) -> Vec<PartialMatch> {
+	let mut matches: Vec<PartialMatch> = activations
+		.iter()
+		.enumerate()
+		.map(|(index, &activation)| {
+			let slots = memory_slots.get(index).map_or(&[][..], Vec::as_slice);
+			let mismatch: f64 = cue
+				.iter()
+				.map(|(slot, cue_value)| {
+					let sim = slots
+						.get(*slot)
+						.map_or(0.0, |value| similarity(cue_value, value).clamp(0.0, 1.0));
+					1.0 - sim
+				})
+				.sum();
+			let mismatch_penalty = config.mismatch_penalty * mismatch;
+
+			PartialMatch {
+				index,
+				activation,
+				mismatch_penalty,
+				score: activation - mismatch_penalty,
+			}
+		})
+		.filter(|m| config.min_score.is_none_or(|min| m.score >= min))
+		.collect();
+
+	matches.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.index.cmp(&b.index)));
+	matches.truncate(config.max_results);
+
+	matches
+}
+
+/// Relative weight of each signal in [`score`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScoreWeights {
+	/// Base-level activation (recency × frequency)
+	pub base_level: f64,
+	/// Spreading activation
+	pub spreading: f64,
+	/// Probe-embedding similarity
+	pub similarity: f64,
+	/// Time since last access
+	pub recency: f64,
+}
+
+impl Default for ScoreWeights {
+	fn default() -> Self {
+		Self {
+			base_level: 1.0,
+			spreading: 1.0,
+			similarity: 1.0,
+			recency: 0.5,
+		}
+	}
+}
+
+/// How each signal is rescaled across candidates before weighting.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScoreNormalization {
+	/// Use raw values
+	None,
+	/// Rescale to 0-1 by the min and max over candidates (default)
+	#[default]
+	MinMax,
+	/// Standardize to zero mean and unit variance
+	ZScore,
+}
+
+/// Configuration for [`score`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScoreConfig {
+	/// Per-signal weights
+	pub weights: ScoreWeights,
+	/// Per-signal rescaling
+	pub normalization: ScoreNormalization,
+	/// Recency half-life (ms): `recency = 2^(-age / half_life)`
+	pub recency_half_life_ms: f64,
+	/// Minimum score to include (None = keep all)
+	pub min_score: Option<f64>,
+	/// Maximum results to return
+	pub max_results: usize,
+}
+
+impl Default for ScoreConfig {
+	fn default() -> Self {
+		Self {
+			weights: ScoreWeights::default(),
+			normalization: ScoreNormalization::MinMax,
+			recency_half_life_ms: 24.0 * 60.0 * 60.0 * 1000.0, // 1 day
+			min_score: None,
+			max_results: 10,
+		}
+	}
+}
+
+/// Per-memory signals for [`score`].
+///
+/// Every slice is indexed by memory; shorter slices are padded with 0.0
+/// (or "never accessed" for `last_access_ms`).
+pub struct ScoreInput<'a> {
+	/// Base-level activation per memory
+	pub base_levels: &'a [f64],
+	/// Spreading activation per memory
+	pub spreading: &'a [f64],
+	/// Probe similarity per memory
+	pub similarities: &'a [f64],
+	/// Last access time (ms) per memory
+	pub last_access_ms: &'a [f64],
+	/// Current time (ms)
+	pub current_time_ms: f64,
+}
+
+/// One value per scoring signal.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ScoreComponents {
+	/// Base-level activation
+	pub base_level: f64,
+	/// Spreading activation
+	pub spreading: f64,
+	/// Probe similarity
+	pub similarity: f64,
+	/// Recency (0-1)
+	pub recency: f64,
+}
+
+/// A memory ranked by [`score`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScoredMemory {
+	/// Memory index
+	pub index: usize,
+	/// Combined score
+	pub score: f64,
+	/// Raw signal values
+	pub components: ScoreComponents,
+	/// Weighted, normalized contribution of each signal (sums to `score`)
+	pub contributions: ScoreComponents,
+}
+
+/// Rescale `values` in place.
+fn normalize_signal(values: &mut [f64], normalization: ScoreNormalization) {
+	if values.is_empty() {
+		return;
+	}
+	match normalization {
+		ScoreNormalization::None => {}
+		ScoreNormalization::MinMax => {
+			let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+			let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+			let range = max - min;
+			for v in values.iter_mut() {
+				*v = if range > 0.0 { (*v - min) / range } else { 0.0 };
+			}
+		}
+		ScoreNormalization::ZScore => {
+			#[allow(clippy::cast_precision_loss)]
+			let n = values.len() as f64;
+			let mean = values.iter().sum::<f64>() / n;
+			let std = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+			for v in values.iter_mut() {
+				*v = if std > 0.0 { (*v - mean) / std } else { 0.0 };
+			}
+		}
+	}
+}
+
+/// Unified retrieval scoring.
+///
+/// `score_i = w_B × B̂_i + w_S × Ŝ_i + w_P × P̂_i + w_R × R̂_i`
+///
+/// Where:
+/// - `B` = base-level activation
+/// - `S` = spreading activation
+/// - `P` = probe similarity
+/// - `R` = recency, `2^(-age / half_life)`
+/// - `x̂` = the signal after `config.normalization`
+///
+/// Normalizing first keeps signals on different scales (log-odds
+/// base level vs. 0-1 similarity) from drowning each other out.
+///
+/// # Returns
+///
+/// Memories ranked by score, each with raw components and the weighted
+/// contribution of each signal.
+#[must_use]
+pub fn score(input: &ScoreInput<'_>, config: &ScoreConfig) -> Vec<ScoredMemory> {
+	let n = input
+		.base_levels
+		.len()
+		.max(input.spreading.len())
+		.max(input.similarities.len())
+		.max(input.last_access_ms.len());
+	let signal = |values: &[f64]| -> Vec<f64> {
+		(0..n)
+			.map(|i| values.get(i).copied().unwrap_or(0.0))
+			.collect()
+	};
+
+	let base_levels = signal(input.base_levels);
+	let spreading = signal(input.spreading);
+	let similarities = signal(input.similarities);
+	let recency: Vec<f64> = (0..n)
+		.map(|i| {
+			input.last_access_ms.get(i).map_or(0.0, |&last| {
+				let age = (input.current_time_ms - last).max(0.0);
+				if config.recency_half_life_ms > 0.0 {
+					(-age / config.recency_half_life_ms).exp2()
+				} else {
+					0.0
+				}
+			})
+		})
+		.collect();
+
+	let mut normalized = [
+		base_levels.clone(),
+		spreading.clone(),
+		similarities.clone(),
+		recency.clone(),
+	];
+	for values in &mut normalized {
+		normalize_signal(values, config.normalization);
+	}
+	let [base_hat, spreading_hat, similarity_hat, recency_hat] = normalized;
+
+	let weights = &config.weights;
+	let mut scored: Vec<ScoredMemory> = (0..n)
+		.map(|i| {
+			let contributions = ScoreComponents {
+				base_level: weights.base_level * base_hat[i],
+				spreading: weights.spreading * spreading_hat[i],
+				similarity: weights.similarity * similarity_hat[i],
+				recency: weights.recency * recency_hat[i],
+			};
+			ScoredMemory {
+				index: i,
+				score: contributions.base_level
+					+ contributions.spreading
+					+ contributions.similarity
+					+ contributions.recency,
+				components: ScoreComponents {
+					base_level: base_levels[i],
+					spreading: spreading[i],
+					similarity: similarities[i],
+					recency: recency[i],
+				},
+				contributions,
+			}
+		})
+		.filter(|m| config.min_score.is_none_or(|min| m.score >= min))
+		.collect();
+
+	scored.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.index.cmp(&b.index)));
+	scored.truncate(config.max_results);
+
+	scored
+}
+
 /// Compute surprise (prediction error) between expected and actual.
 ///
 /// Used to trigger reconsolidation - when a retrieved memory differs
@@ -318,6 +677,7 @@ pub fn triggers_lability(surprise: f64, threshold: f64) -> bool {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::spreading::AssociationType;
 
 	#[test]
 	fn test_retrieve_empty() {
@@ -328,6 +688,7 @@ mod tests {
 			emotional_weights: &[],
 			decay_rates: &[],
 			working_memory_boosts: &[],
+			retrieval_thresholds: &[],
 			associations: &[],
 			current_time_ms: 1_000_000.0,
 		};
@@ -354,6 +715,7 @@ mod tests {
 			emotional_weights: &[0.5, 0.5, 0.5],
 			decay_rates: &[0.05, 0.05, 0.05],
 			working_memory_boosts: &[1.0, 1.0, 1.0], // No boost
+			retrieval_thresholds: &[],
 			associations: &[],
 			current_time_ms: now,
 		};
@@ -403,6 +765,7 @@ mod tests {
 			emotional_weights: &[0.5, 0.5],
 			decay_rates: &[0.5, 0.5],
 			working_memory_boosts: &[1.0, 2.0], // Memory 1 gets 2x WM boost
+			retrieval_thresholds: &[],
 			associations: &[],
 			current_time_ms: now,
 		};
@@ -439,6 +802,7 @@ mod tests {
 			emotional_weights: &[0.5],
 			decay_rates: &[0.5],
 			working_memory_boosts: &[2.0], // 2x boost would exceed 1.0, should cap
+			retrieval_thresholds: &[],
 			associations: &[],
 			current_time_ms: now,
 		};
@@ -458,4 +822,194 @@ mod tests {
 			"Probe activation should be capped at 1.0"
 		);
 	}
+
+	#[test]
+	fn test_retrieval_thresholds_suppress_memory() {
+		let probe = vec![1.0, 0.0, 0.0];
+		let memories = vec![vec![1.0, 0.0, 0.0], vec![1.0, 0.0, 0.0]];
+		let now = 1_000_000.0;
+
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &[vec![now], vec![now]],
+			emotional_weights: &[0.5, 0.5],
+			decay_rates: &[0.5, 0.5],
+			working_memory_boosts: &[],
+			retrieval_thresholds: &[100.0], // Memory 0 is suppressed
+			associations: &[],
+			current_time_ms: now,
+		};
+
+		let config = RetrievalConfig {
+			spreading_depth: 0,
+			min_probability: 0.0,
+			..Default::default()
+		};
+
+		let result = retrieve(&input, &config);
+		let indices: Vec<usize> = result.iter().map(|c| c.index).collect();
+		assert_eq!(indices, vec![1]);
+	}
+
+	#[test]
+	fn test_retrieval_threshold_does_not_block_spreading() {
+		let probe = vec![1.0, 0.0, 0.0];
+		let memories = vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]];
+		let associations = [Association {
+			source: 0,
+			target: 1,
+			forward_strength: 0.9,
+			backward_strength: 0.9,
+			association_type: AssociationType::Semantic,
+		}];
+		let now = 1_000_000.0;
+
+		let input = RetrievalInput {
+			probe_embedding: &probe,
+			memory_embeddings: &memories,
+			access_histories_ms: &[vec![now], vec![now]],
+			emotional_weights: &[0.5, 0.5],
+			decay_rates: &[0.5, 0.5],
+			retrieval_thresholds: &[100.0], // Memory 0 is suppressed
+			associations: &associations,
+			current_time_ms: now,
+			..RetrievalInput::default()
+		};
+
+		let config = RetrievalConfig {
+			spreading_depth: 1,
+			min_probability: 0.0,
+			..Default::default()
+		};
+
+		// Memory 0 is left out but still primes its neighbor
+		let result = retrieve(&input, &config);
+		assert_eq!(result.len(), 1);
+		assert_eq!(result[0].index, 1);
+		assert!(result[0].spreading > 0.0);
+	}
+
+	#[test]
+	fn test_partial_match_prefers_closest() {
+		// Slots: [color, size]
+		let memory_slots = vec![
+			vec!["red", "large"],
+			vec!["red", "small"],
+			vec!["blue", "small"],
+		];
+		let activations = [0.5, 0.5, 0.9];
+		let cue = [(0, "red"), (1, "small")];
+
+		let results = partial_match(
+			&activations,
+			&memory_slots,
+			&cue,
+			exact_similarity,
+			&PartialMatchConfig::default(),
+		);
+
+		// Exact match wins despite lower activation; blue/small beats red/large
+		assert_eq!(results[0].index, 1);
+		assert!(results[0].mismatch_penalty.abs() < 1e-10);
+		assert_eq!(results[1].index, 2);
+		assert!((results[1].score - (-0.1)).abs() < 1e-10);
+		assert_eq!(results[2].index, 0);
+	}
+
+	#[test]
+	fn test_partial_match_graded_similarity() {
+		let memory_slots = vec![vec![10.0], vec![12.0], vec![]];
+		let activations = [0.0, 0.0, 0.0];
+		let cue = [(0, 10.5)];
+		let config = PartialMatchConfig {
+			mismatch_penalty: 2.0,
+			min_score: Some(-1.5),
+			..Default::default()
+		};
+
+		let results = partial_match(
+			&activations,
+			&memory_slots,
+			&cue,
+			|a: &f64, b: &f64| 1.0 - (a - b).abs() / 4.0,
+			&config,
+		);
+
+		// Missing slot pays the full penalty (-2.0) and is filtered out
+		assert_eq!(results.len(), 2);
+		assert_eq!(results[0].index, 0);
+		assert!((results[0].score - (-0.25)).abs() < 1e-10);
+		assert!((results[1].score - (-0.75)).abs() < 1e-10);
+	}
+
+	#[test]
+	fn test_score_breakdown() {
+		let now = 10.0 * 86_400_000.0;
+		let input = ScoreInput {
+			base_levels: &[-1.0, 0.5, 0.0],
+			spreading: &[0.0, 0.2, 0.8],
+			similarities: &[0.9, 0.1, 0.5],
+			last_access_ms: &[now, now - 86_400_000.0],
+			current_time_ms: now,
+		};
+		let config = ScoreConfig {
+			normalization: ScoreNormalization::None,
+			..Default::default()
+		};
+
+		let results = score(&input, &config);
+		assert_eq!(results.len(), 3);
+
+		for result in &results {
+			let c = &result.contributions;
+			let total = c.base_level + c.spreading + c.similarity + c.recency;
+			assert!((result.score - total).abs() < 1e-10);
+		}
+
+		// Memory 1: 0.5 + 0.2 + 0.1 + 0.5 × 2^-1
+		let m1 = results.iter().find(|r| r.index == 1);
+		assert!(m1.is_some_and(|r| (r.score - 1.05).abs() < 1e-10));
+		// Memory 2 has no access history, so no recency
+		let m2 = results.iter().find(|r| r.index == 2);
+		assert!(m2.is_some_and(|r| r.components.recency.abs() < 1e-10));
+	}
+
+	#[test]
+	fn test_score_normalization_balances_scales() {
+		// Base level spans a much wider range than similarity
+		let input = ScoreInput {
+			base_levels: &[-10.0, 10.0],
+			spreading: &[],
+			similarities: &[1.0, 0.0],
+			last_access_ms: &[],
+			current_time_ms: 0.0,
+		};
+		let weights = ScoreWeights {
+			base_level: 1.0,
+			spreading: 0.0,
+			similarity: 1.5,
+			recency: 0.0,
+		};
+
+		let raw = score(
+			&input,
+			&ScoreConfig {
+				weights: weights.clone(),
+				normalization: ScoreNormalization::None,
+				..Default::default()
+			},
+		);
+		let scaled = score(
+			&input,
+			&ScoreConfig {
+				weights,
+				normalization: ScoreNormalization::MinMax,
+				..Default::default()
+			},
+		);
+
+		assert_eq!(raw[0].index, 1);
+		assert_eq!(scaled[0].index, 0);
+	}
 }
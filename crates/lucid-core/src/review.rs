@@ -0,0 +1,355 @@
+//! Spaced Repetition
+//!
+//! Schedules reviews straight from the ACT-R forgetting curve instead of
+//! SM-2's fixed ease multipliers.
+//!
+//! Base-level activation falls as time passes without access:
+//!
+//! `B(t) = ln[Σ(t - t_k)^(-d)]`
+//!
+//! A memory is due when `B(t)` reaches the target activation. Each review
+//! adds a term to the sum, so the next crossing comes later — intervals
+//! expand on their own, and expand faster for well-practiced memories.
+//!
+//! `B(t)` is strictly decreasing between accesses, so the crossing is found
+//! by doubling the interval until `B` drops below target, then bisecting.
+//!
+//! Reviews are retrieval attempts, and retrieving a memory strengthens it
+//! more than restudying it does (the testing effect; Roediger & Karpicke,
+//! 2006). [`record_retrieval`] applies that asymmetrically: a success is
+//! logged as an access and strengthens the links leading to the memory,
+//! by more when the memory was hard to reach; a failure leaves the history
+//! untouched and weakens those links slightly.
+
+use serde::{Deserialize, Serialize};
+
+use crate::activation::{compute_base_level, retrieval_probability};
+use crate::graph::MemoryGraph;
+use crate::learning::hebbian_update;
+
+const MS_PER_DAY: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Configuration for review scheduling.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReviewConfig {
+	/// `d` in the forgetting equation (default: 0.5)
+	pub decay_rate: f64,
+	/// Base-level activation to keep memories above (default: -5.0, about
+	/// six hours after a single study)
+	pub target_activation: f64,
+	/// Longest interval scheduled, in milliseconds (default: 365 days)
+	pub max_interval_ms: f64,
+}
+
+impl Default for ReviewConfig {
+	fn default() -> Self {
+		Self {
+			decay_rate: 0.5,
+			target_activation: -5.0,
+			max_interval_ms: 365.0 * MS_PER_DAY,
+		}
+	}
+}
+
+/// Compute when a memory should next be reviewed.
+///
+/// # Arguments
+///
+/// * `access_timestamps_ms` - Previous accesses/reviews (in milliseconds)
+/// * `current_time_ms` - Current time (in milliseconds)
+/// * `config` - Decay rate, target, and interval cap
+///
+/// # Returns
+///
+/// The time (ms) at which base-level activation falls to the target,
+/// accurate to one second. `current_time_ms` if the memory is already at
+/// or below target or has no history; at most `current_time_ms +
+/// max_interval_ms`.
+#[must_use]
+pub fn next_review_time(
+	access_timestamps_ms: &[f64],
+	current_time_ms: f64,
+	config: &ReviewConfig,
+) -> f64 {
+	let activation_at = |t: f64| compute_base_level(access_timestamps_ms, t, config.decay_rate);
+	let target = config.target_activation;
+
+	if activation_at(current_time_ms) <= target {
+		return current_time_ms;
+	}
+	let latest = current_time_ms + config.max_interval_ms.max(0.0);
+	if activation_at(latest) > target {
+		return latest;
+	}
+
+	// Bracket the crossing, then bisect to one second. 64 doublings or
+	// halvings covers any representable interval
+	let mut lo = current_time_ms;
+	let mut hi = latest;
+	let mut step = 1000.0;
+	for _ in 0..64 {
+		let candidate = (current_time_ms + step).min(latest);
+		if activation_at(candidate) <= target {
+			hi = candidate;
+			break;
+		}
+		lo = candidate;
+		step *= 2.0;
+	}
+	for _ in 0..64 {
+		if hi - lo <= 1000.0 {
+			break;
+		}
+		let mid = lo + (hi - lo) / 2.0;
+		if activation_at(mid) > target {
+			lo = mid;
+		} else {
+			hi = mid;
+		}
+	}
+	hi
+}
+
+/// Compute due dates for a set of memories.
+///
+/// # Arguments
+///
+/// * `access_histories_ms` - Access timestamps per memory
+/// * `current_time_ms` - Current time (in milliseconds)
+/// * `config` - Decay rate, target, and interval cap
+///
+/// # Returns
+///
+/// Due time (ms) per memory, parallel to `access_histories_ms`.
+#[must_use]
+pub fn schedule_reviews(
+	access_histories_ms: &[Vec<f64>],
+	current_time_ms: f64,
+	config: &ReviewConfig,
+) -> Vec<f64> {
+	access_histories_ms
+		.iter()
+		.map(|history| next_review_time(history, current_time_ms, config))
+		.collect()
+}
+
+// ============================================================================
+// Retrieval Practice
+// ============================================================================
+
+/// Result of a retrieval attempt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RetrievalOutcome {
+	/// The memory was recalled
+	Success,
+	/// The memory could not be recalled
+	Failure,
+}
+
+/// Configuration for retrieval practice updates.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RetrievalPracticeConfig {
+	/// `d` in the forgetting equation, used to judge effort (default: 0.5)
+	pub decay_rate: f64,
+	/// Base-level activation at which recall is a coin flip (default: -3.0,
+	/// about seven minutes after a single study)
+	pub effort_threshold: f64,
+	/// Logistic noise `s` around the threshold (default: 0.5)
+	pub effort_noise: f64,
+	/// Hebbian rate for links into a recalled memory (default: 0.1)
+	pub success_rate: f64,
+	/// Extra rate, as a multiple of `success_rate`, for a fully effortful
+	/// recall (default: 1.0)
+	pub effort_bonus: f64,
+	/// Fraction of strength lost by links into a memory that could not be
+	/// recalled (default: 0.05)
+	pub failure_rate: f64,
+}
+
+impl Default for RetrievalPracticeConfig {
+	fn default() -> Self {
+		Self {
+			decay_rate: 0.5,
+			effort_threshold: -3.0,
+			effort_noise: 0.5,
+			success_rate: 0.1,
+			effort_bonus: 1.0,
+			failure_rate: 0.05,
+		}
+	}
+}
+
+/// Record a retrieval attempt and apply the testing effect.
+///
+/// Effort is how unlikely recall was given the history so far:
+///
+/// `effort = 1 - P(B(t))`
+///
+/// with `P` the logistic [`retrieval_probability`]. On success the attempt
+/// is appended to the history and every link into `node` (forward strength
+/// of edges targeting it, backward strength of edges leaving it) gets a
+/// [`hebbian_update`] at rate `success_rate × (1 + effort_bonus × effort)`.
+/// On failure the history is unchanged and those strengths are scaled by
+/// `1 - failure_rate`. Updated edges are stamped at `current_time_ms`.
+///
+/// # Arguments
+///
+/// * `graph` - Graph holding the node's associations
+/// * `access_timestamps_ms` - The node's access history (in milliseconds)
+/// * `node` - Memory that was tested
+/// * `outcome` - Whether it was recalled
+/// * `current_time_ms` - Time of the attempt (in milliseconds)
+/// * `config` - Effort curve and update rates
+///
+/// # Returns
+///
+/// The effort (0-1) of the attempt.
+pub fn record_retrieval(
+	graph: &mut MemoryGraph,
+	access_timestamps_ms: &mut Vec<f64>,
+	node: usize,
+	outcome: RetrievalOutcome,
+	current_time_ms: f64,
+	config: &RetrievalPracticeConfig,
+) -> f64 {
+	let base_level = compute_base_level(access_timestamps_ms, current_time_ms, config.decay_rate);
+	let effort =
+		1.0 - retrieval_probability(base_level, config.effort_threshold, config.effort_noise);
+
+	if outcome == RetrievalOutcome::Success {
+		access_timestamps_ms.push(current_time_ms);
+	}
+	let rate = config.success_rate * config.effort_bonus.mul_add(effort, 1.0);
+	let keep = 1.0 - config.failure_rate.clamp(0.0, 1.0);
+	let update = |strength: f64| match outcome {
+		RetrievalOutcome::Success => hebbian_update(strength, rate),
+		RetrievalOutcome::Failure => strength * keep,
+	};
+
+	for (association, meta) in graph.edges_mut() {
+		if association.target == node {
+			association.forward_strength = update(association.forward_strength);
+		} else if association.source == node {
+			association.backward_strength = update(association.backward_strength);
+		} else {
+			continue;
+		}
+		meta.last_reinforced_ms = Some(current_time_ms);
+	}
+
+	effort
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::{Association, AssociationType};
+
+	#[test]
+	fn test_next_review_time_hits_target() {
+		let config = ReviewConfig::default();
+		let due = next_review_time(&[0.0], 0.0, &config);
+
+		// Single study: B = -0.5 ln(t_s) = -5 at t = e^10 seconds
+		let expected = 10.0_f64.exp() * 1000.0;
+		assert!((due - expected).abs() <= 1000.0);
+
+		assert!((next_review_time(&[], 5.0, &config) - 5.0).abs() < 1e-12);
+		assert!((next_review_time(&[0.0], expected * 2.0, &config) - expected * 2.0).abs() < 1e-9);
+	}
+
+	#[test]
+	fn test_review_intervals_expand() {
+		let config = ReviewConfig::default();
+		let mut history = vec![0.0];
+		let mut now = 0.0;
+		let mut intervals = Vec::new();
+
+		for _ in 0..4 {
+			let due = next_review_time(&history, now, &config);
+			intervals.push(due - now);
+			history.push(due);
+			now = due;
+		}
+
+		assert!(intervals.windows(2).all(|w| w[1] > w[0]));
+
+		let capped = ReviewConfig {
+			max_interval_ms: 60_000.0,
+			..ReviewConfig::default()
+		};
+		let due = schedule_reviews(&[vec![0.0], vec![]], 0.0, &capped);
+		assert_eq!(due, vec![60_000.0, 0.0]);
+	}
+
+	fn make_assoc(source: usize, target: usize, strength: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	#[test]
+	fn test_effortful_recall_strengthens_more() {
+		let config = RetrievalPracticeConfig::default();
+		let mut easy = MemoryGraph::from_associations(2, vec![make_assoc(0, 1, 0.5)]);
+		let mut hard = easy.clone();
+
+		// Tested one second vs one day after study
+		let mut history = vec![0.0];
+		let easy_effort = record_retrieval(
+			&mut easy,
+			&mut history,
+			1,
+			RetrievalOutcome::Success,
+			1000.0,
+			&config,
+		);
+		assert_eq!(history.len(), 2);
+		let mut history = vec![0.0];
+		let hard_effort = record_retrieval(
+			&mut hard,
+			&mut history,
+			1,
+			RetrievalOutcome::Success,
+			MS_PER_DAY,
+			&config,
+		);
+		assert!(hard_effort > easy_effort);
+
+		let gain = |g: &MemoryGraph| g.associations()[0].forward_strength - 0.5;
+		assert!(gain(&hard) > gain(&easy) && gain(&easy) > 0.0);
+		// Only the direction leading into node 1 changes
+		assert!((easy.associations()[0].backward_strength - 0.5).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_failed_recall_weakens_without_access() {
+		let config = RetrievalPracticeConfig::default();
+		let mut graph =
+			MemoryGraph::from_associations(3, vec![make_assoc(1, 0, 0.5), make_assoc(1, 2, 0.5)]);
+		let mut history = vec![0.0];
+
+		let _ = record_retrieval(
+			&mut graph,
+			&mut history,
+			1,
+			RetrievalOutcome::Failure,
+			1000.0,
+			&config,
+		);
+
+		assert_eq!(history, vec![0.0]);
+		for association in graph.associations() {
+			assert!((association.backward_strength - 0.475).abs() < 1e-12);
+			assert!((association.forward_strength - 0.5).abs() < 1e-12);
+		}
+		assert_eq!(
+			graph.metadata(1, 0).and_then(|m| m.last_reinforced_ms),
+			Some(1000.0)
+		);
+	}
+}
@@ -0,0 +1,235 @@
+//! Emotional Salience
+//!
+//! Emotionally significant moments are remembered better: they come to
+//! mind more readily, pull related memories along with them, and fade more
+//! slowly.
+//!
+//! Each memory carries an arousal (0-1) and a valence (-1 to 1), folded
+//! into one intensity:
+//!
+//! `i = arousal × (1 + b × max(0, -valence))`
+//!
+//! Where `b` is the negativity bias, since unpleasant events tend to
+//! stick harder than pleasant ones of equal arousal. Configurable curves
+//! turn intensity into multipliers on activation emitted while spreading,
+//! activation attracted while spreading, and base-level decay.
+
+use serde::{Deserialize, Serialize};
+
+use crate::graph::AssociationGraph;
+
+/// Emotional attributes of a memory.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Salience {
+	/// How stirring the memory was (0 = neutral, 1 = intense)
+	pub arousal: f64,
+	/// How pleasant it was (-1 = very unpleasant, 1 = very pleasant)
+	pub valence: f64,
+}
+
+/// Maps salience intensity to a multiplier.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SalienceCurve {
+	/// Always 1.0; salience has no effect
+	None,
+	/// `1 + gain × i`
+	Linear {
+		/// Multiplier added per unit intensity
+		gain: f64,
+	},
+	/// `e^(gain × i)`
+	Exponential {
+		/// Log-multiplier per unit intensity
+		gain: f64,
+	},
+}
+
+impl SalienceCurve {
+	/// Multiplier for intensity `i`, never below zero.
+	#[must_use]
+	pub fn multiplier(&self, intensity: f64) -> f64 {
+		match *self {
+			Self::None => 1.0,
+			Self::Linear { gain } => gain.mul_add(intensity, 1.0).max(0.0),
+			Self::Exponential { gain } => (gain * intensity).exp(),
+		}
+	}
+}
+
+/// Configuration for salience modulation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SalienceConfig {
+	/// Extra weight on negative valence (default: 0.25)
+	pub negativity_bias: f64,
+	/// Gain on activation a salient node sends (default: linear, gain 1.0)
+	pub emission: SalienceCurve,
+	/// Gain on activation a salient node receives (default: linear, gain 0.5)
+	pub attraction: SalienceCurve,
+	/// Divisor on the base-level decay rate (default: linear, gain 1.0,
+	/// so a fully aroused memory decays at half the rate)
+	pub decay: SalienceCurve,
+}
+
+impl Default for SalienceConfig {
+	fn default() -> Self {
+		Self {
+			negativity_bias: 0.25,
+			emission: SalienceCurve::Linear { gain: 1.0 },
+			attraction: SalienceCurve::Linear { gain: 0.5 },
+			decay: SalienceCurve::Linear { gain: 1.0 },
+		}
+	}
+}
+
+/// Combined emotional intensity of a memory.
+///
+/// `i = arousal × (1 + b × max(0, -valence))`
+#[inline]
+#[must_use]
+pub fn salience_intensity(salience: &Salience, config: &SalienceConfig) -> f64 {
+	let negativity = (-salience.valence.clamp(-1.0, 1.0)).max(0.0);
+	salience.arousal.clamp(0.0, 1.0) * config.negativity_bias.mul_add(negativity, 1.0)
+}
+
+/// Emission and attraction gains for every node.
+///
+/// # Returns
+///
+/// `(emission, attraction)`, parallel to `saliences`, ready for
+/// [`AssociationGraph::with_node_gains`].
+#[must_use]
+pub fn salience_gains(saliences: &[Salience], config: &SalienceConfig) -> (Vec<f64>, Vec<f64>) {
+	saliences
+		.iter()
+		.map(|salience| {
+			let intensity = salience_intensity(salience, config);
+			(
+				config.emission.multiplier(intensity),
+				config.attraction.multiplier(intensity),
+			)
+		})
+		.unzip()
+}
+
+/// Attach salience gains to a prebuilt graph.
+///
+/// Nodes beyond `saliences` are treated as neutral.
+#[must_use]
+pub fn apply_salience(
+	graph: AssociationGraph,
+	saliences: &[Salience],
+	config: &SalienceConfig,
+) -> AssociationGraph {
+	let (emission, attraction) = salience_gains(saliences, config);
+	graph.with_node_gains(&emission, &attraction)
+}
+
+/// Base-level decay rate slowed by salience.
+///
+/// `d' = d / decay(i)`
+///
+/// Pass the result as the decay rate to
+/// [`compute_base_level`](crate::activation::compute_base_level).
+#[inline]
+#[must_use]
+pub fn salient_decay_rate(decay_rate: f64, salience: &Salience, config: &SalienceConfig) -> f64 {
+	let divisor = config
+		.decay
+		.multiplier(salience_intensity(salience, config));
+	if divisor <= 0.0 {
+		return decay_rate;
+	}
+	decay_rate / divisor
+}
+
+/// Batch compute salient decay rates.
+#[must_use]
+pub fn salient_decay_rates(
+	decay_rate: f64,
+	saliences: &[Salience],
+	config: &SalienceConfig,
+) -> Vec<f64> {
+	saliences
+		.iter()
+		.map(|salience| salient_decay_rate(decay_rate, salience, config))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::{
+		spread_activation_graph, Association, AssociationType, SpreadingConfig,
+	};
+
+	fn make_assoc(source: usize, target: usize, strength: f64) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	#[test]
+	fn test_salient_nodes_attract_more_activation() {
+		// Seed 0 links equally to a neutral (1) and a salient (2) memory
+		let associations = vec![make_assoc(0, 1, 0.5), make_assoc(0, 2, 0.5)];
+		let saliences = [
+			Salience::default(),
+			Salience::default(),
+			Salience {
+				arousal: 1.0,
+				valence: -1.0,
+			},
+		];
+		let config = SalienceConfig::default();
+		let graph = apply_salience(AssociationGraph::new(3, &associations), &saliences, &config);
+
+		let result = spread_activation_graph(&graph, &[0], &[1.0], &SpreadingConfig::default(), 1);
+		// 1 + 0.5 × 1.25
+		let ratio = result.activations[2] / result.activations[1];
+		assert!((ratio - 1.625).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_salient_nodes_emit_more_activation() {
+		let associations = vec![make_assoc(0, 2, 0.5), make_assoc(1, 3, 0.5)];
+		let saliences = [
+			Salience {
+				arousal: 1.0,
+				valence: 1.0,
+			},
+			Salience::default(),
+		];
+		let graph = apply_salience(
+			AssociationGraph::new(4, &associations),
+			&saliences,
+			&SalienceConfig::default(),
+		);
+
+		let result =
+			spread_activation_graph(&graph, &[0, 1], &[1.0, 1.0], &SpreadingConfig::default(), 1);
+		assert!((result.activations[2] / result.activations[3] - 2.0).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_salient_memories_decay_slower() {
+		let config = SalienceConfig::default();
+		let calm = Salience::default();
+		let intense = Salience {
+			arousal: 1.0,
+			valence: 0.5,
+		};
+
+		assert!((salient_decay_rate(0.5, &calm, &config) - 0.5).abs() < 1e-12);
+		assert!((salient_decay_rate(0.5, &intense, &config) - 0.25).abs() < 1e-12);
+
+		let none = SalienceConfig {
+			decay: SalienceCurve::None,
+			..SalienceConfig::default()
+		};
+		assert_eq!(salient_decay_rates(0.5, &[intense], &none), vec![0.5]);
+	}
+}
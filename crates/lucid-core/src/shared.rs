@@ -0,0 +1,334 @@
+//! Shared Graph
+//!
+//! A recall endpoint runs many spreading queries at once while ingestion
+//! keeps adding memories. Wrapping a [`MemoryGraph`] in one `Mutex`
+//! serializes all of them behind each other and behind every write.
+//!
+//! [`SharedMemoryGraph`] uses a snapshot read model instead. Writers
+//! mutate the graph under a mutex and then publish a fresh CSR snapshot;
+//! readers take the current snapshot (an `Arc` clone under a briefly held
+//! read lock) and spread over it without holding any lock. Queries never
+//! wait for each other or for a write in progress, and each query sees one
+//! consistent version of the graph from start to finish.
+//!
+//! Publishing rebuilds the snapshot, which costs time proportional to the
+//! graph. For bursts of writes, mutate with
+//! [`write_unpublished`](SharedMemoryGraph::write_unpublished) and
+//! [`publish`](SharedMemoryGraph::publish) once at the end.
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
+
+use crate::attributes::TraversalFilter;
+use crate::graph::{AssociationGraph, MemoryGraph};
+use crate::spreading::{
+	spread_activation_filtered, spread_activation_graph, SpreadingConfig, SpreadingResult,
+};
+
+/// A published, immutable version of the graph.
+#[derive(Clone, Debug)]
+pub struct PublishedGraph {
+	/// Number of publishes before this one
+	pub version: u64,
+	/// The graph as of that publish
+	pub graph: Arc<AssociationGraph>,
+}
+
+/// Graph state guarded by the writer lock.
+#[derive(Debug)]
+struct Writer {
+	graph: MemoryGraph,
+	/// Changed since the last publish
+	dirty: bool,
+}
+
+/// A [`MemoryGraph`] that many threads can query while one writes.
+///
+/// `Send + Sync`; share it behind an `Arc`.
+#[derive(Debug)]
+pub struct SharedMemoryGraph {
+	writer: Mutex<Writer>,
+	published: RwLock<PublishedGraph>,
+}
+
+impl SharedMemoryGraph {
+	/// Share `graph`, publishing it as version 0.
+	#[must_use]
+	pub fn new(graph: MemoryGraph) -> Self {
+		let published = PublishedGraph {
+			version: 0,
+			graph: Arc::new(graph.index()),
+		};
+		Self {
+			writer: Mutex::new(Writer {
+				graph,
+				dirty: false,
+			}),
+			published: RwLock::new(published),
+		}
+	}
+
+	/// The latest published snapshot.
+	///
+	/// Holding it keeps that version alive; later publishes do not affect
+	/// it.
+	#[must_use]
+	pub fn snapshot(&self) -> PublishedGraph {
+		self.published
+			.read()
+			.unwrap_or_else(PoisonError::into_inner)
+			.clone()
+	}
+
+	/// Version of the latest published snapshot.
+	#[must_use]
+	pub fn version(&self) -> u64 {
+		self.published
+			.read()
+			.unwrap_or_else(PoisonError::into_inner)
+			.version
+	}
+
+	/// Spread activation over the latest published snapshot.
+	///
+	/// Seeds the snapshot does not contain yet are ignored.
+	#[must_use]
+	pub fn spread(
+		&self,
+		seed_indices: &[usize],
+		seed_activations: &[f64],
+		config: &SpreadingConfig,
+		depth: usize,
+	) -> SpreadingResult {
+		let snapshot = self.snapshot();
+		spread_activation_graph(
+			&snapshot.graph,
+			seed_indices,
+			seed_activations,
+			config,
+			depth,
+		)
+	}
+
+	/// Spread activation over the latest published snapshot, restricted by
+	/// `filter`.
+	#[must_use]
+	pub fn spread_filtered(
+		&self,
+		seed_indices: &[usize],
+		seed_activations: &[f64],
+		config: &SpreadingConfig,
+		depth: usize,
+		filter: &TraversalFilter<'_>,
+	) -> SpreadingResult {
+		let snapshot = self.snapshot();
+		spread_activation_filtered(
+			&snapshot.graph,
+			seed_indices,
+			seed_activations,
+			config,
+			depth,
+			filter,
+		)
+	}
+
+	/// Read the writable graph, including unpublished changes.
+	///
+	/// Waits for any write in progress; queries should use
+	/// [`snapshot`](Self::snapshot) instead.
+	pub fn read<R>(&self, f: impl FnOnce(&MemoryGraph) -> R) -> R {
+		f(&self.lock_writer().graph)
+	}
+
+	/// Mutate the graph and publish the result.
+	pub fn write<R>(&self, f: impl FnOnce(&mut MemoryGraph) -> R) -> R {
+		let mut writer = self.lock_writer();
+		let result = f(&mut writer.graph);
+		self.publish_locked(&mut writer);
+		result
+	}
+
+	/// Mutate the graph without publishing; queries keep seeing the
+	/// previous snapshot until [`publish`](Self::publish).
+	pub fn write_unpublished<R>(&self, f: impl FnOnce(&mut MemoryGraph) -> R) -> R {
+		let mut writer = self.lock_writer();
+		writer.dirty = true;
+		f(&mut writer.graph)
+	}
+
+	/// Publish unpublished changes, if any.
+	///
+	/// # Returns
+	///
+	/// The version now visible to queries.
+	pub fn publish(&self) -> u64 {
+		let mut writer = self.lock_writer();
+		if writer.dirty {
+			self.publish_locked(&mut writer);
+		}
+		self.version()
+	}
+
+	/// Take back the graph, including unpublished changes.
+	#[must_use]
+	pub fn into_inner(self) -> MemoryGraph {
+		self.writer
+			.into_inner()
+			.unwrap_or_else(PoisonError::into_inner)
+			.graph
+	}
+
+	/// A panic inside a write closure poisons the lock. [`MemoryGraph`]
+	/// methods do not run caller code while the graph is half-updated, so
+	/// the graph holds exactly the edits made before the panic; the lookup
+	/// index is rebuilt anyway, the lock is recovered rather than
+	/// propagated, and those edits are left for the next publish.
+	fn lock_writer(&self) -> MutexGuard<'_, Writer> {
+		self.writer.lock().unwrap_or_else(|poisoned| {
+			self.writer.clear_poison();
+			let mut writer = poisoned.into_inner();
+			writer.graph.reindex();
+			writer.dirty = true;
+			writer
+		})
+	}
+
+	/// Build the snapshot under the writer lock so it matches the graph,
+	/// then swap it in; readers only wait for the swap.
+	fn publish_locked(&self, writer: &mut Writer) {
+		let graph = Arc::new(writer.graph.index());
+		writer.dirty = false;
+		let mut published = self
+			.published
+			.write()
+			.unwrap_or_else(PoisonError::into_inner);
+		published.version += 1;
+		published.graph = graph;
+	}
+}
+
+impl Default for SharedMemoryGraph {
+	fn default() -> Self {
+		Self::new(MemoryGraph::default())
+	}
+}
+
+impl From<MemoryGraph> for SharedMemoryGraph {
+	fn from(graph: MemoryGraph) -> Self {
+		Self::new(graph)
+	}
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::{Association, AssociationType};
+
+	fn link(source: usize, target: usize) -> Association {
+		Association {
+			source,
+			target,
+			forward_strength: 0.9,
+			backward_strength: 0.9,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	#[test]
+	fn test_snapshot_isolated_from_writes() {
+		let shared = SharedMemoryGraph::new(MemoryGraph::new(2));
+		let before = shared.snapshot();
+
+		let _ = shared.write(|graph| graph.insert_association(link(0, 1)));
+		assert_eq!(shared.version(), 1);
+		assert_eq!(before.graph.num_edges(), 0);
+		assert_eq!(shared.snapshot().graph.num_edges(), 1);
+
+		let config = SpreadingConfig::default();
+		assert!(shared.spread(&[0], &[1.0], &config, 2).activations[1] > 0.0);
+	}
+
+	#[test]
+	fn test_spread_ignores_unpublished_seeds() {
+		let shared = SharedMemoryGraph::new(MemoryGraph::new(2));
+		let _ = shared.write(|graph| graph.insert_association(link(0, 1)));
+		let _ = shared.write_unpublished(MemoryGraph::add_node);
+
+		let config = SpreadingConfig::default();
+		let result = shared.spread(&[0, 2], &[1.0, 1.0], &config, 2);
+		assert_eq!(result.activations.len(), 2);
+		assert_eq!(result.visited_by_depth[0], vec![0]);
+		assert!(result.activations[1] > 0.0);
+
+		let filter = TraversalFilter::default();
+		let result = shared.spread_filtered(&[usize::MAX], &[1.0], &config, 2, &filter);
+		assert!(result.activations.iter().all(|&a| a == 0.0));
+	}
+
+	#[test]
+	fn test_unpublished_writes_hidden_until_publish() {
+		let shared = SharedMemoryGraph::new(MemoryGraph::new(2));
+		assert_eq!(shared.publish(), 0);
+
+		let node = shared.write_unpublished(MemoryGraph::add_node);
+		assert_eq!(node, 2);
+		assert_eq!(shared.read(MemoryGraph::num_nodes), 3);
+		assert_eq!(shared.snapshot().graph.num_nodes(), 2);
+
+		assert_eq!(shared.publish(), 1);
+		assert_eq!(shared.snapshot().graph.num_nodes(), 3);
+		assert_eq!(shared.publish(), 1);
+	}
+
+	#[test]
+	fn test_panicking_write_keeps_earlier_edits() {
+		let shared = SharedMemoryGraph::new(MemoryGraph::new(3));
+		let outcome = std::panic::catch_unwind(|| {
+			shared.write(|graph| {
+				let _ = graph.insert_association(link(0, 1));
+				assert!(graph.num_edges() == 0, "write failed");
+			});
+		});
+		assert!(outcome.is_err());
+		assert_eq!(shared.version(), 0);
+
+		// The lock recovers and the finished edit reaches the next publish
+		assert_eq!(shared.read(MemoryGraph::num_edges), 1);
+		assert_eq!(shared.publish(), 1);
+		let _ = shared.write(|graph| graph.insert_association(link(1, 2)));
+		assert_eq!(shared.snapshot().graph.num_edges(), 2);
+	}
+
+	#[test]
+	fn test_concurrent_queries_during_writes() {
+		let shared = SharedMemoryGraph::new(MemoryGraph::new(1));
+		let config = SpreadingConfig::default();
+
+		std::thread::scope(|scope| {
+			for _ in 0..4 {
+				let _ = scope.spawn(|| {
+					for _ in 0..50 {
+						let snapshot = shared.snapshot();
+						let result =
+							spread_activation_graph(&snapshot.graph, &[0], &[1.0], &config, 2);
+						assert_eq!(result.activations.len(), snapshot.graph.num_nodes());
+					}
+				});
+			}
+			let _ = scope.spawn(|| {
+				for _ in 0..50 {
+					let _ = shared.write(|graph| {
+						let node = graph.add_node();
+						graph.insert_association(link(node - 1, node))
+					});
+				}
+			});
+		});
+
+		assert_eq!(shared.version(), 50);
+		assert_eq!(shared.into_inner().num_nodes(), 51);
+	}
+}
@@ -0,0 +1,553 @@
+//! Graph Snapshots
+//!
+//! Persist and restore the full memory state in one compact binary blob:
+//! the association graph with its per-edge bookkeeping, temporal links,
+//! and base-level access histories.
+//!
+//! Layout (all integers little-endian):
+//!
+//! | Bytes | Field |
+//! |-------|-------|
+//! | 4     | Magic `LUCD` |
+//! | 2     | Format version |
+//! | 2     | Reserved (0) |
+//! | 8     | Payload length |
+//! | 8     | FNV-1a 64 checksum of the payload |
+//! | ...   | Payload |
+//!
+//! The payload is fixed-width records, so encoding is a single pass with
+//! no per-field tags. A snapshot that is truncated, altered, or written
+//! by a newer format version is rejected rather than partially loaded.
+//!
+//! Version 2 added the graph's strength normalization; version 1
+//! snapshots still load, with no normalization recorded.
+
+use std::io::{Read, Write};
+
+use crate::activation::AssociationState;
+use crate::error::{Classify, ErrorKind};
+use crate::graph::MemoryGraph;
+use crate::normalize::StrengthNormalization;
+use crate::spreading::{Association, AssociationType, TemporalLink};
+
+/// Snapshot format version written by this build.
+pub const SNAPSHOT_VERSION: u16 = 2;
+
+const MAGIC: [u8; 4] = *b"LUCD";
+const HEADER_LEN: usize = 24;
+
+/// Error type for snapshot decoding.
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+	/// The data does not start with the snapshot magic.
+	#[error("Not a lucid snapshot")]
+	BadMagic,
+
+	/// The snapshot was written by an unsupported format version.
+	#[error("Unsupported snapshot version: {0}")]
+	UnsupportedVersion(u16),
+
+	/// The payload does not match its checksum.
+	#[error("Checksum mismatch: expected {expected:#018x}, got {actual:#018x}")]
+	ChecksumMismatch {
+		/// Checksum stored in the header
+		expected: u64,
+		/// Checksum of the payload read
+		actual: u64,
+	},
+
+	/// The data ended before the snapshot did.
+	#[error("Snapshot truncated")]
+	Truncated,
+
+	/// A field holds a value this version cannot represent.
+	#[error("Invalid snapshot field: {0}")]
+	Invalid(&'static str),
+
+	/// Reading or writing failed.
+	#[error("Snapshot I/O failed: {0}")]
+	Io(std::io::Error),
+}
+
+impl Classify for SnapshotError {
+	fn kind(&self) -> ErrorKind {
+		match self {
+			Self::Io(e) => ErrorKind::from_io(e),
+			Self::BadMagic
+			| Self::UnsupportedVersion(_)
+			| Self::ChecksumMismatch { .. }
+			| Self::Truncated
+			| Self::Invalid(_) => ErrorKind::InvalidInput,
+		}
+	}
+}
+
+/// Full memory state for persistence.
+#[derive(Clone, Debug, Default)]
+pub struct GraphSnapshot {
+	/// Associations with their decay bookkeeping
+	pub graph: MemoryGraph,
+	/// Temporal links between episode events
+	pub temporal_links: Vec<TemporalLink>,
+	/// Access timestamps (ms) per memory, for base-level activation
+	pub access_histories: Vec<Vec<f64>>,
+}
+
+impl GraphSnapshot {
+	/// Encode the snapshot, header included.
+	#[must_use]
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut payload = Vec::new();
+		self.encode_payload(&mut payload);
+
+		let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+		bytes.extend_from_slice(&MAGIC);
+		bytes.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+		bytes.extend_from_slice(&0u16.to_le_bytes());
+		put_usize(&mut bytes, payload.len());
+		bytes.extend_from_slice(&fnv1a(&payload).to_le_bytes());
+		bytes.extend_from_slice(&payload);
+		bytes
+	}
+
+	/// Decode a snapshot produced by [`GraphSnapshot::to_bytes`].
+	///
+	/// # Errors
+	///
+	/// Returns [`SnapshotError::BadMagic`] or
+	/// [`SnapshotError::UnsupportedVersion`] for foreign data,
+	/// [`SnapshotError::Truncated`] or [`SnapshotError::ChecksumMismatch`]
+	/// for damaged data, and [`SnapshotError::Invalid`] for an unknown
+	/// association type, state, or normalization, a node count above
+	/// [`MemoryGraph::MAX_NODES`], or an association outside the graph.
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+		let mut header = Reader { bytes };
+		if header.take(4)? != MAGIC {
+			return Err(SnapshotError::BadMagic);
+		}
+		let version = header.u16()?;
+		if version == 0 || version > SNAPSHOT_VERSION {
+			return Err(SnapshotError::UnsupportedVersion(version));
+		}
+		let _reserved = header.u16()?;
+		let payload_len = header.usize()?;
+		let expected = header.u64()?;
+		let payload = header.take(payload_len)?;
+
+		let actual = fnv1a(payload);
+		if actual != expected {
+			return Err(SnapshotError::ChecksumMismatch { expected, actual });
+		}
+		Self::decode_payload(&mut Reader { bytes: payload }, version)
+	}
+
+	/// Write the snapshot to `writer`.
+	///
+	/// # Errors
+	///
+	/// Returns [`SnapshotError::Io`] if writing fails.
+	pub fn save(&self, mut writer: impl Write) -> Result<(), SnapshotError> {
+		writer
+			.write_all(&self.to_bytes())
+			.map_err(SnapshotError::Io)
+	}
+
+	/// Read a snapshot from `reader`.
+	///
+	/// # Errors
+	///
+	/// Returns [`SnapshotError::Io`] if reading fails, otherwise as
+	/// [`GraphSnapshot::from_bytes`].
+	pub fn load(mut reader: impl Read) -> Result<Self, SnapshotError> {
+		let mut bytes = Vec::new();
+		let _ = reader.read_to_end(&mut bytes).map_err(SnapshotError::Io)?;
+		Self::from_bytes(&bytes)
+	}
+
+	fn encode_payload(&self, out: &mut Vec<u8>) {
+		put_usize(out, self.graph.num_nodes());
+		out.push(normalization_code(self.graph.normalization()));
+
+		let associations = self.graph.associations();
+		put_usize(out, associations.len());
+		for association in associations {
+			let meta = self
+				.graph
+				.metadata(association.source, association.target)
+				.copied()
+				.unwrap_or_default();
+			put_usize(out, association.source);
+			put_usize(out, association.target);
+			put_f64(out, association.forward_strength);
+			put_f64(out, association.backward_strength);
+			out.push(type_code(association.association_type));
+			out.push(state_code(meta.state));
+			match meta.last_reinforced_ms {
+				Some(ms) => {
+					out.push(1);
+					put_f64(out, ms);
+				}
+				None => out.push(0),
+			}
+		}
+
+		put_usize(out, self.temporal_links.len());
+		for link in &self.temporal_links {
+			put_usize(out, link.source_position);
+			put_usize(out, link.target_position);
+			put_usize(out, link.source_memory);
+			put_usize(out, link.target_memory);
+			put_f64(out, link.forward_strength);
+			put_f64(out, link.backward_strength);
+		}
+
+		put_usize(out, self.access_histories.len());
+		for history in &self.access_histories {
+			put_usize(out, history.len());
+			for &timestamp in history {
+				put_f64(out, timestamp);
+			}
+		}
+	}
+
+	fn decode_payload(reader: &mut Reader<'_>, version: u16) -> Result<Self, SnapshotError> {
+		let num_nodes = reader.usize()?;
+		if num_nodes > MemoryGraph::MAX_NODES {
+			return Err(SnapshotError::Invalid("node count"));
+		}
+		let mut graph = MemoryGraph::new(num_nodes);
+		if version >= 2 {
+			graph.set_normalization(normalization_from_code(reader.u8()?)?);
+		}
+
+		for _ in 0..reader.usize()? {
+			let source = reader.usize()?;
+			let target = reader.usize()?;
+			let forward_strength = reader.f64()?;
+			let backward_strength = reader.f64()?;
+			let association_type = type_from_code(reader.u8()?)?;
+			let state = state_from_code(reader.u8()?)?;
+			let last_reinforced_ms = match reader.u8()? {
+				0 => None,
+				1 => Some(reader.f64()?),
+				_ => return Err(SnapshotError::Invalid("reinforcement flag")),
+			};
+
+			if graph
+				.insert_association(Association {
+					source,
+					target,
+					forward_strength,
+					backward_strength,
+					association_type,
+				})
+				.is_some()
+			{
+				return Err(SnapshotError::Invalid("association"));
+			}
+			if let Some(meta) = graph.metadata_mut(source, target) {
+				meta.state = state;
+				meta.last_reinforced_ms = last_reinforced_ms;
+			}
+		}
+
+		let mut temporal_links = Vec::new();
+		for _ in 0..reader.usize()? {
+			temporal_links.push(TemporalLink {
+				source_position: reader.usize()?,
+				target_position: reader.usize()?,
+				source_memory: reader.usize()?,
+				target_memory: reader.usize()?,
+				forward_strength: reader.f64()?,
+				backward_strength: reader.f64()?,
+			});
+		}
+
+		let mut access_histories = Vec::new();
+		for _ in 0..reader.usize()? {
+			let len = reader.usize()?;
+			let mut history = Vec::new();
+			for _ in 0..len {
+				history.push(reader.f64()?);
+			}
+			access_histories.push(history);
+		}
+
+		if !reader.bytes.is_empty() {
+			return Err(SnapshotError::Invalid("trailing payload bytes"));
+		}
+
+		Ok(Self {
+			graph,
+			temporal_links,
+			access_histories,
+		})
+	}
+}
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+/// FNV-1a 64-bit hash.
+fn fnv1a(bytes: &[u8]) -> u64 {
+	bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+		(hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+	})
+}
+
+fn put_usize(out: &mut Vec<u8>, value: usize) {
+	out.extend_from_slice(&(value as u64).to_le_bytes());
+}
+
+fn put_f64(out: &mut Vec<u8>, value: f64) {
+	out.extend_from_slice(&value.to_le_bytes());
+}
+
+const fn type_code(association_type: AssociationType) -> u8 {
+	match association_type {
+		AssociationType::Semantic => 0,
+		AssociationType::Temporal => 1,
+		AssociationType::Causal => 2,
+		AssociationType::Entity => 3,
+		AssociationType::VisualSimilarity => 4,
+	}
+}
+
+const fn type_from_code(code: u8) -> Result<AssociationType, SnapshotError> {
+	Ok(match code {
+		0 => AssociationType::Semantic,
+		1 => AssociationType::Temporal,
+		2 => AssociationType::Causal,
+		3 => AssociationType::Entity,
+		4 => AssociationType::VisualSimilarity,
+		_ => return Err(SnapshotError::Invalid("association type")),
+	})
+}
+
+const fn state_code(state: AssociationState) -> u8 {
+	match state {
+		AssociationState::Fresh => 0,
+		AssociationState::Consolidating => 1,
+		AssociationState::Consolidated => 2,
+		AssociationState::Reconsolidating => 3,
+	}
+}
+
+const fn state_from_code(code: u8) -> Result<AssociationState, SnapshotError> {
+	Ok(match code {
+		0 => AssociationState::Fresh,
+		1 => AssociationState::Consolidating,
+		2 => AssociationState::Consolidated,
+		3 => AssociationState::Reconsolidating,
+		_ => return Err(SnapshotError::Invalid("association state")),
+	})
+}
+
+const fn normalization_code(normalization: Option<StrengthNormalization>) -> u8 {
+	match normalization {
+		None => 0,
+		Some(StrengthNormalization::PerNodeSum) => 1,
+		Some(StrengthNormalization::MinMax) => 2,
+		Some(StrengthNormalization::Log) => 3,
+	}
+}
+
+const fn normalization_from_code(code: u8) -> Result<Option<StrengthNormalization>, SnapshotError> {
+	Ok(match code {
+		0 => None,
+		1 => Some(StrengthNormalization::PerNodeSum),
+		2 => Some(StrengthNormalization::MinMax),
+		3 => Some(StrengthNormalization::Log),
+		_ => return Err(SnapshotError::Invalid("strength normalization")),
+	})
+}
+
+/// Cursor over the remaining bytes.
+struct Reader<'a> {
+	bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+	const fn take(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+		if self.bytes.len() < len {
+			return Err(SnapshotError::Truncated);
+		}
+		let (head, rest) = self.bytes.split_at(len);
+		self.bytes = rest;
+		Ok(head)
+	}
+
+	fn array<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+		let mut array = [0; N];
+		array.copy_from_slice(self.take(N)?);
+		Ok(array)
+	}
+
+	fn u8(&mut self) -> Result<u8, SnapshotError> {
+		Ok(self.take(1)?[0])
+	}
+
+	fn u16(&mut self) -> Result<u16, SnapshotError> {
+		self.array().map(u16::from_le_bytes)
+	}
+
+	fn u64(&mut self) -> Result<u64, SnapshotError> {
+		self.array().map(u64::from_le_bytes)
+	}
+
+	fn usize(&mut self) -> Result<usize, SnapshotError> {
+		usize::try_from(self.u64()?).map_err(|_| SnapshotError::Invalid("index exceeds usize"))
+	}
+
+	fn f64(&mut self) -> Result<f64, SnapshotError> {
+		self.array().map(f64::from_le_bytes)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn sample() -> GraphSnapshot {
+		let mut graph = MemoryGraph::new(3);
+		let _ = graph.insert_association(Association {
+			source: 0,
+			target: 1,
+			forward_strength: 0.8,
+			backward_strength: 0.4,
+			association_type: AssociationType::Causal,
+		});
+		let _ = graph.insert_association(Association {
+			source: 1,
+			target: 2,
+			forward_strength: 0.5,
+			backward_strength: 0.5,
+			association_type: AssociationType::Temporal,
+		});
+		if let Some(meta) = graph.metadata_mut(0, 1) {
+			meta.state = AssociationState::Fresh;
+			meta.last_reinforced_ms = Some(1234.5);
+		}
+		graph.set_normalization(Some(StrengthNormalization::MinMax));
+
+		GraphSnapshot {
+			graph,
+			temporal_links: vec![TemporalLink {
+				source_position: 0,
+				target_position: 1,
+				source_memory: 1,
+				target_memory: 2,
+				forward_strength: 0.9,
+				backward_strength: 0.6,
+			}],
+			access_histories: vec![vec![0.0, 1000.0], vec![], vec![500.0]],
+		}
+	}
+
+	#[test]
+	fn test_snapshot_round_trip() {
+		let snapshot = sample();
+		let mut buffer = Vec::new();
+		assert!(snapshot.save(&mut buffer).is_ok());
+
+		let restored = GraphSnapshot::load(buffer.as_slice());
+		assert!(restored.is_ok());
+		let Ok(restored) = restored else { return };
+
+		assert_eq!(restored.graph.num_nodes(), 3);
+		assert_eq!(
+			restored.graph.normalization(),
+			Some(StrengthNormalization::MinMax)
+		);
+		assert_eq!(restored.graph.num_edges(), 2);
+		let edge = restored.graph.association(0, 1);
+		assert!(
+			edge.is_some_and(|a| a.association_type == AssociationType::Causal
+				&& (a.backward_strength - 0.4).abs() < f64::EPSILON)
+		);
+		let meta = restored.graph.metadata(0, 1).copied().unwrap_or_default();
+		assert_eq!(meta.state, AssociationState::Fresh);
+		assert_eq!(meta.last_reinforced_ms, Some(1234.5));
+		assert_eq!(restored.temporal_links.len(), 1);
+		assert_eq!(restored.access_histories, snapshot.access_histories);
+
+		// Encoding is deterministic
+		assert_eq!(restored.to_bytes(), buffer);
+	}
+
+	#[test]
+	fn test_snapshot_rejects_damage() {
+		let bytes = sample().to_bytes();
+
+		let mut corrupted = bytes.clone();
+		let last = corrupted.len() - 1;
+		corrupted[last] ^= 0xFF;
+		assert!(matches!(
+			GraphSnapshot::from_bytes(&corrupted),
+			Err(SnapshotError::ChecksumMismatch { .. })
+		));
+
+		assert!(matches!(
+			GraphSnapshot::from_bytes(&bytes[..bytes.len() - 3]),
+			Err(SnapshotError::Truncated)
+		));
+
+		let mut future = bytes;
+		future[4] = 99;
+		assert!(matches!(
+			GraphSnapshot::from_bytes(&future),
+			Err(SnapshotError::UnsupportedVersion(99))
+		));
+
+		assert!(matches!(
+			GraphSnapshot::from_bytes(b"nope"),
+			Err(SnapshotError::BadMagic)
+		));
+	}
+
+	/// Re-wrap `bytes` with its node count replaced by `num_nodes`.
+	fn with_num_nodes(bytes: &[u8], num_nodes: usize) -> Vec<u8> {
+		let mut payload = bytes[24..].to_vec();
+		payload[..8].copy_from_slice(&(num_nodes as u64).to_le_bytes());
+		let mut out = bytes[..16].to_vec();
+		out.extend_from_slice(&fnv1a(&payload).to_le_bytes());
+		out.extend_from_slice(&payload);
+		out
+	}
+
+	#[test]
+	fn test_snapshot_rejects_bad_node_count() {
+		let bytes = sample().to_bytes();
+		assert!(GraphSnapshot::from_bytes(&with_num_nodes(&bytes, 3)).is_ok());
+		assert!(matches!(
+			GraphSnapshot::from_bytes(&with_num_nodes(&bytes, MemoryGraph::MAX_NODES + 1)),
+			Err(SnapshotError::Invalid("node count"))
+		));
+		// The sample's associations reach node 2
+		assert!(matches!(
+			GraphSnapshot::from_bytes(&with_num_nodes(&bytes, 2)),
+			Err(SnapshotError::Invalid("association"))
+		));
+	}
+
+	#[test]
+	fn test_snapshot_reads_version_1() {
+		let bytes = sample().to_bytes();
+		// Version 1 payloads lack the normalization byte after the node count
+		let mut payload = bytes[24..].to_vec();
+		let _ = payload.remove(8);
+		let mut v1 = Vec::new();
+		v1.extend_from_slice(&MAGIC);
+		v1.extend_from_slice(&1u16.to_le_bytes());
+		v1.extend_from_slice(&0u16.to_le_bytes());
+		v1.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+		v1.extend_from_slice(&fnv1a(&payload).to_le_bytes());
+		v1.extend_from_slice(&payload);
+
+		let restored = GraphSnapshot::from_bytes(&v1);
+		assert!(
+			restored.is_ok_and(|r| r.graph.num_edges() == 2 && r.graph.normalization().is_none())
+		);
+	}
+}
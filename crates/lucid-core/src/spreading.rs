@@ -11,12 +11,85 @@
 //! - `W_i` = source strength of node i
 //! - `n_i` = fan (number of outgoing connections from i)
 //! - `S_ij` = associative strength between i and j
+//!
+//! [`FanNormalization`] offers gentler alternatives to dividing by `n_i`
+//! for graphs with heavily connected hubs.
+//!
+//! Results are deterministic: the same graph, seeds, and configuration
+//! give bit-identical activations on every run and platform. Neighbors
+//! are visited in association input order, frontiers in first-encounter
+//! order, and per-level updates are applied in node order. Wherever
+//! nodes are ranked, equal activations are ordered by lower node index.
 
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+use crate::attributes::TraversalFilter;
+use crate::error::{Classify, ConfigIssue, ErrorKind};
+use crate::graph::{AssociationGraph, Neighbors};
+use crate::paths::ActivationPath;
+use crate::rng::SplitMix64;
+
+/// What kind of relationship an association encodes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AssociationType {
+	/// Related in meaning (the default)
+	#[default]
+	Semantic,
+	/// Experienced close together in time
+	Temporal,
+	/// One memory led to or explains the other
+	Causal,
+	/// Both memories involve the same person, place, or thing
+	Entity,
+	/// Visually similar content (frames, images)
+	VisualSimilarity,
+}
+
+impl AssociationType {
+	/// Lowercase name, as written by the stores and accepted by
+	/// [`str::parse`].
+	#[must_use]
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Semantic => "semantic",
+			Self::Temporal => "temporal",
+			Self::Causal => "causal",
+			Self::Entity => "entity",
+			Self::VisualSimilarity => "visual_similarity",
+		}
+	}
+}
+
+impl std::str::FromStr for AssociationType {
+	type Err = UnknownAssociationType;
+
+	/// Parse a name case-insensitively; `visualsimilarity` is accepted as
+	/// well as `visual_similarity`.
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		match name.to_lowercase().as_str() {
+			"semantic" => Ok(Self::Semantic),
+			"temporal" => Ok(Self::Temporal),
+			"causal" => Ok(Self::Causal),
+			"entity" => Ok(Self::Entity),
+			"visual_similarity" | "visualsimilarity" => Ok(Self::VisualSimilarity),
+			_ => Err(UnknownAssociationType(name.to_string())),
+		}
+	}
+}
 
-/// Adjacency list type for graph edges: Vec of (`target_index`, weight) pairs per node.
-type AdjacencyList = Vec<Vec<(usize, f64)>>;
+/// Error type for parsing an [`AssociationType`] name.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown association type: {0}")]
+pub struct UnknownAssociationType(pub String);
+
+impl Classify for UnknownAssociationType {
+	fn kind(&self) -> ErrorKind {
+		ErrorKind::InvalidInput
+	}
+}
 
 /// An edge in the association graph.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -29,6 +102,9 @@ pub struct Association {
 	pub forward_strength: f64,
 	/// Backward strength (target → source)
 	pub backward_strength: f64,
+	/// Kind of relationship
+	#[serde(default)]
+	pub association_type: AssociationType,
 }
 
 /// Result of spreading activation.
@@ -38,19 +114,254 @@ pub struct SpreadingResult {
 	pub activations: Vec<f64>,
 	/// Which nodes were visited at each depth
 	pub visited_by_depth: Vec<Vec<usize>>,
+	/// Activation received by each node, by sender and edge
+	/// (only when `SpreadingConfig::trace_provenance` is set)
+	#[serde(default)]
+	pub provenance: Option<HashMap<usize, Vec<ActivationContribution>>>,
+	/// Activation each node received at each level; `depth_deltas[d - 1]`
+	/// holds level `d` (only when `SpreadingConfig::record_depth_deltas` is set)
+	#[serde(default)]
+	pub depth_deltas: Option<Vec<HashMap<usize, f64>>>,
+}
+
+impl SpreadingResult {
+	/// Spread contributions received by `node`, strongest first; ties
+	/// are ordered by depth, then sender, then edge.
+	///
+	/// Empty if provenance was not traced or the node received nothing.
+	#[must_use]
+	pub fn contributions(&self, node: usize) -> Vec<ActivationContribution> {
+		let mut contributions = self
+			.provenance
+			.as_ref()
+			.and_then(|provenance| provenance.get(&node))
+			.cloned()
+			.unwrap_or_default();
+		contributions.sort_by(|a, b| {
+			b.amount
+				.total_cmp(&a.amount)
+				.then(a.depth.cmp(&b.depth))
+				.then(a.source.cmp(&b.source))
+				.then(a.edge.cmp(&b.edge))
+		});
+		contributions
+	}
+
+	/// Activation `node` received at each level, starting at level 1.
+	///
+	/// Empty if depth deltas were not recorded.
+	#[must_use]
+	pub fn depth_profile(&self, node: usize) -> Vec<f64> {
+		self.depth_deltas.as_ref().map_or_else(Vec::new, |levels| {
+			levels
+				.iter()
+				.map(|level| level.get(&node).copied().unwrap_or(0.0))
+				.collect()
+		})
+	}
+}
+
+/// Activation one node passed to another over one edge.
+///
+/// Recorded by provenance tracing to explain *why* a memory was recalled.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActivationContribution {
+	/// Node the activation came from
+	pub source: usize,
+	/// Edge id in the [`AssociationGraph`]
+	pub edge: usize,
+	/// Whether activation flowed against the association's direction
+	pub backward: bool,
+	/// Activation transferred
+	pub amount: f64,
+	/// Spreading level (1 = sent by a seed)
+	pub depth: usize,
+}
+
+/// Spreading multipliers for one [`AssociationType`].
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TypeSpreadingParams {
+	/// Multiplier on edge strength
+	pub weight: f64,
+	/// Multiplier on `decay_per_hop` (above 1.0 carries activation farther)
+	pub decay_multiplier: f64,
+}
+
+impl Default for TypeSpreadingParams {
+	fn default() -> Self {
+		Self {
+			weight: 1.0,
+			decay_multiplier: 1.0,
+		}
+	}
+}
+
+/// Per-[`AssociationType`] spreading parameters.
+///
+/// Defaults to neutral multipliers, so untyped graphs spread exactly as before.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TypeSpreadingConfig {
+	/// Semantic edges
+	pub semantic: TypeSpreadingParams,
+	/// Temporal edges
+	pub temporal: TypeSpreadingParams,
+	/// Causal edges
+	pub causal: TypeSpreadingParams,
+	/// Entity edges
+	pub entity: TypeSpreadingParams,
+	/// Visual-similarity edges
+	pub visual_similarity: TypeSpreadingParams,
+}
+
+impl TypeSpreadingConfig {
+	/// Parameters for an association type.
+	#[inline]
+	#[must_use]
+	pub const fn get(&self, association_type: AssociationType) -> &TypeSpreadingParams {
+		match association_type {
+			AssociationType::Semantic => &self.semantic,
+			AssociationType::Temporal => &self.temporal,
+			AssociationType::Causal => &self.causal,
+			AssociationType::Entity => &self.entity,
+			AssociationType::VisualSimilarity => &self.visual_similarity,
+		}
+	}
+}
+
+/// Post-spreading competition between activated memories.
+///
+/// Lateral inhibition sharpens a long tail of similar activations into a
+/// decisive set of winners.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Competition {
+	/// No competition (default)
+	#[default]
+	None,
+	/// Redistribute the total activation by `softmax(A / temperature)`.
+	///
+	/// Lower temperatures concentrate activation on the strongest nodes;
+	/// a temperature ≤ 0 gives everything to the single strongest node.
+	Softmax {
+		/// Softmax temperature
+		temperature: f64,
+	},
+	/// k-winners-take-all: the `k` strongest nodes keep their activation,
+	/// the rest are scaled by `1 - inhibition`.
+	KWinnersTakeAll {
+		/// Number of winners
+		k: usize,
+		/// Fraction of activation removed from losers (0-1; 1 = hard WTA)
+		inhibition: f64,
+	},
+}
+
+/// How a node's activation is divided among its links.
+///
+/// ACT-R's `W_i / n_i` splits activation evenly by fan, which leaves a
+/// hub with hundreds of links passing almost nothing along any of them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FanNormalization {
+	/// Divide by the fan, `W_i / n_i` (default)
+	#[default]
+	Count,
+	/// Divide by the fan, but by no more than `cap`: `W_i / min(n_i, cap)`
+	Capped {
+		/// Largest divisor applied
+		cap: usize,
+	},
+	/// Divide by `1 + ln(n_i)`, so doubling the fan costs a constant amount
+	Log,
+	/// Divide by the sum of outgoing strengths, `W_i / Σ_k S_ik`, so a
+	/// node passes on exactly `W_i` before decay and weights
+	Strength,
+}
+
+/// Which nodes activation may spread into, by tag.
+///
+/// Seeds are never filtered. An empty filter admits every node.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagFilter {
+	/// Admit only nodes carrying at least one of these tags (empty = any)
+	pub any_of: Vec<String>,
+	/// Keep out nodes carrying any of these tags
+	pub none_of: Vec<String>,
+}
+
+impl TagFilter {
+	/// Whether the filter admits every node.
+	#[inline]
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.any_of.is_empty() && self.none_of.is_empty()
+	}
+
+	/// Whether a node with `tags` is admitted.
+	#[must_use]
+	pub fn allows(&self, tags: &[String]) -> bool {
+		(self.any_of.is_empty() || tags.iter().any(|t| self.any_of.contains(t)))
+			&& !tags.iter().any(|t| self.none_of.contains(t))
+	}
+}
+
+/// How a node below its retrieval threshold takes part in spreading.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BelowThreshold {
+	/// Keep the activation it receives but pass none on (default)
+	#[default]
+	Hold,
+	/// Spread as any other node would
+	Propagate,
 }
 
 /// Configuration for spreading activation.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SpreadingConfig {
-	/// How much activation decays per hop (0-1)
+	/// How much activation decays per hop (0-1); edges with their own
+	/// factor (see [`AssociationGraph::with_edge_decays`]) use that instead
 	pub decay_per_hop: f64,
 	/// Minimum activation to propagate
 	pub minimum_activation: f64,
-	/// Maximum nodes to visit
+	/// Maximum nodes to visit; when a level would exceed it, the new
+	/// nodes that received the most activation are kept
 	pub max_nodes: usize,
 	/// Whether to spread bidirectionally
 	pub bidirectional: bool,
+	/// Spread until a level adds less than this much total activation.
+	///
+	/// When set, the `depth` argument is ignored and spreading continues
+	/// until convergence or `max_iterations` levels, whichever comes first.
+	pub convergence_epsilon: Option<f64>,
+	/// Hard cap on levels when spreading to convergence
+	pub max_iterations: usize,
+	/// Per-type weight and decay multipliers
+	pub type_params: TypeSpreadingConfig,
+	/// Logistic activation noise scale (ACT-R `s`); 0.0 disables noise.
+	///
+	/// Noise is added once to every visited node after spreading.
+	pub activation_noise: f64,
+	/// Seed for activation noise; the same seed yields the same noise
+	pub noise_seed: u64,
+	/// Lateral inhibition among visited nodes, applied after noise
+	pub competition: Competition,
+	/// Record every transfer in [`SpreadingResult::provenance`].
+	///
+	/// Amounts are raw spread, before noise and competition. Tracing keeps
+	/// frontier expansion sequential.
+	pub trace_provenance: bool,
+	/// Record per-level activation deltas in [`SpreadingResult::depth_deltas`]
+	pub record_depth_deltas: bool,
+	/// Restrict spreading to nodes with matching tags
+	/// (see [`AssociationGraph::with_node_tags`])
+	pub tag_filter: TagFilter,
+	/// Multiplier on activation entering nodes with each tag; a node with
+	/// several weighted tags gets their product
+	pub tag_weights: HashMap<String, f64>,
+	/// What nodes below their retrieval threshold (see
+	/// [`AssociationGraph::with_node_thresholds`]) do with the activation
+	/// they receive
+	pub below_threshold: BelowThreshold,
+	/// How a node's activation is divided among its links (default: `Count`)
+	pub fan_normalization: FanNormalization,
 }
 
 impl Default for SpreadingConfig {
@@ -60,26 +371,210 @@ impl Default for SpreadingConfig {
 			minimum_activation: 0.01,
 			max_nodes: 1000,
 			bidirectional: true,
+			convergence_epsilon: None,
+			max_iterations: 100,
+			type_params: TypeSpreadingConfig::default(),
+			activation_noise: 0.0,
+			noise_seed: 0,
+			competition: Competition::None,
+			trace_provenance: false,
+			record_depth_deltas: false,
+			tag_filter: TagFilter::default(),
+			tag_weights: HashMap::new(),
+			below_threshold: BelowThreshold::Hold,
+			fan_normalization: FanNormalization::Count,
 		}
 	}
 }
 
-/// Build adjacency lists from associations.
-fn build_adjacency(
-	associations: &[Association],
-	num_nodes: usize,
-) -> (AdjacencyList, AdjacencyList) {
-	let mut forward: Vec<Vec<(usize, f64)>> = vec![Vec::new(); num_nodes];
-	let mut backward: Vec<Vec<(usize, f64)>> = vec![Vec::new(); num_nodes];
+impl SpreadingConfig {
+	/// Recall of experienced events.
+	///
+	/// Temporal links carry 1.5× weight and decay 20% slower, entity links
+	/// 1.2×, so activation follows "what happened next" and "who was
+	/// there" ahead of topical similarity. A steeper `decay_per_hop` (0.6)
+	/// and a 500-node cap keep recall focused on the episode.
+	#[must_use]
+	pub fn episodic_recall() -> Self {
+		Self {
+			decay_per_hop: 0.6,
+			minimum_activation: 0.02,
+			max_nodes: 500,
+			type_params: TypeSpreadingConfig {
+				temporal: TypeSpreadingParams {
+					weight: 1.5,
+					decay_multiplier: 1.2,
+				},
+				entity: TypeSpreadingParams {
+					weight: 1.2,
+					decay_multiplier: 1.0,
+				},
+				..TypeSpreadingConfig::default()
+			},
+			..Self::default()
+		}
+	}
 
-	for assoc in associations {
-		if assoc.source < num_nodes && assoc.target < num_nodes {
-			forward[assoc.source].push((assoc.target, assoc.forward_strength));
-			backward[assoc.target].push((assoc.source, assoc.backward_strength));
+	/// Related concepts regardless of when they were learned.
+	///
+	/// Semantic links carry 1.2× weight while temporal links are halved
+	/// and decay faster, so co-occurrence does not masquerade as meaning.
+	/// Uses the default per-hop decay (0.7).
+	#[must_use]
+	pub fn semantic_association() -> Self {
+		Self {
+			type_params: TypeSpreadingConfig {
+				semantic: TypeSpreadingParams {
+					weight: 1.2,
+					decay_multiplier: 1.0,
+				},
+				temporal: TypeSpreadingParams {
+					weight: 0.5,
+					decay_multiplier: 0.8,
+				},
+				..TypeSpreadingConfig::default()
+			},
+			..Self::default()
 		}
 	}
 
-	(forward, backward)
+	/// Loose, wandering association ("this reminds me of...").
+	///
+	/// Slow decay (0.85 per hop), a low propagation floor (0.005) and a
+	/// 5,000-node budget let activation travel far. ACT-R noise
+	/// (`s = 0.25`) varies what surfaces; change `noise_seed` between
+	/// calls for different associations.
+	#[must_use]
+	pub fn free_association() -> Self {
+		Self {
+			decay_per_hop: 0.85,
+			minimum_activation: 0.005,
+			max_nodes: 5000,
+			activation_noise: 0.25,
+			..Self::default()
+		}
+	}
+
+	/// Every out-of-range or inconsistent field, in declaration order.
+	///
+	/// Spreading itself never rejects a config, so a `NaN` decay or a
+	/// zero node budget would otherwise show up only as empty or
+	/// meaningless activations.
+	#[must_use]
+	pub fn validate(&self) -> Vec<ConfigIssue> {
+		let mut issues = Vec::new();
+
+		if !(self.decay_per_hop > 0.0 && self.decay_per_hop <= 1.0) {
+			issues.push(ConfigIssue::new(
+				"decay_per_hop",
+				format!("must be in (0, 1], got {}", self.decay_per_hop),
+			));
+		}
+		if !(self.minimum_activation.is_finite() && self.minimum_activation >= 0.0) {
+			issues.push(ConfigIssue::new(
+				"minimum_activation",
+				format!(
+					"must be finite and non-negative, got {}",
+					self.minimum_activation
+				),
+			));
+		}
+		if self.max_nodes == 0 {
+			issues.push(ConfigIssue::new(
+				"max_nodes",
+				"must be at least 1; no node could be visited",
+			));
+		}
+		if let Some(epsilon) = self.convergence_epsilon {
+			if !(epsilon.is_finite() && epsilon > 0.0) {
+				issues.push(ConfigIssue::new(
+					"convergence_epsilon",
+					format!("must be finite and positive, got {epsilon}"),
+				));
+			}
+			if self.max_iterations == 0 {
+				issues.push(ConfigIssue::new(
+					"max_iterations",
+					"must be at least 1 when convergence_epsilon is set",
+				));
+			}
+		}
+		for (name, params) in [
+			("semantic", &self.type_params.semantic),
+			("temporal", &self.type_params.temporal),
+			("causal", &self.type_params.causal),
+			("entity", &self.type_params.entity),
+			("visual_similarity", &self.type_params.visual_similarity),
+		] {
+			for (field, value) in [
+				("weight", params.weight),
+				("decay_multiplier", params.decay_multiplier),
+			] {
+				if !(value.is_finite() && value >= 0.0) {
+					issues.push(
+						ConfigIssue::new(
+							field,
+							format!("must be finite and non-negative, got {value}"),
+						)
+						.within(name)
+						.within("type_params"),
+					);
+				}
+			}
+		}
+		if !(self.activation_noise.is_finite() && self.activation_noise >= 0.0) {
+			issues.push(ConfigIssue::new(
+				"activation_noise",
+				format!(
+					"must be finite and non-negative, got {}",
+					self.activation_noise
+				),
+			));
+		}
+		match self.competition {
+			Competition::None => {}
+			Competition::Softmax { temperature } => {
+				if temperature.is_nan() {
+					issues.push(ConfigIssue::new(
+						"competition.temperature",
+						"must not be NaN",
+					));
+				}
+			}
+			Competition::KWinnersTakeAll { k, inhibition } => {
+				if k == 0 {
+					issues.push(ConfigIssue::new(
+						"competition.k",
+						"must be at least 1; every node would be inhibited",
+					));
+				}
+				if !(0.0..=1.0).contains(&inhibition) {
+					issues.push(ConfigIssue::new(
+						"competition.inhibition",
+						format!("must be in [0, 1], got {inhibition}"),
+					));
+				}
+			}
+		}
+		let mut tags: Vec<_> = self.tag_weights.iter().collect();
+		tags.sort_by(|a, b| a.0.cmp(b.0));
+		for (tag, &weight) in tags {
+			if !(weight.is_finite() && weight >= 0.0) {
+				issues.push(ConfigIssue::new(
+					format!("tag_weights[{tag:?}]"),
+					format!("must be finite and non-negative, got {weight}"),
+				));
+			}
+		}
+		if self.fan_normalization == (FanNormalization::Capped { cap: 0 }) {
+			issues.push(ConfigIssue::new(
+				"fan_normalization.cap",
+				"must be at least 1",
+			));
+		}
+
+		issues
+	}
 }
 
 /// Perform spreading activation through the association graph.
@@ -91,10 +586,11 @@ fn build_adjacency(
 ///
 /// * `num_nodes` - Total number of nodes in the graph
 /// * `associations` - Edges with forward/backward strengths
-/// * `seed_indices` - Starting nodes
+/// * `seed_indices` - Starting nodes; indices past `num_nodes` are ignored
 /// * `seed_activations` - Initial activation values for seeds
 /// * `config` - Spreading configuration
-/// * `depth` - Maximum spreading depth
+/// * `depth` - Maximum spreading depth (ignored when
+///   `config.convergence_epsilon` is set)
 ///
 /// # Returns
 ///
@@ -108,7 +604,50 @@ pub fn spread_activation(
 	config: &SpreadingConfig,
 	depth: usize,
 ) -> SpreadingResult {
-	let (forward_adj, backward_adj) = build_adjacency(associations, num_nodes);
+	let graph = AssociationGraph::new(num_nodes, associations);
+	spread_activation_graph(&graph, seed_indices, seed_activations, config, depth)
+}
+
+/// Perform spreading activation over a prebuilt [`AssociationGraph`].
+///
+/// Identical to [`spread_activation`], but skips the adjacency build so
+/// the same index can serve many queries.
+#[must_use]
+pub fn spread_activation_graph(
+	graph: &AssociationGraph,
+	seed_indices: &[usize],
+	seed_activations: &[f64],
+	config: &SpreadingConfig,
+	depth: usize,
+) -> SpreadingResult {
+	spread_activation_filtered(
+		graph,
+		seed_indices,
+		seed_activations,
+		config,
+		depth,
+		&TraversalFilter::default(),
+	)
+}
+
+/// Spread activation only through nodes and edges `filter` admits.
+///
+/// Predicates are evaluated as edges are expanded. Fans count admitted
+/// edges only, so the result equals spreading over the filtered
+/// subgraph. Seeds are never filtered, but seeds past the end of the
+/// graph are dropped.
+#[must_use]
+pub fn spread_activation_filtered(
+	graph: &AssociationGraph,
+	seed_indices: &[usize],
+	seed_activations: &[f64],
+	config: &SpreadingConfig,
+	depth: usize,
+	filter: &TraversalFilter<'_>,
+) -> SpreadingResult {
+	#[cfg(feature = "metrics")]
+	let started = std::time::Instant::now();
+	let num_nodes = graph.num_nodes();
 
 	// Initialize activations
 	let mut activations = vec![0.0; num_nodes];
@@ -118,155 +657,1148 @@ pub fn spread_activation(
 		}
 	}
 
-	let mut visited: HashSet<usize> = seed_indices.iter().copied().collect();
-	let mut visited_by_depth: Vec<Vec<usize>> = vec![seed_indices.to_vec()];
-	let mut frontier: Vec<usize> = seed_indices.to_vec();
+	let seeds: Vec<usize> = seed_indices
+		.iter()
+		.copied()
+		.filter(|&idx| idx < num_nodes)
+		.collect();
+	let mut visited: HashSet<usize> = seeds.iter().copied().collect();
+	let mut visited_by_depth: Vec<Vec<usize>> = vec![seeds.clone()];
+	let mut frontier: Vec<usize> = seeds;
 	let mut total_visited = frontier.len();
 
+	let max_levels = if config.convergence_epsilon.is_some() {
+		config.max_iterations
+	} else {
+		depth
+	};
+	let mut provenance = config.trace_provenance.then(HashMap::new);
+	let mut depth_deltas = config.record_depth_deltas.then(Vec::new);
+	let spread = Spread {
+		graph,
+		config,
+		filter: *filter,
+	};
+
 	// Spread for each depth level
-	for _ in 0..depth {
+	for level in 1..=max_levels {
 		if total_visited >= config.max_nodes {
 			break;
 		}
 
-		let mut next_frontier: Vec<usize> = Vec::new();
-		let mut next_activations: HashMap<usize, f64> = HashMap::new();
+		let (next_activations, next_frontier) = expand_frontier(
+			spread,
+			&activations,
+			&frontier,
+			&mut visited,
+			&mut total_visited,
+			provenance.as_mut().map(|provenance| (provenance, level)),
+		);
 
-		for &source_idx in &frontier {
-			let source_activation = activations[source_idx];
-			if source_activation < config.minimum_activation {
-				continue;
+		// Update activations BEFORE checking frontier
+		// This ensures spread is applied even when targets are already seeds
+		// Node order keeps the convergence sum independent of hash order
+		let mut updates: Vec<(usize, f64)> = next_activations
+			.iter()
+			.map(|(&idx, &activation)| (idx, activation))
+			.collect();
+		updates.sort_unstable_by_key(|&(idx, _)| idx);
+		let mut total_change = 0.0;
+		for (idx, activation) in updates {
+			activations[idx] += activation;
+			total_change += activation.abs();
+		}
+		if let Some(levels) = depth_deltas.as_mut() {
+			levels.push(next_activations);
+		}
+
+		if next_frontier.is_empty() {
+			break;
+		}
+
+		visited_by_depth.push(next_frontier.clone());
+		frontier = next_frontier;
+
+		if config
+			.convergence_epsilon
+			.is_some_and(|epsilon| total_change < epsilon)
+		{
+			break;
+		}
+	}
+
+	if config.activation_noise > 0.0 {
+		let mut rng = SplitMix64::new(config.noise_seed);
+		for &idx in visited_by_depth.iter().flatten() {
+			if idx < num_nodes {
+				activations[idx] += rng.logistic(config.activation_noise);
 			}
+		}
+	}
+
+	if config.competition != Competition::None {
+		let candidates: Vec<usize> = visited_by_depth.iter().flatten().copied().collect();
+		apply_competition(&mut activations, &candidates, &config.competition);
+	}
 
-			// Forward spreading
-			let forward_edges = &forward_adj[source_idx];
-			#[allow(clippy::cast_precision_loss)]
-			let fan = forward_edges.len().max(1) as f64;
+	#[cfg(feature = "metrics")]
+	crate::metrics::record_spreading_query(started.elapsed());
 
-			for &(target_idx, strength) in forward_edges {
-				if total_visited >= config.max_nodes {
-					break;
+	SpreadingResult {
+		activations,
+		visited_by_depth,
+		provenance,
+		depth_deltas,
+	}
+}
+
+/// Provenance being recorded, with the current spreading level.
+type Trace<'a> = (&'a mut HashMap<usize, Vec<ActivationContribution>>, usize);
+
+/// One seed set for [`spread_activation_batch`].
+#[derive(Clone, Copy, Debug)]
+pub struct SpreadingQuery<'a> {
+	/// Starting nodes
+	pub seed_indices: &'a [usize],
+	/// Initial activation for each seed (missing entries default to 1.0)
+	pub seed_activations: &'a [f64],
+}
+
+/// Run many seed sets against one association graph.
+///
+/// Builds the adjacency once and shares it across queries, which is the
+/// common case when scoring a page of search queries at once.
+///
+/// # Returns
+///
+/// One [`SpreadingResult`] per query, in query order.
+#[must_use]
+pub fn spread_activation_batch(
+	num_nodes: usize,
+	associations: &[Association],
+	queries: &[SpreadingQuery<'_>],
+	config: &SpreadingConfig,
+	depth: usize,
+) -> Vec<SpreadingResult> {
+	let graph = AssociationGraph::new(num_nodes, associations);
+	spread_activation_batch_graph(&graph, queries, config, depth)
+}
+
+/// Run many seed sets over a prebuilt [`AssociationGraph`].
+///
+/// With the `parallel` feature, queries run concurrently; results are
+/// identical to calling [`spread_activation_graph`] per query.
+#[must_use]
+pub fn spread_activation_batch_graph(
+	graph: &AssociationGraph,
+	queries: &[SpreadingQuery<'_>],
+	config: &SpreadingConfig,
+	depth: usize,
+) -> Vec<SpreadingResult> {
+	let run = |query: &SpreadingQuery<'_>| {
+		spread_activation_graph(
+			graph,
+			query.seed_indices,
+			query.seed_activations,
+			config,
+			depth,
+		)
+	};
+
+	#[cfg(feature = "parallel")]
+	{
+		use rayon::prelude::*;
+		queries.par_iter().map(run).collect()
+	}
+
+	#[cfg(not(feature = "parallel"))]
+	{
+		queries.iter().map(run).collect()
+	}
+}
+
+/// How content and context activation combine in [`spread_joint_cues`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum CueCombination {
+	/// `A = A_content × A_context` (default): only memories reached by
+	/// both cue sets survive, as in encoding specificity
+	#[default]
+	Multiplicative,
+	/// `A = w_content × A_content + w_context × A_context`: either cue set
+	/// alone can surface a memory
+	Additive {
+		/// Weight on content activation
+		content_weight: f64,
+		/// Weight on context activation
+		context_weight: f64,
+	},
+}
+
+/// Configuration for [`spread_joint_cues`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct JointCueConfig {
+	/// Spreading from content cues (semantic matches)
+	pub content: SpreadingConfig,
+	/// Spreading from context cues (current episode, app, time of day)
+	pub context: SpreadingConfig,
+	/// How the two activations combine (default: multiplicative)
+	pub combination: CueCombination,
+}
+
+/// Result of [`spread_joint_cues`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JointCueResult {
+	/// Combined activation per node
+	pub activations: Vec<f64>,
+	/// `(node, activation)` for every positively activated node,
+	/// strongest first; ties keep the lower index first
+	pub ranked: Vec<(usize, f64)>,
+	/// Spreading from the content cues alone
+	pub content: SpreadingResult,
+	/// Spreading from the context cues alone
+	pub context: SpreadingResult,
+}
+
+/// Spread content and context cues separately and combine them.
+///
+/// Each cue set spreads under its own config, so context cues can, for
+/// instance, favor temporal links while content cues follow semantic
+/// ones. Seeds keep their own activation in their set's result, so a
+/// memory that is itself a cue in one set still needs support from the
+/// other under multiplicative combination.
+///
+/// # Arguments
+///
+/// * `graph` - Association graph
+/// * `content` - Content cue seeds
+/// * `context` - Context cue seeds
+/// * `config` - Per-set spreading configs and combination policy
+/// * `depth` - Spreading depth for both sets
+#[must_use]
+pub fn spread_joint_cues(
+	graph: &AssociationGraph,
+	content: SpreadingQuery<'_>,
+	context: SpreadingQuery<'_>,
+	config: &JointCueConfig,
+	depth: usize,
+) -> JointCueResult {
+	let content = spread_activation_graph(
+		graph,
+		content.seed_indices,
+		content.seed_activations,
+		&config.content,
+		depth,
+	);
+	let context = spread_activation_graph(
+		graph,
+		context.seed_indices,
+		context.seed_activations,
+		&config.context,
+		depth,
+	);
+
+	let activations: Vec<f64> = content
+		.activations
+		.iter()
+		.zip(&context.activations)
+		.map(|(&a, &b)| match config.combination {
+			CueCombination::Multiplicative => a * b,
+			CueCombination::Additive {
+				content_weight,
+				context_weight,
+			} => content_weight.mul_add(a, context_weight * b),
+		})
+		.collect();
+	let ranked = get_top_activated_with_scores(&activations, activations.len());
+
+	JointCueResult {
+		activations,
+		ranked,
+		content,
+		context,
+	}
+}
+
+/// Configuration for [`spread_activation_sampled`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MonteCarloConfig {
+	/// Edges drawn per node with more outgoing links than this; smaller
+	/// nodes spread exactly (default: 32)
+	pub samples_per_node: usize,
+	/// Independent runs averaged, which also yield the error estimate
+	/// (default: 8)
+	pub num_runs: usize,
+	/// Random seed; the same seed yields the same estimate (default: 0)
+	pub seed: u64,
+}
+
+impl Default for MonteCarloConfig {
+	fn default() -> Self {
+		Self {
+			samples_per_node: 32,
+			num_runs: 8,
+			seed: 0,
+		}
+	}
+}
+
+/// Result of [`spread_activation_sampled`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MonteCarloResult {
+	/// Mean activation per node over the runs
+	pub activations: Vec<f64>,
+	/// Standard error of each mean (0 with fewer than two runs)
+	pub std_errors: Vec<f64>,
+	/// Nodes visited in any run, ascending
+	pub visited: Vec<usize>,
+	/// Runs averaged
+	pub runs: usize,
+}
+
+/// Estimate spreading activation by sampling edges.
+///
+/// A node with more than `samples_per_node` outgoing links sends its
+/// activation down `K` edges drawn with replacement, each edge chosen in
+/// proportion to the activation it would carry. Each draw carries `1/K`
+/// of the node's total outflow, so every run is an unbiased estimate of
+/// one exact hop, while the frontier grows by at most `K` nodes per
+/// source instead of the full fan-out. Averaging `num_runs` runs gives
+/// the estimate and its standard error.
+///
+/// Follows `config` as [`spread_activation_graph`] does, except that
+/// noise, competition, provenance and depth deltas are not applied.
+#[must_use]
+pub fn spread_activation_sampled(
+	graph: &AssociationGraph,
+	seed_indices: &[usize],
+	seed_activations: &[f64],
+	config: &SpreadingConfig,
+	sampling: &MonteCarloConfig,
+	depth: usize,
+) -> MonteCarloResult {
+	let num_nodes = graph.num_nodes();
+	let spread = Spread {
+		graph,
+		config,
+		filter: TraversalFilter::default(),
+	};
+	let max_levels = if config.convergence_epsilon.is_some() {
+		config.max_iterations
+	} else {
+		depth
+	};
+	let mut rng = SplitMix64::new(sampling.seed);
+	let mut sums = vec![0.0; num_nodes];
+	let mut squares = vec![0.0; num_nodes];
+	let mut ever_visited: HashSet<usize> = HashSet::new();
+
+	for _ in 0..sampling.num_runs {
+		let mut activations = vec![0.0; num_nodes];
+		for (i, &idx) in seed_indices.iter().enumerate() {
+			if idx < num_nodes {
+				activations[idx] = seed_activations.get(i).copied().unwrap_or(1.0);
+			}
+		}
+		let mut frontier: Vec<usize> = seed_indices
+			.iter()
+			.copied()
+			.filter(|&idx| idx < num_nodes)
+			.collect();
+		let mut visited: HashSet<usize> = frontier.iter().copied().collect();
+		let mut total_visited = frontier.len();
+
+		for _ in 1..=max_levels {
+			if total_visited >= config.max_nodes {
+				break;
+			}
+			let mut next_activations = HashMap::new();
+			let mut touched = Vec::new();
+			for &source in &frontier {
+				let source_activation = activations[source];
+				if !propagates(spread, source, source_activation) {
+					continue;
+				}
+				for (target, amount) in sample_transfers(
+					spread,
+					source,
+					source_activation,
+					sampling.samples_per_node,
+					&mut rng,
+				) {
+					accumulate(&mut next_activations, &mut touched, target, amount);
+				}
+			}
+			let next_frontier = admit_targets(
+				&mut next_activations,
+				&touched,
+				&mut visited,
+				&mut total_visited,
+				config.max_nodes,
+			);
+
+			let mut updates: Vec<(usize, f64)> = next_activations.into_iter().collect();
+			updates.sort_unstable_by_key(|&(idx, _)| idx);
+			let mut total_change = 0.0;
+			for (idx, activation) in updates {
+				activations[idx] += activation;
+				total_change += activation.abs();
+			}
+
+			if next_frontier.is_empty() {
+				break;
+			}
+			frontier = next_frontier;
+			if config
+				.convergence_epsilon
+				.is_some_and(|epsilon| total_change < epsilon)
+			{
+				break;
+			}
+		}
+
+		for (i, &activation) in activations.iter().enumerate() {
+			sums[i] += activation;
+			squares[i] += activation * activation;
+		}
+		ever_visited.extend(visited);
+	}
+
+	#[allow(clippy::cast_precision_loss)]
+	let runs = sampling.num_runs as f64;
+	let activations: Vec<f64> = sums
+		.iter()
+		.map(|&sum| if runs > 0.0 { sum / runs } else { 0.0 })
+		.collect();
+	let std_errors = squares
+		.iter()
+		.zip(&activations)
+		.map(|(&square, &mean)| {
+			if sampling.num_runs < 2 {
+				return 0.0;
+			}
+			// Sample variance of the runs, then of their mean
+			let variance = mean.mul_add(-mean * runs, square).max(0.0) / (runs - 1.0);
+			(variance / runs).sqrt()
+		})
+		.collect();
+	let mut visited: Vec<usize> = ever_visited
+		.into_iter()
+		.filter(|&node| node < num_nodes)
+		.collect();
+	visited.sort_unstable();
+
+	MonteCarloResult {
+		activations,
+		std_errors,
+		visited,
+		runs: sampling.num_runs,
+	}
+}
+
+/// `(target, amount)` sent from `source` in one hop, sampled down to
+/// `samples` draws when it has more links than that.
+fn sample_transfers(
+	spread: Spread<'_>,
+	source: usize,
+	source_activation: f64,
+	samples: usize,
+	rng: &mut SplitMix64,
+) -> Vec<(usize, f64)> {
+	let transfers: Vec<Transfer> = outgoing_spread(spread, source, source_activation).collect();
+	if transfers.len() <= samples {
+		return transfers.iter().map(|t| (t.target, t.amount)).collect();
+	}
+
+	let mut cumulative = Vec::with_capacity(transfers.len());
+	let mut total = 0.0;
+	for transfer in &transfers {
+		total += transfer.amount.max(0.0);
+		cumulative.push(total);
+	}
+	if total <= 0.0 {
+		return Vec::new();
+	}
+
+	#[allow(clippy::cast_precision_loss)]
+	let share = total / samples as f64;
+	(0..samples)
+		.map(|_| {
+			let point = rng.next_f64() * total;
+			let index = cumulative
+				.partition_point(|&c| c <= point)
+				.min(transfers.len() - 1);
+			(transfers[index].target, share)
+		})
+		.collect()
+}
+
+/// Frontier size below which the parallel path is not worth its overhead.
+#[cfg(feature = "parallel")]
+const PARALLEL_FRONTIER_THRESHOLD: usize = 1024;
+
+/// Sources handed to each rayon task during parallel expansion.
+#[cfg(feature = "parallel")]
+const PARALLEL_CHUNK_SIZE: usize = 256;
+
+/// Graph, configuration, and filter shared by every hop of one run.
+#[derive(Clone, Copy, Debug)]
+struct Spread<'a> {
+	graph: &'a AssociationGraph,
+	config: &'a SpreadingConfig,
+	filter: TraversalFilter<'a>,
+}
+
+/// Activation sent over one edge in one hop.
+#[derive(Clone, Copy, Debug)]
+struct Transfer {
+	target: usize,
+	amount: f64,
+	edge: usize,
+	backward: bool,
+}
+
+/// Tag multiplier on activation entering `node`, or `None` if the tag
+/// filter keeps activation out.
+#[inline]
+fn tag_gain(graph: &AssociationGraph, node: usize, config: &SpreadingConfig) -> Option<f64> {
+	if config.tag_filter.is_empty() && config.tag_weights.is_empty() {
+		return Some(1.0);
+	}
+	let tags = graph.node_tags(node);
+	if !config.tag_filter.allows(tags) {
+		return None;
+	}
+	Some(
+		tags.iter()
+			.filter_map(|tag| config.tag_weights.get(tag))
+			.product(),
+	)
+}
+
+/// Number of edges in `edges` that `filter` admits.
+fn admitted_len(
+	graph: &AssociationGraph,
+	edges: Neighbors<'_>,
+	filter: &TraversalFilter<'_>,
+) -> usize {
+	if filter.is_empty() {
+		return edges.len();
+	}
+	edges
+		.iter_edges()
+		.filter(|&(edge, target, _)| filter.allows(graph, edge, target))
+		.count()
+}
+
+/// What `edges`' source divides its activation by under `normalization`.
+///
+/// Always at least 1, so a node with few or weak links never amplifies.
+fn fan_divisor(
+	graph: &AssociationGraph,
+	edges: Neighbors<'_>,
+	filter: &TraversalFilter<'_>,
+	normalization: FanNormalization,
+) -> f64 {
+	#[allow(clippy::cast_precision_loss)]
+	let fan = admitted_len(graph, edges, filter) as f64;
+	let divisor = match normalization {
+		FanNormalization::Count => fan,
+		#[allow(clippy::cast_precision_loss)]
+		FanNormalization::Capped { cap } => fan.min(cap as f64),
+		FanNormalization::Log if fan > 0.0 => 1.0 + fan.ln(),
+		FanNormalization::Log => 0.0,
+		FanNormalization::Strength => edges
+			.iter_edges()
+			.filter(|&(edge, target, _)| filter.allows(graph, edge, target))
+			.map(|(_, _, strength)| strength.max(0.0))
+			.sum(),
+	};
+	divisor.max(1.0)
+}
+
+/// `(target, source, coefficient)` for every transfer one hop can make:
+/// a source at activation `a` sends `a × coefficient` to the target.
+///
+/// Transfers are linear in the source activation, so this is the hop as
+/// a sparse matrix, for backends that spread by matrix-vector products.
+#[cfg(feature = "gpu")]
+pub(crate) fn transfer_coefficients(
+	graph: &AssociationGraph,
+	config: &SpreadingConfig,
+) -> Vec<(usize, usize, f64)> {
+	let spread = Spread {
+		graph,
+		config,
+		filter: TraversalFilter::default(),
+	};
+	(0..graph.num_nodes())
+		.flat_map(|source| {
+			outgoing_spread(spread, source, 1.0).map(move |t| (t.target, source, t.amount))
+		})
+		.collect()
+}
+
+/// Whether `node` at `activation` spreads onward under `config`.
+#[cfg(feature = "gpu")]
+pub(crate) fn propagates_from(
+	graph: &AssociationGraph,
+	config: &SpreadingConfig,
+	node: usize,
+	activation: f64,
+) -> bool {
+	let spread = Spread {
+		graph,
+		config,
+		filter: TraversalFilter::default(),
+	};
+	propagates(spread, node, activation)
+}
+
+/// Whether `source` at `activation` spreads onward this hop.
+fn propagates(spread: Spread<'_>, source: usize, activation: f64) -> bool {
+	activation >= spread.config.minimum_activation
+		&& (spread.config.below_threshold == BelowThreshold::Propagate
+			|| spread.graph.is_retrievable(source, activation))
+}
+
+/// Activation `source` sends to each neighbor in one hop.
+fn outgoing_spread(
+	spread: Spread<'_>,
+	source: usize,
+	source_activation: f64,
+) -> impl Iterator<Item = Transfer> + '_ {
+	let Spread {
+		graph,
+		config,
+		filter,
+	} = spread;
+	let forward_edges = graph.forward(source);
+	let fan = fan_divisor(graph, forward_edges, &filter, config.fan_normalization);
+	let source_activation = source_activation * graph.emission_gain(source);
+
+	// ACT-R spreading: A_j = Σ(W_i / n_i) × S_ij
+	let forward = forward_edges
+		.iter_edges()
+		.filter(move |&(edge, target, _)| filter.allows(graph, edge, target))
+		.filter_map(move |(edge, target, strength)| {
+			let params = config.type_params.get(graph.association_type(edge));
+			Some(Transfer {
+				target,
+				amount: (source_activation / fan)
+					* strength * params.weight
+					* graph
+						.edge_decay(edge)
+						.unwrap_or(config.decay_per_hop * params.decay_multiplier)
+					* graph.attraction_gain(target)
+					* tag_gain(graph, target, config)?,
+				edge,
+				backward: false,
+			})
+		});
+
+	// Backward spreading (if enabled), at reduced strength
+	let backward_edges = graph.backward(source);
+	let back_fan = fan_divisor(graph, backward_edges, &filter, config.fan_normalization);
+	let backward_len = if config.bidirectional {
+		backward_edges.len()
+	} else {
+		0
+	};
+	let backward = backward_edges
+		.iter_edges()
+		.take(backward_len)
+		.filter(move |&(edge, target, _)| filter.allows(graph, edge, target))
+		.filter_map(move |(edge, target, strength)| {
+			let params = config.type_params.get(graph.association_type(edge));
+			Some(Transfer {
+				target,
+				amount: (source_activation / back_fan)
+					* strength * params.weight
+					* graph
+						.edge_decay(edge)
+						.unwrap_or(config.decay_per_hop * params.decay_multiplier)
+					* graph.attraction_gain(target)
+					* tag_gain(graph, target, config)?
+					* 0.7,
+				edge,
+				backward: true,
+			})
+		});
+
+	forward.chain(backward)
+}
+
+/// Spread one depth level from `frontier`.
+///
+/// The whole level is computed before any new node is visited. If the
+/// new targets would take the visit count past `max_nodes`, the ones that
+/// received the most activation are kept (ties to the lower index) and
+/// the rest are dropped along with their activation, so the budget cuts
+/// the least important nodes rather than whichever edges came last.
+///
+/// Returns the activation received by each target and the newly
+/// visited nodes in first-encounter order.
+fn expand_frontier(
+	spread: Spread<'_>,
+	activations: &[f64],
+	frontier: &[usize],
+	visited: &mut HashSet<usize>,
+	total_visited: &mut usize,
+	trace: Option<Trace<'_>>,
+) -> (HashMap<usize, f64>, Vec<usize>) {
+	#[cfg(feature = "parallel")]
+	if trace.is_none() && frontier.len() >= PARALLEL_FRONTIER_THRESHOLD {
+		return expand_frontier_parallel(spread, activations, frontier, visited, total_visited);
+	}
+
+	expand_frontier_sequential(spread, activations, frontier, visited, total_visited, trace)
+}
+
+/// Add `amount` to `target`, recording first-encounter order.
+fn accumulate(sums: &mut HashMap<usize, f64>, order: &mut Vec<usize>, target: usize, amount: f64) {
+	match sums.entry(target) {
+		Entry::Occupied(mut entry) => *entry.get_mut() += amount,
+		Entry::Vacant(entry) => {
+			let _ = entry.insert(amount);
+			order.push(target);
+		}
+	}
+}
+
+/// Visit the unvisited nodes among `touched` within the `max_nodes`
+/// budget, strongest first when it binds; see [`expand_frontier`].
+///
+/// Returns the admitted nodes in first-encounter order. Dropped nodes are
+/// removed from `next_activations`.
+pub(crate) fn admit_targets(
+	next_activations: &mut HashMap<usize, f64>,
+	touched: &[usize],
+	visited: &mut HashSet<usize>,
+	total_visited: &mut usize,
+	max_nodes: usize,
+) -> Vec<usize> {
+	let mut fresh: Vec<usize> = touched
+		.iter()
+		.copied()
+		.filter(|target| !visited.contains(target))
+		.collect();
+
+	let budget = max_nodes.saturating_sub(*total_visited);
+	if fresh.len() > budget {
+		let received = |node: &usize| next_activations.get(node).copied().unwrap_or(0.0);
+		let mut ranked = fresh.clone();
+		ranked.sort_by(|a, b| received(b).total_cmp(&received(a)).then(a.cmp(b)));
+		let kept: HashSet<usize> = ranked[..budget].iter().copied().collect();
+		for dropped in &ranked[budget..] {
+			let _ = next_activations.remove(dropped);
+		}
+		fresh.retain(|node| kept.contains(node));
+	}
+
+	visited.extend(fresh.iter().copied());
+	*total_visited += fresh.len();
+	fresh
+}
+
+/// Sequential frontier expansion.
+fn expand_frontier_sequential(
+	spread: Spread<'_>,
+	activations: &[f64],
+	frontier: &[usize],
+	visited: &mut HashSet<usize>,
+	total_visited: &mut usize,
+	trace: Option<Trace<'_>>,
+) -> (HashMap<usize, f64>, Vec<usize>) {
+	let config = spread.config;
+	let mut next_activations: HashMap<usize, f64> = HashMap::new();
+	let mut touched = Vec::new();
+	let mut contributions: HashMap<usize, Vec<ActivationContribution>> = HashMap::new();
+	let depth = trace.as_ref().map_or(0, |&(_, depth)| depth);
+
+	for &source_idx in frontier {
+		let source_activation = activations[source_idx];
+		if !propagates(spread, source_idx, source_activation) {
+			continue;
+		}
+
+		for transfer in outgoing_spread(spread, source_idx, source_activation) {
+			accumulate(
+				&mut next_activations,
+				&mut touched,
+				transfer.target,
+				transfer.amount,
+			);
+
+			if trace.is_some() {
+				contributions
+					.entry(transfer.target)
+					.or_default()
+					.push(ActivationContribution {
+						source: source_idx,
+						edge: transfer.edge,
+						backward: transfer.backward,
+						amount: transfer.amount,
+						depth,
+					});
+			}
+		}
+	}
+
+	let next_frontier = admit_targets(
+		&mut next_activations,
+		&touched,
+		visited,
+		total_visited,
+		config.max_nodes,
+	);
+
+	if let Some((provenance, _)) = trace {
+		// Only targets that kept their activation keep their contributions
+		for target in touched {
+			if next_activations.contains_key(&target) {
+				if let Some(received) = contributions.remove(&target) {
+					provenance.entry(target).or_default().extend(received);
 				}
+			}
+		}
+	}
 
-				// ACT-R spreading: A_j = Σ(W_i / n_i) × S_ij
-				let spread_amount = (source_activation / fan) * strength * config.decay_per_hop;
+	(next_activations, next_frontier)
+}
+
+/// Parallel variant of [`expand_frontier`].
+///
+/// Sources are partitioned into chunks; each rayon task accumulates its
+/// own `next_activations` map, and the maps are merged in chunk order so
+/// the new frontier keeps the sequential first-encounter order. Sums
+/// match the sequential path up to floating-point reassociation, and are
+/// identical from run to run since chunk boundaries are fixed.
+#[cfg(feature = "parallel")]
+fn expand_frontier_parallel(
+	spread: Spread<'_>,
+	activations: &[f64],
+	frontier: &[usize],
+	visited: &mut HashSet<usize>,
+	total_visited: &mut usize,
+) -> (HashMap<usize, f64>, Vec<usize>) {
+	use rayon::prelude::*;
+
+	let config = spread.config;
+	let chunks: Vec<(HashMap<usize, f64>, Vec<usize>)> = frontier
+		.par_chunks(PARALLEL_CHUNK_SIZE)
+		.map(|chunk| {
+			let mut sums = HashMap::new();
+			let mut order = Vec::new();
+			for &source_idx in chunk {
+				let source_activation = activations[source_idx];
+				if !propagates(spread, source_idx, source_activation) {
+					continue;
+				}
+				for transfer in outgoing_spread(spread, source_idx, source_activation) {
+					accumulate(&mut sums, &mut order, transfer.target, transfer.amount);
+				}
+			}
+			(sums, order)
+		})
+		.collect();
+
+	// Merge left to right so sums do not depend on how rayon split the work
+	let mut next_activations = HashMap::new();
+	let mut touched = Vec::new();
+	for (sums, order) in chunks {
+		for target_idx in order {
+			let amount = sums.get(&target_idx).copied().unwrap_or(0.0);
+			accumulate(&mut next_activations, &mut touched, target_idx, amount);
+		}
+	}
+
+	let next_frontier = admit_targets(
+		&mut next_activations,
+		&touched,
+		visited,
+		total_visited,
+		config.max_nodes,
+	);
+	(next_activations, next_frontier)
+}
+
+/// Apply lateral inhibition among `candidates`.
+///
+/// Only candidate entries of `activations` are modified; duplicate and
+/// out-of-range indices are ignored. Ties are broken by lower index.
+pub fn apply_competition(activations: &mut [f64], candidates: &[usize], competition: &Competition) {
+	let mut nodes: Vec<usize> = candidates
+		.iter()
+		.copied()
+		.filter(|&i| i < activations.len())
+		.collect();
+	nodes.sort_unstable();
+	nodes.dedup();
+	if nodes.is_empty() {
+		return;
+	}
 
-				*next_activations.entry(target_idx).or_insert(0.0) += spread_amount;
+	// Strongest first, lower index on ties
+	nodes.sort_by(|&a, &b| activations[b].total_cmp(&activations[a]).then(a.cmp(&b)));
 
-				if visited.insert(target_idx) {
-					next_frontier.push(target_idx);
-					total_visited += 1;
+	match *competition {
+		Competition::None => {}
+		Competition::Softmax { temperature } => {
+			let total: f64 = nodes.iter().map(|&i| activations[i]).sum();
+			if temperature <= 0.0 {
+				for &i in &nodes {
+					activations[i] = 0.0;
 				}
+				activations[nodes[0]] = total;
+				return;
 			}
 
-			// Backward spreading (if enabled)
-			if config.bidirectional {
-				let backward_edges = &backward_adj[source_idx];
-				#[allow(clippy::cast_precision_loss)]
-				let back_fan = backward_edges.len().max(1) as f64;
+			let max = activations[nodes[0]];
+			let weights: Vec<f64> = nodes
+				.iter()
+				.map(|&i| ((activations[i] - max) / temperature).exp())
+				.collect();
+			let weight_sum: f64 = weights.iter().sum();
+			for (&i, weight) in nodes.iter().zip(weights) {
+				activations[i] = total * weight / weight_sum;
+			}
+		}
+		Competition::KWinnersTakeAll { k, inhibition } => {
+			let keep = 1.0 - inhibition.clamp(0.0, 1.0);
+			for &i in nodes.iter().skip(k) {
+				activations[i] *= keep;
+			}
+		}
+	}
+}
 
-				for &(target_idx, strength) in backward_edges {
-					if total_visited >= config.max_nodes {
-						break;
-					}
+/// Get top k activated nodes, strongest first; ties keep the lower
+/// index first.
+#[must_use]
+pub fn get_top_activated(activations: &[f64], top_k: usize) -> Vec<usize> {
+	get_top_activated_with_scores(activations, top_k)
+		.into_iter()
+		.map(|(i, _)| i)
+		.collect()
+}
+
+/// Heap entry ordered by activation, then by *lower* index.
+#[derive(Clone, Copy, Debug)]
+struct RankedNode {
+	activation: f64,
+	index: usize,
+}
 
-					// Reduced strength for backward spreading
-					let spread_amount =
-						(source_activation / back_fan) * strength * config.decay_per_hop * 0.7;
+impl PartialEq for RankedNode {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == std::cmp::Ordering::Equal
+	}
+}
+
+impl Eq for RankedNode {}
+
+impl PartialOrd for RankedNode {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
 
-					*next_activations.entry(target_idx).or_insert(0.0) += spread_amount;
+impl Ord for RankedNode {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		self.activation
+			.total_cmp(&other.activation)
+			.then(other.index.cmp(&self.index))
+	}
+}
 
-					if visited.insert(target_idx) {
-						next_frontier.push(target_idx);
-						total_visited += 1;
-					}
-				}
-			}
-		}
+/// Get the top k activated nodes as `(index, activation)` pairs.
+///
+/// Keeps a bounded min-heap of size `k`, so selecting from `n` nodes costs
+/// `O(n log k)` rather than a full sort. Only positive activations are
+/// returned, sorted descending; ties keep the lower index first.
+#[must_use]
+pub fn get_top_activated_with_scores(activations: &[f64], top_k: usize) -> Vec<(usize, f64)> {
+	if top_k == 0 {
+		return Vec::new();
+	}
 
-		// Update activations BEFORE checking frontier
-		// This ensures spread is applied even when targets are already seeds
-		for (idx, activation) in next_activations {
-			activations[idx] += activation;
+	let mut heap: BinaryHeap<Reverse<RankedNode>> = BinaryHeap::with_capacity(top_k + 1);
+	for (index, &activation) in activations.iter().enumerate() {
+		if activation <= 0.0 {
+			continue;
 		}
-
-		if next_frontier.is_empty() {
-			break;
+		let node = RankedNode { activation, index };
+		if heap.len() < top_k {
+			heap.push(Reverse(node));
+		} else if heap.peek().is_some_and(|Reverse(weakest)| node > *weakest) {
+			let _ = heap.pop();
+			heap.push(Reverse(node));
 		}
-
-		visited_by_depth.push(next_frontier.clone());
-		frontier = next_frontier;
 	}
 
-	SpreadingResult {
-		activations,
-		visited_by_depth,
-	}
+	// Ascending order of Reverse is descending activation
+	heap.into_sorted_vec()
+		.into_iter()
+		.map(|Reverse(node)| (node.index, node.activation))
+		.collect()
 }
 
-/// Get top k activated nodes.
+/// Like [`get_top_activated_with_scores`], but skipping nodes below their
+/// retrieval threshold (see [`AssociationGraph::with_node_thresholds`]).
 #[must_use]
-pub fn get_top_activated(activations: &[f64], top_k: usize) -> Vec<usize> {
-	let mut indexed: Vec<(usize, f64)> = activations
+pub fn get_top_retrievable(
+	graph: &AssociationGraph,
+	activations: &[f64],
+	top_k: usize,
+) -> Vec<(usize, f64)> {
+	let retrievable: Vec<f64> = activations
 		.iter()
 		.enumerate()
-		.filter(|(_, &a)| a > 0.0)
-		.map(|(i, &a)| (i, a))
+		.map(|(node, &a)| {
+			if graph.is_retrievable(node, a) {
+				a
+			} else {
+				0.0
+			}
+		})
 		.collect();
-
-	indexed.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-
-	indexed.into_iter().take(top_k).map(|(i, _)| i).collect()
+	get_top_activated_with_scores(&retrievable, top_k)
 }
 
-/// Find shortest path between two nodes using BFS.
+/// Find the path with the fewest hops between two nodes.
 #[must_use]
 pub fn find_activation_path(
 	num_nodes: usize,
 	associations: &[Association],
 	source: usize,
 	target: usize,
-) -> Vec<usize> {
-	let (forward_adj, _) = build_adjacency(associations, num_nodes);
+) -> Option<ActivationPath> {
+	let graph = AssociationGraph::new(num_nodes, associations);
+	find_activation_path_graph(&graph, source, target)
+}
+
+/// Find shortest path between two nodes over a prebuilt [`AssociationGraph`].
+///
+/// Bidirectional BFS: one search follows forward edges from `source`,
+/// the other follows backward rows from `target`, and the smaller frontier
+/// is expanded each round. With average fan `b` and path length `d`, this
+/// visits roughly `2 × b^(d/2)` nodes instead of `b^d`.
+///
+/// Where several associations link the same pair, the path records the
+/// strongest. Returns `None` if `target` is unreachable or either node is
+/// out of range.
+#[must_use]
+pub fn find_activation_path_graph(
+	graph: &AssociationGraph,
+	source: usize,
+	target: usize,
+) -> Option<ActivationPath> {
+	find_activation_path_filtered(graph, source, target, &TraversalFilter::default())
+}
 
+/// Find the shortest path using only nodes and edges `filter` admits.
+///
+/// Returns `None` if either endpoint is rejected by the node filter.
+#[must_use]
+pub fn find_activation_path_filtered(
+	graph: &AssociationGraph,
+	source: usize,
+	target: usize,
+	filter: &TraversalFilter<'_>,
+) -> Option<ActivationPath> {
+	let num_nodes = graph.num_nodes();
+	if source >= num_nodes || target >= num_nodes {
+		return None;
+	}
+	if !filter.allows_node(graph, source) || !filter.allows_node(graph, target) {
+		return None;
+	}
 	if source == target {
-		return vec![source];
-	}
-
-	let mut visited = vec![false; num_nodes];
-	let mut parent = vec![usize::MAX; num_nodes];
-	let mut queue = VecDeque::new();
-
-	visited[source] = true;
-	queue.push_back(source);
-
-	while let Some(current) = queue.pop_front() {
-		for &(neighbor, _) in &forward_adj[current] {
-			if !visited[neighbor] {
-				visited[neighbor] = true;
-				parent[neighbor] = current;
-				queue.push_back(neighbor);
-
-				if neighbor == target {
-					// Reconstruct path
-					let mut path = Vec::new();
-					let mut node = target;
-					while node != usize::MAX {
-						path.push(node);
-						node = parent[node];
+		return ActivationPath::from_nodes_filtered(graph, vec![source], filter);
+	}
+
+	// Hop distance from each end, and the neighbor one step closer to it
+	let mut forward_depth = vec![usize::MAX; num_nodes];
+	let mut backward_depth = vec![usize::MAX; num_nodes];
+	let mut forward_parent = vec![usize::MAX; num_nodes];
+	let mut backward_parent = vec![usize::MAX; num_nodes];
+	forward_depth[source] = 0;
+	backward_depth[target] = 0;
+
+	let mut forward_frontier = vec![source];
+	let mut backward_frontier = vec![target];
+
+	while !forward_frontier.is_empty() && !backward_frontier.is_empty() {
+		let expand_forward = forward_frontier.len() <= backward_frontier.len();
+		let (frontier, depth, parent, other_depth) = if expand_forward {
+			(
+				&mut forward_frontier,
+				&mut forward_depth,
+				&mut forward_parent,
+				&backward_depth,
+			)
+		} else {
+			(
+				&mut backward_frontier,
+				&mut backward_depth,
+				&mut backward_parent,
+				&forward_depth,
+			)
+		};
+
+		// Finish the whole level, then keep the meeting with the shortest total
+		let mut next = Vec::new();
+		let mut meeting: Option<(usize, usize)> = None;
+		for &node in frontier.iter() {
+			let edges = if expand_forward {
+				graph.forward(node)
+			} else {
+				graph.backward(node)
+			};
+			for (edge, neighbor, _) in edges.iter_edges() {
+				if !filter.allows(graph, edge, neighbor) {
+					continue;
+				}
+				if depth[neighbor] == usize::MAX {
+					depth[neighbor] = depth[node] + 1;
+					parent[neighbor] = node;
+					next.push(neighbor);
+				}
+				if other_depth[neighbor] != usize::MAX {
+					let total = depth[neighbor] + other_depth[neighbor];
+					if meeting.is_none_or(|(_, best)| total < best) {
+						meeting = Some((neighbor, total));
 					}
-					path.reverse();
-					return path;
 				}
 			}
 		}
+		*frontier = next;
+
+		if let Some((meet, _)) = meeting {
+			let mut path = Vec::new();
+			let mut node = meet;
+			while node != usize::MAX {
+				path.push(node);
+				node = forward_parent[node];
+			}
+			path.reverse();
+			let mut node = backward_parent[meet];
+			while node != usize::MAX {
+				path.push(node);
+				node = backward_parent[node];
+			}
+			return ActivationPath::from_nodes_filtered(graph, path, filter);
+		}
 	}
 
 	// No path found
-	Vec::new()
+	None
 }
 
 /// Compute `PageRank` for node importance.
+///
+/// Rank is split evenly over out-links regardless of strength; see
+/// [`compute_weighted_pagerank`] to split it by strength.
 #[must_use]
 pub fn compute_pagerank(
 	num_nodes: usize,
@@ -274,40 +1806,283 @@ pub fn compute_pagerank(
 	damping: f64,
 	iterations: usize,
 ) -> Vec<f64> {
-	let (forward_adj, _) = build_adjacency(associations, num_nodes);
+	let graph = AssociationGraph::new(num_nodes, associations);
+	compute_pagerank_graph(&graph, damping, iterations)
+}
+
+/// Compute `PageRank` over a prebuilt [`AssociationGraph`].
+#[must_use]
+pub fn compute_pagerank_graph(
+	graph: &AssociationGraph,
+	damping: f64,
+	iterations: usize,
+) -> Vec<f64> {
+	compute_pagerank_filtered(graph, damping, iterations, &TraversalFilter::default())
+}
+
+/// Compute `PageRank` over the nodes and edges `filter` admits.
+///
+/// Rejected nodes score 0 and take no part in teleportation or dangling
+/// redistribution, so ranks over admitted nodes sum to 1.
+#[must_use]
+pub fn compute_pagerank_filtered(
+	graph: &AssociationGraph,
+	damping: f64,
+	iterations: usize,
+	filter: &TraversalFilter<'_>,
+) -> Vec<f64> {
+	pagerank(graph, damping, iterations, filter, false)
+}
+
+/// Compute `PageRank` with rank split in proportion to forward strengths.
+///
+/// `PR(j) = (1 - d) / n + d × Σ_i PR(i) × s_ij / Σ_k s_ik`
+///
+/// Where `s_ij` is the forward strength of `i → j`. Negative strengths
+/// count as 0, and a node whose outgoing strengths sum to 0 is treated as
+/// dangling, its rank spread over every node.
+#[must_use]
+pub fn compute_weighted_pagerank(
+	num_nodes: usize,
+	associations: &[Association],
+	damping: f64,
+	iterations: usize,
+) -> Vec<f64> {
+	let graph = AssociationGraph::new(num_nodes, associations);
+	compute_weighted_pagerank_graph(&graph, damping, iterations)
+}
+
+/// Compute weighted `PageRank` over a prebuilt [`AssociationGraph`].
+#[must_use]
+pub fn compute_weighted_pagerank_graph(
+	graph: &AssociationGraph,
+	damping: f64,
+	iterations: usize,
+) -> Vec<f64> {
+	compute_weighted_pagerank_filtered(graph, damping, iterations, &TraversalFilter::default())
+}
+
+/// Compute weighted `PageRank` over the nodes and edges `filter` admits.
+///
+/// As [`compute_pagerank_filtered`], with rank split by strength.
+#[must_use]
+pub fn compute_weighted_pagerank_filtered(
+	graph: &AssociationGraph,
+	damping: f64,
+	iterations: usize,
+	filter: &TraversalFilter<'_>,
+) -> Vec<f64> {
+	pagerank(graph, damping, iterations, filter, true)
+}
+
+/// Where `PageRank` sends the rank of nodes with no outgoing links.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DanglingPolicy {
+	/// Spread it over every node (default)
+	#[default]
+	All,
+	/// Send it back to the seeds, in proportion to seed weight; the same
+	/// as `All` for a global run
+	Seeds,
+	/// Discard it, so ranks sum to less than 1
+	Drop,
+}
 
+/// Configuration for [`compute_pagerank_with_config`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PageRankConfig {
+	/// Probability of following a link rather than teleporting (default: 0.85)
+	pub damping: f64,
+	/// Iteration cap (default: 100)
+	pub max_iterations: usize,
+	/// Stop once an iteration changes ranks by less than this, in L1
+	/// norm (default: 1e-9)
+	pub tolerance: f64,
+	/// Split rank by forward strength instead of evenly (default: false)
+	pub weighted: bool,
+	/// Handling of nodes with no outgoing links (default: all)
+	pub dangling: DanglingPolicy,
+}
+
+impl Default for PageRankConfig {
+	fn default() -> Self {
+		Self {
+			damping: 0.85,
+			max_iterations: 100,
+			tolerance: 1e-9,
+			weighted: false,
+			dangling: DanglingPolicy::All,
+		}
+	}
+}
+
+/// Result of [`compute_pagerank_with_config`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PageRankResult {
+	/// Rank per node
+	pub ranks: Vec<f64>,
+	/// Iterations performed
+	pub iterations: usize,
+	/// L1 change in ranks over the last iteration
+	pub residual: f64,
+	/// Whether the residual fell below the tolerance
+	pub converged: bool,
+}
+
+/// Compute global or personalized `PageRank` until convergence.
+///
+/// `PR = (1 - d) × t + d × (PR P + dangling)`
+///
+/// Where `t` is uniform over admitted nodes for a global run, or the
+/// normalized seed weights for a personalized one. Iteration starts from
+/// `t` and stops once the L1 change falls below `config.tolerance` or
+/// after `config.max_iterations`, reporting which in the result.
+///
+/// # Arguments
+///
+/// * `graph` - Association graph
+/// * `seeds` - Personalization weights; empty for global `PageRank`.
+///   Out-of-range, rejected and non-positive entries are ignored, and a
+///   list with none left falls back to global.
+/// * `config` - Damping, stopping and dangling handling
+/// * `filter` - Nodes and edges taking part (rejected nodes score 0)
+#[must_use]
+pub fn compute_pagerank_with_config(
+	graph: &AssociationGraph,
+	seeds: &[(usize, f64)],
+	config: &PageRankConfig,
+	filter: &TraversalFilter<'_>,
+) -> PageRankResult {
+	let num_nodes = graph.num_nodes();
+	let admitted: Vec<bool> = (0..num_nodes)
+		.map(|node| filter.allows_node(graph, node))
+		.collect();
+	let num_admitted = admitted.iter().filter(|&&a| a).count();
+	if num_admitted == 0 {
+		return PageRankResult {
+			ranks: vec![0.0; num_nodes],
+			iterations: 0,
+			residual: 0.0,
+			converged: true,
+		};
+	}
+
+	let mut teleport = vec![0.0; num_nodes];
+	for &(node, weight) in seeds {
+		if node < num_nodes && admitted[node] && weight > 0.0 {
+			teleport[node] += weight;
+		}
+	}
+	let seed_total: f64 = teleport.iter().sum();
+	if seed_total > 0.0 {
+		for t in &mut teleport {
+			*t /= seed_total;
+		}
+	} else {
+		#[allow(clippy::cast_precision_loss)]
+		let share = 1.0 / num_admitted as f64;
+		for (t, _) in teleport.iter_mut().zip(&admitted).filter(|(_, &a)| a) {
+			*t = share;
+		}
+	}
+	let uniform_dangling = config.dangling == DanglingPolicy::All && seed_total > 0.0;
 	#[allow(clippy::cast_precision_loss)]
-	let num_nodes_f64 = num_nodes as f64;
-	let mut ranks = vec![1.0 / num_nodes_f64; num_nodes];
+	let num_admitted_f64 = num_admitted as f64;
+
+	let damping = config.damping;
+	let mut ranks = teleport.clone();
 	let mut new_ranks = vec![0.0; num_nodes];
+	let mut targets: Vec<(usize, f64)> = Vec::new();
+	let mut iterations = 0;
+	let mut residual = 0.0;
 
-	for _ in 0..iterations {
-		// Reset new ranks
-		for r in &mut new_ranks {
-			*r = (1.0 - damping) / num_nodes_f64;
+	while iterations < config.max_iterations {
+		for (r, &t) in new_ranks.iter_mut().zip(&teleport) {
+			*r = (1.0 - damping) * t;
 		}
 
-		// Distribute rank
-		for (i, edges) in forward_adj.iter().enumerate() {
-			if edges.is_empty() {
-				// Dangling node: distribute to all
-				let contribution = damping * ranks[i] / num_nodes_f64;
-				for r in &mut new_ranks {
-					*r += contribution;
+		let mut dangling = 0.0;
+		for (i, &rank) in ranks.iter().enumerate() {
+			if !admitted[i] {
+				continue;
+			}
+			targets.clear();
+			targets.extend(
+				graph
+					.forward(i)
+					.iter_edges()
+					.filter(|&(edge, target, _)| filter.allows(graph, edge, target))
+					.map(|(_, target, strength)| {
+						(
+							target,
+							if config.weighted {
+								strength.max(0.0)
+							} else {
+								1.0
+							},
+						)
+					}),
+			);
+			let total: f64 = targets.iter().map(|&(_, weight)| weight).sum();
+			if total > 0.0 {
+				for &(target, weight) in &targets {
+					new_ranks[target] += damping * rank * weight / total;
 				}
 			} else {
-				#[allow(clippy::cast_precision_loss)]
-				let contribution = damping * ranks[i] / edges.len() as f64;
-				for &(target, _) in edges {
-					new_ranks[target] += contribution;
+				dangling += rank;
+			}
+		}
+
+		// Redistribute dangling rank
+		if config.dangling != DanglingPolicy::Drop && dangling > 0.0 {
+			let mass = damping * dangling;
+			for (node, r) in new_ranks.iter_mut().enumerate() {
+				if uniform_dangling {
+					if admitted[node] {
+						*r += mass / num_admitted_f64;
+					}
+				} else {
+					*r += mass * teleport[node];
 				}
 			}
 		}
 
+		residual = ranks
+			.iter()
+			.zip(&new_ranks)
+			.map(|(a, b)| (a - b).abs())
+			.sum();
 		std::mem::swap(&mut ranks, &mut new_ranks);
+		iterations += 1;
+		if residual < config.tolerance {
+			break;
+		}
+	}
+
+	PageRankResult {
+		ranks,
+		iterations,
+		converged: iterations > 0 && residual < config.tolerance,
+		residual,
 	}
+}
 
-	ranks
+/// Fixed-iteration global `PageRank` with dangling rank spread over all.
+fn pagerank(
+	graph: &AssociationGraph,
+	damping: f64,
+	iterations: usize,
+	filter: &TraversalFilter<'_>,
+	weighted: bool,
+) -> Vec<f64> {
+	let config = PageRankConfig {
+		damping,
+		max_iterations: iterations,
+		tolerance: 0.0,
+		weighted,
+		dangling: DanglingPolicy::All,
+	};
+	compute_pagerank_with_config(graph, &[], &config, filter).ranks
 }
 
 // ============================================================================
@@ -346,6 +2121,57 @@ impl Default for TemporalSpreadingConfig {
 	}
 }
 
+impl TemporalSpreadingConfig {
+	/// Recall of experienced events.
+	///
+	/// Strong forward asymmetry (1.0 vs 0.5) follows the forward bias in
+	/// free recall (Kahana, 1996); slow distance decay (0.2), high context
+	/// persistence (0.85) and a 20-position window reach across a whole
+	/// episode.
+	#[must_use]
+	pub const fn episodic_recall() -> Self {
+		Self {
+			forward_strength: 1.0,
+			backward_strength: 0.5,
+			distance_decay_rate: 0.2,
+			episode_boost: 1.5,
+			context_persistence: 0.85,
+			max_temporal_distance: 20,
+		}
+	}
+
+	/// Temporal context as a weak hint when the query is about meaning.
+	///
+	/// Symmetric, weak links (0.5) that decay quickly (0.5) within a
+	/// 5-position window, and no episode boost.
+	#[must_use]
+	pub const fn semantic_association() -> Self {
+		Self {
+			forward_strength: 0.5,
+			backward_strength: 0.5,
+			distance_decay_rate: 0.5,
+			episode_boost: 1.0,
+			context_persistence: 0.5,
+			max_temporal_distance: 5,
+		}
+	}
+
+	/// Broad, undirected drift through time.
+	///
+	/// Symmetric links (0.8), slow decay (0.15) and a 30-position window.
+	#[must_use]
+	pub const fn free_association() -> Self {
+		Self {
+			forward_strength: 0.8,
+			backward_strength: 0.8,
+			distance_decay_rate: 0.15,
+			episode_boost: 1.2,
+			context_persistence: 0.6,
+			max_temporal_distance: 30,
+		}
+	}
+}
+
 /// A temporal link between two memories within an episode.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TemporalLink {
@@ -430,6 +2256,149 @@ pub fn create_episode_links(
 	links
 }
 
+/// Compute temporal link strength based on elapsed wall-clock time.
+///
+/// `strength = base × 0.5^(elapsed / half_life)`
+///
+/// A non-positive `half_life_ms` disables time decay.
+#[inline]
+#[must_use]
+pub fn compute_elapsed_link_strength(
+	base_strength: f64,
+	elapsed_ms: f64,
+	half_life_ms: f64,
+) -> f64 {
+	if half_life_ms <= 0.0 {
+		return base_strength;
+	}
+	base_strength * 0.5_f64.powf(elapsed_ms.abs() / half_life_ms)
+}
+
+/// Create temporal links for an episode, decaying by elapsed time.
+///
+/// Like `create_episode_links`, but strength falls off with the time
+/// between events rather than their position distance, so two events
+/// 5 seconds apart link more strongly than two events 2 hours apart.
+/// `max_temporal_distance` still bounds which positions are linked.
+///
+/// # Arguments
+///
+/// * `event_memory_indices` - Memory index of each event, in episode order
+/// * `timestamps_ms` - When each event happened (ms); extra entries in
+///   either slice are ignored
+/// * `half_life_ms` - Elapsed time at which link strength halves
+/// * `config` - Temporal spreading configuration
+#[must_use]
+pub fn create_episode_links_timed(
+	event_memory_indices: &[usize],
+	timestamps_ms: &[f64],
+	half_life_ms: f64,
+	config: &TemporalSpreadingConfig,
+) -> Vec<TemporalLink> {
+	let mut links = Vec::new();
+	let n = event_memory_indices.len().min(timestamps_ms.len());
+
+	for i in 0..n {
+		for j in (i + 1)..n.min(i + config.max_temporal_distance + 1) {
+			let elapsed = timestamps_ms[j] - timestamps_ms[i];
+
+			links.push(TemporalLink {
+				source_position: i,
+				target_position: j,
+				source_memory: event_memory_indices[i],
+				target_memory: event_memory_indices[j],
+				forward_strength: compute_elapsed_link_strength(
+					config.forward_strength,
+					elapsed,
+					half_life_ms,
+				),
+				backward_strength: compute_elapsed_link_strength(
+					config.backward_strength,
+					elapsed,
+					half_life_ms,
+				),
+			});
+		}
+	}
+
+	links
+}
+
+/// Create temporal links for an episode from raw event timestamps.
+///
+/// Events may arrive in any order: they are sorted by time, and each
+/// distinct timestamp becomes one position, so simultaneous events share
+/// a position. Strength decays with elapsed time as in
+/// [`create_episode_links_timed`], and `max_temporal_distance` bounds the
+/// number of positions a link may span.
+///
+/// Simultaneous events have no order, so they are linked with both
+/// strengths set to the mean of the configured forward and backward
+/// strengths. Events with a non-finite timestamp are dropped, and a memory
+/// is never linked to itself.
+///
+/// # Arguments
+///
+/// * `events` - `(memory index, timestamp ms)` for each event
+/// * `half_life_ms` - Elapsed time at which link strength halves
+/// * `config` - Temporal spreading configuration
+#[must_use]
+pub fn create_episode_links_from_timestamps(
+	events: &[(usize, f64)],
+	half_life_ms: f64,
+	config: &TemporalSpreadingConfig,
+) -> Vec<TemporalLink> {
+	let mut sorted: Vec<(usize, f64)> = events
+		.iter()
+		.copied()
+		.filter(|(_, time)| time.is_finite())
+		.collect();
+	sorted.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+	// Position advances only when time does
+	let mut positions = Vec::with_capacity(sorted.len());
+	for (i, &(_, time)) in sorted.iter().enumerate() {
+		let position = match positions.last() {
+			Some(&last) if time > sorted[i - 1].1 => last + 1,
+			Some(&last) => last,
+			None => 0,
+		};
+		positions.push(position);
+	}
+
+	let simultaneous = (config.forward_strength + config.backward_strength) / 2.0;
+	let mut links = Vec::new();
+	for i in 0..sorted.len() {
+		let (source_memory, source_time) = sorted[i];
+		for j in (i + 1)..sorted.len() {
+			if positions[j] - positions[i] > config.max_temporal_distance {
+				break;
+			}
+			let (target_memory, target_time) = sorted[j];
+			if source_memory == target_memory {
+				continue;
+			}
+			let elapsed = target_time - source_time;
+			let (forward, backward) = if positions[j] == positions[i] {
+				(simultaneous, simultaneous)
+			} else {
+				(config.forward_strength, config.backward_strength)
+			};
+
+			links.push(TemporalLink {
+				source_position: positions[i],
+				target_position: positions[j],
+				source_memory,
+				target_memory,
+				forward_strength: compute_elapsed_link_strength(forward, elapsed, half_life_ms),
+				backward_strength: compute_elapsed_link_strength(backward, elapsed, half_life_ms),
+			});
+		}
+	}
+
+	links
+}
+
 /// Spread activation through temporal links.
 ///
 /// Given a seed memory within an episode, spreads activation to
@@ -563,9 +2532,115 @@ pub fn spread_temporal_activation_multi(
 	}
 }
 
-/// Find temporally adjacent memories ("what was I working on before/after X?").
+/// Which side of an anchor memory to search in an episode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemporalDirection {
+	/// Earlier events, reached through backward links
+	Before,
+	/// Later events, reached through forward links
+	After,
+	/// Both sides of the anchor
+	#[default]
+	Both,
+}
+
+impl TemporalDirection {
+	/// Parse a direction name.
+	///
+	/// Accepts `"before"`/`"backward"`, `"after"`/`"forward"` and `"both"`.
+	/// Returns `None` for anything else rather than guessing.
+	#[must_use]
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name {
+			"before" | "backward" => Some(Self::Before),
+			"after" | "forward" => Some(Self::After),
+			"both" => Some(Self::Both),
+			_ => None,
+		}
+	}
+
+	const fn includes_before(self) -> bool {
+		matches!(self, Self::Before | Self::Both)
+	}
+
+	const fn includes_after(self) -> bool {
+		matches!(self, Self::After | Self::Both)
+	}
+}
+
+/// A memory found next to an anchor in an episode.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TemporalNeighbor {
+	/// Memory index of the neighbor
+	pub memory: usize,
+	/// Link strength in the direction of travel
+	pub strength: f64,
+	/// Side of the anchor this neighbor is on (`Before` or `After`, never `Both`)
+	pub direction: TemporalDirection,
+	/// Position distance from the anchor within the episode
+	pub distance: usize,
+}
+
+/// Find temporally adjacent memories ("what was I working on before/after X?").
+///
+/// Returns neighbors sorted by temporal proximity (closest first), then by
+/// strength (highest first), then by memory index.
+///
+/// # Arguments
+///
+/// * `temporal_links` - Links from `create_episode_links`
+/// * `anchor_memory` - The reference memory
+/// * `direction` - Which side of the anchor to search
+/// * `limit` - Maximum memories to return
+#[must_use]
+pub fn temporal_neighbors(
+	temporal_links: &[TemporalLink],
+	anchor_memory: usize,
+	direction: TemporalDirection,
+	limit: usize,
+) -> Vec<TemporalNeighbor> {
+	let mut neighbors = Vec::new();
+
+	for link in temporal_links {
+		let distance = link.target_position.abs_diff(link.source_position);
+
+		// Memories BEFORE the anchor (anchor is target)
+		if direction.includes_before() && link.target_memory == anchor_memory {
+			neighbors.push(TemporalNeighbor {
+				memory: link.source_memory,
+				strength: link.backward_strength,
+				direction: TemporalDirection::Before,
+				distance,
+			});
+		}
+
+		// Memories AFTER the anchor (anchor is source)
+		if direction.includes_after() && link.source_memory == anchor_memory {
+			neighbors.push(TemporalNeighbor {
+				memory: link.target_memory,
+				strength: link.forward_strength,
+				direction: TemporalDirection::After,
+				distance,
+			});
+		}
+	}
+
+	neighbors.sort_by(|a, b| {
+		a.distance
+			.cmp(&b.distance)
+			.then_with(|| b.strength.total_cmp(&a.strength))
+			.then(a.memory.cmp(&b.memory))
+	});
+	neighbors.truncate(limit);
+	neighbors
+}
+
+/// Find temporally adjacent memories by direction name.
 ///
-/// Returns memory indices sorted by temporal proximity.
+/// Unrecognized names search both directions, as they always have; use
+/// [`temporal_neighbors`] with a [`TemporalDirection`] so typos fail to
+/// compile instead.
 ///
 /// # Arguments
 ///
@@ -573,6 +2648,7 @@ pub fn spread_temporal_activation_multi(
 /// * `anchor_memory` - The reference memory
 /// * `direction` - "before" (backward), "after" (forward), or "both"
 /// * `limit` - Maximum memories to return
+#[deprecated(note = "use `temporal_neighbors` with `TemporalDirection`")]
 #[must_use]
 pub fn find_temporal_neighbors(
 	temporal_links: &[TemporalLink],
@@ -580,49 +2656,10 @@ pub fn find_temporal_neighbors(
 	direction: &str,
 	limit: usize,
 ) -> Vec<(usize, f64)> {
-	let mut neighbors: Vec<(usize, f64, usize)> = Vec::new(); // (memory, strength, distance)
-
-	for link in temporal_links {
-		match direction {
-			"before" | "backward" => {
-				// Looking for memories BEFORE anchor (anchor is target)
-				if link.target_memory == anchor_memory {
-					let distance = link.target_position - link.source_position;
-					neighbors.push((link.source_memory, link.backward_strength, distance));
-				}
-			}
-			"after" | "forward" => {
-				// Looking for memories AFTER anchor (anchor is source)
-				if link.source_memory == anchor_memory {
-					let distance = link.target_position - link.source_position;
-					neighbors.push((link.target_memory, link.forward_strength, distance));
-				}
-			}
-			_ => {
-				// Both directions
-				if link.target_memory == anchor_memory {
-					let distance = link.target_position - link.source_position;
-					neighbors.push((link.source_memory, link.backward_strength, distance));
-				}
-				if link.source_memory == anchor_memory {
-					let distance = link.target_position - link.source_position;
-					neighbors.push((link.target_memory, link.forward_strength, distance));
-				}
-			}
-		}
-	}
-
-	// Sort by distance (closest first), then by strength (highest first)
-	neighbors.sort_by(|a, b| {
-		a.2.cmp(&b.2)
-			.then_with(|| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal))
-	});
-
-	// Return (memory, strength) pairs
-	neighbors
+	let direction = TemporalDirection::from_name(direction).unwrap_or_default();
+	temporal_neighbors(temporal_links, anchor_memory, direction, limit)
 		.into_iter()
-		.take(limit)
-		.map(|(m, s, _)| (m, s))
+		.map(|n| (n.memory, n.strength))
 		.collect()
 }
 
@@ -636,7 +2673,33 @@ mod tests {
 			target,
 			forward_strength: strength,
 			backward_strength: strength * 0.5,
+			association_type: AssociationType::Semantic,
+		}
+	}
+
+	#[test]
+	fn test_association_type_names_round_trip() {
+		for association_type in [
+			AssociationType::Semantic,
+			AssociationType::Temporal,
+			AssociationType::Causal,
+			AssociationType::Entity,
+			AssociationType::VisualSimilarity,
+		] {
+			assert_eq!(association_type.as_str().parse(), Ok(association_type));
 		}
+		assert_eq!(
+			"VisualSimilarity".parse(),
+			Ok(AssociationType::VisualSimilarity)
+		);
+		assert_eq!(
+			"Causal".parse::<AssociationType>(),
+			Ok(AssociationType::Causal)
+		);
+		assert_eq!(
+			"friendship".parse::<AssociationType>(),
+			Err(UnknownAssociationType("friendship".to_string()))
+		);
 	}
 
 	#[test]
@@ -649,6 +2712,7 @@ mod tests {
 			minimum_activation: 0.01,
 			max_nodes: 100,
 			bidirectional: false,
+			..SpreadingConfig::default()
 		};
 
 		let result = spread_activation(3, &associations, &[0], &[1.0], &config, 2);
@@ -672,6 +2736,7 @@ mod tests {
 			minimum_activation: 0.01,
 			max_nodes: 100,
 			bidirectional: false,
+			..SpreadingConfig::default()
 		};
 
 		let result = spread_activation(4, &associations, &[0], &[1.0], &config, 1);
@@ -683,6 +2748,684 @@ mod tests {
 		assert!((result.activations[3] - expected).abs() < 0.01);
 	}
 
+	#[test]
+	fn test_fan_normalization() {
+		// Hub 0 links to 1..=8; node 1's link is strong, the rest weak
+		let mut associations = vec![make_assoc(0, 1, 1.0)];
+		associations.extend((2..=8).map(|target| make_assoc(0, target, 0.25)));
+		let spread_with = |fan_normalization| {
+			let config = SpreadingConfig {
+				bidirectional: false,
+				fan_normalization,
+				..SpreadingConfig::default()
+			};
+			spread_activation(9, &associations, &[0], &[1.0], &config, 1).activations[1]
+		};
+
+		let count = spread_with(FanNormalization::Count);
+		assert!((count - 0.7 / 8.0).abs() < 1e-12);
+		let capped = spread_with(FanNormalization::Capped { cap: 4 });
+		assert!((capped - 0.7 / 4.0).abs() < 1e-12);
+		let log = spread_with(FanNormalization::Log);
+		assert!((log - 0.7 / (1.0 + 8.0_f64.ln())).abs() < 1e-12);
+		// Σ strengths = 1 + 7 × 0.25 = 2.75
+		let strength = spread_with(FanNormalization::Strength);
+		assert!((strength - 0.7 / 2.75).abs() < 1e-12);
+
+		// A single weak link never amplifies
+		let weak = vec![make_assoc(0, 1, 0.5)];
+		let config = SpreadingConfig {
+			bidirectional: false,
+			fan_normalization: FanNormalization::Strength,
+			..SpreadingConfig::default()
+		};
+		let result = spread_activation(2, &weak, &[0], &[1.0], &config, 1);
+		assert!((result.activations[1] - 0.35).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_spreading_prebuilt_graph_matches() {
+		let associations = vec![
+			make_assoc(0, 1, 0.9),
+			make_assoc(0, 2, 0.4),
+			make_assoc(1, 3, 0.8),
+			make_assoc(2, 3, 0.6),
+			make_assoc(3, 4, 1.0),
+		];
+		let config = SpreadingConfig::default();
+		let graph = AssociationGraph::new(5, &associations);
+
+		let direct = spread_activation(5, &associations, &[0], &[1.0], &config, 3);
+		let prebuilt = spread_activation_graph(&graph, &[0], &[1.0], &config, 3);
+
+		assert_eq!(direct.activations, prebuilt.activations);
+		assert_eq!(direct.visited_by_depth, prebuilt.visited_by_depth);
+	}
+
+	#[test]
+	fn test_spreading_type_params() {
+		// 0 → 1 (semantic), 0 → 2 (causal)
+		let associations = vec![
+			make_assoc(0, 1, 0.8),
+			Association {
+				association_type: AssociationType::Causal,
+				..make_assoc(0, 2, 0.8)
+			},
+		];
+
+		let neutral = SpreadingConfig {
+			bidirectional: false,
+			..SpreadingConfig::default()
+		};
+		let result = spread_activation(3, &associations, &[0], &[1.0], &neutral, 1);
+		assert!((result.activations[1] - result.activations[2]).abs() < f64::EPSILON);
+
+		let mut typed = neutral;
+		typed.type_params.causal = TypeSpreadingParams {
+			weight: 1.0,
+			decay_multiplier: 1.2,
+		};
+		typed.type_params.semantic.weight = 0.5;
+		let result = spread_activation(3, &associations, &[0], &[1.0], &typed, 1);
+
+		// (1.0 / 2) × 0.8 × 0.5 × 0.7 and (1.0 / 2) × 0.8 × (0.7 × 1.2)
+		assert!((result.activations[1] - 0.14).abs() < 1e-10);
+		assert!((result.activations[2] - 0.336).abs() < 1e-10);
+	}
+
+	#[test]
+	fn test_spreading_tag_priors() {
+		// 0 → 1 (project:lucid), 0 → 2 (project:lucid, archived), 0 → 3 (untagged)
+		let associations = vec![
+			make_assoc(0, 1, 0.8),
+			make_assoc(0, 2, 0.8),
+			make_assoc(0, 3, 0.8),
+		];
+		let tag = |t: &str| t.to_string();
+		let graph = AssociationGraph::new(4, &associations).with_node_tags(vec![
+			vec![],
+			vec![tag("project:lucid")],
+			vec![tag("project:lucid"), tag("archived")],
+		]);
+
+		let mut config = SpreadingConfig {
+			bidirectional: false,
+			..SpreadingConfig::default()
+		};
+		config.tag_filter.any_of.push(tag("project:lucid"));
+		let _ = config.tag_weights.insert(tag("archived"), 0.5);
+		let result = spread_activation_graph(&graph, &[0], &[1.0], &config, 1);
+
+		assert!((result.activations[2] / result.activations[1] - 0.5).abs() < 1e-12);
+		assert!(result.activations[3].abs() < f64::EPSILON);
+		assert_eq!(result.visited_by_depth[1], vec![1, 2]);
+
+		config.tag_filter.none_of.push(tag("archived"));
+		let result = spread_activation_graph(&graph, &[0], &[1.0], &config, 1);
+		assert_eq!(result.visited_by_depth[1], vec![1]);
+	}
+
+	#[test]
+	fn test_spreading_edge_decay_override() {
+		// 0 → 1 same-document link (lossless), 0 → 2 weak co-occurrence
+		let associations = vec![
+			make_assoc(0, 1, 0.8),
+			make_assoc(0, 2, 0.8),
+			make_assoc(0, 3, 0.8),
+		];
+		let graph =
+			AssociationGraph::new(4, &associations).with_edge_decays(vec![Some(1.0), Some(0.1)]);
+		let config = SpreadingConfig {
+			bidirectional: false,
+			..SpreadingConfig::default()
+		};
+		let result = spread_activation_graph(&graph, &[0], &[1.0], &config, 1);
+
+		// Equal strengths, so activation ratios are the decay ratios
+		assert!((result.activations[1] / result.activations[3] - 1.0 / 0.7).abs() < 1e-12);
+		assert!((result.activations[2] / result.activations[3] - 0.1 / 0.7).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_spreading_node_thresholds() {
+		// 1 is weakly encoded: it receives activation but sits below τ
+		let associations = vec![make_assoc(0, 1, 0.8), make_assoc(1, 2, 0.8)];
+		let graph = AssociationGraph::new(3, &associations).with_node_thresholds(vec![0.0, 0.9]);
+		let gated = SpreadingConfig {
+			bidirectional: false,
+			..SpreadingConfig::default()
+		};
+		let result = spread_activation_graph(&graph, &[0], &[1.0], &gated, 2);
+		assert!(result.activations[1] > 0.0);
+		assert!(result.activations[2].abs() < f64::EPSILON);
+
+		let top = get_top_retrievable(&graph, &result.activations, 3);
+		assert_eq!(top.iter().map(|&(i, _)| i).collect::<Vec<_>>(), vec![0]);
+
+		let open = SpreadingConfig {
+			below_threshold: BelowThreshold::Propagate,
+			..gated
+		};
+		let result = spread_activation_graph(&graph, &[0], &[1.0], &open, 2);
+		assert!(result.activations[2] > 0.0);
+	}
+
+	#[test]
+	fn test_spreading_sampled() {
+		// Hub 0 links to 200 leaves; 201 and 202 form a small chain
+		let mut associations: Vec<Association> =
+			(1..=200).map(|leaf| make_assoc(0, leaf, 0.5)).collect();
+		associations.push(make_assoc(201, 202, 0.8));
+		let graph = AssociationGraph::new(203, &associations);
+		let config = SpreadingConfig {
+			bidirectional: false,
+			..SpreadingConfig::default()
+		};
+
+		// Below the sample count, spreading is exact
+		let exact = spread_activation_graph(&graph, &[201], &[1.0], &config, 2);
+		let sampled = spread_activation_sampled(
+			&graph,
+			&[201],
+			&[1.0],
+			&config,
+			&MonteCarloConfig::default(),
+			2,
+		);
+		assert!((sampled.activations[202] - exact.activations[202]).abs() < 1e-12);
+		assert!(sampled.std_errors[202].abs() < 1e-12);
+		assert_eq!(sampled.visited, vec![201, 202]);
+
+		// At the hub, each run visits at most K leaves but totals match
+		let sampling = MonteCarloConfig {
+			samples_per_node: 8,
+			num_runs: 400,
+			seed: 5,
+		};
+		let exact = spread_activation_graph(&graph, &[0], &[1.0], &config, 1);
+		let sampled = spread_activation_sampled(&graph, &[0], &[1.0], &config, &sampling, 1);
+		let leaves = |activations: &[f64]| activations[1..=200].iter().sum::<f64>();
+		assert!((leaves(&sampled.activations) - leaves(&exact.activations)).abs() < 1e-9);
+		assert!(sampled.std_errors[1] > 0.0);
+		let expected = exact.activations[1];
+		let mean_error = (1..=200)
+			.map(|leaf| (sampled.activations[leaf] - expected).abs())
+			.sum::<f64>()
+			/ 200.0;
+		assert!(mean_error < 0.3 * expected);
+	}
+
+	#[test]
+	fn test_joint_cues() {
+		// Content cue 0 reaches 2 and 3; context cue 1 reaches 3 and 4
+		let associations = vec![
+			make_assoc(0, 2, 0.8),
+			make_assoc(0, 3, 0.8),
+			make_assoc(1, 3, 0.8),
+			make_assoc(1, 4, 0.8),
+		];
+		let graph = AssociationGraph::new(5, &associations);
+		let content = SpreadingQuery {
+			seed_indices: &[0],
+			seed_activations: &[1.0],
+		};
+		let context = SpreadingQuery {
+			seed_indices: &[1],
+			seed_activations: &[1.0],
+		};
+		let directed = SpreadingConfig {
+			bidirectional: false,
+			..SpreadingConfig::default()
+		};
+		let mut config = JointCueConfig {
+			content: directed.clone(),
+			context: directed,
+			combination: CueCombination::Multiplicative,
+		};
+
+		let joint = spread_joint_cues(&graph, content, context, &config, 1);
+		let nodes: Vec<usize> = joint.ranked.iter().map(|&(i, _)| i).collect();
+		assert_eq!(nodes, vec![3]);
+
+		config.combination = CueCombination::Additive {
+			content_weight: 1.0,
+			context_weight: 0.5,
+		};
+		let joint = spread_joint_cues(&graph, content, context, &config, 1);
+		let nodes: Vec<usize> = joint.ranked.iter().map(|&(i, _)| i).collect();
+		// Seeds keep their cue activation; 3 gathers support from both sets
+		assert_eq!(nodes, vec![0, 1, 3, 2, 4]);
+	}
+
+	#[test]
+	fn test_spreading_noise_seeded() {
+		let associations = vec![make_assoc(0, 1, 0.8), make_assoc(1, 2, 0.6)];
+		let quiet = SpreadingConfig::default();
+		let noisy = SpreadingConfig {
+			activation_noise: 0.3,
+			noise_seed: 11,
+			..SpreadingConfig::default()
+		};
+
+		let baseline = spread_activation(4, &associations, &[0], &[1.0], &quiet, 2);
+		let a = spread_activation(4, &associations, &[0], &[1.0], &noisy, 2);
+		let b = spread_activation(4, &associations, &[0], &[1.0], &noisy, 2);
+		let reseeded = SpreadingConfig {
+			noise_seed: 12,
+			..noisy
+		};
+		let c = spread_activation(4, &associations, &[0], &[1.0], &reseeded, 2);
+
+		// Same seed, same result; different seed, different noise
+		assert_eq!(a.activations, b.activations);
+		assert_ne!(a.activations, c.activations);
+		assert_ne!(a.activations, baseline.activations);
+
+		// Unvisited nodes receive no noise
+		assert!(a.activations[3].abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn test_competition_k_winners() {
+		let mut activations = vec![0.9, 0.5, 0.8, 0.5, 0.1];
+		let competition = Competition::KWinnersTakeAll {
+			k: 2,
+			inhibition: 1.0,
+		};
+		apply_competition(&mut activations, &[0, 1, 2, 3], &competition);
+
+		// Node 4 is not a candidate and is left alone
+		assert_eq!(activations, vec![0.9, 0.0, 0.8, 0.0, 0.1]);
+	}
+
+	#[test]
+	fn test_competition_softmax() {
+		let original = vec![1.0, 0.8, 0.2];
+		let total: f64 = original.iter().sum();
+
+		let mut warm = original.clone();
+		apply_competition(
+			&mut warm,
+			&[0, 1, 2],
+			&Competition::Softmax { temperature: 1.0 },
+		);
+		let mut cold = original;
+		apply_competition(
+			&mut cold,
+			&[0, 1, 2],
+			&Competition::Softmax { temperature: 0.05 },
+		);
+
+		// Total activation is preserved, order is kept, and colder is sharper
+		assert!((warm.iter().sum::<f64>() - total).abs() < 1e-10);
+		assert!((cold.iter().sum::<f64>() - total).abs() < 1e-10);
+		assert!(warm[0] > warm[1] && warm[1] > warm[2]);
+		assert!(cold[0] > warm[0]);
+		assert!(cold[2] < warm[2]);
+	}
+
+	#[test]
+	fn test_spreading_with_competition() {
+		let associations = vec![
+			make_assoc(0, 1, 0.9),
+			make_assoc(0, 2, 0.85),
+			make_assoc(0, 3, 0.8),
+		];
+		let config = SpreadingConfig {
+			bidirectional: false,
+			competition: Competition::KWinnersTakeAll {
+				k: 2,
+				inhibition: 1.0,
+			},
+			..SpreadingConfig::default()
+		};
+
+		let result = spread_activation(4, &associations, &[0], &[1.0], &config, 1);
+		let winners = get_top_activated(&result.activations, 10);
+		assert_eq!(winners, vec![0, 1]);
+	}
+
+	#[test]
+	fn test_spreading_batch_matches_single() {
+		let associations = vec![
+			make_assoc(0, 1, 0.8),
+			make_assoc(1, 2, 0.6),
+			make_assoc(2, 3, 0.9),
+			make_assoc(3, 0, 0.4),
+		];
+		let config = SpreadingConfig::default();
+		let queries = [
+			SpreadingQuery {
+				seed_indices: &[0],
+				seed_activations: &[1.0],
+			},
+			SpreadingQuery {
+				seed_indices: &[2, 3],
+				seed_activations: &[0.5, 0.8],
+			},
+			SpreadingQuery {
+				seed_indices: &[],
+				seed_activations: &[],
+			},
+		];
+
+		let batch = spread_activation_batch(4, &associations, &queries, &config, 3);
+		assert_eq!(batch.len(), 3);
+		for (query, result) in queries.iter().zip(&batch) {
+			let single = spread_activation(
+				4,
+				&associations,
+				query.seed_indices,
+				query.seed_activations,
+				&config,
+				3,
+			);
+			assert_eq!(single.activations, result.activations);
+			assert_eq!(single.visited_by_depth, result.visited_by_depth);
+		}
+	}
+
+	#[test]
+	fn test_spreading_presets() {
+		// Seed 0 has one temporal and one semantic neighbor of equal strength
+		let associations = vec![
+			Association {
+				association_type: AssociationType::Temporal,
+				..make_assoc(0, 1, 0.8)
+			},
+			make_assoc(0, 2, 0.8),
+		];
+		let spread = |config: &SpreadingConfig| {
+			spread_activation(3, &associations, &[0], &[1.0], config, 1).activations
+		};
+
+		let episodic = spread(&SpreadingConfig::episodic_recall());
+		assert!(episodic[1] > episodic[2]);
+
+		let semantic = spread(&SpreadingConfig::semantic_association());
+		assert!(semantic[2] > semantic[1]);
+
+		let free = SpreadingConfig::free_association();
+		assert!(free.decay_per_hop > SpreadingConfig::default().decay_per_hop);
+		assert!(free.activation_noise > 0.0);
+
+		let temporal = TemporalSpreadingConfig::episodic_recall();
+		assert!(temporal.forward_strength > temporal.backward_strength);
+		let loose = TemporalSpreadingConfig::free_association();
+		assert!(loose.max_temporal_distance > temporal.max_temporal_distance);
+	}
+
+	#[test]
+	fn test_spreading_config_validate() {
+		assert!(SpreadingConfig::default().validate().is_empty());
+		assert!(SpreadingConfig::episodic_recall().validate().is_empty());
+		assert!(SpreadingConfig::free_association().validate().is_empty());
+
+		let mut config = SpreadingConfig {
+			decay_per_hop: 1.5,
+			max_nodes: 0,
+			convergence_epsilon: Some(0.01),
+			max_iterations: 0,
+			competition: Competition::KWinnersTakeAll {
+				k: 3,
+				inhibition: f64::NAN,
+			},
+			..SpreadingConfig::default()
+		};
+		config.type_params.entity.weight = -1.0;
+		let _ = config
+			.tag_weights
+			.insert("urgent".to_string(), f64::INFINITY);
+
+		let fields: Vec<String> = config.validate().into_iter().map(|i| i.field).collect();
+		assert_eq!(
+			fields,
+			[
+				"decay_per_hop",
+				"max_nodes",
+				"max_iterations",
+				"type_params.entity.weight",
+				"competition.inhibition",
+				"tag_weights[\"urgent\"]",
+			]
+		);
+	}
+
+	#[test]
+	fn test_spreading_provenance() {
+		// Two seeds both feed node 2, which passes activation on to 3
+		let associations = vec![
+			make_assoc(0, 2, 0.9),
+			make_assoc(1, 2, 0.3),
+			make_assoc(2, 3, 0.8),
+		];
+		let config = SpreadingConfig {
+			bidirectional: false,
+			trace_provenance: true,
+			..SpreadingConfig::default()
+		};
+
+		let result = spread_activation(4, &associations, &[0, 1], &[1.0, 1.0], &config, 2);
+
+		let into_two = result.contributions(2);
+		let sources: Vec<usize> = into_two.iter().map(|c| c.source).collect();
+		assert_eq!(sources, vec![0, 1]);
+		assert!(into_two.iter().all(|c| c.depth == 1 && !c.backward));
+		let total: f64 = into_two.iter().map(|c| c.amount).sum();
+		assert!((total - result.activations[2]).abs() < 1e-12);
+
+		let into_three = result.contributions(3);
+		assert_eq!(into_three.len(), 1);
+		assert_eq!(
+			(
+				into_three[0].source,
+				into_three[0].edge,
+				into_three[0].depth
+			),
+			(2, 2, 2)
+		);
+
+		// Off by default
+		let untraced = spread_activation(
+			4,
+			&associations,
+			&[0, 1],
+			&[1.0, 1.0],
+			&SpreadingConfig::default(),
+			2,
+		);
+		assert!(untraced.provenance.is_none());
+		assert!(untraced.contributions(2).is_empty());
+	}
+
+	#[test]
+	fn test_spreading_depth_deltas() {
+		// 0 → 1 → 2, plus 0 → 2 directly
+		let associations = vec![
+			make_assoc(0, 1, 1.0),
+			make_assoc(1, 2, 1.0),
+			make_assoc(0, 2, 1.0),
+		];
+		let config = SpreadingConfig {
+			bidirectional: false,
+			minimum_activation: 0.0,
+			record_depth_deltas: true,
+			..SpreadingConfig::default()
+		};
+
+		let result = spread_activation(3, &associations, &[0], &[1.0], &config, 3);
+		let deltas = result.depth_deltas.as_ref().map_or(0, Vec::len);
+		assert_eq!(deltas, 2);
+
+		// Node 2 hears from 0 at level 1 and again via 1 at level 2
+		let profile = result.depth_profile(2);
+		assert!((profile[0] - 0.35).abs() < 1e-12);
+		assert!((profile[1] - 0.245).abs() < 1e-12);
+		assert!((profile.iter().sum::<f64>() - result.activations[2]).abs() < 1e-12);
+
+		let untracked = spread_activation(
+			3,
+			&associations,
+			&[0],
+			&[1.0],
+			&SpreadingConfig::default(),
+			3,
+		);
+		assert!(untracked.depth_profile(2).is_empty());
+	}
+
+	#[test]
+	fn test_spreading_convergence() {
+		// Long chain: 0 → 1 → ... → 19
+		let associations: Vec<Association> = (0..19).map(|i| make_assoc(i, i + 1, 1.0)).collect();
+
+		let config = SpreadingConfig {
+			bidirectional: false,
+			minimum_activation: 0.0,
+			convergence_epsilon: Some(0.05),
+			..SpreadingConfig::default()
+		};
+
+		// Depth argument is ignored; spreading runs past it until the
+		// per-level change (0.7^k) drops below epsilon.
+		let result = spread_activation(20, &associations, &[0], &[1.0], &config, 2);
+		assert_eq!(result.visited_by_depth.len(), 10);
+		assert!(result.activations[9] > 0.0);
+		assert!(result.activations[10].abs() < f64::EPSILON);
+
+		// The hard cap wins when convergence is slower
+		let capped = SpreadingConfig {
+			max_iterations: 4,
+			..config
+		};
+		let result = spread_activation(20, &associations, &[0], &[1.0], &capped, 2);
+		assert_eq!(result.visited_by_depth.len(), 5);
+	}
+
+	#[cfg(feature = "parallel")]
+	#[test]
+	fn test_parallel_expansion_matches_sequential() {
+		let num_nodes = 4000;
+		let associations: Vec<Association> = (0..num_nodes)
+			.flat_map(|i| {
+				[
+					make_assoc(i, (i * 7 + 1) % num_nodes, 0.8),
+					make_assoc(i, (i * 13 + 5) % num_nodes, 0.5),
+				]
+			})
+			.collect();
+		let graph = AssociationGraph::new(num_nodes, &associations);
+		let config = SpreadingConfig {
+			max_nodes: num_nodes,
+			..SpreadingConfig::default()
+		};
+		let frontier: Vec<usize> = (0..2000).collect();
+		let activations = vec![1.0; num_nodes];
+
+		let mut seq_visited: HashSet<usize> = frontier.iter().copied().collect();
+		let mut seq_total = frontier.len();
+		let spread = Spread {
+			graph: &graph,
+			config: &config,
+			filter: TraversalFilter::default(),
+		};
+		let (seq_activations, seq_frontier) = expand_frontier_sequential(
+			spread,
+			&activations,
+			&frontier,
+			&mut seq_visited,
+			&mut seq_total,
+			None,
+		);
+
+		let mut par_visited: HashSet<usize> = frontier.iter().copied().collect();
+		let mut par_total = frontier.len();
+		let (par_activations, par_frontier) = expand_frontier_parallel(
+			spread,
+			&activations,
+			&frontier,
+			&mut par_visited,
+			&mut par_total,
+		);
+
+		assert_eq!(seq_frontier, par_frontier);
+		assert_eq!(seq_total, par_total);
+		assert_eq!(seq_activations.len(), par_activations.len());
+		for (idx, activation) in &seq_activations {
+			assert!((activation - par_activations[idx]).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn test_top_activated_with_scores() {
+		let activations = [0.2, 0.9, 0.0, 0.5, 0.9, -1.0, 0.1];
+
+		let top = get_top_activated_with_scores(&activations, 3);
+		assert_eq!(top, vec![(1, 0.9), (4, 0.9), (3, 0.5)]);
+		assert_eq!(get_top_activated(&activations, 3), vec![1, 4, 3]);
+
+		// Only positive activations, never more than exist
+		assert_eq!(get_top_activated_with_scores(&activations, 10).len(), 5);
+		assert!(get_top_activated_with_scores(&activations, 0).is_empty());
+	}
+
+	#[test]
+	fn test_max_nodes_keeps_strongest_targets() {
+		// The weakest link comes first in edge order
+		let associations = vec![
+			make_assoc(0, 1, 0.1),
+			make_assoc(0, 2, 0.9),
+			make_assoc(0, 3, 0.5),
+		];
+		let config = SpreadingConfig {
+			max_nodes: 3,
+			bidirectional: false,
+			trace_provenance: true,
+			..SpreadingConfig::default()
+		};
+		let result = spread_activation(4, &associations, &[0], &[1.0], &config, 2);
+
+		assert_eq!(result.visited_by_depth[1], vec![2, 3]);
+		assert!(result.activations[1].abs() < f64::EPSILON);
+		assert!(result.activations[2] > result.activations[3]);
+		assert!(result.contributions(1).is_empty());
+		assert_eq!(result.contributions(3).len(), 1);
+	}
+
+	#[test]
+	fn test_spreading_is_reproducible() {
+		// Dense enough that per-level updates touch many nodes
+		let mut associations = Vec::new();
+		for source in 0..40 {
+			for step in [1, 3, 7] {
+				let strength = [0.3, 0.4, 0.5, 0.6, 0.7][source % 5];
+				associations.push(make_assoc(source, (source + step) % 40, strength));
+			}
+		}
+		let graph = AssociationGraph::new(40, &associations);
+		let config = SpreadingConfig {
+			convergence_epsilon: Some(1e-9),
+			competition: Competition::KWinnersTakeAll {
+				k: 5,
+				inhibition: 0.5,
+			},
+			..SpreadingConfig::default()
+		};
+
+		let first = spread_activation_graph(&graph, &[0, 20], &[1.0, 1.0], &config, 3);
+		for _ in 0..5 {
+			let again = spread_activation_graph(&graph, &[0, 20], &[1.0, 1.0], &config, 3);
+			let bits = |r: &SpreadingResult| {
+				r.activations
+					.iter()
+					.map(|a| a.to_bits())
+					.collect::<Vec<_>>()
+			};
+			assert_eq!(bits(&first), bits(&again));
+			assert_eq!(first.visited_by_depth, again.visited_by_depth);
+		}
+	}
+
 	#[test]
 	fn test_find_path() {
 		let associations = vec![
@@ -692,7 +3435,27 @@ mod tests {
 		];
 
 		let path = find_activation_path(4, &associations, 0, 3);
-		assert_eq!(path, vec![0, 1, 2, 3]);
+		assert!(path.as_ref().is_some_and(|p| p.nodes == vec![0, 1, 2, 3]));
+		assert!(path.is_some_and(|p| p.edges.len() == 3 && (p.strength - 1.0).abs() < 1e-12));
+	}
+
+	#[test]
+	fn test_find_path_bidirectional() {
+		// Long chain 0 → 1 → … → 6 with a shortcut 2 → 5
+		let mut associations: Vec<Association> =
+			(0..6).map(|i| make_assoc(i, i + 1, 1.0)).collect();
+		associations.push(make_assoc(2, 5, 1.0));
+		// Fan-out noise around the source
+		associations.extend((7..12).map(|i| make_assoc(0, i, 1.0)));
+
+		let path = find_activation_path(12, &associations, 0, 6).map(|p| p.nodes);
+		assert_eq!(path, Some(vec![0, 1, 2, 5, 6]));
+
+		// Direction matters
+		assert!(find_activation_path(12, &associations, 6, 0).is_none());
+		assert!(find_activation_path(12, &associations, 0, 99).is_none());
+		let single = find_activation_path(12, &associations, 3, 3).map(|p| p.nodes);
+		assert_eq!(single, Some(vec![3]));
 	}
 
 	#[test]
@@ -713,6 +3476,67 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn test_weighted_pagerank() {
+		let associations = vec![
+			make_assoc(0, 1, 0.9),
+			make_assoc(0, 2, 0.1),
+			make_assoc(1, 0, 1.0),
+			make_assoc(2, 0, 1.0),
+		];
+
+		let plain = compute_pagerank(3, &associations, 0.85, 100);
+		assert!((plain[1] - plain[2]).abs() < 1e-12);
+		let weighted = compute_weighted_pagerank(3, &associations, 0.85, 100);
+		assert!(weighted[1] > 4.0 * weighted[2]);
+		assert!((weighted.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+		// Outgoing strengths summing to 0 make 2 dangling, not a sink
+		let zero = vec![
+			make_assoc(0, 1, 1.0),
+			make_assoc(1, 2, 1.0),
+			make_assoc(2, 0, 0.0),
+		];
+		let ranks = compute_weighted_pagerank(3, &zero, 0.85, 100);
+		assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+		assert!(ranks[0] > 0.0);
+	}
+
+	#[test]
+	fn test_pagerank_convergence_and_dangling() {
+		let cycle = vec![
+			make_assoc(0, 1, 1.0),
+			make_assoc(1, 2, 1.0),
+			make_assoc(2, 0, 1.0),
+			make_assoc(0, 3, 1.0),
+		];
+		let graph = AssociationGraph::new(4, &cycle);
+		let filter = TraversalFilter::default();
+		let config = PageRankConfig::default();
+		let result = compute_pagerank_with_config(&graph, &[], &config, &filter);
+		assert!(result.converged);
+		assert!(result.iterations < config.max_iterations);
+		assert!(result.residual < config.tolerance);
+
+		// 1 is dangling and 2 isolated; a run personalized to 0
+		let graph = AssociationGraph::new(3, &[make_assoc(0, 1, 1.0)]);
+		let run = |dangling| {
+			let config = PageRankConfig {
+				dangling,
+				..PageRankConfig::default()
+			};
+			compute_pagerank_with_config(&graph, &[(0, 1.0)], &config, &filter).ranks
+		};
+		let all = run(DanglingPolicy::All);
+		assert!(all[2] > 0.0);
+		assert!((all.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+		let seeds = run(DanglingPolicy::Seeds);
+		assert!(seeds[2].abs() < f64::EPSILON);
+		assert!((seeds.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+		let dropped = run(DanglingPolicy::Drop);
+		assert!(dropped.iter().sum::<f64>() < 0.9);
+	}
+
 	// Temporal Spreading tests
 
 	#[test]
@@ -767,6 +3591,70 @@ mod tests {
 		assert!(result.activations[2] > result.activations[0]);
 	}
 
+	#[test]
+	fn test_create_episode_links_timed() {
+		let config = TemporalSpreadingConfig::default();
+		let minute = 60_000.0;
+		// 0 and 1 are 5 seconds apart, 1 and 2 are 2 hours apart
+		let timestamps = [0.0, 5_000.0, 7_205_000.0];
+		let links = create_episode_links_timed(&[10, 20, 30], &timestamps, 10.0 * minute, &config);
+
+		assert_eq!(links.len(), 3);
+		let close = &links[0];
+		let far = &links[2];
+		assert!(close.forward_strength > 0.99);
+		assert!(far.forward_strength < 0.001);
+
+		// One half-life of elapsed time halves the base strength
+		let halved = compute_elapsed_link_strength(0.8, minute, minute);
+		assert!((halved - 0.4).abs() < 1e-12);
+		assert!((compute_elapsed_link_strength(0.8, minute, 0.0) - 0.8).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_create_episode_links_from_timestamps() {
+		let config = TemporalSpreadingConfig {
+			max_temporal_distance: 1,
+			..TemporalSpreadingConfig::default()
+		};
+		// Out of order; 30 and 40 happen together; 50 has no valid time
+		let events = [
+			(20, 5_000.0),
+			(40, 9_000.0),
+			(10, 0.0),
+			(30, 9_000.0),
+			(50, f64::NAN),
+		];
+		let links = create_episode_links_from_timestamps(&events, 60_000.0, &config);
+
+		let pairs: Vec<(usize, usize, usize, usize)> = links
+			.iter()
+			.map(|l| {
+				(
+					l.source_memory,
+					l.target_memory,
+					l.source_position,
+					l.target_position,
+				)
+			})
+			.collect();
+		assert_eq!(
+			pairs,
+			vec![
+				(10, 20, 0, 1),
+				(20, 40, 1, 2),
+				(20, 30, 1, 2),
+				(40, 30, 2, 2)
+			]
+		);
+
+		// Simultaneous events link symmetrically at full (undecayed) strength
+		let together = &links[3];
+		assert!((together.forward_strength - together.backward_strength).abs() < 1e-12);
+		assert!((together.forward_strength - 0.85).abs() < 1e-12);
+		assert!(links[0].forward_strength > links[0].backward_strength);
+	}
+
 	#[test]
 	fn test_find_temporal_neighbors_before() {
 		let config = TemporalSpreadingConfig::default();
@@ -774,10 +3662,10 @@ mod tests {
 		let links = create_episode_links(&[0, 1, 2, 3], &config);
 
 		// Find memories BEFORE memory 2
-		let before = find_temporal_neighbors(&links, 2, "before", 10);
+		let before = temporal_neighbors(&links, 2, TemporalDirection::Before, 10);
 
 		// Should find 0 and 1 (both come before 2)
-		let memory_ids: Vec<usize> = before.iter().map(|(m, _)| *m).collect();
+		let memory_ids: Vec<usize> = before.iter().map(|n| n.memory).collect();
 		assert!(memory_ids.contains(&0));
 		assert!(memory_ids.contains(&1));
 		// Should NOT contain 3 (comes after)
@@ -791,13 +3679,46 @@ mod tests {
 		let links = create_episode_links(&[0, 1, 2, 3], &config);
 
 		// Find memories AFTER memory 1
-		let after = find_temporal_neighbors(&links, 1, "after", 10);
+		let after = temporal_neighbors(&links, 1, TemporalDirection::After, 10);
 
 		// Should find 2 and 3 (both come after 1)
-		let memory_ids: Vec<usize> = after.iter().map(|(m, _)| *m).collect();
+		let memory_ids: Vec<usize> = after.iter().map(|n| n.memory).collect();
 		assert!(memory_ids.contains(&2));
 		assert!(memory_ids.contains(&3));
 		// Should NOT contain 0 (comes before)
 		assert!(!memory_ids.contains(&0));
 	}
+
+	#[test]
+	fn test_temporal_neighbors_both_tags_direction() {
+		let config = TemporalSpreadingConfig::default();
+		let links = create_episode_links(&[0, 1, 2, 3], &config);
+
+		let both = temporal_neighbors(&links, 1, TemporalDirection::Both, 10);
+		assert_eq!(both.len(), 3);
+		assert_eq!(both[0].distance, 1);
+
+		for neighbor in &both {
+			let expected = if neighbor.memory < 1 {
+				TemporalDirection::Before
+			} else {
+				TemporalDirection::After
+			};
+			assert_eq!(neighbor.direction, expected);
+		}
+	}
+
+	#[test]
+	#[allow(deprecated)]
+	fn test_find_temporal_neighbors_str_shim() {
+		let config = TemporalSpreadingConfig::default();
+		let links = create_episode_links(&[0, 1, 2, 3], &config);
+
+		assert_eq!(TemporalDirection::from_name("bakward"), None);
+		// Unknown names keep the old "both" behavior
+		let shim = find_temporal_neighbors(&links, 1, "bakward", 10);
+		assert_eq!(shim.len(), 3);
+		let before = find_temporal_neighbors(&links, 1, "backward", 10);
+		assert_eq!(before, vec![(0, links[0].backward_strength)]);
+	}
 }
@@ -0,0 +1,981 @@
+//! `SQLite` Store
+//!
+//! Durable storage for everything the rest of the crate computes on:
+//! [`Moment`]s with their tags and embeddings, associations, and episodes
+//! with their temporal links. One file holds a whole memory, in a schema
+//! this module owns, so callers query it through typed methods instead of
+//! maintaining their own tables around raw outputs.
+//!
+//! Moment text is indexed with FTS5 for [`MemoryStore::search_text`];
+//! triggers keep the index in step with the `moments` table. Embeddings
+//! are stored as little-endian `f64` blobs.
+//!
+//! The schema version is kept in `PRAGMA user_version`. Opening a file
+//! written by a newer version fails rather than guessing at its layout.
+
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+
+use crate::episodes::Episode;
+use crate::error::{Classify, ErrorKind};
+use crate::graph::MemoryGraph;
+use crate::moment::{MediaRef, Modality, Moment};
+use crate::spreading::{Association, AssociationType, TemporalLink};
+
+/// Schema version written by this build.
+pub const STORE_SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS moments (
+	id INTEGER PRIMARY KEY,
+	modality TEXT NOT NULL,
+	start_ms REAL NOT NULL,
+	end_ms REAL NOT NULL,
+	text TEXT NOT NULL,
+	media TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS moments_by_time ON moments (start_ms, end_ms);
+
+CREATE TABLE IF NOT EXISTS tags (
+	moment_id INTEGER NOT NULL REFERENCES moments (id) ON DELETE CASCADE,
+	tag TEXT NOT NULL,
+	PRIMARY KEY (moment_id, tag)
+);
+CREATE INDEX IF NOT EXISTS tags_by_tag ON tags (tag);
+
+CREATE TABLE IF NOT EXISTS embeddings (
+	moment_id INTEGER NOT NULL REFERENCES moments (id) ON DELETE CASCADE,
+	modality TEXT NOT NULL,
+	vector BLOB NOT NULL,
+	PRIMARY KEY (moment_id, modality)
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS moments_fts
+	USING fts5 (text, content = 'moments', content_rowid = 'id');
+CREATE TRIGGER IF NOT EXISTS moments_fts_insert AFTER INSERT ON moments BEGIN
+	INSERT INTO moments_fts (rowid, text) VALUES (new.id, new.text);
+END;
+CREATE TRIGGER IF NOT EXISTS moments_fts_delete AFTER DELETE ON moments BEGIN
+	INSERT INTO moments_fts (moments_fts, rowid, text) VALUES ('delete', old.id, old.text);
+END;
+CREATE TRIGGER IF NOT EXISTS moments_fts_update AFTER UPDATE ON moments BEGIN
+	INSERT INTO moments_fts (moments_fts, rowid, text) VALUES ('delete', old.id, old.text);
+	INSERT INTO moments_fts (rowid, text) VALUES (new.id, new.text);
+END;
+
+CREATE TABLE IF NOT EXISTS associations (
+	source INTEGER NOT NULL,
+	target INTEGER NOT NULL,
+	forward_strength REAL NOT NULL,
+	backward_strength REAL NOT NULL,
+	association_type TEXT NOT NULL,
+	PRIMARY KEY (source, target)
+);
+CREATE INDEX IF NOT EXISTS associations_by_target ON associations (target);
+
+CREATE TABLE IF NOT EXISTS episodes (
+	id INTEGER PRIMARY KEY AUTOINCREMENT
+);
+CREATE TABLE IF NOT EXISTS episode_events (
+	episode_id INTEGER NOT NULL REFERENCES episodes (id) ON DELETE CASCADE,
+	position INTEGER NOT NULL,
+	moment_id INTEGER NOT NULL,
+	PRIMARY KEY (episode_id, position)
+);
+CREATE INDEX IF NOT EXISTS episode_events_by_moment ON episode_events (moment_id);
+CREATE TABLE IF NOT EXISTS temporal_links (
+	episode_id INTEGER NOT NULL REFERENCES episodes (id) ON DELETE CASCADE,
+	source_position INTEGER NOT NULL,
+	target_position INTEGER NOT NULL,
+	source_memory INTEGER NOT NULL,
+	target_memory INTEGER NOT NULL,
+	forward_strength REAL NOT NULL,
+	backward_strength REAL NOT NULL
+);
+CREATE INDEX IF NOT EXISTS temporal_links_by_episode ON temporal_links (episode_id);
+";
+
+const MOMENT_COLUMNS: &str = "id, modality, start_ms, end_ms, text, media";
+
+/// Error type for store operations.
+#[derive(Debug, thiserror::Error)]
+pub enum StoreError {
+	/// `SQLite` reported an error.
+	#[error("SQLite error: {0}")]
+	Sqlite(#[from] rusqlite::Error),
+
+	/// A media reference list could not be encoded or decoded.
+	#[error("Media encoding error: {0}")]
+	Json(#[from] serde_json::Error),
+
+	/// The file was written by a newer schema version.
+	#[error("Unsupported store schema version: {0}")]
+	UnsupportedVersion(i64),
+
+	/// A stored value does not decode.
+	#[error("Invalid {0} in store")]
+	Invalid(&'static str),
+}
+
+impl Classify for StoreError {
+	fn kind(&self) -> ErrorKind {
+		match self {
+			Self::Sqlite(e) => sqlite_error_kind(e),
+			Self::Json(_) | Self::UnsupportedVersion(_) | Self::Invalid(_) => {
+				ErrorKind::InvalidInput
+			}
+		}
+	}
+}
+
+/// Classify a `SQLite` error by its result code.
+///
+/// Errors raised by rusqlite itself (no result code) are API misuse or
+/// type mismatches between schema and code.
+fn sqlite_error_kind(error: &rusqlite::Error) -> ErrorKind {
+	use rusqlite::ErrorCode;
+
+	match error.sqlite_error_code() {
+		Some(
+			ErrorCode::DatabaseBusy
+			| ErrorCode::DatabaseLocked
+			| ErrorCode::OperationInterrupted
+			| ErrorCode::SystemIoFailure
+			| ErrorCode::FileLockingProtocolFailed
+			| ErrorCode::SchemaChanged,
+		) => ErrorKind::Transient,
+		Some(ErrorCode::OutOfMemory | ErrorCode::DiskFull | ErrorCode::TooBig) => {
+			ErrorKind::ResourceExhausted
+		}
+		Some(
+			ErrorCode::PermissionDenied
+			| ErrorCode::ReadOnly
+			| ErrorCode::DatabaseCorrupt
+			| ErrorCode::NotFound
+			| ErrorCode::CannotOpen
+			| ErrorCode::NotADatabase
+			| ErrorCode::ConstraintViolation
+			| ErrorCode::NoLargeFileSupport,
+		) => ErrorKind::InvalidInput,
+		_ => ErrorKind::Internal,
+	}
+}
+
+/// A moment matched by [`MemoryStore::search_text`].
+#[derive(Debug, Clone)]
+pub struct TextMatch {
+	/// The matching moment
+	pub moment: Moment,
+	/// Relevance; higher is better (negated FTS5 `bm25`)
+	pub score: f64,
+}
+
+/// A memory persisted in `SQLite`.
+#[derive(Debug)]
+pub struct MemoryStore {
+	conn: Connection,
+}
+
+impl MemoryStore {
+	/// Open or create a store file.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the file cannot be opened, is not a store, or
+	/// was written by a newer schema version.
+	pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+		Self::init(Connection::open(path)?)
+	}
+
+	/// Create a store that lives only as long as the value.
+	///
+	/// # Errors
+	///
+	/// Returns an error if `SQLite` cannot allocate the database.
+	pub fn open_in_memory() -> Result<Self, StoreError> {
+		Self::init(Connection::open_in_memory()?)
+	}
+
+	fn init(conn: Connection) -> Result<Self, StoreError> {
+		let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+		if version > STORE_SCHEMA_VERSION {
+			return Err(StoreError::UnsupportedVersion(version));
+		}
+		conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+		conn.execute_batch(SCHEMA)?;
+		conn.execute_batch(&format!("PRAGMA user_version = {STORE_SCHEMA_VERSION};"))?;
+		Ok(Self { conn })
+	}
+
+	// ========================================================================
+	// Moments
+	// ========================================================================
+
+	/// Insert or replace moments by id, with their tags and embeddings.
+	///
+	/// # Errors
+	///
+	/// Returns an error if a write fails; nothing is written in that case.
+	pub fn put_moments(&mut self, moments: &[Moment]) -> Result<(), StoreError> {
+		let tx = self.conn.transaction()?;
+		for moment in moments {
+			put_moment(&tx, moment)?;
+		}
+		tx.commit()?;
+		Ok(())
+	}
+
+	/// Insert or replace one moment.
+	///
+	/// # Errors
+	///
+	/// As [`put_moments`](Self::put_moments).
+	pub fn put_moment(&mut self, moment: &Moment) -> Result<(), StoreError> {
+		self.put_moments(std::slice::from_ref(moment))
+	}
+
+	/// The moment with `id`, if stored.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the read fails or the row does not decode.
+	pub fn moment(&self, id: usize) -> Result<Option<Moment>, StoreError> {
+		let sql = format!("SELECT {MOMENT_COLUMNS} FROM moments WHERE id = ?1");
+		let row = self
+			.conn
+			.query_row(&sql, params![id], MomentRow::read)
+			.optional()?;
+		row.map(|row| self.load(row)).transpose()
+	}
+
+	/// Remove a moment and every association touching it.
+	///
+	/// Episodes are history and keep their events. Returns `false` if
+	/// there was no such moment.
+	///
+	/// # Errors
+	///
+	/// Returns an error if a write fails; nothing is removed in that case.
+	pub fn remove_moment(&mut self, id: usize) -> Result<bool, StoreError> {
+		let tx = self.conn.transaction()?;
+		let removed = tx.execute("DELETE FROM moments WHERE id = ?1", params![id])?;
+		let _ = tx.execute(
+			"DELETE FROM associations WHERE source = ?1 OR target = ?1",
+			params![id],
+		)?;
+		tx.commit()?;
+		Ok(removed > 0)
+	}
+
+	/// Number of stored moments.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the read fails.
+	pub fn num_moments(&self) -> Result<usize, StoreError> {
+		Ok(self
+			.conn
+			.query_row("SELECT COUNT(*) FROM moments", [], |row| row.get(0))?)
+	}
+
+	/// Moments overlapping `start_ms..end_ms`, by start time.
+	///
+	/// An instantaneous moment matches if it falls inside the range.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the read fails or a row does not decode.
+	pub fn moments_between(&self, start_ms: f64, end_ms: f64) -> Result<Vec<Moment>, StoreError> {
+		let sql = format!(
+			"SELECT {MOMENT_COLUMNS} FROM moments
+			 WHERE start_ms < ?2 AND (end_ms > ?1 OR (end_ms <= start_ms AND start_ms >= ?1))
+			 ORDER BY start_ms, id"
+		);
+		self.query_moments(&sql, params![start_ms, end_ms])
+	}
+
+	/// Moments carrying `tag`, by start time.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the read fails or a row does not decode.
+	pub fn moments_with_tag(&self, tag: &str) -> Result<Vec<Moment>, StoreError> {
+		let sql = format!(
+			"SELECT {MOMENT_COLUMNS} FROM moments
+			 WHERE id IN (SELECT moment_id FROM tags WHERE tag = ?1)
+			 ORDER BY start_ms, id"
+		);
+		self.query_moments(&sql, params![tag])
+	}
+
+	/// Full-text search over moment text, best match first.
+	///
+	/// Each word of `query` is searched for on its own and matches are
+	/// ranked by BM25, so no FTS5 syntax is needed (or interpreted).
+	///
+	/// # Errors
+	///
+	/// Returns an error if the read fails or a row does not decode.
+	pub fn search_text(&self, query: &str, limit: usize) -> Result<Vec<TextMatch>, StoreError> {
+		let terms: Vec<String> = query
+			.split(|c: char| !c.is_alphanumeric())
+			.filter(|word| !word.is_empty())
+			.map(|word| format!("\"{word}\""))
+			.collect();
+		if terms.is_empty() || limit == 0 {
+			return Ok(Vec::new());
+		}
+
+		let sql = format!(
+			"SELECT {MOMENT_COLUMNS}, score FROM moments
+			 JOIN (SELECT rowid, bm25(moments_fts) AS score FROM moments_fts
+			       WHERE moments_fts MATCH ?1 ORDER BY score LIMIT ?2) AS hits
+			 ON moments.id = hits.rowid
+			 ORDER BY score, id"
+		);
+		let mut statement = self.conn.prepare_cached(&sql)?;
+		let rows = statement
+			.query_map(params![terms.join(" OR "), limit], |row| {
+				Ok((MomentRow::read(row)?, row.get::<_, f64>(6)?))
+			})?
+			.collect::<Result<Vec<_>, _>>()?;
+		rows.into_iter()
+			.map(|(row, bm25)| {
+				Ok(TextMatch {
+					moment: self.load(row)?,
+					score: -bm25,
+				})
+			})
+			.collect()
+	}
+
+	/// `(moment id, embedding)` for every stored embedding of `modality`,
+	/// by id.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the read fails or a vector does not decode.
+	pub fn embeddings(&self, modality: Modality) -> Result<Vec<(usize, Vec<f64>)>, StoreError> {
+		let mut statement = self.conn.prepare_cached(
+			"SELECT moment_id, vector FROM embeddings WHERE modality = ?1 ORDER BY moment_id",
+		)?;
+		let rows = statement
+			.query_map(params![modality_name(modality)], |row| {
+				Ok((row.get::<_, usize>(0)?, row.get::<_, Vec<u8>>(1)?))
+			})?
+			.collect::<Result<Vec<_>, _>>()?;
+		rows.into_iter()
+			.map(|(id, blob)| Ok((id, decode_vector(&blob)?)))
+			.collect()
+	}
+
+	fn query_moments(
+		&self,
+		sql: &str,
+		params: impl rusqlite::Params,
+	) -> Result<Vec<Moment>, StoreError> {
+		let mut statement = self.conn.prepare_cached(sql)?;
+		let rows = statement
+			.query_map(params, MomentRow::read)?
+			.collect::<Result<Vec<_>, _>>()?;
+		rows.into_iter().map(|row| self.load(row)).collect()
+	}
+
+	/// Decode a moment row and attach its tags and embeddings.
+	fn load(&self, row: MomentRow) -> Result<Moment, StoreError> {
+		let mut moment = Moment::new(
+			row.id,
+			parse_modality(&row.modality).ok_or(StoreError::Invalid("modality"))?,
+			row.start_ms,
+			row.end_ms,
+		);
+		moment.text = row.text;
+		moment.media = serde_json::from_str::<Vec<MediaRef>>(&row.media)?;
+
+		let mut tags = self
+			.conn
+			.prepare_cached("SELECT tag FROM tags WHERE moment_id = ?1 ORDER BY rowid")?;
+		moment.tags = tags
+			.query_map(params![row.id], |r| r.get(0))?
+			.collect::<Result<_, _>>()?;
+
+		let mut embeddings = self.conn.prepare_cached(
+			"SELECT modality, vector FROM embeddings WHERE moment_id = ?1 ORDER BY rowid",
+		)?;
+		let stored = embeddings
+			.query_map(params![row.id], |r| {
+				Ok((r.get::<_, String>(0)?, r.get::<_, Vec<u8>>(1)?))
+			})?
+			.collect::<Result<Vec<_>, _>>()?;
+		for (modality, blob) in stored {
+			let modality = parse_modality(&modality).ok_or(StoreError::Invalid("modality"))?;
+			moment.set_embedding(modality, decode_vector(&blob)?);
+		}
+		Ok(moment)
+	}
+
+	// ========================================================================
+	// Associations
+	// ========================================================================
+
+	/// Insert or replace associations by `(source, target)`.
+	///
+	/// # Errors
+	///
+	/// Returns an error if a write fails; nothing is written in that case.
+	pub fn put_associations(&mut self, associations: &[Association]) -> Result<(), StoreError> {
+		let tx = self.conn.transaction()?;
+		{
+			let mut statement = tx.prepare_cached(
+				"INSERT OR REPLACE INTO associations
+				 (source, target, forward_strength, backward_strength, association_type)
+				 VALUES (?1, ?2, ?3, ?4, ?5)",
+			)?;
+			for association in associations {
+				let _ = statement.execute(params![
+					association.source,
+					association.target,
+					association.forward_strength,
+					association.backward_strength,
+					association.association_type.as_str(),
+				])?;
+			}
+		}
+		tx.commit()?;
+		Ok(())
+	}
+
+	/// Remove the association from `source` to `target`. Returns `false`
+	/// if there was none.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the write fails.
+	pub fn remove_association(&mut self, source: usize, target: usize) -> Result<bool, StoreError> {
+		let removed = self.conn.execute(
+			"DELETE FROM associations WHERE source = ?1 AND target = ?2",
+			params![source, target],
+		)?;
+		Ok(removed > 0)
+	}
+
+	/// Every association, sorted by `(source, target)`.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the read fails or a type does not decode.
+	pub fn associations(&self) -> Result<Vec<Association>, StoreError> {
+		self.query_associations("SELECT * FROM associations ORDER BY source, target", [])
+	}
+
+	/// Associations with `node` at either end, sorted by `(source, target)`.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the read fails or a type does not decode.
+	pub fn associations_of(&self, node: usize) -> Result<Vec<Association>, StoreError> {
+		self.query_associations(
+			"SELECT * FROM associations WHERE source = ?1 OR target = ?1 ORDER BY source, target",
+			params![node],
+		)
+	}
+
+	fn query_associations(
+		&self,
+		sql: &str,
+		params: impl rusqlite::Params,
+	) -> Result<Vec<Association>, StoreError> {
+		let mut statement = self.conn.prepare_cached(sql)?;
+		let rows = statement
+			.query_map(params, |row| {
+				Ok((
+					row.get::<_, usize>(0)?,
+					row.get::<_, usize>(1)?,
+					row.get::<_, f64>(2)?,
+					row.get::<_, f64>(3)?,
+					row.get::<_, String>(4)?,
+				))
+			})?
+			.collect::<Result<Vec<_>, _>>()?;
+		rows.into_iter()
+			.map(
+				|(source, target, forward_strength, backward_strength, name)| {
+					Ok(Association {
+						source,
+						target,
+						forward_strength,
+						backward_strength,
+						association_type: parse_association_type(&name)
+							.ok_or(StoreError::Invalid("association type"))?,
+					})
+				},
+			)
+			.collect()
+	}
+
+	/// Build an editable graph of every stored association, with a node
+	/// for each moment id and association endpoint.
+	///
+	/// # Errors
+	///
+	/// As [`associations`](Self::associations).
+	pub fn memory_graph(&self) -> Result<MemoryGraph, StoreError> {
+		let associations = self.associations()?;
+		let max_moment: Option<usize> =
+			self.conn
+				.query_row("SELECT MAX(id) FROM moments", [], |row| row.get(0))?;
+		let num_nodes = associations
+			.iter()
+			.map(|a| a.source.max(a.target) + 1)
+			.chain(max_moment.map(|id| id + 1))
+			.max()
+			.unwrap_or(0);
+		Ok(MemoryGraph::from_associations(num_nodes, associations))
+	}
+
+	// ========================================================================
+	// Episodes
+	// ========================================================================
+
+	/// Store an episode and return its id.
+	///
+	/// # Errors
+	///
+	/// Returns an error if a write fails; nothing is written in that case.
+	pub fn add_episode(&mut self, episode: &Episode) -> Result<i64, StoreError> {
+		let tx = self.conn.transaction()?;
+		let _ = tx.execute("INSERT INTO episodes DEFAULT VALUES", [])?;
+		let id = tx.last_insert_rowid();
+		{
+			let mut events = tx.prepare_cached(
+				"INSERT INTO episode_events (episode_id, position, moment_id) VALUES (?1, ?2, ?3)",
+			)?;
+			for (position, &moment) in episode.events.iter().enumerate() {
+				let _ = events.execute(params![id, position, moment])?;
+			}
+			let mut links = tx.prepare_cached(
+				"INSERT INTO temporal_links
+				 (episode_id, source_position, target_position, source_memory, target_memory,
+				  forward_strength, backward_strength)
+				 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+			)?;
+			for link in &episode.links {
+				let _ = links.execute(params![
+					id,
+					link.source_position,
+					link.target_position,
+					link.source_memory,
+					link.target_memory,
+					link.forward_strength,
+					link.backward_strength,
+				])?;
+			}
+		}
+		tx.commit()?;
+		Ok(id)
+	}
+
+	/// The episode with `id`, if stored.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the read fails.
+	pub fn episode(&self, id: i64) -> Result<Option<Episode>, StoreError> {
+		let exists: Option<i64> = self
+			.conn
+			.query_row(
+				"SELECT id FROM episodes WHERE id = ?1",
+				params![id],
+				|row| row.get(0),
+			)
+			.optional()?;
+		if exists.is_none() {
+			return Ok(None);
+		}
+
+		let mut events = self.conn.prepare_cached(
+			"SELECT moment_id FROM episode_events WHERE episode_id = ?1 ORDER BY position",
+		)?;
+		let events = events
+			.query_map(params![id], |row| row.get(0))?
+			.collect::<Result<Vec<usize>, _>>()?;
+		let mut links = self.conn.prepare_cached(
+			"SELECT source_position, target_position, source_memory, target_memory,
+			        forward_strength, backward_strength
+			 FROM temporal_links WHERE episode_id = ?1 ORDER BY rowid",
+		)?;
+		let links = links
+			.query_map(params![id], |row| {
+				Ok(TemporalLink {
+					source_position: row.get(0)?,
+					target_position: row.get(1)?,
+					source_memory: row.get(2)?,
+					target_memory: row.get(3)?,
+					forward_strength: row.get(4)?,
+					backward_strength: row.get(5)?,
+				})
+			})?
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(Some(Episode { events, links }))
+	}
+
+	/// Ids of the episodes containing moment `id`, oldest first.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the read fails.
+	pub fn episodes_containing(&self, id: usize) -> Result<Vec<i64>, StoreError> {
+		let mut statement = self.conn.prepare_cached(
+			"SELECT DISTINCT episode_id FROM episode_events WHERE moment_id = ?1 ORDER BY episode_id",
+		)?;
+		let ids = statement
+			.query_map(params![id], |row| row.get(0))?
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(ids)
+	}
+}
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+/// A `moments` row before its tags and embeddings are attached.
+struct MomentRow {
+	id: usize,
+	modality: String,
+	start_ms: f64,
+	end_ms: f64,
+	text: String,
+	media: String,
+}
+
+impl MomentRow {
+	fn read(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+		Ok(Self {
+			id: row.get(0)?,
+			modality: row.get(1)?,
+			start_ms: row.get(2)?,
+			end_ms: row.get(3)?,
+			text: row.get(4)?,
+			media: row.get(5)?,
+		})
+	}
+}
+
+fn put_moment(tx: &Transaction<'_>, moment: &Moment) -> Result<(), StoreError> {
+	let media = serde_json::to_string(&moment.media)?;
+	let _ = tx.execute(
+		"INSERT INTO moments (id, modality, start_ms, end_ms, text, media)
+		 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+		 ON CONFLICT (id) DO UPDATE SET
+		 modality = excluded.modality, start_ms = excluded.start_ms, end_ms = excluded.end_ms,
+		 text = excluded.text, media = excluded.media",
+		params![
+			moment.id,
+			modality_name(moment.modality),
+			moment.start_ms,
+			moment.end_ms,
+			moment.text,
+			media,
+		],
+	)?;
+
+	let _ = tx.execute("DELETE FROM tags WHERE moment_id = ?1", params![moment.id])?;
+	let mut tags =
+		tx.prepare_cached("INSERT OR IGNORE INTO tags (moment_id, tag) VALUES (?1, ?2)")?;
+	for tag in &moment.tags {
+		let _ = tags.execute(params![moment.id, tag])?;
+	}
+
+	let _ = tx.execute(
+		"DELETE FROM embeddings WHERE moment_id = ?1",
+		params![moment.id],
+	)?;
+	let mut embeddings = tx.prepare_cached(
+		"INSERT OR REPLACE INTO embeddings (moment_id, modality, vector) VALUES (?1, ?2, ?3)",
+	)?;
+	for embedding in &moment.embeddings {
+		let _ = embeddings.execute(params![
+			moment.id,
+			modality_name(embedding.modality),
+			encode_vector(&embedding.vector),
+		])?;
+	}
+	Ok(())
+}
+
+const fn modality_name(modality: Modality) -> &'static str {
+	match modality {
+		Modality::Text => "text",
+		Modality::Visual => "visual",
+		Modality::Audio => "audio",
+	}
+}
+
+fn parse_modality(name: &str) -> Option<Modality> {
+	match name {
+		"text" => Some(Modality::Text),
+		"visual" => Some(Modality::Visual),
+		"audio" => Some(Modality::Audio),
+		_ => None,
+	}
+}
+
+/// Exact inverse of [`AssociationType::as_str`], the only form stored.
+fn parse_association_type(name: &str) -> Option<AssociationType> {
+	match name {
+		"semantic" => Some(AssociationType::Semantic),
+		"temporal" => Some(AssociationType::Temporal),
+		"causal" => Some(AssociationType::Causal),
+		"entity" => Some(AssociationType::Entity),
+		"visual_similarity" => Some(AssociationType::VisualSimilarity),
+		_ => None,
+	}
+}
+
+fn encode_vector(vector: &[f64]) -> Vec<u8> {
+	vector.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn decode_vector(blob: &[u8]) -> Result<Vec<f64>, StoreError> {
+	let chunks = blob.chunks_exact(8);
+	if !chunks.remainder().is_empty() {
+		return Err(StoreError::Invalid("embedding"));
+	}
+	Ok(chunks
+		.map(|chunk| {
+			let mut bytes = [0; 8];
+			bytes.copy_from_slice(chunk);
+			f64::from_le_bytes(bytes)
+		})
+		.collect())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn make_moment(id: usize, start_ms: f64, end_ms: f64, text: &str) -> Moment {
+		let mut moment = Moment::new(id, Modality::Audio, start_ms, end_ms);
+		moment.text = text.to_string();
+		moment
+	}
+
+	#[test]
+	fn test_moments_round_trip_and_queries() {
+		let store = MemoryStore::open_in_memory();
+		assert!(store.is_ok());
+		let Ok(mut store) = store else { return };
+
+		let mut scene = make_moment(0, 0.0, 10_000.0, "");
+		scene.modality = Modality::Visual;
+		scene.media.push(MediaRef {
+			modality: Modality::Visual,
+			uri: "frame_0.jpg".to_string(),
+			offset_ms: Some(0.0),
+			index: Some(0),
+		});
+		scene.set_embedding(Modality::Visual, vec![0.25, -1.5]);
+		scene.tags.push("scene".to_string());
+		let moments = vec![
+			scene.clone(),
+			make_moment(1, 2_000.0, 4_000.0, "The billing service crashed"),
+			make_moment(2, 12_000.0, 15_000.0, "Rolled back billing, deploy fixed"),
+		];
+		assert!(store.put_moments(&moments).is_ok());
+		assert_eq!(store.num_moments().ok(), Some(3));
+		assert_eq!(store.moment(0).ok().flatten(), Some(scene));
+
+		let ids = |found: Vec<Moment>| found.iter().map(|m| m.id).collect::<Vec<_>>();
+		assert_eq!(
+			store.moments_between(3_000.0, 11_000.0).map(ids).ok(),
+			Some(vec![0, 1])
+		);
+		assert_eq!(store.moments_with_tag("scene").map(ids).ok(), Some(vec![0]));
+
+		let hits = store.search_text("billing deploy", 10);
+		assert!(hits.is_ok());
+		let Ok(hits) = hits else { return };
+		assert_eq!(
+			hits.iter().map(|h| h.moment.id).collect::<Vec<_>>(),
+			vec![2, 1]
+		);
+		assert!(hits[0].score > hits[1].score);
+		assert!(store.search_text("\" OR *", 10).is_ok_and(|h| h.is_empty()));
+
+		// Replacing a moment re-indexes its text
+		assert!(store
+			.put_moment(&make_moment(1, 2_000.0, 4_000.0, "lunch"))
+			.is_ok());
+		assert!(store.search_text("billing", 10).is_ok_and(|h| h.len() == 1));
+
+		let visual = store.embeddings(Modality::Visual);
+		assert_eq!(visual.ok(), Some(vec![(0, vec![0.25, -1.5])]));
+	}
+
+	#[test]
+	fn test_associations_and_episodes() {
+		let store = MemoryStore::open_in_memory();
+		assert!(store.is_ok());
+		let Ok(mut store) = store else { return };
+		let moments: Vec<Moment> = (0..3).map(|i| make_moment(i, 0.0, 0.0, "")).collect();
+		assert!(store.put_moments(&moments).is_ok());
+
+		let association = |source, target, strength| Association {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength,
+			association_type: AssociationType::VisualSimilarity,
+		};
+		assert!(store
+			.put_associations(&[association(1, 2, 0.5), association(0, 1, 0.25)])
+			.is_ok());
+		assert!(store.put_associations(&[association(0, 1, 0.75)]).is_ok());
+		let stored = store.associations();
+		assert!(stored.is_ok());
+		let Ok(stored) = stored else { return };
+		assert_eq!(stored.len(), 2);
+		assert!((stored[0].forward_strength - 0.75).abs() < 1e-12);
+		assert_eq!(
+			stored[1].association_type,
+			AssociationType::VisualSimilarity
+		);
+
+		let graph = store.memory_graph();
+		assert!(graph.is_ok_and(|g| g.num_nodes() == 3 && g.num_edges() == 2));
+
+		let episode = Episode::new(vec![2, 0, 1], &crate::TemporalSpreadingConfig::default());
+		let id = store.add_episode(&episode);
+		assert!(id.is_ok());
+		let Ok(id) = id else { return };
+		let loaded = store.episode(id);
+		assert!(loaded.is_ok());
+		let Ok(Some(loaded)) = loaded else { return };
+		assert_eq!(loaded.events, episode.events);
+		assert_eq!(loaded.links.len(), episode.links.len());
+		assert_eq!(store.episodes_containing(0).ok(), Some(vec![id]));
+
+		// Removing a moment drops its associations but not its episodes
+		assert!(store.remove_moment(1).is_ok_and(|removed| removed));
+		assert!(store.associations().is_ok_and(|a| a.is_empty()));
+		assert_eq!(store.episodes_containing(1).ok(), Some(vec![id]));
+		assert_eq!(store.moment(1).ok(), Some(None));
+	}
+
+	#[test]
+	fn test_remove_association() {
+		let store = MemoryStore::open_in_memory();
+		assert!(store.is_ok());
+		let Ok(mut store) = store else { return };
+		let association = |source, target| Association {
+			source,
+			target,
+			forward_strength: 0.5,
+			backward_strength: 0.5,
+			association_type: AssociationType::Causal,
+		};
+		assert!(store
+			.put_associations(&[association(0, 1), association(1, 0), association(1, 2)])
+			.is_ok());
+
+		assert!(store.remove_association(0, 1).is_ok_and(|removed| removed));
+		assert!(store.remove_association(0, 1).is_ok_and(|removed| !removed));
+		assert!(store.remove_association(2, 1).is_ok_and(|removed| !removed));
+		let remaining = store.associations();
+		assert!(remaining.is_ok());
+		let Ok(remaining) = remaining else { return };
+		assert_eq!(
+			remaining
+				.iter()
+				.map(|a| (a.source, a.target))
+				.collect::<Vec<_>>(),
+			vec![(1, 0), (1, 2)]
+		);
+		assert!(store.associations_of(0).is_ok_and(|a| a.len() == 1));
+	}
+
+	#[test]
+	fn test_moments_between_boundaries() {
+		let store = MemoryStore::open_in_memory();
+		assert!(store.is_ok());
+		let Ok(mut store) = store else { return };
+		let moments = vec![
+			make_moment(0, 0.0, 1_000.0, ""),
+			make_moment(1, 1_000.0, 1_000.0, ""),
+			make_moment(2, 2_000.0, 3_000.0, ""),
+		];
+		assert!(store.put_moments(&moments).is_ok());
+
+		let ids = |start, end| {
+			store
+				.moments_between(start, end)
+				.map(|found| found.iter().map(|m| m.id).collect::<Vec<_>>())
+				.ok()
+		};
+		// Ranges are half-open: touching a moment's end does not overlap it,
+		// but an instantaneous moment at the range start is inside.
+		assert_eq!(ids(1_000.0, 2_000.0), Some(vec![1]));
+		assert_eq!(ids(0.0, 1_000.0), Some(vec![0]));
+		// A zero-width range finds the moments spanning that instant
+		assert_eq!(ids(500.0, 500.0), Some(vec![0]));
+		assert_eq!(ids(3_000.0, 2_000.0), Some(vec![]));
+		assert_eq!(ids(f64::NEG_INFINITY, f64::INFINITY), Some(vec![0, 1, 2]));
+	}
+
+	#[test]
+	fn test_reopen_file_store() {
+		let path = std::env::temp_dir().join(format!("lucid-store-{}.db", std::process::id()));
+		let _ = std::fs::remove_file(&path);
+
+		{
+			let store = MemoryStore::open(&path);
+			assert!(store.is_ok());
+			let Ok(mut store) = store else { return };
+			assert!(store
+				.put_moment(&make_moment(0, 0.0, 1_000.0, "first light"))
+				.is_ok());
+			assert!(store
+				.put_associations(&[Association {
+					source: 0,
+					target: 1,
+					forward_strength: 0.5,
+					backward_strength: 0.25,
+					association_type: AssociationType::VisualSimilarity,
+				}])
+				.is_ok());
+		}
+
+		let store = MemoryStore::open(&path);
+		assert!(store.is_ok());
+		if let Ok(store) = store {
+			assert_eq!(store.num_moments().ok(), Some(1));
+			assert!(store
+				.search_text("light", 10)
+				.is_ok_and(|hits| hits.len() == 1));
+			let associations = store.associations();
+			assert!(associations.is_ok());
+			if let Ok(associations) = associations {
+				assert_eq!(
+					associations
+						.iter()
+						.map(|a| a.association_type)
+						.collect::<Vec<_>>(),
+					vec![AssociationType::VisualSimilarity]
+				);
+			}
+			let stored: Result<String, _> = store.conn.query_row(
+				"SELECT association_type FROM associations WHERE source = 0",
+				[],
+				|row| row.get(0),
+			);
+			assert_eq!(stored.ok().as_deref(), Some("visual_similarity"));
+		}
+		let _ = std::fs::remove_file(&path);
+	}
+}
@@ -0,0 +1,572 @@
+//! Synthetic Data
+//!
+//! Generators for memory graphs and episode streams with the shape of real
+//! recordings, for benchmarking spreading and recall and tuning configs
+//! before there is real data at scale.
+//!
+//! ## Graphs
+//!
+//! Nodes are split into equal, contiguous clusters (topics). Each node gets
+//! a weight from the degree distribution, scaled so the weights average
+//! `mean_degree`; a node's out-degree is its weight, and link targets are
+//! drawn in proportion to weight (Chung & Lu, 2002), so high-weight nodes
+//! become hubs. With probability `intra_cluster_probability` a target is
+//! drawn from the source's own cluster and the link is `Semantic`;
+//! otherwise it comes from the whole graph and the link is `Entity`, the
+//! kind of bridge a shared person or project makes between topics.
+//!
+//! ## Episodes
+//!
+//! Episodes start after exponentially distributed gaps (a Poisson process)
+//! and hold events at exponentially distributed intervals. With
+//! `active_hours` set, an episode that would start outside the daily
+//! window is moved to the start of the next one, giving the day/night
+//! rhythm of an always-on recorder. Each episode dwells on one topic: an
+//! event is drawn from the episode's cluster with probability
+//! `topic_focus`, otherwise from anywhere.
+//!
+//! Everything is drawn from a seeded generator, so the same config and
+//! seed always give the same data.
+
+use serde::{Deserialize, Serialize};
+
+use crate::episodes::Episode;
+use crate::graph::MemoryGraph;
+use crate::rng::SplitMix64;
+use crate::spreading::{Association, AssociationType, TemporalSpreadingConfig};
+
+/// Milliseconds in a day
+const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Draws per link before giving up on finding a new target
+const MAX_TARGET_ATTEMPTS: usize = 16;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// How node degrees are distributed.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DegreeDistribution {
+	/// Every node has about the mean degree
+	Uniform,
+	/// Pareto-distributed degrees, `P(k) ∝ k^(-exponent)`; a few hubs and
+	/// many sparsely linked nodes. Exponents between 2 and 3 match most
+	/// real networks
+	PowerLaw {
+		/// Tail exponent, > 1
+		exponent: f64,
+	},
+}
+
+impl Default for DegreeDistribution {
+	fn default() -> Self {
+		Self::PowerLaw { exponent: 2.5 }
+	}
+}
+
+/// Configuration for [`generate_graph`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SyntheticGraphConfig {
+	/// Number of nodes (default: 1000)
+	pub num_nodes: usize,
+	/// Average out-links per node (default: 8.0)
+	pub mean_degree: f64,
+	/// Shape of the degree distribution (default: power law, exponent 2.5)
+	pub degree_distribution: DegreeDistribution,
+	/// Number of topic clusters; 0 is treated as 1 (default: 10)
+	pub num_clusters: usize,
+	/// Probability that a link stays inside its source's cluster
+	/// (default: 0.8)
+	pub intra_cluster_probability: f64,
+	/// Range forward and backward strengths are drawn from, uniformly
+	/// (default: 0.2 to 0.9)
+	pub strength_range: (f64, f64),
+	/// Random seed (default: 0)
+	pub seed: u64,
+}
+
+impl Default for SyntheticGraphConfig {
+	fn default() -> Self {
+		Self {
+			num_nodes: 1000,
+			mean_degree: 8.0,
+			degree_distribution: DegreeDistribution::default(),
+			num_clusters: 10,
+			intra_cluster_probability: 0.8,
+			strength_range: (0.2, 0.9),
+			seed: 0,
+		}
+	}
+}
+
+/// Configuration for [`generate_episodes`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SyntheticEpisodeConfig {
+	/// Number of episodes (default: 100)
+	pub num_episodes: usize,
+	/// Average events per episode; counts are drawn uniformly from half to
+	/// one and a half times this (default: 12)
+	pub mean_events_per_episode: usize,
+	/// Time of the first episode, in ms (default: 0)
+	pub start_ms: f64,
+	/// Average time between events in an episode, in ms (default: 5000)
+	pub mean_event_gap_ms: f64,
+	/// Average time between the end of one episode and the start of the
+	/// next, in ms (default: 1800000, half an hour)
+	pub mean_episode_gap_ms: f64,
+	/// Daily window `(start_hour, end_hour)` episodes may start in, in
+	/// hours since midnight of `start_ms`'s day; `None` allows any time
+	/// (default: 8 to 22)
+	pub active_hours: Option<(f64, f64)>,
+	/// Probability that an event comes from the episode's topic cluster
+	/// (default: 0.8)
+	pub topic_focus: f64,
+	/// Strengths of the episodes' temporal links
+	pub temporal: TemporalSpreadingConfig,
+	/// Random seed (default: 0)
+	pub seed: u64,
+}
+
+impl Default for SyntheticEpisodeConfig {
+	fn default() -> Self {
+		Self {
+			num_episodes: 100,
+			mean_events_per_episode: 12,
+			start_ms: 0.0,
+			mean_event_gap_ms: 5000.0,
+			mean_episode_gap_ms: 30.0 * 60.0 * 1000.0,
+			active_hours: Some((8.0, 22.0)),
+			topic_focus: 0.8,
+			temporal: TemporalSpreadingConfig::default(),
+			seed: 0,
+		}
+	}
+}
+
+// ============================================================================
+// Results
+// ============================================================================
+
+/// A generated graph and the cluster of each node.
+#[derive(Clone, Debug)]
+pub struct SyntheticGraph {
+	/// The association graph
+	pub graph: MemoryGraph,
+	/// Cluster of each node
+	pub clusters: Vec<usize>,
+}
+
+impl SyntheticGraph {
+	/// Number of clusters.
+	#[must_use]
+	pub fn num_clusters(&self) -> usize {
+		self.clusters.iter().max().map_or(0, |&c| c + 1)
+	}
+
+	/// Nodes of each cluster, in index order.
+	#[must_use]
+	pub fn cluster_members(&self) -> Vec<Vec<usize>> {
+		let mut members = vec![Vec::new(); self.num_clusters()];
+		for (node, &cluster) in self.clusters.iter().enumerate() {
+			members[cluster].push(node);
+		}
+		members
+	}
+}
+
+/// A generated stream of episodes over a graph's nodes.
+#[derive(Clone, Debug, Default)]
+pub struct SyntheticEpisodes {
+	/// Episodes in time order; events are graph nodes
+	pub episodes: Vec<Episode>,
+	/// Time of each event, in ms, parallel to each episode's events
+	pub timestamps_ms: Vec<Vec<f64>>,
+}
+
+impl SyntheticEpisodes {
+	/// Times each of `num_nodes` nodes occurred as an event, in time order,
+	/// for base-level activation.
+	#[must_use]
+	pub fn access_histories_ms(&self, num_nodes: usize) -> Vec<Vec<f64>> {
+		let mut histories = vec![Vec::new(); num_nodes];
+		for (episode, times) in self.episodes.iter().zip(&self.timestamps_ms) {
+			for (&node, &time) in episode.events.iter().zip(times) {
+				if let Some(history) = histories.get_mut(node) {
+					history.push(time);
+				}
+			}
+		}
+		histories
+	}
+
+	/// Time of the last event, if any.
+	#[must_use]
+	pub fn end_ms(&self) -> Option<f64> {
+		self.timestamps_ms.last().and_then(|t| t.last()).copied()
+	}
+}
+
+// ============================================================================
+// Generators
+// ============================================================================
+
+/// Generate a clustered association graph.
+#[must_use]
+pub fn generate_graph(config: &SyntheticGraphConfig) -> SyntheticGraph {
+	let n = config.num_nodes;
+	let k = config.num_clusters.clamp(1, n.max(1));
+	let mut rng = SplitMix64::new(config.seed);
+
+	let clusters: Vec<usize> = (0..n).map(|node| node * k / n.max(1)).collect();
+	let mut graph = MemoryGraph::new(n);
+	if n < 2 {
+		return SyntheticGraph { graph, clusters };
+	}
+
+	let weights = degree_weights(n, config, &mut rng);
+	let global = WeightedSampler::new((0..n).collect(), &weights);
+	let by_cluster: Vec<WeightedSampler> = (0..k)
+		.map(|cluster| {
+			let members = (0..n).filter(|&node| clusters[node] == cluster).collect();
+			WeightedSampler::new(members, &weights)
+		})
+		.collect();
+
+	let (low, high) = config.strength_range;
+	for source in 0..n {
+		let degree = stochastic_round(weights[source], &mut rng).min(n - 1);
+		for _ in 0..degree {
+			let intra = rng.next_f64() < config.intra_cluster_probability;
+			let sampler = if intra {
+				&by_cluster[clusters[source]]
+			} else {
+				&global
+			};
+			let target = (0..MAX_TARGET_ATTEMPTS)
+				.map(|_| sampler.sample(&mut rng))
+				.find(|&t| t != source && graph.association(source, t).is_none());
+			let Some(target) = target else { continue };
+
+			let association_type = if clusters[target] == clusters[source] {
+				AssociationType::Semantic
+			} else {
+				AssociationType::Entity
+			};
+			let _ = graph.insert_association(Association {
+				source,
+				target,
+				forward_strength: (high - low).mul_add(rng.next_f64(), low),
+				backward_strength: (high - low).mul_add(rng.next_f64(), low),
+				association_type,
+			});
+		}
+	}
+
+	SyntheticGraph { graph, clusters }
+}
+
+/// Generate an episode stream over the nodes of `graph`.
+#[must_use]
+pub fn generate_episodes(
+	graph: &SyntheticGraph,
+	config: &SyntheticEpisodeConfig,
+) -> SyntheticEpisodes {
+	let members = graph.cluster_members();
+	let n = graph.clusters.len();
+	if n == 0 {
+		return SyntheticEpisodes::default();
+	}
+	let mut rng = SplitMix64::new(config.seed);
+
+	let mean = config.mean_events_per_episode.max(1);
+	let (min_events, max_events) = ((mean / 2).max(1), mean + mean / 2);
+
+	let mut result = SyntheticEpisodes::default();
+	let mut time = config.start_ms;
+	for _ in 0..config.num_episodes {
+		time = next_active_time(time, config.start_ms, config.active_hours);
+
+		let topic = &members[uniform_index(members.len(), &mut rng)];
+		let count = min_events + uniform_index(max_events - min_events + 1, &mut rng);
+		let mut events = Vec::with_capacity(count);
+		let mut times = Vec::with_capacity(count);
+		for i in 0..count {
+			if i > 0 {
+				time += exponential(config.mean_event_gap_ms, &mut rng);
+			}
+			let node = if rng.next_f64() < config.topic_focus {
+				topic[uniform_index(topic.len(), &mut rng)]
+			} else {
+				uniform_index(n, &mut rng)
+			};
+			events.push(node);
+			times.push(time);
+		}
+
+		result.episodes.push(Episode::new(events, &config.temporal));
+		result.timestamps_ms.push(times);
+		time += exponential(config.mean_episode_gap_ms, &mut rng);
+	}
+
+	result
+}
+
+/// Embeddings clustered by `clusters`: each cluster gets a random unit
+/// centroid and each node its centroid plus Gaussian noise of standard
+/// deviation `spread` per dimension.
+#[must_use]
+pub fn generate_embeddings(
+	clusters: &[usize],
+	dimensions: usize,
+	spread: f64,
+	seed: u64,
+) -> Vec<Vec<f64>> {
+	let mut rng = SplitMix64::new(seed);
+	let num_clusters = clusters.iter().max().map_or(0, |&c| c + 1);
+	let centroids: Vec<Vec<f64>> = (0..num_clusters)
+		.map(|_| {
+			let v: Vec<f64> = (0..dimensions).map(|_| gaussian(&mut rng)).collect();
+			let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+			v.into_iter()
+				.map(|x| if norm > 0.0 { x / norm } else { 0.0 })
+				.collect()
+		})
+		.collect();
+
+	clusters
+		.iter()
+		.map(|&cluster| {
+			centroids[cluster]
+				.iter()
+				.map(|&c| spread.mul_add(gaussian(&mut rng), c))
+				.collect()
+		})
+		.collect()
+}
+
+// ============================================================================
+// Sampling
+// ============================================================================
+
+/// Per-node weights averaging `mean_degree`.
+fn degree_weights(n: usize, config: &SyntheticGraphConfig, rng: &mut SplitMix64) -> Vec<f64> {
+	let raw: Vec<f64> = match config.degree_distribution {
+		DegreeDistribution::Uniform => vec![1.0; n],
+		DegreeDistribution::PowerLaw { exponent } => {
+			let alpha = (exponent - 1.0).max(f64::EPSILON);
+			(0..n)
+				.map(|_| (1.0 - rng.next_f64()).powf(-1.0 / alpha))
+				.collect()
+		}
+	};
+	#[allow(clippy::cast_precision_loss)]
+	let mean = raw.iter().sum::<f64>() / n as f64;
+	raw.into_iter()
+		.map(|w| w / mean * config.mean_degree.max(0.0))
+		.collect()
+}
+
+/// Draws items in proportion to their weight.
+struct WeightedSampler {
+	items: Vec<usize>,
+	/// Running total of item weights
+	cumulative: Vec<f64>,
+}
+
+impl WeightedSampler {
+	fn new(items: Vec<usize>, weights: &[f64]) -> Self {
+		let mut total = 0.0;
+		let cumulative = items
+			.iter()
+			.map(|&item| {
+				total += weights[item].max(0.0);
+				total
+			})
+			.collect();
+		Self { items, cumulative }
+	}
+
+	fn sample(&self, rng: &mut SplitMix64) -> usize {
+		let total = self.cumulative.last().copied().unwrap_or(0.0);
+		if total <= 0.0 {
+			return self.items[uniform_index(self.items.len(), rng)];
+		}
+		let x = rng.next_f64() * total;
+		let i = self.cumulative.partition_point(|&c| c <= x);
+		self.items[i.min(self.items.len() - 1)]
+	}
+}
+
+/// Uniform index in `0..len`; `len` must be nonzero.
+#[allow(
+	clippy::cast_possible_truncation,
+	clippy::cast_sign_loss,
+	clippy::cast_precision_loss
+)]
+fn uniform_index(len: usize, rng: &mut SplitMix64) -> usize {
+	((rng.next_f64() * len as f64) as usize).min(len - 1)
+}
+
+/// `floor(x)` plus one with probability `fract(x)`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn stochastic_round(x: f64, rng: &mut SplitMix64) -> usize {
+	let floor = x.floor();
+	let extra = usize::from(rng.next_f64() < x - floor);
+	floor.max(0.0) as usize + extra
+}
+
+/// Exponential sample with the given mean.
+fn exponential(mean: f64, rng: &mut SplitMix64) -> f64 {
+	-mean.max(0.0) * (1.0 - rng.next_f64()).ln()
+}
+
+/// Standard normal sample (Box–Muller).
+fn gaussian(rng: &mut SplitMix64) -> f64 {
+	let u = (1.0 - rng.next_f64()).max(f64::MIN_POSITIVE);
+	let v = rng.next_f64();
+	(-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+}
+
+/// `time`, or the start of the next active window if it falls outside one.
+fn next_active_time(time: f64, origin_ms: f64, active_hours: Option<(f64, f64)>) -> f64 {
+	let Some((start_hour, end_hour)) = active_hours else {
+		return time;
+	};
+	let hour_ms = DAY_MS / 24.0;
+	let since_origin = time - origin_ms;
+	let day_start = (since_origin / DAY_MS).floor().mul_add(DAY_MS, origin_ms);
+	let hour = (time - day_start) / hour_ms;
+
+	if end_hour <= start_hour {
+		// Window wraps past midnight, e.g. 22 to 6
+		if hour >= start_hour || hour < end_hour {
+			return time;
+		}
+		return start_hour.mul_add(hour_ms, day_start);
+	}
+	if hour < start_hour {
+		start_hour.mul_add(hour_ms, day_start)
+	} else if hour >= end_hour {
+		start_hour.mul_add(hour_ms, day_start + DAY_MS)
+	} else {
+		time
+	}
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_graph_reproducible_and_clustered() {
+		let config = SyntheticGraphConfig {
+			num_nodes: 500,
+			seed: 7,
+			..SyntheticGraphConfig::default()
+		};
+		let a = generate_graph(&config);
+		let b = generate_graph(&config);
+		assert_eq!(a.graph.num_nodes(), 500);
+		assert_eq!(a.num_clusters(), 10);
+		assert_eq!(a.graph.num_edges(), b.graph.num_edges());
+
+		#[allow(clippy::cast_precision_loss)]
+		let mean_degree = a.graph.num_edges() as f64 / 500.0;
+		assert!((mean_degree - 8.0).abs() < 1.5, "mean degree {mean_degree}");
+
+		let associations = a.graph.associations();
+		let intra = associations
+			.iter()
+			.filter(|e| a.clusters[e.source] == a.clusters[e.target])
+			.count();
+		#[allow(clippy::cast_precision_loss)]
+		let fraction = intra as f64 / associations.len() as f64;
+		assert!(fraction > 0.7, "intra-cluster fraction {fraction}");
+		assert!(associations.iter().all(|e| e.source != e.target
+			&& (e.association_type == AssociationType::Semantic)
+				== (a.clusters[e.source] == a.clusters[e.target])));
+	}
+
+	#[test]
+	fn test_power_law_has_hubs() {
+		let out_degrees = |distribution| {
+			let graph = generate_graph(&SyntheticGraphConfig {
+				degree_distribution: distribution,
+				..SyntheticGraphConfig::default()
+			});
+			let mut degrees = vec![0usize; graph.graph.num_nodes()];
+			for e in graph.graph.associations() {
+				degrees[e.source] += 1;
+			}
+			degrees.into_iter().max().unwrap_or(0)
+		};
+		assert!(
+			out_degrees(DegreeDistribution::default())
+				> 3 * out_degrees(DegreeDistribution::Uniform)
+		);
+	}
+
+	#[test]
+	fn test_episodes_respect_active_hours() {
+		let graph = generate_graph(&SyntheticGraphConfig::default());
+		let config = SyntheticEpisodeConfig {
+			num_episodes: 200,
+			..SyntheticEpisodeConfig::default()
+		};
+		let stream = generate_episodes(&graph, &config);
+		assert_eq!(stream.episodes.len(), 200);
+
+		let hour_ms = DAY_MS / 24.0;
+		let mut last = f64::NEG_INFINITY;
+		for (episode, times) in stream.episodes.iter().zip(&stream.timestamps_ms) {
+			assert_eq!(episode.events.len(), times.len());
+			assert!((6..=18).contains(&times.len()));
+			let start_hour = (times[0] % DAY_MS) / hour_ms;
+			assert!(
+				(8.0..22.0).contains(&start_hour),
+				"episode starts at {start_hour}"
+			);
+			assert!(times.iter().all(|&t| t >= last));
+			last = times[times.len() - 1];
+		}
+
+		let histories = stream.access_histories_ms(graph.graph.num_nodes());
+		let accesses: usize = histories.iter().map(Vec::len).sum();
+		let events: usize = stream.episodes.iter().map(|e| e.events.len()).sum();
+		assert_eq!(accesses, events);
+		assert_eq!(stream.end_ms(), Some(last));
+	}
+
+	#[test]
+	fn test_embeddings_cluster() {
+		let graph = generate_graph(&SyntheticGraphConfig {
+			num_nodes: 40,
+			num_clusters: 2,
+			..SyntheticGraphConfig::default()
+		});
+		let embeddings = generate_embeddings(&graph.clusters, 32, 0.05, 3);
+		assert_eq!(embeddings.len(), 40);
+		let same = crate::activation::cosine_similarity(&embeddings[0], &embeddings[1]);
+		let other = crate::activation::cosine_similarity(&embeddings[0], &embeddings[39]);
+		assert!(same > other);
+	}
+
+	#[test]
+	fn test_next_active_time() {
+		let hour = DAY_MS / 24.0;
+		let moved = |h: f64, window| next_active_time(h * hour, 0.0, window) / hour;
+		let window = Some((8.0, 22.0));
+		assert!((moved(3.0, window) - 8.0).abs() < 1e-9);
+		assert!((moved(12.0, window) - 12.0).abs() < 1e-9);
+		assert!((moved(23.0, window) - 32.0).abs() < 1e-9);
+		let overnight = Some((22.0, 6.0));
+		assert!((moved(12.0, overnight) - 22.0).abs() < 1e-9);
+		assert!((moved(2.0, overnight) - 2.0).abs() < 1e-9);
+		assert!((moved(5.0, None) - 5.0).abs() < 1e-9);
+	}
+}
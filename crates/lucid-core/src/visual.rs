@@ -385,6 +385,7 @@ pub fn retrieve_visual(
 			minimum_activation: 0.01,
 			max_nodes: 1000,
 			bidirectional: config.bidirectional,
+			..SpreadingConfig::default()
 		};
 
 		spread_activation(
@@ -399,6 +400,8 @@ pub fn retrieve_visual(
 		SpreadingResult {
 			activations: vec![0.0; n],
 			visited_by_depth: Vec::new(),
+			provenance: None,
+			depth_deltas: None,
 		}
 	};
 
@@ -0,0 +1,283 @@
+//! Working Memory Buffer
+//!
+//! The focus of attention above long-term memory: a handful of slots
+//! holding what is currently being thought about (Cowan, 2001).
+//!
+//! Each slot's activation decays with time since it was last attended:
+//!
+//! `a(t) = a_0 × e^(-age/τ)`
+//!
+//! using the same time constant as [`compute_working_memory_boost`]. When
+//! the buffer is full, a new item displaces the weakest slot, but only if
+//! it arrives more active than that slot currently is. The buffer's
+//! contents seed [`spread_activation`], so whatever is in focus primes
+//! its associates in long-term memory.
+//!
+//! [`compute_working_memory_boost`]: crate::activation::compute_working_memory_boost
+
+use serde::{Deserialize, Serialize};
+
+use crate::activation::WorkingMemoryConfig;
+use crate::graph::AssociationGraph;
+use crate::spreading::{
+	spread_activation, spread_activation_graph, Association, SpreadingConfig, SpreadingResult,
+};
+
+/// Default number of slots (Cowan's 4 ± 1).
+pub const DEFAULT_WORKING_MEMORY_CAPACITY: usize = 4;
+
+/// What happened when an item was brought into focus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FocusOutcome {
+	/// Took a free slot
+	Added,
+	/// Was already in focus; its activation was refreshed
+	Refreshed,
+	/// Displaced the given memory from the weakest slot
+	Displaced(usize),
+	/// Buffer full and every slot more active; not admitted
+	Rejected,
+}
+
+/// A single working memory slot.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Slot {
+	memory: usize,
+	activation: f64,
+	attended_at_ms: f64,
+}
+
+/// Capacity-limited buffer of currently attended memories.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkingMemory {
+	capacity: usize,
+	config: WorkingMemoryConfig,
+	slots: Vec<Slot>,
+}
+
+impl Default for WorkingMemory {
+	fn default() -> Self {
+		Self::new(
+			DEFAULT_WORKING_MEMORY_CAPACITY,
+			WorkingMemoryConfig::default(),
+		)
+	}
+}
+
+impl WorkingMemory {
+	/// Create an empty buffer with `capacity` slots.
+	#[must_use]
+	pub fn new(capacity: usize, config: WorkingMemoryConfig) -> Self {
+		Self {
+			capacity,
+			config,
+			slots: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Number of slots.
+	#[must_use]
+	pub const fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	/// Number of occupied slots.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.slots.len()
+	}
+
+	/// Whether nothing is in focus.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.slots.is_empty()
+	}
+
+	/// Whether a memory is in focus.
+	#[must_use]
+	pub fn contains(&self, memory: usize) -> bool {
+		self.slots.iter().any(|s| s.memory == memory)
+	}
+
+	/// Decayed activation of a slot at `current_time_ms`.
+	fn decayed(&self, slot: &Slot, current_time_ms: f64) -> f64 {
+		let age = (current_time_ms - slot.attended_at_ms).max(0.0);
+		if self.config.decay_ms <= 0.0 {
+			return slot.activation;
+		}
+		slot.activation * (-age / self.config.decay_ms).exp()
+	}
+
+	/// Current activation of a memory (0 if not in focus).
+	#[must_use]
+	pub fn activation(&self, memory: usize, current_time_ms: f64) -> f64 {
+		self.slots
+			.iter()
+			.find(|s| s.memory == memory)
+			.map_or(0.0, |s| self.decayed(s, current_time_ms))
+	}
+
+	/// Bring a memory into focus with the given activation.
+	///
+	/// A memory already in focus keeps the larger of its current and new
+	/// activation and is re-stamped. Otherwise it takes a free slot, or
+	/// displaces the weakest slot if it is more active than that slot.
+	pub fn focus(&mut self, memory: usize, activation: f64, current_time_ms: f64) -> FocusOutcome {
+		if let Some(i) = self.slots.iter().position(|s| s.memory == memory) {
+			let current = self.decayed(&self.slots[i], current_time_ms);
+			let slot = &mut self.slots[i];
+			slot.activation = current.max(activation);
+			slot.attended_at_ms = current_time_ms;
+			return FocusOutcome::Refreshed;
+		}
+
+		let slot = Slot {
+			memory,
+			activation,
+			attended_at_ms: current_time_ms,
+		};
+		if self.slots.len() < self.capacity {
+			self.slots.push(slot);
+			return FocusOutcome::Added;
+		}
+
+		let weakest = self
+			.slots
+			.iter()
+			.enumerate()
+			.map(|(i, s)| (i, self.decayed(s, current_time_ms)))
+			.min_by(|a, b| a.1.total_cmp(&b.1));
+		match weakest {
+			Some((i, current)) if activation > current => {
+				let displaced = std::mem::replace(&mut self.slots[i], slot);
+				FocusOutcome::Displaced(displaced.memory)
+			}
+			_ => FocusOutcome::Rejected,
+		}
+	}
+
+	/// Drop a memory from focus. Returns `false` if it was not in focus.
+	pub fn remove(&mut self, memory: usize) -> bool {
+		let before = self.slots.len();
+		self.slots.retain(|s| s.memory != memory);
+		self.slots.len() < before
+	}
+
+	/// Drop every slot whose activation has decayed below `floor`.
+	///
+	/// # Returns
+	///
+	/// The memories dropped.
+	pub fn decay(&mut self, current_time_ms: f64, floor: f64) -> Vec<usize> {
+		let mut dropped = Vec::new();
+		let mut kept = Vec::with_capacity(self.slots.len());
+		for slot in std::mem::take(&mut self.slots) {
+			if self.decayed(&slot, current_time_ms) < floor {
+				dropped.push(slot.memory);
+			} else {
+				kept.push(slot);
+			}
+		}
+		self.slots = kept;
+		dropped
+	}
+
+	/// Memories in focus with their current activations, most active first.
+	#[must_use]
+	pub fn contents(&self, current_time_ms: f64) -> Vec<(usize, f64)> {
+		let mut contents: Vec<(usize, f64)> = self
+			.slots
+			.iter()
+			.map(|s| (s.memory, self.decayed(s, current_time_ms)))
+			.collect();
+		contents.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+		contents
+	}
+
+	/// Seed indices and activations for spreading.
+	#[must_use]
+	pub fn seeds(&self, current_time_ms: f64) -> (Vec<usize>, Vec<f64>) {
+		self.contents(current_time_ms).into_iter().unzip()
+	}
+
+	/// Spread activation from the buffer's contents into long-term memory.
+	#[must_use]
+	pub fn spread(
+		&self,
+		num_nodes: usize,
+		associations: &[Association],
+		config: &SpreadingConfig,
+		depth: usize,
+		current_time_ms: f64,
+	) -> SpreadingResult {
+		let (seeds, activations) = self.seeds(current_time_ms);
+		spread_activation(num_nodes, associations, &seeds, &activations, config, depth)
+	}
+
+	/// [`WorkingMemory::spread`] over a prebuilt [`AssociationGraph`].
+	#[must_use]
+	pub fn spread_graph(
+		&self,
+		graph: &AssociationGraph,
+		config: &SpreadingConfig,
+		depth: usize,
+		current_time_ms: f64,
+	) -> SpreadingResult {
+		let (seeds, activations) = self.seeds(current_time_ms);
+		spread_activation_graph(graph, &seeds, &activations, config, depth)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::spreading::AssociationType;
+
+	#[test]
+	fn test_working_memory_displacement() {
+		let mut wm = WorkingMemory::new(2, WorkingMemoryConfig::default());
+		assert_eq!(wm.focus(1, 1.0, 0.0), FocusOutcome::Added);
+		assert_eq!(wm.focus(2, 0.5, 0.0), FocusOutcome::Added);
+		assert_eq!(wm.focus(1, 0.2, 100.0), FocusOutcome::Refreshed);
+
+		// Weaker than both slots: not admitted
+		assert_eq!(wm.focus(3, 0.1, 100.0), FocusOutcome::Rejected);
+		// Stronger than the weakest (2): displaces it
+		assert_eq!(wm.focus(3, 0.9, 100.0), FocusOutcome::Displaced(2));
+		assert!(wm.contains(3) && !wm.contains(2));
+		assert_eq!(wm.len(), 2);
+	}
+
+	#[test]
+	fn test_working_memory_decay() {
+		let mut wm = WorkingMemory::default();
+		let _ = wm.focus(7, 1.0, 0.0);
+		let _ = wm.focus(8, 1.0, 8000.0);
+
+		// One time constant later
+		assert!((wm.activation(7, 4000.0) - (-1.0_f64).exp()).abs() < 1e-12);
+		assert_eq!(wm.contents(8000.0)[0].0, 8);
+
+		let dropped = wm.decay(12_000.0, 0.1);
+		assert_eq!(dropped, vec![7]);
+		assert!(wm.remove(8));
+		assert!(wm.is_empty());
+	}
+
+	#[test]
+	fn test_working_memory_seeds_spreading() {
+		let associations = vec![Association {
+			source: 0,
+			target: 1,
+			forward_strength: 0.8,
+			backward_strength: 0.4,
+			association_type: AssociationType::Semantic,
+		}];
+		let mut wm = WorkingMemory::default();
+		let _ = wm.focus(0, 1.0, 0.0);
+
+		let result = wm.spread(3, &associations, &SpreadingConfig::default(), 2, 0.0);
+		assert!(result.activations[1] > 0.0);
+		assert!(result.activations[2].abs() < 1e-12);
+	}
+}
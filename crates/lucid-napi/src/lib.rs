@@ -11,6 +11,11 @@ use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
 use lucid_core::{
+	centrality::{
+		betweenness_centrality, closeness_centrality, eigenvector_centrality,
+		personalized_pagerank, CentralityConfig, EigenvectorConfig, PersonalizedPageRankConfig,
+	},
+	graph::{AssociationGraph, MemoryGraph as CoreMemoryGraph},
 	location::{
 		compute_association_strength as core_association_strength,
 		compute_familiarity as core_compute_familiarity,
@@ -19,7 +24,10 @@ use lucid_core::{
 		ActivityInference, ActivityType, LocationAssociation, LocationConfig,
 	},
 	retrieval::{retrieve as core_retrieve, RetrievalConfig as CoreConfig, RetrievalInput},
-	spreading::Association as CoreAssociation,
+	spreading::{
+		compute_pagerank_graph, compute_weighted_pagerank_graph, Association as CoreAssociation,
+		SpreadingConfig,
+	},
 	visual::{
 		retrieve_visual as core_retrieve_visual, should_prune as core_should_prune, VisualConfig,
 		VisualRetrievalConfig, VisualRetrievalInput,
@@ -34,6 +42,9 @@ pub struct JsAssociation {
 	pub target: u32,
 	pub forward_strength: f64,
 	pub backward_strength: f64,
+	/// "semantic" (default), "temporal", "causal", "entity", or
+	/// "visual_similarity"; any other name is an error
+	pub association_type: Option<String>,
 }
 
 /// Configuration for retrieval.
@@ -97,6 +108,10 @@ pub struct JsRetrievalCandidate {
 /// * `current_time_ms` - Current time in milliseconds
 /// * `associations` - Optional association graph edges
 /// * `config` - Optional retrieval configuration
+///
+/// # Errors
+///
+/// Throws if an association has an unknown `association_type`.
 #[napi]
 pub fn retrieve(
 	probe_embedding: Vec<f64>,
@@ -108,7 +123,7 @@ pub fn retrieve(
 	current_time_ms: f64,
 	associations: Option<Vec<JsAssociation>>,
 	config: Option<JsRetrievalConfig>,
-) -> Vec<JsRetrievalCandidate> {
+) -> napi::Result<Vec<JsRetrievalCandidate>> {
 	let config = config.unwrap_or(JsRetrievalConfig {
 		decay_rate: None,
 		activation_threshold: None,
@@ -134,13 +149,8 @@ pub fn retrieve(
 	let associations: Vec<CoreAssociation> = associations
 		.unwrap_or_default()
 		.into_iter()
-		.map(|a| CoreAssociation {
-			source: a.source as usize,
-			target: a.target as usize,
-			forward_strength: a.forward_strength,
-			backward_strength: a.backward_strength,
-		})
-		.collect();
+		.map(js_association_to_core)
+		.collect::<napi::Result<_>>()?;
 
 	let input = RetrievalInput {
 		probe_embedding: &probe_embedding,
@@ -149,13 +159,14 @@ pub fn retrieve(
 		emotional_weights: &emotional_weights,
 		decay_rates: &decay_rates,
 		working_memory_boosts: &working_memory_boosts,
+		retrieval_thresholds: &[],
 		associations: &associations,
 		current_time_ms,
 	};
 
 	let candidates = core_retrieve(&input, &core_config);
 
-	candidates
+	Ok(candidates
 		.into_iter()
 		.map(|c| JsRetrievalCandidate {
 			index: c.index as u32,
@@ -166,7 +177,7 @@ pub fn retrieve(
 			total_activation: c.total_activation,
 			probability: c.probability,
 		})
-		.collect()
+		.collect())
 }
 
 /// Compute cosine similarity between two vectors.
@@ -205,6 +216,14 @@ pub fn retrieval_probability(activation: f64, threshold: f64, noise: f64) -> f64
 	lucid_core::retrieval_probability(activation, threshold, noise)
 }
 
+/// Estimate retrieval latency in milliseconds.
+///
+/// latency = F × e^(-A) × 1000
+#[napi]
+pub fn retrieval_latency(activation: f64, latency_factor: f64) -> f64 {
+	lucid_core::retrieval_latency(activation, latency_factor)
+}
+
 /// Compute surprise (prediction error) between expected and actual.
 #[napi]
 pub fn compute_surprise(
@@ -501,7 +520,7 @@ pub struct JsTemporalLink {
 /// Result of temporal spreading.
 #[napi(object)]
 pub struct JsTemporalSpreadingResult {
-	pub activations: Vec<f64>,
+	pub activations: Float64Array,
 	pub forward_activated: Vec<u32>,
 	pub backward_activated: Vec<u32>,
 }
@@ -511,6 +530,10 @@ pub struct JsTemporalSpreadingResult {
 pub struct JsTemporalNeighbor {
 	pub memory_index: u32,
 	pub strength: f64,
+	/// "before" or "after" the anchor
+	pub direction: String,
+	/// Position distance from the anchor within the episode
+	pub distance: u32,
 }
 
 /// Create temporal links for an episode.
@@ -560,7 +583,7 @@ pub fn spread_temporal_activation(
 	);
 
 	JsTemporalSpreadingResult {
-		activations: result.activations,
+		activations: Float64Array::new(result.activations),
 		forward_activated: result
 			.forward_activated
 			.into_iter()
@@ -576,33 +599,44 @@ pub fn spread_temporal_activation(
 
 /// Find temporally adjacent memories.
 ///
-/// direction: "before", "after", or "both"
+/// direction: "before", "after", or "both" (throws on anything else)
 #[napi]
 pub fn find_temporal_neighbors(
 	temporal_links: Vec<JsTemporalLink>,
 	anchor_memory: u32,
 	direction: String,
 	limit: u32,
-) -> Vec<JsTemporalNeighbor> {
+) -> napi::Result<Vec<JsTemporalNeighbor>> {
+	let direction = lucid_core::TemporalDirection::from_name(&direction).ok_or_else(|| {
+		napi::Error::from_reason(format!(
+			"Unknown temporal direction \"{direction}\" (expected \"before\", \"after\" or \"both\")"
+		))
+	})?;
 	let core_links: Vec<lucid_core::TemporalLink> = temporal_links
 		.into_iter()
 		.map(js_temporal_link_to_core)
 		.collect();
 
-	let neighbors = lucid_core::find_temporal_neighbors(
+	let neighbors = lucid_core::temporal_neighbors(
 		&core_links,
 		anchor_memory as usize,
-		&direction,
+		direction,
 		limit as usize,
 	);
 
-	neighbors
+	Ok(neighbors
 		.into_iter()
-		.map(|(m, s)| JsTemporalNeighbor {
-			memory_index: m as u32,
-			strength: s,
+		.map(|n| JsTemporalNeighbor {
+			memory_index: n.memory as u32,
+			strength: n.strength,
+			direction: match n.direction {
+				lucid_core::TemporalDirection::Before => "before",
+				_ => "after",
+			}
+			.to_string(),
+			distance: n.distance as u32,
 		})
-		.collect()
+		.collect())
 }
 
 /// Library version
@@ -611,6 +645,258 @@ pub fn version() -> String {
 	lucid_core::VERSION.to_string()
 }
 
+// ============================================================================
+// Memory Graph
+// ============================================================================
+
+/// Configuration for spreading activation over a `MemoryGraph`.
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsSpreadingConfig {
+	/// How much activation decays per hop (default: 0.7)
+	pub decay_per_hop: Option<f64>,
+	/// Minimum activation to propagate (default: 0.01)
+	pub minimum_activation: Option<f64>,
+	/// Maximum nodes to visit (default: 1000)
+	pub max_nodes: Option<u32>,
+	/// Whether to spread bidirectionally (default: true)
+	pub bidirectional: Option<bool>,
+	/// Spread until a level adds less than this; `depth` is then ignored
+	pub convergence_epsilon: Option<f64>,
+	/// Level cap when spreading to convergence (default: 100)
+	pub max_iterations: Option<u32>,
+	/// Logistic activation noise scale; 0 disables noise (default: 0)
+	pub activation_noise: Option<f64>,
+	/// Seed for activation noise (default: 0)
+	pub noise_seed: Option<u32>,
+}
+
+/// Configuration for betweenness and closeness centrality.
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsCentralityConfig {
+	/// Measure path length by edge strength instead of hop count (default: false)
+	pub weighted: Option<bool>,
+	/// Scale betweenness into 0-1 (default: true)
+	pub normalized: Option<bool>,
+}
+
+/// Editable association graph for spreading and centrality queries.
+///
+/// Results come back as `Float64Array`s that take ownership of the Rust
+/// buffer, so no per-node conversion or JSON round-trip is involved. The
+/// query index is rebuilt lazily after the graph is edited.
+#[napi(js_name = "MemoryGraph")]
+pub struct JsMemoryGraph {
+	graph: CoreMemoryGraph,
+	index: Option<AssociationGraph>,
+}
+
+#[napi]
+impl JsMemoryGraph {
+	/// Create an empty graph with `num_nodes` nodes.
+	#[napi(constructor)]
+	pub fn new(num_nodes: u32) -> Self {
+		Self {
+			graph: CoreMemoryGraph::new(num_nodes as usize),
+			index: None,
+		}
+	}
+
+	/// Create a graph from existing associations; for a repeated
+	/// `(source, target)` pair the last one wins.
+	///
+	/// # Errors
+	///
+	/// Throws if an endpoint is not below `num_nodes`.
+	#[napi(factory)]
+	pub fn from_associations(
+		num_nodes: u32,
+		associations: Vec<JsAssociation>,
+	) -> napi::Result<Self> {
+		let associations = associations
+			.into_iter()
+			.enumerate()
+			.map(|(i, association)| {
+				check_endpoints(&association, num_nodes).map_err(|reason| {
+					napi::Error::from_reason(format!("association {i} {reason}"))
+				})?;
+				js_association_to_core(association)
+			})
+			.collect::<napi::Result<_>>()?;
+		Ok(Self {
+			graph: CoreMemoryGraph::from_associations(num_nodes as usize, associations),
+			index: None,
+		})
+	}
+
+	/// Number of nodes in the graph.
+	#[napi(getter)]
+	pub fn num_nodes(&self) -> u32 {
+		self.graph.num_nodes() as u32
+	}
+
+	/// Number of associations in the graph.
+	#[napi(getter)]
+	pub fn num_edges(&self) -> u32 {
+		self.graph.num_edges() as u32
+	}
+
+	/// Append a node and return its index.
+	#[napi]
+	pub fn add_node(&mut self) -> u32 {
+		self.index = None;
+		self.graph.add_node() as u32
+	}
+
+	/// Insert an association, replacing any edge with the same endpoints.
+	///
+	/// Returns true if an existing association was replaced.
+	///
+	/// # Errors
+	///
+	/// Throws if an endpoint is not an existing node; call `addNode` first.
+	#[napi]
+	pub fn insert_association(&mut self, association: JsAssociation) -> napi::Result<bool> {
+		check_endpoints(&association, self.num_nodes())
+			.map_err(|reason| napi::Error::from_reason(format!("association {reason}")))?;
+		let association = js_association_to_core(association)?;
+		self.index = None;
+		Ok(self.graph.insert_association(association).is_some())
+	}
+
+	/// Remove the association from `source` to `target`.
+	///
+	/// Returns true if it existed.
+	#[napi]
+	pub fn remove_association(&mut self, source: u32, target: u32) -> bool {
+		self.index = None;
+		self.graph
+			.remove_association(source as usize, target as usize)
+			.is_some()
+	}
+
+	/// Spread activation from seed nodes; returns one activation per node.
+	///
+	/// # Errors
+	///
+	/// Throws if a seed is not an existing node.
+	#[napi]
+	pub fn spread(
+		&mut self,
+		seed_indices: Uint32Array,
+		seed_activations: Float64Array,
+		depth: u32,
+		config: Option<JsSpreadingConfig>,
+	) -> napi::Result<Float64Array> {
+		check_seeds(&seed_indices, self.num_nodes()).map_err(napi::Error::from_reason)?;
+		let core_config = js_spreading_config_to_core(config);
+		let seeds: Vec<usize> = seed_indices.iter().map(|&i| i as usize).collect();
+		let result = lucid_core::spread_activation_graph(
+			self.indexed(),
+			&seeds,
+			&seed_activations,
+			&core_config,
+			depth as usize,
+		);
+		Ok(Float64Array::new(result.activations))
+	}
+
+	/// `PageRank` of every node.
+	///
+	/// With `weighted`, rank is split in proportion to forward strength
+	/// rather than evenly over out-links.
+	#[napi]
+	pub fn pagerank(
+		&mut self,
+		damping: f64,
+		iterations: u32,
+		weighted: Option<bool>,
+	) -> Float64Array {
+		let graph = self.indexed();
+		let ranks = if weighted.unwrap_or(false) {
+			compute_weighted_pagerank_graph(graph, damping, iterations as usize)
+		} else {
+			compute_pagerank_graph(graph, damping, iterations as usize)
+		};
+		Float64Array::new(ranks)
+	}
+
+	/// Personalized `PageRank` around weighted seed nodes.
+	#[napi]
+	pub fn personalized_pagerank(
+		&mut self,
+		seed_indices: Uint32Array,
+		seed_weights: Float64Array,
+		damping: Option<f64>,
+	) -> Float64Array {
+		let default = PersonalizedPageRankConfig::default();
+		let config = PersonalizedPageRankConfig {
+			damping: damping.unwrap_or(default.damping),
+			..default
+		};
+		let seeds: Vec<(usize, f64)> = seed_indices
+			.iter()
+			.zip(seed_weights.iter())
+			.map(|(&i, &w)| (i as usize, w))
+			.collect();
+		Float64Array::new(personalized_pagerank(self.indexed(), &seeds, &config).scores)
+	}
+
+	/// Betweenness centrality of every node.
+	#[napi]
+	pub fn betweenness(&mut self, config: Option<JsCentralityConfig>) -> Float64Array {
+		let config = js_centrality_config_to_core(config);
+		Float64Array::new(betweenness_centrality(self.indexed(), &config))
+	}
+
+	/// Closeness centrality of every node.
+	#[napi]
+	pub fn closeness(&mut self, config: Option<JsCentralityConfig>) -> Float64Array {
+		let config = js_centrality_config_to_core(config);
+		Float64Array::new(closeness_centrality(self.indexed(), &config))
+	}
+
+	/// Eigenvector centrality of every node, weighted by forward strength.
+	#[napi]
+	pub fn eigenvector(&mut self) -> Float64Array {
+		let scores = eigenvector_centrality(self.indexed(), &EigenvectorConfig::default());
+		Float64Array::new(scores.scores)
+	}
+}
+
+impl JsMemoryGraph {
+	/// Query index, rebuilt if the graph changed since the last query.
+	fn indexed(&mut self) -> &AssociationGraph {
+		self.index.get_or_insert_with(|| self.graph.index())
+	}
+}
+
+/// Reject endpoints outside `0..num_nodes`, so one stray index cannot grow
+/// the graph to billions of nodes.
+fn check_endpoints(association: &JsAssociation, num_nodes: u32) -> Result<(), String> {
+	if association.source < num_nodes && association.target < num_nodes {
+		Ok(())
+	} else {
+		Err(format!(
+			"links {} → {}, but the graph has {num_nodes} nodes",
+			association.source, association.target
+		))
+	}
+}
+
+/// Reject seeds outside `0..num_nodes`.
+fn check_seeds(seeds: &[u32], num_nodes: u32) -> Result<(), String> {
+	seeds
+		.iter()
+		.find(|&&seed| seed >= num_nodes)
+		.map_or(Ok(()), |seed| {
+			Err(format!(
+				"seed {seed} is out of range: the graph has {num_nodes} nodes"
+			))
+		})
+}
+
 // ============================================================================
 // Embedding (In-Process ONNX)
 // ============================================================================
@@ -1036,6 +1322,10 @@ pub struct JsPruningCandidate {
 }
 
 /// Retrieve visual memories based on probe embedding.
+///
+/// # Errors
+///
+/// Throws if an association has an unknown `association_type`.
 #[napi]
 pub fn visual_retrieve(
 	probe_embedding: Vec<f64>,
@@ -1046,19 +1336,14 @@ pub fn visual_retrieve(
 	current_time_ms: f64,
 	associations: Option<Vec<JsAssociation>>,
 	config: Option<JsVisualRetrievalConfig>,
-) -> Vec<JsVisualRetrievalCandidate> {
+) -> napi::Result<Vec<JsVisualRetrievalCandidate>> {
 	let config = js_visual_retrieval_config_to_core(config);
 
 	let associations: Vec<CoreAssociation> = associations
 		.unwrap_or_default()
 		.into_iter()
-		.map(|a| CoreAssociation {
-			source: a.source as usize,
-			target: a.target as usize,
-			forward_strength: a.forward_strength,
-			backward_strength: a.backward_strength,
-		})
-		.collect();
+		.map(js_association_to_core)
+		.collect::<napi::Result<_>>()?;
 
 	let input = VisualRetrievalInput {
 		probe_embedding: &probe_embedding,
@@ -1072,7 +1357,7 @@ pub fn visual_retrieve(
 
 	let candidates = core_retrieve_visual(&input, &config);
 
-	candidates
+	Ok(candidates
 		.into_iter()
 		.map(|c| JsVisualRetrievalCandidate {
 			index: c.index as u32,
@@ -1084,7 +1369,7 @@ pub fn visual_retrieve(
 			total_activation: c.total_activation,
 			probability: c.probability,
 		})
-		.collect()
+		.collect())
 }
 
 /// Check if a visual memory should be pruned.
@@ -1376,6 +1661,53 @@ fn parse_activity_type(s: &str) -> Option<ActivityType> {
 	}
 }
 
+fn js_association_to_core(js: JsAssociation) -> napi::Result<CoreAssociation> {
+	let association_type = match js.association_type.as_deref() {
+		Some(name) => name
+			.parse()
+			.map_err(|e: lucid_core::UnknownAssociationType| {
+				napi::Error::from_reason(e.to_string())
+			})?,
+		None => lucid_core::AssociationType::default(),
+	};
+	Ok(CoreAssociation {
+		source: js.source as usize,
+		target: js.target as usize,
+		forward_strength: js.forward_strength,
+		backward_strength: js.backward_strength,
+		association_type,
+	})
+}
+
+fn js_spreading_config_to_core(js: Option<JsSpreadingConfig>) -> SpreadingConfig {
+	js.map_or_else(SpreadingConfig::default, |c| {
+		let default = SpreadingConfig::default();
+		SpreadingConfig {
+			decay_per_hop: c.decay_per_hop.unwrap_or(default.decay_per_hop),
+			minimum_activation: c.minimum_activation.unwrap_or(default.minimum_activation),
+			max_nodes: c.max_nodes.map_or(default.max_nodes, |n| n as usize),
+			bidirectional: c.bidirectional.unwrap_or(default.bidirectional),
+			convergence_epsilon: c.convergence_epsilon,
+			max_iterations: c
+				.max_iterations
+				.map_or(default.max_iterations, |n| n as usize),
+			activation_noise: c.activation_noise.unwrap_or(default.activation_noise),
+			noise_seed: c.noise_seed.map_or(default.noise_seed, u64::from),
+			..default
+		}
+	})
+}
+
+fn js_centrality_config_to_core(js: Option<JsCentralityConfig>) -> CentralityConfig {
+	js.map_or_else(CentralityConfig::default, |c| {
+		let default = CentralityConfig::default();
+		CentralityConfig {
+			weighted: c.weighted.unwrap_or(default.weighted),
+			normalized: c.normalized.unwrap_or(default.normalized),
+		}
+	})
+}
+
 fn js_instance_noise_config_to_core(
 	js: Option<JsInstanceNoiseConfig>,
 ) -> lucid_core::activation::InstanceNoiseConfig {
@@ -1519,6 +1851,8 @@ mod tests {
 			}),
 		);
 
+		assert!(results.is_ok());
+		let Ok(results) = results else { return };
 		assert!(!results.is_empty());
 		assert_eq!(results[0].index, 0);
 	}
@@ -1629,4 +1963,137 @@ mod tests {
 		assert_eq!(results[1].location_id, 1);
 		assert_eq!(results[2].location_id, 3);
 	}
+
+	// Memory graph tests
+
+	fn js_assoc(source: u32, target: u32, strength: f64) -> JsAssociation {
+		JsAssociation {
+			source,
+			target,
+			forward_strength: strength,
+			backward_strength: strength * 0.5,
+			association_type: None,
+		}
+	}
+
+	#[test]
+	fn test_memory_graph_spread() {
+		let graph =
+			JsMemoryGraph::from_associations(3, vec![js_assoc(0, 1, 1.0), js_assoc(1, 2, 1.0)]);
+		assert!(graph.is_ok());
+		let Ok(mut graph) = graph else { return };
+
+		let activations = graph.spread(
+			Uint32Array::new(vec![0]),
+			Float64Array::new(vec![1.0]),
+			2,
+			None,
+		);
+		assert!(activations.is_ok());
+		let Ok(activations) = activations else { return };
+		assert_eq!(activations.len(), 3);
+		assert!(activations[0] > activations[1]);
+		assert!(activations[1] > activations[2]);
+		assert!(activations[2] > 0.0);
+	}
+
+	#[test]
+	fn test_memory_graph_pagerank() {
+		let graph =
+			JsMemoryGraph::from_associations(3, vec![js_assoc(0, 2, 1.0), js_assoc(1, 2, 1.0)]);
+		assert!(graph.is_ok());
+		let Ok(mut graph) = graph else { return };
+
+		for weighted in [None, Some(true)] {
+			let ranks = graph.pagerank(0.85, 50, weighted);
+			assert_eq!(ranks.len(), 3);
+			assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+			assert!(ranks[2] > ranks[0]);
+			assert!(ranks[2] > ranks[1]);
+		}
+	}
+
+	#[test]
+	fn test_memory_graph_insert_remove() {
+		let mut graph = JsMemoryGraph::new(2);
+		assert!(matches!(
+			graph.insert_association(js_assoc(0, 1, 0.5)),
+			Ok(false)
+		));
+		assert!(matches!(
+			graph.insert_association(js_assoc(0, 1, 0.8)),
+			Ok(true)
+		));
+		assert_eq!(graph.num_edges(), 1);
+
+		// The cached index must see edits.
+		let before = graph.spread(
+			Uint32Array::new(vec![0]),
+			Float64Array::new(vec![1.0]),
+			1,
+			None,
+		);
+		assert!(before.is_ok_and(|before| before[1] > 0.0));
+		assert!(graph.remove_association(0, 1));
+		assert!(!graph.remove_association(0, 1));
+		assert_eq!(graph.num_edges(), 0);
+		let after = graph.spread(
+			Uint32Array::new(vec![0]),
+			Float64Array::new(vec![1.0]),
+			1,
+			None,
+		);
+		assert!(after.is_ok_and(|after| after[1].abs() < f64::EPSILON));
+
+		let node = graph.add_node();
+		assert!(matches!(
+			graph.insert_association(js_assoc(1, node, 0.5)),
+			Ok(false)
+		));
+		assert_eq!(graph.num_nodes(), 3);
+	}
+
+	#[test]
+	fn test_memory_graph_rejects_out_of_range_endpoints() {
+		let mut graph = JsMemoryGraph::new(2);
+		assert!(graph.insert_association(js_assoc(0, 2, 0.5)).is_err());
+		assert!(graph
+			.insert_association(js_assoc(u32::MAX, 0, 0.5))
+			.is_err());
+		assert_eq!(graph.num_nodes(), 2);
+		assert_eq!(graph.num_edges(), 0);
+
+		let graph =
+			JsMemoryGraph::from_associations(2, vec![js_assoc(0, 1, 0.5), js_assoc(1, 5, 0.5)]);
+		assert!(graph.is_err());
+	}
+
+	#[test]
+	fn test_memory_graph_rejects_unknown_association_type() {
+		let mut association = js_assoc(0, 1, 0.5);
+		association.association_type = Some("Visual_Similarity".to_string());
+		let mut graph = JsMemoryGraph::new(2);
+		assert!(graph.insert_association(association.clone()).is_ok());
+
+		association.association_type = Some("friendship".to_string());
+		assert!(graph.insert_association(association.clone()).is_err());
+		assert!(JsMemoryGraph::from_associations(2, vec![association]).is_err());
+		assert_eq!(graph.num_edges(), 1);
+	}
+
+	#[test]
+	fn test_memory_graph_rejects_out_of_range_seeds() {
+		assert!(check_seeds(&[0, 1], 2).is_ok());
+		let result = check_seeds(&[0, 2], 2);
+		assert!(result.is_err_and(|e| e.contains("seed 2") && e.contains("2 nodes")));
+
+		let mut graph = JsMemoryGraph::new(2);
+		let result = graph.spread(
+			Uint32Array::new(vec![u32::MAX]),
+			Float64Array::new(vec![1.0]),
+			1,
+			None,
+		);
+		assert!(result.is_err());
+	}
 }
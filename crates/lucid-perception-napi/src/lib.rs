@@ -7,6 +7,7 @@
 // napi-rs macro generates trailing_empty_array in FFI types - this is unavoidable
 #![allow(clippy::trailing_empty_array)]
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use napi::bindgen_prelude::*;
@@ -16,7 +17,8 @@ use lucid_perception::{
 	pipeline::{PipelineConfig, VideoProcessingOutput},
 	scene::{FrameCandidate, SceneConfig},
 	transcribe::{TranscriptionConfig, TranscriptionResult},
-	video::{ExtractedFrame, ImageFormat, VideoConfig, VideoMetadata},
+	variants::{FrameSize, FrameVariant},
+	video::{ExtractedFrame, HwAccel, ImageFormat, NetworkConfig, VideoConfig, VideoMetadata},
 	PerceptionError,
 };
 
@@ -41,6 +43,8 @@ pub struct JsVideoMetadata {
 	pub codec: String,
 	/// Has audio
 	pub has_audio: bool,
+	/// Clockwise rotation to display frames upright (0, 90, 180 or 270)
+	pub rotation: u32,
 }
 
 /// An extracted frame.
@@ -54,6 +58,25 @@ pub struct JsExtractedFrame {
 	pub frame_number: u32,
 	/// Is keyframe
 	pub is_keyframe: bool,
+	/// Encoded image, for frames extracted to memory (path is then empty)
+	pub data: Option<Buffer>,
+	/// The frame at each configured variant size
+	pub variants: Vec<JsFrameVariant>,
+}
+
+/// An extracted frame at one variant size.
+#[napi(object)]
+pub struct JsFrameVariant {
+	/// Variant name
+	pub name: String,
+	/// Path to variant image
+	pub path: String,
+	/// Width in pixels
+	pub width: u32,
+	/// Height in pixels
+	pub height: u32,
+	/// Encoded image, for frames extracted to memory (path is then empty)
+	pub data: Option<Buffer>,
 }
 
 /// Frame with scene detection info.
@@ -75,6 +98,8 @@ pub struct JsFrameCandidate {
 	pub is_duplicate: bool,
 	/// Distance from previous
 	pub distance_from_previous: u32,
+	/// The frame at each configured variant size
+	pub variants: Vec<JsFrameVariant>,
 }
 
 /// Transcript segment.
@@ -118,6 +143,14 @@ pub struct JsProcessingStats {
 	pub scene_detection_time_ms: i64,
 	/// Transcription time (ms)
 	pub transcription_time_ms: i64,
+	/// Peak resident memory during extraction (bytes, if sampled)
+	pub extraction_peak_rss_bytes: Option<i64>,
+	/// Peak resident memory during scene detection (bytes, if sampled)
+	pub scene_detection_peak_rss_bytes: Option<i64>,
+	/// Peak resident memory during transcription (bytes, if sampled)
+	pub transcription_peak_rss_bytes: Option<i64>,
+	/// Peak GPU memory during transcription (bytes, if sampled)
+	pub transcription_peak_vram_bytes: Option<i64>,
 }
 
 /// Video processing output.
@@ -147,10 +180,48 @@ pub struct JsVideoConfig {
 	pub interval_seconds: Option<f64>,
 	/// Quality (1-31, lower is better)
 	pub quality: Option<u32>,
-	/// Output format: "jpeg" or "png"
+	/// Output format: "jpeg", "png", "avif" or "webp" (lossless)
 	pub format: Option<String>,
+	/// Encoder effort for AVIF and WebP, 1 (fastest) to 10 (smallest files)
+	/// (default: 7)
+	pub effort: Option<u32>,
 	/// Extract keyframes only
 	pub keyframes_only: Option<bool>,
+	/// Hardware decoder: "auto", "nvdec", "vaapi", "videotoolbox" or "qsv"
+	/// (default: CPU)
+	pub hwaccel: Option<String>,
+	/// Timeout and headers for http(s) URLs
+	pub network: Option<JsNetworkConfig>,
+	/// Seconds into the video to start at (default: beginning)
+	pub start_time: Option<f64>,
+	/// Seconds into the video to stop at (default: end)
+	pub end_time: Option<f64>,
+	/// Extra sizes to write every frame at, e.g. thumbnails
+	pub variants: Option<Vec<JsFrameSize>>,
+	/// Turn frames upright by the video's rotation metadata (default: true)
+	pub auto_rotate: Option<bool>,
+}
+
+/// A size to write every extracted frame at, besides full size.
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsFrameSize {
+	/// Variant name, appended to frame file names
+	pub name: String,
+	/// Max width in pixels (0 or unset = any)
+	pub max_width: Option<u32>,
+	/// Max height in pixels (0 or unset = any)
+	pub max_height: Option<u32>,
+}
+
+/// Network config for http(s) URLs and HLS playlists.
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsNetworkConfig {
+	/// Seconds a connection may stall (0 = no timeout, default: 30)
+	pub timeout_seconds: Option<f64>,
+	/// Extra HTTP headers sent with every request
+	pub headers: Option<HashMap<String, String>>,
 }
 
 /// Scene detection config.
@@ -193,6 +264,10 @@ pub struct JsPipelineConfig {
 	pub enable_scene_detection: Option<bool>,
 	/// Skip transcription
 	pub skip_transcription: Option<bool>,
+	/// Peak memory sampling interval in ms (0 = disabled)
+	pub resource_sample_interval_ms: Option<u32>,
+	/// Reproducible output for golden-file tests
+	pub deterministic: Option<bool>,
 }
 
 // ============================================================================
@@ -218,8 +293,12 @@ pub async fn video_check_ffmpeg() -> Result<bool> {
 ///
 /// Returns an error if the video cannot be read or `FFmpeg` fails.
 #[napi]
-pub async fn video_get_metadata(video_path: String) -> Result<JsVideoMetadata> {
-	let metadata = lucid_perception::get_video_metadata(&video_path)
+pub async fn video_get_metadata(
+	video_path: String,
+	network: Option<JsNetworkConfig>,
+) -> Result<JsVideoMetadata> {
+	let network = js_network_config_to_core(network);
+	let metadata = lucid_perception::get_video_metadata_with(&video_path, &network)
 		.await
 		.map_err(perception_error_to_napi)?;
 
@@ -245,6 +324,48 @@ pub async fn video_extract_frames(
 	Ok(frames.into_iter().map(extracted_frame_to_js).collect())
 }
 
+/// Extract the frame at each of `timestamps` in one pass over the video.
+///
+/// Frames come back in the order requested, each labeled with the time of
+/// the frame actually hit.
+///
+/// # Errors
+///
+/// Returns an error if frame extraction fails or a timestamp is invalid.
+#[napi]
+pub async fn video_extract_frames_at(
+	video_path: String,
+	timestamps: Vec<f64>,
+	config: Option<JsVideoConfig>,
+) -> Result<Vec<JsExtractedFrame>> {
+	let config = js_video_config_to_core(config);
+
+	let frames = lucid_perception::extract_frames_at(&video_path, &timestamps, &config)
+		.await
+		.map_err(perception_error_to_napi)?;
+
+	Ok(frames.into_iter().map(extracted_frame_to_js).collect())
+}
+
+/// Extract frames from a video into memory, without writing files.
+///
+/// # Errors
+///
+/// Returns an error if frame extraction fails.
+#[napi]
+pub async fn video_extract_frames_to_memory(
+	video_path: String,
+	config: Option<JsVideoConfig>,
+) -> Result<Vec<JsExtractedFrame>> {
+	let config = js_video_config_to_core(config);
+
+	let frames = lucid_perception::extract_frames_to_memory(&video_path, &config)
+		.await
+		.map_err(perception_error_to_napi)?;
+
+	Ok(frames.into_iter().map(extracted_frame_to_js).collect())
+}
+
 /// Transcribe audio from a video.
 ///
 /// # Errors
@@ -326,6 +447,7 @@ fn metadata_to_js(m: VideoMetadata) -> JsVideoMetadata {
 		height: m.height,
 		codec: m.codec,
 		has_audio: m.has_audio,
+		rotation: m.rotation,
 	}
 }
 
@@ -335,6 +457,18 @@ fn extracted_frame_to_js(f: ExtractedFrame) -> JsExtractedFrame {
 		timestamp_seconds: f.timestamp_seconds,
 		frame_number: f.frame_number,
 		is_keyframe: f.is_keyframe,
+		data: f.data.map(Buffer::from),
+		variants: f.variants.into_iter().map(frame_variant_to_js).collect(),
+	}
+}
+
+fn frame_variant_to_js(v: FrameVariant) -> JsFrameVariant {
+	JsFrameVariant {
+		name: v.name,
+		path: v.path.display().to_string(),
+		width: v.width,
+		height: v.height,
+		data: v.data.map(Buffer::from),
 	}
 }
 
@@ -348,6 +482,12 @@ fn frame_candidate_to_js(f: FrameCandidate) -> JsFrameCandidate {
 		is_scene_change: f.is_scene_change,
 		is_duplicate: f.is_duplicate,
 		distance_from_previous: f.distance_from_previous,
+		variants: f
+			.frame
+			.variants
+			.into_iter()
+			.map(frame_variant_to_js)
+			.collect(),
 	}
 }
 
@@ -383,10 +523,24 @@ fn processing_output_to_js(o: VideoProcessingOutput) -> JsVideoProcessingOutput
 			scene_detection_time_ms: i64::try_from(o.stats.scene_detection_time_ms)
 				.unwrap_or(i64::MAX),
 			transcription_time_ms: i64::try_from(o.stats.transcription_time_ms).unwrap_or(i64::MAX),
+			extraction_peak_rss_bytes: bytes_to_js(o.stats.resources.extraction.peak_rss_bytes),
+			scene_detection_peak_rss_bytes: bytes_to_js(
+				o.stats.resources.scene_detection.peak_rss_bytes,
+			),
+			transcription_peak_rss_bytes: bytes_to_js(
+				o.stats.resources.transcription.peak_rss_bytes,
+			),
+			transcription_peak_vram_bytes: bytes_to_js(
+				o.stats.resources.transcription.peak_vram_bytes,
+			),
 		},
 	}
 }
 
+fn bytes_to_js(bytes: Option<u64>) -> Option<i64> {
+	bytes.map(|b| i64::try_from(b).unwrap_or(i64::MAX))
+}
+
 fn js_video_config_to_core(js: Option<JsVideoConfig>) -> VideoConfig {
 	js.map_or_else(VideoConfig::default, |js| {
 		let default = VideoConfig::default();
@@ -400,9 +554,49 @@ fn js_video_config_to_core(js: Option<JsVideoConfig>) -> VideoConfig {
 			quality: js.quality.unwrap_or(default.quality),
 			format: js.format.as_deref().map_or(default.format, |s| match s {
 				"png" => ImageFormat::Png,
+				"avif" => ImageFormat::Avif,
+				"webp" => ImageFormat::WebpLossless,
 				_ => ImageFormat::Jpeg,
 			}),
+			effort: js.effort.unwrap_or(default.effort),
 			keyframes_only: js.keyframes_only.unwrap_or(default.keyframes_only),
+			deterministic: default.deterministic,
+			hwaccel: js.hwaccel.as_deref().map_or(default.hwaccel, |s| match s {
+				"auto" => HwAccel::Auto,
+				"nvdec" | "cuda" => HwAccel::Nvdec,
+				"vaapi" => HwAccel::Vaapi,
+				"videotoolbox" => HwAccel::VideoToolbox,
+				"qsv" => HwAccel::Qsv,
+				_ => HwAccel::None,
+			}),
+			network: js_network_config_to_core(js.network),
+			start_time: js.start_time.or(default.start_time),
+			end_time: js.end_time.or(default.end_time),
+			variants: js.variants.map_or(default.variants, |sizes| {
+				sizes
+					.into_iter()
+					.map(|size| {
+						FrameSize::new(
+							size.name,
+							size.max_width.unwrap_or(0),
+							size.max_height.unwrap_or(0),
+						)
+					})
+					.collect()
+			}),
+			auto_rotate: js.auto_rotate.unwrap_or(default.auto_rotate),
+		}
+	})
+}
+
+fn js_network_config_to_core(js: Option<JsNetworkConfig>) -> NetworkConfig {
+	js.map_or_else(NetworkConfig::default, |js| {
+		let default = NetworkConfig::default();
+		NetworkConfig {
+			timeout_seconds: js.timeout_seconds.unwrap_or(default.timeout_seconds),
+			headers: js
+				.headers
+				.map_or(default.headers, |headers| headers.into_iter().collect()),
 		}
 	})
 }
@@ -432,10 +626,13 @@ fn js_transcription_config_to_core(js: Option<JsTranscriptionConfig>) -> Transcr
 			threads: js.threads.unwrap_or(default.threads),
 			translate: js.translate.unwrap_or(default.translate),
 			max_segment_length: default.max_segment_length,
+			deterministic: default.deterministic,
 		}
 	})
 }
 
+// `verify_frames` only exists with lucid-perception's `verify` feature
+#[allow(clippy::needless_update)]
 fn js_pipeline_config_to_core(js: Option<JsPipelineConfig>) -> PipelineConfig {
 	js.map_or_else(PipelineConfig::default, |js| {
 		let default = PipelineConfig::default();
@@ -449,6 +646,11 @@ fn js_pipeline_config_to_core(js: Option<JsPipelineConfig>) -> PipelineConfig {
 				.enable_scene_detection
 				.unwrap_or(default.enable_scene_detection),
 			skip_transcription: js.skip_transcription.unwrap_or(default.skip_transcription),
+			resource_sample_interval_ms: js
+				.resource_sample_interval_ms
+				.map_or(default.resource_sample_interval_ms, u64::from),
+			deterministic: js.deterministic.unwrap_or(default.deterministic),
+			..default
 		}
 	})
 }
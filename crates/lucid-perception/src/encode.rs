@@ -0,0 +1,258 @@
+//! In-process image encoding.
+//!
+//! `FFmpeg` encodes the frames it writes itself. Frames decoded through
+//! libav, [variants](crate::variants) scaled from extracted frames, and
+//! AVIF frames, which `FFmpeg` writes as PNG (see
+//! [`ImageFormat::ffmpeg_format`]), are encoded here instead, at the
+//! quality `-q:v` would give.
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::{ImageEncoder, RgbImage};
+
+use crate::error::Result;
+use crate::variants::render_variants;
+use crate::video::{ExtractedFrame, ImageFormat, VideoConfig};
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+/// Encode `image` in `config`'s format, quality and effort.
+///
+/// Lossless `WebP` is encoded here at one fixed effort; `effort` applies to
+/// `WebP` only where `FFmpeg` writes it.
+pub fn encode(image: &RgbImage, config: &VideoConfig) -> Result<Vec<u8>> {
+	let mut bytes = Vec::new();
+	match config.format {
+		ImageFormat::Jpeg => {
+			JpegEncoder::new_with_quality(&mut bytes, percent_quality(config.quality))
+				.encode_image(image)?;
+		}
+		ImageFormat::Png => PngEncoder::new(&mut bytes).write_image(
+			image.as_raw(),
+			image.width(),
+			image.height(),
+			image::ExtendedColorType::Rgb8,
+		)?,
+		ImageFormat::WebpLossless => WebPEncoder::new_lossless(&mut bytes).encode(
+			image.as_raw(),
+			image.width(),
+			image.height(),
+			image::ExtendedColorType::Rgb8,
+		)?,
+		ImageFormat::Avif => encode_avif(image, config, &mut bytes)?,
+	}
+	Ok(bytes)
+}
+
+/// Encode `image` as AVIF into `bytes`, with [`VideoConfig::effort`] 7
+/// giving `cavif`'s default speed of 4.
+#[cfg(feature = "avif")]
+fn encode_avif(image: &RgbImage, config: &VideoConfig, bytes: &mut Vec<u8>) -> Result<()> {
+	use image::codecs::avif::AvifEncoder;
+
+	let speed = u8::try_from(11 - config.effort.clamp(1, 10)).unwrap_or(4);
+	AvifEncoder::new_with_speed_quality(bytes, speed, percent_quality(config.quality))
+		.write_image(
+			image.as_raw(),
+			image.width(),
+			image.height(),
+			image::ExtendedColorType::Rgb8,
+		)?;
+	Ok(())
+}
+
+/// AVIF is unavailable without the `avif` feature.
+#[cfg(not(feature = "avif"))]
+fn encode_avif(_image: &RgbImage, _config: &VideoConfig, _bytes: &mut Vec<u8>) -> Result<()> {
+	let format = image::error::ImageFormatHint::Exact(image::ImageFormat::Avif);
+	Err(image::ImageError::Unsupported(format.into()).into())
+}
+
+/// `FFmpeg`'s `-q:v` scale (1 best, 31 worst) mapped onto the 1-100
+/// quality of the JPEG and AVIF encoders (100 best); the default of 2
+/// gives 97.
+fn percent_quality(q: u32) -> u8 {
+	let quality = 100_u32
+		.saturating_sub(q.saturating_sub(1).saturating_mul(3))
+		.max(10);
+	u8::try_from(quality).unwrap_or(100)
+}
+
+// ============================================================================
+// Finishing FFmpeg Output
+// ============================================================================
+
+/// `frames` extracted by `FFmpeg`, each finished by [`finish_frame`].
+pub async fn finish_frames(
+	frames: Vec<ExtractedFrame>,
+	config: &VideoConfig,
+) -> Result<Vec<ExtractedFrame>> {
+	let mut finished = Vec::with_capacity(frames.len());
+	for frame in frames {
+		finished.push(finish_frame(frame, config).await?);
+	}
+	Ok(finished)
+}
+
+/// `frame` as `FFmpeg` wrote it, in `config`'s
+/// [`ffmpeg_format`](ImageFormat::ffmpeg_format), with its variants
+/// rendered and, if `FFmpeg` could not write `config.format`, re-encoded
+/// in it.
+///
+/// Decoding and encoding run on Tokio's blocking pool; a frame needing
+/// neither is returned untouched.
+pub async fn finish_frame(frame: ExtractedFrame, config: &VideoConfig) -> Result<ExtractedFrame> {
+	if config.variants.is_empty() && config.format.ffmpeg_format() == config.format {
+		return Ok(frame);
+	}
+
+	let config = config.clone();
+	tokio::task::spawn_blocking(move || finish(frame, &config))
+		.await
+		.map_err(std::io::Error::from)?
+}
+
+fn finish(mut frame: ExtractedFrame, config: &VideoConfig) -> Result<ExtractedFrame> {
+	let image = match &frame.data {
+		Some(data) => image::load_from_memory(data)?,
+		None => image::open(&frame.path)?,
+	}
+	.into_rgb8();
+
+	let path = frame.data.is_none().then_some(frame.path.as_path());
+	frame.variants = render_variants(&image, path, config)?;
+
+	if config.format.ffmpeg_format() != config.format {
+		let bytes = encode(&image, config)?;
+		if frame.data.is_some() {
+			frame.data = Some(bytes);
+		} else {
+			let path = frame.path.with_extension(config.format.extension());
+			std::fs::write(&path, bytes)?;
+			std::fs::remove_file(&frame.path)?;
+			frame.path = path;
+		}
+	}
+	Ok(frame)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+
+	use super::*;
+	use crate::variants::FrameSize;
+
+	#[test]
+	fn test_percent_quality() {
+		assert_eq!(percent_quality(1), 100);
+		assert_eq!(percent_quality(2), 97);
+		assert_eq!(percent_quality(31), 10);
+	}
+
+	#[test]
+	fn test_encode_round_trip() {
+		let image = RgbImage::from_pixel(4, 3, image::Rgb([10, 200, 30]));
+		for format in [
+			ImageFormat::Jpeg,
+			ImageFormat::Png,
+			ImageFormat::WebpLossless,
+		] {
+			let config = VideoConfig {
+				format,
+				..VideoConfig::default()
+			};
+			let bytes = encode(&image, &config);
+			assert!(bytes.is_ok());
+			let Ok(bytes) = bytes else { return };
+			let decoded = image::load_from_memory(&bytes);
+			assert!(decoded.is_ok_and(|d| (d.width(), d.height()) == (4, 3)));
+		}
+
+		// Lossless really is
+		let config = VideoConfig {
+			format: ImageFormat::WebpLossless,
+			..VideoConfig::default()
+		};
+		let decoded = encode(&image, &config)
+			.ok()
+			.and_then(|bytes| image::load_from_memory(&bytes).ok());
+		assert_eq!(decoded.map(image::DynamicImage::into_rgb8), Some(image));
+	}
+
+	#[test]
+	fn test_encode_avif() {
+		let image = RgbImage::from_pixel(16, 16, image::Rgb([10, 200, 30]));
+		let config = VideoConfig {
+			format: ImageFormat::Avif,
+			effort: 10,
+			..VideoConfig::default()
+		};
+		let bytes = encode(&image, &config);
+		if cfg!(feature = "avif") {
+			// An ISO-BMFF `ftyp` box naming the AVIF brand
+			assert!(bytes.is_ok_and(|b| b.get(4..12) == Some(b"ftypavif".as_slice())));
+		} else {
+			assert!(bytes.is_err());
+		}
+	}
+
+	#[tokio::test]
+	async fn test_finish_frames() {
+		let dir = tempfile::tempdir();
+		assert!(dir.is_ok());
+		let Ok(dir) = dir else { return };
+
+		let path = dir.path().join("frame-00000.png");
+		assert!(RgbImage::new(64, 32).save(&path).is_ok());
+		let data = encode(&RgbImage::new(64, 32), &VideoConfig::default());
+		assert!(data.is_ok());
+
+		let frame = ExtractedFrame {
+			path: path.clone(),
+			timestamp_seconds: 0.0,
+			frame_number: 0,
+			is_keyframe: false,
+			data: None,
+			variants: Vec::new(),
+		};
+		let in_memory = ExtractedFrame {
+			path: PathBuf::new(),
+			data: data.ok(),
+			..frame.clone()
+		};
+		let config = VideoConfig {
+			format: ImageFormat::Png,
+			variants: vec![FrameSize::new("thumb", 16, 0), FrameSize::new("big", 0, 64)],
+			..VideoConfig::default()
+		};
+
+		let frames = finish_frames(vec![frame, in_memory], &config).await;
+		assert!(frames.is_ok());
+		let Ok(frames) = frames else { return };
+
+		let sizes: Vec<(u32, u32)> = frames[0]
+			.variants
+			.iter()
+			.map(|v| (v.width, v.height))
+			.collect();
+		assert_eq!(sizes, vec![(16, 8), (64, 32)]);
+		let thumbnail = &frames[0].variants[0];
+		assert_eq!(thumbnail.path, dir.path().join("frame-00000-thumb.png"));
+		assert!(image::open(&thumbnail.path).is_ok_and(|i| (i.width(), i.height()) == (16, 8)));
+
+		let in_memory = &frames[1].variants[0];
+		assert_eq!(in_memory.path, PathBuf::new());
+		assert!(in_memory
+			.data
+			.as_deref()
+			.is_some_and(|data| image::load_from_memory(data).is_ok_and(|i| i.width() == 16)));
+	}
+}
@@ -2,6 +2,8 @@
 
 use std::path::PathBuf;
 
+pub use lucid_core::{Classify, ConfigIssue, ErrorKind};
+
 /// Errors that can occur during perception operations.
 #[derive(Debug, thiserror::Error)]
 pub enum PerceptionError {
@@ -69,6 +71,20 @@ pub enum PerceptionError {
 	#[error("Transcription failed: {0}")]
 	TranscriptionFailed(String),
 
+	/// An extracted frame failed verification.
+	#[cfg(feature = "verify")]
+	#[error("Extracted frame {path} failed verification: {reason}")]
+	CorruptFrame {
+		/// Path to the frame image file
+		path: PathBuf,
+		/// What is wrong with it
+		reason: String,
+	},
+
+	/// A configuration value is out of range.
+	#[error("Invalid configuration: {0}")]
+	InvalidConfig(ConfigIssue),
+
 	/// Task was cancelled.
 	#[error("Operation was cancelled")]
 	Cancelled,
@@ -81,6 +97,51 @@ pub enum PerceptionError {
 	},
 }
 
+impl Classify for PerceptionError {
+	/// Broad class of this error, for deciding between retrying, skipping
+	/// the video, and alerting.
+	///
+	/// `FFmpeg` exiting with a status is blamed on the input; `FFmpeg`
+	/// killed by a signal (no exit code) is treated as transient.
+	fn kind(&self) -> ErrorKind {
+		match self {
+			Self::FfmpegNotFound | Self::FfprobeNotFound => ErrorKind::MissingDependency,
+			Self::VideoNotFound(_)
+			| Self::InvalidVideo(_)
+			| Self::FrameExtractionFailed { .. }
+			| Self::NoVideoStream(_)
+			| Self::NoAudioStream(_)
+			| Self::InvalidConfig(_) => ErrorKind::InvalidInput,
+			Self::FfmpegError { exit_code, .. } => {
+				if exit_code.is_some() {
+					ErrorKind::InvalidInput
+				} else {
+					ErrorKind::Transient
+				}
+			}
+			Self::ImageReadError(e) => match e {
+				image::ImageError::IoError(e) => ErrorKind::from_io(e),
+				image::ImageError::Limits(_) => ErrorKind::ResourceExhausted,
+				image::ImageError::Decoding(_) | image::ImageError::Unsupported(_) => {
+					ErrorKind::InvalidInput
+				}
+				image::ImageError::Encoding(_) | image::ImageError::Parameter(_) => {
+					ErrorKind::Internal
+				}
+			},
+			Self::IoError(e) => ErrorKind::from_io(e),
+			Self::JsonParseError(_) => ErrorKind::Internal,
+			#[cfg(feature = "transcription")]
+			Self::WhisperModelNotFound(_) => ErrorKind::MissingDependency,
+			#[cfg(feature = "transcription")]
+			Self::TranscriptionFailed(_) => ErrorKind::Internal,
+			#[cfg(feature = "verify")]
+			Self::CorruptFrame { .. } => ErrorKind::Transient,
+			Self::Cancelled | Self::Timeout { .. } => ErrorKind::Transient,
+		}
+	}
+}
+
 impl PerceptionError {
 	/// Check if this error indicates no audio stream (not a fatal error for some operations).
 	#[must_use]
@@ -90,17 +151,8 @@ impl PerceptionError {
 
 	/// Check if this error is due to a missing dependency (`FFmpeg`, Whisper model).
 	#[must_use]
-	pub const fn is_missing_dependency(&self) -> bool {
-		matches!(self, Self::FfmpegNotFound | Self::FfprobeNotFound) || {
-			#[cfg(feature = "transcription")]
-			{
-				matches!(self, Self::WhisperModelNotFound(_))
-			}
-			#[cfg(not(feature = "transcription"))]
-			{
-				false
-			}
-		}
+	pub fn is_missing_dependency(&self) -> bool {
+		self.kind() == ErrorKind::MissingDependency
 	}
 
 	/// Check if the error is recoverable (e.g., try again later).
@@ -112,3 +164,31 @@ impl PerceptionError {
 
 /// Result type alias for perception operations.
 pub type Result<T> = std::result::Result<T, PerceptionError>;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_error_kinds() {
+		assert_eq!(
+			PerceptionError::FfmpegNotFound.kind(),
+			ErrorKind::MissingDependency
+		);
+		assert!(PerceptionError::FfmpegNotFound.is_missing_dependency());
+		assert_eq!(
+			PerceptionError::InvalidVideo(PathBuf::from("a.mp4")).kind(),
+			ErrorKind::InvalidInput
+		);
+		assert!(PerceptionError::Timeout { seconds: 5 }.is_retryable());
+		assert!(!PerceptionError::NoVideoStream(PathBuf::from("a.mp4")).is_retryable());
+
+		let killed = PerceptionError::FfmpegError {
+			message: String::new(),
+			exit_code: None,
+		};
+		assert!(killed.is_retryable());
+		let io = PerceptionError::from(std::io::Error::from(std::io::ErrorKind::Interrupted));
+		assert_eq!(io.kind(), ErrorKind::Transient);
+	}
+}
@@ -0,0 +1,698 @@
+//! Hierarchical video index.
+//!
+//! A flat list of scene moments is fine for one video, but recall over
+//! thousands of hours of recordings is slow (every scene is scored) and
+//! noisy (a stray match in an unrelated recording ranks next to the
+//! right one). A [`VideoHierarchy`] summarizes a video at three levels:
+//!
+//! - **Scenes**: the scene moments themselves
+//! - **Chapters**: consecutive runs of scenes, split where the footage
+//!   skips ahead or a chapter grows too long
+//! - **Video**: the whole recording
+//!
+//! Every node is a lucid-core [`Moment`] carrying its span, the transcript
+//! text of its children, representative keyframes as `Visual` media, and
+//! per-modality embeddings averaged over its children, so chapters and
+//! videos can be stored and embedded like any other memory.
+//!
+//! [`retrieve_hierarchies`] drills down coarse-to-fine: it ranks videos,
+//! keeps the best few, ranks only their chapters, keeps the best few, and
+//! scores only the scenes inside those.
+
+use std::ops::Range;
+
+use lucid_core::cosine_similarity;
+use serde::{Deserialize, Serialize};
+
+use crate::memory::{scene_runs, MediaRef, Modality, Moment, MomentQuery};
+use crate::pipeline::VideoProcessingOutput;
+use crate::scene::FrameCandidate;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Configuration for building a [`VideoHierarchy`].
+///
+/// A new chapter starts at whichever limit is reached first; a limit of 0
+/// is off.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HierarchyConfig {
+	/// Split where no frame was captured for this long between scenes, in ms (default: 30000)
+	pub chapter_gap_ms: f64,
+
+	/// Most scenes in one chapter (default: 0)
+	pub max_chapter_scenes: usize,
+
+	/// Longest chapter, from its first scene's start to its last scene's end, in ms (default: 600000)
+	pub max_chapter_duration_ms: f64,
+
+	/// Representative keyframes kept per chapter (default: 3)
+	pub chapter_keyframes: usize,
+
+	/// Representative keyframes kept for the whole video (default: 5)
+	pub video_keyframes: usize,
+}
+
+impl Default for HierarchyConfig {
+	fn default() -> Self {
+		Self {
+			chapter_gap_ms: 30_000.0,
+			max_chapter_scenes: 0,
+			max_chapter_duration_ms: 600_000.0,
+			chapter_keyframes: 3,
+			video_keyframes: 5,
+		}
+	}
+}
+
+/// Configuration for coarse-to-fine retrieval.
+///
+/// Each level is scored on its own text, visual, and hash similarity; a
+/// scene's total adds the weighted scores of its chapter and video.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HierarchyRetrievalConfig {
+	/// Weight of text-embedding similarity (default: 1.0)
+	pub text_weight: f64,
+
+	/// Weight of visual-embedding similarity (default: 1.0)
+	pub visual_weight: f64,
+
+	/// Weight of keyframe-hash similarity (default: 0.5)
+	pub hash_weight: f64,
+
+	/// Largest Hamming distance counted as a hash match (default: 12)
+	pub max_hash_distance: u32,
+
+	/// Best-scoring videos whose chapters are searched (default: 3)
+	pub video_beam: usize,
+
+	/// Best-scoring chapters, across those videos, whose scenes are searched (default: 5)
+	pub chapter_beam: usize,
+
+	/// Weight of the video's score in a scene's total (default: 0.25)
+	pub video_weight: f64,
+
+	/// Weight of the chapter's score in a scene's total (default: 0.5)
+	pub chapter_weight: f64,
+
+	/// Maximum scenes to return (default: 10)
+	pub max_results: usize,
+}
+
+impl Default for HierarchyRetrievalConfig {
+	fn default() -> Self {
+		Self {
+			text_weight: 1.0,
+			visual_weight: 1.0,
+			hash_weight: 0.5,
+			max_hash_distance: 12,
+			video_beam: 3,
+			chapter_beam: 5,
+			video_weight: 0.25,
+			chapter_weight: 0.5,
+			max_results: 10,
+		}
+	}
+}
+
+// ============================================================================
+// Index
+// ============================================================================
+
+/// One node of a [`VideoHierarchy`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HierarchyNode {
+	/// Span, aggregated text, representative keyframes, and embeddings
+	pub moment: Moment,
+
+	/// Children in the next finer level (empty for scenes)
+	pub children: Range<usize>,
+}
+
+/// A video summarized at scene, chapter, and video level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoHierarchy {
+	/// The whole video; its children are all chapters
+	pub video: HierarchyNode,
+
+	/// Chapters in time order; each one's id is its position
+	pub chapters: Vec<HierarchyNode>,
+
+	/// Scenes in time order, as passed to [`build_hierarchy`]
+	pub scenes: Vec<HierarchyNode>,
+}
+
+impl VideoHierarchy {
+	/// Scenes of one chapter, or none if `chapter` is out of range.
+	#[must_use]
+	pub fn chapter_scenes(&self, chapter: usize) -> &[HierarchyNode] {
+		self.chapters
+			.get(chapter)
+			.map(|node| &self.scenes[node.children.clone()])
+			.unwrap_or_default()
+	}
+
+	/// Chapter holding `scene`, if `scene` is in range.
+	#[must_use]
+	pub fn scene_chapter(&self, scene: usize) -> Option<usize> {
+		self.chapters
+			.iter()
+			.position(|node| node.children.contains(&scene))
+	}
+
+	/// Rank this video's scenes against a query, coarse-to-fine.
+	///
+	/// Same as [`retrieve_hierarchies`] over this video alone.
+	#[must_use]
+	pub fn retrieve(
+		&self,
+		query: &MomentQuery<'_>,
+		frames: &[FrameCandidate],
+		config: &HierarchyRetrievalConfig,
+	) -> Vec<HierarchyHit> {
+		retrieve_hierarchies(&[(self, frames)], query, config)
+	}
+}
+
+/// Per-level scores for a retrieved scene.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct HierarchyScores {
+	/// Weighted similarity of the whole video
+	pub video: f64,
+	/// Weighted similarity of the chapter
+	pub chapter: f64,
+	/// Weighted similarity of the scene
+	pub scene: f64,
+	/// `scene + chapter_weight × chapter + video_weight × video`
+	pub total: f64,
+}
+
+/// A scene returned by [`retrieve_hierarchies`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HierarchyHit {
+	/// Position of the video in the searched list
+	pub video: usize,
+	/// Index into [`VideoHierarchy::chapters`]
+	pub chapter: usize,
+	/// Index into [`VideoHierarchy::scenes`]
+	pub scene: usize,
+	/// Score breakdown
+	pub scores: HierarchyScores,
+}
+
+// ============================================================================
+// Building
+// ============================================================================
+
+/// Summarize a processed video at scene, chapter, and video level.
+///
+/// `scenes` holds one moment per scene of `output`, in order: the
+/// `Visual` moments of a [`MomentGraph`](crate::memory::MomentGraph) or
+/// [`SceneEpisodes::scenes`](crate::memory::SceneEpisodes::scenes), with
+/// any text and embeddings already attached. Scene moments are kept as
+/// they are, so their ids still point at graph nodes.
+///
+/// Chapter and video nodes get:
+///
+/// - The non-empty text of their children, joined in order
+/// - Up to `chapter_keyframes` / `video_keyframes` of their children's
+///   keyframes, picked to differ from each other as much as possible
+/// - Per modality, the duration-weighted mean of their children's unit
+///   embeddings, normalized
+#[must_use]
+pub fn build_hierarchy(
+	output: &VideoProcessingOutput,
+	scenes: Vec<Moment>,
+	config: &HierarchyConfig,
+) -> VideoHierarchy {
+	let frames = &output.frames;
+	let runs = scene_runs(
+		frames,
+		&scenes,
+		config.chapter_gap_ms,
+		config.max_chapter_scenes,
+		config.max_chapter_duration_ms,
+	);
+
+	let chapters: Vec<HierarchyNode> = runs
+		.into_iter()
+		.enumerate()
+		.map(|(id, run)| HierarchyNode {
+			moment: summarize(id, &scenes[run.clone()], frames, config.chapter_keyframes),
+			children: run,
+		})
+		.collect();
+	let chapter_moments: Vec<Moment> = chapters.iter().map(|c| c.moment.clone()).collect();
+	let video = HierarchyNode {
+		moment: summarize(0, &chapter_moments, frames, config.video_keyframes),
+		children: 0..chapters.len(),
+	};
+	let scenes = scenes
+		.into_iter()
+		.map(|moment| HierarchyNode {
+			moment,
+			children: 0..0,
+		})
+		.collect();
+
+	VideoHierarchy {
+		video,
+		chapters,
+		scenes,
+	}
+}
+
+/// A `Visual` moment spanning `children`, summarizing them.
+fn summarize(
+	id: usize,
+	children: &[Moment],
+	frames: &[FrameCandidate],
+	keyframes: usize,
+) -> Moment {
+	let start_ms = children.first().map_or(0.0, |c| c.start_ms);
+	let end_ms = children.last().map_or(start_ms, |c| c.end_ms.max(start_ms));
+	let mut moment = Moment::new(id, Modality::Visual, start_ms, end_ms);
+
+	moment.text = children
+		.iter()
+		.map(|c| c.text.trim())
+		.filter(|text| !text.is_empty())
+		.collect::<Vec<_>>()
+		.join(" ");
+	moment.media = diverse_keyframes(children, frames, keyframes);
+	for modality in [Modality::Text, Modality::Visual, Modality::Audio] {
+		if let Some(mean) = mean_embedding(children, modality) {
+			moment.set_embedding(modality, mean);
+		}
+	}
+	moment
+}
+
+/// Up to `count` of the children's keyframes, in time order.
+///
+/// Farthest-point selection: start from the keyframe of the longest child,
+/// then repeatedly add the keyframe whose hash is farthest from every one
+/// picked so far, so a chapter of near-identical scenes does not spend its
+/// keyframes on the same shot.
+fn diverse_keyframes(
+	children: &[Moment],
+	frames: &[FrameCandidate],
+	count: usize,
+) -> Vec<MediaRef> {
+	let mut candidates: Vec<(&MediaRef, f64)> = Vec::new();
+	for child in children {
+		for media in child
+			.media
+			.iter()
+			.filter(|m| m.modality == Modality::Visual)
+		{
+			let known = media.index.is_some_and(|i| i < frames.len());
+			if known && !candidates.iter().any(|(m, _)| m.index == media.index) {
+				candidates.push((media, child.duration_ms()));
+			}
+		}
+	}
+	let hash = |media: &MediaRef| media.index.map(|i| &frames[i].hash);
+
+	let mut picked: Vec<usize> = Vec::new();
+	let mut closest = vec![u32::MAX; candidates.len()];
+	while picked.len() < count.min(candidates.len()) {
+		let next = (0..candidates.len())
+			.filter(|i| !picked.contains(i))
+			.max_by(|&a, &b| {
+				closest[a]
+					.cmp(&closest[b])
+					.then(candidates[a].1.total_cmp(&candidates[b].1))
+					.then(b.cmp(&a))
+			});
+		let Some(next) = next else {
+			break;
+		};
+		picked.push(next);
+		if let Some(next_hash) = hash(candidates[next].0) {
+			for (i, (media, _)) in candidates.iter().enumerate() {
+				if let Some(other) = hash(media) {
+					closest[i] = closest[i].min(next_hash.distance(other));
+				}
+			}
+		}
+	}
+
+	picked.sort_unstable();
+	picked
+		.into_iter()
+		.map(|i| candidates[i].0.clone())
+		.collect()
+}
+
+/// Duration-weighted mean of the children's unit embeddings of `modality`,
+/// normalized; children with another dimension than the first are skipped.
+fn mean_embedding(children: &[Moment], modality: Modality) -> Option<Vec<f64>> {
+	let embedded: Vec<(&[f64], f64)> = children
+		.iter()
+		.filter_map(|c| c.embedding(modality).map(|e| (e, c.duration_ms())))
+		.collect();
+	let dimension = embedded.first()?.0.len();
+	// Zero-length children would otherwise drop out; weigh all equally then
+	let equal = embedded.iter().all(|(_, duration)| *duration <= 0.0);
+
+	let mut mean = vec![0.0; dimension];
+	for &(embedding, duration) in &embedded {
+		let norm = embedding.iter().map(|x| x * x).sum::<f64>().sqrt();
+		if embedding.len() != dimension || norm == 0.0 {
+			continue;
+		}
+		let weight = if equal { 1.0 } else { duration } / norm;
+		for (m, x) in mean.iter_mut().zip(embedding) {
+			*m = weight.mul_add(*x, *m);
+		}
+	}
+
+	let norm = mean.iter().map(|x| x * x).sum::<f64>().sqrt();
+	(norm > 0.0).then(|| mean.iter().map(|x| x / norm).collect())
+}
+
+// ============================================================================
+// Retrieval
+// ============================================================================
+
+/// Rank scenes across many videos, drilling down coarse-to-fine.
+///
+/// 1. Score every video node; keep the `video_beam` best
+/// 2. Score their chapters; keep the `chapter_beam` best overall
+/// 3. Score the scenes of those chapters
+/// 4. `total = scene + chapter_weight × chapter + video_weight × video`,
+///    ranked descending
+///
+/// Each level's score is `Σ weight × similarity` over text embedding,
+/// visual embedding, and the closest of the node's keyframe hashes. Only
+/// `video_beam` chapter lists and `chapter_beam` scene lists are ever
+/// scored, however much footage is indexed.
+///
+/// # Arguments
+///
+/// * `videos` - Each video's hierarchy with its frames, for keyframe hashes
+/// * `query` - Embeddings and hash to match
+/// * `config` - Signal weights and beam widths
+#[must_use]
+pub fn retrieve_hierarchies(
+	videos: &[(&VideoHierarchy, &[FrameCandidate])],
+	query: &MomentQuery<'_>,
+	config: &HierarchyRetrievalConfig,
+) -> Vec<HierarchyHit> {
+	let video_scores: Vec<(usize, f64)> = videos
+		.iter()
+		.enumerate()
+		.map(|(v, (hierarchy, frames))| {
+			(
+				v,
+				node_score(&hierarchy.video.moment, query, frames, config),
+			)
+		})
+		.collect();
+	let video_scores = best(video_scores, config.video_beam);
+
+	let chapter_scores: Vec<((usize, usize), f64)> = video_scores
+		.iter()
+		.flat_map(|&(v, _)| {
+			let (hierarchy, frames) = videos[v];
+			hierarchy
+				.chapters
+				.iter()
+				.enumerate()
+				.map(move |(c, chapter)| {
+					((v, c), node_score(&chapter.moment, query, frames, config))
+				})
+		})
+		.collect();
+	let chapter_scores = best(chapter_scores, config.chapter_beam);
+
+	let mut hits: Vec<HierarchyHit> = Vec::new();
+	for &((v, c), chapter) in &chapter_scores {
+		let (hierarchy, frames) = videos[v];
+		let video = video_scores
+			.iter()
+			.find(|(candidate, _)| *candidate == v)
+			.map_or(0.0, |(_, score)| *score);
+		let first = hierarchy.chapters[c].children.start;
+		for (offset, node) in hierarchy.chapter_scenes(c).iter().enumerate() {
+			let scene = node_score(&node.moment, query, frames, config);
+			let total = config
+				.video_weight
+				.mul_add(video, config.chapter_weight.mul_add(chapter, scene));
+			if total > 0.0 {
+				hits.push(HierarchyHit {
+					video: v,
+					chapter: c,
+					scene: first + offset,
+					scores: HierarchyScores {
+						video,
+						chapter,
+						scene,
+						total,
+					},
+				});
+			}
+		}
+	}
+	hits.sort_by(|a, b| {
+		b.scores
+			.total
+			.total_cmp(&a.scores.total)
+			.then((a.video, a.scene).cmp(&(b.video, b.scene)))
+	});
+	hits.truncate(config.max_results);
+	hits
+}
+
+/// The `count` highest-scoring entries, best first, ties by key.
+fn best<K: Ord + Copy>(mut scores: Vec<(K, f64)>, count: usize) -> Vec<(K, f64)> {
+	scores.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+	scores.truncate(count);
+	scores
+}
+
+/// Weighted direct similarity of one node to the query.
+fn node_score(
+	moment: &Moment,
+	query: &MomentQuery<'_>,
+	frames: &[FrameCandidate],
+	config: &HierarchyRetrievalConfig,
+) -> f64 {
+	let similarity =
+		|probe: Option<&[f64]>, modality: Modality| match (probe, moment.embedding(modality)) {
+			(Some(probe), Some(embedding)) => cosine_similarity(probe, embedding).max(0.0),
+			_ => 0.0,
+		};
+	let text = similarity(query.text_embedding, Modality::Text);
+	let visual = similarity(query.visual_embedding, Modality::Visual);
+
+	let scale = f64::from(config.max_hash_distance) + 1.0;
+	let hash = query.hash.map_or(0.0, |probe| {
+		moment
+			.media
+			.iter()
+			.filter(|m| m.modality == Modality::Visual)
+			.filter_map(|m| m.index.and_then(|i| frames.get(i)))
+			.map(|frame| probe.distance(&frame.hash))
+			.filter(|&distance| distance <= config.max_hash_distance)
+			.min()
+			.map_or(0.0, |distance| 1.0 - f64::from(distance) / scale)
+	});
+
+	config.hash_weight.mul_add(
+		hash,
+		config
+			.text_weight
+			.mul_add(text, config.visual_weight * visual),
+	)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::memory::{episodes_from_scenes, SceneEpisodeConfig};
+	use crate::pipeline::ProcessingStats;
+	use crate::resources::PipelineResources;
+	use crate::scene::PerceptualHash;
+	use crate::video::{ExtractedFrame, VideoMetadata};
+
+	fn make_frame(frame_number: u32, timestamp_seconds: f64, hash: u8) -> FrameCandidate {
+		FrameCandidate {
+			frame: ExtractedFrame {
+				path: format!("frame_{frame_number}.jpg").into(),
+				timestamp_seconds,
+				frame_number,
+				is_keyframe: false,
+				data: None,
+				variants: Vec::new(),
+			},
+			hash: PerceptualHash {
+				bytes: vec![hash, 0],
+				hex: String::new(),
+			},
+			is_scene_change: true,
+			is_duplicate: false,
+			distance_from_previous: 0,
+		}
+	}
+
+	/// One frame per scene at `times` (seconds), each with its own hash.
+	fn make_output(times: &[(f64, u8)], duration_seconds: f64) -> VideoProcessingOutput {
+		VideoProcessingOutput {
+			metadata: VideoMetadata {
+				duration_seconds,
+				frame_rate: 30.0,
+				frame_count: 0,
+				width: 640,
+				height: 360,
+				codec: "h264".to_string(),
+				has_audio: false,
+				rotation: 0,
+			},
+			frames: times
+				.iter()
+				.zip(0..)
+				.map(|(&(t, hash), n)| make_frame(n, t, hash))
+				.collect(),
+			#[cfg(feature = "transcription")]
+			transcript: None,
+			no_audio: true,
+			stats: ProcessingStats {
+				frames_extracted: 0,
+				scene_changes: 0,
+				duplicates: 0,
+				extraction_time_ms: 0,
+				scene_detection_time_ms: 0,
+				transcription_time_ms: 0,
+				resources: PipelineResources::default(),
+			},
+		}
+	}
+
+	/// Scenes with text and text embeddings attached.
+	fn scenes(output: &VideoProcessingOutput, embeddings: &[[f64; 2]]) -> Vec<Moment> {
+		let mut scenes = episodes_from_scenes(output, &SceneEpisodeConfig::default()).scenes;
+		for (i, (scene, embedding)) in scenes.iter_mut().zip(embeddings).enumerate() {
+			scene.text = format!("scene {i}");
+			scene.set_embedding(Modality::Text, embedding.to_vec());
+		}
+		scenes
+	}
+
+	#[test]
+	fn test_build_hierarchy_levels() {
+		// Two chapters: scenes at 0s and 10s, then a skip to 100s and 110s
+		let output = make_output(
+			&[
+				(0.0, 0b0000_0000),
+				(10.0, 0b0000_0001),
+				(100.0, 0b1111_0000),
+				(110.0, 0b1111_1111),
+			],
+			120.0,
+		);
+		let embeddings = [[1.0, 0.0], [1.0, 0.0], [0.0, 1.0], [0.0, 1.0]];
+		let hierarchy = build_hierarchy(
+			&output,
+			scenes(&output, &embeddings),
+			&HierarchyConfig::default(),
+		);
+
+		assert_eq!(hierarchy.scenes.len(), 4);
+		let children: Vec<Range<usize>> = hierarchy
+			.chapters
+			.iter()
+			.map(|c| c.children.clone())
+			.collect();
+		assert_eq!(children, vec![0..2, 2..4]);
+		assert_eq!(hierarchy.chapter_scenes(1).len(), 2);
+		assert_eq!(hierarchy.scene_chapter(3), Some(1));
+		assert_eq!(hierarchy.scene_chapter(4), None);
+
+		let chapter = &hierarchy.chapters[1].moment;
+		assert_eq!((chapter.start_ms, chapter.end_ms), (100_000.0, 120_000.0));
+		assert_eq!(chapter.text, "scene 2 scene 3");
+		assert_eq!(chapter.embedding(Modality::Text), Some(&[0.0, 1.0][..]));
+		assert_eq!(chapter.media.len(), 2);
+
+		let video = &hierarchy.video.moment;
+		assert_eq!(hierarchy.video.children, 0..2);
+		assert_eq!((video.start_ms, video.end_ms), (0.0, 120_000.0));
+		assert_eq!(video.text, "scene 0 scene 1 scene 2 scene 3");
+		// The first chapter lasts 100s and the second 20s
+		assert!(video
+			.embedding(Modality::Text)
+			.is_some_and(|mean| mean[0] > mean[1]));
+	}
+
+	#[test]
+	fn test_diverse_keyframes() {
+		// Scenes 0 and 1 look alike; scene 2 is the odd one out
+		let output = make_output(
+			&[(0.0, 0b0000_0000), (10.0, 0b0000_0001), (20.0, 0b1111_1111)],
+			30.0,
+		);
+		let config = HierarchyConfig {
+			chapter_keyframes: 2,
+			..HierarchyConfig::default()
+		};
+		let hierarchy = build_hierarchy(&output, scenes(&output, &[]), &config);
+
+		let picked: Vec<Option<usize>> = hierarchy.chapters[0]
+			.moment
+			.media
+			.iter()
+			.map(|m| m.index)
+			.collect();
+		assert_eq!(picked, vec![Some(0), Some(2)]);
+	}
+
+	#[test]
+	fn test_retrieve_drills_down() {
+		let config = HierarchyConfig::default();
+		let meeting_output = make_output(&[(0.0, 0), (10.0, 0), (100.0, 0), (110.0, 0)], 120.0);
+		let meeting_scenes = scenes(
+			&meeting_output,
+			&[[1.0, 0.0], [0.9, 0.1], [0.0, 1.0], [0.1, 0.9]],
+		);
+		let meeting = build_hierarchy(&meeting_output, meeting_scenes, &config);
+		let commute_output = make_output(&[(0.0, 0), (10.0, 0)], 20.0);
+		let commute_scenes = scenes(&commute_output, &[[0.0, 1.0], [0.0, 1.0]]);
+		let commute = build_hierarchy(&commute_output, commute_scenes, &config);
+
+		let probe = [1.0, 0.0];
+		let query = MomentQuery {
+			text_embedding: Some(&probe),
+			..MomentQuery::default()
+		};
+		let config = HierarchyRetrievalConfig {
+			video_beam: 1,
+			chapter_beam: 1,
+			..HierarchyRetrievalConfig::default()
+		};
+		let videos = [
+			(&commute, commute_output.frames.as_slice()),
+			(&meeting, meeting_output.frames.as_slice()),
+		];
+		let hits = retrieve_hierarchies(&videos, &query, &config);
+
+		// Only the meeting's first chapter is searched
+		assert_eq!(hits.len(), 2);
+		assert!(hits.iter().all(|h| h.video == 1 && h.chapter == 0));
+		assert_eq!(hits[0].scene, 0);
+		assert!(hits[0].scores.total > hits[1].scores.total);
+		assert!(hits[0].scores.chapter > 0.0 && hits[0].scores.video > 0.0);
+
+		// Wider beams reach the other chapter too
+		let config = HierarchyRetrievalConfig::default();
+		let hits = meeting.retrieve(&query, &meeting_output.frames, &config);
+		assert_eq!(hits.len(), 4);
+		assert_eq!(hits[0].scene, 0);
+	}
+}
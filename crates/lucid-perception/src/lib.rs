@@ -12,9 +12,13 @@
 //! ## Features
 //!
 //! - **Frame Extraction**: Extract frames from videos using `FFmpeg` CLI
+//! - **Frame Variants**: Write thumbnails and other sizes in the same pass
+//! - **Remote Input**: Read `http(s)://` URLs and HLS playlists without downloading first
 //! - **Scene Detection**: Detect scene changes using perceptual hashing
 //! - **Transcription**: Transcribe audio using Whisper (optional)
 //! - **Pipeline**: Parallel processing of video analysis tasks
+//! - **Memory Graph**: Turn pipeline output into lucid-core moments and associations
+//! - **Hierarchical Index**: Summarize long recordings by scene, chapter, and video
 //!
 //! ## Example
 //!
@@ -34,13 +38,30 @@
 //!
 //! - `transcription`: Enable Whisper-based audio transcription
 //! - `cuda`: Enable CUDA acceleration for Whisper (requires `transcription`)
+//! - `metrics`: Emit processing counters and timings via the `metrics` crate
+//! - `verify`: Decode a sample of extracted frames to catch truncated output.
+//!   Requested as `image`, which Cargo reserves for the `image` dependency.
+//! - `libav`: Decode through linked `FFmpeg` libraries instead of the `ffmpeg` and
+//!   `ffprobe` binaries
+//! - `avif`: Write frames as AVIF, encoded in process
 
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 #![allow(clippy::needless_return)]
 
+mod encode;
 pub mod error;
+pub mod hierarchy;
+#[cfg(feature = "libav")]
+mod libav;
+pub mod memory;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod resources;
 pub mod scene;
+pub mod variants;
+#[cfg(feature = "verify")]
+pub mod verify;
 pub mod video;
 
 #[cfg(feature = "transcription")]
@@ -49,21 +70,43 @@ pub mod transcribe;
 pub mod pipeline;
 
 // Re-exports for convenience
-pub use error::{PerceptionError, Result};
+pub use error::{Classify, ConfigIssue, ErrorKind, PerceptionError, Result};
 pub use scene::{
-	compute_phash, detect_scene_changes, hamming_distance, FrameCandidate, SceneConfig,
+	compute_phash, compute_phash_from_memory, detect_scene_changes, hamming_distance,
+	FrameCandidate, HashIndex, SceneConfig,
 };
+pub use variants::{FrameSize, FrameVariant};
 pub use video::{
-	check_ffmpeg, check_ffprobe, extract_frame_at, extract_frames, get_video_metadata,
-	ExtractedFrame, ImageFormat, VideoConfig, VideoMetadata,
+	available_hwaccels, check_ffmpeg, check_ffprobe, extract_frame_at, extract_frames,
+	extract_frames_at, extract_frames_stream, extract_frames_to_memory, get_video_metadata,
+	get_video_metadata_with, is_remote, ExtractedFrame, HwAccel, ImageFormat, NetworkConfig,
+	VideoConfig, VideoMetadata,
+};
+
+#[cfg(feature = "verify")]
+pub use verify::{
+	verify_frame, verify_frames, FrameDefect, FrameFailure, FrameVerification, FrameVerifyConfig,
 };
 
 #[cfg(feature = "transcription")]
 pub use transcribe::{
-	transcribe_video, TranscriptSegment, TranscriptionConfig, TranscriptionResult,
+	transcribe_video, transcribe_video_with, TranscriptSegment, TranscriptionConfig,
+	TranscriptionResult,
+};
+
+pub use hierarchy::{
+	build_hierarchy, retrieve_hierarchies, HierarchyConfig, HierarchyHit, HierarchyNode,
+	HierarchyRetrievalConfig, HierarchyScores, VideoHierarchy,
+};
+
+pub use memory::{
+	build_moment_graph, episodes_from_scenes, visual_similarity_associations, MediaRef, Modality,
+	Moment, MomentGraph, MomentGraphConfig, MomentQuery, MomentRetrievalConfig, MomentScores,
+	RetrievedMoment, SceneEpisodeConfig, SceneEpisodes, VisualSimilarityConfig,
 };
 
 pub use pipeline::{process_video, process_video_sync, PipelineConfig, VideoProcessingOutput};
+pub use resources::{PeakSampler, PipelineResources, StageResources};
 
 /// Library version.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
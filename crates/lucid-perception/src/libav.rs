@@ -0,0 +1,689 @@
+//! Native video backend through the `FFmpeg` libraries.
+//!
+//! With the `libav` feature, [`video`](crate::video) probes and decodes
+//! through libavformat/libavcodec linked into the process (via
+//! `ffmpeg-next`) instead of spawning `ffmpeg` and `ffprobe`, so nothing
+//! needs to be on PATH. Frames are selected as the CLI backend selects
+//! them: keyframes with `keyframes_only`, otherwise the first frame at or
+//! after each `interval_seconds` step from `start_time`, up to
+//! `max_frames` and `end_time`.
+//!
+//! Decoding is blocking and runs on Tokio's blocking pool. Hardware
+//! decoding is not available here; `hwaccel` is ignored. libav does not
+//! apply the display matrix as the `ffmpeg` binary does, so frames are
+//! turned upright here after conversion to RGB.
+
+use std::path::{Path, PathBuf};
+
+use ffmpeg::{codec, decoder, format::Pixel, frame, media, packet, software::scaling, threading};
+use ffmpeg_next as ffmpeg;
+use futures::stream::{self, Stream};
+use image::{imageops, RgbImage};
+use tokio::sync::mpsc;
+use tracing::debug;
+
+use crate::encode::encode;
+use crate::error::{PerceptionError, Result};
+use crate::variants::render_variants;
+use crate::video::{
+	check_input, display_rotation, input_options, ExtractedFrame, ImageFormat, NetworkConfig,
+	VideoConfig, VideoMetadata, DETERMINISTIC_FFMPEG_THREADS,
+};
+
+/// libav's internal time unit (`AV_TIME_BASE`), per second.
+const AV_TIME_BASE: f64 = 1_000_000.0;
+
+/// libav's "no timestamp" marker (`AV_NOPTS_VALUE`).
+const NO_PTS: i64 = i64::MIN;
+
+// ============================================================================
+// Entry Points
+// ============================================================================
+
+/// Initialize the libraries; fails only if they cannot be loaded.
+pub fn init() -> Result<()> {
+	ffmpeg::init().map_err(libav_error)
+}
+
+/// [`get_video_metadata_with`](crate::video::get_video_metadata_with)
+/// through libav.
+pub async fn metadata(video_path: &Path, network: &NetworkConfig) -> Result<VideoMetadata> {
+	let video_path = video_path.to_path_buf();
+	let network = network.clone();
+	blocking(move || probe(&video_path, &network)).await
+}
+
+/// [`extract_frames`](crate::video::extract_frames) through libav, writing
+/// `{prefix}-{n:05}.{ext}` into `output_dir`.
+pub async fn extract_frames(
+	video_path: &Path,
+	config: &VideoConfig,
+	prefix: &str,
+) -> Result<Vec<ExtractedFrame>> {
+	let video_path = video_path.to_path_buf();
+	let config = config.clone();
+	let prefix = prefix.to_string();
+	blocking(move || {
+		let mut frames = Vec::new();
+		let mut failure = None;
+		decode_frames(&video_path, &config, |decoded| {
+			let path = config.output_dir.join(format!(
+				"{prefix}-{:05}.{}",
+				frames.len(),
+				config.format.extension()
+			));
+			match decoded.into_frame(frames.len(), Some(path), &config) {
+				Ok(frame) => {
+					frames.push(frame);
+					true
+				}
+				Err(e) => {
+					failure = Some(e);
+					false
+				}
+			}
+		})?;
+		failure.map_or(Ok(frames), Err)
+	})
+	.await
+}
+
+/// [`extract_frame_at`](crate::video::extract_frame_at) through libav.
+pub async fn extract_frame_at(
+	video_path: &Path,
+	timestamp_seconds: f64,
+	output_path: &Path,
+	quality: u32,
+) -> Result<ExtractedFrame> {
+	let video_path = video_path.to_path_buf();
+	let output_path = output_path.to_path_buf();
+	blocking(move || {
+		let decoded = frame_at(&video_path, timestamp_seconds)?;
+		let config = VideoConfig {
+			quality,
+			format: format_for(&output_path),
+			..VideoConfig::default()
+		};
+		if let Some(parent) = output_path.parent() {
+			std::fs::create_dir_all(parent)?;
+		}
+		decoded.into_frame(0, Some(output_path), &config)
+	})
+	.await
+}
+
+/// [`extract_frames_at`](crate::video::extract_frames_at) through libav,
+/// writing `{prefix}-at-{n:05}.{ext}` into `output_dir` for the first
+/// frame at or after each of `targets` (sorted, distinct).
+pub async fn extract_frames_at(
+	video_path: &Path,
+	targets: &[f64],
+	config: &VideoConfig,
+	prefix: &str,
+) -> Result<Vec<ExtractedFrame>> {
+	let video_path = video_path.to_path_buf();
+	let targets = targets.to_vec();
+	let config = config.clone();
+	let prefix = prefix.to_string();
+	blocking(move || {
+		let threads = config.deterministic.then_some(DETERMINISTIC_FFMPEG_THREADS);
+		let mut decoder =
+			VideoDecoder::open(&video_path, threads, &config.network, config.auto_rotate)?;
+		if let Some(&first) = targets.first().filter(|first| **first > 0.0) {
+			decoder.seek(first)?;
+		}
+
+		let mut frames = Vec::new();
+		let mut next = 0;
+		decoder.run(false, |decoder| {
+			let timestamp = decoder.timestamp_seconds();
+			if targets.get(next).is_some_and(|target| timestamp < *target) {
+				return Ok(true);
+			}
+			// One frame may satisfy several close targets
+			while targets.get(next).is_some_and(|target| *target <= timestamp) {
+				next += 1;
+			}
+
+			let decoded = decoder.convert()?;
+			let path = config.output_dir.join(format!(
+				"{prefix}-at-{:05}.{}",
+				frames.len(),
+				config.format.extension()
+			));
+			frames.push(decoded.into_frame(frames.len(), Some(path), &config)?);
+			Ok(next < targets.len())
+		})?;
+
+		debug!(count = frames.len(), "Decoded frames at timestamps");
+		Ok(frames)
+	})
+	.await
+}
+
+/// [`extract_frames_to_memory`](crate::video::extract_frames_to_memory)
+/// through libav.
+pub async fn extract_frames_to_memory(
+	video_path: &Path,
+	config: &VideoConfig,
+) -> Result<Vec<ExtractedFrame>> {
+	let video_path = video_path.to_path_buf();
+	let config = config.clone();
+	blocking(move || {
+		let mut frames = Vec::new();
+		let mut failure = None;
+		decode_frames(&video_path, &config, |decoded| {
+			match decoded.into_frame(frames.len(), None, &config) {
+				Ok(frame) => {
+					frames.push(frame);
+					true
+				}
+				Err(e) => {
+					failure = Some(e);
+					false
+				}
+			}
+		})?;
+		failure.map_or(Ok(frames), Err)
+	})
+	.await
+}
+
+/// [`extract_frames_stream`](crate::video::extract_frames_stream) through
+/// libav.
+///
+/// The decoder runs on the blocking pool and hands frames over a channel
+/// of one, so it stays at most a frame ahead of the consumer. Dropping the
+/// stream closes the channel, which stops the decoder.
+pub fn extract_frames_stream(
+	video_path: PathBuf,
+	config: VideoConfig,
+) -> impl Stream<Item = Result<ExtractedFrame>> + Send + 'static {
+	enum State {
+		Pending(PathBuf, VideoConfig),
+		Running(mpsc::Receiver<Result<ExtractedFrame>>),
+	}
+
+	stream::unfold(State::Pending(video_path, config), |state| async move {
+		let mut receiver = match state {
+			State::Pending(video_path, config) => {
+				let (sender, receiver) = mpsc::channel(1);
+				drop(tokio::task::spawn_blocking(move || {
+					let mut number = 0;
+					let result = decode_frames(&video_path, &config, |decoded| {
+						let frame = decoded.into_frame(number, None, &config);
+						number += 1;
+						let failed = frame.is_err();
+						sender.blocking_send(frame).is_ok() && !failed
+					});
+					if let Err(e) = result {
+						let _ = sender.blocking_send(Err(e));
+					}
+				}));
+				receiver
+			}
+			State::Running(receiver) => receiver,
+		};
+		let item = receiver.recv().await?;
+		Some((item, State::Running(receiver)))
+	})
+}
+
+/// Run blocking libav work on the blocking pool.
+async fn blocking<T: Send + 'static>(
+	work: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T> {
+	tokio::task::spawn_blocking(work)
+		.await
+		.map_err(|e| PerceptionError::FfmpegError {
+			message: e.to_string(),
+			exit_code: None,
+		})?
+}
+
+fn libav_error(e: ffmpeg::Error) -> PerceptionError {
+	PerceptionError::FfmpegError {
+		message: e.to_string(),
+		exit_code: None,
+	}
+}
+
+// ============================================================================
+// Probing
+// ============================================================================
+
+/// Open `video_path`, passing the same input options as the CLI backend.
+fn open(video_path: &Path, network: &NetworkConfig) -> Result<ffmpeg::format::context::Input> {
+	init()?;
+	check_input(video_path)?;
+	let mut options = ffmpeg::Dictionary::new();
+	for (name, value) in input_options(video_path, network)? {
+		options.set(name, &value);
+	}
+	ffmpeg::format::input_with_dictionary(&video_path, options)
+		.map_err(|_| PerceptionError::InvalidVideo(video_path.to_path_buf()))
+}
+
+/// A rational as `f64`, or `None` if it is zero or undefined.
+fn positive(value: ffmpeg::Rational) -> Option<f64> {
+	let value = f64::from(value);
+	(value.is_finite() && value > 0.0).then_some(value)
+}
+
+#[allow(
+	clippy::cast_precision_loss,
+	clippy::cast_possible_truncation,
+	clippy::cast_sign_loss
+)]
+fn probe(video_path: &Path, network: &NetworkConfig) -> Result<VideoMetadata> {
+	let input = open(video_path, network)?;
+	let stream = input
+		.streams()
+		.best(media::Type::Video)
+		.ok_or_else(|| PerceptionError::NoVideoStream(video_path.to_path_buf()))?;
+	let has_audio = input.streams().best(media::Type::Audio).is_some();
+
+	// Stream duration first, then the container's, as with FFprobe
+	let duration_seconds = positive(stream.time_base())
+		.filter(|_| stream.duration() > 0)
+		.map(|time_base| stream.duration() as f64 * time_base)
+		.or_else(|| (input.duration() > 0).then(|| input.duration() as f64 / AV_TIME_BASE))
+		.unwrap_or(0.0);
+	let frame_rate = positive(stream.rate())
+		.or_else(|| positive(stream.avg_frame_rate()))
+		.unwrap_or(30.0);
+	let frame_count = u64::try_from(stream.frames())
+		.ok()
+		.filter(|&frames| frames > 0)
+		.unwrap_or((duration_seconds * frame_rate) as u64);
+
+	let codec_name = stream.parameters().id().name().to_string();
+	let decoder = codec::context::Context::from_parameters(stream.parameters())
+		.and_then(|context| context.decoder().video())
+		.map_err(libav_error)?;
+
+	Ok(VideoMetadata {
+		duration_seconds,
+		frame_rate,
+		frame_count,
+		width: decoder.width(),
+		height: decoder.height(),
+		codec: codec_name,
+		has_audio,
+		rotation: stream_rotation(&stream),
+	})
+}
+
+/// Clockwise rotation in degrees from `stream`'s display matrix, or 0
+/// without one.
+fn stream_rotation(stream: &ffmpeg::format::stream::Stream) -> u32 {
+	stream
+		.side_data()
+		.find(|side_data| side_data.kind() == packet::side_data::Type::DisplayMatrix)
+		.and_then(|side_data| matrix_rotation(side_data.data()))
+		.map_or(0, display_rotation)
+}
+
+/// The counterclockwise angle of a display matrix, given as libav stores
+/// it (nine native-endian `i32`s, the first two columns 16.16 fixed
+/// point), as `av_display_rotation_get` computes it.
+fn matrix_rotation(data: &[u8]) -> Option<f64> {
+	let matrix: Vec<f64> = data
+		.get(..36)?
+		.chunks_exact(4)
+		.map(|b| f64::from(i32::from_ne_bytes([b[0], b[1], b[2], b[3]])) / 65536.0)
+		.collect();
+	let scale_x = matrix[0].hypot(matrix[3]);
+	let scale_y = matrix[1].hypot(matrix[4]);
+	if scale_x == 0.0 || scale_y == 0.0 {
+		return None;
+	}
+	Some(
+		-(matrix[1] / scale_y)
+			.atan2(matrix[0] / scale_x)
+			.to_degrees(),
+	)
+}
+
+// ============================================================================
+// Decoding
+// ============================================================================
+
+/// A decoded frame, converted to RGB.
+struct DecodedFrame {
+	timestamp_seconds: f64,
+	is_keyframe: bool,
+	image: RgbImage,
+}
+
+impl DecodedFrame {
+	/// This frame as extracted frame `frame_number`, encoded as `config`
+	/// asks and with its variants: written to `path`, or kept in memory
+	/// without one.
+	fn into_frame(
+		self,
+		frame_number: usize,
+		path: Option<PathBuf>,
+		config: &VideoConfig,
+	) -> Result<ExtractedFrame> {
+		let bytes = encode(&self.image, config)?;
+		let variants = render_variants(&self.image, path.as_deref(), config)?;
+		let data = match &path {
+			Some(path) => {
+				std::fs::write(path, bytes)?;
+				None
+			}
+			None => Some(bytes),
+		};
+		Ok(ExtractedFrame {
+			path: path.unwrap_or_default(),
+			timestamp_seconds: self.timestamp_seconds,
+			frame_number: u32::try_from(frame_number).unwrap_or(u32::MAX),
+			is_keyframe: self.is_keyframe,
+			data,
+			variants,
+		})
+	}
+}
+
+/// The video stream of an open input, with a decoder for it.
+struct VideoDecoder {
+	input: ffmpeg::format::context::Input,
+	stream_index: usize,
+	time_base: f64,
+	start_pts: i64,
+	decoder: decoder::Video,
+	scaler: Option<scaling::Context>,
+	decoded: frame::Video,
+	/// Clockwise degrees to turn converted frames (0 = as decoded)
+	rotation: u32,
+}
+
+impl VideoDecoder {
+	/// Open the video stream of `video_path`, turning frames upright by
+	/// its display matrix if `rotate` is set.
+	fn open(
+		video_path: &Path,
+		threads: Option<u32>,
+		network: &NetworkConfig,
+		rotate: bool,
+	) -> Result<Self> {
+		let input = open(video_path, network)?;
+		let stream = input
+			.streams()
+			.best(media::Type::Video)
+			.ok_or_else(|| PerceptionError::NoVideoStream(video_path.to_path_buf()))?;
+		let stream_index = stream.index();
+		let time_base = f64::from(stream.time_base());
+		let start_pts = match stream.start_time() {
+			NO_PTS => 0,
+			start => start,
+		};
+		let rotation = if rotate { stream_rotation(&stream) } else { 0 };
+
+		let mut context =
+			codec::context::Context::from_parameters(stream.parameters()).map_err(libav_error)?;
+		if let Some(threads) = threads {
+			context.set_threading(threading::Config::count(threads as usize));
+		}
+		let decoder = context.decoder().video().map_err(libav_error)?;
+
+		Ok(Self {
+			input,
+			stream_index,
+			time_base,
+			start_pts,
+			decoder,
+			scaler: None,
+			decoded: frame::Video::empty(),
+			rotation,
+		})
+	}
+
+	/// Seek to the keyframe at or before `seconds`, so decoding resumes
+	/// there.
+	#[allow(clippy::cast_possible_truncation)]
+	fn seek(&mut self, seconds: f64) -> Result<()> {
+		let target = (seconds * AV_TIME_BASE) as i64;
+		self.input
+			.seek(target, ..target)
+			.map_err(|e| PerceptionError::FrameExtractionFailed {
+				timestamp: seconds,
+				reason: e.to_string(),
+			})
+	}
+
+	/// Decode every frame, passing each to `visit` until it returns false.
+	///
+	/// With `keyframes_only`, only key packets are sent to the decoder, so
+	/// nothing between keyframes is decoded at all.
+	fn run(
+		&mut self,
+		keyframes_only: bool,
+		mut visit: impl FnMut(&mut Self) -> Result<bool>,
+	) -> Result<()> {
+		loop {
+			let mut packet = ffmpeg::Packet::empty();
+			match packet.read(&mut self.input) {
+				Ok(()) => {}
+				Err(ffmpeg::Error::Eof) => break,
+				Err(e) => return Err(libav_error(e)),
+			}
+			if packet.stream() != self.stream_index || (keyframes_only && !packet.is_key()) {
+				continue;
+			}
+			match self.decoder.send_packet(&packet) {
+				// A damaged packet is skipped, as the CLI does
+				Ok(()) | Err(ffmpeg::Error::InvalidData) => {}
+				Err(e) => return Err(libav_error(e)),
+			}
+			if !self.drain(&mut visit)? {
+				return Ok(());
+			}
+		}
+		self.decoder.send_eof().map_err(libav_error)?;
+		let _ = self.drain(&mut visit)?;
+		Ok(())
+	}
+
+	/// Hand every frame the decoder has ready to `visit`.
+	fn drain(&mut self, visit: &mut impl FnMut(&mut Self) -> Result<bool>) -> Result<bool> {
+		while self.decoder.receive_frame(&mut self.decoded).is_ok() {
+			if !visit(self)? {
+				return Ok(false);
+			}
+		}
+		Ok(true)
+	}
+
+	/// Time of the last decoded frame, in seconds from the stream start.
+	#[allow(clippy::cast_precision_loss)]
+	fn timestamp_seconds(&self) -> f64 {
+		self.decoded
+			.timestamp()
+			.or_else(|| self.decoded.pts())
+			.map_or(0.0, |pts| (pts - self.start_pts) as f64 * self.time_base)
+			.max(0.0)
+	}
+
+	/// The last decoded frame as RGB.
+	fn convert(&mut self) -> Result<DecodedFrame> {
+		let (format, width, height) = (
+			self.decoded.format(),
+			self.decoded.width(),
+			self.decoded.height(),
+		);
+		let stale = self.scaler.as_ref().is_none_or(|scaler| {
+			let input = scaler.input();
+			(input.format, input.width, input.height) != (format, width, height)
+		});
+		if stale {
+			self.scaler = Some(
+				scaling::Context::get(
+					format,
+					width,
+					height,
+					Pixel::RGB24,
+					width,
+					height,
+					scaling::Flags::BILINEAR,
+				)
+				.map_err(libav_error)?,
+			);
+		}
+		let mut rgb = frame::Video::empty();
+		if let Some(scaler) = self.scaler.as_mut() {
+			scaler.run(&self.decoded, &mut rgb).map_err(libav_error)?;
+		}
+
+		// Rows may be padded past `width × 3` bytes
+		let stride = rgb.stride(0);
+		let row = width as usize * 3;
+		let pixels: Vec<u8> = rgb
+			.data(0)
+			.chunks(stride)
+			.take(height as usize)
+			.flat_map(|line| line.get(..row).unwrap_or(line))
+			.copied()
+			.collect();
+		let image = RgbImage::from_raw(width, height, pixels).ok_or_else(|| {
+			PerceptionError::FfmpegError {
+				message: format!("decoded frame does not fill {width}x{height}"),
+				exit_code: None,
+			}
+		})?;
+		let image = match self.rotation {
+			90 => imageops::rotate90(&image),
+			180 => imageops::rotate180(&image),
+			270 => imageops::rotate270(&image),
+			_ => image,
+		};
+
+		Ok(DecodedFrame {
+			timestamp_seconds: self.timestamp_seconds(),
+			is_keyframe: self.decoded.is_key(),
+			image,
+		})
+	}
+}
+
+/// Decode the frames `config` selects, passing each to `emit` until it
+/// returns false.
+fn decode_frames(
+	video_path: &Path,
+	config: &VideoConfig,
+	mut emit: impl FnMut(DecodedFrame) -> bool,
+) -> Result<()> {
+	let threads = config.deterministic.then_some(DETERMINISTIC_FFMPEG_THREADS);
+	let mut decoder = VideoDecoder::open(video_path, threads, &config.network, config.auto_rotate)?;
+	let interval = if config.interval_seconds > 0.0 {
+		config.interval_seconds
+	} else {
+		1.0
+	};
+
+	let (start, end) = config.time_range(f64::INFINITY);
+	if start > 0.0 {
+		decoder.seek(start)?;
+	}
+
+	let mut next_seconds = start;
+	let mut emitted = 0;
+	decoder.run(config.keyframes_only, |decoder| {
+		let timestamp = decoder.timestamp_seconds();
+		if timestamp >= end || (config.max_frames > 0 && emitted >= config.max_frames) {
+			return Ok(false);
+		}
+		if timestamp < start {
+			return Ok(true);
+		}
+		if !config.keyframes_only {
+			if timestamp < next_seconds {
+				return Ok(true);
+			}
+			// Skip ahead past gaps rather than emitting a burst after them
+			next_seconds = ((timestamp / interval).floor() + 1.0) * interval;
+		}
+		emitted += 1;
+		Ok(emit(decoder.convert()?))
+	})?;
+
+	debug!(count = emitted, "Decoded frames");
+	Ok(())
+}
+
+/// The first frame at or after `timestamp_seconds`.
+fn frame_at(video_path: &Path, timestamp_seconds: f64) -> Result<DecodedFrame> {
+	let mut decoder = VideoDecoder::open(video_path, None, &NetworkConfig::default(), true)?;
+	decoder.seek(timestamp_seconds)?;
+
+	let mut found = None;
+	decoder.run(false, |decoder| {
+		if decoder.timestamp_seconds() + 1e-3 < timestamp_seconds {
+			return Ok(true);
+		}
+		found = Some(decoder.convert()?);
+		Ok(false)
+	})?;
+	found.ok_or_else(|| PerceptionError::FrameExtractionFailed {
+		timestamp: timestamp_seconds,
+		reason: "no frame at or after this time".to_string(),
+	})
+}
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+/// The format named by `path`'s extension, JPEG if unknown.
+fn format_for(path: &Path) -> ImageFormat {
+	let ext = path
+		.extension()
+		.map(|e| e.to_string_lossy().to_ascii_lowercase());
+	match ext.as_deref() {
+		Some("png") => ImageFormat::Png,
+		Some("avif") => ImageFormat::Avif,
+		Some("webp") => ImageFormat::WebpLossless,
+		_ => ImageFormat::Jpeg,
+	}
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_matrix_rotation() {
+		// The 2x2 rotation part of a display matrix, in whole units
+		let matrix = |a: i32, b: i32, c: i32, d: i32| -> Vec<u8> {
+			[a << 16, b << 16, 0, c << 16, d << 16, 0, 0, 0, 1 << 30]
+				.iter()
+				.flat_map(|v| v.to_ne_bytes())
+				.collect()
+		};
+		let rotation = |data: Vec<u8>| matrix_rotation(&data).map_or(0, display_rotation);
+		assert_eq!(rotation(matrix(1, 0, 0, 1)), 0);
+		// A phone held upright: `FFprobe` reports -90
+		assert!(matrix_rotation(&matrix(0, 1, -1, 0)).is_some_and(|r| (r + 90.0).abs() < 1e-9));
+		assert_eq!(rotation(matrix(0, 1, -1, 0)), 90);
+		assert_eq!(rotation(matrix(-1, 0, 0, -1)), 180);
+		assert_eq!(rotation(matrix(0, -1, 1, 0)), 270);
+		assert_eq!(matrix_rotation(&[0; 8]), None);
+	}
+
+	#[test]
+	fn test_format_for() {
+		assert_eq!(format_for(Path::new("a/frame.PNG")), ImageFormat::Png);
+		assert_eq!(format_for(Path::new("a/frame.jpg")), ImageFormat::Jpeg);
+		assert_eq!(format_for(Path::new("a/frame.avif")), ImageFormat::Avif);
+		assert_eq!(
+			format_for(Path::new("a/frame.webp")),
+			ImageFormat::WebpLossless
+		);
+		assert_eq!(format_for(Path::new("a/frame")), ImageFormat::Jpeg);
+	}
+}
@@ -0,0 +1,973 @@
+//! Memory graph integration.
+//!
+//! Converts a [`VideoProcessingOutput`] into lucid-core structures, so a
+//! processed video can be stored and recalled like any other memory.
+//! Nodes are lucid-core [`Moment`]s, the same type other producers emit:
+//!
+//! - **Moments**: one node per detected scene and, with the
+//!   `transcription` feature, one per transcript segment
+//! - **Temporal links**: moments ordered by start time, with strength
+//!   decaying over the time between them
+//! - **Co-occurrence associations**: moments whose transcript text shares
+//!   terms, weighted by the Jaccard index of their term sets
+//!
+//! [`episodes_from_scenes`] groups a video's scenes into lucid-core
+//! [`Episode`]s instead, splitting where the footage skips ahead.
+//!
+//! [`visual_similarity_associations`] works across videos: given stored
+//! frame hashes for many memories, it links memories whose frames look
+//! alike, so visually similar moments recall each other.
+//!
+//! [`MomentGraph::retrieve`] answers a query against all of it at once:
+//! text and keyframe embeddings, keyframe hashes, the association graph,
+//! and temporal links each contribute a score, reported separately.
+//!
+//! Node indices are positions in [`MomentGraph::moments`]; callers storing
+//! several videos in one graph offset them as they insert.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use lucid_core::{
+	cosine_similarity, create_episode_links_from_timestamps, moment_events,
+	spread_activation_graph, spread_temporal_activation, Association, AssociationGraph,
+	AssociationType, Episode, MemoryGraph, SpreadingConfig, TemporalLink, TemporalSpreadingConfig,
+};
+use serde::{Deserialize, Serialize};
+
+pub use lucid_core::{MediaRef, Modality, Moment};
+
+use crate::pipeline::VideoProcessingOutput;
+use crate::scene::{
+	representative_offset, scene_ranges, FrameCandidate, HashIndex, PerceptualHash,
+};
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Configuration for building a moment graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MomentGraphConfig {
+	/// Base strengths and reach of temporal links
+	pub temporal: TemporalSpreadingConfig,
+
+	/// Time between moments at which temporal link strength halves, in ms (default: 60000)
+	pub half_life_ms: f64,
+
+	/// Shortest word, in characters, counted as a term (default: 4)
+	pub min_term_length: usize,
+
+	/// Terms two moments must share to be associated (default: 2)
+	pub min_shared_terms: usize,
+
+	/// Association strength between moments with identical term sets (default: 0.5)
+	pub cooccurrence_strength: f64,
+}
+
+impl Default for MomentGraphConfig {
+	fn default() -> Self {
+		Self {
+			temporal: TemporalSpreadingConfig::default(),
+			half_life_ms: 60_000.0,
+			min_term_length: 4,
+			min_shared_terms: 2,
+			cooccurrence_strength: 0.5,
+		}
+	}
+}
+
+/// Configuration for grouping scenes into episodes.
+///
+/// A new episode starts at whichever limit is reached first; a limit of 0
+/// is off. With every limit off, the whole video is one episode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneEpisodeConfig {
+	/// Split where no frame was captured for this long between scenes, in ms (default: 30000)
+	pub min_gap_ms: f64,
+
+	/// Most scenes in one episode (default: 0)
+	pub max_scenes: usize,
+
+	/// Longest episode, from its first scene's start to its last scene's end, in ms (default: 0)
+	pub max_duration_ms: f64,
+
+	/// Node index of the video's first scene in the target graph (default: 0)
+	pub first_node: usize,
+
+	/// Strengths and reach of the links within each episode
+	pub temporal: TemporalSpreadingConfig,
+}
+
+impl Default for SceneEpisodeConfig {
+	fn default() -> Self {
+		Self {
+			min_gap_ms: 30_000.0,
+			max_scenes: 0,
+			max_duration_ms: 0.0,
+			first_node: 0,
+			temporal: TemporalSpreadingConfig::default(),
+		}
+	}
+}
+
+/// Configuration for visual-similarity associations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VisualSimilarityConfig {
+	/// Largest Hamming distance between frame hashes that counts as similar (default: 8)
+	pub max_distance: u32,
+
+	/// Association strength between identical frames (default: 0.6)
+	pub strength: f64,
+}
+
+impl Default for VisualSimilarityConfig {
+	fn default() -> Self {
+		Self {
+			max_distance: 8,
+			strength: 0.6,
+		}
+	}
+}
+
+/// Configuration for cross-modal moment retrieval.
+///
+/// Direct matches (text, visual, hash) seed spreading; a moment's total is
+/// the weighted sum of every signal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MomentRetrievalConfig {
+	/// Weight of text-embedding similarity (default: 1.0)
+	pub text_weight: f64,
+
+	/// Weight of keyframe-embedding similarity (default: 1.0)
+	pub visual_weight: f64,
+
+	/// Weight of keyframe-hash similarity (default: 0.5)
+	pub hash_weight: f64,
+
+	/// Largest Hamming distance counted as a hash match (default: 12)
+	pub max_hash_distance: u32,
+
+	/// Weight of activation spread over associations (default: 0.5)
+	pub spreading_weight: f64,
+
+	/// Weight of activation spread over temporal links (default: 0.3)
+	pub temporal_weight: f64,
+
+	/// Best direct matches used as spreading seeds (default: 5)
+	pub max_seeds: usize,
+
+	/// Hops of association spreading (default: 2)
+	pub spreading_depth: usize,
+
+	/// Association spreading parameters
+	pub spreading: SpreadingConfig,
+
+	/// Temporal spreading parameters
+	pub temporal: TemporalSpreadingConfig,
+
+	/// Maximum moments to return (default: 10)
+	pub max_results: usize,
+}
+
+impl Default for MomentRetrievalConfig {
+	fn default() -> Self {
+		Self {
+			text_weight: 1.0,
+			visual_weight: 1.0,
+			hash_weight: 0.5,
+			max_hash_distance: 12,
+			spreading_weight: 0.5,
+			temporal_weight: 0.3,
+			max_seeds: 5,
+			spreading_depth: 2,
+			spreading: SpreadingConfig::default(),
+			temporal: TemporalSpreadingConfig::default(),
+			max_results: 10,
+		}
+	}
+}
+
+// ============================================================================
+// Output
+// ============================================================================
+
+/// lucid-core structures derived from one processed video.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MomentGraph {
+	/// One entry per node, scenes (`Visual`) first, then transcript
+	/// segments (`Audio`); each moment's `id` is its position
+	pub moments: Vec<Moment>,
+
+	/// Temporal links between moments, in time order
+	pub temporal_links: Vec<TemporalLink>,
+
+	/// Co-occurrence associations, sorted by `(source, target)`
+	pub associations: Vec<Association>,
+}
+
+impl MomentGraph {
+	/// Build an editable [`MemoryGraph`] holding the co-occurrence
+	/// associations and, as `Temporal` associations, the temporal links.
+	#[must_use]
+	pub fn to_memory_graph(&self) -> MemoryGraph {
+		let temporal = self.temporal_links.iter().map(|link| Association {
+			source: link.source_memory,
+			target: link.target_memory,
+			forward_strength: link.forward_strength,
+			backward_strength: link.backward_strength,
+			association_type: AssociationType::Temporal,
+		});
+		let associations = temporal.chain(self.associations.iter().cloned()).collect();
+		MemoryGraph::from_associations(self.moments.len(), associations)
+	}
+}
+
+/// A video's scenes grouped into episodes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneEpisodes {
+	/// One moment per scene, in order; scene `i` has id `first_node + i`
+	pub scenes: Vec<Moment>,
+
+	/// Consecutive runs of scenes, each with its temporal links
+	pub episodes: Vec<Episode>,
+}
+
+/// Per-signal scores for a retrieved moment, before weighting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MomentScores {
+	/// Cosine similarity of the query and moment text embeddings (0-1)
+	pub text: f64,
+	/// Cosine similarity of the query and keyframe embeddings (0-1)
+	pub visual: f64,
+	/// `1 - distance / (max_hash_distance + 1)` for the keyframe hash (0-1)
+	pub hash: f64,
+	/// Activation received over associations from the seeds
+	pub spreading: f64,
+	/// Activation received over temporal links from the seeds
+	pub temporal: f64,
+	/// Weighted sum of the above
+	pub total: f64,
+}
+
+/// A moment returned by [`MomentGraph::retrieve`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RetrievedMoment {
+	/// Index into [`MomentGraph::moments`]
+	pub index: usize,
+	/// Score breakdown
+	pub scores: MomentScores,
+}
+
+// ============================================================================
+// Building
+// ============================================================================
+
+/// Build moments, temporal links, and co-occurrence associations for a
+/// processed video.
+///
+/// Each scene spans from its first frame to the next scene (or the end of
+/// the video). Co-occurrence is measured between moments of one modality:
+/// a scene's text is whatever was said during it, so comparing it with its
+/// own transcript segments would only restate that overlap.
+///
+/// A scene's [`MediaRef`] points at its most representative frame: `index`
+/// is the frame's position in `output.frames` and `uri` its path. Frames
+/// extracted to memory have no path, so for them `uri` is empty and
+/// `index` is the only reference.
+#[must_use]
+pub fn build_moment_graph(
+	output: &VideoProcessingOutput,
+	config: &MomentGraphConfig,
+) -> MomentGraph {
+	#[cfg_attr(not(feature = "transcription"), allow(unused_mut))]
+	let mut moments = scene_moments(output, 0);
+	#[cfg(feature = "transcription")]
+	add_transcript(&mut moments, output);
+
+	let events = moment_events(&moments);
+	let temporal_links =
+		create_episode_links_from_timestamps(&events, config.half_life_ms, &config.temporal);
+	let associations = cooccurrence_associations(&moments, config);
+
+	MomentGraph {
+		moments,
+		temporal_links,
+		associations,
+	}
+}
+
+fn seconds_to_ms(seconds: f64) -> f64 {
+	(seconds * 1000.0).round()
+}
+
+/// One `Visual` moment per scene, ids from `first_id`, each referencing
+/// its most representative frame (with an empty `uri` if it is in memory).
+fn scene_moments(output: &VideoProcessingOutput, first_id: usize) -> Vec<Moment> {
+	let frames = &output.frames;
+	let video_end_ms = seconds_to_ms(output.metadata.duration_seconds);
+	let scenes = scene_ranges(frames);
+
+	scenes
+		.iter()
+		.enumerate()
+		.map(|(i, scene)| {
+			let start_ms = seconds_to_ms(frames[scene.start].frame.timestamp_seconds);
+			let end_ms = scenes.get(i + 1).map_or(video_end_ms, |next| {
+				seconds_to_ms(frames[next.start].frame.timestamp_seconds)
+			});
+			let mut moment = Moment::new(
+				first_id + i,
+				Modality::Visual,
+				start_ms,
+				end_ms.max(start_ms),
+			);
+			if let Some(offset) = representative_offset(&frames[scene.clone()]) {
+				let keyframe = &frames[scene.start + offset].frame;
+				moment.media.push(MediaRef {
+					modality: Modality::Visual,
+					uri: keyframe.path.to_string_lossy().into_owned(),
+					offset_ms: Some(seconds_to_ms(keyframe.timestamp_seconds)),
+					index: Some(scene.start + offset),
+				});
+			}
+			moment
+		})
+		.collect()
+}
+
+/// Give scenes the text spoken during them and add a moment per segment.
+#[cfg(feature = "transcription")]
+#[allow(clippy::cast_precision_loss)]
+fn add_transcript(moments: &mut Vec<Moment>, output: &VideoProcessingOutput) {
+	let Some(transcript) = &output.transcript else {
+		return;
+	};
+	let segments: Vec<_> = transcript
+		.segments
+		.iter()
+		.filter(|segment| !segment.text.trim().is_empty())
+		.collect();
+
+	for scene in &mut *moments {
+		scene.text = segments
+			.iter()
+			.filter(|s| (s.start_ms as f64) < scene.end_ms && (s.end_ms as f64) > scene.start_ms)
+			.map(|s| s.text.trim())
+			.collect::<Vec<_>>()
+			.join(" ");
+	}
+	let first_id = moments.len();
+	moments.extend(segments.iter().enumerate().map(|(k, segment)| {
+		let mut moment = Moment::new(
+			first_id + k,
+			Modality::Audio,
+			segment.start_ms as f64,
+			segment.end_ms as f64,
+		);
+		moment.text = segment.text.trim().to_string();
+		moment
+	}));
+}
+
+/// Lowercase words of at least `min_length` characters.
+fn terms(text: &str, min_length: usize) -> HashSet<String> {
+	text.split(|c: char| !c.is_alphanumeric())
+		.filter(|word| word.chars().count() >= min_length)
+		.map(str::to_lowercase)
+		.collect()
+}
+
+/// `strength × |A ∩ B| / |A ∪ B|` between same-modality moments sharing
+/// at least `min_shared_terms` terms, in both directions.
+fn cooccurrence_associations(moments: &[Moment], config: &MomentGraphConfig) -> Vec<Association> {
+	let term_sets: Vec<HashSet<String>> = moments
+		.iter()
+		.map(|moment| terms(&moment.text, config.min_term_length))
+		.collect();
+
+	// term → moments using it, so only pairs sharing a term are compared
+	let mut postings: HashMap<&str, Vec<usize>> = HashMap::new();
+	for (i, set) in term_sets.iter().enumerate() {
+		for term in set {
+			postings.entry(term.as_str()).or_default().push(i);
+		}
+	}
+	let mut shared: HashMap<(usize, usize), usize> = HashMap::new();
+	for users in postings.values() {
+		for (k, &a) in users.iter().enumerate() {
+			for &b in &users[k + 1..] {
+				if moments[a].modality == moments[b].modality {
+					*shared.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+				}
+			}
+		}
+	}
+
+	let mut associations: Vec<Association> = shared
+		.into_iter()
+		.filter(|&(_, count)| count >= config.min_shared_terms.max(1))
+		.map(|((a, b), count)| {
+			let union = term_sets[a].len() + term_sets[b].len() - count;
+			#[allow(clippy::cast_precision_loss)]
+			let strength = config.cooccurrence_strength * count as f64 / union as f64;
+			Association {
+				source: a,
+				target: b,
+				forward_strength: strength,
+				backward_strength: strength,
+				association_type: AssociationType::Semantic,
+			}
+		})
+		.collect();
+	associations.sort_by_key(|a| (a.source, a.target));
+	associations
+}
+
+// ============================================================================
+// Episodes
+// ============================================================================
+
+/// Group a processed video's scenes into episodes.
+///
+/// Scene order gives positions within an episode. The gap between two
+/// scenes is the time from the last frame of one to the first frame of
+/// the next; a long gap means the footage skipped ahead, and starts a new
+/// episode. Episode events are graph node indices, offset by `first_node`,
+/// so the result can be inserted next to earlier videos as is.
+#[must_use]
+pub fn episodes_from_scenes(
+	output: &VideoProcessingOutput,
+	config: &SceneEpisodeConfig,
+) -> SceneEpisodes {
+	let scenes = scene_moments(output, config.first_node);
+	let episodes = scene_runs(
+		&output.frames,
+		&scenes,
+		config.min_gap_ms,
+		config.max_scenes,
+		config.max_duration_ms,
+	)
+	.into_iter()
+	.map(|run| {
+		let events = run.map(|i| config.first_node + i).collect();
+		Episode::new(events, &config.temporal)
+	})
+	.collect();
+
+	SceneEpisodes { scenes, episodes }
+}
+
+/// Split consecutive scenes into runs, starting a new run at whichever
+/// limit is reached first; a limit of 0 is off.
+///
+/// `scenes[i]` is the moment for the `i`th scene of `frames`; the gap
+/// before it is measured from the last frame of the scene before.
+pub(crate) fn scene_runs(
+	frames: &[FrameCandidate],
+	scenes: &[Moment],
+	min_gap_ms: f64,
+	max_scenes: usize,
+	max_duration_ms: f64,
+) -> Vec<Range<usize>> {
+	let ranges = scene_ranges(frames);
+	let mut runs = Vec::new();
+	let mut start = 0;
+	for (i, scene) in scenes.iter().enumerate().skip(1) {
+		let gap_ms = ranges.get(i - 1).map_or(0.0, |previous| {
+			scene.start_ms - seconds_to_ms(frames[previous.end - 1].frame.timestamp_seconds)
+		});
+		let split = (min_gap_ms > 0.0 && gap_ms >= min_gap_ms)
+			|| (max_scenes > 0 && i - start >= max_scenes)
+			|| (max_duration_ms > 0.0 && scene.end_ms - scenes[start].start_ms > max_duration_ms);
+		if split {
+			runs.push(start..i);
+			start = i;
+		}
+	}
+	if !scenes.is_empty() {
+		runs.push(start..scenes.len());
+	}
+	runs
+}
+
+// ============================================================================
+// Visual Similarity
+// ============================================================================
+
+/// Link memories whose frames are within `max_distance` of each other.
+///
+/// `strength × (1 - d / (max_distance + 1))`
+///
+/// Where `d` is the Hamming distance between the closest pair of frames
+/// the two memories hold. A memory may contribute several frames; it is
+/// never linked to itself. Lookups go through a [`HashIndex`], so each
+/// frame is compared only with frames near it rather than with all.
+///
+/// # Arguments
+///
+/// * `frames` - `(memory index, frame hash)` for each stored frame
+/// * `config` - Distance threshold and strength
+///
+/// # Returns
+///
+/// `VisualSimilarity` associations in both directions, sorted by
+/// `(source, target)`, with `source < target`.
+#[must_use]
+pub fn visual_similarity_associations(
+	frames: &[(usize, PerceptualHash)],
+	config: &VisualSimilarityConfig,
+) -> Vec<Association> {
+	let mut index = HashIndex::new();
+	for (i, (_, hash)) in frames.iter().enumerate() {
+		index.insert(i, hash.clone());
+	}
+
+	// Closest distance per memory pair
+	let mut closest: HashMap<(usize, usize), u32> = HashMap::new();
+	for (i, (memory, hash)) in frames.iter().enumerate() {
+		for (j, distance) in index.within(hash, config.max_distance) {
+			let other = frames[j].0;
+			if j <= i || other == *memory {
+				continue;
+			}
+			let key = ((*memory).min(other), (*memory).max(other));
+			let best = closest.entry(key).or_insert(distance);
+			*best = (*best).min(distance);
+		}
+	}
+
+	let scale = f64::from(config.max_distance) + 1.0;
+	let mut associations: Vec<Association> = closest
+		.into_iter()
+		.map(|((source, target), distance)| {
+			let strength = config.strength * (1.0 - f64::from(distance) / scale);
+			Association {
+				source,
+				target,
+				forward_strength: strength,
+				backward_strength: strength,
+				association_type: AssociationType::VisualSimilarity,
+			}
+		})
+		.collect();
+	associations.sort_by_key(|a| (a.source, a.target));
+	associations
+}
+
+// ============================================================================
+// Retrieval
+// ============================================================================
+
+/// What to look for; any part may be left out.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MomentQuery<'a> {
+	/// Embedding of the query text, compared with `Text` embeddings
+	pub text_embedding: Option<&'a [f64]>,
+	/// Embedding of a query image, compared with `Visual` embeddings
+	pub visual_embedding: Option<&'a [f64]>,
+	/// Perceptual hash of a query image
+	pub hash: Option<&'a PerceptualHash>,
+}
+
+impl MomentGraph {
+	/// Rank moments against a query, combining every modality.
+	///
+	/// 1. Score each moment directly on text, visual, and hash similarity
+	/// 2. Seed spreading with the `max_seeds` best direct matches
+	/// 3. Spread over associations, then over temporal links
+	/// 4. `total = Σ weight × signal`, ranked descending
+	///
+	/// Embeddings are read from the moments; a moment without one of a
+	/// query's modalities scores 0 on it. Spread scores exclude the
+	/// activation a seed started with, so a moment found directly is not
+	/// counted twice.
+	///
+	/// # Arguments
+	///
+	/// * `query` - Embeddings and hash to match
+	/// * `frames` - Frames of the processed video, for keyframe hashes
+	/// * `config` - Signal weights and spreading parameters
+	#[must_use]
+	pub fn retrieve(
+		&self,
+		query: &MomentQuery<'_>,
+		frames: &[FrameCandidate],
+		config: &MomentRetrievalConfig,
+	) -> Vec<RetrievedMoment> {
+		let n = self.moments.len();
+		if n == 0 {
+			return Vec::new();
+		}
+
+		let similarities = |probe: Option<&[f64]>, modality: Modality| -> Vec<f64> {
+			self.moments
+				.iter()
+				.map(|moment| match (probe, moment.embedding(modality)) {
+					(Some(probe), Some(embedding)) => cosine_similarity(probe, embedding).max(0.0),
+					_ => 0.0,
+				})
+				.collect()
+		};
+		let text = similarities(query.text_embedding, Modality::Text);
+		let visual = similarities(query.visual_embedding, Modality::Visual);
+		let scale = f64::from(config.max_hash_distance) + 1.0;
+		let hash: Vec<f64> = self
+			.moments
+			.iter()
+			.map(|moment| {
+				let keyframe = moment
+					.media(Modality::Visual)
+					.and_then(|media| media.index)
+					.and_then(|i| frames.get(i));
+				match (query.hash, keyframe) {
+					(Some(probe), Some(frame)) => {
+						let distance = probe.distance(&frame.hash);
+						if distance > config.max_hash_distance {
+							0.0
+						} else {
+							1.0 - f64::from(distance) / scale
+						}
+					}
+					_ => 0.0,
+				}
+			})
+			.collect();
+
+		let direct: Vec<f64> = (0..n)
+			.map(|i| {
+				config.hash_weight.mul_add(
+					hash[i],
+					config
+						.text_weight
+						.mul_add(text[i], config.visual_weight * visual[i]),
+				)
+			})
+			.collect();
+
+		let mut seeds: Vec<(usize, f64)> = direct
+			.iter()
+			.enumerate()
+			.filter(|(_, &score)| score > 0.0)
+			.map(|(i, &score)| (i, score))
+			.collect();
+		seeds.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+		seeds.truncate(config.max_seeds);
+
+		let mut spreading = vec![0.0; n];
+		let mut temporal = vec![0.0; n];
+		if !seeds.is_empty() {
+			let seed_indices: Vec<usize> = seeds.iter().map(|(i, _)| *i).collect();
+			let seed_activations: Vec<f64> = seeds.iter().map(|(_, a)| *a).collect();
+			if config.spreading_depth > 0 {
+				let graph = AssociationGraph::new(n, &self.associations);
+				let result = spread_activation_graph(
+					&graph,
+					&seed_indices,
+					&seed_activations,
+					&config.spreading,
+					config.spreading_depth,
+				);
+				spreading = result.activations;
+				for &(seed, activation) in &seeds {
+					spreading[seed] = (spreading[seed] - activation).max(0.0);
+				}
+			}
+			for &(seed, activation) in &seeds {
+				let result = spread_temporal_activation(
+					n,
+					&self.temporal_links,
+					seed,
+					activation,
+					&config.temporal,
+				);
+				for (i, received) in result.activations.iter().enumerate() {
+					if i != seed {
+						temporal[i] += received;
+					}
+				}
+			}
+		}
+
+		let mut results: Vec<RetrievedMoment> = (0..n)
+			.filter_map(|i| {
+				let total = config.temporal_weight.mul_add(
+					temporal[i],
+					config.spreading_weight.mul_add(spreading[i], direct[i]),
+				);
+				(total > 0.0).then_some(RetrievedMoment {
+					index: i,
+					scores: MomentScores {
+						text: text[i],
+						visual: visual[i],
+						hash: hash[i],
+						spreading: spreading[i],
+						temporal: temporal[i],
+						total,
+					},
+				})
+			})
+			.collect();
+		results.sort_by(|a, b| {
+			b.scores
+				.total
+				.total_cmp(&a.scores.total)
+				.then(a.index.cmp(&b.index))
+		});
+		results.truncate(config.max_results);
+		results
+	}
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::pipeline::ProcessingStats;
+	use crate::resources::PipelineResources;
+	use crate::scene::{FrameCandidate, PerceptualHash};
+	use crate::video::{ExtractedFrame, VideoMetadata};
+
+	fn make_frame(
+		frame_number: u32,
+		timestamp_seconds: f64,
+		is_scene_change: bool,
+	) -> FrameCandidate {
+		FrameCandidate {
+			frame: ExtractedFrame {
+				path: format!("frame_{frame_number}.jpg").into(),
+				timestamp_seconds,
+				frame_number,
+				is_keyframe: false,
+				data: None,
+				variants: Vec::new(),
+			},
+			hash: PerceptualHash {
+				bytes: vec![0; 8],
+				hex: String::new(),
+			},
+			is_scene_change,
+			is_duplicate: false,
+			distance_from_previous: 0,
+		}
+	}
+
+	fn make_output(frames: Vec<FrameCandidate>) -> VideoProcessingOutput {
+		VideoProcessingOutput {
+			metadata: VideoMetadata {
+				duration_seconds: 30.0,
+				frame_rate: 30.0,
+				frame_count: 900,
+				width: 640,
+				height: 360,
+				codec: "h264".to_string(),
+				has_audio: false,
+				rotation: 0,
+			},
+			frames,
+			#[cfg(feature = "transcription")]
+			transcript: None,
+			no_audio: true,
+			stats: ProcessingStats {
+				frames_extracted: 0,
+				scene_changes: 0,
+				duplicates: 0,
+				extraction_time_ms: 0,
+				scene_detection_time_ms: 0,
+				transcription_time_ms: 0,
+				resources: PipelineResources::default(),
+			},
+		}
+	}
+
+	#[test]
+	fn test_scene_moments_and_temporal_links() {
+		let output = make_output(vec![
+			make_frame(0, 0.0, true),
+			make_frame(1, 5.0, false),
+			make_frame(2, 10.0, true),
+			make_frame(3, 20.0, true),
+		]);
+		let graph = build_moment_graph(&output, &MomentGraphConfig::default());
+
+		let spans: Vec<(usize, f64, f64, Option<usize>)> = graph
+			.moments
+			.iter()
+			.map(|m| {
+				let keyframe = m.media(Modality::Visual).and_then(|media| media.index);
+				(m.id, m.start_ms, m.end_ms, keyframe)
+			})
+			.collect();
+		assert_eq!(
+			spans,
+			vec![
+				(0, 0.0, 10_000.0, Some(0)),
+				(1, 10_000.0, 20_000.0, Some(2)),
+				(2, 20_000.0, 30_000.0, Some(3))
+			]
+		);
+
+		// Adjacent scenes link more strongly than distant ones
+		assert_eq!(graph.temporal_links.len(), 3);
+		let near = &graph.temporal_links[0];
+		let far = &graph.temporal_links[1];
+		assert_eq!((near.source_memory, near.target_memory), (0, 1));
+		assert_eq!((far.source_memory, far.target_memory), (0, 2));
+		assert!(near.forward_strength > far.forward_strength);
+		assert!(graph.associations.is_empty());
+
+		let memory_graph = graph.to_memory_graph();
+		assert_eq!(memory_graph.num_nodes(), 3);
+		assert_eq!(memory_graph.num_edges(), 3);
+	}
+
+	#[test]
+	fn test_in_memory_keyframes_referenced_by_index() {
+		let mut frame = make_frame(0, 0.0, true);
+		frame.frame.path = std::path::PathBuf::new();
+		frame.frame.data = Some(vec![0xFF, 0xD8]);
+		let graph = build_moment_graph(&make_output(vec![frame]), &MomentGraphConfig::default());
+
+		let media = graph.moments[0].media(Modality::Visual);
+		assert!(media.is_some_and(|media| media.uri.is_empty() && media.index == Some(0)));
+	}
+
+	#[test]
+	fn test_cooccurrence_associations() {
+		let moment = |text: &str| {
+			let mut moment = Moment::new(0, Modality::Audio, 0.0, 0.0);
+			moment.text = text.to_string();
+			moment
+		};
+		let moments = vec![
+			moment("The billing service crashed during deploy"),
+			moment("Rolling back the billing deploy fixed it"),
+			moment("Lunch was great"),
+		];
+		let associations = cooccurrence_associations(&moments, &MomentGraphConfig::default());
+
+		// Shared terms: billing, deploy; union of 4-letter terms is 8
+		assert_eq!(associations.len(), 1);
+		assert_eq!((associations[0].source, associations[0].target), (0, 1));
+		assert!((associations[0].forward_strength - 0.5 * 2.0 / 8.0).abs() < 1e-12);
+	}
+
+	#[test]
+	#[allow(clippy::suboptimal_flops)]
+	fn test_visual_similarity_associations() {
+		let hash = |byte: u8| PerceptualHash {
+			bytes: vec![byte, 0],
+			hex: String::new(),
+		};
+		let frames = vec![
+			(0, hash(0b0000_0000)),
+			(0, hash(0b0000_0001)),
+			(1, hash(0b0000_0011)),
+			(2, hash(0b1111_1111)),
+		];
+		let config = VisualSimilarityConfig {
+			max_distance: 2,
+			strength: 0.6,
+		};
+		let associations = visual_similarity_associations(&frames, &config);
+
+		// Memory 0's second frame is 1 bit from memory 1; memory 2 is far
+		// from both, and memory 0's own frames are not linked together
+		assert_eq!(associations.len(), 1);
+		let link = &associations[0];
+		assert_eq!((link.source, link.target), (0, 1));
+		assert_eq!(link.association_type, AssociationType::VisualSimilarity);
+		assert!((link.forward_strength - 0.6 * (1.0 - 1.0 / 3.0)).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_episodes_from_scenes() {
+		// Scenes at 0s, 4s, 60s, 64s; frames from 4s to 60s are missing
+		let output = make_output(vec![
+			make_frame(0, 0.0, true),
+			make_frame(1, 2.0, false),
+			make_frame(2, 4.0, true),
+			make_frame(3, 60.0, true),
+			make_frame(4, 64.0, true),
+		]);
+		let config = SceneEpisodeConfig {
+			first_node: 100,
+			..SceneEpisodeConfig::default()
+		};
+		let result = episodes_from_scenes(&output, &config);
+
+		assert_eq!(result.scenes.len(), 4);
+		let events: Vec<Vec<usize>> = result.episodes.iter().map(|e| e.events.clone()).collect();
+		assert_eq!(events, vec![vec![100, 101], vec![102, 103]]);
+		assert_eq!(result.episodes[0].links.len(), 1);
+
+		// Finer granularity: at most one scene per episode
+		let config = SceneEpisodeConfig {
+			max_scenes: 1,
+			..SceneEpisodeConfig::default()
+		};
+		assert_eq!(episodes_from_scenes(&output, &config).episodes.len(), 4);
+
+		// Coarsest: one episode for the whole video
+		let config = SceneEpisodeConfig {
+			min_gap_ms: 0.0,
+			..SceneEpisodeConfig::default()
+		};
+		assert_eq!(episodes_from_scenes(&output, &config).episodes.len(), 1);
+	}
+
+	#[test]
+	fn test_retrieve_combines_signals() {
+		// Scenes at 0s, 10s, 20s; scene 2 is linked to scene 0 by association
+		let output = make_output(vec![
+			make_frame(0, 0.0, true),
+			make_frame(1, 10.0, true),
+			make_frame(2, 20.0, true),
+		]);
+		let mut graph = build_moment_graph(&output, &MomentGraphConfig::default());
+		graph.associations.push(Association {
+			source: 0,
+			target: 2,
+			forward_strength: 0.8,
+			backward_strength: 0.8,
+			association_type: AssociationType::Semantic,
+		});
+
+		for (moment, embedding) in
+			graph
+				.moments
+				.iter_mut()
+				.zip([[1.0, 0.0], [0.0, 1.0], [0.0, 1.0]])
+		{
+			moment.set_embedding(Modality::Text, embedding.to_vec());
+		}
+		let probe = [1.0, 0.0];
+		let query = MomentQuery {
+			text_embedding: Some(&probe),
+			..MomentQuery::default()
+		};
+		let config = MomentRetrievalConfig::default();
+		let results = graph.retrieve(&query, &output.frames, &config);
+
+		assert_eq!(results[0].index, 0);
+		assert!((results[0].scores.text - 1.0).abs() < 1e-9);
+		// Only reachable by spreading
+		let linked = results.iter().find(|r| r.index == 2);
+		assert!(linked.is_some_and(|r| r.scores.text == 0.0 && r.scores.spreading > 0.0));
+		// Temporal neighbour of the seed
+		let next = results.iter().find(|r| r.index == 1);
+		assert!(next.is_some_and(|r| r.scores.temporal > 0.0));
+
+		// Identical keyframe hashes match every scene
+		let query = MomentQuery {
+			hash: Some(&output.frames[0].hash),
+			..MomentQuery::default()
+		};
+		let results = graph.retrieve(&query, &output.frames, &config);
+		assert_eq!(results.len(), 3);
+		assert!(results.iter().all(|r| (r.scores.hash - 1.0).abs() < 1e-9));
+	}
+}
@@ -0,0 +1,83 @@
+//! Metrics
+//!
+//! Per-video counters and stage timings, emitted through the [`metrics`]
+//! facade when a video finishes processing. Nothing is recorded until the
+//! application installs a recorder. With this feature on, lucid-core's
+//! metrics (spreading latency, graph sizes) are enabled too; see
+//! `lucid_core::metrics`.
+//!
+//! | Name | Kind |
+//! |------|------|
+//! | `lucid_perception_videos_processed_total` | counter |
+//! | `lucid_perception_frames_extracted_total` | counter |
+//! | `lucid_perception_scenes_detected_total` | counter |
+//! | `lucid_perception_extraction_seconds` | histogram |
+//! | `lucid_perception_scene_detection_seconds` | histogram |
+//! | `lucid_perception_transcription_seconds` | histogram |
+
+use ::metrics::{counter, describe_counter, describe_histogram, histogram, Unit};
+
+use crate::pipeline::ProcessingStats;
+
+/// Videos that finished processing.
+pub const VIDEOS_PROCESSED_TOTAL: &str = "lucid_perception_videos_processed_total";
+/// Frames extracted across all videos.
+pub const FRAMES_EXTRACTED_TOTAL: &str = "lucid_perception_frames_extracted_total";
+/// Scene changes detected across all videos.
+pub const SCENES_DETECTED_TOTAL: &str = "lucid_perception_scenes_detected_total";
+/// Time spent extracting one video's frames.
+pub const EXTRACTION_SECONDS: &str = "lucid_perception_extraction_seconds";
+/// Time spent detecting one video's scenes.
+pub const SCENE_DETECTION_SECONDS: &str = "lucid_perception_scene_detection_seconds";
+/// Time spent transcribing one video.
+pub const TRANSCRIPTION_SECONDS: &str = "lucid_perception_transcription_seconds";
+
+/// Register units and help text for every metric above, and lucid-core's.
+pub fn describe_metrics() {
+	lucid_core::metrics::describe_metrics();
+	describe_counter!(
+		VIDEOS_PROCESSED_TOTAL,
+		Unit::Count,
+		"Videos that finished processing"
+	);
+	describe_counter!(
+		FRAMES_EXTRACTED_TOTAL,
+		Unit::Count,
+		"Frames extracted across all videos"
+	);
+	describe_counter!(
+		SCENES_DETECTED_TOTAL,
+		Unit::Count,
+		"Scene changes detected across all videos"
+	);
+	describe_histogram!(
+		EXTRACTION_SECONDS,
+		Unit::Seconds,
+		"Time spent extracting one video's frames"
+	);
+	describe_histogram!(
+		SCENE_DETECTION_SECONDS,
+		Unit::Seconds,
+		"Time spent detecting one video's scenes"
+	);
+	describe_histogram!(
+		TRANSCRIPTION_SECONDS,
+		Unit::Seconds,
+		"Time spent transcribing one video"
+	);
+}
+
+/// Record one processed video.
+///
+/// Transcription time is recorded only for videos that were transcribed.
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn record_processing(stats: &ProcessingStats) {
+	counter!(VIDEOS_PROCESSED_TOTAL).increment(1);
+	counter!(FRAMES_EXTRACTED_TOTAL).increment(stats.frames_extracted as u64);
+	counter!(SCENES_DETECTED_TOTAL).increment(stats.scene_changes as u64);
+	histogram!(EXTRACTION_SECONDS).record(stats.extraction_time_ms as f64 / 1000.0);
+	histogram!(SCENE_DETECTION_SECONDS).record(stats.scene_detection_time_ms as f64 / 1000.0);
+	if stats.transcription_time_ms > 0 {
+		histogram!(TRANSCRIPTION_SECONDS).record(stats.transcription_time_ms as f64 / 1000.0);
+	}
+}
@@ -3,19 +3,24 @@
 //! This module coordinates frame extraction, scene detection, and transcription
 //! to run in parallel where possible.
 
+use std::borrow::Cow;
 use std::path::Path;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument};
 
-use crate::error::{PerceptionError, Result};
+use crate::error::{ConfigIssue, PerceptionError, Result};
+use crate::resources::{PeakSampler, PipelineResources, StageResources};
 use crate::scene::{detect_scene_changes, FrameCandidate, SceneConfig};
 use crate::video::{
-	extract_frames, get_video_metadata, ExtractedFrame, VideoConfig, VideoMetadata,
+	extract_frames, get_video_metadata_with, ExtractedFrame, VideoConfig, VideoMetadata,
 };
 
 #[cfg(feature = "transcription")]
-use crate::transcribe::{transcribe_video, TranscriptionConfig, TranscriptionResult};
+use crate::transcribe::{transcribe_video_with, TranscriptionConfig, TranscriptionResult};
+#[cfg(feature = "verify")]
+use crate::verify::{verify_frames, FrameVerifyConfig};
 
 // ============================================================================
 // Configuration
@@ -40,6 +45,21 @@ pub struct PipelineConfig {
 	/// Whether to skip transcription even if configured
 	#[cfg(feature = "transcription")]
 	pub skip_transcription: bool,
+
+	/// Decode a sample of extracted frames before scene detection
+	/// (`None` = skip, requires `verify` feature)
+	#[cfg(feature = "verify")]
+	pub verify_frames: Option<FrameVerifyConfig>,
+
+	/// How often to sample peak memory per stage, in milliseconds
+	/// (0 = disabled) (default: 100)
+	pub resource_sample_interval_ms: u64,
+
+	/// Make output reproducible for golden-file tests: turns on
+	/// `deterministic` in the video and transcription configs, reports
+	/// zero stage timings, and sorts frames and transcript segments
+	/// canonically (default: false)
+	pub deterministic: bool,
 }
 
 impl Default for PipelineConfig {
@@ -52,7 +72,63 @@ impl Default for PipelineConfig {
 			enable_scene_detection: true,
 			#[cfg(feature = "transcription")]
 			skip_transcription: false,
+			#[cfg(feature = "verify")]
+			verify_frames: Some(FrameVerifyConfig::default()),
+			resource_sample_interval_ms: 100,
+			deterministic: false,
+		}
+	}
+}
+
+impl PipelineConfig {
+	/// Every issue in the stage configs, with fields prefixed by stage
+	/// (e.g. `"scene.hash_size"`).
+	///
+	/// Scene config is checked only when scene detection is enabled, and
+	/// transcription config only when transcription will run.
+	#[must_use]
+	pub fn validate(&self) -> Vec<ConfigIssue> {
+		let mut issues: Vec<ConfigIssue> = self
+			.video
+			.validate()
+			.into_iter()
+			.map(|issue| issue.within("video"))
+			.collect();
+		if self.enable_scene_detection {
+			issues.extend(
+				self.scene
+					.validate()
+					.into_iter()
+					.map(|issue| issue.within("scene")),
+			);
+		}
+		#[cfg(feature = "transcription")]
+		if let Some(transcription) = self
+			.transcription
+			.as_ref()
+			.filter(|_| !self.skip_transcription)
+		{
+			issues.extend(
+				transcription
+					.validate()
+					.into_iter()
+					.map(|issue| issue.within("transcription")),
+			);
+		}
+		issues
+	}
+
+	/// This config with `deterministic` pushed down into every stage.
+	#[must_use]
+	pub fn with_deterministic_stages(&self) -> Self {
+		let mut config = self.clone();
+		config.deterministic = true;
+		config.video.deterministic = true;
+		#[cfg(feature = "transcription")]
+		if let Some(transcription) = config.transcription.as_mut() {
+			transcription.deterministic = true;
 		}
+		config
 	}
 }
 
@@ -100,6 +176,10 @@ pub struct ProcessingStats {
 
 	/// Time spent on transcription (ms)
 	pub transcription_time_ms: u64,
+
+	/// Peak memory per stage (all `None` when sampling is disabled)
+	#[serde(default)]
+	pub resources: PipelineResources,
 }
 
 // ============================================================================
@@ -109,20 +189,29 @@ pub struct ProcessingStats {
 /// Process a video file, extracting frames and optionally transcribing.
 ///
 /// This runs frame extraction and transcription in parallel using `tokio::join!`.
+/// An `http(s)://` URL or HLS playlist is read with `config.video.network`,
+/// and both stages cover only `config.video`'s time range.
 ///
 /// # Errors
 ///
 /// Returns an error if video metadata cannot be read, frame extraction fails,
-/// or transcription fails (when enabled).
+/// a sampled frame fails verification (when enabled), or transcription fails
+/// (when enabled).
 #[instrument(skip_all, fields(video = %video_path.as_ref().display()))]
 pub async fn process_video(
 	video_path: impl AsRef<Path>,
 	config: &PipelineConfig,
 ) -> Result<VideoProcessingOutput> {
 	let video_path = video_path.as_ref();
+	let config = if config.deterministic {
+		Cow::Owned(config.with_deterministic_stages())
+	} else {
+		Cow::Borrowed(config)
+	};
+	let config = config.as_ref();
 
 	// Get video metadata first
-	let metadata = get_video_metadata(video_path).await?;
+	let metadata = get_video_metadata_with(video_path, &config.video.network).await?;
 	debug!(?metadata, "Got video metadata");
 
 	let mut stats = ProcessingStats {
@@ -132,6 +221,7 @@ pub async fn process_video(
 		extraction_time_ms: 0,
 		scene_detection_time_ms: 0,
 		transcription_time_ms: 0,
+		resources: PipelineResources::default(),
 	};
 
 	// Run frame extraction and transcription in parallel
@@ -140,35 +230,39 @@ pub async fn process_video(
 		let video_path_clone = video_path.to_path_buf();
 
 		let frames_task = async {
+			let sampler = start_sampler(config, false);
 			let start = std::time::Instant::now();
 			let result = extract_frames(video_path, &config.video).await;
 			#[allow(clippy::cast_possible_truncation)]
 			let elapsed = start.elapsed().as_millis() as u64;
-			(result, elapsed)
+			(result, elapsed, finish_sampler(sampler))
 		};
 
 		let transcript_task = async {
 			if config.skip_transcription {
-				return (Ok(None), 0);
+				return (Ok(None), 0, StageResources::default());
 			}
 
 			if let Some(ref t_config) = config.transcription {
 				if !metadata.has_audio {
-					return (Ok(None), 0);
+					return (Ok(None), 0, StageResources::default());
 				}
 
+				let sampler = start_sampler(config, true);
 				let start = std::time::Instant::now();
-				let result = transcribe_video(&video_path_clone, t_config).await;
+				let result =
+					transcribe_video_with(&video_path_clone, t_config, &config.video).await;
 				#[allow(clippy::cast_possible_truncation)]
 				let elapsed = start.elapsed().as_millis() as u64;
+				let resources = finish_sampler(sampler);
 
 				match result {
-					Ok(t) => (Ok(Some(t)), elapsed),
-					Err(e) if e.is_no_audio() => (Ok(None), elapsed),
-					Err(e) => (Err(e), elapsed),
+					Ok(t) => (Ok(Some(t)), elapsed, resources),
+					Err(e) if e.is_no_audio() => (Ok(None), elapsed, resources),
+					Err(e) => (Err(e), elapsed, resources),
 				}
 			} else {
-				(Ok(None), 0)
+				(Ok(None), 0, StageResources::default())
 			}
 		};
 
@@ -177,20 +271,35 @@ pub async fn process_video(
 
 	#[cfg(not(feature = "transcription"))]
 	let frames_result = {
+		let sampler = start_sampler(config, false);
 		let start = std::time::Instant::now();
 		let result = extract_frames(video_path, &config.video).await;
 		#[allow(clippy::cast_possible_truncation)]
 		let elapsed = start.elapsed().as_millis() as u64;
-		(result, elapsed)
+		(result, elapsed, finish_sampler(sampler))
 	};
 
 	// Process frame extraction result
-	let (frames, extraction_time) = frames_result;
+	let (frames, extraction_time, extraction_resources) = frames_result;
 	stats.extraction_time_ms = extraction_time;
+	stats.resources.extraction = extraction_resources;
 	let frames: Vec<ExtractedFrame> = frames?;
 	stats.frames_extracted = frames.len();
 
+	// Catch truncated or empty frames before they are hashed
+	#[cfg(feature = "verify")]
+	if let Some(verify_config) = &config.verify_frames {
+		let verification = verify_frames(&frames, &metadata, verify_config);
+		if let Some(failure) = verification.failures.first() {
+			return Err(PerceptionError::CorruptFrame {
+				path: frames[failure.index].path.clone(),
+				reason: failure.defect.to_string(),
+			});
+		}
+	}
+
 	// Run scene detection
+	let sampler = start_sampler(config, false);
 	let scene_start = std::time::Instant::now();
 	let frame_candidates = if config.enable_scene_detection && !frames.is_empty() {
 		detect_scene_changes(&frames, &config.scene)?
@@ -214,6 +323,7 @@ pub async fn process_video(
 	{
 		stats.scene_detection_time_ms = scene_start.elapsed().as_millis() as u64;
 	}
+	stats.resources.scene_detection = finish_sampler(sampler);
 
 	stats.scene_changes = frame_candidates
 		.iter()
@@ -224,8 +334,9 @@ pub async fn process_video(
 	// Process transcription result
 	#[cfg(feature = "transcription")]
 	let (transcript, no_audio) = {
-		let (result, transcription_time) = transcript_result;
+		let (result, transcription_time, transcription_resources) = transcript_result;
 		stats.transcription_time_ms = transcription_time;
+		stats.resources.transcription = transcription_resources;
 		match result {
 			Ok(Some(t)) => (Some(t), false),
 			Ok(None) => (None, !metadata.has_audio),
@@ -241,19 +352,64 @@ pub async fn process_video(
 		frames = stats.frames_extracted,
 		scene_changes = stats.scene_changes,
 		duplicates = stats.duplicates,
+		peak_rss_bytes = stats.resources.peak_rss_bytes(),
 		"Processing complete"
 	);
+	#[cfg(feature = "metrics")]
+	crate::metrics::record_processing(&stats);
 
-	Ok(VideoProcessingOutput {
+	let mut output = VideoProcessingOutput {
 		metadata,
 		frames: frame_candidates,
 		#[cfg(feature = "transcription")]
 		transcript,
 		no_audio,
 		stats,
+	};
+	if config.deterministic {
+		canonicalize(&mut output);
+	}
+	Ok(output)
+}
+
+/// Start a stage's [`PeakSampler`] unless sampling is disabled.
+///
+/// `track_vram` matters only with the `cuda` feature, where Whisper runs
+/// on the GPU.
+fn start_sampler(config: &PipelineConfig, track_vram: bool) -> Option<PeakSampler> {
+	(config.resource_sample_interval_ms > 0).then(|| {
+		PeakSampler::start(
+			Duration::from_millis(config.resource_sample_interval_ms),
+			track_vram,
+		)
 	})
 }
 
+fn finish_sampler(sampler: Option<PeakSampler>) -> StageResources {
+	sampler.map_or_else(StageResources::default, PeakSampler::finish)
+}
+
+/// Strip wall-clock timings and resource peaks and put every list in
+/// canonical order.
+fn canonicalize(output: &mut VideoProcessingOutput) {
+	output.stats.extraction_time_ms = 0;
+	output.stats.scene_detection_time_ms = 0;
+	output.stats.transcription_time_ms = 0;
+	output.stats.resources = PipelineResources::default();
+
+	output.frames.sort_by(|a, b| {
+		a.frame
+			.timestamp_seconds
+			.total_cmp(&b.frame.timestamp_seconds)
+			.then(a.frame.frame_number.cmp(&b.frame.frame_number))
+	});
+
+	#[cfg(feature = "transcription")]
+	if let Some(transcript) = output.transcript.as_mut() {
+		transcript.segments.sort_by_key(|s| (s.start_ms, s.end_ms));
+	}
+}
+
 /// Synchronous wrapper for `process_video` (blocks the current thread).
 ///
 /// Use this when calling from a synchronous context. For async code,
@@ -291,6 +447,84 @@ mod tests {
 		assert_eq!(config.video.max_frames, 100);
 	}
 
+	#[test]
+	fn test_deterministic_stages() {
+		let config = PipelineConfig::default().with_deterministic_stages();
+		assert!(config.deterministic);
+		assert!(config.video.deterministic);
+		#[cfg(feature = "transcription")]
+		assert!(config
+			.transcription
+			.as_ref()
+			.is_some_and(|t| t.deterministic));
+	}
+
+	#[test]
+	fn test_canonicalize_output() {
+		let frame = |timestamp_seconds: f64, frame_number: u32| FrameCandidate {
+			frame: ExtractedFrame {
+				path: format!("{frame_number}.jpg").into(),
+				timestamp_seconds,
+				frame_number,
+				is_keyframe: false,
+				data: None,
+				variants: Vec::new(),
+			},
+			hash: crate::scene::PerceptualHash {
+				bytes: vec![],
+				hex: String::new(),
+			},
+			is_scene_change: false,
+			is_duplicate: false,
+			distance_from_previous: 0,
+		};
+		let mut output = VideoProcessingOutput {
+			metadata: VideoMetadata {
+				duration_seconds: 2.0,
+				frame_rate: 1.0,
+				frame_count: 2,
+				width: 4,
+				height: 2,
+				codec: "h264".to_string(),
+				has_audio: false,
+				rotation: 0,
+			},
+			frames: vec![frame(1.0, 1), frame(0.0, 0)],
+			#[cfg(feature = "transcription")]
+			transcript: None,
+			no_audio: true,
+			stats: ProcessingStats {
+				frames_extracted: 2,
+				scene_changes: 0,
+				duplicates: 0,
+				extraction_time_ms: 120,
+				scene_detection_time_ms: 7,
+				transcription_time_ms: 0,
+				resources: PipelineResources::default(),
+			},
+		};
+
+		canonicalize(&mut output);
+		assert_eq!(output.frames[0].frame.frame_number, 0);
+		assert_eq!(output.stats.extraction_time_ms, 0);
+		assert_eq!(output.stats.scene_detection_time_ms, 0);
+		assert_eq!(output.stats.frames_extracted, 2);
+	}
+
+	#[test]
+	fn test_pipeline_config_validate() {
+		assert!(PipelineConfig::default().validate().is_empty());
+
+		let mut config = PipelineConfig::default();
+		config.video.quality = 40;
+		config.scene.hash_size = 3;
+		let fields: Vec<String> = config.validate().into_iter().map(|i| i.field).collect();
+		assert_eq!(fields, ["video.quality", "scene.hash_size"]);
+
+		config.enable_scene_detection = false;
+		assert_eq!(config.validate().len(), 1);
+	}
+
 	#[test]
 	fn test_processing_stats_default() {
 		let stats = ProcessingStats {
@@ -300,6 +534,7 @@ mod tests {
 			extraction_time_ms: 0,
 			scene_detection_time_ms: 0,
 			transcription_time_ms: 0,
+			resources: PipelineResources::default(),
 		};
 
 		assert_eq!(stats.frames_extracted, 0);
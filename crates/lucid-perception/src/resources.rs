@@ -0,0 +1,293 @@
+//! Peak resource sampling.
+//!
+//! A [`PeakSampler`] runs on its own thread for the duration of a pipeline
+//! stage and records the highest resident memory it sees, so a report of
+//! "the worker was OOM-killed" can be matched against what each stage
+//! actually needed.
+//!
+//! Resident memory covers this process and its direct children (the
+//! `FFmpeg` processes spawned for extraction and audio decoding) and is
+//! read from `/proc`, so it is only available on Linux. With the `cuda`
+//! feature, GPU memory held by this process is sampled through
+//! `nvidia-smi` where requested.
+//!
+//! Extraction and transcription run concurrently, so each of their peaks
+//! includes whatever the other stage held at the time; treat them as upper
+//! bounds.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Reports
+// ============================================================================
+
+/// Peak usage observed during one stage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageResources {
+	/// Peak resident memory of this process and its children, in bytes
+	/// (`None` = not sampled or unsupported platform)
+	pub peak_rss_bytes: Option<u64>,
+
+	/// Peak GPU memory held by this process, in bytes (`None` = not
+	/// sampled, no `cuda` feature, or `nvidia-smi` unavailable)
+	pub peak_vram_bytes: Option<u64>,
+}
+
+impl StageResources {
+	fn observe(&mut self, rss: Option<u64>, vram: Option<u64>) {
+		self.peak_rss_bytes = max_option(self.peak_rss_bytes, rss);
+		self.peak_vram_bytes = max_option(self.peak_vram_bytes, vram);
+	}
+}
+
+/// Peak usage per pipeline stage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PipelineResources {
+	/// Frame extraction
+	pub extraction: StageResources,
+	/// Scene detection
+	pub scene_detection: StageResources,
+	/// Transcription
+	pub transcription: StageResources,
+}
+
+impl PipelineResources {
+	/// Highest resident memory seen in any stage.
+	#[must_use]
+	pub fn peak_rss_bytes(&self) -> Option<u64> {
+		self.stages()
+			.into_iter()
+			.fold(None, |peak, s| max_option(peak, s.peak_rss_bytes))
+	}
+
+	/// Highest GPU memory seen in any stage.
+	#[must_use]
+	pub fn peak_vram_bytes(&self) -> Option<u64> {
+		self.stages()
+			.into_iter()
+			.fold(None, |peak, s| max_option(peak, s.peak_vram_bytes))
+	}
+
+	const fn stages(&self) -> [StageResources; 3] {
+		[self.extraction, self.scene_detection, self.transcription]
+	}
+}
+
+fn max_option(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+	match (a, b) {
+		(Some(a), Some(b)) => Some(a.max(b)),
+		(a, None) => a,
+		(None, b) => b,
+	}
+}
+
+// ============================================================================
+// Sampler
+// ============================================================================
+
+/// Samples usage on a background thread until [`finish`](Self::finish)ed.
+///
+/// The first sample is taken on start and the last on finish, so even a
+/// stage shorter than the interval gets a reading. Dropping the sampler
+/// without finishing stops the thread.
+#[derive(Debug)]
+pub struct PeakSampler {
+	stop: Arc<AtomicBool>,
+	handle: Option<JoinHandle<StageResources>>,
+}
+
+impl PeakSampler {
+	/// Start sampling every `interval`; `track_vram` adds GPU memory
+	/// (ignored without the `cuda` feature).
+	#[must_use]
+	pub fn start(interval: Duration, track_vram: bool) -> Self {
+		let stop = Arc::new(AtomicBool::new(false));
+		let thread_stop = Arc::clone(&stop);
+		let handle = std::thread::Builder::new()
+			.name("lucid-resource-sampler".to_string())
+			.spawn(move || {
+				let mut peak = StageResources::default();
+				loop {
+					let vram = if track_vram {
+						process_vram_bytes()
+					} else {
+						None
+					};
+					peak.observe(process_tree_rss_bytes(), vram);
+					if thread_stop.load(Ordering::Acquire) {
+						return peak;
+					}
+					std::thread::park_timeout(interval);
+				}
+			})
+			.ok();
+		Self { stop, handle }
+	}
+
+	/// Stop sampling and return the peaks.
+	#[must_use]
+	pub fn finish(mut self) -> StageResources {
+		self.stop.store(true, Ordering::Release);
+		self.handle
+			.take()
+			.map_or_else(StageResources::default, |handle| {
+				handle.thread().unpark();
+				handle.join().unwrap_or_default()
+			})
+	}
+}
+
+impl Drop for PeakSampler {
+	fn drop(&mut self) {
+		self.stop.store(true, Ordering::Release);
+		if let Some(handle) = &self.handle {
+			handle.thread().unpark();
+		}
+	}
+}
+
+// ============================================================================
+// Platform Readings
+// ============================================================================
+
+/// Resident memory of this process plus its direct children, in bytes.
+#[cfg(target_os = "linux")]
+fn process_tree_rss_bytes() -> Option<u64> {
+	let own = std::fs::read_to_string("/proc/self/status").ok()?;
+	let own = parse_status_kib(&own, "VmRSS:")?;
+
+	let children: u64 = child_pids()
+		.into_iter()
+		.filter_map(|pid| std::fs::read_to_string(format!("/proc/{pid}/status")).ok())
+		.filter_map(|status| parse_status_kib(&status, "VmRSS:"))
+		.sum();
+
+	Some((own + children) * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+const fn process_tree_rss_bytes() -> Option<u64> {
+	None
+}
+
+/// Pids of this process's direct children, from every thread's
+/// `children` list.
+#[cfg(target_os = "linux")]
+fn child_pids() -> Vec<u32> {
+	let Ok(tasks) = std::fs::read_dir("/proc/self/task") else {
+		return Vec::new();
+	};
+	tasks
+		.filter_map(std::result::Result::ok)
+		.filter_map(|task| std::fs::read_to_string(task.path().join("children")).ok())
+		.flat_map(|children| {
+			children
+				.split_whitespace()
+				.filter_map(|pid| pid.parse().ok())
+				.collect::<Vec<u32>>()
+		})
+		.collect()
+}
+
+/// Value of a `kB` line such as `VmRSS:   1234 kB` in `/proc/*/status`.
+#[cfg(any(target_os = "linux", test))]
+fn parse_status_kib(status: &str, key: &str) -> Option<u64> {
+	status
+		.lines()
+		.find_map(|line| line.strip_prefix(key))
+		.and_then(|rest| rest.split_whitespace().next())
+		.and_then(|kib| kib.parse().ok())
+}
+
+/// GPU memory held by this process across all devices, in bytes.
+#[cfg(feature = "cuda")]
+fn process_vram_bytes() -> Option<u64> {
+	let output = std::process::Command::new("nvidia-smi")
+		.args([
+			"--query-compute-apps=pid,used_memory",
+			"--format=csv,noheader,nounits",
+		])
+		.output()
+		.ok()?;
+	if !output.status.success() {
+		return None;
+	}
+	parse_nvidia_smi(&String::from_utf8_lossy(&output.stdout), std::process::id())
+}
+
+#[cfg(not(feature = "cuda"))]
+const fn process_vram_bytes() -> Option<u64> {
+	None
+}
+
+/// Sum the `pid, used_memory` rows (MiB) belonging to `pid`.
+#[cfg(any(feature = "cuda", test))]
+fn parse_nvidia_smi(csv: &str, pid: u32) -> Option<u64> {
+	let mut total = None;
+	for line in csv.lines() {
+		let mut fields = line.split(',').map(str::trim);
+		let (Some(row_pid), Some(mib)) = (fields.next(), fields.next()) else {
+			continue;
+		};
+		if row_pid.parse() == Ok(pid) {
+			if let Ok(mib) = mib.parse::<u64>() {
+				total = Some(total.unwrap_or(0) + mib * 1024 * 1024);
+			}
+		}
+	}
+	total
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_status_kib() {
+		let status = "Name:\tlucid\nVmHWM:\t  2048 kB\nVmRSS:\t  1024 kB\n";
+		assert_eq!(parse_status_kib(status, "VmRSS:"), Some(1024));
+		assert_eq!(parse_status_kib(status, "VmSwap:"), None);
+	}
+
+	#[test]
+	fn test_parse_nvidia_smi() {
+		let csv = "100, 512\n200, 256\n100, 64\n";
+		assert_eq!(parse_nvidia_smi(csv, 100), Some(576 * 1024 * 1024));
+		assert_eq!(parse_nvidia_smi(csv, 300), None);
+	}
+
+	#[test]
+	fn test_pipeline_peaks() {
+		let resources = PipelineResources {
+			extraction: StageResources {
+				peak_rss_bytes: Some(300),
+				peak_vram_bytes: None,
+			},
+			scene_detection: StageResources {
+				peak_rss_bytes: Some(500),
+				peak_vram_bytes: None,
+			},
+			transcription: StageResources::default(),
+		};
+		assert_eq!(resources.peak_rss_bytes(), Some(500));
+		assert_eq!(resources.peak_vram_bytes(), None);
+	}
+
+	#[test]
+	fn test_sampler_reports_rss() {
+		let resources = PeakSampler::start(Duration::from_millis(5), false).finish();
+		if cfg!(target_os = "linux") {
+			assert!(resources.peak_rss_bytes.is_some_and(|b| b > 0));
+		}
+		assert_eq!(resources.peak_vram_bytes, None);
+	}
+}
@@ -10,13 +10,14 @@
 //! 2. Compare consecutive frames using Hamming distance
 //! 3. Frames with distance above threshold indicate scene changes
 
+use std::ops::Range;
 use std::path::Path;
 
 use image_hasher::{HashAlg, HasherConfig, ImageHash};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument};
 
-use crate::error::{PerceptionError, Result};
+use crate::error::{ConfigIssue, PerceptionError, Result};
 use crate::video::ExtractedFrame;
 
 // ============================================================================
@@ -49,6 +50,56 @@ impl Default for SceneConfig {
 	}
 }
 
+impl SceneConfig {
+	/// Every out-of-range or inconsistent field.
+	///
+	/// Thresholds are Hamming distances, so neither can exceed the
+	/// `hash_size²` bits of a hash; a duplicate threshold at or above the
+	/// scene threshold would mark scene changes as duplicates.
+	#[must_use]
+	pub fn validate(&self) -> Vec<ConfigIssue> {
+		let mut issues = Vec::new();
+
+		if self.hash_size < 2 || !self.hash_size.is_power_of_two() {
+			issues.push(ConfigIssue::new(
+				"hash_size",
+				format!("must be a power of 2 of at least 2, got {}", self.hash_size),
+			));
+		}
+		let bits = self.hash_size.saturating_mul(self.hash_size);
+		if self.scene_threshold == 0 || self.scene_threshold > bits {
+			issues.push(ConfigIssue::new(
+				"scene_threshold",
+				format!(
+					"must be in 1..={bits} for a {size}x{size} hash, got {}",
+					self.scene_threshold,
+					size = self.hash_size
+				),
+			));
+		}
+		if self.duplicate_threshold > bits {
+			issues.push(ConfigIssue::new(
+				"duplicate_threshold",
+				format!(
+					"must be at most {bits} for a {size}x{size} hash, got {}",
+					self.duplicate_threshold,
+					size = self.hash_size
+				),
+			));
+		} else if self.duplicate_threshold >= self.scene_threshold {
+			issues.push(ConfigIssue::new(
+				"duplicate_threshold",
+				format!(
+					"must be below scene_threshold ({}), got {}",
+					self.scene_threshold, self.duplicate_threshold
+				),
+			));
+		}
+
+		issues
+	}
+}
+
 // ============================================================================
 // Perceptual Hash
 // ============================================================================
@@ -120,6 +171,26 @@ pub fn compute_phash_sized(image_path: impl AsRef<Path>, hash_size: u32) -> Resu
 	Ok(PerceptualHash::from_image_hash(&hash))
 }
 
+/// Compute perceptual hash of an encoded image held in memory, such as
+/// [`ExtractedFrame::data`].
+///
+/// # Errors
+///
+/// Returns an error if the image cannot be decoded.
+#[instrument(skip_all, fields(bytes = bytes.len(), size = hash_size))]
+pub fn compute_phash_from_memory(bytes: &[u8], hash_size: u32) -> Result<PerceptualHash> {
+	let image = image::load_from_memory(bytes)?;
+
+	let hasher = HasherConfig::new()
+		.hash_alg(HashAlg::DoubleGradient)
+		.hash_size(hash_size, hash_size)
+		.to_hasher();
+
+	let hash = hasher.hash_image(&image);
+
+	Ok(PerceptualHash::from_image_hash(&hash))
+}
+
 // ============================================================================
 // Hamming Distance
 // ============================================================================
@@ -136,6 +207,106 @@ pub fn hamming_distance(a: &[u8], b: &[u8]) -> u32 {
 		.sum()
 }
 
+// ============================================================================
+// Hash Index
+// ============================================================================
+
+/// BK-tree over perceptual hashes for Hamming-radius lookups.
+///
+/// Each child sits at its distance from the parent, so by the triangle
+/// inequality a query within radius `r` of a node at distance `d` only
+/// needs the children at distances `d - r..=d + r`. Near-duplicate
+/// searches over thousands of stored frames touch a small part of the tree.
+#[derive(Debug, Clone, Default)]
+pub struct HashIndex {
+	nodes: Vec<HashIndexNode>,
+}
+
+#[derive(Debug, Clone)]
+struct HashIndexNode {
+	id: usize,
+	hash: PerceptualHash,
+	/// `(distance from this node, child node index)`
+	children: Vec<(u32, usize)>,
+}
+
+impl HashIndex {
+	/// Create an empty index.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { nodes: Vec::new() }
+	}
+
+	/// Number of hashes stored.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.nodes.len()
+	}
+
+	/// Whether the index is empty.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.nodes.is_empty()
+	}
+
+	/// Store `hash` under a caller-chosen `id`; ids need not be unique.
+	pub fn insert(&mut self, id: usize, hash: PerceptualHash) {
+		let new_index = self.nodes.len();
+		let mut current = 0;
+		while current < new_index {
+			let distance = self.nodes[current].hash.distance(&hash);
+			let next = self.nodes[current]
+				.children
+				.iter()
+				.find(|&&(d, _)| d == distance)
+				.map(|&(_, child)| child);
+			if let Some(child) = next {
+				current = child;
+			} else {
+				self.nodes[current].children.push((distance, new_index));
+				break;
+			}
+		}
+		self.nodes.push(HashIndexNode {
+			id,
+			hash,
+			children: Vec::new(),
+		});
+	}
+
+	/// Ids of stored hashes within `max_distance` of `hash`, with their
+	/// distances, nearest first (ties by insertion order).
+	#[must_use]
+	pub fn within(&self, hash: &PerceptualHash, max_distance: u32) -> Vec<(usize, u32)> {
+		let mut found = Vec::new();
+		let mut stack = if self.nodes.is_empty() {
+			Vec::new()
+		} else {
+			vec![0]
+		};
+		while let Some(current) = stack.pop() {
+			let node = &self.nodes[current];
+			let distance = node.hash.distance(hash);
+			if distance <= max_distance {
+				found.push((current, distance));
+			}
+			let low = distance.saturating_sub(max_distance);
+			let high = distance.saturating_add(max_distance);
+			stack.extend(
+				node.children
+					.iter()
+					.filter(|&&(d, _)| (low..=high).contains(&d))
+					.map(|&(_, child)| child),
+			);
+		}
+		found.sort_unstable_by_key(|&(node, distance)| (distance, node));
+		found
+			.into_iter()
+			.map(|(node, distance)| (self.nodes[node].id, distance))
+			.collect()
+	}
+}
+
 // ============================================================================
 // Frame Candidate
 // ============================================================================
@@ -167,6 +338,9 @@ pub struct FrameCandidate {
 ///
 /// Returns indices of frames where scene changes occur.
 ///
+/// Frames extracted to memory are hashed from their `data` rather than
+/// read from `path`.
+///
 /// # Errors
 ///
 /// Returns an error if any frame image cannot be read or hashed.
@@ -183,7 +357,10 @@ pub fn detect_scene_changes(
 	let mut previous_hash: Option<PerceptualHash> = None;
 
 	for frame in frames {
-		let hash = compute_phash_sized(&frame.path, config.hash_size)?;
+		let hash = match &frame.data {
+			Some(data) => compute_phash_from_memory(data, config.hash_size)?,
+			None => compute_phash_sized(&frame.path, config.hash_size)?,
+		};
 
 		let (is_scene_change, is_duplicate, distance) = previous_hash.as_ref().map_or(
 			(true, false, 0), // First frame is always a scene boundary
@@ -238,44 +415,47 @@ pub fn get_unique_frames(candidates: &[FrameCandidate]) -> Vec<&FrameCandidate>
 /// that is most similar to all other frames in that scene (the "centroid").
 #[must_use]
 pub fn get_representative_frames(candidates: &[FrameCandidate]) -> Vec<&FrameCandidate> {
-	if candidates.is_empty() {
-		return Vec::new();
-	}
+	scene_ranges(candidates)
+		.into_iter()
+		.filter_map(|scene| {
+			representative_offset(&candidates[scene.clone()]).map(|i| &candidates[scene.start + i])
+		})
+		.collect()
+}
 
-	let mut representatives = Vec::new();
+/// Index range of each scene, split at scene change frames.
+///
+/// Frames before the first scene change form a scene of their own.
+pub(crate) fn scene_ranges(candidates: &[FrameCandidate]) -> Vec<Range<usize>> {
+	let mut scenes = Vec::new();
 	let mut scene_start = 0;
 
 	for (i, candidate) in candidates.iter().enumerate() {
 		if candidate.is_scene_change && i > 0 {
-			// End of previous scene, find representative
-			if let Some(rep) = find_scene_representative(&candidates[scene_start..i]) {
-				representatives.push(rep);
-			}
+			scenes.push(scene_start..i);
 			scene_start = i;
 		}
 	}
-
-	// Don't forget the last scene
-	if let Some(rep) = find_scene_representative(&candidates[scene_start..]) {
-		representatives.push(rep);
+	if scene_start < candidates.len() {
+		scenes.push(scene_start..candidates.len());
 	}
 
-	representatives
+	scenes
 }
 
-/// Find the most representative frame within a scene.
-fn find_scene_representative(scene_frames: &[FrameCandidate]) -> Option<&FrameCandidate> {
+/// Offset of the most representative frame within a scene.
+pub(crate) fn representative_offset(scene_frames: &[FrameCandidate]) -> Option<usize> {
 	if scene_frames.is_empty() {
 		return None;
 	}
 
 	if scene_frames.len() == 1 {
-		return Some(&scene_frames[0]);
+		return Some(0);
 	}
 
 	// For each frame, compute average distance to all other frames
 	let mut min_avg_distance = u32::MAX;
-	let mut best_frame = &scene_frames[0];
+	let mut best_frame = 0;
 
 	for (i, frame) in scene_frames.iter().enumerate() {
 		let total_distance: u32 = scene_frames
@@ -290,7 +470,7 @@ fn find_scene_representative(scene_frames: &[FrameCandidate]) -> Option<&FrameCa
 
 		if avg_distance < min_avg_distance {
 			min_avg_distance = avg_distance;
-			best_frame = frame;
+			best_frame = i;
 		}
 	}
 
@@ -334,6 +514,48 @@ mod tests {
 		assert_eq!(config.duplicate_threshold, 3);
 	}
 
+	#[test]
+	fn test_scene_config_validate() {
+		assert!(SceneConfig::default().validate().is_empty());
+
+		let config = SceneConfig {
+			hash_size: 8,
+			scene_threshold: 65,
+			duplicate_threshold: 70,
+		};
+		let fields: Vec<String> = config.validate().into_iter().map(|i| i.field).collect();
+		assert_eq!(fields, ["scene_threshold", "duplicate_threshold"]);
+
+		let config = SceneConfig {
+			hash_size: 6,
+			scene_threshold: 10,
+			duplicate_threshold: 10,
+		};
+		let fields: Vec<String> = config.validate().into_iter().map(|i| i.field).collect();
+		assert_eq!(fields, ["hash_size", "duplicate_threshold"]);
+	}
+
+	#[test]
+	fn test_hash_index_within() {
+		let hash = |byte: u8| PerceptualHash {
+			bytes: vec![byte],
+			hex: String::new(),
+		};
+		let mut index = HashIndex::new();
+		index.insert(10, hash(0b0000_0000));
+		index.insert(11, hash(0b0000_0001));
+		index.insert(12, hash(0b0000_0111));
+		index.insert(13, hash(0b1111_1111));
+		assert_eq!(index.len(), 4);
+
+		assert_eq!(index.within(&hash(0b0000_0011), 1), vec![(11, 1), (12, 1)]);
+		assert_eq!(
+			index.within(&hash(0b0000_0000), 3),
+			vec![(10, 0), (11, 1), (12, 3)]
+		);
+		assert!(HashIndex::new().within(&hash(0), 8).is_empty());
+	}
+
 	#[test]
 	fn test_perceptual_hash_distance() {
 		let hash1 = PerceptualHash {
@@ -20,7 +20,8 @@ use tokio::process::Command;
 use tracing::{debug, instrument, warn};
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
-use crate::error::{PerceptionError, Result};
+use crate::error::{ConfigIssue, PerceptionError, Result};
+use crate::video::{input_args, range_args, VideoConfig};
 
 // ============================================================================
 // Configuration
@@ -43,8 +44,17 @@ pub struct TranscriptionConfig {
 
 	/// Maximum segment length in characters
 	pub max_segment_length: usize,
+
+	/// Disable Whisper's temperature fallback (which resamples a failed
+	/// segment at higher temperature) and pin `threads = 0` to
+	/// [`DETERMINISTIC_THREADS`] (default: false)
+	pub deterministic: bool,
 }
 
+/// Whisper thread count used when [`TranscriptionConfig::deterministic`]
+/// is set and `threads` is 0.
+pub const DETERMINISTIC_THREADS: u32 = 4;
+
 impl Default for TranscriptionConfig {
 	fn default() -> Self {
 		Self {
@@ -53,7 +63,65 @@ impl Default for TranscriptionConfig {
 			threads: 0,
 			translate: false,
 			max_segment_length: 0,
+			deterministic: false,
+		}
+	}
+}
+
+impl TranscriptionConfig {
+	/// Every malformed or inconsistent field.
+	///
+	/// Checks the language code's form and that English-only models
+	/// (`*.en.bin`) are not asked for another language or for
+	/// translation. Whether the model file exists is left to
+	/// [`is_model_available`].
+	#[must_use]
+	pub fn validate(&self) -> Vec<ConfigIssue> {
+		let mut issues = Vec::new();
+
+		if self.model_path.as_os_str().is_empty() {
+			issues.push(ConfigIssue::new("model_path", "must not be empty"));
+		}
+		let language_ok = self.language == "auto"
+			|| ((2..=3).contains(&self.language.len())
+				&& self.language.bytes().all(|b| b.is_ascii_lowercase()));
+		if !language_ok {
+			issues.push(ConfigIssue::new(
+				"language",
+				format!(
+					"must be \"auto\" or a lowercase ISO 639 code such as \"en\", got {:?}",
+					self.language
+				),
+			));
+		}
+
+		let english_only = self
+			.model_path
+			.file_name()
+			.and_then(|name| name.to_str())
+			.is_some_and(|name| name.ends_with(".en.bin"));
+		if english_only {
+			if language_ok && self.language != "en" {
+				issues.push(ConfigIssue::new(
+					"language",
+					format!(
+						"{} is an English-only model; use \"en\" or a multilingual model",
+						self.model_path.display()
+					),
+				));
+			}
+			if self.translate {
+				issues.push(ConfigIssue::new(
+					"translate",
+					format!(
+						"{} is an English-only model and cannot translate",
+						self.model_path.display()
+					),
+				));
+			}
 		}
+
+		issues
 	}
 }
 
@@ -160,9 +228,14 @@ impl TranscriptionResult {
 // Audio Extraction
 // ============================================================================
 
-/// Extract audio from a video file to WAV format for Whisper.
+/// Extract audio from a video file or URL to WAV format for Whisper, over
+/// the part of the video `video` selects.
 #[instrument(skip_all, fields(video = %video_path.as_ref().display()))]
-async fn extract_audio(video_path: impl AsRef<Path>, output_path: impl AsRef<Path>) -> Result<()> {
+async fn extract_audio(
+	video_path: impl AsRef<Path>,
+	output_path: impl AsRef<Path>,
+	video: &VideoConfig,
+) -> Result<()> {
 	let video_path = video_path.as_ref();
 	let output_path = output_path.as_ref();
 
@@ -173,10 +246,10 @@ async fn extract_audio(video_path: impl AsRef<Path>, output_path: impl AsRef<Pat
 
 	// Extract audio as 16kHz mono WAV (required by Whisper)
 	let output = Command::new("ffmpeg")
-		.args([
-			"-y", // Overwrite output
-			"-i",
-		])
+		.arg("-y") // Overwrite output
+		.args(input_args(video_path, &video.network)?)
+		.args(range_args(video))
+		.arg("-i")
 		.arg(video_path)
 		.args([
 			"-vn", // No video
@@ -234,6 +307,23 @@ async fn extract_audio(video_path: impl AsRef<Path>, output_path: impl AsRef<Pat
 pub async fn transcribe_video(
 	video_path: impl AsRef<Path>,
 	config: &TranscriptionConfig,
+) -> Result<TranscriptionResult> {
+	transcribe_video_with(video_path, config, &VideoConfig::default()).await
+}
+
+/// [`transcribe_video`] reading the video as frame extraction with `video`
+/// does: its network settings for a URL, and only its time range.
+/// Segment times stay relative to the start of the video.
+///
+/// # Errors
+///
+/// Returns an error if the Whisper model is not found, audio extraction fails,
+/// or transcription fails.
+#[instrument(skip_all, fields(video = %video_path.as_ref().display()))]
+pub async fn transcribe_video_with(
+	video_path: impl AsRef<Path>,
+	config: &TranscriptionConfig,
+	video: &VideoConfig,
 ) -> Result<TranscriptionResult> {
 	let video_path = video_path.as_ref();
 
@@ -252,20 +342,30 @@ pub async fn transcribe_video(
 
 	// Extract audio
 	debug!("Extracting audio from video");
-	extract_audio(video_path, &audio_path).await?;
+	extract_audio(video_path, &audio_path, video).await?;
 
 	// Clone paths for the closure and cleanup
 	let audio_path_for_cleanup = audio_path.clone();
 
 	// Run transcription in blocking task (Whisper is CPU-bound)
 	let config = config.clone();
-	let result = tokio::task::spawn_blocking(move || transcribe_audio_sync(&audio_path, &config))
-		.await
-		.map_err(|e| PerceptionError::TranscriptionFailed(e.to_string()))??;
+	let mut result =
+		tokio::task::spawn_blocking(move || transcribe_audio_sync(&audio_path, &config))
+			.await
+			.map_err(|e| PerceptionError::TranscriptionFailed(e.to_string()))??;
 
 	// Clean up temp file
 	let _ = tokio::fs::remove_file(&audio_path_for_cleanup).await;
 
+	// Whisper times the extracted audio, which begins at the range start
+	let (start, _) = video.time_range(f64::INFINITY);
+	#[allow(clippy::cast_possible_truncation)]
+	let offset_ms = (start * 1000.0).round() as i64;
+	for segment in &mut result.segments {
+		segment.start_ms += offset_ms;
+		segment.end_ms += offset_ms;
+	}
+
 	Ok(result)
 }
 
@@ -303,9 +403,21 @@ fn transcribe_audio_sync(
 	}
 
 	// Set thread count
-	if config.threads > 0 {
+	let threads = if config.threads == 0 && config.deterministic {
+		DETERMINISTIC_THREADS
+	} else {
+		config.threads
+	};
+	if threads > 0 {
 		#[allow(clippy::cast_possible_wrap)]
-		params.set_n_threads(config.threads as i32);
+		params.set_n_threads(threads as i32);
+	}
+
+	// Greedy decoding at temperature 0 with no fallback gives the same text
+	// on every run
+	if config.deterministic {
+		params.set_temperature(0.0);
+		params.set_temperature_inc(0.0);
 	}
 
 	// Enable translation if requested
@@ -474,6 +586,28 @@ mod tests {
 		assert!(!config.translate);
 	}
 
+	#[test]
+	fn test_config_validate() {
+		assert!(TranscriptionConfig::default().validate().is_empty());
+
+		let config = TranscriptionConfig {
+			model_path: PathBuf::from("ggml-base.en.bin"),
+			language: "de".to_string(),
+			translate: true,
+			..TranscriptionConfig::default()
+		};
+		let fields: Vec<String> = config.validate().into_iter().map(|i| i.field).collect();
+		assert_eq!(fields, ["language", "translate"]);
+
+		let config = TranscriptionConfig {
+			model_path: PathBuf::from("ggml-base.bin"),
+			language: "German".to_string(),
+			..TranscriptionConfig::default()
+		};
+		let fields: Vec<String> = config.validate().into_iter().map(|i| i.field).collect();
+		assert_eq!(fields, ["language"]);
+	}
+
 	#[test]
 	fn test_model_download_url() {
 		let url = get_model_download_url();
@@ -0,0 +1,238 @@
+//! Downscaled variants of extracted frames.
+//!
+//! The UI shows thumbnails while scene detection wants full frames. Rather
+//! than extracting twice, each [`FrameSize`] in [`VideoConfig::variants`]
+//! has every extracted frame also written at that size, scaled from the
+//! decoded frame. Variants keep the frame's aspect ratio, are never larger
+//! than the frame, and use its format and quality. They are written beside
+//! the frame as `{stem}-{name}.{ext}`, or into
+//! [`data`](FrameVariant::data) for frames extracted to memory.
+//!
+//! [`extract_frame_at`](crate::video::extract_frame_at) takes no
+//! [`VideoConfig`] and writes no variants.
+
+use std::path::{Path, PathBuf};
+
+use image::imageops::{self, FilterType};
+use image::RgbImage;
+use serde::{Deserialize, Serialize};
+
+use crate::encode::encode;
+use crate::error::{ConfigIssue, Result};
+use crate::video::{ImageFormat, VideoConfig};
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// A size to write every extracted frame at, besides full size.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameSize {
+	/// Name of the variant, appended to the frame's file name; ASCII
+	/// letters, digits, `-` and `_`
+	pub name: String,
+
+	/// Widest the variant may be, in pixels (0 = any width)
+	pub max_width: u32,
+
+	/// Tallest the variant may be, in pixels (0 = any height)
+	pub max_height: u32,
+}
+
+impl FrameSize {
+	/// A variant named `name` fitting within `max_width` × `max_height`
+	/// (0 = unbounded).
+	#[must_use]
+	pub fn new(name: impl Into<String>, max_width: u32, max_height: u32) -> Self {
+		Self {
+			name: name.into(),
+			max_width,
+			max_height,
+		}
+	}
+
+	/// Every out-of-range field.
+	#[must_use]
+	pub fn validate(&self) -> Vec<ConfigIssue> {
+		let mut issues = Vec::new();
+
+		let bad_name = self.name.is_empty()
+			|| !self
+				.name
+				.chars()
+				.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+		if bad_name {
+			issues.push(ConfigIssue::new(
+				"name",
+				format!(
+					"must be non-empty ASCII letters, digits, '-' or '_', got {:?}",
+					self.name
+				),
+			));
+		}
+		if self.max_width == 0 && self.max_height == 0 {
+			issues.push(ConfigIssue::new(
+				"max_width",
+				"max_width or max_height must be set (a full-size variant repeats the frame)",
+			));
+		}
+
+		issues
+	}
+
+	/// Dimensions of a `width` × `height` frame scaled down, keeping its
+	/// aspect ratio, to fit this size; a frame that already fits is kept
+	/// as is.
+	#[must_use]
+	#[allow(
+		clippy::cast_possible_truncation,
+		clippy::cast_sign_loss,
+		clippy::cast_precision_loss
+	)]
+	pub fn fit(&self, width: u32, height: u32) -> (u32, u32) {
+		let bound = |max: u32, actual: u32| {
+			if max == 0 || actual == 0 {
+				1.0
+			} else {
+				f64::from(max) / f64::from(actual)
+			}
+		};
+		let scale = bound(self.max_width, width)
+			.min(bound(self.max_height, height))
+			.min(1.0);
+		if scale >= 1.0 {
+			return (width, height);
+		}
+		let scaled = |actual: u32| ((f64::from(actual) * scale).round() as u32).max(1);
+		(scaled(width), scaled(height))
+	}
+}
+
+// ============================================================================
+// Results
+// ============================================================================
+
+/// An extracted frame at one of [`VideoConfig::variants`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameVariant {
+	/// [`FrameSize::name`] this was written for
+	pub name: String,
+
+	/// Path to the variant image file (empty for in-memory frames)
+	pub path: PathBuf,
+
+	/// Width in pixels
+	pub width: u32,
+
+	/// Height in pixels
+	pub height: u32,
+
+	/// Encoded image, for frames extracted to memory (`None` = the image
+	/// is at `path`)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub data: Option<Vec<u8>>,
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+/// `image`, an extracted frame, at each of `config`'s variant sizes:
+/// written beside `frame_path`, or kept in memory without one.
+pub(crate) fn render_variants(
+	image: &RgbImage,
+	frame_path: Option<&Path>,
+	config: &VideoConfig,
+) -> Result<Vec<FrameVariant>> {
+	config
+		.variants
+		.iter()
+		.map(|size| {
+			let (width, height) = size.fit(image.width(), image.height());
+			let bytes = if (width, height) == image.dimensions() {
+				encode(image, config)?
+			} else {
+				let scaled = imageops::resize(image, width, height, FilterType::Triangle);
+				encode(&scaled, config)?
+			};
+
+			let (path, data) = match frame_path {
+				Some(frame_path) => {
+					let path = variant_path(frame_path, &size.name, config.format);
+					std::fs::write(&path, bytes)?;
+					(path, None)
+				}
+				None => (PathBuf::new(), Some(bytes)),
+			};
+			Ok(FrameVariant {
+				name: size.name.clone(),
+				path,
+				width,
+				height,
+				data,
+			})
+		})
+		.collect()
+}
+
+/// `{stem}-{name}.{ext}` beside the frame at `frame_path`, with the
+/// extension of `format` (the frame itself may still be `FFmpeg`'s PNG).
+fn variant_path(frame_path: &Path, name: &str, format: ImageFormat) -> PathBuf {
+	let stem = frame_path
+		.file_stem()
+		.map(|s| s.to_string_lossy())
+		.unwrap_or_default();
+	frame_path.with_file_name(format!("{stem}-{name}.{}", format.extension()))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_fit() {
+		let thumbnail = FrameSize::new("thumb", 160, 0);
+		assert_eq!(thumbnail.fit(1920, 1080), (160, 90));
+		assert_eq!(thumbnail.fit(1080, 1920), (160, 284));
+		assert_eq!(thumbnail.fit(120, 80), (120, 80));
+
+		let analysis = FrameSize::new("analysis", 0, 1080);
+		assert_eq!(analysis.fit(3840, 2160), (1920, 1080));
+		assert_eq!(analysis.fit(1280, 720), (1280, 720));
+
+		let boxed = FrameSize::new("box", 100, 100);
+		assert_eq!(boxed.fit(400, 200), (100, 50));
+		assert_eq!(boxed.fit(10, 4000), (1, 100));
+	}
+
+	#[test]
+	fn test_frame_size_validate() {
+		assert!(FrameSize::new("thumb_160", 160, 0).validate().is_empty());
+
+		let fields = |size: FrameSize| -> Vec<String> {
+			size.validate()
+				.into_iter()
+				.map(|issue| issue.field)
+				.collect()
+		};
+		assert_eq!(fields(FrameSize::new("", 160, 0)), vec!["name"]);
+		assert_eq!(fields(FrameSize::new("../up", 160, 0)), vec!["name"]);
+		assert_eq!(fields(FrameSize::new("full", 0, 0)), vec!["max_width"]);
+	}
+
+	#[test]
+	fn test_variant_path() {
+		assert_eq!(
+			variant_path(Path::new("/out/abc-00001.jpg"), "thumb", ImageFormat::Jpeg),
+			PathBuf::from("/out/abc-00001-thumb.jpg")
+		);
+		assert_eq!(
+			variant_path(Path::new("/out/abc-00001.png"), "thumb", ImageFormat::Avif),
+			PathBuf::from("/out/abc-00001-thumb.avif")
+		);
+	}
+}
@@ -0,0 +1,344 @@
+//! Frame verification.
+//!
+//! `FFmpeg` can exit successfully after writing a truncated or empty image
+//! (a full disk, a killed process, a damaged stream). This module decodes a
+//! sample of extracted frames in Rust and checks that each is a non-empty
+//! image with the video's dimensions, so bad frames are caught before they
+//! are hashed, described, or stored.
+//!
+//! Requires the `verify` feature.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::video::{ExtractedFrame, VideoMetadata};
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Configuration for frame verification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameVerifyConfig {
+	/// Frames to decode, spread evenly over the video and always including
+	/// the first and last (0 = all) (default: 8)
+	pub sample_size: usize,
+
+	/// Accept frames whose width and height are swapped relative to the
+	/// stream, as `FFmpeg`'s autorotation produces (default: true)
+	pub allow_rotated: bool,
+}
+
+impl Default for FrameVerifyConfig {
+	fn default() -> Self {
+		Self {
+			sample_size: 8,
+			allow_rotated: true,
+		}
+	}
+}
+
+// ============================================================================
+// Results
+// ============================================================================
+
+/// Why a frame failed verification.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FrameDefect {
+	/// The file could not be read or decoded.
+	#[error("unreadable: {0}")]
+	Unreadable(String),
+
+	/// The file or the decoded image has no content.
+	#[error("empty image")]
+	Empty,
+
+	/// The decoded image does not have the video's dimensions.
+	#[error("expected {}x{}, got {}x{}", .expected.0, .expected.1, .actual.0, .actual.1)]
+	DimensionMismatch {
+		/// Video width and height
+		expected: (u32, u32),
+		/// Decoded width and height
+		actual: (u32, u32),
+	},
+}
+
+/// A frame that failed verification.
+#[derive(Debug, Clone)]
+pub struct FrameFailure {
+	/// Position of the frame in the verified slice
+	pub index: usize,
+	/// What is wrong with it
+	pub defect: FrameDefect,
+}
+
+/// Outcome of verifying a set of frames.
+#[derive(Debug, Clone, Default)]
+pub struct FrameVerification {
+	/// Frames decoded
+	pub checked: usize,
+	/// Frames that failed, in index order
+	pub failures: Vec<FrameFailure>,
+}
+
+impl FrameVerification {
+	/// Whether every checked frame passed.
+	#[must_use]
+	pub fn is_ok(&self) -> bool {
+		self.failures.is_empty()
+	}
+}
+
+// ============================================================================
+// Verification
+// ============================================================================
+
+/// Decode one frame and check it.
+///
+/// `expected` is the stream's width and height; `None` (or a zero
+/// dimension, as `FFprobe` reports for unknown sizes) skips the dimension
+/// check.
+///
+/// # Errors
+///
+/// Returns the [`FrameDefect`] found.
+pub fn verify_frame(
+	path: impl AsRef<Path>,
+	expected: Option<(u32, u32)>,
+	allow_rotated: bool,
+) -> std::result::Result<(u32, u32), FrameDefect> {
+	let path = path.as_ref();
+
+	let len = std::fs::metadata(path)
+		.map_err(|e| FrameDefect::Unreadable(e.to_string()))?
+		.len();
+	if len == 0 {
+		return Err(FrameDefect::Empty);
+	}
+
+	let image = image::open(path).map_err(|e| FrameDefect::Unreadable(e.to_string()))?;
+	check_image(&image, expected, allow_rotated)
+}
+
+/// Decode one frame held in memory and check it, as [`verify_frame`] does
+/// for a file.
+fn verify_frame_data(
+	data: &[u8],
+	expected: Option<(u32, u32)>,
+	allow_rotated: bool,
+) -> std::result::Result<(u32, u32), FrameDefect> {
+	if data.is_empty() {
+		return Err(FrameDefect::Empty);
+	}
+	let image =
+		image::load_from_memory(data).map_err(|e| FrameDefect::Unreadable(e.to_string()))?;
+	check_image(&image, expected, allow_rotated)
+}
+
+fn check_image(
+	image: &image::DynamicImage,
+	expected: Option<(u32, u32)>,
+	allow_rotated: bool,
+) -> std::result::Result<(u32, u32), FrameDefect> {
+	let actual = (image.width(), image.height());
+	if actual.0 == 0 || actual.1 == 0 {
+		return Err(FrameDefect::Empty);
+	}
+
+	if let Some(expected) = expected.filter(|&(w, h)| w > 0 && h > 0) {
+		let rotated = (expected.1, expected.0);
+		if actual != expected && !(allow_rotated && actual == rotated) {
+			return Err(FrameDefect::DimensionMismatch { expected, actual });
+		}
+	}
+
+	Ok(actual)
+}
+
+/// Decode a sample of `frames` and check them against `metadata`.
+///
+/// Frames carrying [`data`](ExtractedFrame::data) are decoded from memory;
+/// the rest are read from their path. Decoding is synchronous; each sampled
+/// frame is read in full.
+#[must_use]
+#[instrument(skip_all, fields(frames = frames.len(), sample = config.sample_size))]
+pub fn verify_frames(
+	frames: &[ExtractedFrame],
+	metadata: &VideoMetadata,
+	config: &FrameVerifyConfig,
+) -> FrameVerification {
+	let expected = Some((metadata.width, metadata.height));
+
+	let mut verification = FrameVerification::default();
+	for index in sample_indices(frames.len(), config.sample_size) {
+		verification.checked += 1;
+		let frame = &frames[index];
+		let checked = frame.data.as_deref().map_or_else(
+			|| verify_frame(&frame.path, expected, config.allow_rotated),
+			|data| verify_frame_data(data, expected, config.allow_rotated),
+		);
+		if let Err(defect) = checked {
+			verification.failures.push(FrameFailure { index, defect });
+		}
+	}
+
+	debug!(
+		checked = verification.checked,
+		failed = verification.failures.len(),
+		"Verified frames"
+	);
+	verification
+}
+
+/// `sample_size` evenly spaced indices into `0..len`, including both ends.
+fn sample_indices(len: usize, sample_size: usize) -> Vec<usize> {
+	if sample_size == 0 || sample_size >= len {
+		return (0..len).collect();
+	}
+	if sample_size == 1 {
+		return vec![len - 1];
+	}
+	(0..sample_size)
+		.map(|i| i * (len - 1) / (sample_size - 1))
+		.collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::path::PathBuf;
+
+	fn write_png(dir: &Path, name: &str, width: u32, height: u32) -> PathBuf {
+		let path = dir.join(name);
+		let saved = image::RgbImage::new(width, height).save(&path);
+		assert!(saved.is_ok());
+		path
+	}
+
+	#[test]
+	fn test_sample_indices() {
+		assert_eq!(sample_indices(5, 0), vec![0, 1, 2, 3, 4]);
+		assert_eq!(sample_indices(3, 8), vec![0, 1, 2]);
+		assert_eq!(sample_indices(10, 1), vec![9]);
+		assert_eq!(sample_indices(10, 4), vec![0, 3, 6, 9]);
+	}
+
+	#[test]
+	fn test_verify_frame_defects() {
+		let dir = tempfile::tempdir();
+		assert!(dir.is_ok());
+		let Ok(dir) = dir else { return };
+
+		let good = write_png(dir.path(), "good.png", 4, 2);
+		assert_eq!(verify_frame(&good, Some((4, 2)), false), Ok((4, 2)));
+		assert_eq!(verify_frame(&good, Some((2, 4)), true), Ok((4, 2)));
+		assert_eq!(
+			verify_frame(&good, Some((2, 4)), false),
+			Err(FrameDefect::DimensionMismatch {
+				expected: (2, 4),
+				actual: (4, 2),
+			})
+		);
+		assert_eq!(verify_frame(&good, Some((0, 0)), false), Ok((4, 2)));
+
+		let empty = dir.path().join("empty.png");
+		assert!(std::fs::write(&empty, []).is_ok());
+		assert_eq!(verify_frame(&empty, None, true), Err(FrameDefect::Empty));
+
+		let truncated = dir.path().join("truncated.png");
+		let bytes = std::fs::read(&good).unwrap_or_default();
+		assert!(std::fs::write(&truncated, &bytes[..bytes.len() / 2]).is_ok());
+		assert!(matches!(
+			verify_frame(&truncated, None, true),
+			Err(FrameDefect::Unreadable(_))
+		));
+	}
+
+	#[test]
+	fn test_verify_frames_reports_index() {
+		let dir = tempfile::tempdir();
+		assert!(dir.is_ok());
+		let Ok(dir) = dir else { return };
+
+		let frames: Vec<ExtractedFrame> = (0..3)
+			.map(|i| ExtractedFrame {
+				path: write_png(
+					dir.path(),
+					&format!("{i}.png"),
+					4,
+					if i == 2 { 3 } else { 2 },
+				),
+				timestamp_seconds: f64::from(i),
+				frame_number: i,
+				is_keyframe: false,
+				data: None,
+				variants: Vec::new(),
+			})
+			.collect();
+		let metadata = VideoMetadata {
+			duration_seconds: 3.0,
+			frame_rate: 1.0,
+			frame_count: 3,
+			width: 4,
+			height: 2,
+			codec: "h264".to_string(),
+			has_audio: false,
+			rotation: 0,
+		};
+
+		let verification = verify_frames(&frames, &metadata, &FrameVerifyConfig::default());
+		assert_eq!(verification.checked, 3);
+		assert!(!verification.is_ok());
+		assert_eq!(verification.failures.len(), 1);
+		assert_eq!(verification.failures[0].index, 2);
+	}
+
+	#[test]
+	fn test_verify_frames_in_memory() {
+		let mut png = Vec::new();
+		let encoded = image::DynamicImage::ImageRgb8(image::RgbImage::new(4, 2))
+			.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png);
+		assert!(encoded.is_ok());
+
+		let frames: Vec<ExtractedFrame> = [png.clone(), png[..png.len() / 2].to_vec(), Vec::new()]
+			.into_iter()
+			.zip(0..)
+			.map(|(data, i)| ExtractedFrame {
+				path: PathBuf::new(),
+				timestamp_seconds: f64::from(i),
+				frame_number: i,
+				is_keyframe: true,
+				data: Some(data),
+				variants: Vec::new(),
+			})
+			.collect();
+		let metadata = VideoMetadata {
+			duration_seconds: 3.0,
+			frame_rate: 1.0,
+			frame_count: 3,
+			width: 4,
+			height: 2,
+			codec: "h264".to_string(),
+			has_audio: false,
+			rotation: 0,
+		};
+
+		let verification = verify_frames(&frames, &metadata, &FrameVerifyConfig::default());
+		assert_eq!(verification.checked, 3);
+		assert_eq!(verification.failures.len(), 2);
+		assert!(matches!(
+			verification.failures[0],
+			FrameFailure {
+				index: 1,
+				defect: FrameDefect::Unreadable(_)
+			}
+		));
+		assert_eq!(verification.failures[1].defect, FrameDefect::Empty);
+	}
+}
@@ -6,20 +6,36 @@
 //! - No complex build dependencies
 //! - Consistent behavior across platforms
 //! - Support for all video formats `FFmpeg` supports
+//!
+//! With the `libav` feature, probing and extraction go through the linked
+//! `FFmpeg` libraries instead, and no `ffmpeg` or `ffprobe` binary is
+//! needed. The functions here keep their signatures; the CLI code stays
+//! compiled (and tested) but unused.
+
+#![cfg_attr(feature = "libav", allow(dead_code))]
 
+use std::collections::{BTreeMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 
+use futures::stream::{self, Stream};
 use serde::{Deserialize, Serialize};
-use tokio::process::Command;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader, Lines};
+use tokio::process::{Child, ChildStderr, ChildStdout, Command};
+use tokio::sync::OnceCell;
 use tracing::{debug, instrument, warn};
 
-use crate::error::{PerceptionError, Result};
+use crate::encode::{finish_frame, finish_frames};
+use crate::error::{ConfigIssue, PerceptionError, Result};
+use crate::variants::{FrameSize, FrameVariant};
 
 // ============================================================================
 // Configuration
 // ============================================================================
 
+/// `FFmpeg` thread count used by [`VideoConfig::deterministic`] extraction.
+pub const DETERMINISTIC_FFMPEG_THREADS: u32 = 1;
+
 /// Configuration for video frame extraction.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoConfig {
@@ -32,14 +48,58 @@ pub struct VideoConfig {
 	/// Time interval between frames in seconds (0 = use scene detection)
 	pub interval_seconds: f64,
 
-	/// Output image quality (1-31, lower is better, 2 is recommended)
+	/// Output image quality (1-31, lower is better, 2 is recommended).
+	/// AVIF keeps detail at much higher settings than JPEG; 8-12 suits an
+	/// archive. Lossless formats ignore it.
 	pub quality: u32,
 
 	/// Output image format
 	pub format: ImageFormat,
 
+	/// Encoder effort for AVIF and `WebP`, 1 (fastest) to 10 (smallest
+	/// files) (default: 7). `WebP` encoded in process, i.e. frames decoded
+	/// through libav and all variants, always uses one fixed effort; only
+	/// frames `FFmpeg` writes itself honor it.
+	#[serde(default = "default_effort")]
+	pub effort: u32,
+
 	/// Whether to extract keyframes only (faster, less frames)
 	pub keyframes_only: bool,
+
+	/// Name frames after the video instead of a random prefix and run
+	/// `FFmpeg` single-threaded, so repeated runs write identical files
+	/// under identical paths (default: false)
+	pub deterministic: bool,
+
+	/// Hardware decoder; one this machine lacks falls back to the CPU
+	/// (default: `None`)
+	#[serde(default)]
+	pub hwaccel: HwAccel,
+
+	/// Timeout and headers for `http(s)://` inputs
+	#[serde(default)]
+	pub network: NetworkConfig,
+
+	/// Seconds into the video to start extracting from (default: `None`,
+	/// the beginning)
+	#[serde(default)]
+	pub start_time: Option<f64>,
+
+	/// Seconds into the video to stop extracting at (default: `None`, the
+	/// end)
+	#[serde(default)]
+	pub end_time: Option<f64>,
+
+	/// Smaller sizes to also write every frame at, e.g. a thumbnail for
+	/// the UI beside the full frame (default: none)
+	#[serde(default)]
+	pub variants: Vec<FrameSize>,
+
+	/// Turn frames upright by the video's display matrix, as phones record
+	/// portrait video sideways with a rotation to show it by (default:
+	/// true); see [`VideoMetadata::rotation`]
+	#[serde(default = "default_auto_rotate")]
+	pub auto_rotate: bool,
 }
 
 impl Default for VideoConfig {
@@ -50,11 +110,180 @@ impl Default for VideoConfig {
 			interval_seconds: 1.0,
 			quality: 2,
 			format: ImageFormat::Jpeg,
+			effort: default_effort(),
 			keyframes_only: false,
+			deterministic: false,
+			hwaccel: HwAccel::None,
+			network: NetworkConfig::default(),
+			start_time: None,
+			end_time: None,
+			variants: Vec::new(),
+			auto_rotate: true,
+		}
+	}
+}
+
+impl VideoConfig {
+	/// Every out-of-range field.
+	///
+	/// A negative or `NaN` interval would otherwise fall back to one frame
+	/// per second, and an out-of-range quality fails only inside `FFmpeg`.
+	#[must_use]
+	pub fn validate(&self) -> Vec<ConfigIssue> {
+		let mut issues = Vec::new();
+
+		if self.output_dir.as_os_str().is_empty() {
+			issues.push(ConfigIssue::new("output_dir", "must not be empty"));
+		}
+		if !(self.interval_seconds.is_finite() && self.interval_seconds >= 0.0) {
+			issues.push(ConfigIssue::new(
+				"interval_seconds",
+				format!(
+					"must be finite and non-negative (0 = default spacing), got {}",
+					self.interval_seconds
+				),
+			));
+		}
+		if !(1..=31).contains(&self.quality) {
+			issues.push(ConfigIssue::new(
+				"quality",
+				format!("must be in 1..=31 (2 is recommended), got {}", self.quality),
+			));
+		}
+		if !(1..=10).contains(&self.effort) {
+			issues.push(ConfigIssue::new(
+				"effort",
+				format!("must be in 1..=10, got {}", self.effort),
+			));
+		}
+		if self.format == ImageFormat::Avif && !cfg!(feature = "avif") {
+			issues.push(ConfigIssue::new(
+				"format",
+				"AVIF output requires the `avif` feature",
+			));
+		}
+		if let Some(start) = self.start_time.filter(|t| !(t.is_finite() && *t >= 0.0)) {
+			issues.push(ConfigIssue::new(
+				"start_time",
+				format!("must be finite and non-negative, got {start}"),
+			));
 		}
+		if let Some(end) = self.end_time {
+			let start = self.start_time.unwrap_or(0.0);
+			if !(end.is_finite() && end > start) {
+				issues.push(ConfigIssue::new(
+					"end_time",
+					format!("must be finite and after start_time ({start}), got {end}"),
+				));
+			}
+		}
+		issues.extend(
+			self.network
+				.validate()
+				.into_iter()
+				.map(|issue| issue.within("network")),
+		);
+		for (i, size) in self.variants.iter().enumerate() {
+			let parent = format!("variants[{i}]");
+			issues.extend(
+				size.validate()
+					.into_iter()
+					.map(|issue| issue.within(&parent)),
+			);
+			// Variants are named into file names, so a repeat overwrites
+			if let Some(first) = self.variants[..i].iter().position(|s| s.name == size.name) {
+				issues.push(ConfigIssue::new(
+					format!("{parent}.name"),
+					format!("duplicates variants[{first}].name {:?}", size.name),
+				));
+			}
+		}
+
+		issues
+	}
+
+	/// The `(start, end)` seconds to extract from a video lasting
+	/// `duration_seconds`, clamped to the video; pass `f64::INFINITY` when
+	/// the duration is unknown.
+	#[must_use]
+	pub fn time_range(&self, duration_seconds: f64) -> (f64, f64) {
+		let start = self.start_time.map_or(0.0, |start| start.max(0.0));
+		let end = self
+			.end_time
+			.map_or(duration_seconds, |end| end.min(duration_seconds));
+		(start, end.max(start))
+	}
+}
+
+/// Network settings for `http(s)://` inputs, including HLS playlists and
+/// the segments they list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+	/// Seconds a connection may stall before `FFmpeg` gives up, 0 to wait
+	/// forever (default: 30)
+	pub timeout_seconds: f64,
+
+	/// Extra HTTP headers sent with every request, e.g. `Authorization`
+	/// (default: none)
+	pub headers: BTreeMap<String, String>,
+}
+
+impl Default for NetworkConfig {
+	fn default() -> Self {
+		Self {
+			timeout_seconds: 30.0,
+			headers: BTreeMap::new(),
+		}
+	}
+}
+
+impl NetworkConfig {
+	/// Every out-of-range field.
+	///
+	/// Headers are joined into one CRLF-separated block for `FFmpeg`, so a
+	/// line break in a name or value would smuggle in another header.
+	#[must_use]
+	pub fn validate(&self) -> Vec<ConfigIssue> {
+		let mut issues = Vec::new();
+
+		if !(self.timeout_seconds.is_finite() && self.timeout_seconds >= 0.0) {
+			issues.push(ConfigIssue::new(
+				"timeout_seconds",
+				format!(
+					"must be finite and non-negative (0 = no timeout), got {}",
+					self.timeout_seconds
+				),
+			));
+		}
+		for (name, value) in &self.headers {
+			let bad_name = name.is_empty()
+				|| name
+					.chars()
+					.any(|c| c == ':' || c.is_whitespace() || c.is_control());
+			if bad_name || value.contains(['\r', '\n']) {
+				issues.push(ConfigIssue::new(
+					"headers",
+					format!("{name:?} must be a header name with a single-line value"),
+				));
+			}
+		}
+
+		issues
 	}
 }
 
+/// [`VideoConfig::effort`] when unset: AVIF speed 4 and `WebP` compression
+/// level 4, each encoder's own default.
+const fn default_effort() -> u32 {
+	7
+}
+
+/// [`VideoConfig::auto_rotate`] when unset.
+const fn default_auto_rotate() -> bool {
+	true
+}
+
 /// Output image format.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum ImageFormat {
@@ -63,6 +292,11 @@ pub enum ImageFormat {
 	Jpeg,
 	/// PNG format (larger files, lossless)
 	Png,
+	/// AVIF format (smallest files, lossy, slow to encode); requires the
+	/// `avif` feature
+	Avif,
+	/// Lossless `WebP` (exact like PNG, in noticeably less space)
+	WebpLossless,
 }
 
 impl ImageFormat {
@@ -72,6 +306,8 @@ impl ImageFormat {
 		match self {
 			Self::Jpeg => "jpg",
 			Self::Png => "png",
+			Self::Avif => "avif",
+			Self::WebpLossless => "webp",
 		}
 	}
 
@@ -81,6 +317,56 @@ impl ImageFormat {
 		match self {
 			Self::Jpeg => "mjpeg",
 			Self::Png => "png",
+			Self::Avif => "libaom-av1",
+			Self::WebpLossless => "libwebp",
+		}
+	}
+
+	/// The format `FFmpeg` writes frames in when extracting to this one.
+	///
+	/// `FFmpeg`'s image muxers cannot write one AVIF file per frame, so
+	/// AVIF frames come out as PNG and are re-encoded in process.
+	#[must_use]
+	pub(crate) const fn ffmpeg_format(self) -> Self {
+		match self {
+			Self::Avif => Self::Png,
+			format => format,
+		}
+	}
+}
+
+/// Hardware decoder for `FFmpeg` to use.
+///
+/// Decoding dominates extraction time for high-resolution video; a 4K
+/// screen recording decodes several times faster on a GPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum HwAccel {
+	/// Decode on the CPU
+	#[default]
+	None,
+	/// Whichever hardware decoder `FFmpeg` finds first, else the CPU
+	Auto,
+	/// NVIDIA NVDEC
+	Nvdec,
+	/// VA-API (Intel and AMD on Linux)
+	Vaapi,
+	/// Apple `VideoToolbox`
+	VideoToolbox,
+	/// Intel Quick Sync Video
+	Qsv,
+}
+
+impl HwAccel {
+	/// The `-hwaccel` method name (`None` for CPU decoding).
+	#[must_use]
+	pub const fn ffmpeg_name(&self) -> Option<&'static str> {
+		match self {
+			Self::None => None,
+			Self::Auto => Some("auto"),
+			Self::Nvdec => Some("cuda"),
+			Self::Vaapi => Some("vaapi"),
+			Self::VideoToolbox => Some("videotoolbox"),
+			Self::Qsv => Some("qsv"),
 		}
 	}
 }
@@ -112,6 +398,24 @@ pub struct VideoMetadata {
 
 	/// Whether the video has audio
 	pub has_audio: bool,
+
+	/// Clockwise rotation in degrees (0, 90, 180 or 270) that the display
+	/// matrix shows frames at; `width` and `height` are before rotation.
+	/// Frames are extracted rotated unless [`VideoConfig::auto_rotate`] is
+	/// off.
+	#[serde(default)]
+	pub rotation: u32,
+}
+
+/// Clockwise quarter turns, in degrees, for the counterclockwise display
+/// matrix angle `FFmpeg` reports (`av_display_rotation_get`).
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn display_rotation(counterclockwise: f64) -> u32 {
+	if !counterclockwise.is_finite() {
+		return 0;
+	}
+	let quarter_turns = (-counterclockwise / 90.0).round() as i64;
+	u32::try_from(quarter_turns.rem_euclid(4) * 90).unwrap_or(0)
 }
 
 /// Raw `FFprobe` stream data.
@@ -130,6 +434,15 @@ struct FfprobeStream {
 	height: Option<u32>,
 	#[serde(default)]
 	codec_name: Option<String>,
+	#[serde(default)]
+	side_data_list: Vec<FfprobeSideData>,
+}
+
+/// Raw `FFprobe` stream side data; only the display matrix has a rotation.
+#[derive(Debug, Deserialize)]
+struct FfprobeSideData {
+	#[serde(default)]
+	rotation: Option<f64>,
 }
 
 /// Raw `FFprobe` format data.
@@ -165,6 +478,15 @@ pub struct ExtractedFrame {
 
 	/// Whether this is a keyframe
 	pub is_keyframe: bool,
+
+	/// Encoded image, for frames extracted by [`extract_frames_to_memory`]
+	/// (`None` = the image is at `path`)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub data: Option<Vec<u8>>,
+
+	/// The frame at each of [`VideoConfig::variants`], in that order
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub variants: Vec<FrameVariant>,
 }
 
 // ============================================================================
@@ -173,32 +495,47 @@ pub struct ExtractedFrame {
 
 /// Check if `FFmpeg` is available in PATH.
 ///
+/// With the `libav` feature, checks that the linked libraries initialize.
+///
 /// # Errors
 ///
 /// Returns `FfmpegNotFound` if `FFmpeg` is not installed or not in PATH.
 #[instrument]
 pub async fn check_ffmpeg() -> Result<()> {
-	let output = Command::new("ffmpeg")
-		.arg("-version")
-		.stdout(Stdio::null())
-		.stderr(Stdio::null())
-		.status()
-		.await;
+	#[cfg(feature = "libav")]
+	{
+		crate::libav::init().map_err(|_| PerceptionError::FfmpegNotFound)
+	}
 
-	match output {
-		Ok(status) if status.success() => Ok(()),
-		_ => Err(PerceptionError::FfmpegNotFound),
+	#[cfg(not(feature = "libav"))]
+	{
+		check_binary("ffmpeg", PerceptionError::FfmpegNotFound).await
 	}
 }
 
 /// Check if `FFprobe` is available in PATH.
 ///
+/// With the `libav` feature, checks that the linked libraries initialize.
+///
 /// # Errors
 ///
 /// Returns `FfprobeNotFound` if `FFprobe` is not installed or not in PATH.
 #[instrument]
 pub async fn check_ffprobe() -> Result<()> {
-	let output = Command::new("ffprobe")
+	#[cfg(feature = "libav")]
+	{
+		crate::libav::init().map_err(|_| PerceptionError::FfprobeNotFound)
+	}
+
+	#[cfg(not(feature = "libav"))]
+	{
+		check_binary("ffprobe", PerceptionError::FfprobeNotFound).await
+	}
+}
+
+/// Run `{binary} -version`, returning `missing` if it does not succeed.
+async fn check_binary(binary: &str, missing: PerceptionError) -> Result<()> {
+	let output = Command::new(binary)
 		.arg("-version")
 		.stdout(Stdio::null())
 		.stderr(Stdio::null())
@@ -207,7 +544,186 @@ pub async fn check_ffprobe() -> Result<()> {
 
 	match output {
 		Ok(status) if status.success() => Ok(()),
-		_ => Err(PerceptionError::FfprobeNotFound),
+		_ => Err(missing),
+	}
+}
+
+// ============================================================================
+// Hardware Acceleration
+// ============================================================================
+
+/// Hardware acceleration methods the installed `FFmpeg` supports, as listed
+/// by `ffmpeg -hwaccels`.
+///
+/// Probed once per process; empty if `FFmpeg` is missing.
+pub async fn available_hwaccels() -> &'static [String] {
+	static HWACCELS: OnceCell<Vec<String>> = OnceCell::const_new();
+	HWACCELS
+		.get_or_init(|| async {
+			match Command::new("ffmpeg")
+				.args(["-hide_banner", "-hwaccels"])
+				.stdin(Stdio::null())
+				.output()
+				.await
+			{
+				Ok(output) if output.status.success() => {
+					parse_hwaccels(&String::from_utf8_lossy(&output.stdout))
+				}
+				_ => Vec::new(),
+			}
+		})
+		.await
+}
+
+/// Method names from `ffmpeg -hwaccels` output.
+fn parse_hwaccels(listing: &str) -> Vec<String> {
+	listing
+		.lines()
+		.skip_while(|line| !line.starts_with("Hardware acceleration methods"))
+		.skip(1)
+		.map(str::trim)
+		.filter(|line| !line.is_empty())
+		.map(str::to_string)
+		.collect()
+}
+
+/// The `-hwaccel` method to request for `hwaccel`, or `None` to decode on
+/// the CPU because `FFmpeg` lacks the method.
+async fn resolve_hwaccel(hwaccel: HwAccel) -> Option<&'static str> {
+	let name = hwaccel.ffmpeg_name()?;
+	// FFmpeg itself falls back to the CPU when `auto` finds nothing
+	if hwaccel == HwAccel::Auto || available_hwaccels().await.iter().any(|m| m == name) {
+		Some(name)
+	} else {
+		warn!(
+			?hwaccel,
+			"Hardware decoder not supported by FFmpeg, decoding on CPU"
+		);
+		None
+	}
+}
+
+/// `-hwaccel` input arguments.
+fn hwaccel_args(hwaccel: Option<&str>) -> Vec<String> {
+	hwaccel.map_or_else(Vec::new, |name| {
+		vec!["-hwaccel".to_string(), name.to_string()]
+	})
+}
+
+// ============================================================================
+// Input Options
+// ============================================================================
+
+/// Protocols an HLS playlist read from disk may pull segments over.
+/// `FFmpeg` otherwise limits a local playlist to local files.
+const PLAYLIST_PROTOCOLS: &str = "file,http,https,tcp,tls,crypto,data";
+
+/// Whether `video_path` is an `http://` or `https://` URL rather than a
+/// local file.
+///
+/// URLs are passed to `FFmpeg` as they are, so everything that takes a
+/// video path also takes a URL, including HLS playlists (`.m3u8`).
+#[must_use]
+pub fn is_remote(video_path: impl AsRef<Path>) -> bool {
+	video_path.as_ref().to_str().is_some_and(|path| {
+		["http://", "https://"].iter().any(|scheme| {
+			path.get(..scheme.len())
+				.is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+		})
+	})
+}
+
+/// Whether `video_path` is an HLS playlist, ignoring any URL query.
+fn is_playlist(video_path: &Path) -> bool {
+	video_path.to_str().is_some_and(|path| {
+		let path = path.split(['?', '#']).next().unwrap_or(path);
+		Path::new(path)
+			.extension()
+			.is_some_and(|ext| ext.eq_ignore_ascii_case("m3u8"))
+	})
+}
+
+/// `VideoNotFound` unless `video_path` is a URL or an existing file.
+pub(crate) fn check_input(video_path: &Path) -> Result<()> {
+	if is_remote(video_path) || video_path.exists() {
+		Ok(())
+	} else {
+		Err(PerceptionError::VideoNotFound(video_path.to_path_buf()))
+	}
+}
+
+/// `FFmpeg` input options for `video_path`, as name/value pairs.
+///
+/// A URL gets the I/O timeout and headers, which the HLS demuxer carries
+/// over to segment requests; a local playlist gets a protocol whitelist
+/// so it can list remote segments.
+///
+/// `network` is validated here, where every backend and entry point
+/// passes, so a header with a line break never reaches `FFmpeg`.
+pub(crate) fn input_options(
+	video_path: &Path,
+	network: &NetworkConfig,
+) -> Result<Vec<(&'static str, String)>> {
+	if let Some(issue) = network.validate().into_iter().next() {
+		return Err(PerceptionError::InvalidConfig(issue.within("network")));
+	}
+	let mut options = Vec::new();
+	if is_remote(video_path) {
+		if network.timeout_seconds > 0.0 {
+			#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+			let micros = (network.timeout_seconds * 1_000_000.0).round() as u64;
+			options.push(("rw_timeout", micros.to_string()));
+		}
+		if !network.headers.is_empty() {
+			let headers: String = network
+				.headers
+				.iter()
+				.flat_map(|(name, value)| [name.as_str(), ": ", value.as_str(), "\r\n"])
+				.collect();
+			options.push(("headers", headers));
+		}
+	} else if is_playlist(video_path) {
+		options.push(("protocol_whitelist", PLAYLIST_PROTOCOLS.to_string()));
+	}
+	Ok(options)
+}
+
+/// [`input_options`] as `FFmpeg` arguments, to go before `-i`.
+pub(crate) fn input_args(video_path: &Path, network: &NetworkConfig) -> Result<Vec<String>> {
+	Ok(input_options(video_path, network)?
+		.into_iter()
+		.flat_map(|(name, value)| [format!("-{name}"), value])
+		.collect())
+}
+
+/// `-ss`/`-t` arguments limiting `FFmpeg` to [`VideoConfig::time_range`],
+/// to go before `-i`.
+///
+/// Seeking on the input skips decoding what comes before the range, but
+/// restarts `FFmpeg`'s timestamps at zero; add the range start back to
+/// any time `FFmpeg` reports.
+pub(crate) fn range_args(config: &VideoConfig) -> Vec<String> {
+	let (start, end) = config.time_range(f64::INFINITY);
+	let mut args = Vec::new();
+	if start > 0.0 {
+		args.extend(["-ss".to_string(), format!("{start:.3}")]);
+	}
+	if end.is_finite() {
+		args.extend(["-t".to_string(), format!("{:.3}", end - start)]);
+	}
+	args
+}
+
+/// `-noautorotate` if [`VideoConfig::auto_rotate`] is off, to go before
+/// `-i`.
+///
+/// The `ffmpeg` binary turns frames upright by the display matrix itself,
+/// adding `transpose` (or flips, for 180°) ahead of any other filter.
+fn rotate_args(config: &VideoConfig) -> Vec<String> {
+	if config.auto_rotate {
+		Vec::new()
+	} else {
+		vec!["-noautorotate".to_string()]
 	}
 }
 
@@ -215,18 +731,42 @@ pub async fn check_ffprobe() -> Result<()> {
 // Video Metadata Extraction
 // ============================================================================
 
-/// Get metadata about a video file.
+/// Get metadata about a video file or URL.
 ///
 /// # Errors
 ///
 /// Returns an error if the video file is not found, invalid, or `FFprobe` fails.
 #[instrument(skip_all, fields(video = %video_path.as_ref().display()))]
 pub async fn get_video_metadata(video_path: impl AsRef<Path>) -> Result<VideoMetadata> {
+	get_video_metadata_with(video_path, &NetworkConfig::default()).await
+}
+
+/// [`get_video_metadata`] with the timeout and headers for a URL.
+///
+/// # Errors
+///
+/// Returns an error if the video file is not found, invalid, or `FFprobe` fails.
+#[instrument(skip_all, fields(video = %video_path.as_ref().display()))]
+pub async fn get_video_metadata_with(
+	video_path: impl AsRef<Path>,
+	network: &NetworkConfig,
+) -> Result<VideoMetadata> {
 	let video_path = video_path.as_ref();
 
-	if !video_path.exists() {
-		return Err(PerceptionError::VideoNotFound(video_path.to_path_buf()));
+	#[cfg(feature = "libav")]
+	{
+		crate::libav::metadata(video_path, network).await
+	}
+
+	#[cfg(not(feature = "libav"))]
+	{
+		ffprobe_metadata(video_path, network).await
 	}
+}
+
+/// [`get_video_metadata_with`] through `FFprobe`.
+async fn ffprobe_metadata(video_path: &Path, network: &NetworkConfig) -> Result<VideoMetadata> {
+	check_input(video_path)?;
 
 	let output = Command::new("ffprobe")
 		.args([
@@ -237,10 +777,13 @@ pub async fn get_video_metadata(video_path: impl AsRef<Path>) -> Result<VideoMet
 			"-show_entries",
 			"stream=duration,r_frame_rate,nb_frames,width,height,codec_name,codec_type",
 			"-show_entries",
+			"stream_side_data=rotation",
+			"-show_entries",
 			"format=duration",
 			"-of",
 			"json",
 		])
+		.args(input_args(video_path, network)?)
 		.arg(video_path)
 		.output()
 		.await
@@ -321,6 +864,11 @@ pub async fn get_video_metadata(video_path: impl AsRef<Path>) -> Result<VideoMet
 			.clone()
 			.unwrap_or_else(|| "unknown".to_string()),
 		has_audio,
+		rotation: video_stream
+			.side_data_list
+			.iter()
+			.find_map(|side_data| side_data.rotation)
+			.map_or(0, display_rotation),
 	})
 }
 
@@ -330,6 +878,8 @@ pub async fn get_video_metadata(video_path: impl AsRef<Path>) -> Result<VideoMet
 
 /// Extract a single frame at a specific timestamp.
 ///
+/// A URL is read with the default [`NetworkConfig`].
+///
 /// # Errors
 ///
 /// Returns an error if the video is not found or frame extraction fails.
@@ -340,12 +890,51 @@ pub async fn extract_frame_at(
 	output_path: impl AsRef<Path>,
 	quality: u32,
 ) -> Result<ExtractedFrame> {
-	let video_path = video_path.as_ref();
-	let output_path = output_path.as_ref();
+	#[cfg(feature = "libav")]
+	{
+		crate::libav::extract_frame_at(
+			video_path.as_ref(),
+			timestamp_seconds,
+			output_path.as_ref(),
+			quality,
+		)
+		.await
+	}
 
-	if !video_path.exists() {
-		return Err(PerceptionError::VideoNotFound(video_path.to_path_buf()));
+	#[cfg(not(feature = "libav"))]
+	{
+		extract_frame_with(
+			video_path.as_ref(),
+			timestamp_seconds,
+			output_path.as_ref(),
+			&["-q:v".to_string(), quality.to_string()],
+			None,
+			None,
+			&input_args(video_path.as_ref(), &NetworkConfig::default())?,
+		)
+		.await
 	}
+}
+
+/// [`extract_frame_at`] with `FFmpeg` encoder arguments, an optional
+/// thread count for decoding and encoding (`None` = `FFmpeg`'s choice),
+/// `-hwaccel` method (`None` = CPU), and input options such as
+/// [`input_args`].
+async fn extract_frame_with(
+	video_path: &Path,
+	timestamp_seconds: f64,
+	output_path: &Path,
+	encoder: &[String],
+	threads: Option<u32>,
+	hwaccel: Option<&str>,
+	input: &[String],
+) -> Result<ExtractedFrame> {
+	let threads = threads.map(|t| t.to_string());
+	let thread_args = threads
+		.as_deref()
+		.map_or_else(Vec::new, |t| vec!["-threads", t]);
+
+	check_input(video_path)?;
 
 	// Ensure output directory exists
 	if let Some(parent) = output_path.parent() {
@@ -353,15 +942,16 @@ pub async fn extract_frame_at(
 	}
 
 	let output = Command::new("ffmpeg")
-		.args(["-ss", &format!("{timestamp_seconds:.3}"), "-i"])
+		.args(&thread_args)
+		.args(hwaccel_args(hwaccel))
+		.args(["-ss", &format!("{timestamp_seconds:.3}")])
+		.args(input)
+		.arg("-i")
 		.arg(video_path)
-		.args([
-			"-vframes",
-			"1",
-			"-q:v",
-			&quality.to_string(),
-			"-y", // Overwrite output
-		])
+		.args(["-vframes", "1"])
+		.args(encoder)
+		.arg("-y") // Overwrite output
+		.args(&thread_args)
 		.arg(output_path)
 		.output()
 		.await
@@ -388,11 +978,45 @@ pub async fn extract_frame_at(
 		timestamp_seconds,
 		frame_number: 0,
 		is_keyframe: false,
+		data: None,
+		variants: Vec::new(),
 	})
 }
 
+/// `FFmpeg` arguments encoding frames in `config`'s
+/// [`ffmpeg_format`](ImageFormat::ffmpeg_format) and quality.
+///
+/// libwebp reads `-q:v` as its own 0-100 quality, which for lossless
+/// output means effort, so `WebP` is given [`VideoConfig::effort`] as a
+/// compression level instead.
+fn encoder_args(config: &VideoConfig) -> Vec<String> {
+	let format = config.format.ffmpeg_format();
+	let mut args = vec!["-c:v".to_string(), format.codec().to_string()];
+	if format == ImageFormat::WebpLossless {
+		args.extend([
+			"-lossless".to_string(),
+			"1".to_string(),
+			"-compression_level".to_string(),
+			webp_compression_level(config.effort).to_string(),
+		]);
+	} else {
+		args.extend(["-q:v".to_string(), config.quality.to_string()]);
+	}
+	args
+}
+
+/// [`VideoConfig::effort`] (1-10) on libwebp's compression levels (0-6).
+fn webp_compression_level(effort: u32) -> u32 {
+	(effort.clamp(1, 10) - 1) * 6 / 9
+}
+
 /// Extract frames at regular intervals.
 ///
+/// `video_path` may be an `http(s)://` URL or HLS playlist, read with
+/// [`VideoConfig::network`]; frames stream from the server without the
+/// video being downloaded first. With [`VideoConfig::start_time`] or
+/// [`VideoConfig::end_time`] set, only that part of the video is decoded.
+///
 /// # Errors
 ///
 /// Returns an error if the video is not found or frame extraction fails.
@@ -403,24 +1027,54 @@ pub async fn extract_frames(
 ) -> Result<Vec<ExtractedFrame>> {
 	let video_path = video_path.as_ref();
 
-	if !video_path.exists() {
-		return Err(PerceptionError::VideoNotFound(video_path.to_path_buf()));
-	}
-
-	// Get video metadata
-	let metadata = get_video_metadata(video_path).await?;
-	debug!(?metadata, "Got video metadata");
+	check_input(video_path)?;
 
 	// Ensure output directory exists
 	tokio::fs::create_dir_all(&config.output_dir).await?;
 
 	// Generate unique prefix for this extraction
-	let prefix = uuid::Uuid::new_v4();
+	let prefix = if config.deterministic {
+		deterministic_prefix(video_path)
+	} else {
+		uuid::Uuid::new_v4().to_string()
+	};
+
+	#[cfg(feature = "libav")]
+	let frames = crate::libav::extract_frames(video_path, config, &prefix).await?;
+	#[cfg(not(feature = "libav"))]
+	let frames = extract_frames_cli(video_path, config, &prefix).await?;
+
+	debug!(count = frames.len(), "Extracted frames");
+	Ok(frames)
+}
+
+/// [`extract_frames`] through `FFmpeg` processes, writing
+/// `{prefix}-{n:05}.{ext}` into `output_dir`.
+async fn extract_frames_cli(
+	video_path: &Path,
+	config: &VideoConfig,
+	prefix: &str,
+) -> Result<Vec<ExtractedFrame>> {
+	// Get video metadata
+	let metadata = get_video_metadata_with(video_path, &config.network).await?;
+	debug!(?metadata, "Got video metadata");
+
+	let threads = config.deterministic.then_some(DETERMINISTIC_FFMPEG_THREADS);
+	let mut hwaccel = resolve_hwaccel(config.hwaccel).await;
 
 	#[allow(clippy::if_not_else)]
 	let frames = if config.keyframes_only {
 		// Extract keyframes only using select filter
-		extract_keyframes_internal(video_path, config, &prefix, &metadata).await?
+		match extract_keyframes_internal(video_path, config, prefix, threads, hwaccel, &metadata)
+			.await
+		{
+			Err(e) if hwaccel.is_some() => {
+				warn!(?e, "Hardware decoding failed, retrying on CPU");
+				extract_keyframes_internal(video_path, config, prefix, threads, None, &metadata)
+					.await?
+			}
+			result => result?,
+		}
 	} else {
 		// Extract at regular intervals
 		let interval = if config.interval_seconds > 0.0 {
@@ -429,22 +1083,53 @@ pub async fn extract_frames(
 			1.0
 		};
 
-		let mut timestamp = 0.0;
+		let (start, end) = config.time_range(metadata.duration_seconds);
+		let encoder = encoder_args(config);
+		let input = [
+			input_args(video_path, &config.network)?,
+			rotate_args(config),
+		]
+		.concat();
+		let mut timestamp = start;
 		let mut frame_number = 0u32;
 		let mut extracted = Vec::new();
 
 		#[allow(clippy::while_float)]
-		while timestamp < metadata.duration_seconds {
+		while timestamp < end {
 			if config.max_frames > 0 && extracted.len() >= config.max_frames {
 				break;
 			}
 
 			let output_path = config.output_dir.join(format!(
 				"{prefix}-{frame_number:05}.{}",
-				config.format.extension()
+				config.format.ffmpeg_format().extension()
 			));
 
-			match extract_frame_at(video_path, timestamp, &output_path, config.quality).await {
+			let mut result = extract_frame_with(
+				video_path,
+				timestamp,
+				&output_path,
+				&encoder,
+				threads,
+				hwaccel,
+				&input,
+			)
+			.await;
+			if result.is_err() && hwaccel.is_some() {
+				warn!(timestamp, "Hardware decoding failed, falling back to CPU");
+				hwaccel = None;
+				result = extract_frame_with(
+					video_path,
+					timestamp,
+					&output_path,
+					&encoder,
+					threads,
+					None,
+					&input,
+				)
+				.await;
+			}
+			match result {
 				Ok(mut frame) => {
 					frame.frame_number = frame_number;
 					extracted.push(frame);
@@ -460,34 +1145,70 @@ pub async fn extract_frames(
 		extracted
 	};
 
-	debug!(count = frames.len(), "Extracted frames");
-	Ok(frames)
+	finish_frames(frames, config).await
+}
+
+/// Frame file prefix for deterministic extraction: the video's file stem,
+/// reduced to ASCII letters, digits, `-` and `_`.
+///
+/// Two videos with the same stem extracted into one directory overwrite
+/// each other's frames.
+fn deterministic_prefix(video_path: &Path) -> String {
+	let stem: String = video_path
+		.file_stem()
+		.map(|s| s.to_string_lossy())
+		.unwrap_or_default()
+		.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+				c
+			} else {
+				'_'
+			}
+		})
+		.collect();
+	if stem.is_empty() {
+		"video".to_string()
+	} else {
+		stem
+	}
 }
 
 /// Internal function to extract keyframes.
 async fn extract_keyframes_internal(
 	video_path: &Path,
 	config: &VideoConfig,
-	prefix: &uuid::Uuid,
+	prefix: &str,
+	threads: Option<u32>,
+	hwaccel: Option<&str>,
 	metadata: &VideoMetadata,
 ) -> Result<Vec<ExtractedFrame>> {
 	// Use FFmpeg's select filter to extract keyframes
 	let output_pattern = config.output_dir.join(format!(
 		"{}-keyframe-%05d.{}",
 		prefix,
-		config.format.extension()
+		config.format.ffmpeg_format().extension()
 	));
 
-	let mut args = vec![
+	let thread_args: Vec<String> = threads
+		.map(|t| vec!["-threads".to_string(), t.to_string()])
+		.unwrap_or_default();
+
+	let mut args = thread_args.clone();
+	args.extend(hwaccel_args(hwaccel));
+	args.extend(input_args(video_path, &config.network)?);
+	args.extend(rotate_args(config));
+	args.extend(range_args(config));
+	args.extend([
 		"-i".to_string(),
 		video_path.display().to_string(),
 		"-vf".to_string(),
 		"select='eq(pict_type\\,I)'".to_string(),
 		"-vsync".to_string(),
 		"vfr".to_string(),
-		"-q:v".to_string(),
-		config.quality.to_string(),
-	];
+	]);
+	args.extend(encoder_args(config));
+	args.extend(thread_args);
 
 	// Limit frames if configured
 	if config.max_frames > 0 {
@@ -546,6 +1267,8 @@ async fn extract_keyframes_internal(
 						timestamp_seconds: timestamp,
 						frame_number,
 						is_keyframe: true,
+						data: None,
+						variants: Vec::new(),
 					});
 				}
 			}
@@ -557,11 +1280,12 @@ async fn extract_keyframes_internal(
 
 	// Update timestamps based on actual count
 	let count = frames.len();
+	let (start, end) = config.time_range(metadata.duration_seconds);
 	#[allow(clippy::cast_precision_loss)]
-	if count > 0 && metadata.duration_seconds > 0.0 {
-		let interval = metadata.duration_seconds / count as f64;
+	if count > 0 && end > start {
+		let interval = (end - start) / count as f64;
 		for (i, frame) in frames.iter_mut().enumerate() {
-			frame.timestamp_seconds = i as f64 * interval;
+			frame.timestamp_seconds = (i as f64).mul_add(interval, start);
 		}
 	}
 
@@ -569,27 +1293,852 @@ async fn extract_keyframes_internal(
 }
 
 // ============================================================================
-// Tests
+// Batch Extraction
 // ============================================================================
 
-#[cfg(test)]
-mod tests {
-	use super::*;
+/// Extract the frame at each of `timestamps` in one pass.
+///
+/// Where calling [`extract_frame_at`] for each time starts `FFmpeg` and
+/// seeks once per frame, this decodes the video once, selecting the first
+/// frame at or after each requested time. Frames are written to
+/// `output_dir` in `format` and `quality` as by [`extract_frames`]; the
+/// interval, range and `max_frames` settings do not apply.
+///
+/// Returns one frame per requested time, in the order requested, with
+/// `timestamp_seconds` set to the time of the frame actually hit. Times
+/// that land on the same frame share its file.
+///
+/// # Errors
+///
+/// Returns an error if the video is not found, `FFmpeg` fails, or a time
+/// is negative, not finite, or past the last frame.
+#[instrument(skip_all, fields(video = %video_path.as_ref().display(), count = timestamps.len()))]
+pub async fn extract_frames_at(
+	video_path: impl AsRef<Path>,
+	timestamps: &[f64],
+	config: &VideoConfig,
+) -> Result<Vec<ExtractedFrame>> {
+	let video_path = video_path.as_ref();
 
-	#[tokio::test]
-	async fn test_check_ffmpeg() {
-		// This test will pass on systems with FFmpeg installed
-		let result = check_ffmpeg().await;
-		// We just check it doesn't panic; actual availability depends on the system
-		println!("FFmpeg available: {}", result.is_ok());
+	if let Some(&timestamp) = timestamps.iter().find(|t| !(t.is_finite() && **t >= 0.0)) {
+		return Err(PerceptionError::FrameExtractionFailed {
+			timestamp,
+			reason: "timestamp must be finite and non-negative".to_string(),
+		});
+	}
+	check_input(video_path)?;
+	if timestamps.is_empty() {
+		return Ok(Vec::new());
 	}
 
-	#[test]
-	fn test_image_format() {
-		assert_eq!(ImageFormat::Jpeg.extension(), "jpg");
-		assert_eq!(ImageFormat::Png.extension(), "png");
-		assert_eq!(ImageFormat::Jpeg.codec(), "mjpeg");
+	tokio::fs::create_dir_all(&config.output_dir).await?;
+	let prefix = if config.deterministic {
+		deterministic_prefix(video_path)
+	} else {
+		uuid::Uuid::new_v4().to_string()
+	};
+
+	let mut targets = timestamps.to_vec();
+	targets.sort_by(f64::total_cmp);
+	targets.dedup();
+
+	#[cfg(feature = "libav")]
+	let hits = crate::libav::extract_frames_at(video_path, &targets, config, &prefix).await?;
+	#[cfg(not(feature = "libav"))]
+	let hits = select_frames(video_path, &targets, config, &prefix).await?;
+
+	debug!(frames = hits.len(), "Extracted frames at timestamps");
+	frames_for_timestamps(&hits, timestamps)
+}
+
+/// The first of `hits` (sorted by time) at or after each of `timestamps`.
+///
+/// Hit times are the exact times `FFmpeg` compared against, so a request
+/// maps to the same frame the select filter took for it.
+fn frames_for_timestamps(
+	hits: &[ExtractedFrame],
+	timestamps: &[f64],
+) -> Result<Vec<ExtractedFrame>> {
+	timestamps
+		.iter()
+		.map(|&timestamp| {
+			let hit = hits.partition_point(|hit| hit.timestamp_seconds < timestamp);
+			hits.get(hit)
+				.cloned()
+				.ok_or_else(|| PerceptionError::FrameExtractionFailed {
+					timestamp,
+					reason: "no frame at or after this time".to_string(),
+				})
+		})
+		.collect()
+}
+
+/// [`extract_frames_at`] through one `FFmpeg` process, returning each
+/// selected frame in order.
+async fn select_frames(
+	video_path: &Path,
+	targets: &[f64],
+	config: &VideoConfig,
+	prefix: &str,
+) -> Result<Vec<ExtractedFrame>> {
+	let hwaccel = resolve_hwaccel(config.hwaccel).await;
+	let mut output = select_output(video_path, targets, config, prefix, hwaccel).await?;
+	if !output.status.success() && hwaccel.is_some() {
+		warn!("Hardware decoding failed, retrying on CPU");
+		output = select_output(video_path, targets, config, prefix, None).await?;
+	}
+
+	if !output.status.success() {
+		let stderr = String::from_utf8_lossy(&output.stderr);
+		return Err(PerceptionError::FfmpegError {
+			message: stderr.to_string(),
+			exit_code: output.status.code(),
+		});
+	}
+
+	let times = showinfo_exact_timestamps(&String::from_utf8_lossy(&output.stderr));
+	let frames = times
+		.into_iter()
+		.zip(0u32..)
+		.map(|(timestamp_seconds, frame_number)| ExtractedFrame {
+			path: config.output_dir.join(format!(
+				"{prefix}-at-{frame_number:05}.{}",
+				config.format.ffmpeg_format().extension()
+			)),
+			timestamp_seconds,
+			frame_number,
+			is_keyframe: false,
+			data: None,
+			variants: Vec::new(),
+		})
+		.filter(|frame| frame.path.exists())
+		.collect();
+
+	finish_frames(frames, config).await
+}
+
+/// Run `FFmpeg` selecting the first frame at or after each of `targets`.
+async fn select_output(
+	video_path: &Path,
+	targets: &[f64],
+	config: &VideoConfig,
+	prefix: &str,
+	hwaccel: Option<&str>,
+) -> Result<std::process::Output> {
+	let output_pattern = config.output_dir.join(format!(
+		"{prefix}-at-%05d.{}",
+		config.format.ffmpeg_format().extension()
+	));
+	let thread_args: Vec<String> = config
+		.deterministic
+		.then_some(DETERMINISTIC_FFMPEG_THREADS)
+		.map(|t| vec!["-threads".to_string(), t.to_string()])
+		.unwrap_or_default();
+
+	let mut args = thread_args.clone();
+	args.extend(hwaccel_args(hwaccel));
+	args.extend(input_args(video_path, &config.network)?);
+	args.extend(rotate_args(config));
+	args.extend([
+		"-i".to_string(),
+		video_path.display().to_string(),
+		"-vf".to_string(),
+		format!("select='{}',showinfo", select_expression(targets)),
+		"-vsync".to_string(),
+		"vfr".to_string(),
+		// At most one frame per target. Targets that share a frame select it
+		// once, so then the cap is not reached and FFmpeg reads to the end
+		"-frames:v".to_string(),
+		targets.len().to_string(),
+		"-start_number".to_string(),
+		"0".to_string(),
+	]);
+	args.extend(encoder_args(config));
+	args.extend(thread_args);
+	args.push("-y".to_string());
+	args.push(output_pattern.display().to_string());
+
+	Command::new("ffmpeg")
+		.args(&args)
+		.stdin(Stdio::null())
+		.output()
+		.await
+		.map_err(|_| PerceptionError::FfmpegNotFound)
+}
+
+/// `select` expression passing the first frame at or after each target:
+/// one whose time `t` has reached a target the previous frame had not.
+///
+/// Targets are written in Rust's shortest round-trip form, so `FFmpeg`
+/// parses back exactly the values [`frames_for_timestamps`] compares.
+fn select_expression(targets: &[f64]) -> String {
+	targets
+		.iter()
+		.map(|target| format!("gte(t\\,{target})*(isnan(prev_t)+lt(prev_t\\,{target}))"))
+		.collect::<Vec<_>>()
+		.join("+")
+}
+
+/// Exact frame times reported by `showinfo`, in order: each frame's
+/// integer `pts` scaled by the filter's time base, as the `select` filter
+/// computes `t`. `pts_time` is printed to six significant digits, too
+/// coarse to tell which of two close targets a frame satisfied; it is
+/// used only if the time base is not logged.
+#[allow(clippy::cast_precision_loss)]
+fn showinfo_exact_timestamps(stderr: &str) -> Vec<f64> {
+	let mut time_base = None;
+	let mut times = Vec::new();
+	for line in stderr
+		.lines()
+		.filter(|line| line.contains("Parsed_showinfo"))
+	{
+		if let Some(base) = showinfo_time_base(line) {
+			time_base = time_base.or(Some(base));
+		} else if let Some(time) = time_base
+			.zip(showinfo_pts(line))
+			.map(|(base, pts)| pts as f64 * base)
+			.or_else(|| showinfo_timestamp(line))
+		{
+			times.push(time);
+		}
+	}
+	times
+}
+
+/// Input time base of a `showinfo` `config in` line, as seconds per tick.
+fn showinfo_time_base(line: &str) -> Option<f64> {
+	let (num, den) = line
+		.split_once("config in time_base:")?
+		.1
+		.split(',')
+		.next()?
+		.trim()
+		.split_once('/')?;
+	let num: f64 = num.parse().ok()?;
+	let den: f64 = den.parse().ok()?;
+	(den > 0.0).then(|| num / den)
+}
+
+/// Integer `pts` of a `showinfo` frame line.
+fn showinfo_pts(line: &str) -> Option<i64> {
+	line.split_once(" pts:")?
+		.1
+		.split_whitespace()
+		.next()?
+		.parse()
+		.ok()
+}
+
+// ============================================================================
+// In-Memory Extraction
+// ============================================================================
+
+/// Extract frames into memory instead of files.
+///
+/// Runs one `FFmpeg` process that writes every selected frame to stdout
+/// through `image2pipe`, so nothing touches disk. Each frame's encoded
+/// image is returned in [`ExtractedFrame::data`] and its `path` is empty.
+/// Frames are selected as by [`extract_frames`]: keyframes with
+/// `keyframes_only`, otherwise one every `interval_seconds`, up to
+/// `max_frames`; `output_dir` is not used.
+///
+/// Timestamps come from `FFmpeg`'s `showinfo` filter, so keyframes get
+/// their real times rather than the even spacing [`extract_frames`]
+/// estimates.
+///
+/// # Errors
+///
+/// Returns an error if the video is not found or `FFmpeg` fails.
+#[instrument(skip_all, fields(video = %video_path.as_ref().display()))]
+pub async fn extract_frames_to_memory(
+	video_path: impl AsRef<Path>,
+	config: &VideoConfig,
+) -> Result<Vec<ExtractedFrame>> {
+	let video_path = video_path.as_ref();
+
+	check_input(video_path)?;
+
+	#[cfg(feature = "libav")]
+	let frames = crate::libav::extract_frames_to_memory(video_path, config).await?;
+	#[cfg(not(feature = "libav"))]
+	let frames = pipe_frames(video_path, config).await?;
+
+	debug!(count = frames.len(), "Extracted frames to memory");
+	Ok(frames)
+}
+
+/// [`extract_frames_to_memory`] through one `FFmpeg` process.
+async fn pipe_frames(video_path: &Path, config: &VideoConfig) -> Result<Vec<ExtractedFrame>> {
+	let metadata = get_video_metadata_with(video_path, &config.network).await?;
+	debug!(?metadata, "Got video metadata");
+
+	let interval = pipe_interval(config);
+	let hwaccel = resolve_hwaccel(config.hwaccel).await;
+	let mut output = pipe_output(video_path, config, hwaccel).await?;
+	if !output.status.success() && hwaccel.is_some() {
+		warn!("Hardware decoding failed, retrying on CPU");
+		output = pipe_output(video_path, config, None).await?;
+	}
+
+	if !output.status.success() {
+		let stderr = String::from_utf8_lossy(&output.stderr);
+		return Err(PerceptionError::FfmpegError {
+			message: stderr.to_string(),
+			exit_code: output.status.code(),
+		});
+	}
+
+	let images = split_image_stream(&output.stdout, config.format);
+	let timestamps = showinfo_timestamps(&String::from_utf8_lossy(&output.stderr));
+	if timestamps.len() != images.len() {
+		warn!(
+			frames = images.len(),
+			timestamps = timestamps.len(),
+			"showinfo timestamps do not match frames, estimating"
+		);
+	}
+
+	let (start, end) = config.time_range(metadata.duration_seconds);
+	#[allow(clippy::cast_precision_loss)]
+	let spacing = if config.keyframes_only && !images.is_empty() {
+		(end - start) / images.len() as f64
+	} else {
+		interval
+	};
+	let frames = images
+		.into_iter()
+		.zip(0u32..)
+		.map(|(data, frame_number)| ExtractedFrame {
+			path: PathBuf::new(),
+			timestamp_seconds: start
+				+ timestamps
+					.get(frame_number as usize)
+					.copied()
+					.unwrap_or_else(|| f64::from(frame_number) * spacing),
+			frame_number,
+			is_keyframe: config.keyframes_only,
+			data: Some(data),
+			variants: Vec::new(),
+		})
+		.collect();
+
+	finish_frames(frames, config).await
+}
+
+/// Spacing of frames selected by interval, in seconds.
+fn pipe_interval(config: &VideoConfig) -> f64 {
+	if config.interval_seconds > 0.0 {
+		config.interval_seconds
+	} else {
+		1.0
+	}
+}
+
+/// Run `FFmpeg` with [`pipe_args`] to completion.
+async fn pipe_output(
+	video_path: &Path,
+	config: &VideoConfig,
+	hwaccel: Option<&str>,
+) -> Result<std::process::Output> {
+	Command::new("ffmpeg")
+		.args(pipe_args(video_path, config, hwaccel)?)
+		.stdin(Stdio::null())
+		.output()
+		.await
+		.map_err(|_| PerceptionError::FfmpegNotFound)
+}
+
+/// `FFmpeg` arguments that write the frames selected by `config` to stdout
+/// through `image2pipe`, logging each frame's time through `showinfo`.
+fn pipe_args(
+	video_path: &Path,
+	config: &VideoConfig,
+	hwaccel: Option<&str>,
+) -> Result<Vec<String>> {
+	let select = if config.keyframes_only {
+		"select='eq(pict_type\\,I)'".to_string()
+	} else {
+		format!("fps={:.6}", 1.0 / pipe_interval(config))
+	};
+
+	let mut args: Vec<String> = Vec::new();
+	if config.deterministic {
+		args.extend([
+			"-threads".to_string(),
+			DETERMINISTIC_FFMPEG_THREADS.to_string(),
+		]);
+	}
+	args.extend(hwaccel_args(hwaccel));
+	args.extend(input_args(video_path, &config.network)?);
+	args.extend(rotate_args(config));
+	args.extend(range_args(config));
+	args.extend([
+		"-i".to_string(),
+		video_path.display().to_string(),
+		"-vf".to_string(),
+		format!("{select},showinfo"),
+		"-vsync".to_string(),
+		"vfr".to_string(),
+	]);
+	args.extend(encoder_args(config));
+	if config.max_frames > 0 {
+		args.push("-frames:v".to_string());
+		args.push(config.max_frames.to_string());
+	}
+	args.extend(["-f".to_string(), "image2pipe".to_string(), "-".to_string()]);
+	Ok(args)
+}
+
+/// Split `image2pipe` output into one encoded image per frame.
+///
+/// The images are simply concatenated, so each one's end is found by
+/// walking its structure: PNG chunks up to `IEND`, JPEG segments up to the
+/// `EOI` marker, `WebP` by its RIFF size. A truncated trailing image is
+/// dropped.
+fn split_image_stream(bytes: &[u8], format: ImageFormat) -> Vec<Vec<u8>> {
+	let image_len = image_len(format);
+
+	let mut images = Vec::new();
+	let mut rest = bytes;
+	while !rest.is_empty() {
+		let Some(len) = image_len(rest) else {
+			warn!(
+				bytes = rest.len(),
+				"Incomplete image at end of stream, dropping"
+			);
+			break;
+		};
+		images.push(rest[..len].to_vec());
+		rest = &rest[len..];
+	}
+	images
+}
+
+/// Length of the complete image at the start of a stream of `format`
+/// images, or `None` if it is incomplete.
+fn image_len(format: ImageFormat) -> fn(&[u8]) -> Option<usize> {
+	match format {
+		ImageFormat::Jpeg => jpeg_len,
+		// AVIF frames are piped as PNG; see `ImageFormat::ffmpeg_format`
+		ImageFormat::Png | ImageFormat::Avif => png_len,
+		ImageFormat::WebpLossless => webp_len,
+	}
+}
+
+/// Length of the `WebP` at the start of `bytes`.
+fn webp_len(bytes: &[u8]) -> Option<usize> {
+	let header = bytes.get(..12)?;
+	if &header[..4] != b"RIFF" || &header[8..] != b"WEBP" {
+		return None;
+	}
+	// The RIFF size counts everything after itself, padded to even
+	let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+	let end = size.checked_add(size & 1)?.checked_add(8)?;
+	(end <= bytes.len()).then_some(end)
+}
+
+/// Length of the PNG at the start of `bytes`.
+fn png_len(bytes: &[u8]) -> Option<usize> {
+	const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+	if !bytes.starts_with(SIGNATURE) {
+		return None;
+	}
+	let mut pos = SIGNATURE.len();
+	loop {
+		let header = bytes.get(pos..pos + 8)?;
+		let data_len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+		// length, type, data, CRC
+		let end = pos.checked_add(12)?.checked_add(data_len)?;
+		if end > bytes.len() {
+			return None;
+		}
+		if &header[4..8] == b"IEND" {
+			return Some(end);
+		}
+		pos = end;
+	}
+}
+
+/// Length of the JPEG at the start of `bytes`.
+///
+/// Marker segments carry their length; entropy-coded data after `SOS` does
+/// not, but inside it `0xFF` is always followed by `0x00` (a stuffed byte)
+/// or a restart marker, so the next other marker ends it.
+fn jpeg_len(bytes: &[u8]) -> Option<usize> {
+	if !bytes.starts_with(&[0xFF, 0xD8]) {
+		return None;
+	}
+	let mut pos = 2;
+	loop {
+		if *bytes.get(pos)? != 0xFF {
+			return None;
+		}
+		// Markers may be preceded by any number of fill bytes
+		while *bytes.get(pos + 1)? == 0xFF {
+			pos += 1;
+		}
+		let marker = bytes[pos + 1];
+		pos += 2;
+		match marker {
+			0xD9 => return Some(pos),
+			0x01 | 0xD0..=0xD7 => continue,
+			_ => {}
+		}
+
+		let length = bytes.get(pos..pos + 2)?;
+		pos += usize::from(u16::from_be_bytes([length[0], length[1]]));
+		if marker == 0xDA {
+			loop {
+				let offset = bytes.get(pos..)?.iter().position(|&b| b == 0xFF)?;
+				pos += offset;
+				match *bytes.get(pos + 1)? {
+					0x00 | 0xD0..=0xD7 => pos += 2,
+					_ => break,
+				}
+			}
+		}
+	}
+}
+
+/// Frame times (`pts_time`) reported by the `showinfo` filter, in order.
+fn showinfo_timestamps(stderr: &str) -> Vec<f64> {
+	stderr.lines().filter_map(showinfo_timestamp).collect()
+}
+
+/// Frame time (`pts_time`) of one `showinfo` log line, if it reports a frame.
+fn showinfo_timestamp(line: &str) -> Option<f64> {
+	if !line.contains("Parsed_showinfo") {
+		return None;
+	}
+	line.split_once("pts_time:")?
+		.1
+		.split_whitespace()
+		.next()?
+		.parse()
+		.ok()
+}
+
+// ============================================================================
+// Streaming Extraction
+// ============================================================================
+
+/// `FFmpeg` log lines kept for the error message if extraction fails.
+const STREAM_LOG_LINES: usize = 20;
+
+/// Extract frames as `FFmpeg` produces them.
+///
+/// Selects and encodes frames like [`extract_frames_to_memory`], but
+/// yields each one as soon as it is complete instead of waiting for the
+/// whole video, so scene detection can start on the first frames while
+/// later ones are still being decoded. `FFmpeg` writes into a pipe, so it
+/// runs only as far ahead of the consumer as the pipe buffers; memory
+/// stays flat however long the video is.
+///
+/// Nothing runs until the stream is first polled. Dropping the stream
+/// kills `FFmpeg`. If hardware decoding fails before the first frame, the
+/// stream restarts on the CPU; a failure after that ends it with an error.
+///
+/// # Errors
+///
+/// Yields an error, and then ends, if the video is not found, `FFmpeg`
+/// cannot be started or exits with an error, or reading its output fails.
+pub fn extract_frames_stream(
+	video_path: impl AsRef<Path>,
+	config: &VideoConfig,
+) -> impl Stream<Item = Result<ExtractedFrame>> + Send + 'static {
+	#[cfg(feature = "libav")]
+	{
+		crate::libav::extract_frames_stream(video_path.as_ref().to_path_buf(), config.clone())
+	}
+
+	#[cfg(not(feature = "libav"))]
+	{
+		let state = FrameStream::Pending {
+			video_path: video_path.as_ref().to_path_buf(),
+			config: config.clone(),
+		};
+		stream::unfold(state, FrameStream::next)
+	}
+}
+
+/// State of an [`extract_frames_stream`].
+enum FrameStream {
+	/// Not yet polled
+	Pending {
+		video_path: PathBuf,
+		config: VideoConfig,
+	},
+	/// `FFmpeg` is running
+	Running(Box<PipeReader>),
+	/// Ended, after the last frame or an error
+	Done,
+}
+
+impl FrameStream {
+	async fn next(self) -> Option<(Result<ExtractedFrame>, Self)> {
+		let mut reader = match self {
+			Self::Pending { video_path, config } => {
+				let hwaccel = resolve_hwaccel(config.hwaccel).await;
+				match PipeReader::spawn(video_path, config, hwaccel) {
+					Ok(reader) => Box::new(reader),
+					Err(e) => return Some((Err(e), Self::Done)),
+				}
+			}
+			Self::Running(reader) => reader,
+			Self::Done => return None,
+		};
+		loop {
+			match reader.next_frame().await? {
+				Ok(frame) => {
+					return match finish_frame(frame, &reader.config).await {
+						Ok(frame) => Some((Ok(frame), Self::Running(reader))),
+						Err(e) => Some((Err(e), Self::Done)),
+					};
+				}
+				// Nothing was yielded yet, so the CPU can start over
+				Err(e) if reader.hwaccel.is_some() && reader.next_frame_number == 0 => {
+					warn!(?e, "Hardware decoding failed, retrying on CPU");
+					let (video_path, config) = (reader.video_path.clone(), reader.config.clone());
+					match PipeReader::spawn(video_path, config, None) {
+						Ok(cpu) => *reader = cpu,
+						Err(e) => return Some((Err(e), Self::Done)),
+					}
+				}
+				Err(e) => return Some((Err(e), Self::Done)),
+			}
+		}
+	}
+}
+
+/// A running `image2pipe` extraction, read incrementally.
+struct PipeReader {
+	video_path: PathBuf,
+	config: VideoConfig,
+	/// `-hwaccel` method in use
+	hwaccel: Option<&'static str>,
+	child: Child,
+	/// `None` once `FFmpeg` closed it
+	stdout: Option<ChildStdout>,
+	/// `None` once `FFmpeg` closed it
+	stderr: Option<Lines<BufReader<ChildStderr>>>,
+	/// Bytes read but not yet yielded as a frame
+	buffer: Vec<u8>,
+	/// `showinfo` times of frames not yet yielded
+	timestamps: VecDeque<f64>,
+	/// Last other log lines, for the error message
+	log: VecDeque<String>,
+	next_frame_number: u32,
+}
+
+impl PipeReader {
+	fn spawn(
+		video_path: PathBuf,
+		config: VideoConfig,
+		hwaccel: Option<&'static str>,
+	) -> Result<Self> {
+		check_input(&video_path)?;
+
+		let mut child = Command::new("ffmpeg")
+			.args(pipe_args(&video_path, &config, hwaccel)?)
+			.stdin(Stdio::null())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped())
+			.kill_on_drop(true)
+			.spawn()
+			.map_err(|_| PerceptionError::FfmpegNotFound)?;
+		let stdout = child.stdout.take();
+		let stderr = child.stderr.take().map(|e| BufReader::new(e).lines());
+
+		Ok(Self {
+			video_path,
+			config,
+			hwaccel,
+			child,
+			stdout,
+			stderr,
+			buffer: Vec::new(),
+			timestamps: VecDeque::new(),
+			log: VecDeque::new(),
+			next_frame_number: 0,
+		})
+	}
+
+	/// The next frame, or `None` once `FFmpeg` has finished cleanly.
+	///
+	/// A complete image waits for its `showinfo` time, which `FFmpeg` logs
+	/// before encoding the frame; if the log closes first, the time is
+	/// estimated from the interval.
+	async fn next_frame(&mut self) -> Option<Result<ExtractedFrame>> {
+		loop {
+			if let Some(len) = image_len(self.config.format)(&self.buffer) {
+				if !self.timestamps.is_empty() || self.stderr.is_none() {
+					let data = self.buffer.drain(..len).collect();
+					return Some(Ok(self.frame(data)));
+				}
+			} else if self.stdout.is_none() {
+				return self.finish().await;
+			}
+
+			tokio::select! {
+				read = read_chunk(self.stdout.as_mut(), &mut self.buffer) => match read {
+					Ok(0) => self.stdout = None,
+					Ok(_) => {}
+					Err(e) => return Some(Err(e.into())),
+				},
+				line = next_line(self.stderr.as_mut()) => match line {
+					Ok(Some(line)) => self.log_line(line),
+					Ok(None) => self.stderr = None,
+					Err(e) => return Some(Err(e.into())),
+				},
+			}
+		}
+	}
+
+	fn frame(&mut self, data: Vec<u8>) -> ExtractedFrame {
+		let frame_number = self.next_frame_number;
+		self.next_frame_number += 1;
+		let (start, _) = self.config.time_range(f64::INFINITY);
+		let timestamp_seconds = start
+			+ self
+				.timestamps
+				.pop_front()
+				.unwrap_or_else(|| f64::from(frame_number) * pipe_interval(&self.config));
+		ExtractedFrame {
+			path: PathBuf::new(),
+			timestamp_seconds,
+			frame_number,
+			is_keyframe: self.config.keyframes_only,
+			data: Some(data),
+			variants: Vec::new(),
+		}
+	}
+
+	fn log_line(&mut self, line: String) {
+		if let Some(timestamp) = showinfo_timestamp(&line) {
+			self.timestamps.push_back(timestamp);
+		} else {
+			if self.log.len() == STREAM_LOG_LINES {
+				let _ = self.log.pop_front();
+			}
+			self.log.push_back(line);
+		}
+	}
+
+	/// Drain the log and report how `FFmpeg` exited.
+	async fn finish(&mut self) -> Option<Result<ExtractedFrame>> {
+		if !self.buffer.is_empty() {
+			warn!(
+				bytes = self.buffer.len(),
+				"Incomplete image at end of stream, dropping"
+			);
+			self.buffer.clear();
+		}
+		while let Some(stderr) = self.stderr.as_mut() {
+			match stderr.next_line().await {
+				Ok(Some(line)) => self.log_line(line),
+				Ok(None) | Err(_) => self.stderr = None,
+			}
+		}
+
+		match self.child.wait().await {
+			Ok(status) if status.success() => {
+				debug!(count = self.next_frame_number, "Streamed frames");
+				None
+			}
+			Ok(status) => Some(Err(PerceptionError::FfmpegError {
+				message: Vec::from(std::mem::take(&mut self.log)).join("\n"),
+				exit_code: status.code(),
+			})),
+			Err(e) => Some(Err(e.into())),
+		}
+	}
+}
+
+/// Append what `stdout` has ready to `buffer`; never completes once
+/// `stdout` is closed.
+async fn read_chunk(
+	stdout: Option<&mut ChildStdout>,
+	buffer: &mut Vec<u8>,
+) -> std::io::Result<usize> {
+	match stdout {
+		Some(stdout) => {
+			buffer.reserve(64 * 1024);
+			stdout.read_buf(buffer).await
+		}
+		None => std::future::pending().await,
+	}
+}
+
+/// The next log line; never completes once the log is closed.
+async fn next_line(
+	stderr: Option<&mut Lines<BufReader<ChildStderr>>>,
+) -> std::io::Result<Option<String>> {
+	match stderr {
+		Some(stderr) => stderr.next_line().await,
+		None => std::future::pending().await,
+	}
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_check_ffmpeg() {
+		// This test will pass on systems with FFmpeg installed
+		let result = check_ffmpeg().await;
+		// We just check it doesn't panic; actual availability depends on the system
+		println!("FFmpeg available: {}", result.is_ok());
+	}
+
+	#[test]
+	fn test_image_format() {
+		assert_eq!(ImageFormat::Jpeg.extension(), "jpg");
+		assert_eq!(ImageFormat::Png.extension(), "png");
+		assert_eq!(ImageFormat::Jpeg.codec(), "mjpeg");
 		assert_eq!(ImageFormat::Png.codec(), "png");
+		assert_eq!(ImageFormat::Avif.extension(), "avif");
+		assert_eq!(ImageFormat::WebpLossless.extension(), "webp");
+		assert_eq!(ImageFormat::WebpLossless.codec(), "libwebp");
+		assert_eq!(ImageFormat::Avif.ffmpeg_format(), ImageFormat::Png);
+		assert_eq!(ImageFormat::Jpeg.ffmpeg_format(), ImageFormat::Jpeg);
+	}
+
+	#[test]
+	fn test_encoder_args() {
+		assert_eq!(
+			encoder_args(&VideoConfig::default()),
+			["-c:v", "mjpeg", "-q:v", "2"]
+		);
+		let config = VideoConfig {
+			format: ImageFormat::Avif,
+			..VideoConfig::default()
+		};
+		assert_eq!(encoder_args(&config), ["-c:v", "png", "-q:v", "2"]);
+		let config = VideoConfig {
+			format: ImageFormat::WebpLossless,
+			..VideoConfig::default()
+		};
+		assert_eq!(
+			encoder_args(&config),
+			[
+				"-c:v",
+				"libwebp",
+				"-lossless",
+				"1",
+				"-compression_level",
+				"4"
+			]
+		);
+
+		assert_eq!(webp_compression_level(1), 0);
+		assert_eq!(webp_compression_level(7), 4);
+		assert_eq!(webp_compression_level(10), 6);
+		assert_eq!(webp_compression_level(99), 6);
 	}
 
 	#[test]
@@ -599,6 +2148,341 @@ mod tests {
 		assert!((config.interval_seconds - 1.0).abs() < f64::EPSILON);
 		assert_eq!(config.quality, 2);
 		assert_eq!(config.format, ImageFormat::Jpeg);
+		assert_eq!(config.effort, 7);
 		assert!(!config.keyframes_only);
+		assert!(config.auto_rotate);
+	}
+
+	#[test]
+	fn test_deterministic_prefix() {
+		assert_eq!(
+			deterministic_prefix(Path::new("/videos/My Trip (1).mp4")),
+			"My_Trip__1_"
+		);
+		assert_eq!(deterministic_prefix(Path::new("/")), "video");
+	}
+
+	#[test]
+	fn test_split_png_stream() {
+		let encode = |width| {
+			let mut bytes = std::io::Cursor::new(Vec::new());
+			let written =
+				image::RgbImage::new(width, 2).write_to(&mut bytes, image::ImageFormat::Png);
+			assert!(written.is_ok());
+			bytes.into_inner()
+		};
+		let (first, second) = (encode(3), encode(5));
+		let mut stream = [first.clone(), second.clone()].concat();
+
+		assert_eq!(
+			split_image_stream(&stream, ImageFormat::Png),
+			vec![first.clone(), second]
+		);
+
+		// A truncated trailing image is dropped
+		stream.truncate(stream.len() - 4);
+		assert_eq!(split_image_stream(&stream, ImageFormat::Png), vec![first]);
+	}
+
+	#[test]
+	fn test_split_jpeg_stream() {
+		let encode = |shade| {
+			let mut bytes = std::io::Cursor::new(Vec::new());
+			let image = image::RgbImage::from_fn(16, 16, |x, y| {
+				#[allow(clippy::cast_possible_truncation)]
+				let value = (x * y) as u8 ^ shade;
+				image::Rgb([value, 0xFF, value])
+			});
+			let written = image.write_to(&mut bytes, image::ImageFormat::Jpeg);
+			assert!(written.is_ok());
+			bytes.into_inner()
+		};
+		let (first, second) = (encode(0x00), encode(0xFF));
+		let stream = [first.clone(), second.clone()].concat();
+
+		let images = split_image_stream(&stream, ImageFormat::Jpeg);
+		assert_eq!(images, vec![first, second]);
+		assert!(images
+			.iter()
+			.all(|data| image::load_from_memory(data).is_ok()));
+	}
+
+	#[test]
+	fn test_split_webp_stream() {
+		let encode = |width| {
+			let mut bytes = std::io::Cursor::new(Vec::new());
+			let written =
+				image::RgbImage::new(width, 2).write_to(&mut bytes, image::ImageFormat::WebP);
+			assert!(written.is_ok());
+			bytes.into_inner()
+		};
+		let (first, second) = (encode(3), encode(5));
+		let mut stream = [first.clone(), second.clone()].concat();
+
+		assert_eq!(
+			split_image_stream(&stream, ImageFormat::WebpLossless),
+			vec![first.clone(), second]
+		);
+
+		stream.truncate(stream.len() - 1);
+		assert_eq!(
+			split_image_stream(&stream, ImageFormat::WebpLossless),
+			vec![first]
+		);
+	}
+
+	#[test]
+	fn test_rotation() {
+		assert_eq!(display_rotation(0.0), 0);
+		assert_eq!(display_rotation(-90.0), 90);
+		assert_eq!(display_rotation(90.0), 270);
+		assert_eq!(display_rotation(180.0), 180);
+		assert_eq!(display_rotation(-179.9), 180);
+		assert_eq!(display_rotation(f64::NAN), 0);
+
+		let probe: std::result::Result<FfprobeOutput, _> = serde_json::from_str(
+			r#"{"streams": [{"codec_type": "video", "side_data_list": [{"rotation": -90}]}]}"#,
+		);
+		assert!(probe.is_ok_and(|p| p.streams[0].side_data_list[0].rotation == Some(-90.0)));
+
+		let mut config = VideoConfig::default();
+		assert!(rotate_args(&config).is_empty());
+		config.auto_rotate = false;
+		assert_eq!(rotate_args(&config), ["-noautorotate"]);
+	}
+
+	#[test]
+	fn test_showinfo_timestamps() {
+		let stderr = "\
+[Parsed_showinfo_1 @ 0x1] config in time_base: 1/30, frame_rate: 30/1
+[Parsed_showinfo_1 @ 0x1] n:   0 pts:      0 pts_time:0       duration:1
+[Parsed_showinfo_1 @ 0x1] n:   1 pts:     75 pts_time:2.5     duration:1
+frame=    2 fps=0.0 q=2.0 size=N/A time=00:00:02.50
+";
+		assert_eq!(showinfo_timestamps(stderr), vec![0.0, 2.5]);
+	}
+
+	#[test]
+	fn test_hwaccel() {
+		assert_eq!(HwAccel::default(), HwAccel::None);
+		assert_eq!(HwAccel::None.ffmpeg_name(), None);
+		assert_eq!(HwAccel::Nvdec.ffmpeg_name(), Some("cuda"));
+		assert_eq!(hwaccel_args(Some("vaapi")), ["-hwaccel", "vaapi"]);
+
+		let listing = "Hardware acceleration methods:\nvdpau\ncuda\nvaapi\n\n";
+		assert_eq!(parse_hwaccels(listing), ["vdpau", "cuda", "vaapi"]);
+		assert!(parse_hwaccels("").is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_stream_missing_video() {
+		use futures::StreamExt;
+
+		let stream = extract_frames_stream("/nonexistent/video.mp4", &VideoConfig::default());
+		let items: Vec<Result<ExtractedFrame>> = stream.collect().await;
+		assert_eq!(items.len(), 1);
+		assert!(matches!(items[0], Err(PerceptionError::VideoNotFound(_))));
+	}
+
+	#[test]
+	fn test_video_config_validate() {
+		assert!(VideoConfig::default().validate().is_empty());
+
+		let config = VideoConfig {
+			interval_seconds: f64::NAN,
+			quality: 0,
+			..VideoConfig::default()
+		};
+		let fields: Vec<String> = config.validate().into_iter().map(|i| i.field).collect();
+		assert_eq!(fields, ["interval_seconds", "quality"]);
+
+		let config = VideoConfig {
+			format: ImageFormat::Avif,
+			effort: 0,
+			..VideoConfig::default()
+		};
+		let fields: Vec<String> = config.validate().into_iter().map(|i| i.field).collect();
+		if cfg!(feature = "avif") {
+			assert_eq!(fields, ["effort"]);
+		} else {
+			assert_eq!(fields, ["effort", "format"]);
+		}
+
+		let mut config = VideoConfig::default();
+		config.network.timeout_seconds = -1.0;
+		let _ = config
+			.network
+			.headers
+			.insert("X-Token".to_string(), "a\r\nHost: evil".to_string());
+		let fields: Vec<String> = config.validate().into_iter().map(|i| i.field).collect();
+		assert_eq!(fields, ["network.timeout_seconds", "network.headers"]);
+
+		let config = VideoConfig {
+			start_time: Some(f64::INFINITY),
+			end_time: Some(5.0),
+			..VideoConfig::default()
+		};
+		let fields: Vec<String> = config.validate().into_iter().map(|i| i.field).collect();
+		assert_eq!(fields, ["start_time", "end_time"]);
+
+		let config = VideoConfig {
+			variants: vec![
+				FrameSize::new("thumb", 160, 0),
+				FrameSize::new("thumb", 0, 0),
+			],
+			..VideoConfig::default()
+		};
+		let fields: Vec<String> = config.validate().into_iter().map(|i| i.field).collect();
+		assert_eq!(fields, ["variants[1].max_width", "variants[1].name"]);
+	}
+
+	#[test]
+	fn test_time_range() {
+		let mut config = VideoConfig::default();
+		assert_eq!(config.time_range(60.0), (0.0, 60.0));
+		assert!(range_args(&config).is_empty());
+
+		config.start_time = Some(10.0);
+		config.end_time = Some(25.5);
+		assert_eq!(config.time_range(60.0), (10.0, 25.5));
+		assert_eq!(config.time_range(20.0), (10.0, 20.0));
+		assert_eq!(config.time_range(5.0), (10.0, 10.0));
+		assert_eq!(range_args(&config), ["-ss", "10.000", "-t", "15.500"]);
+
+		config.start_time = None;
+		assert_eq!(range_args(&config), ["-t", "25.500"]);
+	}
+
+	#[test]
+	fn test_input_options() {
+		assert!(is_remote("HTTPS://example.com/live.m3u8"));
+		assert!(!is_remote("/videos/http://not-a-url.mp4"));
+		assert!(!is_remote("video.mp4"));
+
+		let mut network = NetworkConfig::default();
+		let _ = network
+			.headers
+			.insert("Authorization".to_string(), "Bearer abc".to_string());
+		let remote = input_options(Path::new("https://example.com/video.mp4"), &network);
+		assert_eq!(
+			remote.ok(),
+			Some(vec![
+				("rw_timeout", "30000000".to_string()),
+				("headers", "Authorization: Bearer abc\r\n".to_string()),
+			])
+		);
+
+		let playlist = input_options(Path::new("/streams/live.M3U8"), &network);
+		assert_eq!(
+			playlist.ok(),
+			Some(vec![("protocol_whitelist", PLAYLIST_PROTOCOLS.to_string())])
+		);
+		let local = input_options(Path::new("/videos/video.mp4"), &network);
+		assert!(local.is_ok_and(|options| options.is_empty()));
+
+		network.timeout_seconds = 0.0;
+		network.headers.clear();
+		let remote = input_options(Path::new("http://example.com/a.m3u8?t=1"), &network);
+		assert!(remote.is_ok_and(|options| options.is_empty()));
+
+		let _ = network
+			.headers
+			.insert("X-Token".to_string(), "a\r\nHost: evil".to_string());
+		assert!(matches!(
+			input_args(Path::new("https://example.com/video.mp4"), &network),
+			Err(PerceptionError::InvalidConfig(issue)) if issue.field == "network.headers"
+		));
+	}
+
+	#[test]
+	fn test_check_input() {
+		assert!(check_input(Path::new("https://example.com/video.mp4")).is_ok());
+		assert!(matches!(
+			check_input(Path::new("/nonexistent/video.mp4")),
+			Err(PerceptionError::VideoNotFound(_))
+		));
+	}
+
+	#[test]
+	fn test_select_expression() {
+		assert_eq!(
+			select_expression(&[0.0, 2.5]),
+			"gte(t\\,0)*(isnan(prev_t)+lt(prev_t\\,0))+gte(t\\,2.5)*(isnan(prev_t)+lt(prev_t\\,2.5))"
+		);
+		// Targets round-trip exactly
+		assert!(select_expression(&[0.1 + 0.2]).contains("0.30000000000000004"));
+	}
+
+	#[test]
+	fn test_showinfo_exact_timestamps() {
+		let stderr = "\
+[Parsed_showinfo_1 @ 0x1] config in time_base: 1/90000, frame_rate: 30000/1001
+[Parsed_showinfo_1 @ 0x1] n:   0 pts:   3003 pts_time:0.0333667 duration:3003
+[Parsed_showinfo_1 @ 0x1] n:   1 pts: 1234567 pts_time:13.7174 duration:3003
+";
+		assert_eq!(
+			showinfo_exact_timestamps(stderr),
+			vec![3003.0 / 90000.0, 1_234_567.0 / 90000.0]
+		);
+
+		// Without a time base, fall back to pts_time
+		let stderr = "[Parsed_showinfo_1 @ 0x1] n:   0 pts:     75 pts_time:2.5     duration:1";
+		assert_eq!(showinfo_exact_timestamps(stderr), vec![2.5]);
+	}
+
+	#[test]
+	fn test_frames_for_timestamps() {
+		let hit = |timestamp_seconds: f64, frame_number: u32| ExtractedFrame {
+			path: PathBuf::from(format!("at-{frame_number:05}.jpg")),
+			timestamp_seconds,
+			frame_number,
+			is_keyframe: false,
+			data: None,
+			variants: Vec::new(),
+		};
+		let hits = [hit(1.0, 0), hit(2.04, 1)];
+
+		let frames = frames_for_timestamps(&hits, &[2.0, 0.5, 1.0, 2.01]);
+		let times: Vec<f64> = frames
+			.iter()
+			.flatten()
+			.map(|frame| frame.timestamp_seconds)
+			.collect();
+		assert_eq!(times, vec![2.04, 1.0, 1.0, 2.04]);
+
+		assert!(matches!(
+			frames_for_timestamps(&hits, &[3.0]),
+			Err(PerceptionError::FrameExtractionFailed { .. })
+		));
+	}
+
+	#[tokio::test]
+	async fn test_extract_frames_rejects_header_injection() {
+		let mut config = VideoConfig {
+			output_dir: std::env::temp_dir().join("lucid-frames-header-test"),
+			..VideoConfig::default()
+		};
+		let _ = config
+			.network
+			.headers
+			.insert("X-Token".to_string(), "a\r\nHost: evil".to_string());
+		let result = extract_frames("https://example.com/video.mp4", &config).await;
+		assert!(matches!(result, Err(PerceptionError::InvalidConfig(_))));
+		let _ = std::fs::remove_dir_all(&config.output_dir);
+	}
+
+	#[tokio::test]
+	async fn test_extract_frames_at_invalid() {
+		let config = VideoConfig::default();
+		for timestamp in [-1.0, f64::NAN] {
+			assert!(matches!(
+				extract_frames_at("/nonexistent/video.mp4", &[1.0, timestamp], &config).await,
+				Err(PerceptionError::FrameExtractionFailed { .. })
+			));
+		}
+		assert!(matches!(
+			extract_frames_at("/nonexistent/video.mp4", &[], &config).await,
+			Err(PerceptionError::VideoNotFound(_))
+		));
 	}
 }
@@ -0,0 +1,510 @@
+//! WebAssembly bindings for lucid-core memory retrieval engine.
+//!
+//! Lets a browser client run recall over a locally synced graph via
+//! wasm-bindgen. Node indices travel as `Uint32Array` and activations as
+//! `Float64Array`; optional settings are plain camelCase objects where a
+//! missing field keeps the core default.
+
+// wasm-bindgen requires owned types at the FFI boundary - can't use references
+#![allow(clippy::needless_pass_by_value)]
+// Memory indices will never exceed u32::MAX in practice
+#![allow(clippy::cast_possible_truncation)]
+
+use serde::{de::DeserializeOwned, Deserialize};
+use wasm_bindgen::prelude::*;
+
+use lucid_core::{
+	graph::AssociationGraph as CoreGraph,
+	spreading::{
+		compute_pagerank_graph, compute_weighted_pagerank_graph,
+		create_episode_links as core_create_episode_links, find_activation_path_graph,
+		spread_activation_graph, spread_temporal_activation as core_spread_temporal, Association,
+		AssociationType, SpreadingConfig, TemporalLink, TemporalSpreadingConfig,
+	},
+	DuplicatePolicy, GraphBuilder,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_OPTIONS: &str = r#"
+/** Spreading settings; omitted fields keep their defaults. */
+export interface SpreadingOptions {
+	/** Decay per hop (default: 0.7) */
+	decayPerHop?: number;
+	/** Minimum activation to propagate (default: 0.01) */
+	minimumActivation?: number;
+	/** Maximum nodes to visit (default: 1000) */
+	maxNodes?: number;
+	/** Whether to spread bidirectionally (default: true) */
+	bidirectional?: boolean;
+	/** Spread until a level adds less than this; overrides depth */
+	convergenceEpsilon?: number;
+	/** Level cap when spreading to convergence (default: 100) */
+	maxIterations?: number;
+	/** Logistic noise scale; 0 disables noise (default: 0) */
+	activationNoise?: number;
+	/** Seed for activation noise (default: 0) */
+	noiseSeed?: number;
+}
+
+/** Temporal spreading settings; omitted fields keep their defaults. */
+export interface TemporalSpreadingOptions {
+	/** Forward link strength multiplier (default: 1.0) */
+	forwardStrength?: number;
+	/** Backward link strength multiplier (default: 0.7) */
+	backwardStrength?: number;
+	/** Distance decay rate (default: 0.3) */
+	distanceDecayRate?: number;
+	/** Activation boost for episode-linked memories (default: 1.2) */
+	episodeBoost?: number;
+	/** TCM context persistence (default: 0.7) */
+	contextPersistence?: number;
+	/** Maximum positions between linked events (default: 10) */
+	maxTemporalDistance?: number;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+	/// JS-side `SpreadingOptions` object.
+	#[wasm_bindgen(typescript_type = "SpreadingOptions")]
+	pub type JsSpreadingOptions;
+
+	/// JS-side `TemporalSpreadingOptions` object.
+	#[wasm_bindgen(typescript_type = "TemporalSpreadingOptions")]
+	pub type JsTemporalSpreadingOptions;
+}
+
+// ============================================================================
+// Options
+// ============================================================================
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpreadingOptions {
+	decay_per_hop: Option<f64>,
+	minimum_activation: Option<f64>,
+	max_nodes: Option<u32>,
+	bidirectional: Option<bool>,
+	convergence_epsilon: Option<f64>,
+	max_iterations: Option<u32>,
+	activation_noise: Option<f64>,
+	noise_seed: Option<u64>,
+}
+
+impl SpreadingOptions {
+	fn into_core(self) -> SpreadingConfig {
+		let default = SpreadingConfig::default();
+		SpreadingConfig {
+			decay_per_hop: self.decay_per_hop.unwrap_or(default.decay_per_hop),
+			minimum_activation: self
+				.minimum_activation
+				.unwrap_or(default.minimum_activation),
+			max_nodes: self.max_nodes.map_or(default.max_nodes, |n| n as usize),
+			bidirectional: self.bidirectional.unwrap_or(default.bidirectional),
+			convergence_epsilon: self.convergence_epsilon,
+			max_iterations: self
+				.max_iterations
+				.map_or(default.max_iterations, |n| n as usize),
+			activation_noise: self.activation_noise.unwrap_or(default.activation_noise),
+			noise_seed: self.noise_seed.unwrap_or(default.noise_seed),
+			..default
+		}
+	}
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TemporalSpreadingOptions {
+	forward_strength: Option<f64>,
+	backward_strength: Option<f64>,
+	distance_decay_rate: Option<f64>,
+	episode_boost: Option<f64>,
+	context_persistence: Option<f64>,
+	max_temporal_distance: Option<u32>,
+}
+
+impl TemporalSpreadingOptions {
+	fn into_core(self) -> TemporalSpreadingConfig {
+		let default = TemporalSpreadingConfig::default();
+		TemporalSpreadingConfig {
+			forward_strength: self.forward_strength.unwrap_or(default.forward_strength),
+			backward_strength: self.backward_strength.unwrap_or(default.backward_strength),
+			distance_decay_rate: self
+				.distance_decay_rate
+				.unwrap_or(default.distance_decay_rate),
+			episode_boost: self.episode_boost.unwrap_or(default.episode_boost),
+			context_persistence: self
+				.context_persistence
+				.unwrap_or(default.context_persistence),
+			max_temporal_distance: self
+				.max_temporal_distance
+				.map_or(default.max_temporal_distance, |d| d as usize),
+		}
+	}
+}
+
+/// Deserialize an options object, treating `undefined` and `null` as all defaults.
+fn parse_options<T: DeserializeOwned + Default>(value: Option<JsValue>) -> Result<T, JsError> {
+	match value {
+		Some(v) if !v.is_undefined() && !v.is_null() => Ok(serde_wasm_bindgen::from_value(v)?),
+		_ => Ok(T::default()),
+	}
+}
+
+/// Validate column-wise edge arrays and zip them into associations.
+///
+/// Errors are plain messages so the checks run without a JS host.
+fn build_associations(
+	num_nodes: u32,
+	sources: &[u32],
+	targets: &[u32],
+	forward_strengths: &[f64],
+	backward_strengths: &[f64],
+	association_types: Option<&[String]>,
+) -> Result<Vec<Association>, String> {
+	let len = sources.len();
+	if targets.len() != len || forward_strengths.len() != len || backward_strengths.len() != len {
+		return Err(
+			"sources, targets, forwardStrengths and backwardStrengths must have equal length"
+				.to_string(),
+		);
+	}
+	if association_types.is_some_and(|t| t.len() != len) {
+		return Err("associationTypes must match sources in length".to_string());
+	}
+
+	// Endpoints and strengths are checked by the builder
+	let mut builder = GraphBuilder::new().with_duplicate_policy(DuplicatePolicy::Last);
+	let _ = builder.add_nodes(num_nodes as usize);
+	for i in 0..len {
+		let association_type = match association_types {
+			Some(types) => types[i]
+				.parse()
+				.map_err(|_| format!("unknown association type \"{}\"", types[i]))?,
+			None => AssociationType::default(),
+		};
+		let _ = builder.add_association(Association {
+			source: sources[i] as usize,
+			target: targets[i] as usize,
+			forward_strength: forward_strengths[i],
+			backward_strength: backward_strengths[i],
+			association_type,
+		});
+	}
+	builder.into_associations().map_err(|e| e.to_string())
+}
+
+fn to_indices(values: &[u32]) -> Vec<usize> {
+	values.iter().map(|&v| v as usize).collect()
+}
+
+/// Seed indices as `usize`, rejecting any past the end of the graph.
+fn checked_seeds(seeds: &[u32], num_nodes: usize) -> Result<Vec<usize>, String> {
+	seeds
+		.iter()
+		.map(|&seed| match seed as usize {
+			seed if seed < num_nodes => Ok(seed),
+			seed => Err(format!(
+				"seed {seed} is out of range: the graph has {num_nodes} nodes"
+			)),
+		})
+		.collect()
+}
+
+fn to_u32(values: &[usize]) -> Vec<u32> {
+	values.iter().map(|&v| v as u32).collect()
+}
+
+// ============================================================================
+// Association Graph
+// ============================================================================
+
+/// Association graph indexed once and queried many times.
+///
+/// Edges are given column-wise: the `i`-th entry of each array describes
+/// one association.
+#[wasm_bindgen(js_name = AssociationGraph)]
+pub struct WasmGraph {
+	graph: CoreGraph,
+}
+
+#[wasm_bindgen(js_class = AssociationGraph)]
+impl WasmGraph {
+	/// Build the graph index.
+	///
+	/// `associationTypes` entries are "semantic" (default), "temporal",
+	/// "causal", "entity", or `"visual_similarity"`. Strengths must be
+	/// finite and within `0..=1`; for a repeated `(source, target)` pair
+	/// the last one wins.
+	///
+	/// # Errors
+	///
+	/// Throws if the arrays differ in length, an endpoint or strength is
+	/// out of range, or a type is unknown.
+	#[wasm_bindgen(constructor)]
+	pub fn new(
+		num_nodes: u32,
+		sources: &[u32],
+		targets: &[u32],
+		forward_strengths: &[f64],
+		backward_strengths: &[f64],
+		association_types: Option<Vec<String>>,
+	) -> Result<Self, JsError> {
+		let associations = build_associations(
+			num_nodes,
+			sources,
+			targets,
+			forward_strengths,
+			backward_strengths,
+			association_types.as_deref(),
+		)
+		.map_err(|message| JsError::new(&message))?;
+
+		Ok(Self {
+			graph: CoreGraph::new(num_nodes as usize, &associations),
+		})
+	}
+
+	/// Number of nodes in the graph.
+	#[wasm_bindgen(getter, js_name = numNodes)]
+	#[allow(clippy::missing_const_for_fn)] // wasm_bindgen exports can't be const
+	pub fn num_nodes(&self) -> u32 {
+		self.graph.num_nodes() as u32
+	}
+
+	/// Number of associations in the graph.
+	#[wasm_bindgen(getter, js_name = numEdges)]
+	pub fn num_edges(&self) -> u32 {
+		self.graph.num_edges() as u32
+	}
+
+	/// Spread activation from seed nodes.
+	///
+	/// Returns one activation per node as a `Float64Array`.
+	///
+	/// # Errors
+	///
+	/// Throws if a seed is out of range or `options` is not a valid
+	/// spreading configuration.
+	pub fn spread(
+		&self,
+		seed_indices: &[u32],
+		seed_activations: &[f64],
+		depth: u32,
+		options: Option<JsSpreadingOptions>,
+	) -> Result<Vec<f64>, JsError> {
+		let seeds = checked_seeds(seed_indices, self.graph.num_nodes())
+			.map_err(|message| JsError::new(&message))?;
+		let config = parse_options::<SpreadingOptions>(options.map(JsValue::from))?.into_core();
+		let result = spread_activation_graph(
+			&self.graph,
+			&seeds,
+			seed_activations,
+			&config,
+			depth as usize,
+		);
+		Ok(result.activations)
+	}
+
+	/// Shortest path from `source` to `target` as a `Uint32Array` of
+	/// node indices, or `undefined` if unreachable.
+	#[wasm_bindgen(js_name = findPath)]
+	pub fn find_path(&self, source: u32, target: u32) -> Option<Vec<u32>> {
+		find_activation_path_graph(&self.graph, source as usize, target as usize)
+			.map(|path| to_u32(&path.nodes))
+	}
+
+	/// `PageRank` with rank split evenly over out-links.
+	pub fn pagerank(&self, damping: f64, iterations: u32) -> Vec<f64> {
+		compute_pagerank_graph(&self.graph, damping, iterations as usize)
+	}
+
+	/// `PageRank` with rank split in proportion to forward strengths.
+	#[wasm_bindgen(js_name = weightedPagerank)]
+	pub fn weighted_pagerank(&self, damping: f64, iterations: u32) -> Vec<f64> {
+		compute_weighted_pagerank_graph(&self.graph, damping, iterations as usize)
+	}
+}
+
+// ============================================================================
+// Temporal Spreading
+// ============================================================================
+
+/// Temporal links between the events of one episode.
+#[wasm_bindgen]
+pub struct TemporalLinks {
+	links: Vec<TemporalLink>,
+}
+
+#[wasm_bindgen]
+impl TemporalLinks {
+	/// Number of links.
+	#[wasm_bindgen(getter)]
+	pub fn length(&self) -> u32 {
+		self.links.len() as u32
+	}
+
+	/// Source memory of each link.
+	#[wasm_bindgen(getter, js_name = sourceMemories)]
+	pub fn source_memories(&self) -> Vec<u32> {
+		self.links.iter().map(|l| l.source_memory as u32).collect()
+	}
+
+	/// Target memory of each link.
+	#[wasm_bindgen(getter, js_name = targetMemories)]
+	pub fn target_memories(&self) -> Vec<u32> {
+		self.links.iter().map(|l| l.target_memory as u32).collect()
+	}
+
+	/// Forward strength of each link.
+	#[wasm_bindgen(getter, js_name = forwardStrengths)]
+	pub fn forward_strengths(&self) -> Vec<f64> {
+		self.links.iter().map(|l| l.forward_strength).collect()
+	}
+
+	/// Backward strength of each link.
+	#[wasm_bindgen(getter, js_name = backwardStrengths)]
+	pub fn backward_strengths(&self) -> Vec<f64> {
+		self.links.iter().map(|l| l.backward_strength).collect()
+	}
+}
+
+/// Result of temporal spreading activation.
+#[wasm_bindgen]
+pub struct TemporalSpreadingResult {
+	activations: Vec<f64>,
+	forward_activated: Vec<usize>,
+	backward_activated: Vec<usize>,
+}
+
+#[wasm_bindgen]
+impl TemporalSpreadingResult {
+	/// Activation of each memory.
+	#[wasm_bindgen(getter)]
+	pub fn activations(&self) -> Vec<f64> {
+		self.activations.clone()
+	}
+
+	/// Memories activated via forward links.
+	#[wasm_bindgen(getter, js_name = forwardActivated)]
+	pub fn forward_activated(&self) -> Vec<u32> {
+		to_u32(&self.forward_activated)
+	}
+
+	/// Memories activated via backward links.
+	#[wasm_bindgen(getter, js_name = backwardActivated)]
+	pub fn backward_activated(&self) -> Vec<u32> {
+		to_u32(&self.backward_activated)
+	}
+}
+
+/// Create temporal links between the events of an episode, in order.
+///
+/// # Errors
+///
+/// Throws if `options` is not a valid temporal spreading configuration.
+#[wasm_bindgen(js_name = createEpisodeLinks)]
+pub fn create_episode_links(
+	event_memory_indices: &[u32],
+	options: Option<JsTemporalSpreadingOptions>,
+) -> Result<TemporalLinks, JsError> {
+	let config = parse_options::<TemporalSpreadingOptions>(options.map(JsValue::from))?.into_core();
+	let links = core_create_episode_links(&to_indices(event_memory_indices), &config);
+	Ok(TemporalLinks { links })
+}
+
+/// Spread activation along an episode's temporal links from one seed memory.
+///
+/// # Errors
+///
+/// Throws if `options` is not a valid temporal spreading configuration.
+#[wasm_bindgen(js_name = spreadTemporalActivation)]
+pub fn spread_temporal_activation(
+	num_memories: u32,
+	links: &TemporalLinks,
+	seed_memory: u32,
+	seed_activation: f64,
+	options: Option<JsTemporalSpreadingOptions>,
+) -> Result<TemporalSpreadingResult, JsError> {
+	let config = parse_options::<TemporalSpreadingOptions>(options.map(JsValue::from))?.into_core();
+	let result = core_spread_temporal(
+		num_memories as usize,
+		&links.links,
+		seed_memory as usize,
+		seed_activation,
+		&config,
+	);
+	Ok(TemporalSpreadingResult {
+		activations: result.activations,
+		forward_activated: result.forward_activated,
+		backward_activated: result.backward_activated,
+	})
+}
+
+// ============================================================================
+// Utility Functions
+// ============================================================================
+
+/// Library version
+#[wasm_bindgen]
+pub fn version() -> String {
+	lucid_core::VERSION.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_build_associations() {
+		let types = ["causal".to_string(), "Visual_Similarity".to_string()];
+		let associations =
+			build_associations(3, &[0, 1], &[1, 2], &[0.5, 0.8], &[0.2, 0.4], Some(&types))
+				.unwrap_or_default();
+		assert_eq!(associations.len(), 2);
+		assert_eq!(associations[0].association_type, AssociationType::Causal);
+		assert_eq!(
+			associations[1].association_type,
+			AssociationType::VisualSimilarity
+		);
+	}
+
+	#[test]
+	fn test_build_associations_length_mismatch() {
+		let result = build_associations(3, &[0, 1], &[1], &[0.5, 0.8], &[0.2, 0.4], None);
+		assert!(result.is_err_and(|e| e.contains("equal length")));
+
+		let types = ["causal".to_string()];
+		let result =
+			build_associations(3, &[0, 1], &[1, 2], &[0.5, 0.8], &[0.2, 0.4], Some(&types));
+		assert!(result.is_err_and(|e| e.contains("associationTypes")));
+	}
+
+	#[test]
+	fn test_build_associations_out_of_range() {
+		let result = build_associations(3, &[0], &[3], &[0.5], &[0.2], None);
+		assert!(result.is_err_and(|e| e.contains("target 3") && e.contains("3 nodes")));
+	}
+
+	#[test]
+	fn test_build_associations_bad_strength() {
+		let result = build_associations(3, &[0], &[1], &[f64::NAN], &[0.2], None);
+		assert!(result.is_err_and(|e| e.contains("forward_strength")));
+		let result = build_associations(3, &[0], &[1], &[0.5], &[-0.1], None);
+		assert!(result.is_err_and(|e| e.contains("backward_strength")));
+	}
+
+	#[test]
+	fn test_build_associations_unknown_type() {
+		let types = ["friendship".to_string()];
+		let result = build_associations(3, &[0], &[1], &[0.5], &[0.2], Some(&types));
+		assert!(result.is_err_and(|e| e.contains("\"friendship\"")));
+	}
+
+	#[test]
+	fn test_checked_seeds_out_of_range() {
+		assert_eq!(checked_seeds(&[0, 2], 3), Ok(vec![0, 2]));
+		let result = checked_seeds(&[0, 3], 3);
+		assert!(result.is_err_and(|e| e.contains("seed 3") && e.contains("3 nodes")));
+	}
+}
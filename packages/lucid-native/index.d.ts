@@ -1,5 +1,80 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
+/**
+ * Editable association graph for spreading and centrality queries.
+ *
+ * Results come back as `Float64Array`s that take ownership of the Rust
+ * buffer, so no per-node conversion or JSON round-trip is involved. The
+ * query index is rebuilt lazily after the graph is edited.
+ */
+export declare class MemoryGraph {
+	/** Create an empty graph with `num_nodes` nodes. */
+	constructor(numNodes: number)
+	/**
+	 * Create a graph from existing associations; for a repeated
+	 * `(source, target)` pair the last one wins.
+	 *
+	 * # Errors
+	 *
+	 * Throws if an endpoint is not below `num_nodes`.
+	 */
+	static fromAssociations(numNodes: number, associations: Array<JsAssociation>): MemoryGraph
+	/** Number of nodes in the graph. */
+	get numNodes(): number
+	/** Number of associations in the graph. */
+	get numEdges(): number
+	/** Append a node and return its index. */
+	addNode(): number
+	/**
+	 * Insert an association, replacing any edge with the same endpoints.
+	 *
+	 * Returns true if an existing association was replaced.
+	 *
+	 * # Errors
+	 *
+	 * Throws if an endpoint is not an existing node; call `addNode` first.
+	 */
+	insertAssociation(association: JsAssociation): boolean
+	/**
+	 * Remove the association from `source` to `target`.
+	 *
+	 * Returns true if it existed.
+	 */
+	removeAssociation(source: number, target: number): boolean
+	/**
+	 * Spread activation from seed nodes; returns one activation per node.
+	 *
+	 * # Errors
+	 *
+	 * Throws if a seed is not an existing node.
+	 */
+	spread(
+		seedIndices: Uint32Array,
+		seedActivations: Float64Array,
+		depth: number,
+		config?: JsSpreadingConfig | undefined | null
+	): Float64Array
+	/**
+	 * `PageRank` of every node.
+	 *
+	 * With `weighted`, rank is split in proportion to forward strength
+	 * rather than evenly over out-links.
+	 */
+	pagerank(damping: number, iterations: number, weighted?: boolean | undefined | null): Float64Array
+	/** Personalized `PageRank` around weighted seed nodes. */
+	personalizedPagerank(
+		seedIndices: Uint32Array,
+		seedWeights: Float64Array,
+		damping?: number | undefined | null
+	): Float64Array
+	/** Betweenness centrality of every node. */
+	betweenness(config?: JsCentralityConfig | undefined | null): Float64Array
+	/** Closeness centrality of every node. */
+	closeness(config?: JsCentralityConfig | undefined | null): Float64Array
+	/** Eigenvector centrality of every node, weighted by forward strength. */
+	eigenvector(): Float64Array
+}
+
 /**
  * Compute decayed association strength.
  *
@@ -147,7 +222,7 @@ export declare function embedBatch(
 /**
  * Find temporally adjacent memories.
  *
- * direction: "before", "after", or "both"
+ * direction: "before", "after", or "both" (throws on anything else)
  */
 export declare function findTemporalNeighbors(
 	temporalLinks: Array<JsTemporalLink>,
@@ -189,6 +264,11 @@ export interface JsAssociation {
 	target: number
 	forwardStrength: number
 	backwardStrength: number
+	/**
+	 * "semantic" (default), "temporal", "causal", "entity", or
+	 * "visual_similarity"; any other name is an error
+	 */
+	associationType?: string
 }
 
 /** Configuration for association decay. */
@@ -207,6 +287,14 @@ export interface JsAssociationDecayConfig {
 	pruneThreshold?: number
 }
 
+/** Configuration for betweenness and closeness centrality. */
+export interface JsCentralityConfig {
+	/** Measure path length by edge strength instead of hop count (default: false) */
+	weighted?: boolean
+	/** Scale betweenness into 0-1 (default: true) */
+	normalized?: boolean
+}
+
 /** Embedding result returned to JavaScript. */
 export interface JsEmbeddingResult {
 	/** The embedding vector (768 dimensions). */
@@ -391,6 +479,26 @@ export interface JsRetrievalConfig {
 	bidirectional?: boolean
 }
 
+/** Configuration for spreading activation over a `MemoryGraph`. */
+export interface JsSpreadingConfig {
+	/** How much activation decays per hop (default: 0.7) */
+	decayPerHop?: number
+	/** Minimum activation to propagate (default: 0.01) */
+	minimumActivation?: number
+	/** Maximum nodes to visit (default: 1000) */
+	maxNodes?: number
+	/** Whether to spread bidirectionally (default: true) */
+	bidirectional?: boolean
+	/** Spread until a level adds less than this; `depth` is then ignored */
+	convergenceEpsilon?: number
+	/** Level cap when spreading to convergence (default: 100) */
+	maxIterations?: number
+	/** Logistic activation noise scale; 0 disables noise (default: 0) */
+	activationNoise?: number
+	/** Seed for activation noise (default: 0) */
+	noiseSeed?: number
+}
+
 /** A temporal link between memories. */
 export interface JsTemporalLink {
 	sourcePosition: number
@@ -405,6 +513,10 @@ export interface JsTemporalLink {
 export interface JsTemporalNeighbor {
 	memoryIndex: number
 	strength: number
+	/** "before" or "after" the anchor */
+	direction: string
+	/** Position distance from the anchor within the episode */
+	distance: number
 }
 
 /** Configuration for temporal spreading. */
@@ -425,7 +537,7 @@ export interface JsTemporalSpreadingConfig {
 
 /** Result of temporal spreading. */
 export interface JsTemporalSpreadingResult {
-	activations: Array<number>
+	activations: Float64Array
 	forwardActivated: Array<number>
 	backwardActivated: Array<number>
 }
@@ -659,6 +771,13 @@ export declare function reinforceAssociation(
 	config?: JsAssociationDecayConfig | undefined | null
 ): number
 
+/**
+ * Estimate retrieval latency in milliseconds.
+ *
+ * latency = F × e^(-A) × 1000
+ */
+export declare function retrievalLatency(activation: number, latencyFactor: number): number
+
 /**
  * Compute retrieval probability using logistic function.
  *
@@ -691,6 +810,10 @@ export declare function retrievalProbability(
  * * `current_time_ms` - Current time in milliseconds
  * * `associations` - Optional association graph edges
  * * `config` - Optional retrieval configuration
+ *
+ * # Errors
+ *
+ * Throws if an association has an unknown `association_type`.
  */
 export declare function retrieve(
 	probeEmbedding: Array<number>,
@@ -762,7 +885,13 @@ export declare function videoSelectFrames(
 	transcriptSegments?: Array<JsTranscriptSegment> | undefined | null
 ): Array<number>
 
-/** Retrieve visual memories based on probe embedding. */
+/**
+ * Retrieve visual memories based on probe embedding.
+ *
+ * # Errors
+ *
+ * Throws if an association has an unknown `association_type`.
+ */
 export declare function visualRetrieve(
 	probeEmbedding: Array<number>,
 	memoryEmbeddings: Array<Array<number>>,
@@ -576,6 +576,7 @@ if (!nativeBinding) {
 }
 
 module.exports = nativeBinding
+module.exports.MemoryGraph = nativeBinding.MemoryGraph
 module.exports.computeAssociationDecay = nativeBinding.computeAssociationDecay
 module.exports.computeBaseLevel = nativeBinding.computeBaseLevel
 module.exports.computeEffectiveThresholds = nativeBinding.computeEffectiveThresholds
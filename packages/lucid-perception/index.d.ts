@@ -10,6 +10,10 @@ export interface JsExtractedFrame {
 	frameNumber: number
 	/** Is keyframe */
 	isKeyframe: boolean
+	/** Encoded image, for frames extracted to memory (path is then empty) */
+	data?: Buffer
+	/** The frame at each configured variant size */
+	variants: Array<JsFrameVariant>
 }
 
 /** Frame with scene detection info. */
@@ -30,6 +34,40 @@ export interface JsFrameCandidate {
 	isDuplicate: boolean
 	/** Distance from previous */
 	distanceFromPrevious: number
+	/** The frame at each configured variant size */
+	variants: Array<JsFrameVariant>
+}
+
+/** A size to write every extracted frame at, besides full size. */
+export interface JsFrameSize {
+	/** Variant name, appended to frame file names */
+	name: string
+	/** Max width in pixels (0 or unset = any) */
+	maxWidth?: number
+	/** Max height in pixels (0 or unset = any) */
+	maxHeight?: number
+}
+
+/** An extracted frame at one variant size. */
+export interface JsFrameVariant {
+	/** Variant name */
+	name: string
+	/** Path to variant image */
+	path: string
+	/** Width in pixels */
+	width: number
+	/** Height in pixels */
+	height: number
+	/** Encoded image, for frames extracted to memory (path is then empty) */
+	data?: Buffer
+}
+
+/** Network config for http(s) URLs and HLS playlists. */
+export interface JsNetworkConfig {
+	/** Seconds a connection may stall (0 = no timeout, default: 30) */
+	timeoutSeconds?: number
+	/** Extra HTTP headers sent with every request */
+	headers?: Record<string, string>
 }
 
 /** Pipeline config. */
@@ -44,6 +82,10 @@ export interface JsPipelineConfig {
 	enableSceneDetection?: boolean
 	/** Skip transcription */
 	skipTranscription?: boolean
+	/** Peak memory sampling interval in ms (0 = disabled) */
+	resourceSampleIntervalMs?: number
+	/** Reproducible output for golden-file tests */
+	deterministic?: boolean
 }
 
 /** Processing statistics. */
@@ -60,6 +102,14 @@ export interface JsProcessingStats {
 	sceneDetectionTimeMs: number
 	/** Transcription time (ms) */
 	transcriptionTimeMs: number
+	/** Peak resident memory during extraction (bytes, if sampled) */
+	extractionPeakRssBytes?: number
+	/** Peak resident memory during scene detection (bytes, if sampled) */
+	sceneDetectionPeakRssBytes?: number
+	/** Peak resident memory during transcription (bytes, if sampled) */
+	transcriptionPeakRssBytes?: number
+	/** Peak GPU memory during transcription (bytes, if sampled) */
+	transcriptionPeakVramBytes?: number
 }
 
 /** Scene detection config. */
@@ -118,10 +168,24 @@ export interface JsVideoConfig {
 	intervalSeconds?: number
 	/** Quality (1-31, lower is better) */
 	quality?: number
-	/** Output format: "jpeg" or "png" */
+	/** Output format: "jpeg", "png", "avif" or "webp" (lossless) */
 	format?: string
+	/** Encoder effort for AVIF and WebP, 1 (fastest) to 10 (smallest files) (default: 7) */
+	effort?: number
 	/** Extract keyframes only */
 	keyframesOnly?: boolean
+	/** Hardware decoder: "auto", "nvdec", "vaapi", "videotoolbox" or "qsv" (default: CPU) */
+	hwaccel?: string
+	/** Timeout and headers for http(s) URLs */
+	network?: JsNetworkConfig
+	/** Seconds into the video to start at (default: beginning) */
+	startTime?: number
+	/** Seconds into the video to stop at (default: end) */
+	endTime?: number
+	/** Extra sizes to write every frame at, e.g. thumbnails */
+	variants?: Array<JsFrameSize>
+	/** Turn frames upright by the video's rotation metadata (default: true) */
+	autoRotate?: boolean
 }
 
 /** Video metadata. */
@@ -140,6 +204,8 @@ export interface JsVideoMetadata {
 	codec: string
 	/** Has audio */
 	hasAudio: boolean
+	/** Clockwise rotation to display frames upright (0, 90, 180 or 270) */
+	rotation: number
 }
 
 /** Video processing output. */
@@ -165,12 +231,26 @@ export declare function videoExtractFrames(
 	config?: JsVideoConfig | undefined | null
 ): Promise<Array<JsExtractedFrame>>
 
+/** Extract the frame at each timestamp in one pass, in the order requested. */
+export declare function videoExtractFramesAt(
+	videoPath: string,
+	timestamps: Array<number>,
+	config?: JsVideoConfig | undefined | null
+): Promise<Array<JsExtractedFrame>>
+
+/** Extract frames from a video into memory, without writing files. */
+export declare function videoExtractFramesToMemory(
+	videoPath: string,
+	config?: JsVideoConfig | undefined | null
+): Promise<Array<JsExtractedFrame>>
+
 /** Get the default model path. */
 export declare function videoGetDefaultModelPath(): string
 
 /** Get video metadata. */
 export declare function videoGetMetadata(
-	videoPath: string
+	videoPath: string,
+	network?: JsNetworkConfig | undefined | null
 ): Promise<JsVideoMetadata>
 
 /** Get the download URL for the default Whisper model. */
@@ -783,6 +783,8 @@ if (!nativeBinding) {
 module.exports = nativeBinding
 module.exports.videoCheckFfmpeg = nativeBinding.videoCheckFfmpeg
 module.exports.videoExtractFrames = nativeBinding.videoExtractFrames
+module.exports.videoExtractFramesAt = nativeBinding.videoExtractFramesAt
+module.exports.videoExtractFramesToMemory = nativeBinding.videoExtractFramesToMemory
 module.exports.videoGetDefaultModelPath = nativeBinding.videoGetDefaultModelPath
 module.exports.videoGetMetadata = nativeBinding.videoGetMetadata
 module.exports.videoGetModelUrl = nativeBinding.videoGetModelUrl
@@ -390,7 +390,7 @@ export class LucidRetrieval {
 					maxTemporalDistance: EpisodicMemoryConfig.maxTemporalDistance,
 				}
 
-				let activations: number[]
+				let activations: ArrayLike<number>
 				if (shouldUseNative && nativeModule) {
 					const result = nativeModule.spreadTemporalActivation(
 						memoriesWithEmbeddings.length,